@@ -19,6 +19,8 @@ mod rbac;
 
 use reqwest::blocking::Client;
 use serde::Deserialize;
+#[cfg(feature = "peer")]
+use serde::Serialize;
 
 use super::CliError;
 
@@ -231,6 +233,167 @@ impl SplinterRestClient {
             })
     }
 
+    /// Lists a page of the Splinter node's REST API audit log.
+    #[cfg(feature = "audit-log")]
+    pub fn list_audit_entries(
+        &self,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<AuditLogEntry>, CliError> {
+        Client::new()
+            .get(&format!("{}/audit", self.url))
+            .query(&[("offset", offset), ("limit", limit)])
+            .header("Authorization", &self.auth)
+            .send()
+            .map_err(|err| {
+                CliError::ActionError(format!("Failed to get audit log entries: {}", err))
+            })
+            .and_then(|res| {
+                let status = res.status();
+                if status.is_success() {
+                    res.json::<AuditLogResponse>()
+                        .map(|response| response.data)
+                        .map_err(|_| {
+                            CliError::ActionError(
+                                "Request was successful, but received an invalid response".into(),
+                            )
+                        })
+                } else {
+                    let message = res
+                        .json::<ServerError>()
+                        .map_err(|_| {
+                            CliError::ActionError(format!(
+                                "Audit log list request failed with status code '{}', but \
+                                 error response was not valid",
+                                status
+                            ))
+                        })?
+                        .message;
+
+                    Err(CliError::ActionError(format!(
+                        "Failed to get audit log entries: {}",
+                        message
+                    )))
+                }
+            })
+    }
+
+    /// Lists the Splinter node's peers.
+    #[cfg(feature = "peer")]
+    pub fn list_peers(&self) -> Result<Vec<Peer>, CliError> {
+        Client::new()
+            .get(&format!("{}/peers", self.url))
+            .header("Authorization", &self.auth)
+            .send()
+            .map_err(|err| CliError::ActionError(format!("Failed to get peers: {}", err)))
+            .and_then(|res| {
+                let status = res.status();
+                if status.is_success() {
+                    res.json::<PeersResponse>()
+                        .map(|response| response.data)
+                        .map_err(|_| {
+                            CliError::ActionError(
+                                "Request was successful, but received an invalid response".into(),
+                            )
+                        })
+                } else {
+                    let message = res
+                        .json::<ServerError>()
+                        .map_err(|_| {
+                            CliError::ActionError(format!(
+                                "Peers list request failed with status code '{}', but error \
+                                 response was not valid",
+                                status
+                            ))
+                        })?
+                        .message;
+
+                    Err(CliError::ActionError(format!(
+                        "Failed to get peers: {}",
+                        message
+                    )))
+                }
+            })
+    }
+
+    /// Fetches one of the Splinter node's peers, or `None` if the node does not know the peer.
+    #[cfg(feature = "peer")]
+    pub fn get_peer(&self, peer_id: &str) -> Result<Option<Peer>, CliError> {
+        Client::new()
+            .get(&format!("{}/peers/{}", self.url, peer_id))
+            .header("Authorization", &self.auth)
+            .send()
+            .map_err(|err| {
+                CliError::ActionError(format!("Failed to fetch peer {}: {}", peer_id, err))
+            })
+            .and_then(|res| {
+                let status = res.status();
+                if status.is_success() {
+                    res.json::<Peer>().map(Some).map_err(|_| {
+                        CliError::ActionError(
+                            "Request was successful, but received an invalid response".into(),
+                        )
+                    })
+                } else if status.as_u16() == 404 {
+                    Ok(None)
+                } else {
+                    let message = res
+                        .json::<ServerError>()
+                        .map_err(|_| {
+                            CliError::ActionError(format!(
+                                "Peer fetch request failed with status code '{}', but error \
+                                 response was not valid",
+                                status
+                            ))
+                        })?
+                        .message;
+
+                    Err(CliError::ActionError(format!(
+                        "Failed to fetch peer {}: {}",
+                        peer_id, message
+                    )))
+                }
+            })
+    }
+
+    /// Lists the retry state of the Splinter node's peers.
+    #[cfg(feature = "peer")]
+    pub fn list_peer_retries(&self) -> Result<Vec<PeerRetry>, CliError> {
+        Client::new()
+            .get(&format!("{}/peers/retries", self.url))
+            .header("Authorization", &self.auth)
+            .send()
+            .map_err(|err| CliError::ActionError(format!("Failed to get peer retries: {}", err)))
+            .and_then(|res| {
+                let status = res.status();
+                if status.is_success() {
+                    res.json::<PeerRetriesResponse>()
+                        .map(|response| response.data)
+                        .map_err(|_| {
+                            CliError::ActionError(
+                                "Request was successful, but received an invalid response".into(),
+                            )
+                        })
+                } else {
+                    let message = res
+                        .json::<ServerError>()
+                        .map_err(|_| {
+                            CliError::ActionError(format!(
+                                "Peer retries list request failed with status code '{}', but \
+                                 error response was not valid",
+                                status
+                            ))
+                        })?
+                        .message;
+
+                    Err(CliError::ActionError(format!(
+                        "Failed to get peer retries: {}",
+                        message
+                    )))
+                }
+            })
+    }
+
     #[cfg(feature = "authorization-handler-rbac")]
     pub fn list_roles(&self) -> Result<rbac::PagingIter<Role>, CliError> {
         Ok(rbac::PagingIter::new(
@@ -315,3 +478,73 @@ pub struct Permission {
     pub permission_display_name: String,
     pub permission_description: String,
 }
+
+#[cfg(feature = "audit-log")]
+#[derive(Deserialize)]
+struct AuditLogResponse {
+    pub data: Vec<AuditLogEntry>,
+}
+
+#[cfg(feature = "audit-log")]
+#[derive(Deserialize)]
+pub struct AuditLogEntry {
+    pub timestamp: u64,
+    pub identity: Option<String>,
+    pub permission: Option<String>,
+    pub method: String,
+    pub route: String,
+    pub outcome: String,
+    pub circuit_id: Option<String>,
+    pub action: Option<String>,
+}
+
+#[cfg(feature = "peer")]
+#[derive(Deserialize)]
+struct PeersResponse {
+    pub data: Vec<Peer>,
+}
+
+#[cfg(feature = "peer")]
+#[derive(Deserialize, Serialize)]
+pub struct Peer {
+    pub peer_id: String,
+    pub local_id: String,
+    pub connection_id: String,
+    pub endpoints: Vec<String>,
+    pub active_endpoint: String,
+    pub status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reconnection_attempts: Option<u64>,
+    pub ref_count: u64,
+    pub connected_since: Option<u64>,
+    pub connection_latency_millis: Option<u64>,
+    pub last_heartbeat: Option<u64>,
+    #[serde(default)]
+    pub last_heartbeat_latency_millis: Option<u64>,
+}
+
+#[cfg(feature = "peer")]
+#[derive(Deserialize)]
+struct PeerRetriesResponse {
+    pub data: Vec<PeerRetry>,
+}
+
+#[cfg(feature = "peer")]
+#[derive(Deserialize)]
+pub struct PeerRetry {
+    pub peer_id: Option<String>,
+    pub endpoints: Vec<String>,
+    pub status: String,
+    pub attempts: u64,
+    pub next_attempt: Option<u64>,
+    pub policy: Option<PeerRetryPolicy>,
+}
+
+#[cfg(feature = "peer")]
+#[derive(Deserialize)]
+pub struct PeerRetryPolicy {
+    pub initial_interval_millis: u64,
+    pub max_interval_millis: u64,
+    pub multiplier: u32,
+    pub max_attempts: Option<u64>,
+}
@@ -44,6 +44,8 @@ pub struct Assignment {
     #[serde(flatten)]
     pub identity: Identity,
     pub roles: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scopes: Vec<String>,
 }
 
 impl Pageable for Assignment {
@@ -62,6 +64,7 @@ struct AssignmentGet {
 pub struct AssignmentBuilder {
     identity: Option<Identity>,
     roles: Vec<String>,
+    scopes: Vec<String>,
 }
 
 impl AssignmentBuilder {
@@ -75,8 +78,17 @@ impl AssignmentBuilder {
         self
     }
 
+    pub fn with_scopes(mut self, scopes: Vec<String>) -> Self {
+        self.scopes = scopes;
+        self
+    }
+
     pub fn build(self) -> Result<Assignment, CliError> {
-        let AssignmentBuilder { identity, roles } = self;
+        let AssignmentBuilder {
+            identity,
+            roles,
+            scopes,
+        } = self;
 
         if roles.is_empty() {
             return Err(CliError::ActionError(
@@ -100,7 +112,11 @@ impl AssignmentBuilder {
             }
         }
 
-        Ok(Assignment { identity, roles })
+        Ok(Assignment {
+            identity,
+            roles,
+            scopes,
+        })
     }
 }
 
@@ -110,12 +126,15 @@ pub struct AssignmentUpdate {
     identity: Identity,
     #[serde(skip_serializing_if = "Option::is_none")]
     roles: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scopes: Option<Vec<String>>,
 }
 
 #[derive(Default)]
 pub struct AssignmentUpdateBuilder {
     identity: Option<Identity>,
     roles: Option<Vec<String>>,
+    scopes: Option<Vec<String>>,
 }
 
 impl AssignmentUpdateBuilder {
@@ -129,8 +148,17 @@ impl AssignmentUpdateBuilder {
         self
     }
 
+    pub fn with_scopes(mut self, scopes: Option<Vec<String>>) -> Self {
+        self.scopes = scopes;
+        self
+    }
+
     pub fn build(self) -> Result<AssignmentUpdate, CliError> {
-        let AssignmentUpdateBuilder { identity, roles } = self;
+        let AssignmentUpdateBuilder {
+            identity,
+            roles,
+            scopes,
+        } = self;
 
         let identity = identity.ok_or_else(|| {
             CliError::ActionError("An assignment must have an associated identity".into())
@@ -144,7 +172,11 @@ impl AssignmentUpdateBuilder {
             }
         }
 
-        Ok(AssignmentUpdate { identity, roles })
+        Ok(AssignmentUpdate {
+            identity,
+            roles,
+            scopes,
+        })
     }
 }
 
@@ -0,0 +1,126 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Actions for handling audit log subcommands.
+
+use chrono::{TimeZone, Utc};
+use clap::ArgMatches;
+
+use crate::error::CliError;
+use crate::signing::{create_cylinder_jwt_auth, load_signer};
+
+use super::{
+    api::SplinterRestClientBuilder, print_table, Action, DEFAULT_SPLINTER_REST_API_URL,
+    SPLINTER_REST_API_URL_ENV,
+};
+
+const DEFAULT_LIMIT: usize = 100;
+
+/// The action responsible for listing REST API audit log entries.
+///
+/// The specific args for this action:
+///
+/// * url: specifies the URL of the splinter node to be queried; falls back to the environment
+///   variable SPLINTER_REST_API_URL
+/// * format: specifies the output format; one of "human", "json" or "csv"
+/// * offset: the number of entries to skip
+/// * limit: the maximum number of entries to display
+pub struct ListAction;
+
+impl Action for ListAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let format = arg_matches
+            .and_then(|args| args.value_of("format"))
+            .unwrap_or("human");
+        let url = arg_matches
+            .and_then(|args| args.value_of("url"))
+            .map(ToOwned::to_owned)
+            .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+            .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+        let offset = arg_matches
+            .and_then(|args| args.value_of("offset"))
+            .map(|offset| {
+                offset.parse::<usize>().map_err(|_| {
+                    CliError::ActionError(format!("'{}' is not a valid offset", offset))
+                })
+            })
+            .transpose()?
+            .unwrap_or(0);
+        let limit = arg_matches
+            .and_then(|args| args.value_of("limit"))
+            .map(|limit| {
+                limit
+                    .parse::<usize>()
+                    .map_err(|_| CliError::ActionError(format!("'{}' is not a valid limit", limit)))
+            })
+            .transpose()?
+            .unwrap_or(DEFAULT_LIMIT);
+
+        let signer = load_signer(arg_matches.and_then(|args| args.value_of("private_key_file")))?;
+
+        let entries = SplinterRestClientBuilder::new()
+            .with_url(url)
+            .with_auth(create_cylinder_jwt_auth(signer)?)
+            .build()?
+            .list_audit_entries(offset, limit)?;
+
+        let data = std::iter::once(vec![
+            "TIME".to_string(),
+            "IDENTITY".to_string(),
+            "PERMISSION".to_string(),
+            "METHOD".to_string(),
+            "ROUTE".to_string(),
+            "OUTCOME".to_string(),
+            "CIRCUIT".to_string(),
+            "ACTION".to_string(),
+        ])
+        .chain(entries.into_iter().map(|entry| {
+            vec![
+                format_timestamp(entry.timestamp),
+                entry.identity.unwrap_or_else(|| "-".into()),
+                entry.permission.unwrap_or_else(|| "-".into()),
+                entry.method,
+                entry.route,
+                entry.outcome,
+                entry.circuit_id.unwrap_or_else(|| "-".into()),
+                entry.action.unwrap_or_else(|| "-".into()),
+            ]
+        }));
+
+        match format {
+            "csv" => {
+                for row in data {
+                    println!("{}", row.join(","))
+                }
+            }
+            "json" => println!(
+                "\n {}",
+                serde_json::to_string_pretty(&data.collect::<Vec<_>>()).map_err(|err| {
+                    CliError::ActionError(format!("Cannot format audit log into json: {}", err))
+                })?
+            ),
+            _ => print_table(data.collect()),
+        }
+
+        Ok(())
+    }
+}
+
+/// Formats a time, given in seconds since the Unix epoch, as an RFC 3339 UTC timestamp.
+fn format_timestamp(secs: u64) -> String {
+    Utc.timestamp_opt(secs as i64, 0)
+        .single()
+        .map(|time| time.to_rfc3339())
+        .unwrap_or_else(|| secs.to_string())
+}
@@ -16,6 +16,7 @@ use std::collections::BTreeMap;
 use std::fmt;
 use std::fmt::Write as _;
 
+use chrono::{TimeZone, Utc};
 use reqwest::{blocking::Client, header, StatusCode};
 use serde::{Deserialize, Serialize};
 use serde_json::error::Result as JsonResult;
@@ -344,14 +345,20 @@ pub struct ProposalSlice {
     pub votes: Vec<VoteRecord>,
     pub requester: String,
     pub requester_node_id: String,
+    #[serde(default)]
+    pub expires_at: Option<u64>,
 }
 
 impl fmt::Display for ProposalSlice {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let mut display_string = if self.proposal_type == "Disband" {
-            format!("Proposal to disband: {}\n    ", self.circuit_id)
-        } else {
-            format!("Proposal to create: {}\n    ", self.circuit_id)
+        let mut display_string = match self.proposal_type.as_str() {
+            "Disband" => format!("Proposal to disband: {}\n    ", self.circuit_id),
+            "Suspend" => format!("Proposal to suspend: {}\n    ", self.circuit_id),
+            "Resume" => format!("Proposal to resume: {}\n    ", self.circuit_id),
+            "UpdateNodeKeys" => {
+                format!("Proposal to update node keys: {}\n    ", self.circuit_id)
+            }
+            _ => format!("Proposal to create: {}\n    ", self.circuit_id),
         };
 
         if let Some(display_name) = &self.circuit.display_name {
@@ -372,6 +379,14 @@ impl fmt::Display for ProposalSlice {
             self.circuit.circuit_version, self.circuit.management_type
         )?;
 
+        if let Some(expires_at) = self.expires_at {
+            writeln!(
+                display_string,
+                "    Expires: {}",
+                format_timestamp(expires_at)
+            )?;
+        }
+
         for member in self.circuit.members.iter() {
             write!(display_string, "\n    {}\n", member.node_id)?;
             if let Some(public_key) = &member.public_key {
@@ -433,6 +448,14 @@ impl fmt::Display for ProposalSlice {
     }
 }
 
+/// Formats a time, given in seconds since the Unix epoch, as an RFC 3339 UTC timestamp.
+pub fn format_timestamp(secs: u64) -> String {
+    Utc.timestamp_opt(secs as i64, 0)
+        .single()
+        .map(|time| time.to_rfc3339())
+        .unwrap_or_else(|| secs.to_string())
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct ProposalCircuitSlice {
     pub circuit_id: String,
@@ -860,6 +883,7 @@ mod tests {
             votes: vec![],
             requester: "03f91f722329b99234be43f962e7ce33bbd4f2e72634a1a68f12ad908ca5693f03".into(),
             requester_node_id: "n20959".into(),
+            expires_at: None,
         };
 
         assert_eq!(format!("{}", proposal), PROPOSAL_STRING);
@@ -936,6 +960,7 @@ mod tests {
             votes: vec![],
             requester: "03f91f722329b99234be43f962e7ce33bbd4f2e72634a1a68f12ad908ca5693f03".into(),
             requester_node_id: "n20959".into(),
+            expires_at: None,
         };
 
         assert_eq!(format!("{}", proposal), PROPOSAL_NONE_STRING);
@@ -1021,6 +1046,7 @@ mod tests {
             }],
             requester: "03f91f722329b99234be43f962e7ce33bbd4f2e72634a1a68f12ad908ca5693f03".into(),
             requester_node_id: "n20959".into(),
+            expires_at: None,
         };
 
         assert_eq!(format!("{}", proposal), PROPOSAL_VOTE_STRING);
@@ -17,7 +17,7 @@ use std::fmt::Write as _;
 use splinter::admin::messages::AuthorizationType;
 use splinter::admin::messages::{
     BuilderError, CircuitStatus, CreateCircuit, CreateCircuitBuilder, SplinterNode,
-    SplinterNodeBuilder, SplinterServiceBuilder,
+    SplinterNodeBuilder, SplinterServiceBuilder, VotingPolicy,
 };
 
 use crate::error::CliError;
@@ -37,6 +37,7 @@ pub struct CreateCircuitMessageBuilder {
     display_name: Option<String>,
     circuit_version: Option<i32>,
     circuit_status: Option<CircuitStatus>,
+    voting_policy: Option<VotingPolicy>,
 }
 
 impl CreateCircuitMessageBuilder {
@@ -52,6 +53,7 @@ impl CreateCircuitMessageBuilder {
             display_name: None,
             circuit_version: None,
             circuit_status: None,
+            voting_policy: None,
         }
     }
 
@@ -255,6 +257,10 @@ impl CreateCircuitMessageBuilder {
         self.circuit_status = Some(circuit_status);
     }
 
+    pub fn set_voting_policy(&mut self, voting_policy: VotingPolicy) {
+        self.voting_policy = Some(voting_policy);
+    }
+
     pub fn build(mut self) -> Result<CreateCircuit, CliError> {
         let circuit_builder = self.create_circuit_builder();
 
@@ -331,6 +337,10 @@ impl CreateCircuitMessageBuilder {
             create_circuit_builder = create_circuit_builder.with_circuit_status(&circuit_status);
         }
 
+        if let Some(voting_policy) = self.voting_policy {
+            create_circuit_builder = create_circuit_builder.with_voting_policy(&voting_policy);
+        }
+
         let create_circuit_builder = match self.authorization_type {
             Some(authorization_type) => {
                 create_circuit_builder.with_authorization_type(&authorization_type)
@@ -18,16 +18,19 @@ mod payload;
 #[cfg(feature = "circuit-template")]
 pub mod template;
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::convert::TryFrom;
 use std::fmt::Write;
 use std::fs::File;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use clap::ArgMatches;
 use cylinder::Signer;
 use serde::Deserialize;
 use splinter::admin::{
-    messages::{CircuitStatus, CreateCircuit, SplinterNode, SplinterService},
+    messages::{
+        CircuitStatus, CreateCircuit, SplinterNode, SplinterService, VotingPolicy, VotingPolicyType,
+    },
     CIRCUIT_PROTOCOL_VERSION,
 };
 
@@ -43,7 +46,7 @@ use super::{
     SPLINTER_REST_API_URL_ENV,
 };
 
-use api::{CircuitMembers, CircuitServiceSlice, CircuitSlice};
+use api::{format_timestamp, CircuitMembers, CircuitServiceSlice, CircuitSlice};
 pub(crate) use builder::CreateCircuitMessageBuilder;
 use payload::make_signed_payload;
 
@@ -197,11 +200,25 @@ impl Action for CircuitProposeAction {
             builder.set_display_name(display_name);
         }
 
+        if args.is_present("voting_policy") || args.is_present("vote_weight") {
+            if args.value_of("compat_version") == Some("0.4") {
+                return Err(CliError::ActionError(
+                    "Voting policies are not compatible with Splinter v0.4".to_string(),
+                ));
+            }
+            builder.set_voting_policy(parse_voting_policy(args)?);
+        }
+
         if args.value_of("compat_version") != Some("0.4") {
             builder.set_circuit_version(CIRCUIT_PROTOCOL_VERSION);
             builder.set_circuit_status(CircuitStatus::Active);
         }
 
+        let expires_at = args
+            .value_of("expires_in")
+            .map(parse_expires_in)
+            .transpose()?;
+
         let create_circuit = builder.build()?;
 
         let circuit_slice = CircuitSlice::try_from(&create_circuit)?;
@@ -222,7 +239,12 @@ impl Action for CircuitProposeAction {
 
             let requester_node = client.get_node_status()?.node_id;
 
-            let signed_payload = make_signed_payload(&requester_node, signer, create_circuit)?;
+            let circuit_create_request = CircuitCreate {
+                create_circuit,
+                expires_at,
+            };
+            let signed_payload =
+                make_signed_payload(&requester_node, signer, circuit_create_request)?;
             client.submit_admin_payload(signed_payload)?;
 
             info!("The circuit proposal was submitted successfully");
@@ -234,6 +256,112 @@ impl Action for CircuitProposeAction {
     }
 }
 
+struct CircuitCreate {
+    create_circuit: CreateCircuit,
+    expires_at: Option<u64>,
+}
+
+/// Converts the `--expires-in` number of seconds into an expiration time, in seconds since the
+/// Unix epoch.
+fn parse_expires_in(expires_in: &str) -> Result<u64, CliError> {
+    let expires_in = expires_in
+        .parse::<u64>()
+        .ok()
+        .filter(|secs| *secs > 0)
+        .ok_or_else(|| {
+            CliError::ActionError(format!(
+                "'expires-in' must be a positive number of seconds: {}",
+                expires_in
+            ))
+        })?;
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|err| CliError::ActionError(format!("Unable to get current time: {}", err)))?;
+
+    Ok(now.as_secs() + expires_in)
+}
+
+fn parse_voting_policy(args: &ArgMatches) -> Result<VotingPolicy, CliError> {
+    let policy_type = match args.value_of("voting_policy").unwrap_or("unanimous") {
+        "unanimous" => VotingPolicyType::Unanimous,
+        "majority" => VotingPolicyType::Majority,
+        "threshold" => {
+            let threshold = args.value_of("vote_threshold").ok_or_else(|| {
+                CliError::ActionError(
+                    "'vote-threshold' is required for the threshold voting policy".into(),
+                )
+            })?;
+            VotingPolicyType::Threshold(
+                threshold
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|threshold| *threshold > 0)
+                    .ok_or_else(|| {
+                        CliError::ActionError(format!(
+                            "'vote-threshold' must be a positive number: {}",
+                            threshold
+                        ))
+                    })?,
+            )
+        }
+        policy => {
+            return Err(CliError::ActionError(format!(
+                "Voting policy '{}' is not supported",
+                policy
+            )))
+        }
+    };
+
+    let mut weights = BTreeMap::new();
+    if let Some(vote_weights) = args.values_of("vote_weight") {
+        for vote_weight in vote_weights {
+            let (node_id, weight) = parse_vote_weight_argument(vote_weight)?;
+            if weights.insert(node_id.clone(), weight).is_some() {
+                return Err(CliError::ActionError(format!(
+                    "Duplicate vote weight detected for '{}'",
+                    node_id
+                )));
+            }
+        }
+    }
+
+    Ok(VotingPolicy {
+        policy_type,
+        weights,
+    })
+}
+
+fn parse_vote_weight_argument(vote_weight: &str) -> Result<(String, u32), CliError> {
+    let mut iter = vote_weight.split("::");
+
+    let node_id = iter
+        .next()
+        .expect("str::split cannot return an empty iterator")
+        .to_string();
+    if node_id.is_empty() {
+        return Err(CliError::ActionError(
+            "Empty '--vote-weight' argument detected".into(),
+        ));
+    }
+
+    let weight = iter
+        .next()
+        .ok_or_else(|| CliError::ActionError(format!("Missing vote weight for '{}'", node_id)))?;
+    let weight = weight
+        .parse::<u32>()
+        .ok()
+        .filter(|weight| *weight > 0)
+        .ok_or_else(|| {
+            CliError::ActionError(format!(
+                "Vote weight for '{}' must be a positive number: {}",
+                node_id, weight
+            ))
+        })?;
+
+    Ok((node_id, weight))
+}
+
 #[derive(Deserialize)]
 struct Node {
     #[serde(alias = "node_id")]
@@ -771,6 +899,218 @@ fn propose_circuit_disband(
     }
 }
 
+struct CircuitSuspend {
+    circuit_id: String,
+}
+
+pub struct CircuitSuspendAction;
+
+impl Action for CircuitSuspendAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+        let url = args
+            .value_of("url")
+            .map(ToOwned::to_owned)
+            .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+            .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+
+        let signer = load_signer(args.value_of("private_key_file"))?;
+
+        let circuit_id = args
+            .value_of("circuit_id")
+            .ok_or_else(|| CliError::ActionError("'circuit-id' argument is required".into()))?;
+
+        propose_circuit_suspend(&url, signer, circuit_id)
+    }
+}
+
+fn propose_circuit_suspend(
+    url: &str,
+    signer: Box<dyn Signer>,
+    circuit_id: &str,
+) -> Result<(), CliError> {
+    let client = SplinterRestClientBuilder::new()
+        .with_url(url.to_string())
+        .with_auth(create_cylinder_jwt_auth(signer.clone())?)
+        .build()?;
+
+    let requester_node = client.get_node_status()?.node_id;
+    let circuit = client.fetch_circuit(circuit_id)?;
+
+    if let Some(circuit) = circuit {
+        // Only an `Active` circuit may be suspended
+        if circuit.circuit_status.is_some() && circuit.circuit_status != Some(CircuitStatus::Active)
+        {
+            return Err(CliError::ActionError(format!(
+                "Circuit '{}' is not active",
+                circuit_id
+            )));
+        }
+        let circuit_suspend_request = CircuitSuspend {
+            circuit_id: circuit_id.into(),
+        };
+        let signed_payload = make_signed_payload(&requester_node, signer, circuit_suspend_request)?;
+        client.submit_admin_payload(signed_payload)
+    } else {
+        Err(CliError::ActionError(format!(
+            "Circuit '{}' does not exist",
+            circuit_id
+        )))
+    }
+}
+
+struct CircuitResume {
+    circuit_id: String,
+}
+
+pub struct CircuitResumeAction;
+
+impl Action for CircuitResumeAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+        let url = args
+            .value_of("url")
+            .map(ToOwned::to_owned)
+            .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+            .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+
+        let signer = load_signer(args.value_of("private_key_file"))?;
+
+        let circuit_id = args
+            .value_of("circuit_id")
+            .ok_or_else(|| CliError::ActionError("'circuit-id' argument is required".into()))?;
+
+        propose_circuit_resume(&url, signer, circuit_id)
+    }
+}
+
+fn propose_circuit_resume(
+    url: &str,
+    signer: Box<dyn Signer>,
+    circuit_id: &str,
+) -> Result<(), CliError> {
+    let client = SplinterRestClientBuilder::new()
+        .with_url(url.to_string())
+        .with_auth(create_cylinder_jwt_auth(signer.clone())?)
+        .build()?;
+
+    let requester_node = client.get_node_status()?.node_id;
+    let circuit = client.fetch_circuit(circuit_id)?;
+
+    if let Some(circuit) = circuit {
+        // Only a `Suspended` circuit may be resumed
+        if circuit.circuit_status != Some(CircuitStatus::Suspended) {
+            return Err(CliError::ActionError(format!(
+                "Circuit '{}' is not suspended",
+                circuit_id
+            )));
+        }
+        let circuit_resume_request = CircuitResume {
+            circuit_id: circuit_id.into(),
+        };
+        let signed_payload = make_signed_payload(&requester_node, signer, circuit_resume_request)?;
+        client.submit_admin_payload(signed_payload)
+    } else {
+        Err(CliError::ActionError(format!(
+            "Circuit '{}' does not exist",
+            circuit_id
+        )))
+    }
+}
+
+struct CircuitUpdateNodeKeys {
+    circuit_id: String,
+    node_id: String,
+    public_key: Vec<u8>,
+    expires_at: Option<u64>,
+}
+
+pub struct CircuitUpdateNodeKeysAction;
+
+impl Action for CircuitUpdateNodeKeysAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+        let url = args
+            .value_of("url")
+            .map(ToOwned::to_owned)
+            .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+            .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+
+        let signer = load_signer(args.value_of("private_key_file"))?;
+
+        let circuit_id = args
+            .value_of("circuit_id")
+            .ok_or_else(|| CliError::ActionError("'circuit-id' argument is required".into()))?;
+
+        let public_key = args
+            .value_of("public_key")
+            .ok_or_else(|| CliError::ActionError("'public-key' argument is required".into()))?;
+        let public_key = parse_hex(public_key).map_err(|_| {
+            CliError::ActionError(format!(
+                "{:?} is not a valid hex-formatted public key",
+                public_key,
+            ))
+        })?;
+
+        let expires_at = args
+            .value_of("expires_in")
+            .map(parse_expires_in)
+            .transpose()?;
+
+        propose_circuit_update_node_keys(&url, signer, circuit_id, public_key, expires_at)
+    }
+}
+
+fn propose_circuit_update_node_keys(
+    url: &str,
+    signer: Box<dyn Signer>,
+    circuit_id: &str,
+    public_key: Vec<u8>,
+    expires_at: Option<u64>,
+) -> Result<(), CliError> {
+    let client = SplinterRestClientBuilder::new()
+        .with_url(url.to_string())
+        .with_auth(create_cylinder_jwt_auth(signer.clone())?)
+        .build()?;
+
+    let requester_node = client.get_node_status()?.node_id;
+    let circuit = client.fetch_circuit(circuit_id)?;
+
+    if let Some(circuit) = circuit {
+        // Node keys may only be updated on an `Active` circuit
+        if circuit.circuit_status.is_some() && circuit.circuit_status != Some(CircuitStatus::Active)
+        {
+            return Err(CliError::ActionError(format!(
+                "Circuit '{}' is not active",
+                circuit_id
+            )));
+        }
+        if !circuit
+            .members
+            .iter()
+            .any(|member| member.node_id == requester_node)
+        {
+            return Err(CliError::ActionError(format!(
+                "Node '{}' is not a member of circuit '{}'",
+                requester_node, circuit_id
+            )));
+        }
+        let circuit_update_request = CircuitUpdateNodeKeys {
+            circuit_id: circuit_id.into(),
+            node_id: requester_node.clone(),
+            public_key,
+            expires_at,
+        };
+        let signed_payload = make_signed_payload(&requester_node, signer, circuit_update_request)?;
+        client.submit_admin_payload(signed_payload)
+    } else {
+        Err(CliError::ActionError(format!(
+            "Circuit '{}' does not exist",
+            circuit_id
+        )))
+    }
+}
+
 struct CircuitPurge {
     circuit_id: String,
 }
@@ -1178,6 +1518,7 @@ fn list_proposals(
             "MEMBERS".to_string(),
             "COMMENTS".to_string(),
             "PROPOSAL_TYPE".to_string(),
+            "EXPIRES".to_string(),
         ],
     ];
     proposals.data.iter().for_each(|proposal| {
@@ -1205,6 +1546,12 @@ fn list_proposals(
             }
         };
 
+        let expires = match proposal.expires_at {
+            Some(expires_at) => format_timestamp(expires_at),
+            None if format == "csv" => String::new(),
+            None => "-".to_string(),
+        };
+
         let members = proposal
             .circuit
             .members
@@ -1219,6 +1566,7 @@ fn list_proposals(
             members,
             comments,
             proposal.proposal_type.to_string(),
+            expires,
         ]);
     });
 
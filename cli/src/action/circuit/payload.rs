@@ -15,19 +15,20 @@
 use cylinder::Signer;
 use openssl::hash::{hash, MessageDigest};
 use protobuf::Message;
-use splinter::admin::messages::CreateCircuit;
 use splinter::protos::admin::CircuitAbandon;
 use splinter::protos::admin::ProposalRemoveRequest;
 use splinter::protos::admin::{
     CircuitCreateRequest, CircuitDisbandRequest, CircuitManagementPayload,
     CircuitManagementPayload_Action as Action, CircuitManagementPayload_Header as Header,
-    CircuitProposalVote, CircuitProposalVote_Vote, CircuitPurgeRequest,
+    CircuitProposalVote, CircuitProposalVote_Vote, CircuitPurgeRequest, CircuitResumeRequest,
+    CircuitSuspendRequest, CircuitUpdateNodeKeysRequest,
 };
 
 use crate::error::CliError;
 
 use super::RemoveProposal;
-use super::{AbandonedCircuit, CircuitDisband, CircuitPurge};
+use super::{AbandonedCircuit, CircuitCreate, CircuitDisband, CircuitPurge};
+use super::{CircuitResume, CircuitSuspend, CircuitUpdateNodeKeys};
 use super::{CircuitVote, Vote};
 
 /// A circuit action that has a type and can be converted into a protobuf-serializable struct.
@@ -95,18 +96,23 @@ where
 
 // Conversions for explicit actions and their associated types.
 
-impl CircuitAction<CircuitCreateRequest> for CreateCircuit {
+impl CircuitAction<CircuitCreateRequest> for CircuitCreate {
     fn action_type(&self) -> Action {
         Action::CIRCUIT_CREATE_REQUEST
     }
 
     fn into_proto(self) -> Result<CircuitCreateRequest, CliError> {
-        CreateCircuit::into_proto(self).map_err(|err| {
+        let mut create_request = self.create_circuit.into_proto().map_err(|err| {
             CliError::ActionError(format!(
                 "Failed to convert circuit create request to protobuf: {}",
                 err
             ))
-        })
+        })?;
+        if let Some(expires_at) = self.expires_at {
+            create_request.set_expires_at(expires_at);
+        }
+
+        Ok(create_request)
     }
 }
 
@@ -158,6 +164,65 @@ impl ApplyToEnvelope for CircuitDisbandRequest {
     }
 }
 
+impl CircuitAction<CircuitSuspendRequest> for CircuitSuspend {
+    fn action_type(&self) -> Action {
+        Action::CIRCUIT_SUSPEND_REQUEST
+    }
+
+    fn into_proto(self) -> Result<CircuitSuspendRequest, CliError> {
+        let mut suspend_request = CircuitSuspendRequest::new();
+        suspend_request.set_circuit_id(self.circuit_id);
+        Ok(suspend_request)
+    }
+}
+
+impl ApplyToEnvelope for CircuitSuspendRequest {
+    fn apply(self, circuit_management_payload: &mut CircuitManagementPayload) {
+        circuit_management_payload.set_circuit_suspend_request(self);
+    }
+}
+
+impl CircuitAction<CircuitResumeRequest> for CircuitResume {
+    fn action_type(&self) -> Action {
+        Action::CIRCUIT_RESUME_REQUEST
+    }
+
+    fn into_proto(self) -> Result<CircuitResumeRequest, CliError> {
+        let mut resume_request = CircuitResumeRequest::new();
+        resume_request.set_circuit_id(self.circuit_id);
+        Ok(resume_request)
+    }
+}
+
+impl ApplyToEnvelope for CircuitResumeRequest {
+    fn apply(self, circuit_management_payload: &mut CircuitManagementPayload) {
+        circuit_management_payload.set_circuit_resume_request(self);
+    }
+}
+
+impl CircuitAction<CircuitUpdateNodeKeysRequest> for CircuitUpdateNodeKeys {
+    fn action_type(&self) -> Action {
+        Action::CIRCUIT_UPDATE_NODE_KEYS_REQUEST
+    }
+
+    fn into_proto(self) -> Result<CircuitUpdateNodeKeysRequest, CliError> {
+        let mut update_request = CircuitUpdateNodeKeysRequest::new();
+        update_request.set_circuit_id(self.circuit_id);
+        update_request.set_node_id(self.node_id);
+        update_request.set_public_key(self.public_key);
+        if let Some(expires_at) = self.expires_at {
+            update_request.set_expires_at(expires_at);
+        }
+        Ok(update_request)
+    }
+}
+
+impl ApplyToEnvelope for CircuitUpdateNodeKeysRequest {
+    fn apply(self, circuit_management_payload: &mut CircuitManagementPayload) {
+        circuit_management_payload.set_circuit_update_node_keys_request(self);
+    }
+}
+
 impl CircuitAction<CircuitPurgeRequest> for CircuitPurge {
     fn action_type(&self) -> Action {
         Action::CIRCUIT_PURGE_REQUEST
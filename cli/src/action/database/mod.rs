@@ -32,7 +32,7 @@ use clap::ArgMatches;
 use self::postgres::get_default_database;
 #[cfg(feature = "sqlite")]
 use self::sqlite::{get_default_database, sqlite_migrations};
-pub use self::state::StateMigrateAction;
+pub use self::state::{StateExportAction, StateImportAction, StateMigrateAction, StatePruneAction};
 #[cfg(feature = "upgrade")]
 pub use self::upgrade::UpgradeAction;
 use crate::error::CliError;
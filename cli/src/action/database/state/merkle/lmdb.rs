@@ -74,15 +74,27 @@ impl LazyLmdbMerkleState {
     }
 
     pub fn delete(self) -> Result<(), InternalError> {
-        if self.inner.borrow().is_some() {
-            self.factory
-                .get_database_purge_handle(&*self.circuit_id, &*self.service_id)
+        if self.inner.borrow().is_none() {
+            let path = self
+                .factory
+                .compute_path(&*self.circuit_id, &*self.service_id)
                 .map_err(|e| InternalError::with_message(format!("{}", e)))?
-                .purge()
-                .map_err(|e| InternalError::with_message(format!("{}", e)))
-        } else {
-            Ok(())
+                .with_extension("lmdb");
+
+            if !path.is_file() {
+                return Ok(());
+            }
+
+            // Open the existing database, so that both its data and lock files are purged
+            self.get_state()
+                .map_err(|e| InternalError::with_message(format!("{}", e)))?;
         }
+
+        self.factory
+            .get_database_purge_handle(&*self.circuit_id, &*self.service_id)
+            .map_err(|e| InternalError::with_message(format!("{}", e)))?
+            .purge()
+            .map_err(|e| InternalError::with_message(format!("{}", e)))
     }
 
     fn get_state(&self) -> Result<MerkleState, transact::error::InternalError> {
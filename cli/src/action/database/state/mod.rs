@@ -12,9 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-//! Provides scabbard state migration functionality
+//! Provides scabbard state migration, pruning, export, and import functionality
 
 mod merkle;
+mod prune;
+mod snapshot;
 
 use std::io;
 use std::io::prelude::*;
@@ -36,6 +38,8 @@ use super::{Action, CliError};
 #[cfg(any(feature = "postgres", feature = "sqlite"))]
 pub use self::merkle::{DieselInTransactionStateTreeStore, DieselStateTreeStore};
 pub use self::merkle::{LazyLmdbMerkleState, LmdbStateTreeStore, MerkleState};
+pub use self::prune::StatePruneAction;
+pub use self::snapshot::{StateExportAction, StateImportAction};
 
 /// A source of available trees
 pub trait StateTreeStore {
@@ -0,0 +1,197 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides scabbard state pruning functionality
+
+use std::str::FromStr;
+
+use clap::ArgMatches;
+use transact::state::Pruner;
+
+use crate::action::database::{get_default_database, stores::new_upgrade_stores, ConnectionUri};
+
+use super::{Action, CliError};
+
+/// The number of state roots retained if none is specified, which matches the default of splinterd
+const DEFAULT_RETAINED_STATE_ROOTS: usize = 1;
+
+pub struct StatePruneAction;
+
+impl Action for StatePruneAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+
+        let database_uri = match args.value_of("connect") {
+            Some(database_uri) => database_uri.to_string(),
+            None => get_default_database()?,
+        };
+
+        let upgrade_stores =
+            new_upgrade_stores(&ConnectionUri::from_str(&database_uri)?).map_err(|e| {
+                CliError::ActionError(format!(
+                    "Unable to get stores for database {}: {}",
+                    database_uri, e
+                ))
+            })?;
+
+        let retained_roots = match args.value_of("retained_roots") {
+            Some(retained_roots) => retained_roots.parse::<usize>().map_err(|_| {
+                CliError::ActionError(format!(
+                    "'{}' is not a valid number of state roots",
+                    retained_roots
+                ))
+            })?,
+            None => DEFAULT_RETAINED_STATE_ROOTS,
+        };
+        if retained_roots == 0 {
+            return Err(CliError::ActionError(
+                "At least one state root must be retained".to_string(),
+            ));
+        }
+
+        let services = match (args.value_of("circuit_id"), args.value_of("service_id")) {
+            (Some(circuit_id), Some(service_id)) => {
+                vec![(circuit_id.to_string(), service_id.to_string())]
+            }
+            (None, None) => {
+                let node_id = match upgrade_stores
+                    .new_node_id_store()
+                    .get_node_id()
+                    .map_err(|e| CliError::ActionError(format!("{}", e)))?
+                {
+                    Some(node_id) => node_id,
+                    None => {
+                        // This node has not set a node id, so it cannot have any circuits.
+                        info!("Skipping scabbard state prune, no local node ID found");
+                        return Ok(());
+                    }
+                };
+
+                upgrade_stores
+                    .new_admin_service_store()
+                    .list_circuits(&[])
+                    .map_err(|e| CliError::ActionError(format!("{}", e)))?
+                    .flat_map(|circuit| {
+                        circuit
+                            .roster()
+                            .iter()
+                            .filter(|svc| {
+                                svc.node_id() == node_id && svc.service_type() == "scabbard"
+                            })
+                            .map(|svc| {
+                                (
+                                    circuit.circuit_id().to_string(),
+                                    svc.service_id().to_string(),
+                                )
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                    .collect()
+            }
+            _ => {
+                return Err(CliError::ActionError(
+                    "'circuit-id' and 'service-id' must be provided together".to_string(),
+                ))
+            }
+        };
+
+        if services.is_empty() {
+            info!("Skipping scabbard state prune, no scabbard services found");
+            return Ok(());
+        }
+
+        let dry_run = args.is_present("dry_run");
+        let mut total_roots = 0;
+        let mut total_entries = 0;
+        for (circuit_id, service_id) in &services {
+            let commit_hash_store = upgrade_stores.new_commit_hash_store(circuit_id, service_id);
+
+            // The current root is always retained, even if the node stopped before recording it
+            // as retained
+            let mut roots = commit_hash_store
+                .get_retained_commit_hashes()
+                .map_err(|e| CliError::ActionError(e.to_string()))?;
+            if let Some(current_root) = commit_hash_store
+                .get_current_commit_hash()
+                .map_err(|e| CliError::ActionError(e.to_string()))?
+            {
+                if roots.last() != Some(&current_root) {
+                    roots.push(current_root);
+                }
+            }
+
+            let expired_count = roots.len().saturating_sub(retained_roots);
+            let expired_roots = roots.drain(..expired_count).collect::<Vec<_>>();
+            total_roots += expired_roots.len();
+
+            let merkle_state = upgrade_stores
+                .get_merkle_state(circuit_id, service_id, false)
+                .map_err(|e| CliError::ActionError(e.to_string()))?;
+
+            if dry_run {
+                info!(
+                    "{} state root(s) of {}::{} would be pruned",
+                    expired_roots.len(),
+                    circuit_id,
+                    service_id
+                );
+                continue;
+            }
+
+            let pruned_entries = if expired_roots.is_empty() {
+                0
+            } else {
+                merkle_state
+                    .prune(expired_roots.clone())
+                    .map_err(|e| {
+                        CliError::ActionError(format!(
+                            "Unable to prune state of {}::{}: {}",
+                            circuit_id, service_id, e
+                        ))
+                    })?
+                    .len()
+            };
+            commit_hash_store
+                .set_retained_commit_hashes(&roots)
+                .map_err(|e| CliError::ActionError(e.to_string()))?;
+            merkle_state.remove_pruned_entries()?;
+            total_entries += pruned_entries;
+
+            info!(
+                "Pruned {} state root(s) of {}::{}, removing {} entries",
+                expired_roots.len(),
+                circuit_id,
+                service_id,
+                pruned_entries
+            );
+        }
+
+        if dry_run {
+            info!(
+                "Dry run was successful: {} state root(s) of {} service(s) would be pruned",
+                total_roots,
+                services.len()
+            );
+        } else {
+            info!(
+                "Pruned {} state root(s) of {} service(s), removing {} entries",
+                total_roots,
+                services.len(),
+                total_entries
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,242 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides scabbard state snapshot export and import functionality
+
+use std::fs::File;
+use std::io::{self, prelude::*, BufReader, BufWriter};
+use std::str::FromStr;
+
+use clap::ArgMatches;
+use scabbard::snapshot::StateSnapshot;
+use scabbard::store::transact::factory::LmdbDatabaseFactory;
+use splinter::error::InternalError;
+use transact::state::{Committer, Reader};
+
+use crate::action::database::{
+    get_default_database,
+    stores::{new_upgrade_stores, TransactionalUpgradeStores, UpgradeStoresWithLmdb},
+    ConnectionUri,
+};
+
+use super::{get_state_dir, Action, CliError};
+
+pub struct StateExportAction;
+
+impl Action for StateExportAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+        let (circuit_id, service_id) = get_service(args)?;
+        let upgrade_stores = get_upgrade_stores(args)?;
+
+        let commit_hash = upgrade_stores
+            .new_commit_hash_store(circuit_id, service_id)
+            .get_current_commit_hash()
+            .map_err(|e| CliError::ActionError(format!("{}", e)))?
+            .ok_or_else(|| {
+                CliError::ActionError(format!(
+                    "No commit hash for service {}::{}",
+                    circuit_id, service_id,
+                ))
+            })?;
+
+        let merkle_state = upgrade_stores
+            .get_merkle_state(circuit_id, service_id, false)
+            .map_err(|e| CliError::ActionError(e.to_string()))?;
+
+        let leaves = merkle_state
+            .filter_iter(&commit_hash, None)
+            .and_then(|iter| iter.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| {
+                CliError::ActionError(format!("Unable to get leaves for commit hash: {}", e))
+            })?;
+
+        info!(
+            "Exporting {} state entries for {}::{} at state root {}",
+            leaves.len(),
+            circuit_id,
+            service_id,
+            commit_hash
+        );
+
+        let snapshot = StateSnapshot::new(commit_hash.clone(), commit_hash, leaves);
+
+        let writer: Box<dyn Write> = match args.value_of("output") {
+            Some(path) => Box::new(File::create(path).map_err(|e| {
+                CliError::ActionError(format!("Unable to create file {}: {}", path, e))
+            })?),
+            None => Box::new(io::stdout()),
+        };
+        let mut writer = BufWriter::new(writer);
+
+        serde_json::to_writer(&mut writer, &snapshot)
+            .map_err(|e| CliError::ActionError(format!("Unable to write snapshot: {}", e)))?;
+        writer
+            .flush()
+            .map_err(|e| CliError::ActionError(format!("Unable to write snapshot: {}", e)))
+    }
+}
+
+pub struct StateImportAction;
+
+impl Action for StateImportAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+        let (circuit_id, service_id) = get_service(args)?;
+        let expected_root = args.value_of("expected_root").ok_or_else(|| {
+            CliError::ActionError("'expected-root' argument is required".to_string())
+        })?;
+
+        let reader: Box<dyn Read> = match args.value_of("input") {
+            Some(path) => Box::new(File::open(path).map_err(|e| {
+                CliError::ActionError(format!("Unable to open file {}: {}", path, e))
+            })?),
+            None => Box::new(io::stdin()),
+        };
+        let snapshot: StateSnapshot = serde_json::from_reader(BufReader::new(reader))
+            .map_err(|e| CliError::ActionError(format!("Unable to read snapshot: {}", e)))?;
+
+        snapshot
+            .verify(expected_root)
+            .map_err(|e| CliError::ActionError(format!("Invalid snapshot: {}", e)))?;
+
+        if !args.is_present("yes") {
+            warn!(
+                "Warning: This will replace the current state of {}::{} with the snapshot. \
+                splinterd must not be running while the snapshot is imported.",
+                circuit_id, service_id
+            );
+            warn!("Are you sure you wish to import the scabbard state snapshot? [y/N]");
+            let stdin = io::stdin();
+            let line = stdin.lock().lines().next();
+            match line {
+                Some(Ok(input)) => match input.as_str() {
+                    "y" => (),
+                    _ => {
+                        info!("Import cancelled");
+                        return Ok(());
+                    }
+                },
+                _ => {
+                    return Err(CliError::ActionError(
+                        "Unable to get prompt response".to_string(),
+                    ))
+                }
+            }
+        }
+
+        info!(
+            "Importing {} state entries for {}::{} at state root {}",
+            snapshot.leaves().len(),
+            circuit_id,
+            service_id,
+            snapshot.state_root()
+        );
+
+        let upgrade_stores = get_upgrade_stores(args)?;
+        upgrade_stores
+            .in_transaction(Box::new(|upgrade_stores| {
+                // Remove the existing tree, so that the imported state does not share storage
+                // with entries that are not part of the snapshot
+                if upgrade_stores
+                    .new_state_tree_store()
+                    .has_tree(circuit_id, service_id)?
+                {
+                    upgrade_stores
+                        .get_merkle_state(circuit_id, service_id, false)?
+                        .delete_tree()
+                        .map_err(|e| InternalError::from_source(Box::new(e)))?;
+                }
+
+                let merkle_state = upgrade_stores.get_merkle_state(circuit_id, service_id, true)?;
+                let initial_state_root = merkle_state
+                    .get_state_root()
+                    .map_err(|e| InternalError::from_source(Box::new(e)))?;
+
+                let state_root = merkle_state
+                    .commit(&initial_state_root, &snapshot.to_state_changes())
+                    .map_err(|e| {
+                        InternalError::with_message(format!(
+                            "Unable to commit snapshot state changes {}",
+                            e
+                        ))
+                    })?;
+
+                if state_root != snapshot.state_root() {
+                    return Err(InternalError::with_message(format!(
+                        "Ending commit hash did not match expected {} != {}",
+                        state_root,
+                        snapshot.state_root()
+                    )));
+                }
+
+                let commit_hash_store =
+                    upgrade_stores.new_commit_hash_store(circuit_id, service_id);
+                commit_hash_store
+                    .set_current_commit_hash(&state_root)
+                    .map_err(|e| InternalError::from_source(Box::new(e)))?;
+                // The previously retained roots were removed with the old tree
+                commit_hash_store
+                    .set_retained_commit_hashes(&[state_root])
+                    .map_err(|e| InternalError::from_source(Box::new(e)))
+            }))
+            .map_err(|e| CliError::ActionError(e.to_string()))?;
+
+        info!(
+            "Scabbard state for {}::{} successfully imported",
+            circuit_id, service_id
+        );
+
+        Ok(())
+    }
+}
+
+/// Gets the circuit ID and service ID of the scabbard service from the arguments
+fn get_service<'a>(args: &'a ArgMatches) -> Result<(&'a str, &'a str), CliError> {
+    let circuit_id = args
+        .value_of("circuit_id")
+        .ok_or_else(|| CliError::ActionError("'circuit-id' argument is required".to_string()))?;
+    let service_id = args
+        .value_of("service_id")
+        .ok_or_else(|| CliError::ActionError("'service-id' argument is required".to_string()))?;
+
+    Ok((circuit_id, service_id))
+}
+
+/// Gets the stores for the database given by the `connect` argument, using the LMDB files in the
+/// state directory for scabbard state if the `lmdb` argument is present
+fn get_upgrade_stores(args: &ArgMatches) -> Result<Box<dyn TransactionalUpgradeStores>, CliError> {
+    let database_uri = match args.value_of("connect") {
+        Some(database_uri) => database_uri.to_string(),
+        None => get_default_database()?,
+    };
+
+    let upgrade_stores =
+        new_upgrade_stores(&ConnectionUri::from_str(&database_uri)?).map_err(|e| {
+            CliError::ActionError(format!(
+                "Unable to get stores for database {}: {}",
+                database_uri, e
+            ))
+        })?;
+
+    if args.is_present("lmdb") {
+        let state_dir = get_state_dir(Some(args))?;
+        Ok(Box::new(UpgradeStoresWithLmdb::new(
+            upgrade_stores,
+            LmdbDatabaseFactory::new_state_db_factory(&state_dir, None),
+        )))
+    } else {
+        Ok(upgrade_stores)
+    }
+}
@@ -27,9 +27,13 @@ use std::os::unix::fs::MetadataExt;
 use clap::ArgMatches;
 use cylinder::{secp256k1::Secp256k1Context, Context};
 use cylinder::{PrivateKey, PublicKey};
+#[cfg(feature = "encrypted-keys")]
+use splinter::keys::encrypted::encrypt_private_key;
 use users::{get_group_by_gid, get_group_by_name};
 
 use crate::error::CliError;
+#[cfg(feature = "encrypted-keys")]
+use crate::signing::load_key_passphrase;
 
 use super::{chown, Action};
 
@@ -163,8 +167,19 @@ impl Action for KeyGenAction {
         let private_key_path = key_dir.join(&key_name).with_extension("priv");
         let public_key_path = key_dir.join(&key_name).with_extension("pub");
 
+        let (private_key, public_key) = create_key_pair()?;
+
+        #[cfg(feature = "encrypted-keys")]
+        let private_key_contents = if args.is_present("encrypt") {
+            encrypt_key(&private_key)?
+        } else {
+            private_key.as_hex()
+        };
+        #[cfg(not(feature = "encrypted-keys"))]
+        let private_key_contents = private_key.as_hex();
+
         write_keys(
-            create_key_pair()?,
+            (private_key_contents, public_key),
             &key_dir,
             private_key_path,
             public_key_path,
@@ -178,7 +193,7 @@ impl Action for KeyGenAction {
 }
 
 fn write_keys(
-    keys: (PrivateKey, PublicKey),
+    keys: (String, PublicKey),
     key_dir: &Path,
     private_key_path: PathBuf,
     public_key_path: PathBuf,
@@ -268,7 +283,7 @@ fn write_keys(
                 ))
             })?;
 
-        writeln!(&private_key_file, "{}", private_key.as_hex()).map_err(|err| {
+        writeln!(&private_key_file, "{}", private_key).map_err(|err| {
             CliError::ActionError(format!(
                 "Failed to write to private key file '{}': {}",
                 private_key_path.display(),
@@ -320,6 +335,26 @@ fn write_keys(
     Ok(())
 }
 
+/// Encrypts the private key with the passphrase from the environment.
+#[cfg(feature = "encrypted-keys")]
+fn encrypt_key(private_key: &PrivateKey) -> Result<String, CliError> {
+    let passphrase = load_key_passphrase()?.ok_or_else(|| {
+        CliError::EnvironmentError(
+            "A passphrase is required to encrypt the private key; set SPLINTER_KEY_PASSPHRASE \
+             or SPLINTER_KEY_PASSPHRASE_FILE"
+                .into(),
+        )
+    })?;
+    if passphrase.is_empty() {
+        return Err(CliError::EnvironmentError(
+            "The passphrase used to encrypt the private key must not be empty".into(),
+        ));
+    }
+
+    encrypt_private_key(private_key.as_slice(), &passphrase)
+        .map_err(|err| CliError::ActionError(format!("Failed to encrypt private key: {}", err)))
+}
+
 /// Creates a public/private key pair.
 ///
 /// Returns both keys if successful
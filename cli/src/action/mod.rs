@@ -14,6 +14,8 @@
 // limitations under the License.
 
 mod api;
+#[cfg(feature = "audit-log")]
+pub mod audit;
 pub mod certs;
 pub mod circuit;
 #[cfg(feature = "command")]
@@ -23,6 +25,8 @@ pub mod database;
 pub mod keygen;
 #[cfg(feature = "authorization-handler-maintenance")]
 pub mod maintenance;
+#[cfg(feature = "peer")]
+pub mod peer;
 pub mod permissions;
 #[cfg(feature = "playlist-smallbank")]
 pub mod playlist;
@@ -0,0 +1,270 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Actions for handling peer subcommands.
+
+use chrono::{TimeZone, Utc};
+use clap::ArgMatches;
+
+use crate::error::CliError;
+use crate::signing::{create_cylinder_jwt_auth, load_signer};
+
+use super::{
+    api::{Peer, PeerRetry, SplinterRestClient, SplinterRestClientBuilder},
+    print_table, Action, DEFAULT_SPLINTER_REST_API_URL, SPLINTER_REST_API_URL_ENV,
+};
+
+/// Constructs a new Splinter REST client from the CLI arguments.
+fn new_client(arg_matches: &Option<&ArgMatches<'_>>) -> Result<SplinterRestClient, CliError> {
+    let url = arg_matches
+        .and_then(|args| args.value_of("url"))
+        .map(ToOwned::to_owned)
+        .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+        .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+
+    let signer = load_signer(arg_matches.and_then(|args| args.value_of("private_key_file")))?;
+
+    SplinterRestClientBuilder::new()
+        .with_url(url)
+        .with_auth(create_cylinder_jwt_auth(signer)?)
+        .build()
+}
+
+/// The action responsible for listing a node's peers.
+///
+/// The specific args for this action:
+///
+/// * url: specifies the URL of the splinter node to be queried; falls back to the environment
+///   variable SPLINTER_REST_API_URL
+/// * format: specifies the output format; one of "human", "json" or "csv"
+pub struct ListPeersAction;
+
+impl Action for ListPeersAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let format = arg_matches
+            .and_then(|args| args.value_of("format"))
+            .unwrap_or("human");
+
+        let peers = new_client(&arg_matches)?.list_peers()?;
+
+        let data = std::iter::once(vec![
+            "PEER".to_string(),
+            "STATUS".to_string(),
+            "ACTIVE ENDPOINT".to_string(),
+            "REFS".to_string(),
+            "CONNECTED SINCE".to_string(),
+            "LAST HEARTBEAT".to_string(),
+            "LATENCY".to_string(),
+        ])
+        .chain(peers.into_iter().map(|peer| {
+            vec![
+                peer.peer_id.clone(),
+                format_status(&peer),
+                peer.active_endpoint.clone(),
+                peer.ref_count.to_string(),
+                format_optional_timestamp(peer.connected_since),
+                format_optional_timestamp(peer.last_heartbeat),
+                format_optional_millis(peer.last_heartbeat_latency_millis),
+            ]
+        }));
+
+        match format {
+            "csv" => {
+                for row in data {
+                    println!("{}", row.join(","))
+                }
+            }
+            "json" => println!(
+                "\n {}",
+                serde_json::to_string_pretty(&data.collect::<Vec<_>>()).map_err(|err| {
+                    CliError::ActionError(format!("Cannot format peers into json: {}", err))
+                })?
+            ),
+            _ => print_table(data.collect()),
+        }
+
+        Ok(())
+    }
+}
+
+/// The action responsible for showing one of a node's peers.
+///
+/// The specific args for this action:
+///
+/// * peer_id: the ID of the peer to show
+/// * url: specifies the URL of the splinter node to be queried; falls back to the environment
+///   variable SPLINTER_REST_API_URL
+/// * format: specifies the output format; one of "human" or "json"
+pub struct ShowPeerAction;
+
+impl Action for ShowPeerAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let format = arg_matches
+            .and_then(|args| args.value_of("format"))
+            .unwrap_or("human");
+
+        let peer_id = arg_matches
+            .and_then(|args| args.value_of("peer_id"))
+            .ok_or_else(|| CliError::ActionError("A peer ID must be specified".into()))?;
+
+        let peer = new_client(&arg_matches)?
+            .get_peer(peer_id)?
+            .ok_or_else(|| CliError::ActionError(format!("Peer {} does not exist", peer_id)))?;
+
+        match format {
+            "json" => println!(
+                "\n {}",
+                serde_json::to_string(&peer).map_err(|err| CliError::ActionError(format!(
+                    "Cannot format peer into json: {}",
+                    err
+                )))?
+            ),
+            _ => print_peer(&peer),
+        }
+
+        Ok(())
+    }
+}
+
+fn print_peer(peer: &Peer) {
+    println!("Peer: {}", peer.peer_id);
+    println!("Local ID: {}", peer.local_id);
+    println!("Status: {}", format_status(peer));
+    println!("Connection ID: {}", peer.connection_id);
+    println!("Active endpoint: {}", peer.active_endpoint);
+    println!("Endpoints:");
+    for endpoint in peer.endpoints.iter() {
+        println!("    {}", endpoint);
+    }
+    println!("References: {}", peer.ref_count);
+    println!(
+        "Connected since: {}",
+        format_optional_timestamp(peer.connected_since)
+    );
+    println!(
+        "Connection latency: {}",
+        format_optional_millis(peer.connection_latency_millis)
+    );
+    println!(
+        "Last heartbeat: {}",
+        format_optional_timestamp(peer.last_heartbeat)
+    );
+    println!(
+        "Last heartbeat latency: {}",
+        format_optional_millis(peer.last_heartbeat_latency_millis)
+    );
+}
+
+/// Formats a peer's status, followed by the number of reconnection attempts if it is
+/// disconnected.
+fn format_status(peer: &Peer) -> String {
+    match peer.reconnection_attempts {
+        Some(attempts) => format!("{} ({} attempts)", peer.status, attempts),
+        None => peer.status.clone(),
+    }
+}
+
+/// The action responsible for listing the retry state of a node's peers.
+///
+/// The specific args for this action:
+///
+/// * url: specifies the URL of the splinter node to be queried; falls back to the environment
+///   variable SPLINTER_REST_API_URL
+/// * format: specifies the output format; one of "human", "json" or "csv"
+pub struct ListRetriesAction;
+
+impl Action for ListRetriesAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let format = arg_matches
+            .and_then(|args| args.value_of("format"))
+            .unwrap_or("human");
+        let retries = new_client(&arg_matches)?.list_peer_retries()?;
+
+        let data = std::iter::once(vec![
+            "PEER".to_string(),
+            "ENDPOINTS".to_string(),
+            "STATUS".to_string(),
+            "ATTEMPTS".to_string(),
+            "NEXT ATTEMPT".to_string(),
+            "POLICY".to_string(),
+        ])
+        .chain(retries.into_iter().map(|retry| {
+            vec![
+                retry.peer_id.clone().unwrap_or_else(|| "-".into()),
+                retry.endpoints.join(";"),
+                retry.status.clone(),
+                retry.attempts.to_string(),
+                format_optional_timestamp(retry.next_attempt),
+                format_policy(&retry),
+            ]
+        }));
+
+        match format {
+            "csv" => {
+                for row in data {
+                    println!("{}", row.join(","))
+                }
+            }
+            "json" => println!(
+                "\n {}",
+                serde_json::to_string_pretty(&data.collect::<Vec<_>>()).map_err(|err| {
+                    CliError::ActionError(format!("Cannot format peer retries into json: {}", err))
+                })?
+            ),
+            _ => print_table(data.collect()),
+        }
+
+        Ok(())
+    }
+}
+
+/// Formats a peer's retry policy as `<initial>-<max>s x<multiplier>`, followed by the maximum
+/// number of attempts if the policy gives up.
+fn format_policy(retry: &PeerRetry) -> String {
+    match &retry.policy {
+        Some(policy) => {
+            let mut formatted = format!(
+                "{}-{}s x{}",
+                policy.initial_interval_millis as f64 / 1000.0,
+                policy.max_interval_millis as f64 / 1000.0,
+                policy.multiplier
+            );
+            if let Some(max_attempts) = policy.max_attempts {
+                formatted.push_str(&format!(" max {}", max_attempts));
+            }
+            formatted
+        }
+        None => "default".into(),
+    }
+}
+
+/// Formats a time, given in seconds since the Unix epoch, as an RFC 3339 UTC timestamp, or "-"
+/// if there is no time.
+fn format_optional_timestamp(secs: Option<u64>) -> String {
+    match secs {
+        Some(secs) => Utc
+            .timestamp_opt(secs as i64, 0)
+            .single()
+            .map(|time| time.to_rfc3339())
+            .unwrap_or_else(|| secs.to_string()),
+        None => "-".into(),
+    }
+}
+
+/// Formats a duration, given in milliseconds, or "-" if there is no duration.
+fn format_optional_millis(millis: Option<u64>) -> String {
+    millis
+        .map(|millis| format!("{}ms", millis))
+        .unwrap_or_else(|| "-".into())
+}
@@ -82,6 +82,7 @@ impl Action for ListAssignmentsAction {
 /// * id_key: an identifier of type key; a public key
 /// * id_user: an identifier of type user; a user ID
 /// * role: a role to add to the assignment; repeated
+/// * scope: a circuit scope to restrict the assignment to; repeated
 pub struct CreateAssignmentAction;
 
 impl Action for CreateAssignmentAction {
@@ -94,9 +95,15 @@ impl Action for CreateAssignmentAction {
             .map(|s| s.to_owned())
             .collect();
 
+        let scopes = arg_matches
+            .and_then(|args| args.values_of("scope"))
+            .map(|vals| vals.map(|s| s.to_owned()).collect())
+            .unwrap_or_else(Vec::new);
+
         let assignment = AssignmentBuilder::default()
             .with_identity(identity.clone())
             .with_roles(roles)
+            .with_scopes(scopes)
             .build()?;
         let client = new_client(&arg_matches)?;
         if !is_dry_run(&arg_matches) {
@@ -169,6 +176,9 @@ impl Action for ShowAssignmentAction {
 /// * add_role: a role to add to the assignment; repeated
 /// * rm_role: a role to remove from the assignment; repeated
 /// * rm_all: remove all the currently assigned roles
+/// * add_scope: a circuit scope to add to the assignment; repeated
+/// * rm_scope: a circuit scope to remove from the assignment; repeated
+/// * rm_all_scopes: remove all the assignment's scopes, making it node-wide
 /// * dry_run: validate the inputs but do not submit the changes
 /// * force: applies the changes, even if a role is added and removed
 pub struct UpdateAssignmentAction;
@@ -202,11 +212,29 @@ impl Action for UpdateAssignmentAction {
             )
         };
 
+        let scopes_to_add = arg_matches
+            .and_then(|args| args.values_of("add_scope"))
+            .map(|vals| vals.map(|s| s.to_owned()).collect())
+            .unwrap_or_else(Vec::new);
+        let scopes_to_rm = arg_matches
+            .and_then(|args| args.values_of("rm_scope"))
+            .map(|vals| vals.map(|s| s.to_owned()).collect())
+            .unwrap_or_else(Vec::new);
+        let rm_all_scopes = arg_matches
+            .map(|args| args.is_present("rm_all_scopes"))
+            .unwrap_or(false);
+        let scope_update = ScopeUpdate {
+            scopes_to_add,
+            scopes_to_rm,
+            rm_all_scopes,
+        };
+
         update_assignment(
             new_client(&arg_matches)?,
             identity,
             roles_to_add,
             role_removal,
+            scope_update,
             force,
             dry_run,
         )
@@ -218,11 +246,65 @@ enum RoleRemoval {
     Remove(Vec<String>),
 }
 
+struct ScopeUpdate {
+    scopes_to_add: Vec<String>,
+    scopes_to_rm: Vec<String>,
+    rm_all_scopes: bool,
+}
+
+impl ScopeUpdate {
+    fn is_empty(&self) -> bool {
+        self.scopes_to_add.is_empty() && self.scopes_to_rm.is_empty() && !self.rm_all_scopes
+    }
+
+    /// Applies the update to the given scopes, returning the updated scopes.
+    fn apply(self, current_scopes: Vec<String>, force: bool) -> Result<Vec<String>, CliError> {
+        if self.rm_all_scopes {
+            return Ok(self.scopes_to_add);
+        }
+
+        let mut scopes_to_add = self.scopes_to_add.into_iter().collect::<BTreeSet<_>>();
+        let mut scopes_to_rm = self.scopes_to_rm.into_iter().collect::<BTreeSet<_>>();
+
+        if !force && scopes_to_add.intersection(&scopes_to_rm).count() > 0 {
+            return Err(CliError::ActionError(format!(
+                "Cannot add and remove the same scopes: {}",
+                scopes_to_add
+                    .intersection(&scopes_to_rm)
+                    .map(|s| s.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )));
+        }
+
+        let mut scopes = current_scopes
+            .into_iter()
+            .filter(|scope| !scopes_to_rm.remove(scope))
+            .collect::<BTreeSet<_>>();
+
+        scopes.append(&mut scopes_to_add);
+
+        if !force && !scopes_to_rm.is_empty() {
+            return Err(CliError::ActionError(format!(
+                "Cannot remove scopes that do not belong to the assignment: {}",
+                scopes_to_rm
+                    .iter()
+                    .map(|s| s.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )));
+        }
+
+        Ok(scopes.into_iter().collect())
+    }
+}
+
 fn update_assignment(
     client: SplinterRestClient,
     identity: Identity,
     roles_to_add: Vec<String>,
     role_removal: RoleRemoval,
+    scope_update: ScopeUpdate,
     force: bool,
     is_dry_run: bool,
 ) -> Result<(), CliError> {
@@ -234,6 +316,12 @@ fn update_assignment(
         ))
     })?;
 
+    let scopes = if scope_update.is_empty() {
+        None
+    } else {
+        Some(scope_update.apply(assignment.scopes, force)?)
+    };
+
     let roles = match role_removal {
         RoleRemoval::RemoveAll => {
             println!("Removing roles {}", assignment.roles.join(", "));
@@ -282,6 +370,7 @@ fn update_assignment(
     let updated_assignment = AssignmentUpdateBuilder::default()
         .with_identity(identity)
         .with_roles(Some(roles))
+        .with_scopes(scopes)
         .build()?;
 
     if !is_dry_run {
@@ -319,6 +408,12 @@ fn display_human_readable(assignment: &Assignment) {
     for role in &assignment.roles {
         println!("        {}", role);
     }
+    if !assignment.scopes.is_empty() {
+        println!("    Scopes:");
+        for scope in &assignment.scopes {
+            println!("        {}", scope);
+        }
+    }
 }
 
 fn get_identity_arg<'a>(arg_matches: &Option<&ArgMatches<'a>>) -> Result<Identity, CliError> {
@@ -22,6 +22,8 @@ use std::collections::HashMap;
 use crate::action::api::{ServerError, SplinterRestClient};
 use crate::error::CliError;
 
+const PAGING_LIMIT: &str = "1000";
+
 impl SplinterRestClient {
     /// Adds a new node to the registry.
     pub fn add_node(&self, node: &RegistryNode) -> Result<(), CliError> {
@@ -56,6 +58,60 @@ impl SplinterRestClient {
             })
     }
 
+    /// Lists the nodes in the registry, optionally filtered by whether or not they are reachable.
+    /// All pages of the registry's response are fetched.
+    pub fn list_nodes(&self, reachable: Option<bool>) -> Result<Vec<RegistryNode>, CliError> {
+        let mut url = format!("{}/registry/nodes?limit={}", self.url, PAGING_LIMIT);
+        if let Some(reachable) = reachable {
+            url = format!("{}&reachable={}", &url, reachable);
+        }
+
+        let mut nodes = vec![];
+        loop {
+            let slice = Client::new()
+                .get(&url)
+                .header("Authorization", &self.auth)
+                .send()
+                .map_err(|err| CliError::ActionError(format!("Failed to list nodes: {}", err)))
+                .and_then(|res| {
+                    let status = res.status();
+                    if status.is_success() {
+                        res.json::<RegistryNodeListSlice>().map_err(|_| {
+                            CliError::ActionError(
+                                "Request was successful, but received an invalid response".into(),
+                            )
+                        })
+                    } else {
+                        let message = res
+                            .json::<ServerError>()
+                            .map_err(|_| {
+                                CliError::ActionError(format!(
+                                    "Registry list nodes request failed with status code '{}', \
+                                     but error response was not valid",
+                                    status
+                                ))
+                            })?
+                            .message;
+
+                        Err(CliError::ActionError(format!(
+                            "Failed to list nodes: {}",
+                            message
+                        )))
+                    }
+                })?;
+
+            nodes.extend(slice.data);
+
+            if slice.paging.has_next() {
+                url = format!("{}{}", self.url, slice.paging.next);
+            } else {
+                break;
+            }
+        }
+
+        Ok(nodes)
+    }
+
     /// Retrieves the node with the given identity from the registry.
     pub fn get_node(&self, identity: &str) -> Result<Option<RegistryNode>, CliError> {
         let request = Client::new()
@@ -100,6 +156,42 @@ pub struct RegistryNode {
     pub display_name: String,
     pub keys: Vec<String>,
     pub metadata: HashMap<String, String>,
+    #[serde(default, skip_serializing)]
+    pub health: Option<RegistryNodeHealth>,
+}
+
+/// The reachability of a node, as reported by the registry; times are in seconds since the Unix
+/// epoch.
+#[cfg(feature = "registry")]
+#[derive(Debug, Deserialize)]
+pub struct RegistryNodeHealth {
+    pub reachable: bool,
+    pub last_connected: Option<u64>,
+    pub last_failure: Option<u64>,
+    pub latency_millis: Option<u64>,
+}
+
+#[cfg(feature = "registry")]
+#[derive(Debug, Deserialize)]
+struct RegistryNodeListSlice {
+    data: Vec<RegistryNode>,
+    paging: Paging,
+}
+
+#[cfg(feature = "registry")]
+#[derive(Debug, Deserialize)]
+struct Paging {
+    next: String,
+    total: usize,
+    limit: usize,
+    offset: usize,
+}
+
+#[cfg(feature = "registry")]
+impl Paging {
+    fn has_next(&self) -> bool {
+        self.total.saturating_sub(self.offset) > self.limit
+    }
 }
 
 #[cfg(feature = "registry")]
@@ -15,6 +15,8 @@
 #[cfg(feature = "registry")]
 mod api;
 
+#[cfg(feature = "registry")]
+use chrono::{TimeZone, Utc};
 use clap::ArgMatches;
 use splinter::registry::{Node, YamlNode};
 #[cfg(feature = "registry")]
@@ -29,6 +31,8 @@ use crate::registry::api::RegistryNode;
 use crate::signing::{create_cylinder_jwt_auth, load_signer};
 
 use super::api::SplinterRestClientBuilder;
+#[cfg(feature = "registry")]
+use super::print_table;
 use super::{msg_from_io_error, Action, DEFAULT_SPLINTER_REST_API_URL, SPLINTER_REST_API_URL_ENV};
 
 const DEFAULT_OUTPUT_FILE: &str = "./nodes.yaml";
@@ -218,6 +222,7 @@ impl Action for RegistryAddAction {
                 display_name: remote_node.display_name,
                 keys: remote_node.keys,
                 metadata: remote_node.metadata,
+                health: None,
             };
 
             if !args.is_present("dry_run") {
@@ -250,6 +255,7 @@ impl Action for RegistryAddAction {
                 display_name,
                 keys,
                 metadata: node_metadata,
+                health: None,
             };
 
             if !args.is_present("dry_run") {
@@ -263,6 +269,87 @@ impl Action for RegistryAddAction {
     }
 }
 
+#[cfg(feature = "registry")]
+pub struct RegistryListAction;
+
+#[cfg(feature = "registry")]
+impl Action for RegistryListAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let url = arg_matches
+            .and_then(|args| args.value_of("url"))
+            .map(ToOwned::to_owned)
+            .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+            .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+
+        let reachable = match arg_matches.and_then(|args| args.value_of("reachable")) {
+            Some("yes") => Some(true),
+            Some("no") => Some(false),
+            _ => None,
+        };
+
+        let format = arg_matches
+            .and_then(|args| args.value_of("format"))
+            .unwrap_or("human");
+
+        let signer = load_signer(arg_matches.and_then(|args| args.value_of("private_key_file")))?;
+
+        let client = SplinterRestClientBuilder::new()
+            .with_url(url)
+            .with_auth(create_cylinder_jwt_auth(signer)?)
+            .build()?;
+
+        let nodes = client.list_nodes(reachable)?;
+
+        let empty = if format == "csv" { "" } else { "-" };
+        let mut data = vec![vec![
+            "IDENTITY".to_string(),
+            "DISPLAY NAME".to_string(),
+            "REACHABLE".to_string(),
+            "LAST CONNECTED".to_string(),
+            "LAST FAILURE".to_string(),
+            "LATENCY".to_string(),
+        ]];
+        for node in nodes {
+            let (reachable, last_connected, last_failure, latency) = match node.health {
+                Some(health) => (
+                    if health.reachable { "yes" } else { "no" }.to_string(),
+                    health.last_connected.map(format_timestamp),
+                    health.last_failure.map(format_timestamp),
+                    health.latency_millis.map(|millis| format!("{}ms", millis)),
+                ),
+                None => (empty.to_string(), None, None, None),
+            };
+            data.push(vec![
+                node.identity,
+                node.display_name,
+                reachable,
+                last_connected.unwrap_or_else(|| empty.to_string()),
+                last_failure.unwrap_or_else(|| empty.to_string()),
+                latency.unwrap_or_else(|| empty.to_string()),
+            ]);
+        }
+
+        if format == "csv" {
+            for row in data {
+                println!("{}", row.join(","))
+            }
+        } else {
+            print_table(data);
+        }
+
+        Ok(())
+    }
+}
+
+/// Formats a time, given in seconds since the Unix epoch, as an RFC 3339 UTC timestamp.
+#[cfg(feature = "registry")]
+fn format_timestamp(secs: u64) -> String {
+    Utc.timestamp_opt(secs as i64, 0)
+        .single()
+        .map(|time| time.to_rfc3339())
+        .unwrap_or_else(|| secs.to_string())
+}
+
 #[cfg(feature = "registry")]
 fn parse_metadata(metadata: &str) -> Result<(String, String), CliError> {
     let mut parts = metadata.splitn(2, ':');
@@ -95,47 +95,54 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
         (@setting SubcommandRequiredElseHelp)
     );
 
-    app = app
-        .subcommand(
-        SubCommand::with_name("keygen")
-            .about("Generates secp256k1 keys")
-            .arg(
-                Arg::with_name("key-name")
-                    .takes_value(true)
-                    .help("Name of keys generated; defaults to user name"),
-            )
-            .arg(
-                Arg::with_name("key_dir")
-                    .long("key-dir")
-                    .takes_value(true)
-                    .conflicts_with("system")
-                    .help(
-                        "Name of the directory in which to create the keys; defaults to \
+    let keygen_command = SubCommand::with_name("keygen")
+        .about("Generates secp256k1 keys")
+        .arg(
+            Arg::with_name("key-name")
+                .takes_value(true)
+                .help("Name of keys generated; defaults to user name"),
+        )
+        .arg(
+            Arg::with_name("key_dir")
+                .long("key-dir")
+                .takes_value(true)
+                .conflicts_with("system")
+                .help(
+                    "Name of the directory in which to create the keys; defaults to \
                              $HOME/splinter/keys",
-                    ),
-            )
-            .arg(
-                Arg::with_name("force")
-                    .short("f")
-                    .long("force")
-                    .help("Overwrite files if they exist"),
-            )
-            .arg(
-                Arg::with_name("skip")
-                    .long("skip")
-                    .conflicts_with("force")
-                    .help("Skip generating the files if they exist"),
-            )
-            .arg(
-                Arg::with_name("system")
-                    .long("system")
-                    .help("Generate system keys in /etc/splinter/keys"),
-            )
-            .arg(Arg::with_name("group").long("group").help(
-                "Key file owning group, options are none|auto|<name_of_group>|<gid_of_group>",
-            )
-            .takes_value(true)),
-    );
+                ),
+        )
+        .arg(
+            Arg::with_name("force")
+                .short("f")
+                .long("force")
+                .help("Overwrite files if they exist"),
+        )
+        .arg(
+            Arg::with_name("skip")
+                .long("skip")
+                .conflicts_with("force")
+                .help("Skip generating the files if they exist"),
+        )
+        .arg(
+            Arg::with_name("system")
+                .long("system")
+                .help("Generate system keys in /etc/splinter/keys"),
+        )
+        .arg(
+            Arg::with_name("group")
+                .long("group")
+                .help("Key file owning group, options are none|auto|<name_of_group>|<gid_of_group>")
+                .takes_value(true),
+        );
+
+    #[cfg(feature = "encrypted-keys")]
+    let keygen_command = keygen_command.arg(Arg::with_name("encrypt").long("encrypt").help(
+        "Encrypt the private key with the passphrase from SPLINTER_KEY_PASSPHRASE_FILE or \
+         SPLINTER_KEY_PASSPHRASE",
+    ));
+
+    app = app.subcommand(keygen_command);
 
     let propose_circuit = SubCommand::with_name("propose")
         .about("Propose that a new circuit is created")
@@ -248,6 +255,38 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                 .takes_value(true)
                 .help("Add human-readable name for the circuit"),
         )
+        .arg(
+            Arg::with_name("expires_in")
+                .long("expires-in")
+                .takes_value(true)
+                .value_name("seconds")
+                .help("Remove the proposal if it has not been accepted after this many seconds"),
+        )
+        .arg(
+            Arg::with_name("voting_policy")
+                .long("voting-policy")
+                .takes_value(true)
+                .possible_values(&["unanimous", "majority", "threshold"])
+                .help("Policy used to decide proposals against the circuit (default: unanimous)"),
+        )
+        .arg(
+            Arg::with_name("vote_threshold")
+                .long("vote-threshold")
+                .takes_value(true)
+                .value_name("weight")
+                .required_if("voting_policy", "threshold")
+                .help("Vote weight required to accept a proposal with the threshold policy"),
+        )
+        .arg(
+            Arg::with_name("vote_weight")
+                .long("vote-weight")
+                .takes_value(true)
+                .multiple(true)
+                .help(
+                    "Weight of a member's vote; members default to 1 \
+                     (<node_id>::<weight>)",
+                ),
+        )
         .arg(
             Arg::with_name("compat_version")
                 .long("compat")
@@ -368,7 +407,7 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                     Arg::with_name("circuit_status")
                         .long("circuit-status")
                         .help("Filter circuits by a circuit status")
-                        .possible_values(&["active", "disbanded", "abandoned"])
+                        .possible_values(&["active", "disbanded", "abandoned", "suspended"])
                         .takes_value(true),
                 )
                 .arg(
@@ -521,6 +560,104 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
             ),
     );
 
+    let circuit_command = circuit_command.subcommand(
+        SubCommand::with_name("suspend")
+            .about("Propose to suspend an existing active circuit")
+            .arg(
+                Arg::with_name("url")
+                    .short("U")
+                    .long("url")
+                    .takes_value(true)
+                    .help("URL of Splinter Daemon"),
+            )
+            .arg(
+                Arg::with_name("private_key_file")
+                    .value_name("private-key-file")
+                    .short("k")
+                    .long("key")
+                    .takes_value(true)
+                    .help("Path to private key file"),
+            )
+            .arg(
+                Arg::with_name("circuit_id")
+                    .value_name("circuit-id")
+                    .takes_value(true)
+                    .required(true)
+                    .help("ID of the circuit to be suspended"),
+            ),
+    );
+
+    let circuit_command = circuit_command.subcommand(
+        SubCommand::with_name("resume")
+            .about("Propose to resume an existing suspended circuit")
+            .arg(
+                Arg::with_name("url")
+                    .short("U")
+                    .long("url")
+                    .takes_value(true)
+                    .help("URL of Splinter Daemon"),
+            )
+            .arg(
+                Arg::with_name("private_key_file")
+                    .value_name("private-key-file")
+                    .short("k")
+                    .long("key")
+                    .takes_value(true)
+                    .help("Path to private key file"),
+            )
+            .arg(
+                Arg::with_name("circuit_id")
+                    .value_name("circuit-id")
+                    .takes_value(true)
+                    .required(true)
+                    .help("ID of the circuit to be resumed"),
+            ),
+    );
+
+    let circuit_command = circuit_command.subcommand(
+        SubCommand::with_name("update-node-keys")
+            .about("Propose to update the local node's public key on an existing active circuit")
+            .arg(
+                Arg::with_name("url")
+                    .short("U")
+                    .long("url")
+                    .takes_value(true)
+                    .help("URL of Splinter Daemon"),
+            )
+            .arg(
+                Arg::with_name("private_key_file")
+                    .value_name("private-key-file")
+                    .short("k")
+                    .long("key")
+                    .takes_value(true)
+                    .help("Path to private key file"),
+            )
+            .arg(
+                Arg::with_name("public_key")
+                    .value_name("public-key")
+                    .long("public-key")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Hex-encoded public key the node will use on the circuit"),
+            )
+            .arg(
+                Arg::with_name("expires_in")
+                    .long("expires-in")
+                    .takes_value(true)
+                    .value_name("seconds")
+                    .help(
+                        "Remove the proposal if it has not been accepted after this many seconds",
+                    ),
+            )
+            .arg(
+                Arg::with_name("circuit_id")
+                    .value_name("circuit-id")
+                    .takes_value(true)
+                    .required(true)
+                    .help("ID of the circuit to update"),
+            ),
+    );
+
     let circuit_command = circuit_command.subcommand(
         SubCommand::with_name("purge")
             .about("Purge an existing inactive circuit")
@@ -905,6 +1042,43 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
             ),
     );
 
+    #[cfg(feature = "registry")]
+    let registry_command = registry_command.subcommand(
+        SubCommand::with_name("list")
+            .about("List the nodes in the registry and whether they are reachable")
+            .arg(
+                Arg::with_name("format")
+                    .short("F")
+                    .long("format")
+                    .help("Output format")
+                    .possible_values(&["human", "csv"])
+                    .default_value("human")
+                    .takes_value(true),
+            )
+            .arg(
+                Arg::with_name("private_key_file")
+                    .value_name("private-key-file")
+                    .short("k")
+                    .long("key")
+                    .takes_value(true)
+                    .help("Name or path of private key to be used for REST API authorization"),
+            )
+            .arg(
+                Arg::with_name("reachable")
+                    .long("reachable")
+                    .takes_value(true)
+                    .possible_values(&["yes", "no"])
+                    .help("Only list nodes that are (yes) or are not (no) currently reachable"),
+            )
+            .arg(
+                Arg::with_name("url")
+                    .short("U")
+                    .long("url")
+                    .takes_value(true)
+                    .help("URL of the splinter REST API"),
+            ),
+    );
+
     app = app.subcommand(registry_command);
 
     #[cfg(feature = "database")]
@@ -980,6 +1154,159 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                             the in database has a commit hash. The command will not \
                             attempt to move the state",
                         )),
+                )
+                .subcommand(
+                    SubCommand::with_name("prune")
+                        .about(
+                            "Prune the state roots that scabbard services no longer retain and \
+                            remove the pruned entries from the database",
+                        )
+                        .arg(
+                            Arg::with_name("circuit_id")
+                                .long("circuit-id")
+                                .takes_value(true)
+                                .requires("service_id")
+                                .help(
+                                    "ID of the circuit the scabbard service belongs to; \
+                                    defaults to all local scabbard services",
+                                ),
+                        )
+                        .arg(
+                            Arg::with_name("service_id")
+                                .long("service-id")
+                                .takes_value(true)
+                                .requires("circuit_id")
+                                .help("ID of the scabbard service"),
+                        )
+                        .arg(
+                            Arg::with_name("connect")
+                                .short("C")
+                                .takes_value(true)
+                                .help("Database connection URI"),
+                        )
+                        .arg(
+                            Arg::with_name("retained_roots")
+                                .long("retained-state-roots")
+                                .takes_value(true)
+                                .help(
+                                    "Number of committed state roots each scabbard service \
+                                    retains; defaults to 1",
+                                ),
+                        )
+                        .arg(Arg::with_name("dry_run").long("dry-run").long_help(
+                            "Report how many state roots of each service would be pruned. The \
+                            command will not prune any roots or remove any entries",
+                        )),
+                )
+                .subcommand(
+                    SubCommand::with_name("export")
+                        .about("Export a snapshot of a scabbard service's state")
+                        .arg(
+                            Arg::with_name("circuit_id")
+                                .long("circuit-id")
+                                .takes_value(true)
+                                .required(true)
+                                .help("ID of the circuit the scabbard service belongs to"),
+                        )
+                        .arg(
+                            Arg::with_name("service_id")
+                                .long("service-id")
+                                .takes_value(true)
+                                .required(true)
+                                .help("ID of the scabbard service"),
+                        )
+                        .arg(
+                            Arg::with_name("connect")
+                                .short("C")
+                                .takes_value(true)
+                                .help("Database connection URI"),
+                        )
+                        .arg(
+                            Arg::with_name("lmdb")
+                                .long("lmdb")
+                                .help("Read scabbard state from individual LMDB files"),
+                        )
+                        .arg(
+                            Arg::with_name("state_dir")
+                                .long("state-dir")
+                                .long_help(
+                                    "The location of the state directory for the LMDB files. \
+                                    Defaults to /var/lib/splinter. This location can also be \
+                                    changed with the SPLINTER_STATE_DIR or SPLINTER_HOME \
+                                    environment variables",
+                                )
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("output")
+                                .short("o")
+                                .long("output")
+                                .takes_value(true)
+                                .help("File to write the snapshot to; defaults to stdout"),
+                        ),
+                )
+                .subcommand(
+                    SubCommand::with_name("import")
+                        .about(
+                            "Import a snapshot of a scabbard service's state, after verifying \
+                            it against the expected state root",
+                        )
+                        .arg(
+                            Arg::with_name("circuit_id")
+                                .long("circuit-id")
+                                .takes_value(true)
+                                .required(true)
+                                .help("ID of the circuit the scabbard service belongs to"),
+                        )
+                        .arg(
+                            Arg::with_name("service_id")
+                                .long("service-id")
+                                .takes_value(true)
+                                .required(true)
+                                .help("ID of the scabbard service"),
+                        )
+                        .arg(
+                            Arg::with_name("expected_root")
+                                .long("expected-root")
+                                .takes_value(true)
+                                .required(true)
+                                .help("State root hash the snapshot must produce"),
+                        )
+                        .arg(
+                            Arg::with_name("connect")
+                                .short("C")
+                                .takes_value(true)
+                                .help("Database connection URI"),
+                        )
+                        .arg(
+                            Arg::with_name("lmdb")
+                                .long("lmdb")
+                                .help("Write scabbard state to individual LMDB files"),
+                        )
+                        .arg(
+                            Arg::with_name("state_dir")
+                                .long("state-dir")
+                                .long_help(
+                                    "The location of the state directory for the LMDB files. \
+                                    Defaults to /var/lib/splinter. This location can also be \
+                                    changed with the SPLINTER_STATE_DIR or SPLINTER_HOME \
+                                    environment variables",
+                                )
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("input")
+                                .short("i")
+                                .long("input")
+                                .takes_value(true)
+                                .help("File to read the snapshot from; defaults to stdin"),
+                        )
+                        .arg(
+                            Arg::with_name("yes")
+                                .short("y")
+                                .long("yes")
+                                .help("Do not prompt for confirmation"),
+                        ),
                 ),
         );
     }
@@ -1424,6 +1751,18 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                                 .required(true)
                                 .help("A role to be assigned to the provided identity"),
                         )
+                        .arg(
+                            Arg::with_name("scope")
+                                .value_name("scope")
+                                .long("scope")
+                                .takes_value(true)
+                                .multiple(true)
+                                .number_of_values(1)
+                                .help(
+                                    "Restrict the roles to a circuit, given as \
+                                    'circuit:<circuit-id>' or 'management-type:<type>'",
+                                ),
+                        )
                         .arg(
                             Arg::with_name("dry_run")
                                 .long("dry-run")
@@ -1503,6 +1842,41 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                                     identity",
                                 ),
                         )
+                        .arg(
+                            Arg::with_name("add_scope")
+                                .value_name("scope")
+                                .long("add-scope")
+                                .takes_value(true)
+                                .multiple(true)
+                                .number_of_values(1)
+                                .help(
+                                    "A circuit scope to be added to the provided identity's \
+                                    assignments, given as 'circuit:<circuit-id>' or \
+                                    'management-type:<type>'",
+                                ),
+                        )
+                        .arg(
+                            Arg::with_name("rm_scope")
+                                .value_name("scope")
+                                .long("rm-scope")
+                                .takes_value(true)
+                                .multiple(true)
+                                .number_of_values(1)
+                                .conflicts_with("rm_all_scopes")
+                                .help(
+                                    "A circuit scope to be removed from the provided identity's \
+                                    assignments"
+                                ),
+                        )
+                        .arg(
+                            Arg::with_name("rm_all_scopes")
+                                .long("rm-all-scopes")
+                                .conflicts_with("rm_scope")
+                                .help(
+                                    "Remove all of the scopes of the authorized identity, so that \
+                                    its roles apply to the whole node",
+                                ),
+                        )
                         .arg(
                             Arg::with_name("dry_run")
                                 .long("dry-run")
@@ -1558,6 +1932,150 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
         );
     }
 
+    #[cfg(feature = "audit-log")]
+    {
+        app = app.subcommand(
+            SubCommand::with_name("audit")
+                .about("Lists the REST API audit log for a Splinter node")
+                .arg(
+                    Arg::with_name("format")
+                        .short("F")
+                        .long("format")
+                        .help("Output format")
+                        .possible_values(&["human", "csv", "json"])
+                        .default_value("human")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("url")
+                        .short("U")
+                        .long("url")
+                        .help("URL of the Splinter daemon REST API")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("private_key_file")
+                        .value_name("private-key-file")
+                        .short("k")
+                        .long("key")
+                        .takes_value(true)
+                        .help("Name or path of private key"),
+                )
+                .arg(
+                    Arg::with_name("offset")
+                        .long("offset")
+                        .takes_value(true)
+                        .help("Number of entries to skip"),
+                )
+                .arg(
+                    Arg::with_name("limit")
+                        .long("limit")
+                        .takes_value(true)
+                        .help("Maximum number of entries to list (defaults to 100)"),
+                ),
+        );
+    }
+
+    #[cfg(feature = "peer")]
+    {
+        app = app.subcommand(
+            SubCommand::with_name("peer")
+                .about("Provides commands for inspecting the peers of a Splinter node")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(
+                    SubCommand::with_name("list")
+                        .about("Lists the peers of a Splinter node")
+                        .arg(
+                            Arg::with_name("format")
+                                .short("F")
+                                .long("format")
+                                .help("Output format")
+                                .possible_values(&["human", "csv", "json"])
+                                .default_value("human")
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("url")
+                                .short("U")
+                                .long("url")
+                                .help("URL of the Splinter daemon REST API")
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("private_key_file")
+                                .value_name("private-key-file")
+                                .short("k")
+                                .long("key")
+                                .takes_value(true)
+                                .help("Name or path of private key"),
+                        ),
+                )
+                .subcommand(
+                    SubCommand::with_name("show")
+                        .about("Shows a peer of a Splinter node")
+                        .arg(
+                            Arg::with_name("peer_id")
+                                .required(true)
+                                .takes_value(true)
+                                .value_name("PEER ID")
+                                .help("ID of the peer to be shown"),
+                        )
+                        .arg(
+                            Arg::with_name("format")
+                                .short("F")
+                                .long("format")
+                                .help("Output format")
+                                .possible_values(&["human", "json"])
+                                .default_value("human")
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("url")
+                                .short("U")
+                                .long("url")
+                                .help("URL of the Splinter daemon REST API")
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("private_key_file")
+                                .value_name("private-key-file")
+                                .short("k")
+                                .long("key")
+                                .takes_value(true)
+                                .help("Name or path of private key"),
+                        ),
+                )
+                .subcommand(
+                    SubCommand::with_name("retries")
+                        .about("Lists the retry state of the peers of a Splinter node")
+                        .arg(
+                            Arg::with_name("format")
+                                .short("F")
+                                .long("format")
+                                .help("Output format")
+                                .possible_values(&["human", "csv", "json"])
+                                .default_value("human")
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("url")
+                                .short("U")
+                                .long("url")
+                                .help("URL of the Splinter daemon REST API")
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("private_key_file")
+                                .value_name("private-key-file")
+                                .short("k")
+                                .long("key")
+                                .takes_value(true)
+                                .help("Name or path of private key"),
+                        ),
+                ),
+        );
+    }
+
     app = app.subcommand(
         SubCommand::with_name("permissions")
             .about("Lists REST API permissions for a Splinter node")
@@ -2040,6 +2558,9 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
         .with_command("show", circuit::CircuitShowAction)
         .with_command("proposals", circuit::CircuitProposalsAction)
         .with_command("disband", circuit::CircuitDisbandAction)
+        .with_command("suspend", circuit::CircuitSuspendAction)
+        .with_command("resume", circuit::CircuitResumeAction)
+        .with_command("update-node-keys", circuit::CircuitUpdateNodeKeysAction)
         .with_command("abandon", circuit::CircuitAbandonAction)
         .with_command("purge", circuit::CircuitPurgeAction);
 
@@ -2061,7 +2582,9 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
         SubcommandActions::new().with_command("build", registry::RegistryGenerateAction);
 
     #[cfg(feature = "registry")]
-    let registry_command = registry_command.with_command("add", registry::RegistryAddAction);
+    let registry_command = registry_command
+        .with_command("add", registry::RegistryAddAction)
+        .with_command("list", registry::RegistryListAction);
 
     subcommands = subcommands.with_command("registry", registry_command);
 
@@ -2075,7 +2598,11 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
 
         subcommands = subcommands.with_command(
             "state",
-            SubcommandActions::new().with_command("migrate", database::StateMigrateAction),
+            SubcommandActions::new()
+                .with_command("migrate", database::StateMigrateAction)
+                .with_command("prune", database::StatePruneAction)
+                .with_command("export", database::StateExportAction)
+                .with_command("import", database::StateImportAction),
         );
     }
 
@@ -2120,6 +2647,24 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
             )
     }
 
+    #[cfg(feature = "audit-log")]
+    {
+        use action::audit;
+        subcommands = subcommands.with_command("audit", audit::ListAction);
+    }
+
+    #[cfg(feature = "peer")]
+    {
+        use action::peer;
+        subcommands = subcommands.with_command(
+            "peer",
+            SubcommandActions::new()
+                .with_command("list", peer::ListPeersAction)
+                .with_command("show", peer::ShowPeerAction)
+                .with_command("retries", peer::ListRetriesAction),
+        );
+    }
+
     subcommands = subcommands.with_command("permissions", permissions::ListAction);
 
     #[cfg(feature = "user")]
@@ -14,10 +14,14 @@
 
 use std::{env, path::Path, path::PathBuf};
 
+#[cfg(not(feature = "encrypted-keys"))]
+use cylinder::load_key_from_path;
 use cylinder::{
-    current_user_key_name, current_user_search_path, jwt::JsonWebTokenBuilder, load_key,
-    load_key_from_path, secp256k1::Secp256k1Context, Context, PrivateKey, Signer,
+    current_user_key_name, current_user_search_path, jwt::JsonWebTokenBuilder,
+    secp256k1::Secp256k1Context, Context, PrivateKey, Signer,
 };
+#[cfg(feature = "encrypted-keys")]
+use splinter::keys::{encrypted, EncryptedKeyError};
 
 use crate::error::CliError;
 
@@ -40,15 +44,57 @@ fn splinter_user_search_path() -> Vec<PathBuf> {
     }
 }
 
+// Returns the path of the first private key file with the given name in the search path.
+fn find_key(key_name: &str, paths: &[PathBuf]) -> Option<PathBuf> {
+    paths
+        .iter()
+        .map(|path| path.join(format!("{}.priv", key_name)))
+        .find(|path| path.is_file())
+}
+
+/// Returns the passphrase used to decrypt private key files.
+///
+/// The passphrase is read from the file named by `SPLINTER_KEY_PASSPHRASE_FILE` if it is set,
+/// otherwise from `SPLINTER_KEY_PASSPHRASE`.
+#[cfg(feature = "encrypted-keys")]
+pub fn load_key_passphrase() -> Result<Option<Vec<u8>>, CliError> {
+    encrypted::load_key_passphrase(None)
+        .map_err(|err| CliError::EnvironmentError(format!("Unable to load passphrase: {}", err)))
+}
+
+#[cfg(feature = "encrypted-keys")]
+fn load_key_file(path: &Path) -> Result<PrivateKey, CliError> {
+    let passphrase = load_key_passphrase()?;
+
+    encrypted::load_private_key(path, passphrase.as_deref()).map_err(|err| match err {
+        EncryptedKeyError::PassphraseRequired => CliError::EnvironmentError(format!(
+            "Private key file '{}' is encrypted; set {} or {} to decrypt it",
+            path.display(),
+            encrypted::KEY_PASSPHRASE_ENV,
+            encrypted::KEY_PASSPHRASE_FILE_ENV
+        )),
+        err => CliError::ActionError(format!(
+            "Unable to load private key file '{}': {}",
+            path.display(),
+            err
+        )),
+    })
+}
+
+#[cfg(not(feature = "encrypted-keys"))]
+fn load_key_file(path: &Path) -> Result<PrivateKey, CliError> {
+    load_key_from_path(path).map_err(|err| CliError::ActionError(err.to_string()))
+}
+
 fn load_private_key(key_name: Option<&str>) -> Result<PrivateKey, CliError> {
     let private_key = if let Some(key_name) = key_name {
         if key_name.contains('/') {
-            load_key_from_path(Path::new(key_name))
-                .map_err(|err| CliError::ActionError(err.to_string()))?
+            load_key_file(Path::new(key_name))?
         } else {
             let path = splinter_user_search_path();
-            load_key(key_name, &path)
-                .map_err(|err| CliError::ActionError(err.to_string()))?
+            find_key(key_name, &path)
+                .map(|key_path| load_key_file(&key_path))
+                .transpose()?
                 .ok_or_else(|| {
                     CliError::ActionError({
                         format!(
@@ -64,8 +110,9 @@ fn load_private_key(key_name: Option<&str>) -> Result<PrivateKey, CliError> {
         }
     } else {
         let path = splinter_user_search_path();
-        load_key(&current_user_key_name(), &path)
-            .map_err(|err| CliError::ActionError(err.to_string()))?
+        find_key(&current_user_key_name(), &path)
+            .map(|key_path| load_key_file(&key_path))
+            .transpose()?
             .ok_or_else(|| {
                 CliError::ActionError({
                     format!(
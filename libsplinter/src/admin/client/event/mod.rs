@@ -63,6 +63,9 @@ pub enum EventType {
     ProposalRejected { requester: PublicKey },
     CircuitReady,
     CircuitDisbanded,
+    ProposalExpired,
+    CircuitSuspended,
+    CircuitResumed,
 }
 
 impl AdminServiceEvent {
@@ -90,6 +93,9 @@ pub enum EventQuery<'a> {
     ProposalRejected { circuit_id: &'a str, key: PublicKey },
     CircuitReady { circuit_id: &'a str },
     CircuitDisbanded { circuit_id: &'a str },
+    ProposalExpired { circuit_id: &'a str },
+    CircuitSuspended { circuit_id: &'a str },
+    CircuitResumed { circuit_id: &'a str },
 }
 
 impl<'a> EventQuery<'a> {
@@ -126,6 +132,18 @@ impl<'a> EventQuery<'a> {
                 event.event_type() == &EventType::CircuitDisbanded
                     && &event.proposal().circuit_id == circuit_id
             }
+            EventQuery::ProposalExpired { circuit_id } => {
+                event.event_type() == &EventType::ProposalExpired
+                    && &event.proposal().circuit_id == circuit_id
+            }
+            EventQuery::CircuitSuspended { circuit_id } => {
+                event.event_type() == &EventType::CircuitSuspended
+                    && &event.proposal().circuit_id == circuit_id
+            }
+            EventQuery::CircuitResumed { circuit_id } => {
+                event.event_type() == &EventType::CircuitResumed
+                    && &event.proposal().circuit_id == circuit_id
+            }
         }
     }
 }
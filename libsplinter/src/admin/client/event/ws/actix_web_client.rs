@@ -334,6 +334,9 @@ impl ParseBytes<AdminServiceEvent> for AdminServiceEvent {
             ),
             CircuitReady(proposal) => (proposal, EventType::CircuitReady),
             CircuitDisbanded(proposal) => (proposal, EventType::CircuitDisbanded),
+            ProposalExpired(proposal) => (proposal, EventType::ProposalExpired),
+            CircuitSuspended(proposal) => (proposal, EventType::CircuitSuspended),
+            CircuitResumed(proposal) => (proposal, EventType::CircuitResumed),
         };
 
         Ok(AdminServiceEvent {
@@ -361,6 +364,9 @@ impl From<messages::CircuitProposal> for ProposalSlice {
             AddNode => "AddNode",
             RemoveNode => "RemoveNode",
             Disband => "Disband",
+            Suspend => "Suspend",
+            Resume => "Resume",
+            UpdateNodeKeys => "UpdateNodeKeys",
         }
         .to_owned();
 
@@ -372,6 +378,7 @@ impl From<messages::CircuitProposal> for ProposalSlice {
             votes: proposal.votes.into_iter().map(VoteRecord::from).collect(),
             requester: hex::to_hex(&proposal.requester),
             requester_node_id: proposal.requester_node_id,
+            expires_at: proposal.expires_at,
         }
     }
 }
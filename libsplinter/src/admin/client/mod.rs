@@ -105,6 +105,8 @@ pub struct ProposalSlice {
     pub votes: Vec<VoteRecord>,
     pub requester: String,
     pub requester_node_id: String,
+    #[serde(default)]
+    pub expires_at: Option<u64>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
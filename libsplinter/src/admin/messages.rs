@@ -15,8 +15,8 @@
 pub use super::service::messages::v1;
 
 pub use super::service::messages::{
-    is_valid_circuit_id, is_valid_service_id, AdminServiceEvent, AuthorizationType, BuilderError,
-    CircuitProposal, CircuitProposalVote, CircuitStatus, CreateCircuit, CreateCircuitBuilder,
-    DurabilityType, PersistenceType, ProposalType, RouteType, SplinterNode, SplinterNodeBuilder,
-    SplinterService, SplinterServiceBuilder, Vote, VoteRecord,
+    circuit_management_action, is_valid_circuit_id, is_valid_service_id, AdminServiceEvent,
+    AuthorizationType, BuilderError, CircuitProposal, CircuitProposalVote, CircuitStatus,
+    CreateCircuit, CreateCircuitBuilder, DurabilityType, PersistenceType, ProposalType, RouteType,
+    SplinterNode, SplinterNodeBuilder, SplinterService, SplinterServiceBuilder, Vote, VoteRecord,
 };
@@ -22,10 +22,14 @@ use cylinder::Verifier as SignatureVerifier;
 
 use crate::admin::lifecycle::LifecycleDispatch;
 use crate::admin::store::AdminServiceStore;
+#[cfg(feature = "circuit-rate-limiting")]
+use crate::circuit::rate_limit::CircuitRateLimiter;
 use crate::circuit::routing::RoutingTableWriter;
 use crate::error::InvalidStateError;
 use crate::keys::KeyPermissionManager;
 use crate::peer::PeerManagerConnector;
+#[cfg(feature = "peer-retry-policy")]
+use crate::peer::RetryPolicy;
 use crate::public_key::PublicKey;
 use crate::service::instance::ServiceArgValidator;
 
@@ -53,6 +57,10 @@ pub struct AdminServiceBuilder {
     routing_table_writer: Option<Box<dyn RoutingTableWriter>>,
     event_store: Option<Box<dyn AdminServiceStore>>,
     public_keys: Option<Vec<PublicKey>>,
+    #[cfg(feature = "circuit-rate-limiting")]
+    rate_limiter: Option<CircuitRateLimiter>,
+    #[cfg(feature = "peer-retry-policy")]
+    peer_retry_policies: HashMap<String, RetryPolicy>,
 }
 
 impl AdminServiceBuilder {
@@ -158,6 +166,27 @@ impl AdminServiceBuilder {
         self
     }
 
+    /// Sets the rate limiter used by the circuit message handlers, so that its state for a
+    /// circuit is removed when the circuit is disbanded or abandoned.
+    #[cfg(feature = "circuit-rate-limiting")]
+    pub fn with_rate_limiter(mut self, rate_limiter: CircuitRateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+
+        self
+    }
+
+    /// Sets the retry policies used when peering with circuit members, by node ID. Members
+    /// without a policy are retried using the defaults of the peer manager.
+    #[cfg(feature = "peer-retry-policy")]
+    pub fn with_peer_retry_policies(
+        mut self,
+        peer_retry_policies: HashMap<String, RetryPolicy>,
+    ) -> Self {
+        self.peer_retry_policies = peer_retry_policies;
+
+        self
+    }
+
     /// Constructs the AdminService.
     ///
     /// # Errors
@@ -219,7 +248,8 @@ impl AdminServiceBuilder {
 
         let public_keys = self.public_keys.unwrap_or_default();
 
-        let admin_service_shared = Arc::new(Mutex::new(AdminServiceShared::new(
+        #[allow(unused_mut)]
+        let mut admin_service_shared = AdminServiceShared::new(
             node_id.clone(),
             lifecycle_dispatch,
             service_arg_validators,
@@ -231,7 +261,12 @@ impl AdminServiceBuilder {
             routing_table_writer,
             admin_event_store,
             public_keys,
-        )));
+        );
+        #[cfg(feature = "circuit-rate-limiting")]
+        admin_service_shared.set_rate_limiter(self.rate_limiter);
+        #[cfg(feature = "peer-retry-policy")]
+        admin_service_shared.set_peer_retry_policies(self.peer_retry_policies);
+        let admin_service_shared = Arc::new(Mutex::new(admin_service_shared));
 
         Ok(AdminService {
             service_id,
@@ -241,6 +276,7 @@ impl AdminServiceBuilder {
             consensus: None,
             peer_connector,
             peer_notification_run_state: None,
+            proposal_expiry_run_state: None,
             admin_store,
         })
     }
@@ -184,11 +184,12 @@ impl ProposalManager for AdminProposalManager {
             };
 
             let mut required_verifiers = RequiredVerifiers::new();
-            let mut verifiers = vec![];
-            let members = circuit_proposal.get_circuit_proposal().get_members();
-            for member in members {
-                verifiers.push(admin_service_id(member.get_node_id()).as_bytes().to_vec());
-            }
+            let verifiers = shared
+                .required_verifiers(&circuit_proposal)
+                .map_err(|err| ProposalManagerError::Internal(Box::new(err)))?
+                .iter()
+                .map(|node_id| admin_service_id(node_id).as_bytes().to_vec())
+                .collect();
             required_verifiers.set_verifiers(RepeatedField::from_vec(verifiers));
             let required_verifiers_bytes = required_verifiers
                 .write_to_bytes()
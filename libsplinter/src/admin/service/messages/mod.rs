@@ -15,6 +15,13 @@
 pub mod v1;
 pub mod v2;
 
+use protobuf::Message;
+
+use crate::error::InvalidArgumentError;
+use crate::protos::admin::{
+    CircuitManagementPayload, CircuitManagementPayload_Action, CircuitManagementPayload_Header,
+};
+
 pub use self::v2::builders::{
     BuilderError, CreateCircuitBuilder, SplinterNodeBuilder, SplinterServiceBuilder,
 };
@@ -22,4 +29,77 @@ pub use self::v2::{
     is_valid_circuit_id, is_valid_service_id, AdminServiceEvent, AuthorizationType,
     CircuitProposal, CircuitProposalVote, CircuitStatus, CreateCircuit, DurabilityType,
     PersistenceType, ProposalType, RouteType, SplinterNode, SplinterService, Vote, VoteRecord,
+    VotingPolicy, VotingPolicyType,
 };
+
+/// Returns the action of a circuit management payload, as specified by its header, and the ID of
+/// the circuit that the action concerns.
+///
+/// # Errors
+///
+/// Returns an [`InvalidArgumentError`] if the payload's header cannot be parsed or does not
+/// specify an action.
+pub fn circuit_management_action(
+    payload: &CircuitManagementPayload,
+) -> Result<(CircuitManagementPayload_Action, &str), InvalidArgumentError> {
+    let header: CircuitManagementPayload_Header = Message::parse_from_bytes(payload.get_header())
+        .map_err(|err| {
+        InvalidArgumentError::new("payload", format!("invalid header: {}", err))
+    })?;
+
+    let action = header.get_action();
+    let circuit_id = match action {
+        CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST => payload
+            .get_circuit_create_request()
+            .get_circuit()
+            .get_circuit_id(),
+        CircuitManagementPayload_Action::CIRCUIT_JOIN_REQUEST => payload
+            .get_circuit_join_request()
+            .get_circuit()
+            .get_circuit_id(),
+        CircuitManagementPayload_Action::CIRCUIT_PROPOSAL_VOTE => {
+            payload.get_circuit_proposal_vote().get_circuit_id()
+        }
+        CircuitManagementPayload_Action::CIRCUIT_UPDATE_ROSTER_REQUEST => {
+            payload.get_circuit_update_roster_request().get_circuit_id()
+        }
+        CircuitManagementPayload_Action::CIRCUIT_UPDATE_ADD_NODE => {
+            payload.get_circuit_update_add_node().get_circuit_id()
+        }
+        CircuitManagementPayload_Action::CIRCUIT_UPDATE_REMOVE_NODE => {
+            payload.get_circuit_update_remove_node().get_circuit_id()
+        }
+        CircuitManagementPayload_Action::CIRCUIT_UPDATE_APPLICATION_METADATA_REQUEST => payload
+            .get_circuit_update_application_metadata_request()
+            .get_circuit_id(),
+        CircuitManagementPayload_Action::CIRCUIT_DISBAND_REQUEST => {
+            payload.get_circuit_disband_request().get_circuit_id()
+        }
+        CircuitManagementPayload_Action::CIRCUIT_PURGE_REQUEST => {
+            payload.get_circuit_purge_request().get_circuit_id()
+        }
+        CircuitManagementPayload_Action::CIRCUIT_ABANDON => {
+            payload.get_circuit_abandon().get_circuit_id()
+        }
+        CircuitManagementPayload_Action::PROPOSAL_REMOVE_REQUEST => {
+            payload.get_proposal_remove_request().get_circuit_id()
+        }
+        CircuitManagementPayload_Action::CIRCUIT_SUSPEND_REQUEST => {
+            payload.get_circuit_suspend_request().get_circuit_id()
+        }
+        CircuitManagementPayload_Action::CIRCUIT_RESUME_REQUEST => {
+            payload.get_circuit_resume_request().get_circuit_id()
+        }
+        CircuitManagementPayload_Action::CIRCUIT_UPDATE_NODE_KEYS_REQUEST => payload
+            .get_circuit_update_node_keys_request()
+            .get_circuit_id(),
+        CircuitManagementPayload_Action::ACTION_UNSET => {
+            return Err(InvalidArgumentError::new(
+                "payload",
+                "header does not specify an action",
+            ))
+        }
+    };
+
+    Ok((action, circuit_id))
+}
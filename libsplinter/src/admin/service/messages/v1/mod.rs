@@ -71,6 +71,9 @@ impl CreateCircuit {
 
         let durability = match proto.get_durability() {
             admin::Circuit_DurabilityType::NO_DURABILITY => DurabilityType::NoDurability,
+            admin::Circuit_DurabilityType::GUARANTEED_DELIVERY => {
+                DurabilityType::GuaranteedDelivery
+            }
             admin::Circuit_DurabilityType::UNSET_DURABILITY_TYPE => {
                 return Err(MarshallingError::UnsetField(
                     "Unset durability type".to_string(),
@@ -80,11 +83,22 @@ impl CreateCircuit {
 
         let routes = match proto.get_routes() {
             admin::Circuit_RouteType::ANY_ROUTE => RouteType::Any,
+            admin::Circuit_RouteType::RELAY_ROUTE => {
+                return Err(MarshallingError::UnsetField(
+                    "Relay route type is not supported".to_string(),
+                ));
+            }
             admin::Circuit_RouteType::UNSET_ROUTE_TYPE => {
                 return Err(MarshallingError::UnsetField("Unset route type".to_string()));
             }
         };
 
+        if proto.has_voting_policy() {
+            return Err(MarshallingError::UnsetField(
+                "Voting policies are not supported".to_string(),
+            ));
+        }
+
         Ok(Self {
             circuit_id: proto.take_circuit_id(),
             roster: proto
@@ -144,6 +158,9 @@ impl CreateCircuit {
             DurabilityType::NoDurability => {
                 circuit.set_durability(admin::Circuit_DurabilityType::NO_DURABILITY);
             }
+            DurabilityType::GuaranteedDelivery => {
+                circuit.set_durability(admin::Circuit_DurabilityType::GUARANTEED_DELIVERY);
+            }
         };
 
         match self.routes {
@@ -189,6 +206,7 @@ impl Default for PersistenceType {
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
 pub enum DurabilityType {
     NoDurability,
+    GuaranteedDelivery,
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
@@ -302,6 +320,13 @@ impl CircuitProposal {
             admin::CircuitProposal_ProposalType::ADD_NODE => ProposalType::AddNode,
             admin::CircuitProposal_ProposalType::REMOVE_NODE => ProposalType::RemoveNode,
             admin::CircuitProposal_ProposalType::DISBAND => ProposalType::Destroy,
+            admin::CircuitProposal_ProposalType::SUSPEND
+            | admin::CircuitProposal_ProposalType::RESUME
+            | admin::CircuitProposal_ProposalType::UPDATE_NODE_KEYS => {
+                return Err(MarshallingError::UnsetField(
+                    "Unsupported proposal type".to_string(),
+                ));
+            }
             admin::CircuitProposal_ProposalType::UNSET_PROPOSAL_TYPE => {
                 return Err(MarshallingError::UnsetField(
                     "Unset proposal type".to_string(),
@@ -488,7 +513,10 @@ impl TryFrom<&store::AdminServiceEvent> for AdminServiceEvent {
                 AdminServiceEvent::ProposalRejected((admin_proposal, requester.to_vec()))
             }
             EventType::CircuitReady => AdminServiceEvent::CircuitReady(admin_proposal),
-            EventType::CircuitDisbanded => {
+            EventType::CircuitDisbanded
+            | EventType::ProposalExpired
+            | EventType::CircuitSuspended
+            | EventType::CircuitResumed => {
                 return Err(MarshallingError::UnsetField(
                     "Unsupported proposal type".to_string(),
                 ))
@@ -18,7 +18,7 @@ use crate::base62::generate_random_base62_string;
 
 use super::{
     is_valid_circuit_id, is_valid_service_id, AuthorizationType, CircuitStatus, CreateCircuit,
-    DurabilityType, PersistenceType, RouteType, SplinterNode, SplinterService,
+    DurabilityType, PersistenceType, RouteType, SplinterNode, SplinterService, VotingPolicy,
     UNSET_CIRCUIT_VERSION,
 };
 
@@ -31,6 +31,8 @@ pub struct CreateCircuitBuilder {
     persistence: Option<PersistenceType>,
     durability: Option<DurabilityType>,
     routes: Option<RouteType>,
+    relay_nodes: Option<Vec<String>>,
+    voting_policy: Option<VotingPolicy>,
     circuit_management_type: Option<String>,
     application_metadata: Option<Vec<u8>>,
     comments: Option<String>,
@@ -72,6 +74,14 @@ impl CreateCircuitBuilder {
         self.routes.clone()
     }
 
+    pub fn relay_nodes(&self) -> Option<Vec<String>> {
+        self.relay_nodes.clone()
+    }
+
+    pub fn voting_policy(&self) -> Option<VotingPolicy> {
+        self.voting_policy.clone()
+    }
+
     pub fn circuit_management_type(&self) -> Option<String> {
         self.circuit_management_type.clone()
     }
@@ -134,6 +144,16 @@ impl CreateCircuitBuilder {
         self
     }
 
+    pub fn with_relay_nodes(mut self, relay_nodes: &[String]) -> CreateCircuitBuilder {
+        self.relay_nodes = Some(relay_nodes.into());
+        self
+    }
+
+    pub fn with_voting_policy(mut self, voting_policy: &VotingPolicy) -> CreateCircuitBuilder {
+        self.voting_policy = Some(voting_policy.clone());
+        self
+    }
+
     pub fn with_circuit_management_type(
         mut self,
         circuit_management_type: &str,
@@ -223,6 +243,10 @@ impl CreateCircuitBuilder {
             RouteType::default()
         });
 
+        let relay_nodes = self.relay_nodes.unwrap_or_default();
+
+        let voting_policy = self.voting_policy.unwrap_or_default();
+
         let circuit_management_type = self
             .circuit_management_type
             .ok_or_else(|| BuilderError::MissingField("circuit_management_type".to_string()))?;
@@ -245,6 +269,8 @@ impl CreateCircuitBuilder {
             persistence,
             durability,
             routes,
+            relay_nodes,
+            voting_policy,
             circuit_management_type,
             application_metadata,
             comments,
@@ -15,6 +15,7 @@
 pub mod builders;
 
 use protobuf::{self, RepeatedField};
+use std::collections::BTreeMap;
 use std::convert::TryInto;
 
 use crate::admin::error::MarshallingError;
@@ -36,6 +37,10 @@ pub struct CreateCircuit {
     pub durability: DurabilityType,
     #[serde(default)]
     pub routes: RouteType,
+    #[serde(default)]
+    pub relay_nodes: Vec<String>,
+    #[serde(default)]
+    pub voting_policy: VotingPolicy,
     pub circuit_management_type: String,
     #[serde(serialize_with = "as_hex")]
     #[serde(deserialize_with = "deserialize_hex")]
@@ -74,6 +79,9 @@ impl CreateCircuit {
 
         let durability = match proto.get_durability() {
             admin::Circuit_DurabilityType::NO_DURABILITY => DurabilityType::NoDurability,
+            admin::Circuit_DurabilityType::GUARANTEED_DELIVERY => {
+                DurabilityType::GuaranteedDelivery
+            }
             admin::Circuit_DurabilityType::UNSET_DURABILITY_TYPE => {
                 return Err(MarshallingError::UnsetField(
                     "Unset durability type".to_string(),
@@ -83,6 +91,7 @@ impl CreateCircuit {
 
         let routes = match proto.get_routes() {
             admin::Circuit_RouteType::ANY_ROUTE => RouteType::Any,
+            admin::Circuit_RouteType::RELAY_ROUTE => RouteType::Relay,
             admin::Circuit_RouteType::UNSET_ROUTE_TYPE => {
                 return Err(MarshallingError::UnsetField("Unset route type".to_string()));
             }
@@ -92,6 +101,7 @@ impl CreateCircuit {
             admin::Circuit_CircuitStatus::ACTIVE => CircuitStatus::Active,
             admin::Circuit_CircuitStatus::DISBANDED => CircuitStatus::Disbanded,
             admin::Circuit_CircuitStatus::ABANDONED => CircuitStatus::Abandoned,
+            admin::Circuit_CircuitStatus::SUSPENDED => CircuitStatus::Suspended,
             admin::Circuit_CircuitStatus::UNSET_CIRCUIT_STATUS => {
                 debug!("Defaulting `UNSET_CIRCUIT_STATUS` of proposed circuit to `Active`");
                 CircuitStatus::Active
@@ -132,6 +142,8 @@ impl CreateCircuit {
             persistence,
             durability,
             routes,
+            relay_nodes: proto.take_relay_nodes().into_vec(),
+            voting_policy: VotingPolicy::from_proto(proto.take_voting_policy()),
             circuit_management_type: proto.take_circuit_management_type(),
             application_metadata: proto.take_application_metadata(),
             comments,
@@ -194,11 +206,19 @@ impl CreateCircuit {
             DurabilityType::NoDurability => {
                 circuit.set_durability(admin::Circuit_DurabilityType::NO_DURABILITY);
             }
+            DurabilityType::GuaranteedDelivery => {
+                circuit.set_durability(admin::Circuit_DurabilityType::GUARANTEED_DELIVERY);
+            }
         };
 
         match self.routes {
             RouteType::Any => circuit.set_routes(admin::Circuit_RouteType::ANY_ROUTE),
+            RouteType::Relay => circuit.set_routes(admin::Circuit_RouteType::RELAY_ROUTE),
         };
+        circuit.set_relay_nodes(RepeatedField::from_vec(self.relay_nodes));
+        if self.voting_policy != VotingPolicy::default() {
+            circuit.set_voting_policy(self.voting_policy.into_proto());
+        }
 
         if self.circuit_version > UNSET_CIRCUIT_VERSION {
             match self.circuit_status {
@@ -211,6 +231,9 @@ impl CreateCircuit {
                 CircuitStatus::Abandoned => {
                     circuit.set_circuit_status(admin::Circuit_CircuitStatus::ABANDONED);
                 }
+                CircuitStatus::Suspended => {
+                    circuit.set_circuit_status(admin::Circuit_CircuitStatus::SUSPENDED);
+                }
             };
         }
 
@@ -277,11 +300,19 @@ impl TryInto<admin::Circuit> for CreateCircuit {
             DurabilityType::NoDurability => {
                 circuit.set_durability(admin::Circuit_DurabilityType::NO_DURABILITY);
             }
+            DurabilityType::GuaranteedDelivery => {
+                circuit.set_durability(admin::Circuit_DurabilityType::GUARANTEED_DELIVERY);
+            }
         };
 
         match self.routes {
             RouteType::Any => circuit.set_routes(admin::Circuit_RouteType::ANY_ROUTE),
+            RouteType::Relay => circuit.set_routes(admin::Circuit_RouteType::RELAY_ROUTE),
         };
+        circuit.set_relay_nodes(RepeatedField::from_vec(self.relay_nodes));
+        if self.voting_policy != VotingPolicy::default() {
+            circuit.set_voting_policy(self.voting_policy.into_proto());
+        }
 
         match self.circuit_status {
             CircuitStatus::Active => {
@@ -293,6 +324,9 @@ impl TryInto<admin::Circuit> for CreateCircuit {
             CircuitStatus::Abandoned => {
                 circuit.set_circuit_status(admin::Circuit_CircuitStatus::ABANDONED);
             }
+            CircuitStatus::Suspended => {
+                circuit.set_circuit_status(admin::Circuit_CircuitStatus::SUSPENDED);
+            }
         };
 
         Ok(circuit)
@@ -349,12 +383,14 @@ impl From<&store::PersistenceType> for PersistenceType {
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
 pub enum DurabilityType {
     NoDurability,
+    GuaranteedDelivery,
 }
 
 impl From<&store::DurabilityType> for DurabilityType {
     fn from(store_enum: &store::DurabilityType) -> Self {
         match *store_enum {
             store::DurabilityType::NoDurability => DurabilityType::NoDurability,
+            store::DurabilityType::GuaranteedDelivery => DurabilityType::GuaranteedDelivery,
         }
     }
 }
@@ -362,6 +398,7 @@ impl From<&store::DurabilityType> for DurabilityType {
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
 pub enum RouteType {
     Any,
+    Relay,
 }
 
 impl Default for RouteType {
@@ -374,6 +411,91 @@ impl From<&store::RouteType> for RouteType {
     fn from(store_enum: &store::RouteType) -> Self {
         match *store_enum {
             store::RouteType::Any => RouteType::Any,
+            store::RouteType::Relay => RouteType::Relay,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub enum VotingPolicyType {
+    Unanimous,
+    Majority,
+    Threshold(u32),
+}
+
+impl Default for VotingPolicyType {
+    fn default() -> Self {
+        VotingPolicyType::Unanimous
+    }
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct VotingPolicy {
+    pub policy_type: VotingPolicyType,
+    #[serde(default)]
+    pub weights: BTreeMap<String, u32>,
+}
+
+impl VotingPolicy {
+    pub fn from_proto(mut proto: admin::Circuit_VotingPolicy) -> Self {
+        let policy_type = match proto.get_policy_type() {
+            admin::Circuit_VotingPolicy_PolicyType::UNANIMOUS => VotingPolicyType::Unanimous,
+            admin::Circuit_VotingPolicy_PolicyType::MAJORITY => VotingPolicyType::Majority,
+            admin::Circuit_VotingPolicy_PolicyType::THRESHOLD => {
+                VotingPolicyType::Threshold(proto.get_threshold())
+            }
+        };
+
+        Self {
+            policy_type,
+            weights: proto
+                .take_weights()
+                .into_iter()
+                .map(|mut node_weight| (node_weight.take_node_id(), node_weight.get_weight()))
+                .collect(),
+        }
+    }
+
+    pub fn into_proto(self) -> admin::Circuit_VotingPolicy {
+        let mut proto = admin::Circuit_VotingPolicy::new();
+        match self.policy_type {
+            VotingPolicyType::Unanimous => {
+                proto.set_policy_type(admin::Circuit_VotingPolicy_PolicyType::UNANIMOUS)
+            }
+            VotingPolicyType::Majority => {
+                proto.set_policy_type(admin::Circuit_VotingPolicy_PolicyType::MAJORITY)
+            }
+            VotingPolicyType::Threshold(threshold) => {
+                proto.set_policy_type(admin::Circuit_VotingPolicy_PolicyType::THRESHOLD);
+                proto.set_threshold(threshold);
+            }
+        }
+        proto.set_weights(RepeatedField::from_vec(
+            self.weights
+                .into_iter()
+                .map(|(node_id, weight)| {
+                    let mut node_weight = admin::Circuit_VotingPolicy_NodeWeight::new();
+                    node_weight.set_node_id(node_id);
+                    node_weight.set_weight(weight);
+                    node_weight
+                })
+                .collect(),
+        ));
+        proto
+    }
+}
+
+impl From<&store::VotingPolicy> for VotingPolicy {
+    fn from(store_policy: &store::VotingPolicy) -> Self {
+        let policy_type = match *store_policy.policy_type() {
+            store::VotingPolicyType::Unanimous => VotingPolicyType::Unanimous,
+            store::VotingPolicyType::Majority => VotingPolicyType::Majority,
+            store::VotingPolicyType::Threshold(threshold) => VotingPolicyType::Threshold(threshold),
+        };
+
+        Self {
+            policy_type,
+            weights: store_policy.weights().clone(),
         }
     }
 }
@@ -383,6 +505,7 @@ pub enum CircuitStatus {
     Active,
     Disbanded,
     Abandoned,
+    Suspended,
 }
 
 impl Default for CircuitStatus {
@@ -397,6 +520,7 @@ impl From<&store::CircuitStatus> for CircuitStatus {
             store::CircuitStatus::Active => CircuitStatus::Active,
             store::CircuitStatus::Disbanded => CircuitStatus::Disbanded,
             store::CircuitStatus::Abandoned => CircuitStatus::Abandoned,
+            store::CircuitStatus::Suspended => CircuitStatus::Suspended,
         }
     }
 }
@@ -407,6 +531,7 @@ impl std::fmt::Display for CircuitStatus {
             CircuitStatus::Active => f.write_str("Active"),
             CircuitStatus::Disbanded => f.write_str("Disbanded"),
             CircuitStatus::Abandoned => f.write_str("Abandoned"),
+            CircuitStatus::Suspended => f.write_str("Suspended"),
         }
     }
 }
@@ -516,6 +641,9 @@ pub struct CircuitProposal {
     #[serde(deserialize_with = "deserialize_hex")]
     pub requester: Vec<u8>,
     pub requester_node_id: String,
+    /// The time, in seconds since the Unix epoch, after which the proposal expires
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
 }
 
 impl CircuitProposal {
@@ -526,6 +654,9 @@ impl CircuitProposal {
             admin::CircuitProposal_ProposalType::ADD_NODE => ProposalType::AddNode,
             admin::CircuitProposal_ProposalType::REMOVE_NODE => ProposalType::RemoveNode,
             admin::CircuitProposal_ProposalType::DISBAND => ProposalType::Disband,
+            admin::CircuitProposal_ProposalType::SUSPEND => ProposalType::Suspend,
+            admin::CircuitProposal_ProposalType::RESUME => ProposalType::Resume,
+            admin::CircuitProposal_ProposalType::UPDATE_NODE_KEYS => ProposalType::UpdateNodeKeys,
             admin::CircuitProposal_ProposalType::UNSET_PROPOSAL_TYPE => {
                 return Err(MarshallingError::UnsetField(
                     "Unset proposal type".to_string(),
@@ -547,6 +678,10 @@ impl CircuitProposal {
             votes,
             requester: proto.take_requester(),
             requester_node_id: proto.take_requester_node_id(),
+            expires_at: match proto.get_expires_at() {
+                0 => None,
+                expires_at => Some(expires_at),
+            },
         })
     }
 
@@ -557,6 +692,9 @@ impl CircuitProposal {
             ProposalType::AddNode => admin::CircuitProposal_ProposalType::ADD_NODE,
             ProposalType::RemoveNode => admin::CircuitProposal_ProposalType::REMOVE_NODE,
             ProposalType::Disband => admin::CircuitProposal_ProposalType::DISBAND,
+            ProposalType::Suspend => admin::CircuitProposal_ProposalType::SUSPEND,
+            ProposalType::Resume => admin::CircuitProposal_ProposalType::RESUME,
+            ProposalType::UpdateNodeKeys => admin::CircuitProposal_ProposalType::UPDATE_NODE_KEYS,
         };
 
         let votes = self
@@ -575,6 +713,7 @@ impl CircuitProposal {
         proposal.set_votes(RepeatedField::from_vec(votes));
         proposal.set_requester(self.requester.to_vec());
         proposal.set_requester_node_id(self.requester_node_id);
+        proposal.set_expires_at(self.expires_at.unwrap_or(0));
 
         Ok(proposal)
     }
@@ -588,6 +727,9 @@ impl From<store::CircuitProposal> for CircuitProposal {
             store::ProposalType::AddNode => ProposalType::AddNode,
             store::ProposalType::RemoveNode => ProposalType::RemoveNode,
             store::ProposalType::Disband => ProposalType::Disband,
+            store::ProposalType::Suspend => ProposalType::Suspend,
+            store::ProposalType::Resume => ProposalType::Resume,
+            store::ProposalType::UpdateNodeKeys => ProposalType::UpdateNodeKeys,
         };
 
         let store_circuit = store_proposal.circuit();
@@ -624,6 +766,8 @@ impl From<store::CircuitProposal> for CircuitProposal {
             persistence: PersistenceType::Any,
             durability: DurabilityType::NoDurability,
             routes: RouteType::Any,
+            relay_nodes: vec![],
+            voting_policy: VotingPolicy::from(store_circuit.voting_policy()),
             circuit_management_type: store_circuit.circuit_management_type().into(),
             application_metadata: store_circuit
                 .application_metadata()
@@ -658,6 +802,7 @@ impl From<store::CircuitProposal> for CircuitProposal {
                 .collect(),
             requester: store_proposal.requester().as_slice().to_vec(),
             requester_node_id: store_proposal.requester_node_id().into(),
+            expires_at: store_proposal.expires_at(),
         }
     }
 }
@@ -669,6 +814,9 @@ pub enum ProposalType {
     AddNode,
     RemoveNode,
     Disband,
+    Suspend,
+    Resume,
+    UpdateNodeKeys,
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
@@ -766,6 +914,9 @@ pub enum AdminServiceEvent {
     ProposalRejected((CircuitProposal, PublicKey)),
     CircuitReady(CircuitProposal),
     CircuitDisbanded(CircuitProposal),
+    ProposalExpired(CircuitProposal),
+    CircuitSuspended(CircuitProposal),
+    CircuitResumed(CircuitProposal),
 }
 
 impl AdminServiceEvent {
@@ -777,6 +928,9 @@ impl AdminServiceEvent {
             AdminServiceEvent::ProposalRejected((proposal, _)) => proposal,
             AdminServiceEvent::CircuitReady(proposal) => proposal,
             AdminServiceEvent::CircuitDisbanded(proposal) => proposal,
+            AdminServiceEvent::ProposalExpired(proposal) => proposal,
+            AdminServiceEvent::CircuitSuspended(proposal) => proposal,
+            AdminServiceEvent::CircuitResumed(proposal) => proposal,
         }
     }
 }
@@ -797,6 +951,9 @@ impl From<&store::AdminServiceEvent> for AdminServiceEvent {
             }
             EventType::CircuitReady => AdminServiceEvent::CircuitReady(admin_proposal),
             EventType::CircuitDisbanded => AdminServiceEvent::CircuitDisbanded(admin_proposal),
+            EventType::ProposalExpired => AdminServiceEvent::ProposalExpired(admin_proposal),
+            EventType::CircuitSuspended => AdminServiceEvent::CircuitSuspended(admin_proposal),
+            EventType::CircuitResumed => AdminServiceEvent::CircuitResumed(admin_proposal),
         }
     }
 }
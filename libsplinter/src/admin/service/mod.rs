@@ -22,7 +22,10 @@ mod subscriber;
 
 use std::any::Any;
 use std::collections::HashMap;
-use std::sync::{mpsc::channel, Arc, Mutex};
+use std::sync::{
+    mpsc::{channel, RecvTimeoutError, Sender},
+    Arc, Mutex,
+};
 use std::thread::{self, JoinHandle};
 use std::time::Duration;
 
@@ -63,6 +66,9 @@ pub use self::subscriber::AdminServiceEventSubscriber;
 const ADMIN_SERVICE_PROTOCOL_MIN: u32 = 1;
 pub(crate) const ADMIN_SERVICE_PROTOCOL_VERSION: u32 = 2;
 
+/// How often the admin service checks for and removes expired circuit proposals
+const PROPOSAL_EXPIRY_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
 pub trait AdminCommands: Send + Sync {
     fn submit_circuit_change(
         &self,
@@ -144,6 +150,7 @@ pub struct AdminService {
     consensus: Option<AdminConsensusManager>,
     peer_connector: PeerManagerConnector,
     peer_notification_run_state: Option<(usize, JoinHandle<()>)>,
+    proposal_expiry_run_state: Option<(Sender<()>, JoinHandle<()>)>,
     admin_store: Box<dyn AdminServiceStore>,
 }
 
@@ -205,6 +212,8 @@ impl AdminService {
     /// be removed if the inactive circuit is purged. The separate handling of the inactive circuits
     /// is necessary in order to avoid adding networking functionality to these circuits, as they
     /// have already had this functionality removed through disbanding or abandoning.
+    /// Suspended circuits keep their routing and peer references, but their services are added
+    /// as stopped services until the circuit is resumed.
     ///
     /// Also adds peer references for members of the circuits and proposals.
     fn re_initialize_circuits(&self) -> Result<(), ServiceStartError> {
@@ -231,7 +240,9 @@ impl AdminService {
                 ServiceStartError::Internal(format!("Unable to get circuits: {}", err))
             })?
         {
-            if circuit.circuit_status() == &store::CircuitStatus::Active {
+            if circuit.circuit_status() == &store::CircuitStatus::Active
+                || circuit.circuit_status() == &store::CircuitStatus::Suspended
+            {
                 active_circuits.push(circuit);
             } else {
                 inactive_circuits.push(circuit);
@@ -250,6 +261,7 @@ impl AdminService {
 
         let mut token_to_peer = HashMap::new();
         for circuit in active_circuits {
+            let suspended = circuit.circuit_status() == &store::CircuitStatus::Suspended;
             let local_required_auth = circuit
                 .get_node_token(&self.node_id)
                 .map_err(|err| {
@@ -283,18 +295,39 @@ impl AdminService {
                 ))
             })?;
 
+            let relayed_members = self
+                .admin_service_shared
+                .lock()
+                .map_err(|_| {
+                    ServiceStartError::PoisonedLock("the admin shared lock was poisoned".into())
+                })?
+                .add_relay_routes(
+                    circuit.routes(),
+                    circuit.authorization_type(),
+                    circuit.relay_nodes(),
+                    &members,
+                )
+                .map_err(|err| ServiceStartError::Internal(err.to_string()))?;
+
             // restart all peer in the circuit
             for member in members {
                 if member.node_id != self.node_id {
-                    let peer_ref = self.peer_connector.add_peer_ref(
-                        member.token.clone(),
-                        member.endpoints.to_vec(),
-                        local_required_auth.clone(),
-                    );
-                    if let Ok(peer_ref) = peer_ref {
-                        peer_refs.push(peer_ref);
-                    } else {
-                        info!("Unable to peer with {} at this time", member.node_id);
+                    // Members reached through relay nodes are not peered with directly
+                    if !relayed_members.contains(&member.node_id) {
+                        let peer_ref = self
+                            .admin_service_shared
+                            .lock()
+                            .map_err(|_| {
+                                ServiceStartError::PoisonedLock(
+                                    "the admin shared lock was poisoned".into(),
+                                )
+                            })?
+                            .request_member_peer(&member, &local_required_auth);
+                        if let Ok(peer_ref) = peer_ref {
+                            peer_refs.push(peer_ref);
+                        } else {
+                            info!("Unable to peer with {} at this time", member.node_id);
+                        }
                     }
 
                     token_to_peer.insert(
@@ -344,12 +377,16 @@ impl AdminService {
                             .map(|node| node.node_id().to_string())
                             .collect(),
                         circuit.authorization_type().into(),
-                    ),
+                    )
+                    .with_durability(circuit.durability().into())
+                    .with_route_type((&circuit).into())
+                    .with_suspended(suspended),
                     routing_members,
                 )
                 .map_err(|err| ServiceStartError::Internal(err.reduce_to_string()))?;
 
-            // Start all services
+            // Start all services; the services of a suspended circuit are added as stopped so
+            // they may be restarted when the circuit is resumed
             for service in circuit.roster() {
                 if service.node_id() != self.node_id {
                     continue;
@@ -369,12 +406,23 @@ impl AdminService {
                         .map(|(key, value)| (key.clone(), value.clone()))
                         .collect();
 
-                    if let Err(err) = dispatch.add_service(
-                        circuit.circuit_id(),
-                        service.service_id(),
-                        service.service_type(),
-                        service_arguments,
-                    ) {
+                    let result = if suspended {
+                        dispatch.add_stopped_service(
+                            circuit.circuit_id(),
+                            service.service_id(),
+                            service.service_type(),
+                            service_arguments.into_iter().collect(),
+                        )
+                    } else {
+                        dispatch.add_service(
+                            circuit.circuit_id(),
+                            service.service_id(),
+                            service.service_type(),
+                            service_arguments,
+                        )
+                    };
+
+                    if let Err(err) = result {
                         error!(
                             "Unable to start service {} on circuit {}: {}",
                             service.service_id(),
@@ -470,19 +518,40 @@ impl AdminService {
                 ))
             })?;
 
+            let relayed_members = self
+                .admin_service_shared
+                .lock()
+                .map_err(|_| {
+                    ServiceStartError::PoisonedLock("the admin shared lock was poisoned".into())
+                })?
+                .add_relay_routes(
+                    proposal.circuit().routes(),
+                    proposal.circuit().authorization_type(),
+                    proposal.circuit().relay_nodes(),
+                    &members,
+                )
+                .map_err(|err| ServiceStartError::Internal(err.to_string()))?;
+
             // connect to all peers in the circuit proposal
             for member in members.iter() {
                 if member.node_id != self.node_id {
-                    let peer_ref = self.peer_connector.add_peer_ref(
-                        member.token.clone(),
-                        member.endpoints.to_vec(),
-                        local_required_auth.clone(),
-                    );
-
-                    if let Ok(peer_ref) = peer_ref {
-                        peer_refs.push(peer_ref);
-                    } else {
-                        info!("Unable to peer with {} at this time", member.node_id);
+                    // Members reached through relay nodes are not peered with directly
+                    if !relayed_members.contains(&member.node_id) {
+                        let peer_ref = self
+                            .admin_service_shared
+                            .lock()
+                            .map_err(|_| {
+                                ServiceStartError::PoisonedLock(
+                                    "the admin shared lock was poisoned".into(),
+                                )
+                            })?
+                            .request_member_peer(member, &local_required_auth);
+
+                        if let Ok(peer_ref) = peer_ref {
+                            peer_refs.push(peer_ref);
+                        } else {
+                            info!("Unable to peer with {} at this time", member.node_id);
+                        }
                     }
 
                     token_to_peer.insert(
@@ -575,6 +644,31 @@ impl ServiceInstance for AdminService {
 
         self.peer_notification_run_state = Some((peer_subscriber_id, notification_join_handle));
 
+        let (expiry_shutdown_sender, expiry_shutdown_receiver) = channel();
+        let expiry_admin_shared = self.admin_service_shared.clone();
+
+        debug!("Starting admin service's proposal expiry checker");
+        let expiry_join_handle = thread::Builder::new()
+            .name("ProposalExpiryChecker".into())
+            .spawn(move || loop {
+                match expiry_shutdown_receiver.recv_timeout(PROPOSAL_EXPIRY_CHECK_INTERVAL) {
+                    Err(RecvTimeoutError::Timeout) => (),
+                    Ok(()) | Err(RecvTimeoutError::Disconnected) => break,
+                }
+
+                if let Ok(mut admin_shared) = expiry_admin_shared.lock() {
+                    if let Err(err) = admin_shared.expire_proposals() {
+                        error!("Unable to remove expired circuit proposals: {}", err);
+                    }
+                } else {
+                    error!("the admin shared lock was poisoned");
+                    break;
+                }
+            })
+            .map_err(|err| ServiceStartError::Internal(err.to_string()))?;
+
+        self.proposal_expiry_run_state = Some((expiry_shutdown_sender, expiry_join_handle));
+
         // Setup consensus
         let consensus = AdminConsensusManager::new(
             self.service_id().into(),
@@ -671,6 +765,17 @@ impl ServiceInstance for AdminService {
             }
         }
 
+        if let Some((expiry_shutdown_sender, expiry_join_handle)) =
+            self.proposal_expiry_run_state.take()
+        {
+            // The thread also exits if the sender is dropped, so a failed send can be ignored
+            let _ = expiry_shutdown_sender.send(());
+
+            if let Err(err) = expiry_join_handle.join() {
+                error!("Failed to join proposal expiry thread: {:?}", err);
+            }
+        }
+
         info!("Admin service stopped and disconnected");
 
         Ok(())
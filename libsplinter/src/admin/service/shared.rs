@@ -16,33 +16,42 @@ use std::collections::{HashMap, HashSet, VecDeque};
 use std::convert::{TryFrom, TryInto};
 use std::iter::ExactSizeIterator;
 use std::sync::mpsc::Sender;
-use std::time::Instant;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
 use cylinder::{PublicKey, Signature, Verifier as SignatureVerifier};
 use protobuf::{Message, RepeatedField};
 
 use crate::admin::lifecycle::LifecycleDispatch;
 use crate::admin::store::{
-    AdminServiceStore, Circuit as StoreCircuit, CircuitBuilder as StoreCircuitBuilder,
-    CircuitPredicate, CircuitProposal as StoreProposal, CircuitStatus as StoreCircuitStatus,
-    ProposalType, ProposedCircuit, Service as StoreService, Vote, VoteRecordBuilder,
+    AdminServiceStore, AuthorizationType as StoreAuthorizationType, Circuit as StoreCircuit,
+    CircuitBuilder as StoreCircuitBuilder, CircuitPredicate, CircuitProposal as StoreProposal,
+    CircuitStatus as StoreCircuitStatus, ProposalType, ProposedCircuit,
+    RouteType as StoreRouteType, Service as StoreService, Vote, VoteRecordBuilder, VotingPolicy,
+    VotingPolicyType,
 };
 use crate::admin::token::{PeerAuthorizationTokenReader, PeerNode};
 use crate::admin::CIRCUIT_PROTOCOL_VERSION;
+#[cfg(feature = "circuit-rate-limiting")]
+use crate::circuit::rate_limit::CircuitRateLimiter;
 use crate::circuit::routing::{self, RoutingTableWriter};
 use crate::consensus::{Proposal, ProposalId, ProposalUpdate};
 use crate::error::InternalError;
 use crate::hex::parse_hex;
 use crate::hex::to_hex;
 use crate::keys::KeyPermissionManager;
-use crate::peer::{PeerAuthorizationToken, PeerManagerConnector, PeerRef, PeerTokenPair};
+#[cfg(feature = "peer-retry-policy")]
+use crate::peer::RetryPolicy;
+use crate::peer::{
+    PeerAuthorizationToken, PeerManagerConnector, PeerRef, PeerRefAddError, PeerTokenPair,
+};
 use crate::protos::admin::{
     AbandonedCircuit, AdminMessage, AdminMessage_Type, Circuit, CircuitManagementPayload,
     CircuitManagementPayload_Action, CircuitManagementPayload_Header, CircuitProposal,
     CircuitProposalVote, CircuitProposalVote_Vote, CircuitProposal_ProposalType,
-    Circuit_AuthorizationType, Circuit_CircuitStatus, Circuit_DurabilityType,
-    Circuit_PersistenceType, Circuit_RouteType, MemberReady, RemovedProposal,
-    ServiceProtocolVersionRequest, SplinterNode, SplinterService,
+    CircuitUpdateNodeKeysRequest, Circuit_AuthorizationType, Circuit_CircuitStatus,
+    Circuit_DurabilityType, Circuit_PersistenceType, Circuit_RouteType, Circuit_VotingPolicy,
+    Circuit_VotingPolicy_PolicyType, MemberReady, RemovedProposal, ServiceProtocolVersionRequest,
+    SplinterNode, SplinterService,
 };
 use crate::public_key;
 use crate::service::instance::{ServiceArgValidator, ServiceError, ServiceNetworkSender};
@@ -153,6 +162,16 @@ pub struct AdminServiceShared {
     // Temporarily hold on to peers that should be removed. This helps avoid dropping messages
     // when removing a proposal.
     peers_to_be_removed: Vec<(Instant, Vec<PeerTokenPair>)>,
+    // Members of circuits with the relay route type that are reached through the circuit's relay
+    // nodes instead of a direct peer connection
+    relayed_peers: HashSet<PeerTokenPair>,
+    // Rate limiter for the messages sent on circuits, whose state for a circuit is removed when
+    // the circuit is removed from the routing table
+    #[cfg(feature = "circuit-rate-limiting")]
+    rate_limiter: Option<CircuitRateLimiter>,
+    // Retry policies used when peering with circuit members, by node ID
+    #[cfg(feature = "peer-retry-policy")]
+    peer_retry_policies: HashMap<String, RetryPolicy>,
 }
 
 impl AdminServiceShared {
@@ -197,6 +216,11 @@ impl AdminServiceShared {
             public_keys,
             token_to_peer: HashMap::new(),
             peers_to_be_removed: Vec::new(),
+            relayed_peers: HashSet::new(),
+            #[cfg(feature = "circuit-rate-limiting")]
+            rate_limiter: None,
+            #[cfg(feature = "peer-retry-policy")]
+            peer_retry_policies: HashMap::new(),
         }
     }
 
@@ -255,6 +279,54 @@ impl AdminServiceShared {
         self.routing_table_writer.clone()
     }
 
+    #[cfg(feature = "circuit-rate-limiting")]
+    pub fn set_rate_limiter(&mut self, rate_limiter: Option<CircuitRateLimiter>) {
+        self.rate_limiter = rate_limiter;
+    }
+
+    /// Removes the rate limiter's state for a circuit that has been removed from the routing
+    /// table. Failing to do so only leaks memory, so the error is logged rather than returned.
+    #[cfg(feature = "circuit-rate-limiting")]
+    fn remove_circuit_rate_limits(&self, circuit_id: &str) {
+        if let Some(rate_limiter) = &self.rate_limiter {
+            if let Err(err) = rate_limiter.remove_circuit(circuit_id) {
+                error!(
+                    "Unable to remove rate limits for circuit {}: {}",
+                    circuit_id, err
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "peer-retry-policy")]
+    pub fn set_peer_retry_policies(&mut self, peer_retry_policies: HashMap<String, RetryPolicy>) {
+        self.peer_retry_policies = peer_retry_policies;
+    }
+
+    /// Requests a peer for a circuit member, using the retry policy configured for the member's
+    /// node ID, if there is one.
+    pub fn request_member_peer(
+        &self,
+        member: &PeerNode,
+        required_local_auth: &PeerAuthorizationToken,
+    ) -> Result<PeerRef, PeerRefAddError> {
+        #[cfg(feature = "peer-retry-policy")]
+        if let Some(retry_policy) = self.peer_retry_policies.get(&member.node_id) {
+            return self.peer_connector.add_peer_ref_with_retry_policy(
+                member.token.clone(),
+                member.endpoints.to_vec(),
+                required_local_auth.clone(),
+                retry_policy.clone(),
+            );
+        }
+
+        self.peer_connector.add_peer_ref(
+            member.token.clone(),
+            member.endpoints.to_vec(),
+            required_local_auth.clone(),
+        )
+    }
+
     pub fn pending_consensus_proposals(
         &self,
         id: &ProposalId,
@@ -358,16 +430,22 @@ impl AdminServiceShared {
                     .circuit_management_type
                     .clone();
 
-                match self.check_approved(&circuit_proposal) {
+                match self.check_approved(&circuit_proposal)? {
                     CircuitProposalStatus::Accepted => {
                         let status = circuit_proposal.get_circuit_proposal().get_circuit_status();
-                        // Verifying if the circuit proposal is associated with a disband request.
-                        // If the status is set to `DISBANDED`, the proposal is associated with
-                        // a disband request. Otherwise, the admin service should continue with
-                        // committing a new circuit proposal. For 0.4 compatibility, this is the
-                        // default action as these proposals will not have the `circuit_status`
-                        // field set.
-                        if status == Circuit_CircuitStatus::DISBANDED {
+                        let proposal_type = circuit_proposal.get_proposal_type();
+                        // Verifying if the circuit proposal is associated with a request to change
+                        // an existing circuit. If the status is set to `DISBANDED`, the proposal is
+                        // associated with a disband request. Suspend, resume and update node keys
+                        // requests are identified by their proposal type. Otherwise, the admin
+                        // service should continue with committing a new circuit proposal. For 0.4
+                        // compatibility, this is the default action as these proposals will not
+                        // have the `circuit_status` field set.
+                        if status == Circuit_CircuitStatus::DISBANDED
+                            || proposal_type == CircuitProposal_ProposalType::SUSPEND
+                            || proposal_type == CircuitProposal_ProposalType::RESUME
+                            || proposal_type == CircuitProposal_ProposalType::UPDATE_NODE_KEYS
+                        {
                             let store_circuit =
                                 StoreCircuit::try_from(circuit_proposal.get_circuit_proposal())
                                     .map_err(|err| {
@@ -377,15 +455,35 @@ impl AdminServiceShared {
                                         ))
                                     })?;
 
-                            if store_circuit.circuit_status() != &StoreCircuitStatus::Disbanded {
+                            let (expected_status, expected_status_name) = match proposal_type {
+                                CircuitProposal_ProposalType::SUSPEND => {
+                                    (StoreCircuitStatus::Suspended, "suspended")
+                                }
+                                CircuitProposal_ProposalType::RESUME
+                                | CircuitProposal_ProposalType::UPDATE_NODE_KEYS => {
+                                    (StoreCircuitStatus::Active, "active")
+                                }
+                                _ => (StoreCircuitStatus::Disbanded, "disbanded"),
+                            };
+
+                            if store_circuit.circuit_status() != &expected_status {
                                 return Err(AdminSharedError::SplinterStateError(format!(
-                                    "Circuit should be disbanded: {}",
-                                    circuit_id
+                                    "Circuit should be {}: {}",
+                                    expected_status_name, circuit_id
                                 )));
                             }
-                            // Updating the corresponding `active` circuit from the admin store
-                            // and then removing the corresponding `CircuitProposal` from the
-                            // disband request
+                            // The circuit's current member keys are needed to replace the peer
+                            // connections of members whose keys are updated
+                            let previous_circuit = if proposal_type
+                                == CircuitProposal_ProposalType::UPDATE_NODE_KEYS
+                            {
+                                self.admin_store.get_circuit(circuit_id)?
+                            } else {
+                                None
+                            };
+
+                            // Updating the corresponding circuit from the admin store and then
+                            // removing the corresponding `CircuitProposal` from the request
                             self.admin_store
                                 .update_circuit(store_circuit.clone())
                                 .map_err(|_| {
@@ -398,7 +496,7 @@ impl AdminServiceShared {
 
                             self.update_metrics()?;
 
-                            // send message about circuit disband proposal being accepted
+                            // send message about the circuit proposal being accepted
                             let circuit_proposal_proto =
                                 messages::CircuitProposal::from_proto(circuit_proposal.clone())
                                     .map_err(AdminSharedError::InvalidMessageFormat)?;
@@ -407,6 +505,14 @@ impl AdminServiceShared {
                                 circuit_proposal_context.signer_public_key,
                             ));
                             self.send_event(&mgmt_type, event);
+
+                            // Updating a node's keys only requires this node's routing table and
+                            // peer connections to be updated; the members do not need to wait on
+                            // each other
+                            if let Some(previous_circuit) = previous_circuit {
+                                return self.update_node_keys(&previous_circuit, &store_circuit);
+                            }
+
                             // send MEMBER_READY message to all other members' admin
                             // services
                             if let Some(ref network_sender) = self.network_sender {
@@ -471,7 +577,9 @@ impl AdminServiceShared {
                                     .map(|node| node.node_id().to_string())
                                     .collect(),
                                 circuit.authorization_type().into(),
-                            );
+                            )
+                            .with_durability(circuit.durability().into())
+                            .with_route_type((&circuit).into());
 
                             let routing_members = circuit_proposal
                                 .get_circuit_proposal()
@@ -607,6 +715,49 @@ impl AdminServiceShared {
                                 );
                                 Ok(())
                             }
+                            CircuitManagementPayload_Action::CIRCUIT_UPDATE_NODE_KEYS_REQUEST => {
+                                self.add_proposal(circuit_proposal.clone())?;
+                                self.update_metrics()?;
+                                // notify registered application authorization handlers of the
+                                // committed update node keys proposal
+                                let event = messages::AdminServiceEvent::ProposalSubmitted(
+                                    messages::CircuitProposal::from_proto(circuit_proposal.clone())
+                                        .map_err(AdminSharedError::InvalidMessageFormat)?,
+                                );
+                                self.send_event(&mgmt_type, event);
+
+                                info!(
+                                    "committed changes for new circuit proposal to update node \
+                                     keys on circuit {}",
+                                    circuit_id
+                                );
+                                Ok(())
+                            }
+                            CircuitManagementPayload_Action::CIRCUIT_SUSPEND_REQUEST
+                            | CircuitManagementPayload_Action::CIRCUIT_RESUME_REQUEST => {
+                                self.add_proposal(circuit_proposal.clone())?;
+                                self.update_metrics()?;
+                                // notify registered application authorization handlers of the
+                                // committed circuit status change proposal
+                                let event = messages::AdminServiceEvent::ProposalSubmitted(
+                                    messages::CircuitProposal::from_proto(circuit_proposal.clone())
+                                        .map_err(AdminSharedError::InvalidMessageFormat)?,
+                                );
+                                self.send_event(&mgmt_type, event);
+
+                                info!(
+                                    "committed changes for new circuit proposal to {} circuit {}",
+                                    if action
+                                        == CircuitManagementPayload_Action::CIRCUIT_SUSPEND_REQUEST
+                                    {
+                                        "suspend"
+                                    } else {
+                                        "resume"
+                                    },
+                                    circuit_id
+                                );
+                                Ok(())
+                            }
                             _ => Err(AdminSharedError::UnknownAction(format!(
                                 "Received unknown action: {:?}",
                                 action
@@ -744,6 +895,7 @@ impl AdminServiceShared {
                 circuit_proposal.set_circuit_proposal(proposed_circuit.clone());
                 circuit_proposal.set_requester(header.get_requester().to_vec());
                 circuit_proposal.set_requester_node_id(header.get_requester_node_id().to_string());
+                circuit_proposal.set_expires_at(create_request.get_expires_at());
 
                 let expected_hash = sha256(&circuit_proposal)?;
                 self.pending_changes = Some(CircuitProposalContext {
@@ -889,11 +1041,16 @@ impl AdminServiceShared {
                     })?;
 
                 // Creating the proposal to disband this circuit
-                let circuit_proposal = self.make_disband_request_circuit_proposal(
+                let mut circuit_proposal = self.make_disband_request_circuit_proposal(
                     circuit_id,
                     header.get_requester(),
                     header.get_requester_node_id(),
                 )?;
+                circuit_proposal.set_expires_at(
+                    circuit_payload
+                        .get_circuit_disband_request()
+                        .get_expires_at(),
+                );
 
                 let local_required_auth = circuit_proposal
                     .get_circuit_proposal()
@@ -961,6 +1118,203 @@ impl AdminServiceShared {
 
                 Ok((expected_hash, circuit_proposal))
             }
+            action @ CircuitManagementPayload_Action::CIRCUIT_SUSPEND_REQUEST
+            | action @ CircuitManagementPayload_Action::CIRCUIT_RESUME_REQUEST => {
+                let suspend = action == CircuitManagementPayload_Action::CIRCUIT_SUSPEND_REQUEST;
+                let change = if suspend { "suspend" } else { "resume" };
+                debug!("Circuit {} request being processed", change);
+                let (circuit_id, expires_at) = if suspend {
+                    let request = circuit_payload.get_circuit_suspend_request();
+                    (request.get_circuit_id(), request.get_expires_at())
+                } else {
+                    let request = circuit_payload.get_circuit_resume_request();
+                    (request.get_circuit_id(), request.get_expires_at())
+                };
+
+                // Creating the proposal to change the status of this circuit
+                let mut circuit_proposal = if suspend {
+                    self.make_suspend_request_circuit_proposal(
+                        circuit_id,
+                        header.get_requester(),
+                        header.get_requester_node_id(),
+                    )?
+                } else {
+                    self.make_resume_request_circuit_proposal(
+                        circuit_id,
+                        header.get_requester(),
+                        header.get_requester_node_id(),
+                    )?
+                };
+                circuit_proposal.set_expires_at(expires_at);
+
+                let local_required_auth = circuit_proposal
+                    .get_circuit_proposal()
+                    .get_node_token(&self.node_id)
+                    .map_err(|err| {
+                        AdminSharedError::ValidationFailed(format!(
+                            "Unable to get local nodes token: {}",
+                            err
+                        ))
+                    })?
+                    .ok_or_else(|| {
+                        AdminSharedError::ValidationFailed(
+                            "Circuit does not have the local node".to_string(),
+                        )
+                    })?;
+
+                let mut protocol = ADMIN_SERVICE_PROTOCOL_VERSION;
+                for member in circuit_proposal
+                    .get_circuit_proposal()
+                    .list_nodes()
+                    .map_err(|_| {
+                        AdminSharedError::SplinterStateError(format!(
+                            "Unable to get tokens for proposal: {}",
+                            circuit_proposal.get_circuit_id()
+                        ))
+                    })?
+                {
+                    // Figure out what protocol version should be used for this proposal
+                    if let Some(protocol_version) = self.service_protocols.get(&PeerTokenPair::new(
+                        member.token.clone(),
+                        local_required_auth.clone(),
+                    )) {
+                        if protocol_version < &protocol {
+                            protocol = *protocol_version
+                        }
+                    }
+                }
+                let signer_public_key = header.get_requester();
+                let requester_node_id = header.get_requester_node_id();
+
+                if suspend {
+                    self.validate_suspend_circuit(
+                        circuit_proposal.get_circuit_proposal(),
+                        signer_public_key,
+                        requester_node_id,
+                        protocol,
+                    )?;
+                } else {
+                    self.validate_resume_circuit(
+                        circuit_proposal.get_circuit_proposal(),
+                        signer_public_key,
+                        requester_node_id,
+                        protocol,
+                    )?;
+                }
+
+                let expected_hash = sha256(&circuit_proposal)?;
+                self.pending_changes = Some(CircuitProposalContext {
+                    circuit_proposal: circuit_proposal.clone(),
+                    signer_public_key: header.get_requester().to_vec(),
+                    action,
+                });
+                self.current_consensus_verifiers = circuit_proposal
+                    .get_circuit_proposal()
+                    .list_tokens(&self.node_id)
+                    .map_err(|_| {
+                        AdminSharedError::SplinterStateError(format!(
+                            "Unable to get tokens for proposal: {}",
+                            circuit_proposal.get_circuit_id()
+                        ))
+                    })?;
+
+                Ok((expected_hash, circuit_proposal))
+            }
+            CircuitManagementPayload_Action::CIRCUIT_UPDATE_NODE_KEYS_REQUEST => {
+                debug!("Circuit update node keys request being processed");
+                let update_request = circuit_payload.get_circuit_update_node_keys_request();
+
+                // Creating the proposal to update the node's keys
+                let mut circuit_proposal = self.make_update_node_keys_request_circuit_proposal(
+                    update_request,
+                    header.get_requester(),
+                    header.get_requester_node_id(),
+                )?;
+                circuit_proposal.set_expires_at(update_request.get_expires_at());
+
+                let local_required_auth = circuit_proposal
+                    .get_circuit_proposal()
+                    .get_node_token(&self.node_id)
+                    .map_err(|err| {
+                        AdminSharedError::ValidationFailed(format!(
+                            "Unable to get local nodes token: {}",
+                            err
+                        ))
+                    })?
+                    .ok_or_else(|| {
+                        AdminSharedError::ValidationFailed(
+                            "Circuit does not have the local node".to_string(),
+                        )
+                    })?;
+
+                // The protocol version is negotiated with the members' current keys, as the
+                // new key is not used until the proposal is accepted
+                let current_circuit = self
+                    .admin_store
+                    .get_circuit(update_request.get_circuit_id())?;
+                let mut protocol = ADMIN_SERVICE_PROTOCOL_VERSION;
+                if let Some(current_circuit) = current_circuit {
+                    let current_local_auth = current_circuit
+                        .get_node_token(&self.node_id)
+                        .map_err(|err| {
+                            AdminSharedError::ValidationFailed(format!(
+                                "Unable to get local nodes token: {}",
+                                err
+                            ))
+                        })?
+                        .unwrap_or(local_required_auth);
+                    for member in current_circuit.list_nodes().map_err(|_| {
+                        AdminSharedError::SplinterStateError(format!(
+                            "Unable to get tokens for circuit: {}",
+                            current_circuit.circuit_id()
+                        ))
+                    })? {
+                        // Figure out what protocol version should be used for this proposal
+                        if let Some(protocol_version) = self.service_protocols.get(
+                            &PeerTokenPair::new(member.token.clone(), current_local_auth.clone()),
+                        ) {
+                            if protocol_version < &protocol {
+                                protocol = *protocol_version
+                            }
+                        }
+                    }
+                }
+
+                self.validate_update_node_keys(
+                    update_request,
+                    circuit_proposal.get_circuit_proposal(),
+                    header.get_requester(),
+                    header.get_requester_node_id(),
+                    protocol,
+                )?;
+
+                let expected_hash = sha256(&circuit_proposal)?;
+                self.pending_changes = Some(CircuitProposalContext {
+                    circuit_proposal: circuit_proposal.clone(),
+                    signer_public_key: header.get_requester().to_vec(),
+                    action: CircuitManagementPayload_Action::CIRCUIT_UPDATE_NODE_KEYS_REQUEST,
+                });
+                // Consensus runs over the existing connections, which use the current keys
+                self.current_consensus_verifiers = self
+                    .admin_store
+                    .get_circuit(update_request.get_circuit_id())?
+                    .ok_or_else(|| {
+                        AdminSharedError::ValidationFailed(format!(
+                            "Received update node keys request for a circuit that does not \
+                             exist: circuit id {}",
+                            update_request.get_circuit_id()
+                        ))
+                    })?
+                    .list_tokens(&self.node_id)
+                    .map_err(|_| {
+                        AdminSharedError::SplinterStateError(format!(
+                            "Unable to get tokens for proposal: {}",
+                            circuit_proposal.get_circuit_id()
+                        ))
+                    })?;
+
+                Ok((expected_hash, circuit_proposal))
+            }
             CircuitManagementPayload_Action::ACTION_UNSET => Err(
                 AdminSharedError::ValidationFailed("Action must be set".to_string()),
             ),
@@ -1023,8 +1377,18 @@ impl AdminServiceShared {
             )))
         })?;
 
+        let relayed_members = self
+            .add_relay_routes(
+                proposed_circuit.routes(),
+                proposed_circuit.authorization_type(),
+                proposed_circuit.relay_nodes(),
+                &members,
+            )
+            .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+
         self.check_connected_peers_payload_create(
             &members,
+            &relayed_members,
             payload,
             message_sender,
             local_required_auth,
@@ -1077,6 +1441,18 @@ impl AdminServiceShared {
             )))
         })?;
 
+        // The routes to relayed members are not kept across restarts, so they are added again
+        // before the vote is sent
+        let relayed_members = self
+            .add_relay_routes(
+                proposal.circuit().routes(),
+                proposal.circuit().authorization_type(),
+                proposal.circuit().relay_nodes(),
+                &members,
+            )
+            .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+        self.add_relayed_peers(&members, &relayed_members, &local_required_auth);
+
         self.check_connected_peers_payload_vote(
             &members,
             local_required_auth,
@@ -1134,43 +1510,174 @@ impl AdminServiceShared {
         )
     }
 
-    pub fn update_metrics(&self) -> Result<(), AdminSharedError> {
-        // initialize circuit and proposal metrics
-        gauge!(
-            "splinter.admin.circuits.active",
-            self.admin_store.count_circuits(&[])? as f64
+    /// Once a local `CircuitSuspendRequest` has been validated, the admin service may now proceed
+    /// to communicating with the remote circuit members to propose the suspend change.
+    pub fn propose_suspend(
+        &mut self,
+        payload: CircuitManagementPayload,
+        requester: &[u8],
+        requester_node_id: &str,
+        message_sender: String,
+    ) -> Result<(), ServiceError> {
+        debug!(
+            "received circuit suspend request {}",
+            payload.get_circuit_suspend_request().get_circuit_id()
         );
-        gauge!(
-            "splinter.admin.proposals",
-            self.admin_store.count_proposals(&[])? as f64
+        let circuit_id = payload.get_circuit_suspend_request().get_circuit_id();
+        let circuit_proposal = self
+            .make_suspend_request_circuit_proposal(circuit_id, requester, requester_node_id)
+            .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+
+        self.propose_status_change(circuit_proposal, payload, message_sender)
+    }
+
+    /// Once a local `CircuitResumeRequest` has been validated, the admin service may now proceed
+    /// to communicating with the remote circuit members to propose the resume change.
+    pub fn propose_resume(
+        &mut self,
+        payload: CircuitManagementPayload,
+        requester: &[u8],
+        requester_node_id: &str,
+        message_sender: String,
+    ) -> Result<(), ServiceError> {
+        debug!(
+            "received circuit resume request {}",
+            payload.get_circuit_resume_request().get_circuit_id()
         );
-        Ok(())
+        let circuit_id = payload.get_circuit_resume_request().get_circuit_id();
+        let circuit_proposal = self
+            .make_resume_request_circuit_proposal(circuit_id, requester, requester_node_id)
+            .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+
+        self.propose_status_change(circuit_proposal, payload, message_sender)
     }
 
-    /// Attempts to purge a circuit and the associated internal Splinter services
-    fn purge_circuit(&mut self, circuit_id: &str) -> Result<(), ServiceError> {
-        // Verifying the circuit is able to be purged
-        let stored_circuit = self
-            .admin_store
-            .get_circuit(circuit_id)
+    /// Checks the members of an existing circuit have agreed on a protocol version before
+    /// proposing a change to the circuit's status.
+    fn propose_status_change(
+        &mut self,
+        circuit_proposal: CircuitProposal,
+        payload: CircuitManagementPayload,
+        message_sender: String,
+    ) -> Result<(), ServiceError> {
+        let local_required_auth = circuit_proposal
+            .get_circuit_proposal()
+            .get_node_token(&self.node_id)
             .map_err(|err| {
-                ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::SplinterStateError(
-                    format!("error occurred when trying to get circuit {}", err),
+                ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::ValidationFailed(
+                    format!("Unable to get local nodes token: {}", err),
                 )))
             })?
             .ok_or_else(|| {
-                ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::SplinterStateError(
-                    format!(
-                        "Received purged request for a circuit that does not exist: circuit id {}",
-                        circuit_id
-                    ),
+                ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::ValidationFailed(
+                    "Circuit does not have the local node".to_string(),
                 )))
             })?;
 
-        self.purge_services(circuit_id, stored_circuit.roster())
-            .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+        let members = circuit_proposal
+            .get_circuit_proposal()
+            .list_nodes()
+            .map_err(|err| {
+                ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::ValidationFailed(
+                    format!("Unable to get peer tokens for members: {}", err),
+                )))
+            })?;
 
-        if let Some(circuit) = self
+        self.check_connected_peers_payload_disband(
+            &members,
+            local_required_auth,
+            payload,
+            message_sender,
+        )
+    }
+
+    /// Once a local `CircuitUpdateNodeKeysRequest` has been validated, the admin service may now
+    /// proceed to communicating with the remote circuit members to propose the key change. The
+    /// members are contacted using their current keys.
+    pub fn propose_update_node_keys(
+        &mut self,
+        payload: CircuitManagementPayload,
+        message_sender: String,
+    ) -> Result<(), ServiceError> {
+        let circuit_id = payload
+            .get_circuit_update_node_keys_request()
+            .get_circuit_id();
+        debug!("received circuit update node keys request {}", circuit_id);
+
+        let circuit = self
+            .admin_store
+            .get_circuit(circuit_id)
+            .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?
+            .ok_or_else(|| {
+                ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::ValidationFailed(
+                    format!("Unable to get circuit: {}", circuit_id),
+                )))
+            })?;
+
+        let local_required_auth = circuit
+            .get_node_token(&self.node_id)
+            .map_err(|err| {
+                ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::ValidationFailed(
+                    format!("Unable to get local nodes token: {}", err),
+                )))
+            })?
+            .ok_or_else(|| {
+                ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::ValidationFailed(
+                    "Circuit does not have the local node".to_string(),
+                )))
+            })?;
+
+        let members = circuit.list_nodes().map_err(|err| {
+            ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::ValidationFailed(
+                format!("Unable to get peer tokens for members: {}", err),
+            )))
+        })?;
+
+        self.check_connected_peers_payload_disband(
+            &members,
+            local_required_auth,
+            payload,
+            message_sender,
+        )
+    }
+
+    pub fn update_metrics(&self) -> Result<(), AdminSharedError> {
+        // initialize circuit and proposal metrics
+        gauge!(
+            "splinter.admin.circuits.active",
+            self.admin_store.count_circuits(&[])? as f64
+        );
+        gauge!(
+            "splinter.admin.proposals",
+            self.admin_store.count_proposals(&[])? as f64
+        );
+        Ok(())
+    }
+
+    /// Attempts to purge a circuit and the associated internal Splinter services
+    fn purge_circuit(&mut self, circuit_id: &str) -> Result<(), ServiceError> {
+        // Verifying the circuit is able to be purged
+        let stored_circuit = self
+            .admin_store
+            .get_circuit(circuit_id)
+            .map_err(|err| {
+                ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::SplinterStateError(
+                    format!("error occurred when trying to get circuit {}", err),
+                )))
+            })?
+            .ok_or_else(|| {
+                ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::SplinterStateError(
+                    format!(
+                        "Received purged request for a circuit that does not exist: circuit id {}",
+                        circuit_id
+                    ),
+                )))
+            })?;
+
+        self.purge_services(circuit_id, stored_circuit.roster())
+            .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+
+        if let Some(circuit) = self
             .remove_circuit(circuit_id)
             .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?
         {
@@ -1283,6 +1790,8 @@ impl AdminServiceShared {
                     ),
                 )))
             })?;
+        #[cfg(feature = "circuit-rate-limiting")]
+        self.remove_circuit_rate_limits(stored_circuit.circuit_id());
         // Removing the circuit's peer refs
         self.remove_peer_refs(stored_circuit.list_tokens(&self.node_id).map_err(|err| {
             ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::SplinterStateError(
@@ -1375,6 +1884,58 @@ impl AdminServiceShared {
         }
     }
 
+    /// Removes any circuit proposals whose expiration time has passed. A `ProposalExpired` event
+    /// is sent for each expired proposal.
+    ///
+    /// A proposal that is currently being committed is skipped; it will be checked again on the
+    /// next call.
+    pub fn expire_proposals(&mut self) -> Result<(), AdminSharedError> {
+        let now = unix_time_now()?;
+        let pending_circuit_id = self
+            .pending_changes
+            .as_ref()
+            .map(|context| context.circuit_proposal.get_circuit_id().to_string());
+
+        let expired_proposals = self
+            .get_proposals(&[])?
+            .filter(|proposal| {
+                proposal.is_expired(now)
+                    && Some(proposal.circuit_id()) != pending_circuit_id.as_deref()
+            })
+            .collect::<Vec<_>>();
+
+        if expired_proposals.is_empty() {
+            return Ok(());
+        }
+
+        for proposal in expired_proposals {
+            let circuit_id = proposal.circuit_id().to_string();
+            self.remove_proposal(&circuit_id)?;
+            self.peers_to_be_removed.push((
+                Instant::now(),
+                proposal
+                    .circuit()
+                    .list_tokens(&self.node_id)
+                    .map_err(|err| {
+                        AdminSharedError::SplinterStateError(format!(
+                            "Unable to remove peer refs for proposal {}: {}",
+                            circuit_id, err
+                        ))
+                    })?,
+            ));
+
+            let mgmt_type = proposal.circuit().circuit_management_type().to_string();
+            let event = messages::AdminServiceEvent::ProposalExpired(
+                messages::CircuitProposal::from(proposal),
+            );
+            self.send_event(&mgmt_type, event);
+
+            info!("circuit proposal for {} has expired", circuit_id);
+        }
+
+        self.update_metrics()
+    }
+
     pub fn send_protocol_request(
         &mut self,
         token: &PeerTokenPair,
@@ -1451,6 +2012,7 @@ impl AdminServiceShared {
     fn check_connected_peers_payload_create(
         &mut self,
         members: &[PeerNode],
+        relayed_members: &[String],
         payload: CircuitManagementPayload,
         message_sender: String,
         local_required_auth: PeerAuthorizationToken,
@@ -1459,18 +2021,20 @@ impl AdminServiceShared {
         let mut pending_peers = vec![];
         let mut pending_members = vec![];
         let mut added_peers: Vec<PeerTokenPair> = vec![];
+        self.add_relayed_peers(members, relayed_members, &local_required_auth);
         for node in members {
             let peer_token_pair =
                 PeerTokenPair::new(node.token.clone(), local_required_auth.clone());
-            if !self.is_local_node(&node.token) {
+            if relayed_members.contains(&node.node_id) {
+                // Relayed members are not peered with; their service protocol requests are sent
+                // through the relay nodes once the relay nodes are peered
+                if self.service_protocols.get(&peer_token_pair).is_none() {
+                    missing_protocol_ids.push(node.clone())
+                }
+            } else if !self.is_local_node(&node.token) {
                 debug!("Referencing node {:?}", &node.token);
                 let peer_ref = self
-                    .peer_connector
-                    .add_peer_ref(
-                        node.token.clone(),
-                        node.endpoints.to_vec(),
-                        local_required_auth.clone(),
-                    )
+                    .request_member_peer(node, &local_required_auth)
                     .map_err(|err| {
                         // remove all peer refs added for this proposal
                         self.remove_peer_refs(added_peers.to_vec());
@@ -1499,6 +2063,20 @@ impl AdminServiceShared {
 
         if missing_protocol_ids.is_empty() {
             self.pending_circuit_payloads.push_back(payload);
+        } else if pending_peers.is_empty() {
+            debug!(
+                "Members {:?} added; awaiting service protocol agreement before proceeding",
+                &missing_protocol_ids
+            );
+            let pending_payload = PendingPayload {
+                unpeered_ids: vec![],
+                missing_protocol_ids,
+                payload_type: PayloadType::Circuit(payload),
+                members: pending_members,
+                message_sender,
+            };
+            self.send_relayed_protocol_requests(&pending_payload)?;
+            self.pending_protocol_payloads.push(pending_payload);
         } else {
             debug!(
                 "Members {:?} added; awaiting peering and service protocol agreement before \
@@ -1517,6 +2095,89 @@ impl AdminServiceShared {
         Ok(())
     }
 
+    /// Adds relay routes for admin messages to the members of a circuit that the local node
+    /// reaches through the circuit's relay nodes
+    ///
+    /// If the circuit uses the relay route type and the local node is not a relay node, every
+    /// other member that is not a relay node is reached through the relay nodes. Relay routes are
+    /// only used for circuits with trust authorization, since admin messages for challenge
+    /// authorization are addressed to the public keys of the directly connected peers.
+    ///
+    /// Returns the node IDs of the members that are reached through the relay nodes, which the
+    /// local node does not peer with directly.
+    pub fn add_relay_routes(
+        &mut self,
+        routes: &StoreRouteType,
+        authorization_type: &StoreAuthorizationType,
+        relay_nodes: &[String],
+        members: &[PeerNode],
+    ) -> Result<Vec<String>, AdminSharedError> {
+        if routes != &StoreRouteType::Relay
+            || authorization_type != &StoreAuthorizationType::Trust
+            || relay_nodes.contains(&self.node_id)
+        {
+            return Ok(vec![]);
+        }
+
+        let relayed_members = members
+            .iter()
+            .map(|node| node.node_id.to_string())
+            .filter(|node_id| node_id != &self.node_id && !relay_nodes.contains(node_id))
+            .collect::<Vec<_>>();
+
+        for node_id in &relayed_members {
+            self.routing_table_writer
+                .add_admin_relay_route(node_id.to_string(), relay_nodes.to_vec())
+                .map_err(|err| {
+                    AdminSharedError::SplinterStateError(format!(
+                        "Unable to add relay route to node {}: {}",
+                        node_id, err
+                    ))
+                })?;
+        }
+
+        Ok(relayed_members)
+    }
+
+    fn add_relayed_peers(
+        &mut self,
+        members: &[PeerNode],
+        relayed_members: &[String],
+        local_required_auth: &PeerAuthorizationToken,
+    ) {
+        for node in members {
+            if relayed_members.contains(&node.node_id) {
+                self.relayed_peers.insert(PeerTokenPair::new(
+                    node.token.clone(),
+                    local_required_auth.clone(),
+                ));
+            }
+        }
+    }
+
+    /// Sends service protocol requests to the members of a pending payload that are reached
+    /// through relay nodes and have not agreed on a protocol version yet
+    ///
+    /// Requests to other members are sent once they are peered, which never happens for relayed
+    /// members.
+    fn send_relayed_protocol_requests(
+        &mut self,
+        pending_payload: &PendingPayload,
+    ) -> Result<(), ServiceError> {
+        for node in &pending_payload.missing_protocol_ids {
+            let relayed_peer = self
+                .relayed_peers
+                .iter()
+                .find(|relayed_peer| relayed_peer.peer_id() == &node.token)
+                .cloned();
+            if let Some(peer_token_pair) = relayed_peer {
+                self.send_protocol_request(&peer_token_pair, &node.admin_service)?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Verify all members of the circuit to be disbanded are using a valid protocol version.
     /// If all circuit members have agreed on a protocol version, the disband payload is moved into
     /// the `pending_circuit_payloads` list for further processing. Otherwise, this payload is
@@ -1580,6 +2241,8 @@ impl AdminServiceShared {
                     ADMIN_SERVICE_PROTOCOL_VERSION,
                 )
                 .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+                self.validate_expiration(payload.get_circuit_create_request().get_expires_at())
+                    .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
 
                 self.propose_circuit(payload, "local".to_string())
             }
@@ -1637,6 +2300,8 @@ impl AdminServiceShared {
                     ADMIN_SERVICE_PROTOCOL_VERSION,
                 )
                 .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+                self.validate_expiration(payload.get_circuit_disband_request().get_expires_at())
+                    .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
 
                 self.propose_disband(
                     payload,
@@ -1645,6 +2310,89 @@ impl AdminServiceShared {
                     "local".to_string(),
                 )
             }
+            CircuitManagementPayload_Action::CIRCUIT_SUSPEND_REQUEST => {
+                let signer_public_key = header.get_requester();
+                let requester_node_id = header.get_requester_node_id();
+                let circuit_id = payload.get_circuit_suspend_request().get_circuit_id();
+                let circuit_proposal = self
+                    .make_suspend_request_circuit_proposal(
+                        circuit_id,
+                        signer_public_key,
+                        requester_node_id,
+                    )
+                    .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+
+                self.validate_suspend_circuit(
+                    circuit_proposal.get_circuit_proposal(),
+                    signer_public_key,
+                    requester_node_id,
+                    ADMIN_SERVICE_PROTOCOL_VERSION,
+                )
+                .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+                self.validate_expiration(payload.get_circuit_suspend_request().get_expires_at())
+                    .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+
+                self.propose_suspend(
+                    payload,
+                    signer_public_key,
+                    requester_node_id,
+                    "local".to_string(),
+                )
+            }
+            CircuitManagementPayload_Action::CIRCUIT_RESUME_REQUEST => {
+                let signer_public_key = header.get_requester();
+                let requester_node_id = header.get_requester_node_id();
+                let circuit_id = payload.get_circuit_resume_request().get_circuit_id();
+                let circuit_proposal = self
+                    .make_resume_request_circuit_proposal(
+                        circuit_id,
+                        signer_public_key,
+                        requester_node_id,
+                    )
+                    .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+
+                self.validate_resume_circuit(
+                    circuit_proposal.get_circuit_proposal(),
+                    signer_public_key,
+                    requester_node_id,
+                    ADMIN_SERVICE_PROTOCOL_VERSION,
+                )
+                .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+                self.validate_expiration(payload.get_circuit_resume_request().get_expires_at())
+                    .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+
+                self.propose_resume(
+                    payload,
+                    signer_public_key,
+                    requester_node_id,
+                    "local".to_string(),
+                )
+            }
+            CircuitManagementPayload_Action::CIRCUIT_UPDATE_NODE_KEYS_REQUEST => {
+                let signer_public_key = header.get_requester();
+                let requester_node_id = header.get_requester_node_id();
+                let update_request = payload.get_circuit_update_node_keys_request();
+                let circuit_proposal = self
+                    .make_update_node_keys_request_circuit_proposal(
+                        update_request,
+                        signer_public_key,
+                        requester_node_id,
+                    )
+                    .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+
+                self.validate_update_node_keys(
+                    update_request,
+                    circuit_proposal.get_circuit_proposal(),
+                    signer_public_key,
+                    requester_node_id,
+                    ADMIN_SERVICE_PROTOCOL_VERSION,
+                )
+                .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+                self.validate_expiration(update_request.get_expires_at())
+                    .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+
+                self.propose_update_node_keys(payload, "local".to_string())
+            }
             CircuitManagementPayload_Action::CIRCUIT_PURGE_REQUEST => {
                 let signer_public_key = header.get_requester();
                 let requester_node_id = header.get_requester_node_id();
@@ -1749,18 +2497,29 @@ impl AdminServiceShared {
                 )))
             })?;
 
+            let relayed_members = self
+                .add_relay_routes(
+                    store_proposed_circuit.routes(),
+                    store_proposed_circuit.authorization_type(),
+                    store_proposed_circuit.relay_nodes(),
+                    &peer_members,
+                )
+                .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+            self.add_relayed_peers(&peer_members, &relayed_members, &local_required_auth);
+
             for node in &peer_members {
                 let peer_token_pair =
                     PeerTokenPair::new(node.token.clone(), local_required_auth.clone());
-                if !self.is_local_node(peer_token_pair.peer_id()) {
+                if relayed_members.contains(&node.node_id) {
+                    // Relayed members are not peered with; their service protocol requests are
+                    // sent through the relay nodes
+                    if self.service_protocols.get(&peer_token_pair).is_none() {
+                        missing_protocol_ids.push(node.clone())
+                    }
+                } else if !self.is_local_node(peer_token_pair.peer_id()) {
                     debug!("Referencing node {:?}", &peer_token_pair);
                     let peer_ref = self
-                        .peer_connector
-                        .add_peer_ref(
-                            node.token.clone(),
-                            node.endpoints.to_vec(),
-                            local_required_auth.clone(),
-                        )
+                        .request_member_peer(node, &local_required_auth)
                         .map_err(|err| {
                             // remove all peer refs added for this proposal
                             self.remove_peer_refs(added_peers.to_vec());
@@ -1788,9 +2547,14 @@ impl AdminServiceShared {
                 );
             }
             members.extend(peer_members);
-        } else if payload.has_circuit_disband_request() {
-            // If a `CircuitDisbandRequest` is present in the payload, the members must be gathered
-            // from the admin store based on the provided circuit id.
+        } else if payload.has_circuit_disband_request()
+            || payload.has_circuit_suspend_request()
+            || payload.has_circuit_resume_request()
+            || payload.has_circuit_update_node_keys_request()
+        {
+            // If a `CircuitDisbandRequest`, `CircuitSuspendRequest`, `CircuitResumeRequest` or
+            // `CircuitUpdateNodeKeysRequest` is present in the payload, the members must be
+            // gathered from the admin store based on the provided circuit id.
             // If the members list has already been updated, the payload was to create a
             // new circuit.
             if !members.is_empty() {
@@ -1800,9 +2564,19 @@ impl AdminServiceShared {
                     ),
                 )));
             }
-            let circuit_id = payload.get_circuit_disband_request().get_circuit_id();
-            // If the proposed circuit is being disbanded, the circuit information must be
-            // gathered from the admin store, as the `CircuitDisbandRequest` only contains
+            let circuit_id = if payload.has_circuit_suspend_request() {
+                payload.get_circuit_suspend_request().get_circuit_id()
+            } else if payload.has_circuit_resume_request() {
+                payload.get_circuit_resume_request().get_circuit_id()
+            } else if payload.has_circuit_update_node_keys_request() {
+                payload
+                    .get_circuit_update_node_keys_request()
+                    .get_circuit_id()
+            } else {
+                payload.get_circuit_disband_request().get_circuit_id()
+            };
+            // If the proposed circuit is being disbanded, suspended or resumed, the circuit
+            // information must be gathered from the admin store, as the request only contains
             // the `circuit_id`.
             let circuit = self
                 .admin_store
@@ -1848,15 +2622,27 @@ impl AdminServiceShared {
                 )))
             })?;
 
+            let relayed_members = self
+                .add_relay_routes(
+                    circuit.routes(),
+                    circuit.authorization_type(),
+                    circuit.relay_nodes(),
+                    &tokens,
+                )
+                .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+            self.add_relayed_peers(&tokens, &relayed_members, &local_required_auth);
+
             for node in tokens {
                 let peer_token_pair =
                     PeerTokenPair::new(node.token.clone(), local_required_auth.clone());
-                // Verify each disband member has an agreed upon protocol version with this node
+                // Verify each member has an agreed upon protocol version with this node
                 // Otherwise, re-establish a peer connection
                 if !self.is_local_node(peer_token_pair.peer_id())
                     && self.service_protocols.get(&peer_token_pair).is_none()
                 {
-                    pending_peers.push(peer_token_pair.clone());
+                    if !relayed_members.contains(&node.node_id) {
+                        pending_peers.push(peer_token_pair.clone());
+                    }
                     missing_protocol_ids.push(node.clone())
                 }
             }
@@ -1878,13 +2664,15 @@ impl AdminServiceShared {
                 &missing_protocol_ids
             );
 
-            self.pending_protocol_payloads.push(PendingPayload {
+            let pending_payload = PendingPayload {
                 unpeered_ids: pending_peers,
                 missing_protocol_ids,
                 payload_type: PayloadType::Consensus(proposal.id.clone(), (proposal, payload)),
                 members: pending_members,
                 message_sender,
-            });
+            };
+            self.send_relayed_protocol_requests(&pending_payload)?;
+            self.pending_protocol_payloads.push(pending_payload);
             Ok(())
         }
     }
@@ -2017,6 +2805,20 @@ impl AdminServiceShared {
             self.pending_protocol_payloads.push(peered_payload);
         }
 
+        // The newly connected peer may be a relay node, through which the service protocol
+        // requests to relayed members can now be sent
+        let pending_protocol_payloads = std::mem::take(&mut self.pending_protocol_payloads);
+        let result = pending_protocol_payloads
+            .iter()
+            .try_for_each(|pending_payload| self.send_relayed_protocol_requests(pending_payload));
+        self.pending_protocol_payloads = pending_protocol_payloads;
+        result.map_err(|err| {
+            AdminSharedError::ServiceProtocolError(format!(
+                "Unable to send service protocol request: {}",
+                err
+            ))
+        })?;
+
         // Ignore own admin service
         if self.is_local_admin_service(peer_id) {
             return Ok(());
@@ -2332,10 +3134,16 @@ impl AdminServiceShared {
         // If the proposal has type `CircuitProposal_ProposalType::DISBAND`, the proposal is
         // intended to disband a circuit and the associated services will need to be stopped. In
         // this case, the next step is to `cleanup_disbanded_circuit_if_members_ready`.
-        if circuit_proposal_type == CircuitProposal_ProposalType::DISBAND {
-            self.cleanup_disbanded_circuit_if_members_ready(&circuit_id)
-        } else {
-            self.initialize_services_if_members_ready(&circuit_id)
+        // If the proposal has type `SUSPEND` or `RESUME`, the circuit's services are stopped or
+        // restarted by `apply_circuit_status_change_if_members_ready`.
+        match circuit_proposal_type {
+            CircuitProposal_ProposalType::DISBAND => {
+                self.cleanup_disbanded_circuit_if_members_ready(&circuit_id)
+            }
+            CircuitProposal_ProposalType::SUSPEND | CircuitProposal_ProposalType::RESUME => {
+                self.apply_circuit_status_change_if_members_ready(&circuit_id)
+            }
+            _ => self.initialize_services_if_members_ready(&circuit_id),
         }
     }
 
@@ -2344,7 +3152,8 @@ impl AdminServiceShared {
     /// voted for. If the proposal type is `Create`, the vote submitted pertains to creating a
     /// circuit so the services must be initialized if all members are now ready. If the proposal
     /// type is disband, the vote submitted pertains to disbanding a circuit so the services
-    /// must be stopped if all members are now ready.
+    /// must be stopped if all members are now ready. Suspend and resume votes stop or restart
+    /// the circuit's services once all members are ready.
     pub fn add_ready_member(
         &mut self,
         circuit_id: &str,
@@ -2388,6 +3197,9 @@ impl AdminServiceShared {
         // associated circuit proposal's type.
         match proposal_type {
             ProposalType::Disband => self.cleanup_disbanded_circuit_if_members_ready(circuit_id),
+            ProposalType::Suspend | ProposalType::Resume => {
+                self.apply_circuit_status_change_if_members_ready(circuit_id)
+            }
             _ => self.initialize_services_if_members_ready(circuit_id),
         }
     }
@@ -2468,6 +3280,10 @@ impl AdminServiceShared {
                     return Err(AdminSharedError::ValidationFailed(
                         "Proposed circuit cannot have a circuit status on protocol 1".to_string(),
                     ));
+                } else if circuit.has_voting_policy() {
+                    return Err(AdminSharedError::ValidationFailed(
+                        "Proposed circuit cannot have a voting policy on protocol 1".to_string(),
+                    ));
                 }
                 // check that the circuit includes supported versions
                 match circuit.get_circuit_version() {
@@ -2540,12 +3356,72 @@ impl AdminServiceShared {
         Ok(())
     }
 
-    fn validate_key(&self, public_key: &[u8]) -> Result<(), AdminSharedError> {
-        if public_key.len() != 33 {
-            return Err(AdminSharedError::ValidationFailed(format!(
-                "{} is not a valid public key: invalid length",
-                to_hex(public_key)
-            )));
+    fn validate_voting_policy(
+        &self,
+        voting_policy: &Circuit_VotingPolicy,
+        members: &[String],
+    ) -> Result<(), AdminSharedError> {
+        let mut weighted_members: Vec<&str> = Vec::new();
+        for node_weight in voting_policy.get_weights() {
+            let node_id = node_weight.get_node_id();
+            if !members.iter().any(|member| member == node_id) {
+                return Err(AdminSharedError::ValidationFailed(format!(
+                    "Voting policy weight is for a node that is not in members: {}",
+                    node_id
+                )));
+            } else if weighted_members.contains(&node_id) {
+                return Err(AdminSharedError::ValidationFailed(
+                    "Every member may only have one voting policy weight".to_string(),
+                ));
+            } else if node_weight.get_weight() == 0 {
+                return Err(AdminSharedError::ValidationFailed(format!(
+                    "Voting policy weight for {} must be greater than 0",
+                    node_id
+                )));
+            } else {
+                weighted_members.push(node_id);
+            }
+        }
+
+        let total_weight = members.iter().try_fold(0u32, |total, member| {
+            let weight = voting_policy
+                .get_weights()
+                .iter()
+                .find(|node_weight| node_weight.get_node_id() == member)
+                .map(|node_weight| node_weight.get_weight())
+                .unwrap_or(1);
+            total.checked_add(weight).ok_or_else(|| {
+                AdminSharedError::ValidationFailed(
+                    "The total voting policy weight is too large".to_string(),
+                )
+            })
+        })?;
+
+        let threshold = voting_policy.get_threshold();
+        if voting_policy.get_policy_type() == Circuit_VotingPolicy_PolicyType::THRESHOLD {
+            if threshold == 0 || threshold > total_weight {
+                return Err(AdminSharedError::ValidationFailed(format!(
+                    "Voting policy threshold must be between 1 and the total weight of the \
+                     members ({}), was {}",
+                    total_weight, threshold
+                )));
+            }
+        } else if threshold != 0 {
+            return Err(AdminSharedError::ValidationFailed(
+                "Voting policy threshold may only be set if the policy type is threshold"
+                    .to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn validate_key(&self, public_key: &[u8]) -> Result<(), AdminSharedError> {
+        if public_key.len() != 33 {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "{} is not a valid public key: invalid length",
+                to_hex(public_key)
+            )));
         }
 
         Ok(())
@@ -2666,6 +3542,37 @@ impl AdminServiceShared {
             )));
         }
 
+        let relay_nodes = circuit.get_relay_nodes();
+        if circuit.get_routes() == Circuit_RouteType::RELAY_ROUTE {
+            if relay_nodes.is_empty() {
+                return Err(AdminSharedError::ValidationFailed(
+                    "A circuit with route type relay must have relay nodes".to_string(),
+                ));
+            }
+
+            let mut seen_relay_nodes: Vec<&String> = Vec::new();
+            for relay_node in relay_nodes {
+                if !members.contains(relay_node) {
+                    return Err(AdminSharedError::ValidationFailed(format!(
+                        "Relay node is not in members: {}",
+                        relay_node
+                    )));
+                } else if seen_relay_nodes.contains(&relay_node) {
+                    return Err(AdminSharedError::ValidationFailed(
+                        "Every relay node must be unique in the circuit.".to_string(),
+                    ));
+                } else {
+                    seen_relay_nodes.push(relay_node);
+                }
+            }
+        } else if !relay_nodes.is_empty() {
+            return Err(AdminSharedError::ValidationFailed(
+                "Relay nodes may only be set if the route type is relay".to_string(),
+            ));
+        }
+
+        self.validate_voting_policy(circuit.get_voting_policy(), &members)?;
+
         if circuit.get_roster().is_empty() {
             return Err(AdminSharedError::ValidationFailed(
                 "The circuit must have services".to_string(),
@@ -2912,6 +3819,226 @@ impl AdminServiceShared {
         Ok(())
     }
 
+    /// Validates a `CircuitSuspendRequest` using the following:
+    ///
+    /// - Validate the protocol version used by the submitter node. Suspending is only available
+    ///   to nodes using `ADMIN_SERVICE_PROTOCOL_VERSION` 2.
+    /// - Validate the requester is authorized to propose a change for the requesting node
+    /// - Validate the signer's public key is authorized for the requesting node
+    /// - Validate a `CircuitProposal` with the same ID is not present
+    /// - Validate the circuit being suspended has a `circuit_version` of at least 2 and a
+    ///   `circuit_status` of `Active`
+    fn validate_suspend_circuit(
+        &self,
+        circuit: &Circuit,
+        signer_public_key: &[u8],
+        requester_node_id: &str,
+        protocol: u32,
+    ) -> Result<(), AdminSharedError> {
+        self.validate_status_change_circuit(
+            circuit,
+            signer_public_key,
+            requester_node_id,
+            protocol,
+            "suspend",
+            &StoreCircuitStatus::Active,
+        )
+    }
+
+    /// Validates a `CircuitResumeRequest` using the following:
+    ///
+    /// - Validate the protocol version used by the submitter node. Resuming is only available to
+    ///   nodes using `ADMIN_SERVICE_PROTOCOL_VERSION` 2.
+    /// - Validate the requester is authorized to propose a change for the requesting node
+    /// - Validate the signer's public key is authorized for the requesting node
+    /// - Validate a `CircuitProposal` with the same ID is not present
+    /// - Validate the circuit being resumed has a `circuit_version` of at least 2 and a
+    ///   `circuit_status` of `Suspended`
+    fn validate_resume_circuit(
+        &self,
+        circuit: &Circuit,
+        signer_public_key: &[u8],
+        requester_node_id: &str,
+        protocol: u32,
+    ) -> Result<(), AdminSharedError> {
+        self.validate_status_change_circuit(
+            circuit,
+            signer_public_key,
+            requester_node_id,
+            protocol,
+            "resume",
+            &StoreCircuitStatus::Suspended,
+        )
+    }
+
+    /// Performs the validation shared by requests that change the status of an existing circuit.
+    /// `change` names the request in error messages and `required_status` is the status the
+    /// stored circuit must currently have.
+    fn validate_status_change_circuit(
+        &self,
+        circuit: &Circuit,
+        signer_public_key: &[u8],
+        requester_node_id: &str,
+        protocol: u32,
+        change: &str,
+        required_status: &StoreCircuitStatus,
+    ) -> Result<(), AdminSharedError> {
+        if protocol != ADMIN_SERVICE_PROTOCOL_VERSION {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Circuit {} is not available for protocol version {}",
+                change, protocol
+            )));
+        }
+
+        if requester_node_id.is_empty() {
+            return Err(AdminSharedError::ValidationFailed(
+                "requester_node_id is empty".to_string(),
+            ));
+        }
+
+        self.validate_key(signer_public_key)?;
+
+        if !self
+            .key_verifier
+            .is_permitted(requester_node_id, signer_public_key)?
+        {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "{} is not registered for the requester node {}",
+                to_hex(signer_public_key),
+                requester_node_id,
+            )));
+        }
+
+        self.key_permission_manager
+            .is_permitted(signer_public_key, PROPOSER_ROLE)
+            .map_err(|_| {
+                AdminSharedError::ValidationFailed(format!(
+                    "{} is not permitted to {} for node {}",
+                    to_hex(signer_public_key),
+                    change,
+                    requester_node_id
+                ))
+            })?;
+
+        if self.has_proposal(circuit.get_circuit_id())? {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Ignoring duplicate proposal for circuit {}",
+                circuit.get_circuit_id()
+            )));
+        }
+
+        let stored_circuit = self
+            .admin_store
+            .get_circuit(circuit.get_circuit_id())
+            .map_err(|err| {
+                AdminSharedError::ValidationFailed(format!(
+                    "error occurred when trying to get circuit {}",
+                    err
+                ))
+            })?
+            .ok_or_else(|| {
+                AdminSharedError::ValidationFailed(format!(
+                    "Received {} request for a circuit that does not exist: circuit id {}",
+                    change,
+                    circuit.get_circuit_id()
+                ))
+            })?;
+
+        if stored_circuit.circuit_status() != required_status {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Attempting to {} circuit {} with status {:?}, must be {:?}",
+                change,
+                circuit.get_circuit_id(),
+                stored_circuit.circuit_status(),
+                required_status,
+            )));
+        }
+
+        if stored_circuit.circuit_version() < CIRCUIT_PROTOCOL_VERSION {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Attempting to {} a circuit with schema version {}, must be {}",
+                change,
+                stored_circuit.circuit_version(),
+                CIRCUIT_PROTOCOL_VERSION,
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Validates a `CircuitUpdateNodeKeysRequest` using the following:
+    ///
+    /// - Validate the request is valid for the circuit's current state, as with a suspend
+    ///   request. The circuit must be `Active`.
+    /// - Validate the requester is the node whose keys are being updated
+    /// - Validate the new public key is valid, differs from the node's current key and is not
+    ///   used by another member of the circuit
+    fn validate_update_node_keys(
+        &self,
+        update_request: &CircuitUpdateNodeKeysRequest,
+        circuit: &Circuit,
+        signer_public_key: &[u8],
+        requester_node_id: &str,
+        protocol: u32,
+    ) -> Result<(), AdminSharedError> {
+        self.validate_status_change_circuit(
+            circuit,
+            signer_public_key,
+            requester_node_id,
+            protocol,
+            "update node keys on",
+            &StoreCircuitStatus::Active,
+        )?;
+
+        if update_request.get_node_id() != requester_node_id {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Node {} may not update the keys of node {}",
+                requester_node_id,
+                update_request.get_node_id()
+            )));
+        }
+
+        self.validate_key(update_request.get_public_key())?;
+
+        let stored_circuit = self
+            .admin_store
+            .get_circuit(circuit.get_circuit_id())?
+            .ok_or_else(|| {
+                AdminSharedError::ValidationFailed(format!(
+                    "Received update node keys request for a circuit that does not exist: \
+                     circuit id {}",
+                    circuit.get_circuit_id()
+                ))
+            })?;
+
+        for member in stored_circuit.members() {
+            let uses_key = member
+                .public_key()
+                .as_ref()
+                .map(|public_key| public_key.as_slice() == update_request.get_public_key())
+                .unwrap_or(false);
+
+            if uses_key {
+                return Err(AdminSharedError::ValidationFailed(
+                    if member.node_id() == update_request.get_node_id() {
+                        format!(
+                            "Node {} already uses the requested public key",
+                            member.node_id()
+                        )
+                    } else {
+                        format!(
+                            "Public key is already used by member {} of circuit {}",
+                            member.node_id(),
+                            circuit.get_circuit_id()
+                        )
+                    },
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
     /// Validates a `CircuitPurgeRequest` using the following:
     ///
     /// - Validate the requester is authorized to propose a change on the requesting node
@@ -3126,6 +4253,19 @@ impl AdminServiceShared {
         Ok(())
     }
 
+    /// Validates that the requested expiration time of a new proposal has not already passed. An
+    /// expiration time of 0 means the proposal does not expire.
+    fn validate_expiration(&self, expires_at: u64) -> Result<(), AdminSharedError> {
+        if expires_at != 0 && expires_at <= unix_time_now()? {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Proposal expiration time {} has already passed",
+                expires_at
+            )));
+        }
+
+        Ok(())
+    }
+
     fn validate_circuit_management_payload(
         &self,
         payload: &CircuitManagementPayload,
@@ -3164,56 +4304,277 @@ impl AdminServiceShared {
         Ok(())
     }
 
-    fn check_approved(&self, proposal: &CircuitProposal) -> CircuitProposalStatus {
-        let mut received_votes = HashSet::new();
-        for vote in proposal.get_votes() {
-            if vote.get_vote() == CircuitProposalVote_Vote::REJECT {
-                return CircuitProposalStatus::Rejected;
-            }
-            received_votes.insert(vote.get_voter_node_id().to_string());
-        }
+    /// Tallies the votes on the proposal against the circuit's voting policy.
+    ///
+    /// Proposals to create a circuit must be accepted by every member. Proposals that change an
+    /// existing circuit are tallied against the voting policy of the stored circuit, not the one
+    /// in the proposal, so a change to the voting policy must pass under the current policy.
+    ///
+    /// The requester is counted as accepting the proposal. The proposal is rejected once the
+    /// weight of the members that have not rejected it can no longer meet the required weight.
+    fn check_approved(
+        &self,
+        proposal: &CircuitProposal,
+    ) -> Result<CircuitProposalStatus, AdminSharedError> {
+        let circuit = proposal.get_circuit_proposal();
+        let voting_policy = self.proposal_voting_policy(proposal)?;
 
-        let mut required_votes = proposal
-            .get_circuit_proposal()
+        let members = circuit
             .get_members()
-            .to_vec()
             .iter()
-            .map(|member| member.get_node_id().to_string())
-            .collect::<HashSet<String>>();
+            .map(|member| member.get_node_id())
+            .collect::<HashSet<&str>>();
+
+        let total_weight: u32 = members
+            .iter()
+            .map(|node_id| voting_policy.weight(node_id))
+            .sum();
+        let required_weight = voting_policy.required_weight(total_weight);
 
-        required_votes.remove(proposal.get_requester_node_id());
+        let mut counted_voters = HashSet::new();
+        let mut accepted_weight = 0;
+        let mut rejected_weight = 0;
 
-        if required_votes == received_votes {
-            CircuitProposalStatus::Accepted
+        let requester = proposal.get_requester_node_id();
+        if members.contains(requester) {
+            counted_voters.insert(requester);
+            accepted_weight += voting_policy.weight(requester);
+        }
+
+        for vote in proposal.get_votes() {
+            let voter = vote.get_voter_node_id();
+            if !members.contains(voter) || !counted_voters.insert(voter) {
+                continue;
+            }
+
+            if vote.get_vote() == CircuitProposalVote_Vote::REJECT {
+                rejected_weight += voting_policy.weight(voter);
+            } else {
+                accepted_weight += voting_policy.weight(voter);
+            }
+        }
+
+        if accepted_weight >= required_weight {
+            Ok(CircuitProposalStatus::Accepted)
+        } else if total_weight - rejected_weight < required_weight {
+            Ok(CircuitProposalStatus::Rejected)
         } else {
-            CircuitProposalStatus::Pending
+            Ok(CircuitProposalStatus::Pending)
         }
     }
 
-    /// Makes the `CircuitProposal` associated with a `CircuitDisbandRequest` based on information
-    /// gathered from the currently active circuit that is specified in the disband request
-    fn make_disband_request_circuit_proposal(
+    /// Returns the voting policy that decides the proposal.
+    ///
+    /// Proposals to create a circuit are decided unanimously; proposals that change an existing
+    /// circuit are decided by the voting policy of the stored circuit.
+    fn proposal_voting_policy(
         &self,
-        circuit_id: &str,
-        requester: &[u8],
-        requester_node_id: &str,
-    ) -> Result<CircuitProposal, AdminSharedError> {
-        let store_circuit = self
+        proposal: &CircuitProposal,
+    ) -> Result<VotingPolicy, AdminSharedError> {
+        if proposal.get_proposal_type() == CircuitProposal_ProposalType::CREATE {
+            return Ok(VotingPolicy::default());
+        }
+
+        Ok(self
             .admin_store
-            .get_circuit(circuit_id)
+            .get_circuit(proposal.get_circuit_id())?
+            .ok_or_else(|| {
+                AdminSharedError::SplinterStateError(format!(
+                    "Unable to find circuit {} to check the voting policy of its proposal",
+                    proposal.get_circuit_id()
+                ))
+            })?
+            .voting_policy()
+            .clone())
+    }
+
+    /// Returns the node IDs of the members that must verify a change to the proposal before the
+    /// change is committed.
+    ///
+    /// Every member must verify changes to proposals that are decided unanimously. Otherwise, only
+    /// the local node and the members that the local admin service has agreed on a protocol
+    /// version with are required, provided they hold the weight required by the voting policy, so
+    /// that an unresponsive member does not stall the proposal. Members that are not required do
+    /// not record the change.
+    pub fn required_verifiers(
+        &self,
+        proposal: &CircuitProposal,
+    ) -> Result<Vec<String>, AdminSharedError> {
+        let circuit = proposal.get_circuit_proposal();
+        let members = circuit
+            .get_members()
+            .iter()
+            .map(|member| member.get_node_id().to_string())
+            .collect::<Vec<_>>();
+
+        let voting_policy = self.proposal_voting_policy(proposal)?;
+        if voting_policy.policy_type() == &VotingPolicyType::Unanimous {
+            return Ok(members);
+        }
+
+        let local_required_auth = circuit
+            .get_node_token(&self.node_id)
             .map_err(|err| {
-                AdminSharedError::ValidationFailed(format!(
-                    "error occurred when trying to get circuit {}",
+                AdminSharedError::SplinterStateError(format!(
+                    "Unable to get local nodes token: {}",
                     err
                 ))
             })?
             .ok_or_else(|| {
-                AdminSharedError::SplinterStateError(format!(
-                    "Unable to get circuit: {}",
-                    circuit_id
+                AdminSharedError::SplinterStateError(
+                    "Circuit does not have the local node".to_string(),
+                )
+            })?;
+
+        let responsive_members = circuit
+            .list_nodes()
+            .map_err(|err| {
+                AdminSharedError::SplinterStateError(format!(
+                    "Unable to get peer tokens for members: {}",
+                    err
+                ))
+            })?
+            .into_iter()
+            .filter(|node| {
+                node.node_id == self.node_id
+                    || self.service_protocols.contains_key(&PeerTokenPair::new(
+                        node.token.clone(),
+                        local_required_auth.clone(),
+                    ))
+            })
+            .map(|node| node.node_id)
+            .collect::<Vec<_>>();
+
+        let total_weight: u32 = members
+            .iter()
+            .map(|node_id| voting_policy.weight(node_id))
+            .sum();
+        let responsive_weight: u32 = responsive_members
+            .iter()
+            .map(|node_id| voting_policy.weight(node_id))
+            .sum();
+
+        if responsive_weight >= voting_policy.required_weight(total_weight) {
+            Ok(responsive_members)
+        } else {
+            Ok(members)
+        }
+    }
+
+    /// Makes the `CircuitProposal` associated with a `CircuitDisbandRequest` based on information
+    /// gathered from the currently active circuit that is specified in the disband request
+    fn make_disband_request_circuit_proposal(
+        &self,
+        circuit_id: &str,
+        requester: &[u8],
+        requester_node_id: &str,
+    ) -> Result<CircuitProposal, AdminSharedError> {
+        self.make_status_change_circuit_proposal(
+            circuit_id,
+            requester,
+            requester_node_id,
+            CircuitProposal_ProposalType::DISBAND,
+            &messages::CircuitStatus::Disbanded,
+        )
+    }
+
+    /// Makes the `CircuitProposal` associated with a `CircuitSuspendRequest` based on information
+    /// gathered from the currently active circuit that is specified in the suspend request
+    fn make_suspend_request_circuit_proposal(
+        &self,
+        circuit_id: &str,
+        requester: &[u8],
+        requester_node_id: &str,
+    ) -> Result<CircuitProposal, AdminSharedError> {
+        self.make_status_change_circuit_proposal(
+            circuit_id,
+            requester,
+            requester_node_id,
+            CircuitProposal_ProposalType::SUSPEND,
+            &messages::CircuitStatus::Suspended,
+        )
+    }
+
+    /// Makes the `CircuitProposal` associated with a `CircuitResumeRequest` based on information
+    /// gathered from the currently suspended circuit that is specified in the resume request
+    fn make_resume_request_circuit_proposal(
+        &self,
+        circuit_id: &str,
+        requester: &[u8],
+        requester_node_id: &str,
+    ) -> Result<CircuitProposal, AdminSharedError> {
+        self.make_status_change_circuit_proposal(
+            circuit_id,
+            requester,
+            requester_node_id,
+            CircuitProposal_ProposalType::RESUME,
+            &messages::CircuitStatus::Active,
+        )
+    }
+
+    /// Makes the `CircuitProposal` associated with a `CircuitUpdateNodeKeysRequest`. The proposed
+    /// circuit is a copy of the existing circuit with the public key of the requested member
+    /// replaced.
+    fn make_update_node_keys_request_circuit_proposal(
+        &self,
+        update_request: &CircuitUpdateNodeKeysRequest,
+        requester: &[u8],
+        requester_node_id: &str,
+    ) -> Result<CircuitProposal, AdminSharedError> {
+        let mut circuit_proposal = self.make_status_change_circuit_proposal(
+            update_request.get_circuit_id(),
+            requester,
+            requester_node_id,
+            CircuitProposal_ProposalType::UPDATE_NODE_KEYS,
+            &messages::CircuitStatus::Active,
+        )?;
+
+        let mut proposed_circuit = circuit_proposal.take_circuit_proposal();
+        let member = proposed_circuit
+            .mut_members()
+            .iter_mut()
+            .find(|member| member.get_node_id() == update_request.get_node_id())
+            .ok_or_else(|| {
+                AdminSharedError::ValidationFailed(format!(
+                    "Node {} is not a member of circuit {}",
+                    update_request.get_node_id(),
+                    update_request.get_circuit_id()
+                ))
+            })?;
+        member.set_public_key(update_request.get_public_key().to_vec());
+
+        circuit_proposal.set_circuit_hash(sha256(&proposed_circuit)?);
+        circuit_proposal.set_circuit_proposal(proposed_circuit);
+
+        Ok(circuit_proposal)
+    }
+
+    /// Makes a `CircuitProposal` that changes the status of an existing circuit. The proposed
+    /// circuit is a copy of the circuit in the admin store with the given `circuit_status`.
+    fn make_status_change_circuit_proposal(
+        &self,
+        circuit_id: &str,
+        requester: &[u8],
+        requester_node_id: &str,
+        proposal_type: CircuitProposal_ProposalType,
+        circuit_status: &messages::CircuitStatus,
+    ) -> Result<CircuitProposal, AdminSharedError> {
+        let store_circuit = self
+            .admin_store
+            .get_circuit(circuit_id)
+            .map_err(|err| {
+                AdminSharedError::ValidationFailed(format!(
+                    "error occurred when trying to get circuit {}",
+                    err
+                ))
+            })?
+            .ok_or_else(|| {
+                AdminSharedError::SplinterStateError(format!(
+                    "Unable to get circuit: {}",
+                    circuit_id
                 ))
             })?;
-        // Collecting the endpoints of the nodes apart of the circuit being disbanded
+        // Collecting the endpoints of the nodes apart of the circuit being changed
         let circuit_members = store_circuit
             .members()
             .iter()
@@ -3254,9 +4615,11 @@ impl AdminServiceShared {
             ))
             .with_durability(&messages::DurabilityType::from(store_circuit.durability()))
             .with_routes(&messages::RouteType::from(store_circuit.routes()))
+            .with_relay_nodes(store_circuit.relay_nodes())
+            .with_voting_policy(&messages::VotingPolicy::from(store_circuit.voting_policy()))
             .with_circuit_management_type(store_circuit.circuit_management_type())
             .with_circuit_version(store_circuit.circuit_version())
-            .with_circuit_status(&messages::CircuitStatus::Disbanded);
+            .with_circuit_status(circuit_status);
 
         if let Some(display_name) = store_circuit.display_name() {
             create_circuit_builder = create_circuit_builder.with_display_name(display_name);
@@ -3279,7 +4642,7 @@ impl AdminServiceShared {
             })?;
 
         let mut circuit_proposal = CircuitProposal::new();
-        circuit_proposal.set_proposal_type(CircuitProposal_ProposalType::DISBAND);
+        circuit_proposal.set_proposal_type(proposal_type);
         circuit_proposal.set_circuit_id(circuit_id.to_string());
         circuit_proposal.set_circuit_hash(sha256(&proposed_circuit)?);
         circuit_proposal.set_circuit_proposal(proposed_circuit);
@@ -3335,6 +4698,12 @@ impl AdminServiceShared {
         circuit.set_persistence(Circuit_PersistenceType::from(store_circuit.persistence()));
         circuit.set_durability(Circuit_DurabilityType::from(store_circuit.durability()));
         circuit.set_routes(Circuit_RouteType::from(store_circuit.routes()));
+        circuit.set_relay_nodes(RepeatedField::from_vec(
+            store_circuit.relay_nodes().to_vec(),
+        ));
+        if !store_circuit.voting_policy().is_default() {
+            circuit.set_voting_policy(Circuit_VotingPolicy::from(store_circuit.voting_policy()));
+        }
         circuit.set_circuit_management_type(store_circuit.circuit_management_type().to_string());
         if let Some(display) = store_circuit.display_name() {
             circuit.set_display_name(display.to_string());
@@ -3351,6 +4720,8 @@ impl AdminServiceShared {
             .with_persistence(store_circuit.persistence())
             .with_durability(store_circuit.durability())
             .with_routes(store_circuit.routes())
+            .with_relay_nodes(store_circuit.relay_nodes())
+            .with_voting_policy(store_circuit.voting_policy())
             .with_circuit_management_type(store_circuit.circuit_management_type())
             .with_circuit_version(store_circuit.circuit_version())
             .with_circuit_status(&StoreCircuitStatus::Abandoned);
@@ -3535,6 +4906,8 @@ impl AdminServiceShared {
                         circuit_id
                     ))
                 })?;
+            #[cfg(feature = "circuit-rate-limiting")]
+            self.remove_circuit_rate_limits(circuit_proposal.get_circuit_id());
 
             let proposed_circuit = ProposedCircuit::from_proto(
                 circuit_proposal.take_circuit_proposal(),
@@ -3558,12 +4931,259 @@ impl AdminServiceShared {
         Ok(())
     }
 
-    /// Collect all circuits from the admin store, including `Disbanded` or `Abandoned` circuits
+    /// Applies an accepted update of member node keys to this node's routing table and peer
+    /// connections. Peer references are added using the updated keys and the references for the
+    /// previous keys are held briefly before being removed, so in-flight messages are not
+    /// dropped.
+    fn update_node_keys(
+        &mut self,
+        previous_circuit: &StoreCircuit,
+        circuit: &StoreCircuit,
+    ) -> Result<(), AdminSharedError> {
+        self.update_routing_table_node_keys(previous_circuit, circuit)?;
+
+        let previous_tokens = self.list_remote_peer_tokens(previous_circuit)?;
+        let local_required_auth = circuit
+            .get_node_token(&self.node_id)
+            .map_err(|err| {
+                AdminSharedError::SplinterStateError(format!(
+                    "Unable to get local nodes token: {}",
+                    err
+                ))
+            })?
+            .ok_or_else(|| {
+                AdminSharedError::SplinterStateError(
+                    "Circuit does not have the local node".to_string(),
+                )
+            })?;
+
+        let mut current_tokens = vec![];
+        for node in circuit.list_nodes().map_err(|err| {
+            AdminSharedError::SplinterStateError(format!(
+                "Unable to get peer tokens for members of {}: {}",
+                circuit.circuit_id(),
+                err
+            ))
+        })? {
+            if node.node_id == self.node_id {
+                continue;
+            }
+
+            let peer_token_pair =
+                PeerTokenPair::new(node.token.clone(), local_required_auth.clone());
+            if !previous_tokens.contains(&peer_token_pair) {
+                debug!("Referencing node {:?}", &peer_token_pair);
+                let peer_ref = self
+                    .request_member_peer(&node, &local_required_auth)
+                    .map_err(|err| {
+                        AdminSharedError::SplinterStateError(format!(
+                            "Unable to add peer ref for node {}: {}",
+                            node.node_id, err
+                        ))
+                    })?;
+                self.add_peer_ref(peer_ref);
+                self.token_to_peer.insert(
+                    peer_token_pair.clone(),
+                    PeerNodePair {
+                        peer_node: node,
+                        local_peer_token: local_required_auth.clone(),
+                    },
+                );
+            }
+            current_tokens.push(peer_token_pair);
+        }
+
+        let replaced_tokens = previous_tokens
+            .into_iter()
+            .filter(|token| !current_tokens.contains(token))
+            .collect::<Vec<_>>();
+        if !replaced_tokens.is_empty() {
+            self.peers_to_be_removed
+                .push((Instant::now(), replaced_tokens));
+        }
+
+        info!("Updated node keys on circuit {}", circuit.circuit_id());
+
+        Ok(())
+    }
+
+    /// Updates the routing table entries of the members whose keys changed on the circuit.
+    ///
+    /// The routing table holds a single entry for each node, which is shared by all of the
+    /// node's circuits. A node's entry is only updated once its other active and suspended
+    /// circuits use the same key; until then, messages to the node keep using the key of its
+    /// entry, which the node's other circuits keep connected.
+    fn update_routing_table_node_keys(
+        &mut self,
+        previous_circuit: &StoreCircuit,
+        circuit: &StoreCircuit,
+    ) -> Result<(), AdminSharedError> {
+        for node in circuit.members() {
+            let previous_public_key = previous_circuit
+                .members()
+                .iter()
+                .find(|previous_node| previous_node.node_id() == node.node_id())
+                .and_then(|previous_node| previous_node.public_key().clone());
+            if &previous_public_key == node.public_key() {
+                continue;
+            }
+
+            let predicates = vec![
+                CircuitPredicate::MembersInclude(vec![node.node_id().to_string()]),
+                CircuitPredicate::CircuitStatus(StoreCircuitStatus::Active),
+                CircuitPredicate::CircuitStatus(StoreCircuitStatus::Suspended),
+            ];
+            let other_key_in_use = self
+                .admin_store
+                .list_circuits(&predicates)?
+                .filter(|other_circuit| other_circuit.circuit_id() != circuit.circuit_id())
+                .any(|other_circuit| {
+                    other_circuit.members().iter().any(|other_node| {
+                        other_node.node_id() == node.node_id()
+                            && other_node.public_key() != node.public_key()
+                    })
+                });
+            if other_key_in_use {
+                info!(
+                    "Not updating the key of node {} in the routing table until its other \
+                     circuits use the same key",
+                    node.node_id()
+                );
+                continue;
+            }
+
+            self.routing_table_writer
+                .add_node(
+                    node.node_id().to_string(),
+                    routing::CircuitNode::new(
+                        node.node_id().to_string(),
+                        node.endpoints().to_vec(),
+                        node.public_key().clone(),
+                    ),
+                )
+                .map_err(|_| {
+                    AdminSharedError::SplinterStateError(format!(
+                        "Unable to update node {} in routing table",
+                        node.node_id()
+                    ))
+                })?;
+        }
+
+        Ok(())
+    }
+
+    /// Lists the peer tokens used to connect to the other members of the circuit
+    fn list_remote_peer_tokens(
+        &self,
+        circuit: &StoreCircuit,
+    ) -> Result<Vec<PeerTokenPair>, AdminSharedError> {
+        let local_required_auth = circuit
+            .get_node_token(&self.node_id)
+            .map_err(|err| {
+                AdminSharedError::SplinterStateError(format!(
+                    "Unable to get local nodes token: {}",
+                    err
+                ))
+            })?
+            .ok_or_else(|| {
+                AdminSharedError::SplinterStateError(
+                    "Circuit does not have the local node".to_string(),
+                )
+            })?;
+
+        Ok(circuit
+            .list_nodes()
+            .map_err(|err| {
+                AdminSharedError::SplinterStateError(format!(
+                    "Unable to get peer tokens for members of {}: {}",
+                    circuit.circuit_id(),
+                    err
+                ))
+            })?
+            .into_iter()
+            .filter(|node| node.node_id != self.node_id)
+            .map(|node| PeerTokenPair::new(node.token, local_required_auth.clone()))
+            .collect())
+    }
+
+    /// Verify all members are ready before suspending or resuming the circuit. Suspending a
+    /// circuit stops its services and marks it as suspended in the routing table, so service
+    /// messages are rejected; the services' state and the circuit's peer refs are kept. Resuming
+    /// a circuit reverses this and restarts its services.
+    pub fn apply_circuit_status_change_if_members_ready(
+        &mut self,
+        circuit_id: &str,
+    ) -> Result<(), AdminSharedError> {
+        let ready = {
+            if let Some(changed_circuit) = self.uninitialized_circuits.get(circuit_id) {
+                if let Some(ref circuit_proposal) = changed_circuit.circuit {
+                    let all_members = circuit_proposal
+                        .get_circuit_proposal()
+                        .members
+                        .iter()
+                        .map(|node| node.node_id.clone())
+                        .collect::<HashSet<String>>();
+                    all_members.is_subset(&changed_circuit.ready_members)
+                } else {
+                    false
+                }
+            } else {
+                false
+            }
+        };
+
+        if ready {
+            let circuit_proposal = self
+                .uninitialized_circuits
+                .remove(circuit_id)
+                .expect("Pending circuit status change not set")
+                .circuit
+                .expect("Pending circuit status change's circuit proposal not set");
+            let suspend =
+                circuit_proposal.get_proposal_type() == CircuitProposal_ProposalType::SUSPEND;
+
+            if suspend {
+                self.stop_services(circuit_proposal.get_circuit_proposal())?;
+            }
+
+            self.routing_table_writer
+                .set_circuit_suspended(circuit_proposal.get_circuit_id(), suspend)
+                .map_err(|_| {
+                    AdminSharedError::SplinterStateError(format!(
+                        "Unable to update circuit in routing table: {}",
+                        circuit_id
+                    ))
+                })?;
+
+            if !suspend {
+                self.initialize_services(circuit_proposal.get_circuit_proposal())?;
+            }
+
+            let mgmt_type = circuit_proposal
+                .get_circuit_proposal()
+                .circuit_management_type
+                .clone();
+            let circuit_proposal_proto = messages::CircuitProposal::from_proto(circuit_proposal)
+                .map_err(AdminSharedError::InvalidMessageFormat)?;
+            let event = if suspend {
+                messages::AdminServiceEvent::CircuitSuspended(circuit_proposal_proto)
+            } else {
+                messages::AdminServiceEvent::CircuitResumed(circuit_proposal_proto)
+            };
+            self.send_event(&mgmt_type, event);
+        }
+
+        Ok(())
+    }
+
+    /// Collect all circuits from the admin store, including `Disbanded`, `Abandoned` or
+    /// `Suspended` circuits
     pub fn get_circuits(
         &self,
     ) -> Result<Box<dyn ExactSizeIterator<Item = StoreCircuit>>, AdminSharedError> {
         let predicates = vec![
             CircuitPredicate::CircuitStatus(StoreCircuitStatus::Active),
+            CircuitPredicate::CircuitStatus(StoreCircuitStatus::Suspended),
             CircuitPredicate::CircuitStatus(StoreCircuitStatus::Disbanded),
             CircuitPredicate::CircuitStatus(StoreCircuitStatus::Abandoned),
         ];
@@ -3589,6 +5209,19 @@ impl AdminServiceShared {
     }
 }
 
+/// Returns the current time in seconds since the Unix epoch.
+fn unix_time_now() -> Result<u64, AdminSharedError> {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .map_err(|err| {
+            AdminSharedError::SplinterStateError(format!(
+                "System time is before the Unix epoch: {}",
+                err
+            ))
+        })
+}
+
 // This should never return an error since we received a message from this service id
 pub fn get_peer_token_from_service_id(
     service_id: &str,
@@ -3674,7 +5307,9 @@ pub fn get_peer_token_from_service_id(
 mod tests {
     use super::*;
 
+    use std::sync::mpsc::channel;
     use std::sync::{Arc, Mutex};
+    use std::time::Duration;
 
     use cylinder::{secp256k1::Secp256k1Context, Context};
     use protobuf::RepeatedField;
@@ -3689,6 +5324,10 @@ mod tests {
     use crate::admin::store::diesel::DieselAdminServiceStore;
     use crate::admin::store::CircuitNode;
     use crate::circuit::routing::memory::RoutingTable;
+    use crate::circuit::routing::RoutingTableReader;
+    use crate::consensus::tests::{MockConsensusNetworkSender, MockProposalManager};
+    use crate::consensus::two_phase::v1::TwoPhaseEngine;
+    use crate::consensus::{ConsensusEngine, ConsensusMessage, StartupState};
     use crate::keys::insecure::AllowAllKeyPermissionManager;
     use crate::mesh::{Envelope, Mesh};
     use crate::migrations::run_sqlite_migrations;
@@ -3707,6 +5346,10 @@ mod tests {
     };
     use crate::protos::network;
     use crate::protos::prelude::*;
+    use crate::protos::two_phase::{
+        RequiredVerifiers, TwoPhaseMessage, TwoPhaseMessage_ProposalVerificationResponse,
+        TwoPhaseMessage_Type,
+    };
     use crate::runtime::service::instance::{ServiceOrchestrator, ServiceOrchestratorBuilder};
     use crate::service::instance::{ServiceMessageContext, ServiceSendError};
     use crate::threading::lifecycle::ShutdownHandle;
@@ -3867,23 +5510,24 @@ mod tests {
         shutdown(mesh, cm, pm);
     }
 
-    /// Test that the CircuitManagementPayload message is dropped, if a node fails to match
-    /// protocol versions
+    /// Test that a proposed circuit with the relay route type only waits for the local node to
+    /// peer with the relay nodes, and that the service protocol request to a member that is
+    /// reached through a relay node is sent once the relay node is peered
     #[test]
-    fn test_protocol_disagreement() {
+    fn test_protocol_agreement_through_relay() {
         let mut transport = InprocTransport::default();
         let mut orchestrator_transport = transport.clone();
 
-        let _listener = transport
-            .listen("inproc://otherplace:8000")
+        let _relay_listener = transport
+            .listen("inproc://relayplace:8000")
             .expect("Unable to get listener");
-        let _admin_listener = transport
-            .listen("inproc://admin-service")
+        let _orchestator_listener = transport
+            .listen("inproc://orchestator")
             .expect("Unable to get listener");
 
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(Some(transport));
         let orchestrator_connection = orchestrator_transport
-            .connect("inproc://admin-service")
+            .connect("inproc://orchestator")
             .expect("failed to create connection");
         let orchestrator = ServiceOrchestratorBuilder::new()
             .with_connection(orchestrator_connection)
@@ -3898,10 +5542,11 @@ mod tests {
         let signature_verifier = Secp256k1Context::new().new_verifier();
 
         let table = RoutingTable::default();
+        let reader: Box<dyn RoutingTableReader> = Box::new(table.clone());
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
 
         let mut shared = AdminServiceShared::new(
-            "test-node".into(),
+            "my_peer_id".into(),
             vec![Box::new(orchestrator)],
             HashMap::new(),
             peer_connector,
@@ -3921,16 +5566,23 @@ mod tests {
         circuit.set_circuit_id("01234-ABCDE".into());
         circuit.set_authorization_type(admin::Circuit_AuthorizationType::TRUST_AUTHORIZATION);
         circuit.set_persistence(admin::Circuit_PersistenceType::ANY_PERSISTENCE);
-        circuit.set_routes(admin::Circuit_RouteType::ANY_ROUTE);
+        circuit.set_routes(admin::Circuit_RouteType::RELAY_ROUTE);
+        circuit.set_relay_nodes(protobuf::RepeatedField::from_vec(vec![
+            "relay-node".to_string()
+        ]));
+        circuit.set_durability(admin::Circuit_DurabilityType::NO_DURABILITY);
         circuit.set_circuit_management_type("test app auth handler".into());
         circuit.set_comments("test circuit".into());
         circuit.set_display_name("test_display".into());
         circuit.set_circuit_status(admin::Circuit_CircuitStatus::ACTIVE);
-        circuit.set_durability(admin::Circuit_DurabilityType::NO_DURABILITY);
 
         circuit.set_members(protobuf::RepeatedField::from_vec(vec![
-            splinter_node("test-node", &["inproc://someplace:8000".to_string()]),
-            splinter_node("other-node", &["inproc://otherplace:8000".to_string()]),
+            splinter_node("relay-node", &["inproc://relayplace:8000".to_string()]),
+            splinter_node(
+                "firewalled-node",
+                &["inproc://unreachable:8000".to_string()],
+            ),
+            splinter_node("my_peer_id", &["inproc://myplace:8000".to_string()]),
         ]));
         circuit.set_roster(protobuf::RepeatedField::from_vec(vec![
             splinter_service("0123", "sabre"),
@@ -3950,16 +5602,162 @@ mod tests {
         payload.set_circuit_create_request(request);
 
         shared
-            .propose_circuit(payload, "local".to_string())
+            .propose_circuit(payload, "test".to_string())
             .expect("Proposal not accepted");
 
-        // None of the proposed members are peered
+        let firewalled_node = PeerTokenPair::new(
+            PeerAuthorizationToken::from_peer_id("firewalled-node"),
+            PeerAuthorizationToken::from_peer_id("my_peer_id"),
+        );
+        let relay_node = PeerTokenPair::new(
+            PeerAuthorizationToken::from_peer_id("relay-node"),
+            PeerAuthorizationToken::from_peer_id("my_peer_id"),
+        );
+
+        // Only the relay node is referenced, and admin messages to the firewalled node are sent
+        // through it
+        assert!(shared.peer_refs.contains_key(&relay_node));
+        assert!(!shared.peer_refs.contains_key(&firewalled_node));
+        assert_eq!(
+            reader
+                .next_hops("admin", "my_peer_id", "firewalled-node")
+                .expect("Unable to get next hops"),
+            vec!["relay-node".to_string()]
+        );
+
+        // Waiting on the relay node to be peered
         assert_eq!(1, shared.unpeered_payloads.len());
         assert_eq!(0, shared.pending_circuit_payloads.len());
+        assert!(service_sender
+            .sent
+            .lock()
+            .expect("sent lock poisoned")
+            .is_empty());
 
-        // Set other-node to peered
         shared
-            .on_peer_connected(&PeerTokenPair::new(
+            .on_peer_connected(&relay_node)
+            .expect("Unable to set peer to peered");
+
+        // We're fully peered, and the protocol request to the firewalled node has been sent
+        // along with the one to the relay node
+        assert_eq!(1, shared.pending_protocol_payloads.len());
+        assert_eq!(0, shared.pending_circuit_payloads.len());
+        let recipients = service_sender
+            .sent
+            .lock()
+            .expect("sent lock poisoned")
+            .iter()
+            .map(|(recipient, _)| recipient.to_string())
+            .collect::<Vec<_>>();
+        assert!(recipients.contains(&"admin::firewalled-node".to_string()));
+        assert!(recipients.contains(&"admin::relay-node".to_string()));
+
+        shared
+            .on_protocol_agreement("admin::relay-node", 1)
+            .expect("received unexpected error");
+        shared
+            .on_protocol_agreement("admin::firewalled-node", 1)
+            .expect("received unexpected error");
+
+        // We're agreed on protocol with every member, so the pending payload is now available
+        assert_eq!(0, shared.pending_protocol_payloads.len());
+        assert_eq!(1, shared.pending_circuit_payloads.len());
+        shutdown(mesh, cm, pm);
+    }
+
+    /// Test that the CircuitManagementPayload message is dropped, if a node fails to match
+    /// protocol versions
+    #[test]
+    fn test_protocol_disagreement() {
+        let mut transport = InprocTransport::default();
+        let mut orchestrator_transport = transport.clone();
+
+        let _listener = transport
+            .listen("inproc://otherplace:8000")
+            .expect("Unable to get listener");
+        let _admin_listener = transport
+            .listen("inproc://admin-service")
+            .expect("Unable to get listener");
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(Some(transport));
+        let orchestrator_connection = orchestrator_transport
+            .connect("inproc://admin-service")
+            .expect("failed to create connection");
+        let orchestrator = ServiceOrchestratorBuilder::new()
+            .with_connection(orchestrator_connection)
+            .build()
+            .expect("failed to create orchestrator")
+            .run()
+            .expect("failed to start orchestrator");
+        let store = setup_admin_service_store();
+
+        let event_store = store.clone_boxed();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let mut shared = AdminServiceShared::new(
+            "test-node".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+
+        let service_sender = MockServiceNetworkSender::new();
+        shared.set_network_sender(Some(Box::new(service_sender.clone())));
+
+        let mut circuit = admin::Circuit::new();
+        circuit.set_circuit_id("01234-ABCDE".into());
+        circuit.set_authorization_type(admin::Circuit_AuthorizationType::TRUST_AUTHORIZATION);
+        circuit.set_persistence(admin::Circuit_PersistenceType::ANY_PERSISTENCE);
+        circuit.set_routes(admin::Circuit_RouteType::ANY_ROUTE);
+        circuit.set_circuit_management_type("test app auth handler".into());
+        circuit.set_comments("test circuit".into());
+        circuit.set_display_name("test_display".into());
+        circuit.set_circuit_status(admin::Circuit_CircuitStatus::ACTIVE);
+        circuit.set_durability(admin::Circuit_DurabilityType::NO_DURABILITY);
+
+        circuit.set_members(protobuf::RepeatedField::from_vec(vec![
+            splinter_node("test-node", &["inproc://someplace:8000".to_string()]),
+            splinter_node("other-node", &["inproc://otherplace:8000".to_string()]),
+        ]));
+        circuit.set_roster(protobuf::RepeatedField::from_vec(vec![
+            splinter_service("0123", "sabre"),
+            splinter_service("ABCD", "sabre"),
+        ]));
+
+        let mut request = admin::CircuitCreateRequest::new();
+        request.set_circuit(circuit);
+
+        let mut header = admin::CircuitManagementPayload_Header::new();
+        header.set_action(admin::CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST);
+
+        let mut payload = admin::CircuitManagementPayload::new();
+
+        payload.set_signature(Vec::new());
+        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
+        payload.set_circuit_create_request(request);
+
+        shared
+            .propose_circuit(payload, "local".to_string())
+            .expect("Proposal not accepted");
+
+        // None of the proposed members are peered
+        assert_eq!(1, shared.unpeered_payloads.len());
+        assert_eq!(0, shared.pending_circuit_payloads.len());
+
+        // Set other-node to peered
+        shared
+            .on_peer_connected(&PeerTokenPair::new(
                 PeerAuthorizationToken::from_peer_id("other-node"),
                 PeerAuthorizationToken::from_peer_id("test-node"),
             ))
@@ -5172,10 +6970,11 @@ mod tests {
     }
 
     #[test]
-    // test that a valid circuit proposal vote comes back as valid
-    fn test_validate_proposal_vote_valid() {
+    // test that a circuit with an invalid voting policy is invalid
+    fn test_validate_circuit_invalid_voting_policy() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
+
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
 
@@ -5197,25 +6996,69 @@ mod tests {
             event_store,
             vec![],
         );
-        let circuit = setup_test_circuit();
-        let vote = setup_test_vote(&circuit);
-        let proposal = setup_test_proposal(&circuit);
 
-        if let Err(err) = admin_shared.validate_circuit_vote(
-            &vote,
+        let mut weight = admin::Circuit_VotingPolicy_NodeWeight::new();
+        weight.set_node_id("node_a".to_string());
+        weight.set_weight(2);
+
+        let mut voting_policy = Circuit_VotingPolicy::new();
+        voting_policy.set_policy_type(Circuit_VotingPolicy_PolicyType::THRESHOLD);
+        voting_policy.set_threshold(3);
+        voting_policy.set_weights(RepeatedField::from_vec(vec![weight.clone()]));
+
+        let mut circuit = setup_test_circuit();
+        circuit.set_voting_policy(voting_policy.clone());
+        assert!(admin_shared
+            .validate_create_circuit(&circuit, PUB_KEY, "node_a", ADMIN_SERVICE_PROTOCOL_VERSION)
+            .is_ok());
+
+        // the threshold is greater than the total weight of the members
+        let mut invalid_policy = voting_policy.clone();
+        invalid_policy.set_threshold(4);
+        circuit.set_voting_policy(invalid_policy);
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
             PUB_KEY,
-            &StoreProposal::from_proto(proposal).expect("Unable to get proposal"),
             "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been valid: {}", err);
+            panic!("Should have been invalid because the threshold cannot be met");
+        }
+
+        // the weight is for a node that is not a member
+        let mut unknown_weight = weight.clone();
+        unknown_weight.set_node_id("node_c".to_string());
+        let mut invalid_policy = voting_policy.clone();
+        invalid_policy.set_weights(RepeatedField::from_vec(vec![unknown_weight]));
+        circuit.set_voting_policy(invalid_policy);
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because node_c is not a member");
+        }
+
+        // a threshold is set for a majority policy
+        let mut invalid_policy = voting_policy;
+        invalid_policy.set_policy_type(Circuit_VotingPolicy_PolicyType::MAJORITY);
+        circuit.set_voting_policy(invalid_policy);
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because a majority policy cannot have a threshold");
         }
         shutdown(mesh, cm, pm);
     }
 
     #[test]
-    // test that if the vote is from a key that is not permitted for the voting node the vote is
-    // invalid
-    fn test_validate_proposal_vote_not_permitted() {
+    // test that proposals to create a circuit require every member to accept them, and that
+    // proposals against an existing circuit are decided by the stored circuit's voting policy
+    fn test_check_approved_voting_policy() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
@@ -5234,30 +7077,133 @@ mod tests {
             peer_connector,
             store,
             signature_verifier,
-            Box::new(MockAdminKeyVerifier::new(false)),
+            Box::new(MockAdminKeyVerifier::default()),
             Box::new(AllowAllKeyPermissionManager),
             writer,
             event_store,
             vec![],
         );
-        let circuit = setup_test_circuit();
-        let vote = setup_test_vote(&circuit);
-        let proposal = setup_test_proposal(&circuit);
 
-        if let Ok(_) = admin_shared.validate_circuit_vote(
-            &vote,
-            PUB_KEY,
-            &StoreProposal::from_proto(proposal).expect("Unable to get proposal"),
+        let mut node_c = SplinterNode::new();
+        node_c.set_node_id("node_c".to_string());
+        node_c.set_endpoints(vec!["test://endpoint_c:0".to_string()].into());
+
+        let mut circuit = setup_test_circuit();
+        circuit.mut_members().push(node_c);
+
+        let mut majority_policy = Circuit_VotingPolicy::new();
+        majority_policy.set_policy_type(Circuit_VotingPolicy_PolicyType::MAJORITY);
+
+        // node_a holds enough weight to block a threshold of 3 out of 4
+        let mut weight = admin::Circuit_VotingPolicy_NodeWeight::new();
+        weight.set_node_id("node_a".to_string());
+        weight.set_weight(2);
+        let mut threshold_policy = Circuit_VotingPolicy::new();
+        threshold_policy.set_policy_type(Circuit_VotingPolicy_PolicyType::THRESHOLD);
+        threshold_policy.set_threshold(3);
+        threshold_policy.set_weights(RepeatedField::from_vec(vec![weight]));
+
+        let vote_record = |node_id: &str, vote| {
+            let mut vote_record = CircuitProposal_VoteRecord::new();
+            vote_record.set_vote(vote);
+            vote_record.set_public_key(format!("test_signer_{}", node_id).into_bytes());
+            vote_record.set_voter_node_id(node_id.to_string());
+            vote_record
+        };
+
+        let check_approved = |proposal: &CircuitProposal| {
+            admin_shared
+                .check_approved(proposal)
+                .expect("Unable to check proposal")
+        };
+
+        // a proposal to create a circuit requires every member to accept it, whatever voting
+        // policy the proposed circuit declares
+        circuit.set_voting_policy(majority_policy.clone());
+        let mut proposal = setup_test_proposal(&circuit);
+        proposal.set_votes(RepeatedField::from_vec(vec![vote_record(
             "node_a",
-        ) {
-            panic!("Should have been invalid because voting node is not registered");
-        }
+            CircuitProposalVote_Vote::ACCEPT,
+        )]));
+        assert!(matches!(
+            check_approved(&proposal),
+            CircuitProposalStatus::Pending
+        ));
+        proposal
+            .mut_votes()
+            .push(vote_record("node_c", CircuitProposalVote_Vote::ACCEPT));
+        assert!(matches!(
+            check_approved(&proposal),
+            CircuitProposalStatus::Accepted
+        ));
+
+        // a proposal against a circuit that is not stored cannot be checked
+        proposal.set_proposal_type(CircuitProposal_ProposalType::DISBAND);
+        assert!(admin_shared.check_approved(&proposal).is_err());
+
+        // store the circuit with the default, unanimous, policy
+        let mut stored_circuit = circuit.clone();
+        stored_circuit.clear_voting_policy();
+        let stored_circuit =
+            StoreCircuit::try_from(&stored_circuit).expect("Unable to convert circuit");
+        admin_shared
+            .admin_store
+            .add_circuit(stored_circuit.clone(), stored_circuit.members().to_vec())
+            .expect("Unable to add circuit to store");
+
+        // the majority policy in the proposal does not apply to the stored circuit
+        let mut proposal = setup_test_proposal(&circuit);
+        proposal.set_proposal_type(CircuitProposal_ProposalType::DISBAND);
+        proposal.set_votes(RepeatedField::from_vec(vec![vote_record(
+            "node_a",
+            CircuitProposalVote_Vote::ACCEPT,
+        )]));
+        assert!(matches!(
+            check_approved(&proposal),
+            CircuitProposalStatus::Pending
+        ));
+
+        // a majority only requires node_a to accept the proposal along with the requester
+        let mut stored_circuit = circuit.clone();
+        stored_circuit.set_voting_policy(majority_policy);
+        admin_shared
+            .admin_store
+            .update_circuit(StoreCircuit::try_from(&stored_circuit).expect("Unable to convert"))
+            .expect("Unable to update circuit");
+        assert!(matches!(
+            check_approved(&proposal),
+            CircuitProposalStatus::Accepted
+        ));
+
+        // the stored threshold policy applies, even though the proposal declares a majority
+        stored_circuit.set_voting_policy(threshold_policy);
+        admin_shared
+            .admin_store
+            .update_circuit(StoreCircuit::try_from(&stored_circuit).expect("Unable to convert"))
+            .expect("Unable to update circuit");
+        proposal.set_votes(RepeatedField::from_vec(vec![vote_record(
+            "node_c",
+            CircuitProposalVote_Vote::ACCEPT,
+        )]));
+        assert!(matches!(
+            check_approved(&proposal),
+            CircuitProposalStatus::Pending
+        ));
+        proposal
+            .mut_votes()
+            .push(vote_record("node_a", CircuitProposalVote_Vote::REJECT));
+        assert!(matches!(
+            check_approved(&proposal),
+            CircuitProposalStatus::Rejected
+        ));
         shutdown(mesh, cm, pm);
     }
 
     #[test]
-    // test if the voter is the original requester node the vote is invalid
-    fn test_validate_proposal_vote_requester() {
+    // test that a proposal against a circuit with a majority voting policy only requires the
+    // members that responded to the local admin service to verify it, and that the proposal is
+    // committed by two-phase commit while the remaining member never responds
+    fn test_required_verifiers_unresponsive_member() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
@@ -5269,7 +7215,7 @@ mod tests {
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
 
-        let admin_shared = AdminServiceShared::new(
+        let mut admin_shared = AdminServiceShared::new(
             "node_a".into(),
             vec![Box::new(orchestrator)],
             HashMap::new(),
@@ -5282,27 +7228,149 @@ mod tests {
             event_store,
             vec![],
         );
-        let circuit = setup_test_circuit();
-        let vote = setup_test_vote(&circuit);
+
+        let mut node_c = SplinterNode::new();
+        node_c.set_node_id("node_c".to_string());
+        node_c.set_endpoints(vec!["test://endpoint_c:0".to_string()].into());
+
+        let mut circuit = setup_test_circuit();
+        circuit.mut_members().push(node_c);
+        let mut majority_policy = Circuit_VotingPolicy::new();
+        majority_policy.set_policy_type(Circuit_VotingPolicy_PolicyType::MAJORITY);
+        circuit.set_voting_policy(majority_policy.clone());
+
+        let stored_circuit = StoreCircuit::try_from(&circuit).expect("Unable to convert circuit");
+        admin_shared
+            .admin_store
+            .add_circuit(stored_circuit.clone(), stored_circuit.members().to_vec())
+            .expect("Unable to add circuit to store");
+
+        // node_b agrees on a protocol version, node_c never responds
+        admin_shared
+            .on_protocol_agreement("admin::node_b", ADMIN_SERVICE_PROTOCOL_VERSION)
+            .expect("Unable to agree on protocol");
+
+        // a proposal to create a circuit must be verified by every member
         let proposal = setup_test_proposal(&circuit);
+        assert_eq!(
+            admin_shared
+                .required_verifiers(&proposal)
+                .expect("Unable to get required verifiers"),
+            vec!["node_a", "node_b", "node_c"]
+        );
 
-        if let Ok(_) = admin_shared.validate_circuit_vote(
-            &vote,
-            PUB_KEY,
-            &StoreProposal::from_proto(proposal).expect("Unable to get proposal"),
-            "node_b",
-        ) {
-            panic!("Should have been invalid because voter is the requester");
+        // node_a and node_b hold a majority, so node_c is not required
+        let mut proposal = setup_test_proposal(&circuit);
+        proposal.set_proposal_type(CircuitProposal_ProposalType::DISBAND);
+        let required_verifiers = admin_shared
+            .required_verifiers(&proposal)
+            .expect("Unable to get required verifiers");
+        assert_eq!(required_verifiers, vec!["node_a", "node_b"]);
+
+        // node_c holds enough weight that node_a and node_b no longer hold a majority, so every
+        // member is required
+        let mut weight = admin::Circuit_VotingPolicy_NodeWeight::new();
+        weight.set_node_id("node_c".to_string());
+        weight.set_weight(3);
+        let mut weighted_circuit = circuit.clone();
+        let mut weighted_policy = majority_policy;
+        weighted_policy.set_weights(RepeatedField::from_vec(vec![weight]));
+        weighted_circuit.set_voting_policy(weighted_policy);
+        admin_shared
+            .admin_store
+            .update_circuit(
+                StoreCircuit::try_from(&weighted_circuit).expect("Unable to convert circuit"),
+            )
+            .expect("Unable to update circuit");
+        assert_eq!(
+            admin_shared
+                .required_verifiers(&proposal)
+                .expect("Unable to get required verifiers"),
+            vec!["node_a", "node_b", "node_c"]
+        );
+
+        // run two-phase commit as node_a with the verifiers required under the majority policy
+        let (update_tx, update_rx) = channel();
+        let (consensus_msg_tx, consensus_msg_rx) = channel();
+
+        let mut manager = MockProposalManager::new(update_tx.clone());
+        let network = MockConsensusNetworkSender::new();
+
+        let mut verifiers = RequiredVerifiers::new();
+        verifiers.set_verifiers(RepeatedField::from_vec(
+            required_verifiers
+                .iter()
+                .map(|node_id| admin_service_id(node_id).as_bytes().to_vec())
+                .collect(),
+        ));
+        manager.set_consensus_data(Some(
+            verifiers
+                .write_to_bytes()
+                .expect("Unable to write required verifiers"),
+        ));
+
+        let startup_state = StartupState {
+            id: admin_service_id("node_a").as_bytes().into(),
+            peer_ids: vec![],
+            last_proposal: None,
+        };
+
+        let mut engine = TwoPhaseEngine::new(Duration::from_secs(5));
+        let network_clone = network.clone();
+        let manager_clone = manager.clone();
+        let thread = std::thread::spawn(move || {
+            engine
+                .run(
+                    consensus_msg_rx,
+                    update_rx,
+                    Box::new(network_clone),
+                    Box::new(manager_clone),
+                    startup_state,
+                )
+                .expect("engine failed")
+        });
+
+        // Wait for the verification request for the proposal
+        while network.broadcast_messages().is_empty() {}
+
+        // Only node_b verifies the proposal
+        let mut response = TwoPhaseMessage::new();
+        response.set_message_type(TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_RESPONSE);
+        response.set_proposal_id(vec![1]);
+        response.set_proposal_verification_response(
+            TwoPhaseMessage_ProposalVerificationResponse::VERIFIED,
+        );
+        consensus_msg_tx
+            .send(ConsensusMessage::new(
+                response
+                    .write_to_bytes()
+                    .expect("Unable to write verification response"),
+                admin_service_id("node_b").as_bytes().into(),
+            ))
+            .expect("Unable to send verification response");
+
+        // Verify the proposal was accepted without node_c's verification
+        loop {
+            if let Some((id, _)) = manager.accepted_proposals().get(0) {
+                assert_eq!(id, &vec![1].into());
+                break;
+            }
         }
+        assert!(manager.rejected_proposals().is_empty());
+
+        update_tx
+            .send(ProposalUpdate::Shutdown)
+            .expect("Unable to send shutdown");
+        thread.join().expect("Unable to join engine thread");
+
         shutdown(mesh, cm, pm);
     }
 
     #[test]
-    // test if a voter has already voted on a proposal the new vote is invalid
-    fn test_validate_proposal_vote_duplicate_vote() {
+    // test that a valid circuit proposal vote comes back as valid
+    fn test_validate_proposal_vote_valid() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
-
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
 
@@ -5326,28 +7394,155 @@ mod tests {
         );
         let circuit = setup_test_circuit();
         let vote = setup_test_vote(&circuit);
-        let mut proposal = setup_test_proposal(&circuit);
-
-        let mut vote_record = CircuitProposal_VoteRecord::new();
-        vote_record.set_vote(CircuitProposalVote_Vote::ACCEPT);
-        vote_record.set_public_key(b"test_signer_a".to_vec());
-        vote_record.set_voter_node_id("node_a".to_string());
-
-        proposal.set_votes(RepeatedField::from_vec(vec![vote_record]));
+        let proposal = setup_test_proposal(&circuit);
 
-        if let Ok(_) = admin_shared.validate_circuit_vote(
+        if let Err(err) = admin_shared.validate_circuit_vote(
             &vote,
             PUB_KEY,
             &StoreProposal::from_proto(proposal).expect("Unable to get proposal"),
             "node_a",
         ) {
-            panic!("Should have been invalid because node as already submitted a vote");
+            panic!("Should have been valid: {}", err);
         }
         shutdown(mesh, cm, pm);
     }
 
     #[test]
-    // test that if the circuit hash in the circuit proposal does not match the circuit hash on
+    // test that if the vote is from a key that is not permitted for the voting node the vote is
+    // invalid
+    fn test_validate_proposal_vote_not_permitted() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::new(false)),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+        let circuit = setup_test_circuit();
+        let vote = setup_test_vote(&circuit);
+        let proposal = setup_test_proposal(&circuit);
+
+        if let Ok(_) = admin_shared.validate_circuit_vote(
+            &vote,
+            PUB_KEY,
+            &StoreProposal::from_proto(proposal).expect("Unable to get proposal"),
+            "node_a",
+        ) {
+            panic!("Should have been invalid because voting node is not registered");
+        }
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // test if the voter is the original requester node the vote is invalid
+    fn test_validate_proposal_vote_requester() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+        let circuit = setup_test_circuit();
+        let vote = setup_test_vote(&circuit);
+        let proposal = setup_test_proposal(&circuit);
+
+        if let Ok(_) = admin_shared.validate_circuit_vote(
+            &vote,
+            PUB_KEY,
+            &StoreProposal::from_proto(proposal).expect("Unable to get proposal"),
+            "node_b",
+        ) {
+            panic!("Should have been invalid because voter is the requester");
+        }
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // test if a voter has already voted on a proposal the new vote is invalid
+    fn test_validate_proposal_vote_duplicate_vote() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+        let circuit = setup_test_circuit();
+        let vote = setup_test_vote(&circuit);
+        let mut proposal = setup_test_proposal(&circuit);
+
+        let mut vote_record = CircuitProposal_VoteRecord::new();
+        vote_record.set_vote(CircuitProposalVote_Vote::ACCEPT);
+        vote_record.set_public_key(b"test_signer_a".to_vec());
+        vote_record.set_voter_node_id("node_a".to_string());
+
+        proposal.set_votes(RepeatedField::from_vec(vec![vote_record]));
+
+        if let Ok(_) = admin_shared.validate_circuit_vote(
+            &vote,
+            PUB_KEY,
+            &StoreProposal::from_proto(proposal).expect("Unable to get proposal"),
+            "node_a",
+        ) {
+            panic!("Should have been invalid because node as already submitted a vote");
+        }
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // test that if the circuit hash in the circuit proposal does not match the circuit hash on
     // the vote, the vote is invalid
     fn test_validate_proposal_vote_circuit_hash_mismatch() {
         let store = setup_admin_service_store();
@@ -5847,30 +8042,566 @@ mod tests {
             vec![],
         );
 
-        if let Ok(()) = admin_shared.validate_disband_circuit(
+        if let Ok(()) = admin_shared.validate_disband_circuit(
+            &setup_test_circuit(),
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because the circuit being disbanded does not exist");
+        }
+
+        shutdown(mesh, cm, pm);
+    }
+
+    /// Tests that a circuit being disbanded is invalid if the requester is not permitted for
+    /// the node.
+    ///
+    /// 1. Set up `AdminServiceShared`
+    /// 2. Add a circuit to the admin store
+    /// 3. Call `validate_disband_circuit` with a valid circuit and valid requester info
+    /// 4. Validate the call to `validate_disband_circuit` returns an error
+    ///
+    /// This test verifies the `validate_disband_circuit` returns an error when given a signer
+    /// key that is not permitted on the node.
+    #[test]
+    fn test_validate_disband_circuit_not_permitted() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::new(false)),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+
+        // Add the circuit to be disbanded
+        admin_shared
+            .admin_store
+            .add_circuit(
+                store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Active),
+                store_circuit_nodes(),
+            )
+            .expect("unable to add circuit to store");
+
+        if let Ok(()) = admin_shared.validate_disband_circuit(
+            &setup_test_circuit(),
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because the requester is not authorized");
+        }
+
+        shutdown(mesh, cm, pm);
+    }
+
+    /// Tests that a circuit being disbanded is invalid if the requester is not permitted for
+    /// the node.
+    ///
+    /// 1. Set up `AdminServiceShared`
+    /// 2. Add a circuit, with `circuit_status` set to `Disbanded`, to the admin store
+    /// 3. Call `validate_disband_circuit` with a valid circuit and valid requester info
+    /// 4. Validate the call to `validate_disband_circuit` returns an error
+    ///
+    /// This test verifies the `validate_disband_circuit` returns an error when the circuit
+    /// attempted to be disbanded already has a status of `Disbanded`. The disbanded action
+    /// is only valid for currently `Active` circuits.
+    #[test]
+    fn test_validate_disband_circuit_already_disbanded() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+
+        admin_shared
+            .admin_store
+            .add_circuit(
+                store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Disbanded),
+                store_circuit_nodes(),
+            )
+            .expect("unable to add circuit to store");
+
+        if let Ok(()) = admin_shared.validate_disband_circuit(
+            &setup_test_circuit(),
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid due to circuit already being disbanded");
+        }
+        shutdown(mesh, cm, pm);
+    }
+
+    /// Tests that the payload submitted via `propose_disband` is moved to the admin service's
+    /// payload lists as peers become fully peered, authorized and agree on a service protocol.
+    ///
+    /// 1. Set up `AdminServiceShared`
+    /// 2. Add a circuit to the admin store
+    /// 3. Create the `CircuitDisbandRequest` payload
+    /// 4. Start another thread to handle authorization for the peer node, "node_b"
+    /// 5. Set each node to peered. The `CircuitDisbandRequest` is only valid for circuits that
+    ///    have already been created, so nodes are assumed to be peered before the request is
+    ///    submitted.
+    /// 6. Submit the `CircuitDisbandRequest` payload
+    /// 7. Assert the payload is present in the "pending_protocol_payloads" list and that no
+    ///    payload is available in the `pending_circuit_payloads` list
+    /// 8. Set each node to agree on the protocol
+    /// 9. Assert the payload is present in the "pending_circuit_payloads" list and that no
+    ///    payload is available in the `pending_protocol_payloads` list
+    ///
+    /// This test verifies a payload submitted using `propose_disband` is moved to the admin
+    /// service's payload lists as expected when the circuit nodes have already been peered
+    /// and then agree on the admin service protocol.
+    #[test]
+    fn test_disband_request() {
+        let mut transport = InprocTransport::default();
+        let mut orchestrator_transport = transport.clone();
+
+        let mut other_listener = transport
+            .listen("inproc://otherplace:8000")
+            .expect("Unable to get listener");
+        let _test_listener = transport
+            .listen("inproc://someplace:8000")
+            .expect("Unable to get listener");
+        let _orchestator_listener = transport
+            .listen("inproc://orchestator")
+            .expect("Unable to get listener");
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(Some(transport));
+        let orchestrator_connection = orchestrator_transport
+            .connect("inproc://orchestator")
+            .expect("failed to create connection");
+        let orchestrator = ServiceOrchestratorBuilder::new()
+            .with_connection(orchestrator_connection)
+            .build()
+            .expect("failed to create orchestrator")
+            .run()
+            .expect("failed to start orchestrator");
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let mut shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+
+        let service_sender = MockServiceNetworkSender::new();
+        shared.set_network_sender(Some(Box::new(service_sender.clone())));
+
+        shared
+            .admin_store
+            .add_circuit(
+                store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Active),
+                store_circuit_nodes(),
+            )
+            .expect("unable to add circuit to store");
+
+        for node in store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Active)
+            .list_nodes()
+            .expect("Unable to get peer nodes from circuit")
+        {
+            shared.token_to_peer.insert(
+                PeerTokenPair::new(
+                    node.token.clone(),
+                    PeerAuthorizationToken::from_peer_id("node_a"),
+                ),
+                PeerNodePair {
+                    peer_node: node,
+                    local_peer_token: PeerAuthorizationToken::from_peer_id("node_a"),
+                },
+            );
+        }
+
+        let mut request = admin::CircuitDisbandRequest::new();
+        request.set_circuit_id("01234-ABCDE".to_string());
+
+        let mut header = admin::CircuitManagementPayload_Header::new();
+        header.set_action(admin::CircuitManagementPayload_Action::CIRCUIT_DISBAND_REQUEST);
+        header.set_requester(b"test_signer_a".to_vec());
+        header.set_requester_node_id("node_a".to_string());
+
+        let mut payload = admin::CircuitManagementPayload::new();
+        payload.set_signature(b"test_signer_a".to_vec());
+        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
+        payload.set_circuit_disband_request(request);
+        // start up thread for other node
+        std::thread::spawn(move || {
+            let mut mesh = Mesh::new(2, 2);
+            let conn = other_listener.accept().unwrap();
+            mesh.add(conn, "my_peer_id".to_string()).unwrap();
+
+            handle_auth(&mesh, "my_peer_id", "node_b");
+
+            mesh.signal_shutdown();
+            mesh.wait_for_shutdown().expect("Unable to shutdown mesh");
+        });
+
+        // Set `node_b` to peered
+        shared
+            .on_peer_connected(&PeerTokenPair::new(
+                PeerAuthorizationToken::from_peer_id("node_b"),
+                PeerAuthorizationToken::from_peer_id("node_a"),
+            ))
+            .expect("Unable to set peer to peered");
+
+        shared
+            .propose_disband(
+                payload,
+                &b"test_signer_a".to_vec(),
+                "node_a",
+                "test".to_string(),
+            )
+            .expect("Proposal not accepted");
+
+        // We're fully peered, but need to wait for protocol to be agreed on
+        assert_eq!(1, shared.pending_protocol_payloads.len());
+        assert_eq!(0, shared.pending_circuit_payloads.len());
+
+        // Set `node_b` to agree on the protocol
+        shared
+            .on_protocol_agreement("admin::node_b", ADMIN_SERVICE_PROTOCOL_VERSION)
+            .expect("received unexpected error");
+
+        // Set `node_a` to agree on the protocol
+        shared
+            .on_protocol_agreement("admin::node_a", ADMIN_SERVICE_PROTOCOL_VERSION)
+            .expect("received unexpected error");
+
+        // We're fully peered and agreed on protocol, so the pending payload is now available
+        assert_eq!(0, shared.pending_protocol_payloads.len());
+        assert_eq!(1, shared.pending_circuit_payloads.len());
+        shutdown(mesh, cm, pm);
+    }
+
+    /// Tests that a circuit being suspended is validated correctly
+    ///
+    /// 1. Set up `AdminServiceShared`
+    /// 2. Add an `Active` circuit to the admin store
+    /// 3. Validate the call to `validate_suspend_circuit` returns successfully
+    #[test]
+    fn test_validate_suspend_circuit_valid() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+
+        admin_shared
+            .admin_store
+            .add_circuit(
+                store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Active),
+                store_circuit_nodes(),
+            )
+            .expect("unable to add circuit to store");
+
+        if let Err(err) = admin_shared.validate_suspend_circuit(
+            &setup_test_circuit(),
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been valid: {}", err);
+        }
+
+        shutdown(mesh, cm, pm);
+    }
+
+    /// Tests that a circuit being suspended is invalid if the circuit is already suspended
+    ///
+    /// 1. Set up `AdminServiceShared`
+    /// 2. Add a circuit, with `circuit_status` set to `Suspended`, to the admin store
+    /// 3. Validate the call to `validate_suspend_circuit` returns an error
+    #[test]
+    fn test_validate_suspend_circuit_already_suspended() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+
+        admin_shared
+            .admin_store
+            .add_circuit(
+                store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Suspended),
+                store_circuit_nodes(),
+            )
+            .expect("unable to add circuit to store");
+
+        if let Ok(()) = admin_shared.validate_suspend_circuit(
+            &setup_test_circuit(),
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid due to circuit already being suspended");
+        }
+
+        shutdown(mesh, cm, pm);
+    }
+
+    /// Tests that a circuit being resumed is validated correctly
+    ///
+    /// 1. Set up `AdminServiceShared`
+    /// 2. Add a `Suspended` circuit to the admin store
+    /// 3. Validate the call to `validate_resume_circuit` returns successfully
+    #[test]
+    fn test_validate_resume_circuit_valid() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+
+        admin_shared
+            .admin_store
+            .add_circuit(
+                store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Suspended),
+                store_circuit_nodes(),
+            )
+            .expect("unable to add circuit to store");
+
+        if let Err(err) = admin_shared.validate_resume_circuit(
+            &setup_test_circuit(),
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been valid: {}", err);
+        }
+
+        shutdown(mesh, cm, pm);
+    }
+
+    /// Tests that a circuit being resumed is invalid if the circuit is not suspended
+    ///
+    /// 1. Set up `AdminServiceShared`
+    /// 2. Add an `Active` circuit to the admin store
+    /// 3. Validate the call to `validate_resume_circuit` returns an error
+    #[test]
+    fn test_validate_resume_circuit_not_suspended() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+
+        admin_shared
+            .admin_store
+            .add_circuit(
+                store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Active),
+                store_circuit_nodes(),
+            )
+            .expect("unable to add circuit to store");
+
+        if let Ok(()) = admin_shared.validate_resume_circuit(
+            &setup_test_circuit(),
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because the circuit is not suspended");
+        }
+
+        shutdown(mesh, cm, pm);
+    }
+
+    /// Tests that a request to update a member's node keys is validated correctly
+    ///
+    /// 1. Set up `AdminServiceShared`
+    /// 2. Add an `Active` circuit to the admin store
+    /// 3. Validate the call to `validate_update_node_keys` returns successfully when the
+    ///    requesting node updates its own key
+    #[test]
+    fn test_validate_update_node_keys_valid() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+
+        admin_shared
+            .admin_store
+            .add_circuit(
+                store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Active),
+                store_circuit_nodes(),
+            )
+            .expect("unable to add circuit to store");
+
+        let mut update_request = CircuitUpdateNodeKeysRequest::new();
+        update_request.set_circuit_id("01234-ABCDE".into());
+        update_request.set_node_id("node_a".into());
+        update_request.set_public_key(vec![2; 33]);
+
+        if let Err(err) = admin_shared.validate_update_node_keys(
+            &update_request,
             &setup_test_circuit(),
             PUB_KEY,
             "node_a",
             ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been invalid because the circuit being disbanded does not exist");
+            panic!("Should have been valid: {}", err);
         }
 
         shutdown(mesh, cm, pm);
     }
 
-    /// Tests that a circuit being disbanded is invalid if the requester is not permitted for
-    /// the node.
-    ///
-    /// 1. Set up `AdminServiceShared`
-    /// 2. Add a circuit to the admin store
-    /// 3. Call `validate_disband_circuit` with a valid circuit and valid requester info
-    /// 4. Validate the call to `validate_disband_circuit` returns an error
+    /// Tests that a node's routing table entry is only updated with its new key once all of the
+    /// node's circuits use it
     ///
-    /// This test verifies the `validate_disband_circuit` returns an error when given a signer
-    /// key that is not permitted on the node.
+    /// 1. Set up `AdminServiceShared` and add two circuits with `node_b` to the admin store and
+    ///    `node_b` to the routing table, using the same key
+    /// 2. Update `node_b`'s key on the first circuit and check that the routing table keeps the
+    ///    previous key, which the second circuit still uses
+    /// 3. Update `node_b`'s key on the second circuit and check that the routing table uses the
+    ///    new key
     #[test]
-    fn test_validate_disband_circuit_not_permitted() {
+    fn test_update_routing_table_node_keys_multiple_circuits() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
@@ -5881,55 +8612,116 @@ mod tests {
 
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+        let reader: Box<dyn RoutingTableReader> = Box::new(table.clone());
 
-        let admin_shared = AdminServiceShared::new(
+        let mut admin_shared = AdminServiceShared::new(
             "node_a".into(),
             vec![Box::new(orchestrator)],
             HashMap::new(),
             peer_connector,
             store,
             signature_verifier,
-            Box::new(MockAdminKeyVerifier::new(false)),
+            Box::new(MockAdminKeyVerifier::default()),
             Box::new(AllowAllKeyPermissionManager),
             writer,
             event_store,
             vec![],
         );
 
-        // Add the circuit to be disbanded
+        let previous_key = public_key::PublicKey::from_bytes(vec![1; 33]);
+        let new_key = public_key::PublicKey::from_bytes(vec![2; 33]);
+
+        let circuit_with_key = |circuit_id: &str, public_key: &public_key::PublicKey| {
+            let nodes = vec![
+                store::CircuitNodeBuilder::new()
+                    .with_node_id("node_a")
+                    .with_endpoints(&["test://endpoint_a:0".to_string()])
+                    .build()
+                    .expect("unable to build store CircuitNode"),
+                store::CircuitNodeBuilder::new()
+                    .with_node_id("node_b")
+                    .with_endpoints(&["test://endpoint_b:0".to_string()])
+                    .with_public_key(public_key)
+                    .build()
+                    .expect("unable to build store CircuitNode"),
+            ];
+            store::CircuitBuilder::new()
+                .with_circuit_id(circuit_id)
+                .with_roster(&[store::ServiceBuilder::new()
+                    .with_service_id("0123")
+                    .with_service_type("type_a")
+                    .with_node_id("node_a")
+                    .build()
+                    .expect("unable to build admin store Service")])
+                .with_members(&nodes)
+                .with_authorization_type(&store::AuthorizationType::Challenge)
+                .with_persistence(&store::PersistenceType::Any)
+                .with_durability(&store::DurabilityType::NoDurability)
+                .with_routes(&store::RouteType::Any)
+                .with_circuit_management_type("test_circuit")
+                .with_circuit_version(CIRCUIT_PROTOCOL_VERSION)
+                .with_circuit_status(&StoreCircuitStatus::Active)
+                .build()
+                .expect("unable to build store Circuit")
+        };
+
+        for &circuit_id in &["01234-ABCDE", "56789-FGHIJ"] {
+            let circuit = circuit_with_key(circuit_id, &previous_key);
+            admin_shared
+                .admin_store
+                .add_circuit(circuit.clone(), circuit.members().to_vec())
+                .expect("unable to add circuit to store");
+        }
         admin_shared
-            .admin_store
-            .add_circuit(
-                store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Active),
-                store_circuit_nodes(),
+            .routing_table_writer
+            .add_node(
+                "node_b".to_string(),
+                routing::CircuitNode::new(
+                    "node_b".to_string(),
+                    vec!["test://endpoint_b:0".to_string()],
+                    Some(previous_key.clone()),
+                ),
             )
-            .expect("unable to add circuit to store");
+            .expect("unable to add node to routing table");
 
-        if let Ok(()) = admin_shared.validate_disband_circuit(
-            &setup_test_circuit(),
-            PUB_KEY,
-            "node_a",
-            ADMIN_SERVICE_PROTOCOL_VERSION,
-        ) {
-            panic!("Should have been invalid because the requester is not authorized");
+        let expected_node = |public_key: &public_key::PublicKey| {
+            Some(routing::CircuitNode::new(
+                "node_b".to_string(),
+                vec!["test://endpoint_b:0".to_string()],
+                Some(public_key.clone()),
+            ))
+        };
+
+        for &(circuit_id, expected_key) in
+            &[("01234-ABCDE", &previous_key), ("56789-FGHIJ", &new_key)]
+        {
+            let previous_circuit = circuit_with_key(circuit_id, &previous_key);
+            let circuit = circuit_with_key(circuit_id, &new_key);
+            admin_shared
+                .admin_store
+                .update_circuit(circuit.clone())
+                .expect("unable to update circuit");
+            admin_shared
+                .update_routing_table_node_keys(&previous_circuit, &circuit)
+                .expect("unable to update routing table");
+
+            assert_eq!(
+                reader.get_node("node_b").expect("unable to get node"),
+                expected_node(expected_key)
+            );
         }
 
         shutdown(mesh, cm, pm);
     }
 
-    /// Tests that a circuit being disbanded is invalid if the requester is not permitted for
-    /// the node.
+    /// Tests that a node may not update the keys of another member
     ///
     /// 1. Set up `AdminServiceShared`
-    /// 2. Add a circuit, with `circuit_status` set to `Disbanded`, to the admin store
-    /// 3. Call `validate_disband_circuit` with a valid circuit and valid requester info
-    /// 4. Validate the call to `validate_disband_circuit` returns an error
-    ///
-    /// This test verifies the `validate_disband_circuit` returns an error when the circuit
-    /// attempted to be disbanded already has a status of `Disbanded`. The disbanded action
-    /// is only valid for currently `Active` circuits.
+    /// 2. Add an `Active` circuit to the admin store
+    /// 3. Validate the call to `validate_update_node_keys` returns an error when `node_b`
+    ///    requests an update of `node_a`'s key
     #[test]
-    fn test_validate_disband_circuit_already_disbanded() {
+    fn test_validate_update_node_keys_other_node() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
@@ -5958,76 +8750,49 @@ mod tests {
         admin_shared
             .admin_store
             .add_circuit(
-                store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Disbanded),
+                store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Active),
                 store_circuit_nodes(),
             )
             .expect("unable to add circuit to store");
 
-        if let Ok(()) = admin_shared.validate_disband_circuit(
+        let mut update_request = CircuitUpdateNodeKeysRequest::new();
+        update_request.set_circuit_id("01234-ABCDE".into());
+        update_request.set_node_id("node_a".into());
+        update_request.set_public_key(vec![2; 33]);
+
+        if let Ok(()) = admin_shared.validate_update_node_keys(
+            &update_request,
             &setup_test_circuit(),
             PUB_KEY,
-            "node_a",
+            "node_b",
             ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been invalid due to circuit already being disbanded");
+            panic!("Should have been invalid because the requester is not the updated node");
         }
+
         shutdown(mesh, cm, pm);
     }
 
-    /// Tests that the payload submitted via `propose_disband` is moved to the admin service's
-    /// payload lists as peers become fully peered, authorized and agree on a service protocol.
+    /// Tests that a request to update a member's node keys is invalid if the new key is malformed
     ///
     /// 1. Set up `AdminServiceShared`
-    /// 2. Add a circuit to the admin store
-    /// 3. Create the `CircuitDisbandRequest` payload
-    /// 4. Start another thread to handle authorization for the peer node, "node_b"
-    /// 5. Set each node to peered. The `CircuitDisbandRequest` is only valid for circuits that
-    ///    have already been created, so nodes are assumed to be peered before the request is
-    ///    submitted.
-    /// 6. Submit the `CircuitDisbandRequest` payload
-    /// 7. Assert the payload is present in the "pending_protocol_payloads" list and that no
-    ///    payload is available in the `pending_circuit_payloads` list
-    /// 8. Set each node to agree on the protocol
-    /// 9. Assert the payload is present in the "pending_circuit_payloads" list and that no
-    ///    payload is available in the `pending_protocol_payloads` list
-    ///
-    /// This test verifies a payload submitted using `propose_disband` is moved to the admin
-    /// service's payload lists as expected when the circuit nodes have already been peered
-    /// and then agree on the admin service protocol.
+    /// 2. Add an `Active` circuit to the admin store
+    /// 3. Validate the call to `validate_update_node_keys` returns an error when the new key is
+    ///    not 33 bytes
     #[test]
-    fn test_disband_request() {
-        let mut transport = InprocTransport::default();
-        let mut orchestrator_transport = transport.clone();
-
-        let mut other_listener = transport
-            .listen("inproc://otherplace:8000")
-            .expect("Unable to get listener");
-        let _test_listener = transport
-            .listen("inproc://someplace:8000")
-            .expect("Unable to get listener");
-        let _orchestator_listener = transport
-            .listen("inproc://orchestator")
-            .expect("Unable to get listener");
-
-        let (mesh, cm, pm, peer_connector) = setup_peer_connector(Some(transport));
-        let orchestrator_connection = orchestrator_transport
-            .connect("inproc://orchestator")
-            .expect("failed to create connection");
-        let orchestrator = ServiceOrchestratorBuilder::new()
-            .with_connection(orchestrator_connection)
-            .build()
-            .expect("failed to create orchestrator")
-            .run()
-            .expect("failed to start orchestrator");
+    fn test_validate_update_node_keys_invalid_key() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
         let signature_verifier = Secp256k1Context::new().new_verifier();
 
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
 
-        let mut shared = AdminServiceShared::new(
+        let admin_shared = AdminServiceShared::new(
             "node_a".into(),
             vec![Box::new(orchestrator)],
             HashMap::new(),
@@ -6041,10 +8806,7 @@ mod tests {
             vec![],
         );
 
-        let service_sender = MockServiceNetworkSender::new();
-        shared.set_network_sender(Some(Box::new(service_sender.clone())));
-
-        shared
+        admin_shared
             .admin_store
             .add_circuit(
                 store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Active),
@@ -6052,80 +8814,21 @@ mod tests {
             )
             .expect("unable to add circuit to store");
 
-        for node in store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Active)
-            .list_nodes()
-            .expect("Unable to get peer nodes from circuit")
-        {
-            shared.token_to_peer.insert(
-                PeerTokenPair::new(
-                    node.token.clone(),
-                    PeerAuthorizationToken::from_peer_id("node_a"),
-                ),
-                PeerNodePair {
-                    peer_node: node,
-                    local_peer_token: PeerAuthorizationToken::from_peer_id("node_a"),
-                },
-            );
-        }
-
-        let mut request = admin::CircuitDisbandRequest::new();
-        request.set_circuit_id("01234-ABCDE".to_string());
-
-        let mut header = admin::CircuitManagementPayload_Header::new();
-        header.set_action(admin::CircuitManagementPayload_Action::CIRCUIT_DISBAND_REQUEST);
-        header.set_requester(b"test_signer_a".to_vec());
-        header.set_requester_node_id("node_a".to_string());
-
-        let mut payload = admin::CircuitManagementPayload::new();
-        payload.set_signature(b"test_signer_a".to_vec());
-        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
-        payload.set_circuit_disband_request(request);
-        // start up thread for other node
-        std::thread::spawn(move || {
-            let mut mesh = Mesh::new(2, 2);
-            let conn = other_listener.accept().unwrap();
-            mesh.add(conn, "my_peer_id".to_string()).unwrap();
-
-            handle_auth(&mesh, "my_peer_id", "node_b");
-
-            mesh.signal_shutdown();
-            mesh.wait_for_shutdown().expect("Unable to shutdown mesh");
-        });
-
-        // Set `node_b` to peered
-        shared
-            .on_peer_connected(&PeerTokenPair::new(
-                PeerAuthorizationToken::from_peer_id("node_b"),
-                PeerAuthorizationToken::from_peer_id("node_a"),
-            ))
-            .expect("Unable to set peer to peered");
-
-        shared
-            .propose_disband(
-                payload,
-                &b"test_signer_a".to_vec(),
-                "node_a",
-                "test".to_string(),
-            )
-            .expect("Proposal not accepted");
-
-        // We're fully peered, but need to wait for protocol to be agreed on
-        assert_eq!(1, shared.pending_protocol_payloads.len());
-        assert_eq!(0, shared.pending_circuit_payloads.len());
-
-        // Set `node_b` to agree on the protocol
-        shared
-            .on_protocol_agreement("admin::node_b", ADMIN_SERVICE_PROTOCOL_VERSION)
-            .expect("received unexpected error");
+        let mut update_request = CircuitUpdateNodeKeysRequest::new();
+        update_request.set_circuit_id("01234-ABCDE".into());
+        update_request.set_node_id("node_a".into());
+        update_request.set_public_key(vec![2; 12]);
 
-        // Set `node_a` to agree on the protocol
-        shared
-            .on_protocol_agreement("admin::node_a", ADMIN_SERVICE_PROTOCOL_VERSION)
-            .expect("received unexpected error");
+        if let Ok(()) = admin_shared.validate_update_node_keys(
+            &update_request,
+            &setup_test_circuit(),
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because the public key is malformed");
+        }
 
-        // We're fully peered and agreed on protocol, so the pending payload is now available
-        assert_eq!(0, shared.pending_protocol_payloads.len());
-        assert_eq!(1, shared.pending_circuit_payloads.len());
         shutdown(mesh, cm, pm);
     }
 
@@ -7043,6 +9746,88 @@ mod tests {
         shutdown(mesh, cm, pm);
     }
 
+    /// Tests that circuit proposals are removed by `expire_proposals` only once their expiration
+    /// time has passed, and that a `ProposalExpired` event is recorded.
+    ///
+    /// 1. Set up `AdminServiceShared`
+    /// 2. Add a circuit proposal without an expiration time to the admin store
+    /// 3. Call `expire_proposals` and validate the proposal still exists
+    /// 4. Update the proposal with an expiration time in the past
+    /// 5. Call `expire_proposals` and validate the proposal has been removed
+    /// 6. Validate a `ProposalExpired` event was added for the proposal
+    #[test]
+    fn test_expire_proposals() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let mut admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+
+        let store_proposal = StoreProposal::from_proto(setup_test_proposal(&setup_test_circuit()))
+            .expect("Unable to build CircuitProposal");
+        admin_shared
+            .admin_store
+            .add_proposal(store_proposal.clone())
+            .expect("Unable to add circuit proposal to store");
+
+        admin_shared
+            .expire_proposals()
+            .expect("Unable to expire proposals");
+        assert!(admin_shared
+            .admin_store
+            .get_proposal("01234-ABCDE")
+            .expect("Unable to get circuit proposal")
+            .is_some());
+
+        let expired_proposal = store_proposal
+            .builder()
+            .with_expires_at(1)
+            .build()
+            .expect("Unable to build expired proposal");
+        admin_shared
+            .admin_store
+            .update_proposal(expired_proposal)
+            .expect("Unable to update circuit proposal");
+
+        admin_shared
+            .expire_proposals()
+            .expect("Unable to expire proposals");
+        assert!(admin_shared
+            .admin_store
+            .get_proposal("01234-ABCDE")
+            .expect("Unable to get circuit proposal")
+            .is_none());
+
+        let events = admin_shared
+            .event_store
+            .list_events_since(0)
+            .expect("Unable to list events")
+            .collect::<Vec<_>>();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type(), &store::EventType::ProposalExpired);
+        assert_eq!(events[0].proposal().circuit_id(), "01234-ABCDE");
+
+        shutdown(mesh, cm, pm);
+    }
+
     pub fn setup_test_circuit() -> Circuit {
         let mut service_a = SplinterService::new();
         service_a.set_service_id("0123".to_string());
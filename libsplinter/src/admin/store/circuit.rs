@@ -14,6 +14,7 @@
 
 //! Structs for building circuits
 
+use std::collections::BTreeMap;
 use std::convert::TryFrom;
 
 use crate::admin::messages::{self, is_valid_circuit_id};
@@ -35,6 +36,8 @@ pub struct Circuit {
     persistence: PersistenceType,
     durability: DurabilityType,
     routes: RouteType,
+    relay_nodes: Vec<String>,
+    voting_policy: VotingPolicy,
     circuit_management_type: String,
     display_name: Option<String>,
     circuit_version: i32,
@@ -77,6 +80,16 @@ impl Circuit {
         &self.routes
     }
 
+    /// Returns the node IDs of the members that relay messages for the circuit
+    pub fn relay_nodes(&self) -> &[String] {
+        &self.relay_nodes
+    }
+
+    /// Returns the voting policy used to decide proposals against the circuit
+    pub fn voting_policy(&self) -> &VotingPolicy {
+        &self.voting_policy
+    }
+
     /// Returns the management type of the circuit
     pub fn circuit_management_type(&self) -> &str {
         &self.circuit_management_type
@@ -140,6 +153,8 @@ impl TryFrom<&admin::Circuit> for Circuit {
             .with_persistence(&PersistenceType::try_from(&proto.get_persistence())?)
             .with_durability(&DurabilityType::try_from(&proto.get_durability())?)
             .with_routes(&RouteType::try_from(&proto.get_routes())?)
+            .with_relay_nodes(proto.get_relay_nodes())
+            .with_voting_policy(&VotingPolicy::try_from(proto.get_voting_policy())?)
             .with_circuit_management_type(proto.get_circuit_management_type())
             .with_circuit_version(proto.get_circuit_version())
             .with_circuit_status(&CircuitStatus::try_from(&proto.get_circuit_status())?);
@@ -239,12 +254,14 @@ impl From<&PersistenceType> for admin::Circuit_PersistenceType {
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum DurabilityType {
     NoDurability,
+    GuaranteedDelivery,
 }
 
 impl From<&messages::DurabilityType> for DurabilityType {
     fn from(message_enum: &messages::DurabilityType) -> Self {
         match *message_enum {
             messages::DurabilityType::NoDurability => DurabilityType::NoDurability,
+            messages::DurabilityType::GuaranteedDelivery => DurabilityType::GuaranteedDelivery,
         }
     }
 }
@@ -255,6 +272,9 @@ impl TryFrom<&admin::Circuit_DurabilityType> for DurabilityType {
     fn try_from(proto: &admin::Circuit_DurabilityType) -> Result<Self, Self::Error> {
         match *proto {
             admin::Circuit_DurabilityType::NO_DURABILITY => Ok(DurabilityType::NoDurability),
+            admin::Circuit_DurabilityType::GUARANTEED_DELIVERY => {
+                Ok(DurabilityType::GuaranteedDelivery)
+            }
             admin::Circuit_DurabilityType::UNSET_DURABILITY_TYPE => Err(
                 InvalidStateError::with_message("DurabilityType is unset".to_string()),
             ),
@@ -266,6 +286,9 @@ impl From<&DurabilityType> for admin::Circuit_DurabilityType {
     fn from(durability: &DurabilityType) -> Self {
         match *durability {
             DurabilityType::NoDurability => admin::Circuit_DurabilityType::NO_DURABILITY,
+            DurabilityType::GuaranteedDelivery => {
+                admin::Circuit_DurabilityType::GUARANTEED_DELIVERY
+            }
         }
     }
 }
@@ -274,6 +297,9 @@ impl From<&DurabilityType> for admin::Circuit_DurabilityType {
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum RouteType {
     Any,
+    /// Messages between members that are not relay nodes are forwarded through one of the
+    /// circuit's relay nodes
+    Relay,
 }
 
 impl Default for RouteType {
@@ -286,6 +312,7 @@ impl From<&messages::RouteType> for RouteType {
     fn from(message_enum: &messages::RouteType) -> Self {
         match *message_enum {
             messages::RouteType::Any => RouteType::Any,
+            messages::RouteType::Relay => RouteType::Relay,
         }
     }
 }
@@ -296,6 +323,7 @@ impl TryFrom<&admin::Circuit_RouteType> for RouteType {
     fn try_from(proto: &admin::Circuit_RouteType) -> Result<Self, Self::Error> {
         match *proto {
             admin::Circuit_RouteType::ANY_ROUTE => Ok(RouteType::Any),
+            admin::Circuit_RouteType::RELAY_ROUTE => Ok(RouteType::Relay),
             admin::Circuit_RouteType::UNSET_ROUTE_TYPE => Err(InvalidStateError::with_message(
                 "RouteType is unset".to_string(),
             )),
@@ -307,7 +335,164 @@ impl From<&RouteType> for admin::Circuit_RouteType {
     fn from(route: &RouteType) -> Self {
         match *route {
             RouteType::Any => admin::Circuit_RouteType::ANY_ROUTE,
+            RouteType::Relay => admin::Circuit_RouteType::RELAY_ROUTE,
+        }
+    }
+}
+
+/// How the weight required to accept a proposal against a circuit is determined
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VotingPolicyType {
+    /// Every member must accept the proposal
+    Unanimous,
+    /// Members holding more than half of the total weight must accept the proposal
+    Majority,
+    /// Members holding at least the given weight must accept the proposal
+    Threshold(u32),
+}
+
+impl Default for VotingPolicyType {
+    fn default() -> Self {
+        VotingPolicyType::Unanimous
+    }
+}
+
+/// The policy used to decide if a proposal against a circuit has been accepted
+///
+/// Each member's vote carries a weight, which defaults to 1 for members that are not given an
+/// explicit weight.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VotingPolicy {
+    policy_type: VotingPolicyType,
+    #[serde(default)]
+    weights: BTreeMap<String, u32>,
+}
+
+impl VotingPolicy {
+    /// Creates a new voting policy
+    ///
+    /// # Arguments
+    ///
+    ///  * `policy_type` - How the weight required to accept a proposal is determined
+    ///  * `weights` - The weight of each member's vote, keyed by node ID
+    pub fn new(policy_type: VotingPolicyType, weights: BTreeMap<String, u32>) -> Self {
+        VotingPolicy {
+            policy_type,
+            weights,
+        }
+    }
+
+    /// Returns the policy type
+    pub fn policy_type(&self) -> &VotingPolicyType {
+        &self.policy_type
+    }
+
+    /// Returns the explicitly configured member weights, keyed by node ID
+    pub fn weights(&self) -> &BTreeMap<String, u32> {
+        &self.weights
+    }
+
+    /// Returns the weight of the given member's vote
+    pub fn weight(&self, node_id: &str) -> u32 {
+        self.weights.get(node_id).copied().unwrap_or(1)
+    }
+
+    /// Returns the weight required to accept a proposal, given the total weight of all members
+    pub fn required_weight(&self, total_weight: u32) -> u32 {
+        match self.policy_type {
+            VotingPolicyType::Unanimous => total_weight,
+            VotingPolicyType::Majority => total_weight / 2 + 1,
+            VotingPolicyType::Threshold(threshold) => threshold,
+        }
+    }
+
+    /// Returns true if this is the unanimous policy with no member weights, which is the policy
+    /// of circuits that do not declare one
+    pub fn is_default(&self) -> bool {
+        self == &VotingPolicy::default()
+    }
+}
+
+impl From<&messages::VotingPolicy> for VotingPolicy {
+    fn from(message: &messages::VotingPolicy) -> Self {
+        let policy_type = match message.policy_type {
+            messages::VotingPolicyType::Unanimous => VotingPolicyType::Unanimous,
+            messages::VotingPolicyType::Majority => VotingPolicyType::Majority,
+            messages::VotingPolicyType::Threshold(threshold) => {
+                VotingPolicyType::Threshold(threshold)
+            }
+        };
+
+        VotingPolicy {
+            policy_type,
+            weights: message.weights.clone(),
+        }
+    }
+}
+
+impl TryFrom<&admin::Circuit_VotingPolicy> for VotingPolicy {
+    type Error = InvalidStateError;
+
+    fn try_from(proto: &admin::Circuit_VotingPolicy) -> Result<Self, Self::Error> {
+        let policy_type = match proto.get_policy_type() {
+            admin::Circuit_VotingPolicy_PolicyType::UNANIMOUS => VotingPolicyType::Unanimous,
+            admin::Circuit_VotingPolicy_PolicyType::MAJORITY => VotingPolicyType::Majority,
+            admin::Circuit_VotingPolicy_PolicyType::THRESHOLD => {
+                VotingPolicyType::Threshold(proto.get_threshold())
+            }
+        };
+
+        let mut weights = BTreeMap::new();
+        for node_weight in proto.get_weights() {
+            if weights
+                .insert(
+                    node_weight.get_node_id().to_string(),
+                    node_weight.get_weight(),
+                )
+                .is_some()
+            {
+                return Err(InvalidStateError::with_message(format!(
+                    "Voting policy has more than one weight for node {}",
+                    node_weight.get_node_id()
+                )));
+            }
+        }
+
+        Ok(VotingPolicy {
+            policy_type,
+            weights,
+        })
+    }
+}
+
+impl From<&VotingPolicy> for admin::Circuit_VotingPolicy {
+    fn from(voting_policy: &VotingPolicy) -> Self {
+        let mut proto = admin::Circuit_VotingPolicy::new();
+        match voting_policy.policy_type {
+            VotingPolicyType::Unanimous => {
+                proto.set_policy_type(admin::Circuit_VotingPolicy_PolicyType::UNANIMOUS)
+            }
+            VotingPolicyType::Majority => {
+                proto.set_policy_type(admin::Circuit_VotingPolicy_PolicyType::MAJORITY)
+            }
+            VotingPolicyType::Threshold(threshold) => {
+                proto.set_policy_type(admin::Circuit_VotingPolicy_PolicyType::THRESHOLD);
+                proto.set_threshold(threshold);
+            }
         }
+        proto.set_weights(
+            voting_policy
+                .weights
+                .iter()
+                .map(|(node_id, weight)| {
+                    let mut node_weight = admin::Circuit_VotingPolicy_NodeWeight::new();
+                    node_weight.set_node_id(node_id.to_string());
+                    node_weight.set_weight(*weight);
+                    node_weight
+                })
+                .collect(),
+        );
+        proto
     }
 }
 
@@ -317,6 +502,7 @@ pub enum CircuitStatus {
     Active,
     Disbanded,
     Abandoned,
+    Suspended,
 }
 
 impl Default for CircuitStatus {
@@ -331,6 +517,7 @@ impl From<&messages::CircuitStatus> for CircuitStatus {
             messages::CircuitStatus::Active => CircuitStatus::Active,
             messages::CircuitStatus::Disbanded => CircuitStatus::Disbanded,
             messages::CircuitStatus::Abandoned => CircuitStatus::Abandoned,
+            messages::CircuitStatus::Suspended => CircuitStatus::Suspended,
         }
     }
 }
@@ -342,6 +529,7 @@ impl TryFrom<String> for CircuitStatus {
         match &*str {
             "disbanded" => Ok(CircuitStatus::Disbanded),
             "abandoned" => Ok(CircuitStatus::Abandoned),
+            "suspended" => Ok(CircuitStatus::Suspended),
             "active" => Ok(CircuitStatus::Active),
             s => Err(InvalidStateError::with_message(format!(
                 "could not form CircuitStatus from: {s}"
@@ -358,6 +546,7 @@ impl TryFrom<&admin::Circuit_CircuitStatus> for CircuitStatus {
             admin::Circuit_CircuitStatus::ACTIVE => Ok(CircuitStatus::Active),
             admin::Circuit_CircuitStatus::DISBANDED => Ok(CircuitStatus::Disbanded),
             admin::Circuit_CircuitStatus::ABANDONED => Ok(CircuitStatus::Abandoned),
+            admin::Circuit_CircuitStatus::SUSPENDED => Ok(CircuitStatus::Suspended),
             admin::Circuit_CircuitStatus::UNSET_CIRCUIT_STATUS => {
                 debug!("Defaulting `UNSET_CIRCUIT_STATUS` of proposed circuit to `Active`");
                 Ok(CircuitStatus::Active)
@@ -372,6 +561,7 @@ impl From<&CircuitStatus> for admin::Circuit_CircuitStatus {
             CircuitStatus::Active => admin::Circuit_CircuitStatus::ACTIVE,
             CircuitStatus::Disbanded => admin::Circuit_CircuitStatus::DISBANDED,
             CircuitStatus::Abandoned => admin::Circuit_CircuitStatus::ABANDONED,
+            CircuitStatus::Suspended => admin::Circuit_CircuitStatus::SUSPENDED,
         }
     }
 }
@@ -386,6 +576,8 @@ pub struct CircuitBuilder {
     persistence: Option<PersistenceType>,
     durability: Option<DurabilityType>,
     routes: Option<RouteType>,
+    relay_nodes: Option<Vec<String>>,
+    voting_policy: Option<VotingPolicy>,
     circuit_management_type: Option<String>,
     display_name: Option<String>,
     circuit_version: Option<i32>,
@@ -433,6 +625,16 @@ impl CircuitBuilder {
         self.routes.clone()
     }
 
+    /// Returns the relay node IDs in the builder
+    pub fn relay_nodes(&self) -> Option<Vec<String>> {
+        self.relay_nodes.clone()
+    }
+
+    /// Returns the voting policy in the builder
+    pub fn voting_policy(&self) -> Option<VotingPolicy> {
+        self.voting_policy.clone()
+    }
+
     /// Returns the circuit management type in the builder
     pub fn circuit_management_type(&self) -> Option<String> {
         self.circuit_management_type.clone()
@@ -526,6 +728,26 @@ impl CircuitBuilder {
         self
     }
 
+    /// Sets the relay nodes
+    ///
+    /// # Arguments
+    ///
+    ///  * `relay_nodes` - The node IDs of the members that relay messages for the circuit
+    pub fn with_relay_nodes(mut self, relay_nodes: &[String]) -> CircuitBuilder {
+        self.relay_nodes = Some(relay_nodes.into());
+        self
+    }
+
+    /// Sets the voting policy
+    ///
+    /// # Arguments
+    ///
+    ///  * `voting_policy` - The policy used to decide proposals against the circuit
+    pub fn with_voting_policy(mut self, voting_policy: &VotingPolicy) -> CircuitBuilder {
+        self.voting_policy = Some(voting_policy.clone());
+        self
+    }
+
     /// Sets the circuit management type
     ///
     /// # Arguments
@@ -605,6 +827,10 @@ impl CircuitBuilder {
 
         let routes = self.routes.unwrap_or_default();
 
+        let relay_nodes = self.relay_nodes.unwrap_or_default();
+
+        let voting_policy = self.voting_policy.unwrap_or_default();
+
         let circuit_management_type = self.circuit_management_type.ok_or_else(|| {
             InvalidStateError::with_message(
                 "unable to build, missing field: `circuit_management_type`".to_string(),
@@ -625,6 +851,8 @@ impl CircuitBuilder {
             persistence,
             durability,
             routes,
+            relay_nodes,
+            voting_policy,
             circuit_management_type,
             display_name,
             circuit_version,
@@ -645,6 +873,8 @@ impl From<ProposedCircuit> for Circuit {
             persistence: circuit.persistence().clone(),
             durability: circuit.durability().clone(),
             routes: circuit.routes().clone(),
+            relay_nodes: circuit.relay_nodes().to_vec(),
+            voting_policy: circuit.voting_policy().clone(),
             circuit_management_type: circuit.circuit_management_type().into(),
             display_name: circuit.display_name().clone(),
             circuit_version: circuit.circuit_version(),
@@ -661,3 +891,21 @@ impl From<&AuthorizationType> for routing::AuthorizationType {
         }
     }
 }
+
+impl From<&DurabilityType> for routing::DurabilityType {
+    fn from(durability: &DurabilityType) -> Self {
+        match durability {
+            DurabilityType::NoDurability => routing::DurabilityType::NoDurability,
+            DurabilityType::GuaranteedDelivery => routing::DurabilityType::GuaranteedDelivery,
+        }
+    }
+}
+
+impl From<&Circuit> for routing::RouteType {
+    fn from(circuit: &Circuit) -> Self {
+        match circuit.routes() {
+            RouteType::Any => routing::RouteType::Any,
+            RouteType::Relay => routing::RouteType::Relay(circuit.relay_nodes().to_vec()),
+        }
+    }
+}
@@ -33,6 +33,7 @@ pub struct CircuitProposal {
     votes: Vec<VoteRecord>,
     requester: PublicKey,
     requester_node_id: String,
+    expires_at: Option<u64>,
 }
 
 impl CircuitProposal {
@@ -71,15 +72,32 @@ impl CircuitProposal {
         &self.requester_node_id
     }
 
+    /// Returns the time, in seconds since the Unix epoch, after which the proposal expires, if
+    /// the proposal expires
+    pub fn expires_at(&self) -> Option<u64> {
+        self.expires_at
+    }
+
+    /// Returns true if the proposal expires at or before the given time, in seconds since the
+    /// Unix epoch
+    pub fn is_expired(&self, now: u64) -> bool {
+        matches!(self.expires_at, Some(expires_at) if expires_at <= now)
+    }
+
     pub fn builder(&self) -> CircuitProposalBuilder {
-        CircuitProposalBuilder::new()
+        let builder = CircuitProposalBuilder::new()
             .with_proposal_type(self.proposal_type())
             .with_circuit_id(self.circuit_id())
             .with_circuit_hash(self.circuit_hash())
             .with_circuit(self.circuit())
             .with_votes(self.votes())
             .with_requester(self.requester())
-            .with_requester_node_id(self.requester_node_id())
+            .with_requester_node_id(self.requester_node_id());
+
+        match self.expires_at {
+            Some(expires_at) => builder.with_expires_at(expires_at),
+            None => builder,
+        }
     }
 
     pub fn from_proto(mut proto: admin::CircuitProposal) -> Result<Self, InvalidStateError> {
@@ -89,6 +107,9 @@ impl CircuitProposal {
             admin::CircuitProposal_ProposalType::ADD_NODE => ProposalType::AddNode,
             admin::CircuitProposal_ProposalType::REMOVE_NODE => ProposalType::RemoveNode,
             admin::CircuitProposal_ProposalType::DISBAND => ProposalType::Disband,
+            admin::CircuitProposal_ProposalType::SUSPEND => ProposalType::Suspend,
+            admin::CircuitProposal_ProposalType::RESUME => ProposalType::Resume,
+            admin::CircuitProposal_ProposalType::UPDATE_NODE_KEYS => ProposalType::UpdateNodeKeys,
             admin::CircuitProposal_ProposalType::UNSET_PROPOSAL_TYPE => {
                 return Err(InvalidStateError::with_message(
                     "unable to build, missing field: `proposal type`".to_string(),
@@ -110,6 +131,10 @@ impl CircuitProposal {
             votes,
             requester: PublicKey::from_bytes(proto.take_requester()),
             requester_node_id: proto.take_requester_node_id(),
+            expires_at: match proto.get_expires_at() {
+                0 => None,
+                expires_at => Some(expires_at),
+            },
         })
     }
 
@@ -120,6 +145,9 @@ impl CircuitProposal {
             ProposalType::AddNode => admin::CircuitProposal_ProposalType::ADD_NODE,
             ProposalType::RemoveNode => admin::CircuitProposal_ProposalType::REMOVE_NODE,
             ProposalType::Disband => admin::CircuitProposal_ProposalType::DISBAND,
+            ProposalType::Suspend => admin::CircuitProposal_ProposalType::SUSPEND,
+            ProposalType::Resume => admin::CircuitProposal_ProposalType::RESUME,
+            ProposalType::UpdateNodeKeys => admin::CircuitProposal_ProposalType::UPDATE_NODE_KEYS,
         };
 
         let votes = self
@@ -138,6 +166,7 @@ impl CircuitProposal {
         proposal.set_votes(protobuf::RepeatedField::from_vec(votes));
         proposal.set_requester(self.requester.into_bytes());
         proposal.set_requester_node_id(self.requester_node_id);
+        proposal.set_expires_at(self.expires_at.unwrap_or(0));
 
         proposal
     }
@@ -153,6 +182,7 @@ pub struct CircuitProposalBuilder {
     votes: Option<Vec<VoteRecord>>,
     requester: Option<PublicKey>,
     requester_node_id: Option<String>,
+    expires_at: Option<u64>,
 }
 
 impl CircuitProposalBuilder {
@@ -196,6 +226,11 @@ impl CircuitProposalBuilder {
         self.requester_node_id.clone()
     }
 
+    /// Returns the time, in seconds since the Unix epoch, after which the proposal expires
+    pub fn expires_at(&self) -> Option<u64> {
+        self.expires_at
+    }
+
     /// Set the proposal type of the circuit proposal
     ///
     /// # Arguments
@@ -268,6 +303,16 @@ impl CircuitProposalBuilder {
         self
     }
 
+    /// Sets the time after which the proposal expires; if not set, the proposal does not expire
+    ///
+    /// # Arguments
+    ///
+    ///  * `expires_at` - The expiration time, in seconds since the Unix epoch
+    pub fn with_expires_at(mut self, expires_at: u64) -> CircuitProposalBuilder {
+        self.expires_at = Some(expires_at);
+        self
+    }
+
     /// Builds a `CircuitProposal`
     ///
     /// Returns an error if the circuit ID, circuit, circuit hash, requester, or requester node id
@@ -327,6 +372,7 @@ impl CircuitProposalBuilder {
             votes,
             requester,
             requester_node_id,
+            expires_at: self.expires_at,
         })
     }
 }
@@ -335,7 +381,7 @@ impl TryFrom<&messages::CircuitProposal> for CircuitProposal {
     type Error = InvalidStateError;
 
     fn try_from(admin_proposal: &messages::CircuitProposal) -> Result<Self, Self::Error> {
-        CircuitProposalBuilder::new()
+        let builder = CircuitProposalBuilder::new()
             .with_proposal_type(&ProposalType::from(&admin_proposal.proposal_type))
             .with_circuit_id(&admin_proposal.circuit_id)
             .with_circuit_hash(&admin_proposal.circuit_hash)
@@ -348,8 +394,13 @@ impl TryFrom<&messages::CircuitProposal> for CircuitProposal {
                     .collect::<Vec<VoteRecord>>(),
             )
             .with_requester(&PublicKey::from_bytes(admin_proposal.requester.clone()))
-            .with_requester_node_id(&admin_proposal.requester_node_id)
-            .build()
+            .with_requester_node_id(&admin_proposal.requester_node_id);
+
+        match admin_proposal.expires_at {
+            Some(expires_at) => builder.with_expires_at(expires_at),
+            None => builder,
+        }
+        .build()
     }
 }
 
@@ -499,6 +550,9 @@ pub enum ProposalType {
     AddNode,
     RemoveNode,
     Disband,
+    Suspend,
+    Resume,
+    UpdateNodeKeys,
 }
 
 impl From<&messages::ProposalType> for ProposalType {
@@ -509,6 +563,9 @@ impl From<&messages::ProposalType> for ProposalType {
             messages::ProposalType::AddNode => ProposalType::AddNode,
             messages::ProposalType::RemoveNode => ProposalType::RemoveNode,
             messages::ProposalType::Disband => ProposalType::Disband,
+            messages::ProposalType::Suspend => ProposalType::Suspend,
+            messages::ProposalType::Resume => ProposalType::Resume,
+            messages::ProposalType::UpdateNodeKeys => ProposalType::UpdateNodeKeys,
         }
     }
 }
@@ -525,6 +582,11 @@ impl TryFrom<&admin::CircuitProposal_ProposalType> for ProposalType {
             admin::CircuitProposal_ProposalType::ADD_NODE => Ok(ProposalType::AddNode),
             admin::CircuitProposal_ProposalType::REMOVE_NODE => Ok(ProposalType::RemoveNode),
             admin::CircuitProposal_ProposalType::DISBAND => Ok(ProposalType::Disband),
+            admin::CircuitProposal_ProposalType::SUSPEND => Ok(ProposalType::Suspend),
+            admin::CircuitProposal_ProposalType::RESUME => Ok(ProposalType::Resume),
+            admin::CircuitProposal_ProposalType::UPDATE_NODE_KEYS => {
+                Ok(ProposalType::UpdateNodeKeys)
+            }
             admin::CircuitProposal_ProposalType::UNSET_PROPOSAL_TYPE => Err(
                 InvalidStateError::with_message("ProposalType is unset".to_string()),
             ),
@@ -42,7 +42,7 @@ use crate::admin::store::error::AdminServiceStoreError;
 use crate::admin::store::{AdminServiceEvent, AdminServiceEventBuilder, EventType};
 use crate::admin::store::{
     AuthorizationType, CircuitStatus, DurabilityType, PersistenceType, ProposalType, RouteType,
-    Vote, VoteRecord, VoteRecordBuilder,
+    Vote, VoteRecord, VoteRecordBuilder, VotingPolicy,
 };
 use crate::admin::store::{Circuit, CircuitProposal, ProposedCircuit};
 use crate::error::{InternalError, InvalidStateError};
@@ -60,6 +60,7 @@ pub struct CircuitProposalModel {
     pub circuit_hash: String,
     pub requester: Vec<u8>,
     pub requester_node_id: String,
+    pub expires_at: Option<i64>,
 }
 
 impl From<&CircuitProposal> for CircuitProposalModel {
@@ -70,6 +71,7 @@ impl From<&CircuitProposal> for CircuitProposalModel {
             circuit_hash: proposal.circuit_hash().into(),
             requester: proposal.requester().as_slice().to_vec(),
             requester_node_id: proposal.requester_node_id().into(),
+            expires_at: proposal.expires_at().map(|expires_at| expires_at as i64),
         }
     }
 }
@@ -93,6 +95,8 @@ pub struct ProposedCircuitModel {
     pub display_name: Option<String>,
     pub circuit_version: i32,
     pub circuit_status: CircuitStatusModel,
+    pub relay_nodes: Option<String>,
+    pub voting_policy: Option<String>,
 }
 
 impl From<&ProposedCircuit> for ProposedCircuitModel {
@@ -109,6 +113,8 @@ impl From<&ProposedCircuit> for ProposedCircuitModel {
             display_name: proposed_circuit.display_name().clone(),
             circuit_version: proposed_circuit.circuit_version(),
             circuit_status: CircuitStatusModel::from(proposed_circuit.circuit_status()),
+            relay_nodes: relay_nodes_to_column(proposed_circuit.relay_nodes()),
+            voting_policy: voting_policy_to_column(proposed_circuit.voting_policy()),
         }
     }
 }
@@ -438,6 +444,8 @@ pub struct CircuitModel {
     pub display_name: Option<String>,
     pub circuit_version: i32,
     pub circuit_status: CircuitStatusModel,
+    pub relay_nodes: Option<String>,
+    pub voting_policy: Option<String>,
 }
 
 impl From<&Circuit> for CircuitModel {
@@ -452,6 +460,8 @@ impl From<&Circuit> for CircuitModel {
             display_name: circuit.display_name().clone(),
             circuit_version: circuit.circuit_version(),
             circuit_status: CircuitStatusModel::from(circuit.circuit_status()),
+            relay_nodes: relay_nodes_to_column(circuit.relay_nodes()),
+            voting_policy: voting_policy_to_column(circuit.voting_policy()),
         }
     }
 }
@@ -541,6 +551,7 @@ pub struct AdminEventCircuitProposalModel {
     pub circuit_hash: String,
     pub requester: Vec<u8>,
     pub requester_node_id: String,
+    pub expires_at: Option<i64>,
 }
 
 impl From<(i64, &messages::CircuitProposal)> for AdminEventCircuitProposalModel {
@@ -552,6 +563,7 @@ impl From<(i64, &messages::CircuitProposal)> for AdminEventCircuitProposalModel
             circuit_hash: proposal.circuit_hash.to_string(),
             requester: proposal.requester.to_vec(),
             requester_node_id: proposal.requester_node_id.to_string(),
+            expires_at: proposal.expires_at.map(|expires_at| expires_at as i64),
         }
     }
 }
@@ -576,6 +588,8 @@ pub struct AdminEventProposedCircuitModel {
     pub display_name: Option<String>,
     pub circuit_version: i32,
     pub circuit_status: CircuitStatusModel,
+    pub relay_nodes: Option<String>,
+    pub voting_policy: Option<String>,
 }
 
 impl From<(i64, &CreateCircuit)> for AdminEventProposedCircuitModel {
@@ -599,6 +613,10 @@ impl From<(i64, &CreateCircuit)> for AdminEventProposedCircuitModel {
             display_name: create_circuit.display_name.clone(),
             circuit_version: create_circuit.circuit_version,
             circuit_status: CircuitStatusModel::from(&create_circuit.circuit_status),
+            relay_nodes: relay_nodes_to_column(&create_circuit.relay_nodes),
+            voting_policy: voting_policy_to_column(&VotingPolicy::from(
+                &create_circuit.voting_policy,
+            )),
         }
     }
 }
@@ -888,6 +906,18 @@ impl<'a> From<&'a messages::AdminServiceEvent> for NewAdminServiceEventModel<'a>
                 event_type: "CircuitDisbanded",
                 data: None,
             },
+            messages::AdminServiceEvent::ProposalExpired(_) => NewAdminServiceEventModel {
+                event_type: "ProposalExpired",
+                data: None,
+            },
+            messages::AdminServiceEvent::CircuitSuspended(_) => NewAdminServiceEventModel {
+                event_type: "CircuitSuspended",
+                data: None,
+            },
+            messages::AdminServiceEvent::CircuitResumed(_) => NewAdminServiceEventModel {
+                event_type: "CircuitResumed",
+                data: None,
+            },
         }
     }
 }
@@ -935,6 +965,24 @@ impl TryFrom<(AdminServiceEventModel, CircuitProposal)> for AdminServiceEvent {
                 .with_proposal(&proposal)
                 .build()
                 .map_err(AdminServiceStoreError::InvalidStateError),
+            ("ProposalExpired", None) => AdminServiceEventBuilder::new()
+                .with_event_id(event_model.id)
+                .with_event_type(&EventType::ProposalExpired)
+                .with_proposal(&proposal)
+                .build()
+                .map_err(AdminServiceStoreError::InvalidStateError),
+            ("CircuitSuspended", None) => AdminServiceEventBuilder::new()
+                .with_event_id(event_model.id)
+                .with_event_type(&EventType::CircuitSuspended)
+                .with_proposal(&proposal)
+                .build()
+                .map_err(AdminServiceStoreError::InvalidStateError),
+            ("CircuitResumed", None) => AdminServiceEventBuilder::new()
+                .with_event_id(event_model.id)
+                .with_event_type(&EventType::CircuitResumed)
+                .with_proposal(&proposal)
+                .build()
+                .map_err(AdminServiceStoreError::InvalidStateError),
             _ => Err(AdminServiceStoreError::InvalidStateError(
                 InvalidStateError::with_message(
                     "Unable to convert AdminServiceEventModel to AdminServiceEvent".into(),
@@ -987,6 +1035,9 @@ impl TryFrom<String> for ProposalType {
             "AddNode" => Ok(ProposalType::AddNode),
             "RemoveNode" => Ok(ProposalType::RemoveNode),
             "Disband" => Ok(ProposalType::Disband),
+            "Suspend" => Ok(ProposalType::Suspend),
+            "Resume" => Ok(ProposalType::Resume),
+            "UpdateNodeKeys" => Ok(ProposalType::UpdateNodeKeys),
             _ => Err(AdminServiceStoreError::InvalidStateError(
                 InvalidStateError::with_message("Unable to convert string to ProposalType".into()),
             )),
@@ -1002,6 +1053,9 @@ impl From<&ProposalType> for String {
             ProposalType::AddNode => String::from("AddNode"),
             ProposalType::RemoveNode => String::from("RemoveNode"),
             ProposalType::Disband => String::from("Disband"),
+            ProposalType::Suspend => String::from("Suspend"),
+            ProposalType::Resume => String::from("Resume"),
+            ProposalType::UpdateNodeKeys => String::from("UpdateNodeKeys"),
         }
     }
 }
@@ -1014,6 +1068,9 @@ impl From<&messages::ProposalType> for String {
             messages::ProposalType::AddNode => String::from("AddNode"),
             messages::ProposalType::RemoveNode => String::from("RemoveNode"),
             messages::ProposalType::Disband => String::from("Disband"),
+            messages::ProposalType::Suspend => String::from("Suspend"),
+            messages::ProposalType::Resume => String::from("Resume"),
+            messages::ProposalType::UpdateNodeKeys => String::from("UpdateNodeKeys"),
         }
     }
 }
@@ -1086,6 +1143,7 @@ impl TryFrom<String> for DurabilityType {
     fn try_from(variant: String) -> Result<Self, Self::Error> {
         match variant.as_ref() {
             "NoDurability" => Ok(DurabilityType::NoDurability),
+            "GuaranteedDelivery" => Ok(DurabilityType::GuaranteedDelivery),
             _ => Err(AdminServiceStoreError::InvalidStateError(
                 InvalidStateError::with_message(
                     "Unable to convert string to DurabilityType".into(),
@@ -1099,6 +1157,7 @@ impl From<&DurabilityType> for String {
     fn from(variant: &DurabilityType) -> Self {
         match variant {
             DurabilityType::NoDurability => String::from("NoDurability"),
+            DurabilityType::GuaranteedDelivery => String::from("GuaranteedDelivery"),
         }
     }
 }
@@ -1107,6 +1166,7 @@ impl From<&messages::DurabilityType> for String {
     fn from(variant: &messages::DurabilityType) -> Self {
         match variant {
             messages::DurabilityType::NoDurability => String::from("NoDurability"),
+            messages::DurabilityType::GuaranteedDelivery => String::from("GuaranteedDelivery"),
         }
     }
 }
@@ -1116,6 +1176,7 @@ impl TryFrom<String> for RouteType {
     fn try_from(variant: String) -> Result<Self, Self::Error> {
         match variant.as_ref() {
             "Any" => Ok(RouteType::Any),
+            "Relay" => Ok(RouteType::Relay),
             _ => Err(AdminServiceStoreError::InvalidStateError(
                 InvalidStateError::with_message("Unable to convert string to RouteType".into()),
             )),
@@ -1127,6 +1188,7 @@ impl From<&RouteType> for String {
     fn from(variant: &RouteType) -> Self {
         match variant {
             RouteType::Any => String::from("Any"),
+            RouteType::Relay => String::from("Relay"),
         }
     }
 }
@@ -1135,16 +1197,70 @@ impl From<&messages::RouteType> for String {
     fn from(variant: &messages::RouteType) -> Self {
         match variant {
             messages::RouteType::Any => String::from("Any"),
+            messages::RouteType::Relay => String::from("Relay"),
         }
     }
 }
 
+/// Converts a circuit's relay nodes into the value stored in the `relay_nodes` column.
+///
+/// The relay nodes are stored as a JSON array; circuits without relay nodes store `NULL`.
+pub fn relay_nodes_to_column(relay_nodes: &[String]) -> Option<String> {
+    if relay_nodes.is_empty() {
+        None
+    } else {
+        // serializing a list of strings cannot fail
+        serde_json::to_string(relay_nodes).ok()
+    }
+}
+
+/// Converts the value stored in the `relay_nodes` column into a circuit's relay nodes.
+pub fn relay_nodes_from_column(
+    column: Option<String>,
+) -> Result<Vec<String>, AdminServiceStoreError> {
+    match column {
+        Some(json) => serde_json::from_str(&json).map_err(|_| {
+            AdminServiceStoreError::InvalidStateError(InvalidStateError::with_message(
+                "Unable to convert string to relay nodes".into(),
+            ))
+        }),
+        None => Ok(vec![]),
+    }
+}
+
+/// Converts a circuit's voting policy into the value stored in the `voting_policy` column.
+///
+/// The voting policy is stored as JSON; circuits that use the default policy store `NULL`.
+pub fn voting_policy_to_column(voting_policy: &VotingPolicy) -> Option<String> {
+    if voting_policy.is_default() {
+        None
+    } else {
+        // serializing a voting policy cannot fail
+        serde_json::to_string(voting_policy).ok()
+    }
+}
+
+/// Converts the value stored in the `voting_policy` column into a circuit's voting policy.
+pub fn voting_policy_from_column(
+    column: Option<String>,
+) -> Result<VotingPolicy, AdminServiceStoreError> {
+    match column {
+        Some(json) => serde_json::from_str(&json).map_err(|_| {
+            AdminServiceStoreError::InvalidStateError(InvalidStateError::with_message(
+                "Unable to convert string to voting policy".into(),
+            ))
+        }),
+        None => Ok(VotingPolicy::default()),
+    }
+}
+
 #[repr(i16)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq, FromSqlRow)]
 pub enum CircuitStatusModel {
     Active = 1,
     Disbanded = 2,
     Abandoned = 3,
+    Suspended = 4,
 }
 
 impl From<&CircuitStatus> for CircuitStatusModel {
@@ -1153,6 +1269,7 @@ impl From<&CircuitStatus> for CircuitStatusModel {
             CircuitStatus::Active => CircuitStatusModel::Active,
             CircuitStatus::Disbanded => CircuitStatusModel::Disbanded,
             CircuitStatus::Abandoned => CircuitStatusModel::Abandoned,
+            CircuitStatus::Suspended => CircuitStatusModel::Suspended,
         }
     }
 }
@@ -1163,6 +1280,7 @@ impl From<&messages::CircuitStatus> for CircuitStatusModel {
             messages::CircuitStatus::Active => CircuitStatusModel::Active,
             messages::CircuitStatus::Disbanded => CircuitStatusModel::Disbanded,
             messages::CircuitStatus::Abandoned => CircuitStatusModel::Abandoned,
+            messages::CircuitStatus::Suspended => CircuitStatusModel::Suspended,
         }
     }
 }
@@ -1173,6 +1291,7 @@ impl From<&CircuitStatusModel> for CircuitStatus {
             CircuitStatusModel::Active => CircuitStatus::Active,
             CircuitStatusModel::Disbanded => CircuitStatus::Disbanded,
             CircuitStatusModel::Abandoned => CircuitStatus::Abandoned,
+            CircuitStatusModel::Suspended => CircuitStatus::Suspended,
         }
     }
 }
@@ -1183,6 +1302,7 @@ impl From<&CircuitStatusModel> for messages::CircuitStatus {
             CircuitStatusModel::Active => messages::CircuitStatus::Active,
             CircuitStatusModel::Disbanded => messages::CircuitStatus::Disbanded,
             CircuitStatusModel::Abandoned => messages::CircuitStatus::Abandoned,
+            CircuitStatusModel::Suspended => messages::CircuitStatus::Suspended,
         }
     }
 }
@@ -1223,6 +1343,7 @@ where
             1 => Ok(CircuitStatusModel::Active),
             2 => Ok(CircuitStatusModel::Disbanded),
             3 => Ok(CircuitStatusModel::Abandoned),
+            4 => Ok(CircuitStatusModel::Suspended),
             int => Err(format!("Invalid circuit status {}", int).into()),
         }
     }
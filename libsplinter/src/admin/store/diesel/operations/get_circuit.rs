@@ -22,7 +22,10 @@ use std::convert::TryFrom;
 use super::{list_services::AdminServiceStoreListServicesOperation, AdminServiceStoreOperations};
 use crate::admin::store::{
     diesel::{
-        models::{CircuitMemberModel, CircuitModel, NodeEndpointModel},
+        models::{
+            relay_nodes_from_column, voting_policy_from_column, CircuitMemberModel, CircuitModel,
+            NodeEndpointModel,
+        },
         schema::{circuit, circuit_member, node_endpoint},
     },
     error::AdminServiceStoreError,
@@ -123,6 +126,8 @@ where
                 .with_persistence(&PersistenceType::try_from(circuit.persistence)?)
                 .with_durability(&DurabilityType::try_from(circuit.durability)?)
                 .with_routes(&RouteType::try_from(circuit.routes)?)
+                .with_relay_nodes(&relay_nodes_from_column(circuit.relay_nodes)?)
+                .with_voting_policy(&voting_policy_from_column(circuit.voting_policy)?)
                 .with_circuit_management_type(&circuit.circuit_management_type)
                 .with_circuit_version(circuit.circuit_version)
                 .with_circuit_status(&CircuitStatus::from(&circuit.circuit_status));
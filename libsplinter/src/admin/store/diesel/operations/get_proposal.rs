@@ -16,7 +16,7 @@
 
 use diesel::{
     prelude::*,
-    sql_types::{Binary, Integer, Nullable, SmallInt, Text},
+    sql_types::{BigInt, Binary, Integer, Nullable, SmallInt, Text},
 };
 use std::collections::HashMap;
 use std::convert::TryFrom;
@@ -25,8 +25,9 @@ use super::AdminServiceStoreOperations;
 use crate::admin::store::{
     diesel::{
         models::{
-            CircuitProposalModel, ProposedCircuitModel, ProposedNodeEndpointModel,
-            ProposedNodeModel, ProposedServiceArgumentModel, ProposedServiceModel, VoteRecordModel,
+            relay_nodes_from_column, voting_policy_from_column, CircuitProposalModel,
+            ProposedCircuitModel, ProposedNodeEndpointModel, ProposedNodeModel,
+            ProposedServiceArgumentModel, ProposedServiceModel, VoteRecordModel,
         },
         schema::{
             circuit_proposal, proposed_circuit, proposed_node, proposed_node_endpoint,
@@ -53,7 +54,8 @@ where
     String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
     i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
     i32: diesel::deserialize::FromSql<diesel::sql_types::Integer, C::Backend>,
-    CircuitProposalModel: diesel::Queryable<(Text, Text, Text, Binary, Text), C::Backend>,
+    CircuitProposalModel:
+        diesel::Queryable<(Text, Text, Text, Binary, Text, Nullable<BigInt>), C::Backend>,
     ProposedCircuitModel: diesel::Queryable<
         (
             Text,
@@ -245,6 +247,8 @@ where
                 .with_persistence(&PersistenceType::try_from(proposed_circuit.persistence)?)
                 .with_durability(&DurabilityType::try_from(proposed_circuit.durability)?)
                 .with_routes(&RouteType::try_from(proposed_circuit.routes)?)
+                .with_relay_nodes(&relay_nodes_from_column(proposed_circuit.relay_nodes)?)
+                .with_voting_policy(&voting_policy_from_column(proposed_circuit.voting_policy)?)
                 .with_circuit_management_type(&proposed_circuit.circuit_management_type)
                 .with_circuit_version(proposed_circuit.circuit_version)
                 .with_circuit_status(&CircuitStatus::from(&proposed_circuit.circuit_status));
@@ -264,15 +268,20 @@ where
             let native_proposed_circuit = builder
                 .build()
                 .map_err(AdminServiceStoreError::InvalidStateError)?;
+            let mut proposal_builder = CircuitProposalBuilder::new()
+                .with_proposal_type(&ProposalType::try_from(proposal.proposal_type)?)
+                .with_circuit_id(&proposal.circuit_id)
+                .with_circuit_hash(&proposal.circuit_hash)
+                .with_circuit(&native_proposed_circuit)
+                .with_votes(&vote_record)
+                .with_requester(&PublicKey::from_bytes(proposal.requester.to_vec()))
+                .with_requester_node_id(&proposal.requester_node_id);
+            if let Some(expires_at) = proposal.expires_at {
+                proposal_builder = proposal_builder.with_expires_at(expires_at as u64);
+            }
+
             Ok(Some(
-                CircuitProposalBuilder::new()
-                    .with_proposal_type(&ProposalType::try_from(proposal.proposal_type)?)
-                    .with_circuit_id(&proposal.circuit_id)
-                    .with_circuit_hash(&proposal.circuit_hash)
-                    .with_circuit(&native_proposed_circuit)
-                    .with_votes(&vote_record)
-                    .with_requester(&PublicKey::from_bytes(proposal.requester.to_vec()))
-                    .with_requester_node_id(&proposal.requester_node_id)
+                proposal_builder
                     .build()
                     .map_err(AdminServiceStoreError::InvalidStateError)?,
             ))
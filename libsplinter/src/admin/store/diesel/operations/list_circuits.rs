@@ -23,8 +23,8 @@ use diesel::{dsl::exists, prelude::*};
 use crate::admin::store::{
     diesel::{
         models::{
-            CircuitMemberModel, CircuitModel, CircuitStatusModel, NodeEndpointModel,
-            ServiceArgumentModel, ServiceModel,
+            relay_nodes_from_column, voting_policy_from_column, CircuitMemberModel, CircuitModel,
+            CircuitStatusModel, NodeEndpointModel, ServiceArgumentModel, ServiceModel,
         },
         schema::{circuit, circuit_member, node_endpoint, service, service_argument},
     },
@@ -251,6 +251,8 @@ where
                         .with_persistence(&PersistenceType::try_from(model.persistence)?)
                         .with_durability(&DurabilityType::try_from(model.durability)?)
                         .with_routes(&RouteType::try_from(model.routes)?)
+                        .with_relay_nodes(&relay_nodes_from_column(model.relay_nodes)?)
+                        .with_voting_policy(&voting_policy_from_column(model.voting_policy)?)
                         .with_circuit_management_type(&model.circuit_management_type)
                         .with_circuit_version(model.circuit_version)
                         .with_circuit_status(&CircuitStatus::from(&model.circuit_status));
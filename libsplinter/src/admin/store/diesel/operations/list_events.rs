@@ -25,10 +25,10 @@ use super::AdminServiceStoreOperations;
 use crate::admin::store::{
     diesel::{
         models::{
-            AdminEventCircuitProposalModel, AdminEventProposedCircuitModel,
-            AdminEventProposedNodeEndpointModel, AdminEventProposedNodeModel,
-            AdminEventProposedServiceArgumentModel, AdminEventProposedServiceModel,
-            AdminEventVoteRecordModel, AdminServiceEventModel,
+            relay_nodes_from_column, voting_policy_from_column, AdminEventCircuitProposalModel,
+            AdminEventProposedCircuitModel, AdminEventProposedNodeEndpointModel,
+            AdminEventProposedNodeModel, AdminEventProposedServiceArgumentModel,
+            AdminEventProposedServiceModel, AdminEventVoteRecordModel, AdminServiceEventModel,
         },
         schema::{
             admin_event_circuit_proposal, admin_event_proposed_circuit, admin_event_proposed_node,
@@ -90,7 +90,7 @@ where
                 .into_iter()
                 .map(
                     |(event_model, circuit_proposal_model, proposed_circuit_model)| {
-                        let proposal_builder = CircuitProposalBuilder::new()
+                        let mut proposal_builder = CircuitProposalBuilder::new()
                             .with_proposal_type(&ProposalType::try_from(
                                 circuit_proposal_model.proposal_type.to_string(),
                             )?)
@@ -100,6 +100,9 @@ where
                                 circuit_proposal_model.requester.to_vec(),
                             ))
                             .with_requester_node_id(&circuit_proposal_model.requester_node_id);
+                        if let Some(expires_at) = circuit_proposal_model.expires_at {
+                            proposal_builder = proposal_builder.with_expires_at(expires_at as u64);
+                        }
                         let mut proposed_circuit_builder = ProposedCircuitBuilder::new()
                             .with_circuit_id(&proposed_circuit_model.circuit_id)
                             .with_authorization_type(&AuthorizationType::try_from(
@@ -112,6 +115,12 @@ where
                                 proposed_circuit_model.durability,
                             )?)
                             .with_routes(&RouteType::try_from(proposed_circuit_model.routes)?)
+                            .with_relay_nodes(&relay_nodes_from_column(
+                                proposed_circuit_model.relay_nodes,
+                            )?)
+                            .with_voting_policy(&voting_policy_from_column(
+                                proposed_circuit_model.voting_policy,
+                            )?)
                             .with_circuit_management_type(
                                 &proposed_circuit_model.circuit_management_type,
                             );
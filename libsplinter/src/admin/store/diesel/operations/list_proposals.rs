@@ -20,14 +20,15 @@ use std::convert::TryFrom;
 use diesel::{
     dsl::exists,
     prelude::*,
-    sql_types::{Binary, Integer, Nullable, SmallInt, Text},
+    sql_types::{BigInt, Binary, Integer, Nullable, SmallInt, Text},
 };
 
 use crate::admin::store::{
     diesel::{
         models::{
-            CircuitProposalModel, ProposedCircuitModel, ProposedNodeEndpointModel,
-            ProposedNodeModel, ProposedServiceArgumentModel, ProposedServiceModel, VoteRecordModel,
+            relay_nodes_from_column, voting_policy_from_column, CircuitProposalModel,
+            ProposedCircuitModel, ProposedNodeEndpointModel, ProposedNodeModel,
+            ProposedServiceArgumentModel, ProposedServiceModel, VoteRecordModel,
         },
         schema::{
             circuit_proposal, proposed_circuit, proposed_node, proposed_node_endpoint,
@@ -57,7 +58,8 @@ where
     String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
     i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
     i32: diesel::deserialize::FromSql<diesel::sql_types::Integer, C::Backend>,
-    CircuitProposalModel: diesel::Queryable<(Text, Text, Text, Binary, Text), C::Backend>,
+    CircuitProposalModel:
+        diesel::Queryable<(Text, Text, Text, Binary, Text, Nullable<BigInt>), C::Backend>,
     ProposedCircuitModel: diesel::Queryable<
         (
             Text,
@@ -161,7 +163,7 @@ where
                                 )
                             })?;
 
-                        let proposal_builder = CircuitProposalBuilder::new()
+                        let mut proposal_builder = CircuitProposalBuilder::new()
                             .with_proposal_type(&ProposalType::try_from(
                                 proposal.proposal_type.to_string(),
                             )?)
@@ -169,6 +171,9 @@ where
                             .with_circuit_hash(&proposal.circuit_hash)
                             .with_requester(&PublicKey::from_bytes(proposal.requester.to_vec()))
                             .with_requester_node_id(&proposal.requester_node_id);
+                        if let Some(expires_at) = proposal.expires_at {
+                            proposal_builder = proposal_builder.with_expires_at(expires_at as u64);
+                        }
                         let mut proposed_circuit_builder = ProposedCircuitBuilder::new()
                             .with_circuit_id(&proposed_circuit.circuit_id)
                             .with_authorization_type(&AuthorizationType::try_from(
@@ -181,6 +186,12 @@ where
                                 proposed_circuit.durability,
                             )?)
                             .with_routes(&RouteType::try_from(proposed_circuit.routes)?)
+                            .with_relay_nodes(&relay_nodes_from_column(
+                                proposed_circuit.relay_nodes,
+                            )?)
+                            .with_voting_policy(&voting_policy_from_column(
+                                proposed_circuit.voting_policy,
+                            )?)
                             .with_circuit_management_type(&proposed_circuit.circuit_management_type)
                             .with_circuit_version(proposed_circuit.circuit_version)
                             .with_circuit_status(&CircuitStatus::from(
@@ -60,6 +60,8 @@ impl<'a> AdminServiceStoreUpdateCircuitOperation
                     circuit::persistence.eq(circuit_model.persistence),
                     circuit::durability.eq(circuit_model.durability),
                     circuit::routes.eq(circuit_model.routes),
+                    circuit::relay_nodes.eq(circuit_model.relay_nodes),
+                    circuit::voting_policy.eq(circuit_model.voting_policy),
                     circuit::circuit_management_type.eq(circuit_model.circuit_management_type),
                     circuit::circuit_status.eq(circuit_model.circuit_status),
                 ))
@@ -119,6 +121,8 @@ impl<'a> AdminServiceStoreUpdateCircuitOperation
                     circuit::persistence.eq(circuit_model.persistence),
                     circuit::durability.eq(circuit_model.durability),
                     circuit::routes.eq(circuit_model.routes),
+                    circuit::relay_nodes.eq(circuit_model.relay_nodes),
+                    circuit::voting_policy.eq(circuit_model.voting_policy),
                     circuit::circuit_management_type.eq(circuit_model.circuit_management_type),
                     circuit::circuit_status.eq(circuit_model.circuit_status),
                 ))
@@ -67,6 +67,7 @@ impl<'a> AdminServiceStoreUpdateProposalOperation
                     circuit_proposal::circuit_hash.eq(proposal_model.circuit_hash),
                     circuit_proposal::requester.eq(proposal_model.requester),
                     circuit_proposal::requester_node_id.eq(proposal_model.requester_node_id),
+                    circuit_proposal::expires_at.eq(proposal_model.expires_at),
                 ))
                 .execute(self.conn)?;
             // Update existing `ProposedCircuit`
@@ -78,6 +79,8 @@ impl<'a> AdminServiceStoreUpdateProposalOperation
                     proposed_circuit::persistence.eq(proposed_circuit_model.persistence),
                     proposed_circuit::durability.eq(proposed_circuit_model.durability),
                     proposed_circuit::routes.eq(proposed_circuit_model.routes),
+                    proposed_circuit::relay_nodes.eq(proposed_circuit_model.relay_nodes),
+                    proposed_circuit::voting_policy.eq(proposed_circuit_model.voting_policy),
                     proposed_circuit::circuit_management_type
                         .eq(proposed_circuit_model.circuit_management_type),
                     proposed_circuit::application_metadata
@@ -168,6 +171,7 @@ impl<'a> AdminServiceStoreUpdateProposalOperation
                     circuit_proposal::circuit_hash.eq(proposal_model.circuit_hash),
                     circuit_proposal::requester.eq(proposal_model.requester),
                     circuit_proposal::requester_node_id.eq(proposal_model.requester_node_id),
+                    circuit_proposal::expires_at.eq(proposal_model.expires_at),
                 ))
                 .execute(self.conn)?;
             // Update existing `ProposedCircuit`
@@ -179,6 +183,8 @@ impl<'a> AdminServiceStoreUpdateProposalOperation
                     proposed_circuit::persistence.eq(proposed_circuit_model.persistence),
                     proposed_circuit::durability.eq(proposed_circuit_model.durability),
                     proposed_circuit::routes.eq(proposed_circuit_model.routes),
+                    proposed_circuit::relay_nodes.eq(proposed_circuit_model.relay_nodes),
+                    proposed_circuit::voting_policy.eq(proposed_circuit_model.voting_policy),
                     proposed_circuit::circuit_management_type
                         .eq(proposed_circuit_model.circuit_management_type),
                     proposed_circuit::application_metadata
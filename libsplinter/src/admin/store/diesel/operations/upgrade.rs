@@ -66,6 +66,8 @@ impl<'a> AdminServiceStoreUpgradeProposalToCircuitOperation
                 .with_persistence(proposed_circuit.persistence())
                 .with_durability(proposed_circuit.durability())
                 .with_routes(proposed_circuit.routes())
+                .with_relay_nodes(proposed_circuit.relay_nodes())
+                .with_voting_policy(proposed_circuit.voting_policy())
                 .with_circuit_management_type(proposed_circuit.circuit_management_type())
                 .with_circuit_version(proposed_circuit.circuit_version())
                 .with_circuit_status(proposed_circuit.circuit_status());
@@ -128,6 +130,8 @@ impl<'a> AdminServiceStoreUpgradeProposalToCircuitOperation
                 .with_persistence(proposed_circuit.persistence())
                 .with_durability(proposed_circuit.durability())
                 .with_routes(proposed_circuit.routes())
+                .with_relay_nodes(proposed_circuit.relay_nodes())
+                .with_voting_policy(proposed_circuit.voting_policy())
                 .with_circuit_management_type(proposed_circuit.circuit_management_type())
                 .with_circuit_version(proposed_circuit.circuit_version())
                 .with_circuit_status(proposed_circuit.circuit_status());
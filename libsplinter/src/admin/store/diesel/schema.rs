@@ -19,6 +19,7 @@ table! {
         circuit_hash -> Text,
         requester -> Binary,
         requester_node_id -> Text,
+        expires_at -> Nullable<Int8>,
     }
 }
 
@@ -35,6 +36,8 @@ table! {
         display_name -> Nullable<Text>,
         circuit_version -> Integer,
         circuit_status -> SmallInt,
+        relay_nodes -> Nullable<Text>,
+        voting_policy -> Nullable<Text>,
     }
 }
 
@@ -117,6 +120,8 @@ table! {
         display_name -> Nullable<Text>,
         circuit_version -> Integer,
         circuit_status -> SmallInt,
+        relay_nodes -> Nullable<Text>,
+        voting_policy -> Nullable<Text>,
     }
 }
 
@@ -170,6 +175,7 @@ table! {
         circuit_hash -> Text,
         requester -> Binary,
         requester_node_id -> Text,
+        expires_at -> Nullable<Int8>,
     }
 }
 
@@ -187,6 +193,8 @@ table! {
         display_name -> Nullable<Text>,
         circuit_version -> Integer,
         circuit_status -> SmallInt,
+        relay_nodes -> Nullable<Text>,
+        voting_policy -> Nullable<Text>,
     }
 }
 
@@ -40,6 +40,9 @@ pub enum EventType {
     ProposalRejected { requester: PublicKey },
     CircuitReady,
     CircuitDisbanded,
+    ProposalExpired,
+    CircuitSuspended,
+    CircuitResumed,
 }
 
 impl AdminServiceEvent {
@@ -176,6 +179,21 @@ impl TryFrom<(i64, &messages::AdminServiceEvent)> for AdminServiceEvent {
                 event_type: EventType::CircuitDisbanded,
                 proposal,
             }),
+            messages::AdminServiceEvent::ProposalExpired(_) => Ok(AdminServiceEvent {
+                event_id,
+                event_type: EventType::ProposalExpired,
+                proposal,
+            }),
+            messages::AdminServiceEvent::CircuitSuspended(_) => Ok(AdminServiceEvent {
+                event_id,
+                event_type: EventType::CircuitSuspended,
+                proposal,
+            }),
+            messages::AdminServiceEvent::CircuitResumed(_) => Ok(AdminServiceEvent {
+                event_id,
+                event_type: EventType::CircuitResumed,
+                proposal,
+            }),
         }
     }
 }
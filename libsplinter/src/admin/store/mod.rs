@@ -47,7 +47,7 @@ use crate::admin::service::messages;
 
 pub use self::circuit::{
     AuthorizationType, Circuit, CircuitBuilder, CircuitStatus, DurabilityType, PersistenceType,
-    RouteType,
+    RouteType, VotingPolicy, VotingPolicyType,
 };
 pub use self::circuit_node::{CircuitNode, CircuitNodeBuilder};
 pub use self::circuit_proposal::{
@@ -22,7 +22,7 @@ use crate::protos::admin;
 
 use super::{
     AuthorizationType, CircuitStatus, DurabilityType, PersistenceType, ProposedNode,
-    ProposedService, RouteType, UNSET_CIRCUIT_VERSION,
+    ProposedService, RouteType, VotingPolicy, UNSET_CIRCUIT_VERSION,
 };
 
 /// Native representation of a circuit that is being proposed in a proposal
@@ -35,6 +35,8 @@ pub struct ProposedCircuit {
     persistence: PersistenceType,
     durability: DurabilityType,
     routes: RouteType,
+    relay_nodes: Vec<String>,
+    voting_policy: VotingPolicy,
     circuit_management_type: String,
     application_metadata: Option<Vec<u8>>,
     comments: Option<String>,
@@ -79,6 +81,16 @@ impl ProposedCircuit {
         &self.routes
     }
 
+    /// Returns the node IDs of the members that relay messages for the circuit
+    pub fn relay_nodes(&self) -> &[String] {
+        &self.relay_nodes
+    }
+
+    /// Returns the voting policy used to decide proposals against the circuit
+    pub fn voting_policy(&self) -> &VotingPolicy {
+        &self.voting_policy
+    }
+
     /// Returns the management type of the circuit
     pub fn circuit_management_type(&self) -> &str {
         &self.circuit_management_type
@@ -133,6 +145,9 @@ impl ProposedCircuit {
 
         let durability = match proto.get_durability() {
             admin::Circuit_DurabilityType::NO_DURABILITY => DurabilityType::NoDurability,
+            admin::Circuit_DurabilityType::GUARANTEED_DELIVERY => {
+                DurabilityType::GuaranteedDelivery
+            }
             admin::Circuit_DurabilityType::UNSET_DURABILITY_TYPE => {
                 return Err(InvalidStateError::with_message(
                     "unable to build, missing field: `durability type`".to_string(),
@@ -142,6 +157,7 @@ impl ProposedCircuit {
 
         let routes = match proto.get_routes() {
             admin::Circuit_RouteType::ANY_ROUTE => RouteType::Any,
+            admin::Circuit_RouteType::RELAY_ROUTE => RouteType::Relay,
             admin::Circuit_RouteType::UNSET_ROUTE_TYPE => {
                 return Err(InvalidStateError::with_message(
                     "unable to build, missing field: `route type`".to_string(),
@@ -153,6 +169,7 @@ impl ProposedCircuit {
             admin::Circuit_CircuitStatus::ACTIVE => CircuitStatus::Active,
             admin::Circuit_CircuitStatus::DISBANDED => CircuitStatus::Disbanded,
             admin::Circuit_CircuitStatus::ABANDONED => CircuitStatus::Abandoned,
+            admin::Circuit_CircuitStatus::SUSPENDED => CircuitStatus::Suspended,
             admin::Circuit_CircuitStatus::UNSET_CIRCUIT_STATUS => {
                 debug!("Defaulting `UNSET_CIRCUIT_STATUS` of proposed circuit to `Active`");
                 CircuitStatus::default()
@@ -177,6 +194,8 @@ impl ProposedCircuit {
             Some(proto.take_application_metadata())
         };
 
+        let voting_policy = VotingPolicy::try_from(proto.get_voting_policy())?;
+
         let circuit_version = if proto.get_circuit_version() == 0 {
             UNSET_CIRCUIT_VERSION
         } else {
@@ -199,6 +218,8 @@ impl ProposedCircuit {
             persistence,
             durability,
             routes,
+            relay_nodes: proto.take_relay_nodes().into_vec(),
+            voting_policy,
             circuit_management_type: proto.take_circuit_management_type(),
             application_metadata,
             comments,
@@ -264,11 +285,21 @@ impl ProposedCircuit {
             DurabilityType::NoDurability => {
                 circuit.set_durability(admin::Circuit_DurabilityType::NO_DURABILITY);
             }
+            DurabilityType::GuaranteedDelivery => {
+                circuit.set_durability(admin::Circuit_DurabilityType::GUARANTEED_DELIVERY);
+            }
         };
 
         match self.routes {
             RouteType::Any => circuit.set_routes(admin::Circuit_RouteType::ANY_ROUTE),
+            RouteType::Relay => circuit.set_routes(admin::Circuit_RouteType::RELAY_ROUTE),
         };
+        circuit.set_relay_nodes(protobuf::RepeatedField::from_vec(self.relay_nodes));
+        // Only set the voting policy if one was declared so the serialized circuit is unchanged
+        // for circuits that use the default policy
+        if !self.voting_policy.is_default() {
+            circuit.set_voting_policy(admin::Circuit_VotingPolicy::from(&self.voting_policy));
+        }
 
         // If the circuit version is equal to the `CIRCUIT_PROTOCOL_VERSION`, the `circuit_status`
         // value should be set.
@@ -283,6 +314,9 @@ impl ProposedCircuit {
                 CircuitStatus::Abandoned => {
                     circuit.set_circuit_status(admin::Circuit_CircuitStatus::ABANDONED);
                 }
+                CircuitStatus::Suspended => {
+                    circuit.set_circuit_status(admin::Circuit_CircuitStatus::SUSPENDED);
+                }
             }
         }
 
@@ -300,6 +334,8 @@ pub struct ProposedCircuitBuilder {
     persistence: Option<PersistenceType>,
     durability: Option<DurabilityType>,
     routes: Option<RouteType>,
+    relay_nodes: Option<Vec<String>>,
+    voting_policy: Option<VotingPolicy>,
     circuit_management_type: Option<String>,
     application_metadata: Option<Vec<u8>>,
     comments: Option<String>,
@@ -349,6 +385,16 @@ impl ProposedCircuitBuilder {
         self.routes.clone()
     }
 
+    /// Returns the relay node IDs in the builder
+    pub fn relay_nodes(&self) -> Option<Vec<String>> {
+        self.relay_nodes.clone()
+    }
+
+    /// Returns the voting policy in the builder
+    pub fn voting_policy(&self) -> Option<VotingPolicy> {
+        self.voting_policy.clone()
+    }
+
     /// Returns the circuit management type in the builder
     pub fn circuit_management_type(&self) -> Option<String> {
         self.circuit_management_type.clone()
@@ -449,6 +495,26 @@ impl ProposedCircuitBuilder {
         self
     }
 
+    /// Sets the relay nodes
+    ///
+    /// # Arguments
+    ///
+    ///  * `relay_nodes` - The node IDs of the members that relay messages for the circuit
+    pub fn with_relay_nodes(mut self, relay_nodes: &[String]) -> ProposedCircuitBuilder {
+        self.relay_nodes = Some(relay_nodes.into());
+        self
+    }
+
+    /// Sets the voting policy
+    ///
+    /// # Arguments
+    ///
+    ///  * `voting_policy` - The policy used to decide proposals against the circuit
+    pub fn with_voting_policy(mut self, voting_policy: &VotingPolicy) -> ProposedCircuitBuilder {
+        self.voting_policy = Some(voting_policy.clone());
+        self
+    }
+
     /// Sets the circuit management type
     ///
     /// # Arguments
@@ -554,6 +620,10 @@ impl ProposedCircuitBuilder {
 
         let routes = self.routes.unwrap_or_default();
 
+        let relay_nodes = self.relay_nodes.unwrap_or_default();
+
+        let voting_policy = self.voting_policy.unwrap_or_default();
+
         let circuit_management_type = self.circuit_management_type.ok_or_else(|| {
             InvalidStateError::with_message(
                 "unable to build, missing field: `circuit_management_type`".to_string(),
@@ -578,6 +648,8 @@ impl ProposedCircuitBuilder {
             persistence,
             durability,
             routes,
+            relay_nodes,
+            voting_policy,
             circuit_management_type,
             application_metadata,
             comments,
@@ -612,6 +684,8 @@ impl TryFrom<&messages::CreateCircuit> for ProposedCircuit {
             .with_persistence(&PersistenceType::from(&create_circuit.persistence))
             .with_durability(&DurabilityType::from(&create_circuit.durability))
             .with_routes(&RouteType::from(&create_circuit.routes))
+            .with_relay_nodes(&create_circuit.relay_nodes)
+            .with_voting_policy(&VotingPolicy::from(&create_circuit.voting_policy))
             .with_circuit_management_type(&create_circuit.circuit_management_type)
             .with_circuit_version(create_circuit.circuit_version)
             .with_circuit_status(&CircuitStatus::from(&create_circuit.circuit_status));
@@ -33,7 +33,7 @@ use super::{
     CircuitStatus, DurabilityType, PersistenceType, ProposalType, ProposedCircuit,
     ProposedCircuitBuilder, ProposedNode, ProposedNodeBuilder, ProposedService,
     ProposedServiceBuilder, RouteType, Service, ServiceBuilder, ServiceId, Vote, VoteRecord,
-    VoteRecordBuilder,
+    VoteRecordBuilder, VotingPolicy,
 };
 use crate::admin::messages;
 
@@ -1133,6 +1133,10 @@ struct YamlCircuit {
     persistence: YamlPersistenceType,
     durability: YamlDurabilityType,
     routes: YamlRouteType,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    relay_nodes: Vec<String>,
+    #[serde(default, skip_serializing_if = "VotingPolicy::is_default")]
+    voting_policy: VotingPolicy,
     circuit_management_type: String,
     display_name: Option<String>,
     #[serde(default = "default_circuit_value")]
@@ -1171,6 +1175,8 @@ impl TryFrom<YamlCircuit> for Circuit {
             .with_persistence(&PersistenceType::from(circuit.persistence))
             .with_durability(&DurabilityType::from(circuit.durability))
             .with_routes(&RouteType::from(circuit.routes))
+            .with_relay_nodes(&circuit.relay_nodes)
+            .with_voting_policy(&circuit.voting_policy)
             .with_circuit_management_type(&circuit.circuit_management_type)
             .with_circuit_version(circuit.circuit_version)
             .with_circuit_status(&CircuitStatus::from(circuit.circuit_status));
@@ -1201,6 +1207,8 @@ impl From<Circuit> for YamlCircuit {
             persistence: circuit.persistence().clone().into(),
             durability: circuit.durability().clone().into(),
             routes: circuit.routes().clone().into(),
+            relay_nodes: circuit.relay_nodes().to_vec(),
+            voting_policy: circuit.voting_policy().clone(),
             circuit_management_type: circuit.circuit_management_type().into(),
             display_name: circuit.display_name().clone(),
             circuit_version: circuit.circuit_version(),
@@ -1323,6 +1331,8 @@ pub struct YamlCircuitProposal {
     votes: Vec<YamlVoteRecord>,
     requester: String,
     requester_node_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    expires_at: Option<u64>,
 }
 
 impl From<ProposalState> for YamlProposalState {
@@ -1358,7 +1368,7 @@ impl TryFrom<YamlCircuitProposal> for CircuitProposal {
     type Error = InvalidStateError;
 
     fn try_from(proposal: YamlCircuitProposal) -> Result<Self, Self::Error> {
-        CircuitProposalBuilder::new()
+        let builder = CircuitProposalBuilder::new()
             .with_circuit_id(&proposal.circuit_id)
             .with_proposal_type(&ProposalType::from(proposal.proposal_type))
             .with_circuit_hash(&proposal.circuit_hash)
@@ -1377,8 +1387,13 @@ impl TryFrom<YamlCircuitProposal> for CircuitProposal {
                     )
                 })?,
             ))
-            .with_requester_node_id(&proposal.requester_node_id)
-            .build()
+            .with_requester_node_id(&proposal.requester_node_id);
+
+        match proposal.expires_at {
+            Some(expires_at) => builder.with_expires_at(expires_at),
+            None => builder,
+        }
+        .build()
     }
 }
 
@@ -1396,6 +1411,7 @@ impl From<CircuitProposal> for YamlCircuitProposal {
                 .collect(),
             requester: to_hex(proposal.requester().as_slice()),
             requester_node_id: proposal.requester_node_id().into(),
+            expires_at: proposal.expires_at(),
         }
     }
 }
@@ -1407,6 +1423,9 @@ pub enum YamlProposalType {
     AddNode,
     RemoveNode,
     Disband,
+    Suspend,
+    Resume,
+    UpdateNodeKeys,
 }
 
 impl From<YamlProposalType> for ProposalType {
@@ -1417,6 +1436,9 @@ impl From<YamlProposalType> for ProposalType {
             YamlProposalType::AddNode => ProposalType::AddNode,
             YamlProposalType::RemoveNode => ProposalType::RemoveNode,
             YamlProposalType::Disband => ProposalType::Disband,
+            YamlProposalType::Suspend => ProposalType::Suspend,
+            YamlProposalType::Resume => ProposalType::Resume,
+            YamlProposalType::UpdateNodeKeys => ProposalType::UpdateNodeKeys,
         }
     }
 }
@@ -1429,6 +1451,9 @@ impl From<ProposalType> for YamlProposalType {
             ProposalType::AddNode => YamlProposalType::AddNode,
             ProposalType::RemoveNode => YamlProposalType::RemoveNode,
             ProposalType::Disband => YamlProposalType::Disband,
+            ProposalType::Suspend => YamlProposalType::Suspend,
+            ProposalType::Resume => YamlProposalType::Resume,
+            ProposalType::UpdateNodeKeys => YamlProposalType::UpdateNodeKeys,
         }
     }
 }
@@ -1483,6 +1508,10 @@ struct YamlProposedCircuit {
     persistence: YamlPersistenceType,
     durability: YamlDurabilityType,
     routes: YamlRouteType,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    relay_nodes: Vec<String>,
+    #[serde(default, skip_serializing_if = "VotingPolicy::is_default")]
+    voting_policy: VotingPolicy,
     circuit_management_type: String,
     application_metadata: Option<String>,
     comments: Option<String>,
@@ -1517,6 +1546,8 @@ impl TryFrom<YamlProposedCircuit> for ProposedCircuit {
             .with_persistence(&PersistenceType::from(circuit.persistence))
             .with_durability(&DurabilityType::from(circuit.durability))
             .with_routes(&RouteType::from(circuit.routes))
+            .with_relay_nodes(&circuit.relay_nodes)
+            .with_voting_policy(&circuit.voting_policy)
             .with_circuit_management_type(&circuit.circuit_management_type)
             .with_circuit_version(circuit.circuit_version)
             .with_circuit_status(&CircuitStatus::from(circuit.circuit_status));
@@ -1568,6 +1599,8 @@ impl From<ProposedCircuit> for YamlProposedCircuit {
             persistence: circuit.persistence().clone().into(),
             durability: circuit.durability().clone().into(),
             routes: circuit.routes().clone().into(),
+            relay_nodes: circuit.relay_nodes().to_vec(),
+            voting_policy: circuit.voting_policy().clone(),
             circuit_management_type: circuit.circuit_management_type().into(),
             application_metadata,
             comments: circuit.comments().clone(),
@@ -1700,12 +1733,14 @@ impl From<YamlPersistenceType> for PersistenceType {
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub enum YamlDurabilityType {
     NoDurability,
+    GuaranteedDelivery,
 }
 
 impl From<DurabilityType> for YamlDurabilityType {
     fn from(durability_type: DurabilityType) -> Self {
         match durability_type {
             DurabilityType::NoDurability => YamlDurabilityType::NoDurability,
+            DurabilityType::GuaranteedDelivery => YamlDurabilityType::GuaranteedDelivery,
         }
     }
 }
@@ -1714,6 +1749,7 @@ impl From<YamlDurabilityType> for DurabilityType {
     fn from(yaml_durability_type: YamlDurabilityType) -> Self {
         match yaml_durability_type {
             YamlDurabilityType::NoDurability => DurabilityType::NoDurability,
+            YamlDurabilityType::GuaranteedDelivery => DurabilityType::GuaranteedDelivery,
         }
     }
 }
@@ -1722,12 +1758,14 @@ impl From<YamlDurabilityType> for DurabilityType {
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub enum YamlRouteType {
     Any,
+    Relay,
 }
 
 impl From<RouteType> for YamlRouteType {
     fn from(route_type: RouteType) -> Self {
         match route_type {
             RouteType::Any => YamlRouteType::Any,
+            RouteType::Relay => YamlRouteType::Relay,
         }
     }
 }
@@ -1736,6 +1774,7 @@ impl From<YamlRouteType> for RouteType {
     fn from(yaml_route_type: YamlRouteType) -> Self {
         match yaml_route_type {
             YamlRouteType::Any => RouteType::Any,
+            YamlRouteType::Relay => RouteType::Relay,
         }
     }
 }
@@ -1827,6 +1866,7 @@ pub enum YamlCircuitStatus {
     Active,
     Disbanded,
     Abandoned,
+    Suspended,
 }
 
 impl From<CircuitStatus> for YamlCircuitStatus {
@@ -1835,6 +1875,7 @@ impl From<CircuitStatus> for YamlCircuitStatus {
             CircuitStatus::Active => YamlCircuitStatus::Active,
             CircuitStatus::Disbanded => YamlCircuitStatus::Disbanded,
             CircuitStatus::Abandoned => YamlCircuitStatus::Abandoned,
+            CircuitStatus::Suspended => YamlCircuitStatus::Suspended,
         }
     }
 }
@@ -1845,6 +1886,7 @@ impl From<YamlCircuitStatus> for CircuitStatus {
             YamlCircuitStatus::Active => CircuitStatus::Active,
             YamlCircuitStatus::Disbanded => CircuitStatus::Disbanded,
             YamlCircuitStatus::Abandoned => CircuitStatus::Abandoned,
+            YamlCircuitStatus::Suspended => CircuitStatus::Suspended,
         }
     }
 }
@@ -15,6 +15,10 @@
 //! Defines a basic API to register and authenticate a User using a username and a password.
 //! Not recommended for use in production.
 
-#[cfg(feature = "rest-api-actix-web-1")]
+#[cfg(feature = "biome-credentials-reset")]
+pub mod reset;
+#[cfg(feature = "rest-api")]
 pub mod rest_api;
 pub mod store;
+#[cfg(feature = "biome-credentials-totp")]
+pub mod totp;
@@ -0,0 +1,55 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Password reset tokens for Biome credentials.
+//!
+//! A reset token is a random value that is given to the user once. Only its hash is stored, so a
+//! leaked store can not be used to reset a password.
+
+use openssl::rand::rand_bytes;
+use openssl::sha::sha256;
+
+use crate::error::InternalError;
+use crate::hex::to_hex;
+
+const RESET_TOKEN_LEN: usize = 32;
+
+/// Generates a new random, hex-encoded password reset token.
+pub fn generate_token() -> Result<String, InternalError> {
+    let mut token_bytes = [0u8; RESET_TOKEN_LEN];
+    rand_bytes(&mut token_bytes).map_err(|err| InternalError::from_source(Box::new(err)))?;
+    Ok(to_hex(&token_bytes))
+}
+
+/// Returns the hex-encoded SHA-256 hash of a password reset token, which is what is stored.
+pub fn hash_token(token: &str) -> String {
+    to_hex(&sha256(token.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that generated tokens are unique and that hashing a token is deterministic.
+    #[test]
+    fn test_generate_and_hash_token() {
+        let token = generate_token().expect("Failed to generate token");
+        let other_token = generate_token().expect("Failed to generate token");
+
+        assert_eq!(RESET_TOKEN_LEN * 2, token.len());
+        assert_ne!(token, other_token);
+        assert_eq!(hash_token(&token), hash_token(&token));
+        assert_ne!(hash_token(&token), hash_token(&other_token));
+        assert_ne!(token, hash_token(&token));
+    }
+}
@@ -25,8 +25,12 @@ use crate::rest_api::{
     ErrorResponse, SPLINTER_PROTOCOL_VERSION,
 };
 
-use crate::biome::credentials::rest_api::actix_web_1::BiomeCredentialsRestConfig;
+#[cfg(feature = "biome-credentials-totp")]
+use crate::biome::credentials::rest_api::actix_web_1::totp::verify_totp_code;
 use crate::biome::credentials::rest_api::resources::credentials::UsernamePassword;
+use crate::biome::credentials::rest_api::BiomeCredentialsRestConfig;
+#[cfg(feature = "biome-credentials-totp")]
+use crate::biome::credentials::store::TotpSecret;
 use crate::biome::credentials::store::{CredentialsStore, CredentialsStoreError};
 use crate::rest_api::sessions::{AccessTokenIssuer, ClaimsBuilder, TokenIssuer};
 
@@ -38,6 +42,7 @@ const BIOME_LOGIN_PROTOCOL_MIN: u32 = 1;
 ///   {
 ///       "username": <existing username of the user>
 ///       "hashed_password": <hash of the user's existing password>
+///       "totp_code": <current TOTP code, required if the user has enabled TOTP>
 ///   }
 pub fn make_login_route(
     credentials_store: Arc<dyn CredentialsStore>,
@@ -100,6 +105,17 @@ pub fn make_login_route(
                     match credentials.verify_password(&username_password.hashed_password) {
                         Ok(is_valid) => {
                             if is_valid {
+                                #[cfg(feature = "biome-credentials-totp")]
+                                {
+                                    if let Err(response) = check_totp_code(
+                                        &*credentials_store,
+                                        &credentials.user_id,
+                                        username_password.totp_code.as_deref(),
+                                    ) {
+                                        return response.into_future();
+                                    }
+                                }
+
                                 let claim_builder = ClaimsBuilder::default();
                                 let claim = match claim_builder
                                     .with_user_id(&credentials.user_id)
@@ -235,6 +251,17 @@ pub fn make_login_route(
                 match credentials.verify_password(&username_password.hashed_password) {
                     Ok(is_valid) => {
                         if is_valid {
+                            #[cfg(feature = "biome-credentials-totp")]
+                            {
+                                if let Err(response) = check_totp_code(
+                                    &*credentials_store,
+                                    &credentials.user_id,
+                                    username_password.totp_code.as_deref(),
+                                ) {
+                                    return response.into_future();
+                                }
+                            }
+
                             let claim_builder = ClaimsBuilder::default();
                             let claim = match claim_builder
                                 .with_user_id(&credentials.user_id)
@@ -322,3 +349,38 @@ pub fn make_login_route(
         })
     }
 }
+
+/// Checks the TOTP code provided at login if the user has enabled TOTP, recording the time step
+/// the code was accepted for so that it can not be used again.
+#[cfg(feature = "biome-credentials-totp")]
+fn check_totp_code(
+    credentials_store: &dyn CredentialsStore,
+    user_id: &str,
+    code: Option<&str>,
+) -> Result<(), HttpResponse> {
+    let secret = match credentials_store.fetch_totp_secret(user_id) {
+        Ok(Some(secret)) if secret.enabled => secret,
+        Ok(_) => return Ok(()),
+        Err(err) => {
+            error!("Failed to fetch TOTP secret: {}", err);
+            return Err(HttpResponse::InternalServerError().json(ErrorResponse::internal_error()));
+        }
+    };
+
+    let code = code.ok_or_else(|| {
+        HttpResponse::BadRequest().json(ErrorResponse::bad_request("TOTP code required"))
+    })?;
+    let step = verify_totp_code(&secret, code)?.ok_or_else(|| {
+        HttpResponse::BadRequest().json(ErrorResponse::bad_request("Invalid TOTP code"))
+    })?;
+
+    credentials_store
+        .set_totp_secret(TotpSecret {
+            last_used_step: Some(step),
+            ..secret
+        })
+        .map_err(|err| {
+            error!("Failed to update TOTP secret: {}", err);
+            HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+        })
+}
@@ -18,8 +18,8 @@ use actix_web::HttpResponse;
 use futures::IntoFuture;
 
 use crate::biome::credentials::rest_api::{
-    actix_web_1::{authorize::authorize_user, config::BiomeCredentialsRestConfig},
-    resources::authorize::AuthorizationResult,
+    actix_web_1::authorize::authorize_user, resources::authorize::AuthorizationResult,
+    BiomeCredentialsRestConfig,
 };
 use crate::biome::refresh_tokens::store::{RefreshTokenError, RefreshTokenStore};
 #[cfg(feature = "authorization")]
@@ -13,21 +13,34 @@
 // limitations under the License.
 
 mod authorize;
-mod config;
 mod login;
 mod logout;
+#[cfg(feature = "biome-credentials-reset")]
+mod password_reset;
 mod register;
 mod token;
+#[cfg(feature = "biome-credentials-totp")]
+mod totp;
 mod user;
 mod verify;
 
 use std::sync::Arc;
+#[cfg(any(
+    feature = "biome-credentials-reset",
+    feature = "biome-credentials-totp"
+))]
+use std::time::{SystemTime, UNIX_EPOCH};
 
 #[cfg(feature = "biome-key-management")]
 use crate::biome::key_management::store::KeyStore;
 use crate::biome::{
     credentials::store::CredentialsStore, refresh_tokens::store::RefreshTokenStore,
 };
+#[cfg(any(
+    feature = "biome-credentials-reset",
+    feature = "biome-credentials-totp"
+))]
+use crate::error::InternalError;
 use crate::error::InvalidStateError;
 use crate::rest_api::{
     auth::identity::biome::BiomeUserIdentityProvider,
@@ -36,7 +49,7 @@ use crate::rest_api::{
     Resource, RestResourceProvider,
 };
 
-pub use config::{BiomeCredentialsRestConfig, BiomeCredentialsRestConfigBuilder};
+use super::{BiomeCredentialsRestConfig, BiomeCredentialsRestConfigBuilder};
 
 /// Provides the following REST API endpoints for Biome credentials:
 ///
@@ -49,6 +62,16 @@ pub use config::{BiomeCredentialsRestConfig, BiomeCredentialsRestConfigBuilder};
 /// * `PUT /biome/users/{id}` - Update user with specified ID
 /// * `GET /biome/users/{id}` - Retrieve user with specified ID
 /// * `DELETE /biome/users/{id}` - Remove user with specified ID
+/// * `POST /biome/users/{id}/password_reset` - Issue a password reset token for the user with
+///   specified ID (requires the `biome-credentials-reset` feature)
+/// * `POST /biome/password_reset` - Reset a user's password using a password reset token
+///   (requires the `biome-credentials-reset` feature)
+/// * `POST /biome/totp` - Start TOTP enrollment for the authorized user (requires the
+///   `biome-credentials-totp` feature)
+/// * `PUT /biome/totp` - Confirm TOTP enrollment for the authorized user (requires the
+///   `biome-credentials-totp` feature)
+/// * `DELETE /biome/totp` - Disable TOTP for the authorized user (requires the
+///   `biome-credentials-totp` feature)
 pub struct BiomeCredentialsRestResourceProvider {
     #[cfg(feature = "biome-key-management")]
     key_store: Arc<dyn KeyStore>,
@@ -112,10 +135,39 @@ impl RestResourceProvider for BiomeCredentialsRestResourceProvider {
                 self.credentials_store.clone(),
                 self.key_store.clone(),
             ),
+            #[cfg(feature = "biome-credentials-reset")]
+            password_reset::make_issue_password_reset_token_route(
+                self.credentials_store.clone(),
+                self.credentials_config.clone(),
+            ),
+            #[cfg(feature = "biome-credentials-reset")]
+            password_reset::make_password_reset_route(
+                self.credentials_store.clone(),
+                self.refresh_token_store.clone(),
+                self.credentials_config.clone(),
+            ),
+            #[cfg(feature = "biome-credentials-totp")]
+            totp::make_totp_route(
+                self.credentials_store.clone(),
+                self.token_secret_manager.clone(),
+                self.credentials_config.clone(),
+            ),
         ]
     }
 }
 
+/// Returns the current time in seconds since the Unix epoch
+#[cfg(any(
+    feature = "biome-credentials-reset",
+    feature = "biome-credentials-totp"
+))]
+fn unix_time_secs() -> Result<u64, InternalError> {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .map_err(|err| InternalError::from_source(Box::new(err)))
+}
+
 /// Builder for BiomeCredentialsRestResourceProvider
 #[derive(Default)]
 pub struct BiomeCredentialsRestResourceProviderBuilder {
@@ -354,6 +406,19 @@ mod tests {
         token: String,
     }
 
+    #[cfg(feature = "biome-credentials-reset")]
+    #[derive(Deserialize)]
+    struct PasswordResetTokenResponse {
+        pub reset_token: String,
+    }
+
+    #[cfg(feature = "biome-credentials-reset")]
+    #[derive(Serialize)]
+    struct PostPasswordReset {
+        pub reset_token: String,
+        pub new_password: String,
+    }
+
     fn start_biome_rest_api() -> (RestApiShutdownHandle, thread::JoinHandle<()>) {
         let refresh_token_store = MemoryRefreshTokenStore::new();
         let cred_store = MemoryCredentialsStore::new();
@@ -754,6 +819,95 @@ mod tests {
         });
     }
 
+    /// Test the password reset flow via POST /biome/users/{id}/password_reset and
+    /// POST /biome/password_reset
+    ///
+    /// Procedure
+    ///
+    /// 1) Create a new user and log in as that user
+    /// 2) Issue a password reset token for the user
+    /// 3) Reset the user's password with the token and verify the correct status code
+    /// 4) Verify that the token can not be used a second time
+    /// 5) Verify that login fails with the old password and succeeds with the new one
+    #[test]
+    #[cfg(feature = "biome-credentials-reset")]
+    fn test_password_reset() {
+        run_test(|url, client| {
+            let login = create_and_authorize_user(
+                url,
+                &client,
+                "test_password_reset@gmail.com",
+                "Admin2193!",
+            );
+
+            let token_response = client
+                .post(&format!(
+                    "{}/biome/users/{}/password_reset",
+                    url, login.user_id
+                ))
+                .header("Authorization", format!("Bearer {}", login.token))
+                .send()
+                .unwrap();
+            assert_eq!(token_response.status().as_u16(), 200);
+            let reset_token = token_response
+                .json::<PasswordResetTokenResponse>()
+                .unwrap()
+                .reset_token;
+
+            let reset = PostPasswordReset {
+                reset_token,
+                new_password: "NewAdmin2193!".to_string(),
+            };
+            assert_eq!(
+                client
+                    .post(&format!("{}/biome/password_reset", url))
+                    .json(&reset)
+                    .send()
+                    .unwrap()
+                    .status()
+                    .as_u16(),
+                200
+            );
+            assert_eq!(
+                client
+                    .post(&format!("{}/biome/password_reset", url))
+                    .json(&reset)
+                    .send()
+                    .unwrap()
+                    .status()
+                    .as_u16(),
+                400
+            );
+
+            assert_eq!(
+                client
+                    .post(&format!("{}/biome/login", url))
+                    .json(&UsernamePassword {
+                        username: "test_password_reset@gmail.com".to_string(),
+                        hashed_password: "Admin2193!".to_string(),
+                    })
+                    .send()
+                    .unwrap()
+                    .status()
+                    .as_u16(),
+                400
+            );
+            assert_eq!(
+                client
+                    .post(&format!("{}/biome/login", url))
+                    .json(&UsernamePassword {
+                        username: "test_password_reset@gmail.com".to_string(),
+                        hashed_password: "NewAdmin2193!".to_string(),
+                    })
+                    .send()
+                    .unwrap()
+                    .status()
+                    .as_u16(),
+                200
+            );
+        });
+    }
+
     /// Test Happy path for POST /biome/verify
     ///
     /// Verify that POST /biome/verify returns a status code
@@ -0,0 +1,301 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use actix_web::HttpResponse;
+use futures::{Future, IntoFuture};
+
+use crate::biome::credentials::reset::{generate_token, hash_token};
+use crate::biome::credentials::rest_api::resources::credentials::PasswordReset;
+#[cfg(feature = "authorization")]
+use crate::biome::credentials::rest_api::BIOME_USER_WRITE_PERMISSION;
+use crate::biome::credentials::rest_api::{
+    actix_web_1::unix_time_secs, BiomeCredentialsRestConfig,
+};
+use crate::biome::credentials::store::{
+    CredentialsStore, CredentialsStoreError, PasswordResetToken,
+};
+use crate::biome::refresh_tokens::store::{RefreshTokenError, RefreshTokenStore};
+#[cfg(feature = "authorization")]
+use crate::rest_api::auth::authorization::Permission;
+use crate::rest_api::{
+    actix_web_1::{into_bytes, HandlerFunction, Method, ProtocolVersionRangeGuard, Resource},
+    ErrorResponse, SPLINTER_PROTOCOL_VERSION,
+};
+
+const BIOME_PASSWORD_RESET_PROTOCOL_MIN: u32 = 1;
+
+/// Defines a REST endpoint for an administrator to issue a password reset token for a user
+///
+/// The token is returned once, in the response, and may be used a single time before it expires.
+/// Issuing a new token for a user replaces any token previously issued for them.
+pub fn make_issue_password_reset_token_route(
+    credentials_store: Arc<dyn CredentialsStore>,
+    rest_config: Arc<BiomeCredentialsRestConfig>,
+) -> Resource {
+    let resource = Resource::build("/biome/users/{id}/password_reset").add_request_guard(
+        ProtocolVersionRangeGuard::new(
+            BIOME_PASSWORD_RESET_PROTOCOL_MIN,
+            SPLINTER_PROTOCOL_VERSION,
+        ),
+    );
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(
+            Method::Post,
+            BIOME_USER_WRITE_PERMISSION,
+            add_issue_password_reset_token_method(credentials_store, rest_config),
+        )
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(
+            Method::Post,
+            add_issue_password_reset_token_method(credentials_store, rest_config),
+        )
+    }
+}
+
+fn add_issue_password_reset_token_method(
+    credentials_store: Arc<dyn CredentialsStore>,
+    rest_config: Arc<BiomeCredentialsRestConfig>,
+) -> HandlerFunction {
+    Box::new(move |request, _| {
+        let user_id = match request.match_info().get("id") {
+            Some(t) => t.to_string(),
+            None => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(
+                            "Failed to process request: no user id",
+                        ))
+                        .into_future(),
+                )
+            }
+        };
+
+        if let Err(err) = credentials_store.fetch_credential_by_user_id(&user_id) {
+            return match err {
+                CredentialsStoreError::NotFoundError(_) => Box::new(
+                    HttpResponse::NotFound()
+                        .json(ErrorResponse::not_found(&format!(
+                            "User not found: {}",
+                            user_id
+                        )))
+                        .into_future(),
+                ),
+                _ => {
+                    error!("Failed to fetch credentials: {}", err);
+                    Box::new(
+                        HttpResponse::InternalServerError()
+                            .json(ErrorResponse::internal_error())
+                            .into_future(),
+                    )
+                }
+            };
+        }
+
+        let token = match generate_token() {
+            Ok(token) => token,
+            Err(err) => {
+                error!("Failed to generate password reset token: {}", err);
+                return Box::new(
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future(),
+                );
+            }
+        };
+
+        let expires_at = match unix_time_secs() {
+            Ok(now) => (now + rest_config.password_reset_token_duration().as_secs()) as i64,
+            Err(err) => {
+                error!("{}", err);
+                return Box::new(
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future(),
+                );
+            }
+        };
+
+        Box::new(
+            match credentials_store.add_password_reset_token(PasswordResetToken {
+                user_id: user_id.clone(),
+                token_hash: hash_token(&token),
+                expires_at,
+            }) {
+                Ok(()) => HttpResponse::Ok()
+                    .json(json!({
+                        "message": "Password reset token issued",
+                        "user_id": user_id,
+                        "reset_token": token,
+                        "expires_at": expires_at,
+                    }))
+                    .into_future(),
+                Err(err) => {
+                    error!("Failed to add password reset token: {}", err);
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future()
+                }
+            },
+        )
+    })
+}
+
+/// Defines a REST endpoint to reset a user's password using a password reset token
+///
+/// The payload should be in the JSON format:
+///   {
+///       "reset_token": <password reset token issued for the user>
+///       "new_password": <hash of the user's new password>
+///   }
+///
+/// A successful reset also removes the user's refresh tokens, so existing sessions can not be
+/// renewed.
+pub fn make_password_reset_route(
+    credentials_store: Arc<dyn CredentialsStore>,
+    refresh_token_store: Arc<dyn RefreshTokenStore>,
+    rest_config: Arc<BiomeCredentialsRestConfig>,
+) -> Resource {
+    let resource =
+        Resource::build("/biome/password_reset").add_request_guard(ProtocolVersionRangeGuard::new(
+            BIOME_PASSWORD_RESET_PROTOCOL_MIN,
+            SPLINTER_PROTOCOL_VERSION,
+        ));
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(
+            Method::Post,
+            Permission::AllowUnauthenticated,
+            add_password_reset_method(credentials_store, refresh_token_store, rest_config),
+        )
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(
+            Method::Post,
+            add_password_reset_method(credentials_store, refresh_token_store, rest_config),
+        )
+    }
+}
+
+fn add_password_reset_method(
+    credentials_store: Arc<dyn CredentialsStore>,
+    refresh_token_store: Arc<dyn RefreshTokenStore>,
+    rest_config: Arc<BiomeCredentialsRestConfig>,
+) -> HandlerFunction {
+    let encryption_cost = rest_config.password_encryption_cost();
+    Box::new(move |_, payload| {
+        let credentials_store = credentials_store.clone();
+        let refresh_token_store = refresh_token_store.clone();
+        Box::new(into_bytes(payload).and_then(move |bytes| {
+            let password_reset = match serde_json::from_slice::<PasswordReset>(&bytes) {
+                Ok(val) => val,
+                Err(err) => {
+                    debug!("Error parsing payload {}", err);
+                    return HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&format!(
+                            "Failed to parse payload: {}",
+                            err
+                        )))
+                        .into_future();
+                }
+            };
+
+            // The token is taken from the store before it is checked, so that it can not be used
+            // again whether or not the reset succeeds
+            let token = match credentials_store
+                .take_password_reset_token(&hash_token(&password_reset.reset_token))
+            {
+                Ok(token) => token,
+                Err(CredentialsStoreError::NotFoundError(_)) => {
+                    return HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(
+                            "Invalid or expired password reset token",
+                        ))
+                        .into_future();
+                }
+                Err(err) => {
+                    error!("Failed to take password reset token: {}", err);
+                    return HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future();
+                }
+            };
+
+            match unix_time_secs() {
+                Ok(now) if (now as i64) < token.expires_at => (),
+                Ok(_) => {
+                    return HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(
+                            "Invalid or expired password reset token",
+                        ))
+                        .into_future();
+                }
+                Err(err) => {
+                    error!("{}", err);
+                    return HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future();
+                }
+            }
+
+            let credentials = match credentials_store.fetch_credential_by_user_id(&token.user_id) {
+                Ok(credentials) => credentials,
+                Err(CredentialsStoreError::NotFoundError(_)) => {
+                    return HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(
+                            "Invalid or expired password reset token",
+                        ))
+                        .into_future();
+                }
+                Err(err) => {
+                    error!("Failed to fetch credentials: {}", err);
+                    return HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future();
+                }
+            };
+
+            if let Err(err) = credentials_store.update_credentials(
+                &credentials.user_id,
+                &credentials.username,
+                &password_reset.new_password,
+                encryption_cost,
+            ) {
+                error!("Failed to update credentials: {}", err);
+                return HttpResponse::InternalServerError()
+                    .json(ErrorResponse::internal_error())
+                    .into_future();
+            }
+
+            match refresh_token_store.remove_token(&credentials.user_id) {
+                Ok(()) | Err(RefreshTokenError::NotFoundError(_)) => (),
+                Err(err) => {
+                    error!("Failed to remove refresh token: {}", err);
+                    return HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future();
+                }
+            }
+
+            HttpResponse::Ok()
+                .json(json!({ "message": "Password reset successfully" }))
+                .into_future()
+        }))
+    })
+}
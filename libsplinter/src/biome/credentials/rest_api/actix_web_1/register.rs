@@ -18,8 +18,8 @@ use uuid::Uuid;
 use actix_web::HttpResponse;
 use futures::{Future, IntoFuture};
 
-use crate::biome::credentials::rest_api::actix_web_1::BiomeCredentialsRestConfig;
 use crate::biome::credentials::rest_api::resources::credentials::{NewUser, UsernamePassword};
+use crate::biome::credentials::rest_api::BiomeCredentialsRestConfig;
 use crate::biome::credentials::store::{
     CredentialsBuilder, CredentialsStore, CredentialsStoreError,
 };
@@ -19,11 +19,9 @@ use futures::{Future, IntoFuture};
 
 use crate::biome::{
     credentials::rest_api::{
-        actix_web_1::{
-            authorize::{authorize_user, validate_claims},
-            config::BiomeCredentialsRestConfig,
-        },
+        actix_web_1::authorize::{authorize_user, validate_claims},
         resources::{authorize::AuthorizationResult, token::RefreshToken},
+        BiomeCredentialsRestConfig,
     },
     refresh_tokens::store::{RefreshTokenError, RefreshTokenStore},
 };
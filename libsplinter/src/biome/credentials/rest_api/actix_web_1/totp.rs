@@ -0,0 +1,398 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use actix_web::HttpResponse;
+use futures::{Future, IntoFuture};
+
+use crate::biome::credentials::rest_api::{
+    actix_web_1::{authorize::authorize_user, unix_time_secs},
+    resources::{authorize::AuthorizationResult, credentials::TotpCode},
+    BiomeCredentialsRestConfig,
+};
+use crate::biome::credentials::store::{CredentialsStore, TotpSecret};
+use crate::biome::credentials::totp;
+#[cfg(feature = "authorization")]
+use crate::rest_api::auth::authorization::Permission;
+use crate::rest_api::{
+    actix_web_1::{into_bytes, HandlerFunction, Method, ProtocolVersionRangeGuard, Resource},
+    secrets::SecretManager,
+    sessions::default_validation,
+    ErrorResponse, SPLINTER_PROTOCOL_VERSION,
+};
+
+const BIOME_TOTP_PROTOCOL_MIN: u32 = 1;
+
+/// Defines the `/biome/totp` REST resource for managing the authorized user's TOTP second factor
+///
+/// * `POST` starts enrollment, returning a new secret. The secret is not required at login until
+///   enrollment is confirmed.
+/// * `PUT` confirms enrollment with a code generated from the new secret.
+/// * `DELETE` disables TOTP, with a code generated from the enabled secret.
+///
+/// The `PUT` and `DELETE` payloads should be in the JSON format:
+///   {
+///       "code": <code generated from the user's TOTP secret>
+///   }
+pub fn make_totp_route(
+    credentials_store: Arc<dyn CredentialsStore>,
+    secret_manager: Arc<dyn SecretManager>,
+    rest_config: Arc<BiomeCredentialsRestConfig>,
+) -> Resource {
+    let resource = Resource::build("/biome/totp").add_request_guard(
+        ProtocolVersionRangeGuard::new(BIOME_TOTP_PROTOCOL_MIN, SPLINTER_PROTOCOL_VERSION),
+    );
+    #[cfg(feature = "authorization")]
+    {
+        resource
+            .add_method(
+                Method::Post,
+                Permission::AllowAuthenticated,
+                add_enroll_totp_method(
+                    credentials_store.clone(),
+                    secret_manager.clone(),
+                    rest_config.clone(),
+                ),
+            )
+            .add_method(
+                Method::Put,
+                Permission::AllowAuthenticated,
+                add_confirm_totp_method(
+                    credentials_store.clone(),
+                    secret_manager.clone(),
+                    rest_config.clone(),
+                ),
+            )
+            .add_method(
+                Method::Delete,
+                Permission::AllowAuthenticated,
+                add_disable_totp_method(credentials_store, secret_manager, rest_config),
+            )
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource
+            .add_method(
+                Method::Post,
+                add_enroll_totp_method(
+                    credentials_store.clone(),
+                    secret_manager.clone(),
+                    rest_config.clone(),
+                ),
+            )
+            .add_method(
+                Method::Put,
+                add_confirm_totp_method(
+                    credentials_store.clone(),
+                    secret_manager.clone(),
+                    rest_config.clone(),
+                ),
+            )
+            .add_method(
+                Method::Delete,
+                add_disable_totp_method(credentials_store, secret_manager, rest_config),
+            )
+    }
+}
+
+fn add_enroll_totp_method(
+    credentials_store: Arc<dyn CredentialsStore>,
+    secret_manager: Arc<dyn SecretManager>,
+    rest_config: Arc<BiomeCredentialsRestConfig>,
+) -> HandlerFunction {
+    Box::new(move |request, _| {
+        let validation = default_validation(&rest_config.issuer());
+        let user_id = match authorize_user(&request, &secret_manager, &validation) {
+            AuthorizationResult::Authorized(claims) => claims.user_id(),
+            AuthorizationResult::Unauthorized => {
+                return Box::new(
+                    HttpResponse::Unauthorized()
+                        .json(ErrorResponse::unauthorized())
+                        .into_future(),
+                )
+            }
+            AuthorizationResult::Failed => {
+                return Box::new(
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future(),
+                );
+            }
+        };
+
+        match credentials_store.fetch_totp_secret(&user_id) {
+            Ok(Some(existing)) if existing.enabled => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(
+                            "TOTP is already enabled; it must be disabled before enrolling again",
+                        ))
+                        .into_future(),
+                )
+            }
+            Ok(_) => (),
+            Err(err) => {
+                error!("Failed to fetch TOTP secret: {}", err);
+                return Box::new(
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future(),
+                );
+            }
+        }
+
+        let username = match credentials_store.fetch_username_by_id(&user_id) {
+            Ok(username_id) => username_id.username,
+            Err(err) => {
+                error!("Failed to fetch username: {}", err);
+                return Box::new(
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future(),
+                );
+            }
+        };
+
+        let secret = match totp::generate_secret() {
+            Ok(secret) => secret,
+            Err(err) => {
+                error!("Failed to generate TOTP secret: {}", err);
+                return Box::new(
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future(),
+                );
+            }
+        };
+        let encoded_secret = totp::encode_secret(&secret);
+        let uri = totp::provisioning_uri(&rest_config.issuer(), &username, &secret);
+
+        Box::new(
+            match credentials_store.set_totp_secret(TotpSecret {
+                user_id,
+                secret,
+                enabled: false,
+                last_used_step: None,
+            }) {
+                Ok(()) => HttpResponse::Ok()
+                    .json(json!({
+                        "message": "TOTP enrollment started; confirm with a code to enable",
+                        "secret": encoded_secret,
+                        "uri": uri,
+                    }))
+                    .into_future(),
+                Err(err) => {
+                    error!("Failed to set TOTP secret: {}", err);
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future()
+                }
+            },
+        )
+    })
+}
+
+fn add_confirm_totp_method(
+    credentials_store: Arc<dyn CredentialsStore>,
+    secret_manager: Arc<dyn SecretManager>,
+    rest_config: Arc<BiomeCredentialsRestConfig>,
+) -> HandlerFunction {
+    Box::new(move |request, payload| {
+        let credentials_store = credentials_store.clone();
+        let validation = default_validation(&rest_config.issuer());
+        let user_id = match authorize_user(&request, &secret_manager, &validation) {
+            AuthorizationResult::Authorized(claims) => claims.user_id(),
+            AuthorizationResult::Unauthorized => {
+                return Box::new(
+                    HttpResponse::Unauthorized()
+                        .json(ErrorResponse::unauthorized())
+                        .into_future(),
+                )
+            }
+            AuthorizationResult::Failed => {
+                return Box::new(
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future(),
+                );
+            }
+        };
+
+        Box::new(into_bytes(payload).and_then(move |bytes| {
+            let totp_code = match serde_json::from_slice::<TotpCode>(&bytes) {
+                Ok(val) => val,
+                Err(err) => {
+                    debug!("Error parsing payload {}", err);
+                    return HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&format!(
+                            "Failed to parse payload: {}",
+                            err
+                        )))
+                        .into_future();
+                }
+            };
+
+            let secret = match credentials_store.fetch_totp_secret(&user_id) {
+                Ok(Some(secret)) if !secret.enabled => secret,
+                Ok(Some(_)) => {
+                    return HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request("TOTP is already enabled"))
+                        .into_future();
+                }
+                Ok(None) => {
+                    return HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(
+                            "TOTP enrollment has not been started",
+                        ))
+                        .into_future();
+                }
+                Err(err) => {
+                    error!("Failed to fetch TOTP secret: {}", err);
+                    return HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future();
+                }
+            };
+
+            let step = match verify_totp_code(&secret, &totp_code.code) {
+                Ok(Some(step)) => step,
+                Ok(None) => {
+                    return HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request("Invalid TOTP code"))
+                        .into_future();
+                }
+                Err(response) => return response.into_future(),
+            };
+
+            match credentials_store.set_totp_secret(TotpSecret {
+                enabled: true,
+                last_used_step: Some(step),
+                ..secret
+            }) {
+                Ok(()) => HttpResponse::Ok()
+                    .json(json!({ "message": "TOTP enabled" }))
+                    .into_future(),
+                Err(err) => {
+                    error!("Failed to set TOTP secret: {}", err);
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future()
+                }
+            }
+        }))
+    })
+}
+
+fn add_disable_totp_method(
+    credentials_store: Arc<dyn CredentialsStore>,
+    secret_manager: Arc<dyn SecretManager>,
+    rest_config: Arc<BiomeCredentialsRestConfig>,
+) -> HandlerFunction {
+    Box::new(move |request, payload| {
+        let credentials_store = credentials_store.clone();
+        let validation = default_validation(&rest_config.issuer());
+        let user_id = match authorize_user(&request, &secret_manager, &validation) {
+            AuthorizationResult::Authorized(claims) => claims.user_id(),
+            AuthorizationResult::Unauthorized => {
+                return Box::new(
+                    HttpResponse::Unauthorized()
+                        .json(ErrorResponse::unauthorized())
+                        .into_future(),
+                )
+            }
+            AuthorizationResult::Failed => {
+                return Box::new(
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future(),
+                );
+            }
+        };
+
+        Box::new(into_bytes(payload).and_then(move |bytes| {
+            let totp_code = match serde_json::from_slice::<TotpCode>(&bytes) {
+                Ok(val) => val,
+                Err(err) => {
+                    debug!("Error parsing payload {}", err);
+                    return HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&format!(
+                            "Failed to parse payload: {}",
+                            err
+                        )))
+                        .into_future();
+                }
+            };
+
+            let secret = match credentials_store.fetch_totp_secret(&user_id) {
+                Ok(Some(secret)) => secret,
+                Ok(None) => {
+                    return HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request("TOTP is not enabled"))
+                        .into_future();
+                }
+                Err(err) => {
+                    error!("Failed to fetch TOTP secret: {}", err);
+                    return HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future();
+                }
+            };
+
+            // A pending enrollment has not protected anything yet, so it may be abandoned
+            // without a code
+            if secret.enabled {
+                match verify_totp_code(&secret, &totp_code.code) {
+                    Ok(Some(_)) => (),
+                    Ok(None) => {
+                        return HttpResponse::BadRequest()
+                            .json(ErrorResponse::bad_request("Invalid TOTP code"))
+                            .into_future();
+                    }
+                    Err(response) => return response.into_future(),
+                }
+            }
+
+            match credentials_store.remove_totp_secret(&user_id) {
+                Ok(()) => HttpResponse::Ok()
+                    .json(json!({ "message": "TOTP disabled" }))
+                    .into_future(),
+                Err(err) => {
+                    error!("Failed to remove TOTP secret: {}", err);
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future()
+                }
+            }
+        }))
+    })
+}
+
+/// Verifies a code against the user's secret at the current time, returning the time step the
+/// code was accepted for.
+///
+/// Returns an internal server error response if the code could not be verified.
+pub(super) fn verify_totp_code(
+    secret: &TotpSecret,
+    code: &str,
+) -> Result<Option<i64>, HttpResponse> {
+    let now = unix_time_secs().map_err(|err| {
+        error!("{}", err);
+        HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+    })?;
+    totp::verify_code(&secret.secret, code, now, secret.last_used_step).map_err(|err| {
+        error!("Failed to verify TOTP code: {}", err);
+        HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+    })
+}
@@ -17,7 +17,7 @@ use std::sync::Arc;
 use actix_web::HttpResponse;
 use futures::{Future, IntoFuture};
 
-use crate::biome::credentials::rest_api::actix_web_1::config::BiomeCredentialsRestConfig;
+use crate::biome::credentials::rest_api::BiomeCredentialsRestConfig;
 use crate::biome::credentials::store::{CredentialsStore, CredentialsStoreError};
 use crate::rest_api::{
     actix_web_1::{into_bytes, HandlerFunction, Method, ProtocolVersionRangeGuard, Resource},
@@ -26,7 +26,7 @@ use crate::rest_api::{
     ErrorResponse, SPLINTER_PROTOCOL_VERSION,
 };
 
-use crate::biome::credentials::rest_api::actix_web_1::config::BiomeCredentialsRestConfig;
+use crate::biome::credentials::rest_api::BiomeCredentialsRestConfig;
 use crate::biome::credentials::store::{CredentialsStore, CredentialsStoreError};
 
 use super::super::resources::authorize::AuthorizationResult;
@@ -20,6 +20,8 @@ use crate::error::InvalidStateError;
 const DEFAULT_ISSUER: &str = "self-issued";
 const DEFAULT_DURATION: u64 = 5400; // in seconds = 90 minutes
 const DEFAULT_REFRESH_DURATION: u64 = 5_184_000; // in seconds = 60 days
+#[cfg(feature = "biome-credentials-reset")]
+const DEFAULT_PASSWORD_RESET_DURATION: u64 = 3600; // in seconds = 1 hour
 
 /// Configuration for Biome credentials REST resources
 #[derive(Deserialize, Debug)]
@@ -32,6 +34,9 @@ pub struct BiomeCredentialsRestConfig {
     refresh_token_duration: Duration,
     /// Cost for encrypting user's password
     password_encryption_cost: PasswordEncryptionCost,
+    /// Duration of password reset tokens issued by this service
+    #[cfg(feature = "biome-credentials-reset")]
+    password_reset_token_duration: Duration,
 }
 
 impl BiomeCredentialsRestConfig {
@@ -58,6 +63,13 @@ impl BiomeCredentialsRestConfig {
     pub fn password_encryption_cost(&self) -> PasswordEncryptionCost {
         self.password_encryption_cost
     }
+
+    /// Returns duration that a password reset token is valid.
+    /// Defaults to 1 hour.
+    #[cfg(feature = "biome-credentials-reset")]
+    pub fn password_reset_token_duration(&self) -> Duration {
+        self.password_reset_token_duration.to_owned()
+    }
 }
 
 /// Builder for BiomeCredentialsRestConfig
@@ -66,6 +78,8 @@ pub struct BiomeCredentialsRestConfigBuilder {
     access_token_duration: Option<Duration>,
     refresh_token_duration: Option<Duration>,
     password_encryption_cost: Option<String>,
+    #[cfg(feature = "biome-credentials-reset")]
+    password_reset_token_duration: Option<Duration>,
 }
 
 impl Default for BiomeCredentialsRestConfigBuilder {
@@ -75,6 +89,10 @@ impl Default for BiomeCredentialsRestConfigBuilder {
             access_token_duration: Some(Duration::from_secs(DEFAULT_DURATION)),
             refresh_token_duration: Some(Duration::from_secs(DEFAULT_REFRESH_DURATION)),
             password_encryption_cost: Some("high".to_string()),
+            #[cfg(feature = "biome-credentials-reset")]
+            password_reset_token_duration: Some(Duration::from_secs(
+                DEFAULT_PASSWORD_RESET_DURATION,
+            )),
         }
     }
 }
@@ -87,6 +105,8 @@ impl BiomeCredentialsRestConfigBuilder {
             access_token_duration: None,
             refresh_token_duration: None,
             password_encryption_cost: None,
+            #[cfg(feature = "biome-credentials-reset")]
+            password_reset_token_duration: None,
         }
     }
 
@@ -115,6 +135,13 @@ impl BiomeCredentialsRestConfigBuilder {
         self
     }
 
+    /// Adds a password reset token duration in seconds.
+    #[cfg(feature = "biome-credentials-reset")]
+    pub fn with_password_reset_token_duration_in_secs(mut self, duration: u64) -> Self {
+        self.password_reset_token_duration = Some(Duration::from_secs(duration));
+        self
+    }
+
     /// Creates a new BiomeCredentialsRestConfig.
     pub fn build(self) -> Result<BiomeCredentialsRestConfig, InvalidStateError> {
         let issuer = self.issuer.unwrap_or_else(|| {
@@ -142,11 +169,18 @@ impl BiomeCredentialsRestConfigBuilder {
                 ))
             })?;
 
+        #[cfg(feature = "biome-credentials-reset")]
+        let password_reset_token_duration = self
+            .password_reset_token_duration
+            .unwrap_or_else(|| Duration::from_secs(DEFAULT_PASSWORD_RESET_DURATION));
+
         Ok(BiomeCredentialsRestConfig {
             issuer,
             access_token_duration,
             refresh_token_duration,
             password_encryption_cost,
+            #[cfg(feature = "biome-credentials-reset")]
+            password_reset_token_duration,
         })
     }
 }
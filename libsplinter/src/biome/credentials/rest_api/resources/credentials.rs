@@ -18,6 +18,23 @@
 pub(crate) struct UsernamePassword {
     pub username: String,
     pub hashed_password: String,
+    /// Only required at login for users that have enabled TOTP
+    #[cfg(feature = "biome-credentials-totp")]
+    #[serde(default)]
+    pub totp_code: Option<String>,
+}
+
+#[cfg(feature = "biome-credentials-reset")]
+#[derive(Deserialize)]
+pub(crate) struct PasswordReset {
+    pub reset_token: String,
+    pub new_password: String,
+}
+
+#[cfg(feature = "biome-credentials-totp")]
+#[derive(Deserialize)]
+pub(crate) struct TotpCode {
+    pub code: String,
 }
 
 #[derive(Serialize)]
@@ -22,17 +22,38 @@ use diesel::r2d2::{ConnectionManager, Pool};
 
 use crate::store::pool::ConnectionPool;
 
+#[cfg(feature = "biome-credentials-totp")]
+use crate::hex::to_hex;
+
+#[cfg(feature = "biome-credentials-reset")]
+use super::PasswordResetToken;
+#[cfg(feature = "biome-credentials-totp")]
+use super::TotpSecret;
 use super::{
     Credentials, CredentialsStore, CredentialsStoreError, PasswordEncryptionCost, UsernameId,
 };
 
 use models::CredentialsModel;
+#[cfg(feature = "biome-credentials-reset")]
+use models::PasswordResetTokenModel;
+#[cfg(feature = "biome-credentials-totp")]
+use models::TotpSecretModel;
 use operations::add_credentials::CredentialsStoreAddCredentialsOperation as _;
+#[cfg(feature = "biome-credentials-reset")]
+use operations::add_password_reset_token::CredentialsStoreAddPasswordResetTokenOperation as _;
 use operations::fetch_credential_by_id::CredentialsStoreFetchCredentialByIdOperation as _;
 use operations::fetch_credential_by_username::CredentialsStoreFetchCredentialByUsernameOperation as _;
+#[cfg(feature = "biome-credentials-totp")]
+use operations::fetch_totp_secret::CredentialsStoreFetchTotpSecretOperation as _;
 use operations::fetch_username::CredentialsStoreFetchUsernameOperation as _;
 use operations::list_usernames::CredentialsStoreListUsernamesOperation as _;
 use operations::remove_credentials::CredentialsStoreRemoveCredentialsOperation as _;
+#[cfg(feature = "biome-credentials-totp")]
+use operations::remove_totp_secret::CredentialsStoreRemoveTotpSecretOperation as _;
+#[cfg(feature = "biome-credentials-totp")]
+use operations::set_totp_secret::CredentialsStoreSetTotpSecretOperation as _;
+#[cfg(feature = "biome-credentials-reset")]
+use operations::take_password_reset_token::CredentialsStoreTakePasswordResetTokenOperation as _;
 use operations::update_credentials::CredentialsStoreUpdateCredentialsOperation as _;
 use operations::CredentialsStoreOperations;
 
@@ -128,6 +149,47 @@ impl CredentialsStore for DieselCredentialsStore<diesel::pg::PgConnection> {
         self.connection_pool
             .execute_read(|conn| CredentialsStoreOperations::new(conn).list_usernames())
     }
+
+    #[cfg(feature = "biome-credentials-reset")]
+    fn add_password_reset_token(
+        &self,
+        token: PasswordResetToken,
+    ) -> Result<(), CredentialsStoreError> {
+        self.connection_pool.execute_write(|conn| {
+            CredentialsStoreOperations::new(conn).add_password_reset_token(token)
+        })
+    }
+
+    #[cfg(feature = "biome-credentials-reset")]
+    fn take_password_reset_token(
+        &self,
+        token_hash: &str,
+    ) -> Result<PasswordResetToken, CredentialsStoreError> {
+        self.connection_pool.execute_write(|conn| {
+            CredentialsStoreOperations::new(conn).take_password_reset_token(token_hash)
+        })
+    }
+
+    #[cfg(feature = "biome-credentials-totp")]
+    fn fetch_totp_secret(
+        &self,
+        user_id: &str,
+    ) -> Result<Option<TotpSecret>, CredentialsStoreError> {
+        self.connection_pool
+            .execute_read(|conn| CredentialsStoreOperations::new(conn).fetch_totp_secret(user_id))
+    }
+
+    #[cfg(feature = "biome-credentials-totp")]
+    fn set_totp_secret(&self, secret: TotpSecret) -> Result<(), CredentialsStoreError> {
+        self.connection_pool
+            .execute_write(|conn| CredentialsStoreOperations::new(conn).set_totp_secret(secret))
+    }
+
+    #[cfg(feature = "biome-credentials-totp")]
+    fn remove_totp_secret(&self, user_id: &str) -> Result<(), CredentialsStoreError> {
+        self.connection_pool
+            .execute_write(|conn| CredentialsStoreOperations::new(conn).remove_totp_secret(user_id))
+    }
 }
 
 #[cfg(feature = "sqlite")]
@@ -188,6 +250,47 @@ impl CredentialsStore for DieselCredentialsStore<diesel::sqlite::SqliteConnectio
         self.connection_pool
             .execute_read(|conn| CredentialsStoreOperations::new(conn).list_usernames())
     }
+
+    #[cfg(feature = "biome-credentials-reset")]
+    fn add_password_reset_token(
+        &self,
+        token: PasswordResetToken,
+    ) -> Result<(), CredentialsStoreError> {
+        self.connection_pool.execute_write(|conn| {
+            CredentialsStoreOperations::new(conn).add_password_reset_token(token)
+        })
+    }
+
+    #[cfg(feature = "biome-credentials-reset")]
+    fn take_password_reset_token(
+        &self,
+        token_hash: &str,
+    ) -> Result<PasswordResetToken, CredentialsStoreError> {
+        self.connection_pool.execute_write(|conn| {
+            CredentialsStoreOperations::new(conn).take_password_reset_token(token_hash)
+        })
+    }
+
+    #[cfg(feature = "biome-credentials-totp")]
+    fn fetch_totp_secret(
+        &self,
+        user_id: &str,
+    ) -> Result<Option<TotpSecret>, CredentialsStoreError> {
+        self.connection_pool
+            .execute_read(|conn| CredentialsStoreOperations::new(conn).fetch_totp_secret(user_id))
+    }
+
+    #[cfg(feature = "biome-credentials-totp")]
+    fn set_totp_secret(&self, secret: TotpSecret) -> Result<(), CredentialsStoreError> {
+        self.connection_pool
+            .execute_write(|conn| CredentialsStoreOperations::new(conn).set_totp_secret(secret))
+    }
+
+    #[cfg(feature = "biome-credentials-totp")]
+    fn remove_totp_secret(&self, user_id: &str) -> Result<(), CredentialsStoreError> {
+        self.connection_pool
+            .execute_write(|conn| CredentialsStoreOperations::new(conn).remove_totp_secret(user_id))
+    }
 }
 
 impl From<CredentialsModel> for UsernameId {
@@ -209,6 +312,40 @@ impl From<CredentialsModel> for Credentials {
     }
 }
 
+#[cfg(feature = "biome-credentials-reset")]
+impl From<PasswordResetToken> for PasswordResetTokenModel {
+    fn from(token: PasswordResetToken) -> Self {
+        Self {
+            user_id: token.user_id,
+            token_hash: token.token_hash,
+            expires_at: token.expires_at,
+        }
+    }
+}
+
+#[cfg(feature = "biome-credentials-reset")]
+impl From<PasswordResetTokenModel> for PasswordResetToken {
+    fn from(token: PasswordResetTokenModel) -> Self {
+        Self {
+            user_id: token.user_id,
+            token_hash: token.token_hash,
+            expires_at: token.expires_at,
+        }
+    }
+}
+
+#[cfg(feature = "biome-credentials-totp")]
+impl From<TotpSecret> for TotpSecretModel {
+    fn from(secret: TotpSecret) -> Self {
+        Self {
+            user_id: secret.user_id,
+            secret: to_hex(&secret.secret),
+            enabled: secret.enabled,
+            last_used_step: secret.last_used_step,
+        }
+    }
+}
+
 #[cfg(all(test, feature = "sqlite"))]
 pub mod tests {
     use super::*;
@@ -616,6 +753,132 @@ pub mod tests {
         }));
     }
 
+    /// Verify that a SQLite-backed `DieselCredentialsStore` correctly supports password reset
+    /// tokens.
+    ///
+    /// 1. Create a connection pool for an in-memory SQLite database and run migrations.
+    /// 2. Create the `DieselCredentialsStore`.
+    /// 3. Add a token for a user, then add a second token for the same user.
+    /// 4. Verify that the first token has been replaced and can not be taken.
+    /// 5. Verify that the second token can be taken exactly once.
+    #[cfg(feature = "biome-credentials-reset")]
+    #[test]
+    fn sqlite_password_reset_token() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselCredentialsStore::new(pool);
+
+        store
+            .add_password_reset_token(PasswordResetToken {
+                user_id: "id1".into(),
+                token_hash: "hash1".into(),
+                expires_at: 100,
+            })
+            .expect("Failed to add first token");
+        let second_token = PasswordResetToken {
+            user_id: "id1".into(),
+            token_hash: "hash2".into(),
+            expires_at: 200,
+        };
+        store
+            .add_password_reset_token(second_token.clone())
+            .expect("Failed to add second token");
+
+        match store.take_password_reset_token("hash1") {
+            Err(CredentialsStoreError::NotFoundError(_)) => {}
+            res => panic!(
+                "Expected Err(CredentialsStoreError::NotFoundError), got {:?} instead",
+                res
+            ),
+        }
+
+        assert_eq!(
+            store
+                .take_password_reset_token("hash2")
+                .expect("Failed to take token"),
+            second_token
+        );
+
+        match store.take_password_reset_token("hash2") {
+            Err(CredentialsStoreError::NotFoundError(_)) => {}
+            res => panic!(
+                "Expected Err(CredentialsStoreError::NotFoundError), got {:?} instead",
+                res
+            ),
+        }
+    }
+
+    /// Verify that a SQLite-backed `DieselCredentialsStore` correctly supports TOTP secrets.
+    ///
+    /// 1. Create a connection pool for an in-memory SQLite database and run migrations.
+    /// 2. Create the `DieselCredentialsStore`.
+    /// 3. Verify that a user without a secret has none.
+    /// 4. Set a pending secret, then replace it with an enabled one, and verify each is fetched.
+    /// 5. Remove the secret and verify it is no longer fetched and can not be removed again.
+    #[cfg(feature = "biome-credentials-totp")]
+    #[test]
+    fn sqlite_totp_secret() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselCredentialsStore::new(pool);
+
+        assert_eq!(
+            store
+                .fetch_totp_secret("id1")
+                .expect("Failed to fetch secret"),
+            None
+        );
+
+        let pending = TotpSecret {
+            user_id: "id1".into(),
+            secret: vec![1, 2, 3, 4],
+            enabled: false,
+            last_used_step: None,
+        };
+        store
+            .set_totp_secret(pending.clone())
+            .expect("Failed to set pending secret");
+        assert_eq!(
+            store
+                .fetch_totp_secret("id1")
+                .expect("Failed to fetch secret"),
+            Some(pending)
+        );
+
+        let enabled = TotpSecret {
+            user_id: "id1".into(),
+            secret: vec![1, 2, 3, 4],
+            enabled: true,
+            last_used_step: Some(42),
+        };
+        store
+            .set_totp_secret(enabled.clone())
+            .expect("Failed to set enabled secret");
+        assert_eq!(
+            store
+                .fetch_totp_secret("id1")
+                .expect("Failed to fetch secret"),
+            Some(enabled)
+        );
+
+        store
+            .remove_totp_secret("id1")
+            .expect("Failed to remove secret");
+        assert_eq!(
+            store
+                .fetch_totp_secret("id1")
+                .expect("Failed to fetch secret"),
+            None
+        );
+        match store.remove_totp_secret("id1") {
+            Err(CredentialsStoreError::NotFoundError(_)) => {}
+            res => panic!(
+                "Expected Err(CredentialsStoreError::NotFoundError), got {:?} instead",
+                res
+            ),
+        }
+    }
+
     /// Creates a connection pool for an in-memory SQLite database with only a single connection
     /// available. Each connection is backed by a different in-memory SQLite database, so limiting
     /// the pool to a single connection insures that the same DB is used for all operations.
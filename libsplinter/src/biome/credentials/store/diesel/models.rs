@@ -13,6 +13,10 @@
 // limitations under the License.
 
 use super::schema::user_credentials;
+#[cfg(feature = "biome-credentials-reset")]
+use super::schema::user_password_reset_token;
+#[cfg(feature = "biome-credentials-totp")]
+use super::schema::user_totp;
 
 #[derive(Queryable, Identifiable, Associations, PartialEq, Eq, Debug)]
 #[table_name = "user_credentials"]
@@ -30,3 +34,22 @@ pub struct NewCredentialsModel {
     pub username: String,
     pub password: String,
 }
+
+#[cfg(feature = "biome-credentials-reset")]
+#[derive(Queryable, Insertable, PartialEq, Eq, Debug)]
+#[table_name = "user_password_reset_token"]
+pub struct PasswordResetTokenModel {
+    pub user_id: String,
+    pub token_hash: String,
+    pub expires_at: i64,
+}
+
+#[cfg(feature = "biome-credentials-totp")]
+#[derive(Queryable, Insertable, PartialEq, Eq, Debug)]
+#[table_name = "user_totp"]
+pub struct TotpSecretModel {
+    pub user_id: String,
+    pub secret: String,
+    pub enabled: bool,
+    pub last_used_step: Option<i64>,
+}
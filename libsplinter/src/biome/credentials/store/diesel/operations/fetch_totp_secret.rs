@@ -0,0 +1,69 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::CredentialsStoreOperations;
+use crate::biome::credentials::store::diesel::{
+    models::TotpSecretModel, schema::user_totp, CredentialsStoreError,
+};
+use crate::biome::credentials::store::TotpSecret;
+use crate::error::InvalidStateError;
+use crate::hex::parse_hex;
+use diesel::{prelude::*, result::Error::NotFound};
+
+pub(in crate::biome::credentials) trait CredentialsStoreFetchTotpSecretOperation {
+    fn fetch_totp_secret(&self, user_id: &str)
+        -> Result<Option<TotpSecret>, CredentialsStoreError>;
+}
+
+impl<'a, C> CredentialsStoreFetchTotpSecretOperation for CredentialsStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+    bool: diesel::deserialize::FromSql<diesel::sql_types::Bool, C::Backend>,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+{
+    fn fetch_totp_secret(
+        &self,
+        user_id: &str,
+    ) -> Result<Option<TotpSecret>, CredentialsStoreError> {
+        let model = user_totp::table
+            .filter(user_totp::user_id.eq(user_id))
+            .first::<TotpSecretModel>(self.conn)
+            .map(Some)
+            .or_else(|err| if err == NotFound { Ok(None) } else { Err(err) })
+            .map_err(|err| CredentialsStoreError::QueryError {
+                context: "Failed to fetch TOTP secret".to_string(),
+                source: Box::new(err),
+            })?;
+
+        model
+            .map(|model| {
+                let secret =
+                    parse_hex(&model.secret).map_err(|_| CredentialsStoreError::StorageError {
+                        context: "Failed to parse TOTP secret".to_string(),
+                        source: Some(Box::new(InvalidStateError::with_message(format!(
+                            "TOTP secret for user id {} is not valid hex",
+                            model.user_id
+                        )))),
+                    })?;
+                Ok(TotpSecret {
+                    user_id: model.user_id,
+                    secret,
+                    enabled: model.enabled,
+                    last_used_step: model.last_used_step,
+                })
+            })
+            .transpose()
+    }
+}
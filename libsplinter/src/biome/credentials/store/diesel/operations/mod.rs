@@ -15,11 +15,21 @@
 //! Provides CredentialsStoreOperations implemented for a diesel backend
 
 pub(super) mod add_credentials;
+#[cfg(feature = "biome-credentials-reset")]
+pub(super) mod add_password_reset_token;
 pub(super) mod fetch_credential_by_id;
 pub(super) mod fetch_credential_by_username;
+#[cfg(feature = "biome-credentials-totp")]
+pub(super) mod fetch_totp_secret;
 pub(super) mod fetch_username;
 pub(super) mod list_usernames;
 pub(super) mod remove_credentials;
+#[cfg(feature = "biome-credentials-totp")]
+pub(super) mod remove_totp_secret;
+#[cfg(feature = "biome-credentials-totp")]
+pub(super) mod set_totp_secret;
+#[cfg(feature = "biome-credentials-reset")]
+pub(super) mod take_password_reset_token;
 pub(super) mod update_credentials;
 
 pub(super) struct CredentialsStoreOperations<'a, C> {
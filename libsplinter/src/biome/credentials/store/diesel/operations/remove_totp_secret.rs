@@ -0,0 +1,42 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::CredentialsStoreOperations;
+use crate::biome::credentials::store::diesel::{schema::user_totp, CredentialsStoreError};
+use diesel::{dsl::delete, prelude::*};
+
+pub(in crate::biome::credentials) trait CredentialsStoreRemoveTotpSecretOperation {
+    fn remove_totp_secret(&self, user_id: &str) -> Result<(), CredentialsStoreError>;
+}
+
+impl<'a, C> CredentialsStoreRemoveTotpSecretOperation for CredentialsStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+{
+    fn remove_totp_secret(&self, user_id: &str) -> Result<(), CredentialsStoreError> {
+        let deleted = delete(user_totp::table.filter(user_totp::user_id.eq(user_id)))
+            .execute(self.conn)
+            .map_err(|err| CredentialsStoreError::OperationError {
+                context: "Failed to remove TOTP secret".to_string(),
+                source: Box::new(err),
+            })?;
+        if deleted == 0 {
+            return Err(CredentialsStoreError::NotFoundError(format!(
+                "TOTP secret not found for user id: {}",
+                user_id
+            )));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::CredentialsStoreOperations;
+use crate::biome::credentials::store::diesel::{
+    models::TotpSecretModel, schema::user_totp, CredentialsStoreError,
+};
+use crate::biome::credentials::store::TotpSecret;
+use diesel::{
+    dsl::{delete, insert_into},
+    prelude::*,
+};
+
+pub(in crate::biome::credentials) trait CredentialsStoreSetTotpSecretOperation {
+    fn set_totp_secret(&self, secret: TotpSecret) -> Result<(), CredentialsStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> CredentialsStoreSetTotpSecretOperation
+    for CredentialsStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn set_totp_secret(&self, secret: TotpSecret) -> Result<(), CredentialsStoreError> {
+        delete(user_totp::table.filter(user_totp::user_id.eq(&secret.user_id)))
+            .execute(self.conn)
+            .map_err(|err| CredentialsStoreError::OperationError {
+                context: "Failed to remove existing TOTP secret".to_string(),
+                source: Box::new(err),
+            })?;
+
+        insert_into(user_totp::table)
+            .values(TotpSecretModel::from(secret))
+            .execute(self.conn)
+            .map(|_| ())
+            .map_err(|err| CredentialsStoreError::OperationError {
+                context: "Failed to set TOTP secret".to_string(),
+                source: Box::new(err),
+            })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> CredentialsStoreSetTotpSecretOperation
+    for CredentialsStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn set_totp_secret(&self, secret: TotpSecret) -> Result<(), CredentialsStoreError> {
+        delete(user_totp::table.filter(user_totp::user_id.eq(&secret.user_id)))
+            .execute(self.conn)
+            .map_err(|err| CredentialsStoreError::OperationError {
+                context: "Failed to remove existing TOTP secret".to_string(),
+                source: Box::new(err),
+            })?;
+
+        insert_into(user_totp::table)
+            .values(TotpSecretModel::from(secret))
+            .execute(self.conn)
+            .map(|_| ())
+            .map_err(|err| CredentialsStoreError::OperationError {
+                context: "Failed to set TOTP secret".to_string(),
+                source: Box::new(err),
+            })
+    }
+}
@@ -0,0 +1,71 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::CredentialsStoreOperations;
+use crate::biome::credentials::store::diesel::{
+    models::PasswordResetTokenModel, schema::user_password_reset_token, CredentialsStoreError,
+};
+use crate::biome::credentials::store::PasswordResetToken;
+use diesel::{dsl::delete, prelude::*, result::Error::NotFound};
+
+pub(in crate::biome::credentials) trait CredentialsStoreTakePasswordResetTokenOperation {
+    fn take_password_reset_token(
+        &self,
+        token_hash: &str,
+    ) -> Result<PasswordResetToken, CredentialsStoreError>;
+}
+
+impl<'a, C> CredentialsStoreTakePasswordResetTokenOperation for CredentialsStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+{
+    fn take_password_reset_token(
+        &self,
+        token_hash: &str,
+    ) -> Result<PasswordResetToken, CredentialsStoreError> {
+        let token = user_password_reset_token::table
+            .filter(user_password_reset_token::token_hash.eq(token_hash))
+            .first::<PasswordResetTokenModel>(self.conn)
+            .map(Some)
+            .or_else(|err| if err == NotFound { Ok(None) } else { Err(err) })
+            .map_err(|err| CredentialsStoreError::QueryError {
+                context: "Failed to fetch password reset token".to_string(),
+                source: Box::new(err),
+            })?
+            .ok_or_else(|| {
+                CredentialsStoreError::NotFoundError("Password reset token not found".to_string())
+            })?;
+
+        // Only the caller that actually deletes the token may use it, so a token can not be
+        // used twice by concurrent requests
+        let deleted = delete(
+            user_password_reset_token::table
+                .filter(user_password_reset_token::token_hash.eq(token_hash)),
+        )
+        .execute(self.conn)
+        .map_err(|err| CredentialsStoreError::OperationError {
+            context: "Failed to remove password reset token".to_string(),
+            source: Box::new(err),
+        })?;
+        if deleted == 0 {
+            return Err(CredentialsStoreError::NotFoundError(
+                "Password reset token not found".to_string(),
+            ));
+        }
+
+        Ok(PasswordResetToken::from(token))
+    }
+}
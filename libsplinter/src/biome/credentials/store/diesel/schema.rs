@@ -20,3 +20,20 @@ table! {
         password -> Text,
     }
 }
+
+table! {
+    user_password_reset_token (user_id) {
+        user_id -> Text,
+        token_hash -> Text,
+        expires_at -> Int8,
+    }
+}
+
+table! {
+    user_totp (user_id) {
+        user_id -> Text,
+        secret -> Text,
+        enabled -> Bool,
+        last_used_step -> Nullable<Int8>,
+    }
+}
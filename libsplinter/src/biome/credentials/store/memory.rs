@@ -15,6 +15,10 @@
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
+#[cfg(feature = "biome-credentials-reset")]
+use crate::biome::credentials::store::PasswordResetToken;
+#[cfg(feature = "biome-credentials-totp")]
+use crate::biome::credentials::store::TotpSecret;
 use crate::biome::credentials::store::{
     error::CredentialsStoreError, Credentials, CredentialsBuilder, CredentialsStore,
     PasswordEncryptionCost, UsernameId,
@@ -23,12 +27,22 @@ use crate::biome::credentials::store::{
 #[derive(Default, Clone)]
 pub struct MemoryCredentialsStore {
     inner: Arc<Mutex<HashMap<String, Credentials>>>,
+    // password reset tokens, keyed by token hash
+    #[cfg(feature = "biome-credentials-reset")]
+    reset_tokens: Arc<Mutex<HashMap<String, PasswordResetToken>>>,
+    // TOTP secrets, keyed by user id
+    #[cfg(feature = "biome-credentials-totp")]
+    totp_secrets: Arc<Mutex<HashMap<String, TotpSecret>>>,
 }
 
 impl MemoryCredentialsStore {
     pub fn new() -> Self {
         MemoryCredentialsStore {
             inner: Arc::new(Mutex::new(HashMap::new())),
+            #[cfg(feature = "biome-credentials-reset")]
+            reset_tokens: Arc::new(Mutex::new(HashMap::new())),
+            #[cfg(feature = "biome-credentials-totp")]
+            totp_secrets: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 }
@@ -180,4 +194,85 @@ impl CredentialsStore for MemoryCredentialsStore {
             })
             .collect())
     }
+
+    #[cfg(feature = "biome-credentials-reset")]
+    fn add_password_reset_token(
+        &self,
+        token: PasswordResetToken,
+    ) -> Result<(), CredentialsStoreError> {
+        let mut reset_tokens =
+            self.reset_tokens
+                .lock()
+                .map_err(|_| CredentialsStoreError::StorageError {
+                    context: "Cannot access password reset tokens: mutex lock poisoned".to_string(),
+                    source: None,
+                })?;
+        reset_tokens.retain(|_, existing| existing.user_id != token.user_id);
+        reset_tokens.insert(token.token_hash.clone(), token);
+        Ok(())
+    }
+
+    #[cfg(feature = "biome-credentials-reset")]
+    fn take_password_reset_token(
+        &self,
+        token_hash: &str,
+    ) -> Result<PasswordResetToken, CredentialsStoreError> {
+        let mut reset_tokens =
+            self.reset_tokens
+                .lock()
+                .map_err(|_| CredentialsStoreError::StorageError {
+                    context: "Cannot access password reset tokens: mutex lock poisoned".to_string(),
+                    source: None,
+                })?;
+        reset_tokens.remove(token_hash).ok_or_else(|| {
+            CredentialsStoreError::NotFoundError("Password reset token not found".to_string())
+        })
+    }
+
+    #[cfg(feature = "biome-credentials-totp")]
+    fn fetch_totp_secret(
+        &self,
+        user_id: &str,
+    ) -> Result<Option<TotpSecret>, CredentialsStoreError> {
+        let totp_secrets =
+            self.totp_secrets
+                .lock()
+                .map_err(|_| CredentialsStoreError::StorageError {
+                    context: "Cannot access TOTP secrets: mutex lock poisoned".to_string(),
+                    source: None,
+                })?;
+        Ok(totp_secrets.get(user_id).cloned())
+    }
+
+    #[cfg(feature = "biome-credentials-totp")]
+    fn set_totp_secret(&self, secret: TotpSecret) -> Result<(), CredentialsStoreError> {
+        let mut totp_secrets =
+            self.totp_secrets
+                .lock()
+                .map_err(|_| CredentialsStoreError::StorageError {
+                    context: "Cannot access TOTP secrets: mutex lock poisoned".to_string(),
+                    source: None,
+                })?;
+        totp_secrets.insert(secret.user_id.clone(), secret);
+        Ok(())
+    }
+
+    #[cfg(feature = "biome-credentials-totp")]
+    fn remove_totp_secret(&self, user_id: &str) -> Result<(), CredentialsStoreError> {
+        let mut totp_secrets =
+            self.totp_secrets
+                .lock()
+                .map_err(|_| CredentialsStoreError::StorageError {
+                    context: "Cannot access TOTP secrets: mutex lock poisoned".to_string(),
+                    source: None,
+                })?;
+        if totp_secrets.remove(user_id).is_some() {
+            Ok(())
+        } else {
+            Err(CredentialsStoreError::NotFoundError(format!(
+                "TOTP secret for user id {} not found",
+                user_id
+            )))
+        }
+    }
 }
@@ -59,6 +59,32 @@ pub struct UsernameId {
     pub user_id: String,
 }
 
+/// Represents a single-use token that allows a user's password to be reset
+///
+/// Only a hash of the token is stored; the token itself is given to the user once, when it is
+/// issued.
+#[cfg(feature = "biome-credentials-reset")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PasswordResetToken {
+    pub user_id: String,
+    /// Hex-encoded SHA-256 hash of the token
+    pub token_hash: String,
+    /// Time the token expires, in seconds since the Unix epoch
+    pub expires_at: i64,
+}
+
+/// Represents a user's TOTP secret, used as a second factor at login
+#[cfg(feature = "biome-credentials-totp")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TotpSecret {
+    pub user_id: String,
+    pub secret: Vec<u8>,
+    /// Whether the user has confirmed enrollment; login only requires a code once enabled
+    pub enabled: bool,
+    /// The last time step a code was accepted for, used to prevent a code being reused
+    pub last_used_step: Option<i64>,
+}
+
 /// Builder for Credential. It hashes the password upon build.
 #[derive(Default)]
 pub struct CredentialsBuilder {
@@ -238,6 +264,76 @@ pub trait CredentialsStore: Send + Sync {
     ///
     /// Returns a CredentialsStoreError if implementation cannot fetch the user IDs
     fn list_usernames(&self) -> Result<Vec<UsernameId>, CredentialsStoreError>;
+
+    /// Adds a password reset token, replacing any existing token for the same user
+    ///
+    /// # Arguments
+    ///
+    ///  * `token` - The password reset token to be added
+    ///
+    /// # Errors
+    ///
+    /// Returns a CredentialsStoreError if the implementation cannot add the token
+    #[cfg(feature = "biome-credentials-reset")]
+    fn add_password_reset_token(
+        &self,
+        token: PasswordResetToken,
+    ) -> Result<(), CredentialsStoreError>;
+
+    /// Removes and returns the password reset token with the given hash. A token can only be
+    /// taken once.
+    ///
+    /// # Arguments
+    ///
+    ///  * `token_hash` - The hash of the password reset token
+    ///
+    /// # Errors
+    ///
+    /// Returns a CredentialsStoreError if implementation cannot remove the token or if the token
+    /// cannot be found
+    #[cfg(feature = "biome-credentials-reset")]
+    fn take_password_reset_token(
+        &self,
+        token_hash: &str,
+    ) -> Result<PasswordResetToken, CredentialsStoreError>;
+
+    /// Fetches the TOTP secret for a user, if the user has one
+    ///
+    /// # Arguments
+    ///
+    ///  * `user_id` - The unique identifier of the user the secret belongs to
+    ///
+    /// # Errors
+    ///
+    /// Returns a CredentialsStoreError if implementation cannot fetch the secret
+    #[cfg(feature = "biome-credentials-totp")]
+    fn fetch_totp_secret(&self, user_id: &str)
+        -> Result<Option<TotpSecret>, CredentialsStoreError>;
+
+    /// Sets the TOTP secret for a user, replacing any existing secret
+    ///
+    /// # Arguments
+    ///
+    ///  * `secret` - The TOTP secret to be set
+    ///
+    /// # Errors
+    ///
+    /// Returns a CredentialsStoreError if implementation cannot set the secret
+    #[cfg(feature = "biome-credentials-totp")]
+    fn set_totp_secret(&self, secret: TotpSecret) -> Result<(), CredentialsStoreError>;
+
+    /// Removes the TOTP secret for a user
+    ///
+    /// # Arguments
+    ///
+    ///  * `user_id` - The unique identifier of the user the secret belongs to
+    ///
+    /// # Errors
+    ///
+    /// Returns a CredentialsStoreError if implementation cannot remove the secret or if the
+    /// user does not have a secret
+    #[cfg(feature = "biome-credentials-totp")]
+    fn remove_totp_secret(&self, user_id: &str) -> Result<(), CredentialsStoreError>;
 }
 
 impl<CS> CredentialsStore for Box<CS>
@@ -288,6 +384,40 @@ where
     fn list_usernames(&self) -> Result<Vec<UsernameId>, CredentialsStoreError> {
         (**self).list_usernames()
     }
+
+    #[cfg(feature = "biome-credentials-reset")]
+    fn add_password_reset_token(
+        &self,
+        token: PasswordResetToken,
+    ) -> Result<(), CredentialsStoreError> {
+        (**self).add_password_reset_token(token)
+    }
+
+    #[cfg(feature = "biome-credentials-reset")]
+    fn take_password_reset_token(
+        &self,
+        token_hash: &str,
+    ) -> Result<PasswordResetToken, CredentialsStoreError> {
+        (**self).take_password_reset_token(token_hash)
+    }
+
+    #[cfg(feature = "biome-credentials-totp")]
+    fn fetch_totp_secret(
+        &self,
+        user_id: &str,
+    ) -> Result<Option<TotpSecret>, CredentialsStoreError> {
+        (**self).fetch_totp_secret(user_id)
+    }
+
+    #[cfg(feature = "biome-credentials-totp")]
+    fn set_totp_secret(&self, secret: TotpSecret) -> Result<(), CredentialsStoreError> {
+        (**self).set_totp_secret(secret)
+    }
+
+    #[cfg(feature = "biome-credentials-totp")]
+    fn remove_totp_secret(&self, user_id: &str) -> Result<(), CredentialsStoreError> {
+        (**self).remove_totp_secret(user_id)
+    }
 }
 
 #[cfg(feature = "diesel")]
@@ -0,0 +1,237 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Time-based one-time passwords (RFC 6238), used as a second factor for Biome credentials.
+//!
+//! Codes are six digits, computed with HMAC-SHA1 over 30 second time steps, which is what common
+//! authenticator apps expect. A code from the previous or the next time step is also accepted to
+//! allow for clock drift.
+
+use openssl::hash::MessageDigest;
+use openssl::pkey::PKey;
+use openssl::rand::rand_bytes;
+use openssl::sign::Signer;
+
+use crate::error::InternalError;
+
+const SECRET_LEN: usize = 20;
+const TIME_STEP_SECS: u64 = 30;
+const CODE_DIGITS: u32 = 6;
+// The number of time steps before and after the current step for which a code is accepted
+const ALLOWED_DRIFT_STEPS: u64 = 1;
+
+const BASE32_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Generates a new random TOTP secret.
+pub fn generate_secret() -> Result<Vec<u8>, InternalError> {
+    let mut secret = vec![0u8; SECRET_LEN];
+    rand_bytes(&mut secret).map_err(|err| InternalError::from_source(Box::new(err)))?;
+    Ok(secret)
+}
+
+/// Returns the `otpauth://` URI for the secret, which authenticator apps accept (usually as a QR
+/// code) to enroll the secret.
+///
+/// # Arguments
+///
+/// * `issuer` - The name of the service the code is for
+/// * `account` - The name of the account the code is for, typically the username
+/// * `secret` - The TOTP secret
+pub fn provisioning_uri(issuer: &str, account: &str, secret: &[u8]) -> String {
+    format!(
+        "otpauth://totp/{}:{}?secret={}&issuer={}&algorithm=SHA1&digits={}&period={}",
+        percent_encode(issuer),
+        percent_encode(account),
+        encode_secret(secret),
+        percent_encode(issuer),
+        CODE_DIGITS,
+        TIME_STEP_SECS,
+    )
+}
+
+/// Verifies a code against the secret at the given time.
+///
+/// Returns the time step the code was accepted for, or `None` if the code is not valid. A code is
+/// not accepted for a time step at or before `last_used_step`, so that each code can only be used
+/// once; the returned step should be stored as the new `last_used_step`.
+///
+/// # Arguments
+///
+/// * `secret` - The TOTP secret
+/// * `code` - The code provided by the user
+/// * `unix_time` - The current time, in seconds since the Unix epoch
+/// * `last_used_step` - The last time step a code was accepted for, if any
+pub fn verify_code(
+    secret: &[u8],
+    code: &str,
+    unix_time: u64,
+    last_used_step: Option<i64>,
+) -> Result<Option<i64>, InternalError> {
+    let code = code.trim();
+    if code.len() != CODE_DIGITS as usize || !code.bytes().all(|b| b.is_ascii_digit()) {
+        return Ok(None);
+    }
+
+    let current_step = unix_time / TIME_STEP_SECS;
+    let first_step = current_step.saturating_sub(ALLOWED_DRIFT_STEPS);
+    for step in first_step..=current_step + ALLOWED_DRIFT_STEPS {
+        if let Some(last_used_step) = last_used_step {
+            if step as i64 <= last_used_step {
+                continue;
+            }
+        }
+        if openssl::memcmp::eq(generate_code(secret, step)?.as_bytes(), code.as_bytes()) {
+            return Ok(Some(step as i64));
+        }
+    }
+
+    Ok(None)
+}
+
+fn generate_code(secret: &[u8], step: u64) -> Result<String, InternalError> {
+    let key = PKey::hmac(secret).map_err(|err| InternalError::from_source(Box::new(err)))?;
+    let mut signer = Signer::new(MessageDigest::sha1(), &key)
+        .map_err(|err| InternalError::from_source(Box::new(err)))?;
+    signer
+        .update(&step.to_be_bytes())
+        .map_err(|err| InternalError::from_source(Box::new(err)))?;
+    let hmac = signer
+        .sign_to_vec()
+        .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+    // Dynamic truncation, as defined by RFC 4226
+    let offset = (hmac[hmac.len() - 1] & 0x0f) as usize;
+    let binary = (u32::from(hmac[offset]) & 0x7f) << 24
+        | u32::from(hmac[offset + 1]) << 16
+        | u32::from(hmac[offset + 2]) << 8
+        | u32::from(hmac[offset + 3]);
+
+    Ok(format!(
+        "{:0width$}",
+        binary % 10u32.pow(CODE_DIGITS),
+        width = CODE_DIGITS as usize
+    ))
+}
+
+/// Encodes the secret as unpadded base32 (RFC 4648), the form authenticator apps accept when a
+/// secret is entered manually.
+pub fn encode_secret(secret: &[u8]) -> String {
+    let mut encoded = String::with_capacity((secret.len() * 8 + 4) / 5);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for byte in secret {
+        buffer = (buffer << 8) | u32::from(*byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            encoded.push(BASE32_ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        encoded.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+    encoded
+}
+
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The SHA1 secret used by the RFC 6238 test vectors
+    const RFC_SECRET: &[u8] = b"12345678901234567890";
+
+    /// Verify that generated codes match the RFC 6238 test vectors, truncated to six digits.
+    #[test]
+    fn test_generate_code_rfc_vectors() {
+        assert_eq!(
+            "287082",
+            generate_code(RFC_SECRET, 59 / TIME_STEP_SECS).unwrap()
+        );
+        assert_eq!(
+            "081804",
+            generate_code(RFC_SECRET, 1111111109 / TIME_STEP_SECS).unwrap()
+        );
+        assert_eq!(
+            "050471",
+            generate_code(RFC_SECRET, 1111111111 / TIME_STEP_SECS).unwrap()
+        );
+        assert_eq!(
+            "005924",
+            generate_code(RFC_SECRET, 1234567890 / TIME_STEP_SECS).unwrap()
+        );
+    }
+
+    /// Verify that a code is accepted within the allowed drift, rejected outside of it, and can
+    /// not be reused once accepted.
+    #[test]
+    fn test_verify_code() {
+        let now = 1111111109;
+        let step = (now / TIME_STEP_SECS) as i64;
+
+        assert_eq!(
+            Some(step),
+            verify_code(RFC_SECRET, "081804", now, None).unwrap()
+        );
+        assert_eq!(
+            Some(step),
+            verify_code(RFC_SECRET, "081804", now + TIME_STEP_SECS, None).unwrap()
+        );
+        assert_eq!(
+            None,
+            verify_code(RFC_SECRET, "081804", now + 2 * TIME_STEP_SECS, None).unwrap()
+        );
+        assert_eq!(
+            None,
+            verify_code(RFC_SECRET, "081804", now, Some(step)).unwrap()
+        );
+        assert_eq!(
+            None,
+            verify_code(RFC_SECRET, "not a code", now, None).unwrap()
+        );
+    }
+
+    /// Verify that base32 encoding matches the RFC 4648 test vectors, without padding.
+    #[test]
+    fn test_encode_secret() {
+        assert_eq!("", encode_secret(b""));
+        assert_eq!("MY", encode_secret(b"f"));
+        assert_eq!("MZXQ", encode_secret(b"fo"));
+        assert_eq!("MZXW6", encode_secret(b"foo"));
+        assert_eq!("MZXW6YQ", encode_secret(b"foob"));
+        assert_eq!("MZXW6YTB", encode_secret(b"fooba"));
+        assert_eq!("MZXW6YTBOI", encode_secret(b"foobar"));
+    }
+
+    /// Verify that the provisioning URI encodes the issuer and account, and includes the secret.
+    #[test]
+    fn test_provisioning_uri() {
+        assert_eq!(
+            "otpauth://totp/Splinter:alice%40example.com?secret=MZXW6YTBOI&issuer=Splinter\
+             &algorithm=SHA1&digits=6&period=30",
+            provisioning_uri("Splinter", "alice@example.com", b"foobar")
+        );
+    }
+}
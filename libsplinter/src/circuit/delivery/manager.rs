@@ -0,0 +1,755 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The background thread that delivers stored circuit messages once nodes reconnect.
+
+use std::collections::HashSet;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::circuit::routing::RoutingTableReader;
+use crate::error::{InternalError, InvalidStateError};
+use crate::network::dispatch::{MessageSender, PeerId};
+use crate::peer::{PeerManagerConnector, PeerManagerNotification, PeerTokenPair, SubscriberId};
+use crate::threading::{lifecycle::ShutdownHandle, pacemaker::Pacemaker};
+
+use super::{GuaranteedDeliveryQueue, PendingMessageStore, DEFAULT_MESSAGE_TTL};
+
+const DEFAULT_RETRY_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Messages handled by the delivery thread
+pub(super) enum DeliveryMessage {
+    PeerNotification(PeerManagerNotification),
+    /// A message was stored for a peer that is connected
+    Queued,
+    Retry,
+    Shutdown,
+}
+
+impl From<PeerManagerNotification> for DeliveryMessage {
+    fn from(notification: PeerManagerNotification) -> Self {
+        DeliveryMessage::PeerNotification(notification)
+    }
+}
+
+/// Builds and starts a [`GuaranteedDeliveryManager`]
+#[derive(Default)]
+pub struct GuaranteedDeliveryManagerBuilder {
+    node_id: Option<String>,
+    store: Option<Box<dyn PendingMessageStore>>,
+    routing_table: Option<Box<dyn RoutingTableReader>>,
+    peer_connector: Option<PeerManagerConnector>,
+    message_sender: Option<Box<dyn MessageSender<PeerId>>>,
+    message_ttl: Option<Duration>,
+    retry_interval: Option<Duration>,
+}
+
+impl GuaranteedDeliveryManagerBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the ID of the local node
+    pub fn with_node_id(mut self, node_id: String) -> Self {
+        self.node_id = Some(node_id);
+        self
+    }
+
+    /// Sets the store used to persist undelivered messages
+    pub fn with_store(mut self, store: Box<dyn PendingMessageStore>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Sets the routing table used to look up the peer for a stored message
+    pub fn with_routing_table(mut self, routing_table: Box<dyn RoutingTableReader>) -> Self {
+        self.routing_table = Some(routing_table);
+        self
+    }
+
+    /// Sets the peer connector used to subscribe to peer connection notifications
+    pub fn with_peer_connector(mut self, peer_connector: PeerManagerConnector) -> Self {
+        self.peer_connector = Some(peer_connector);
+        self
+    }
+
+    /// Sets the sender used to deliver stored messages
+    pub fn with_message_sender(mut self, message_sender: Box<dyn MessageSender<PeerId>>) -> Self {
+        self.message_sender = Some(message_sender);
+        self
+    }
+
+    /// Sets how long an undelivered message is kept before it is discarded. Defaults to 24
+    /// hours.
+    pub fn with_message_ttl(mut self, message_ttl: Duration) -> Self {
+        self.message_ttl = Some(message_ttl);
+        self
+    }
+
+    /// Sets how often stored messages are retried and expired messages are removed. Defaults to
+    /// 30 seconds.
+    pub fn with_retry_interval(mut self, retry_interval: Duration) -> Self {
+        self.retry_interval = Some(retry_interval);
+        self
+    }
+
+    /// Starts the `GuaranteedDeliveryManager`
+    ///
+    /// Returns an error if a required field was not set or the delivery thread could not be
+    /// started.
+    pub fn start(self) -> Result<GuaranteedDeliveryManager, InternalError> {
+        let node_id = self.node_id.ok_or_else(|| missing_field("node_id"))?;
+        let store = self.store.ok_or_else(|| missing_field("store"))?;
+        let routing_table = self
+            .routing_table
+            .ok_or_else(|| missing_field("routing_table"))?;
+        let peer_connector = self
+            .peer_connector
+            .ok_or_else(|| missing_field("peer_connector"))?;
+        let message_sender = self
+            .message_sender
+            .ok_or_else(|| missing_field("message_sender"))?;
+        let message_ttl = self.message_ttl.unwrap_or(DEFAULT_MESSAGE_TTL);
+        let retry_interval = self.retry_interval.unwrap_or(DEFAULT_RETRY_INTERVAL);
+
+        let connected_peers: HashSet<PeerTokenPair> = peer_connector
+            .connection_ids()
+            .map_err(|err| InternalError::from_source(Box::new(err)))?
+            .keys()
+            .cloned()
+            .collect();
+        let connected_peers = Arc::new(Mutex::new(connected_peers));
+
+        let (sender, receiver) = channel();
+        let subscriber_id = peer_connector
+            .subscribe_sender(sender.clone())
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        let pacemaker = Pacemaker::builder()
+            .with_interval(retry_interval.as_secs())
+            .with_sender(sender.clone())
+            .with_message_factory(|| DeliveryMessage::Retry)
+            .start()
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        let delivery = Delivery {
+            node_id,
+            store: store.clone(),
+            routing_table,
+            message_sender,
+            connected_peers: connected_peers.clone(),
+        };
+
+        let join_handle = thread::Builder::new()
+            .name("GuaranteedDeliveryManager".into())
+            .spawn(move || delivery.run(receiver))
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(GuaranteedDeliveryManager {
+            queue: GuaranteedDeliveryQueue::new(
+                store,
+                connected_peers,
+                message_ttl,
+                Some(sender.clone()),
+            ),
+            pacemaker,
+            sender,
+            join_handle,
+            peer_connector,
+            subscriber_id,
+        })
+    }
+}
+
+fn missing_field(field: &str) -> InternalError {
+    InternalError::from_source(Box::new(InvalidStateError::with_message(format!(
+        "unable to build, missing field: `{}`",
+        field
+    ))))
+}
+
+/// Delivers circuit messages that were stored while the recipient node was not connected.
+///
+/// Stored messages are sent when the peer manager notifies that a peer has connected. The stored
+/// messages are also periodically retried for connected peers and removed once their
+/// time-to-live has passed.
+pub struct GuaranteedDeliveryManager {
+    queue: GuaranteedDeliveryQueue,
+    pacemaker: Pacemaker,
+    sender: Sender<DeliveryMessage>,
+    join_handle: thread::JoinHandle<()>,
+    peer_connector: PeerManagerConnector,
+    subscriber_id: SubscriberId,
+}
+
+impl GuaranteedDeliveryManager {
+    pub fn builder() -> GuaranteedDeliveryManagerBuilder {
+        GuaranteedDeliveryManagerBuilder::new()
+    }
+
+    /// Returns a queue that can be used to store messages for disconnected nodes
+    pub fn queue(&self) -> GuaranteedDeliveryQueue {
+        self.queue.clone()
+    }
+}
+
+impl ShutdownHandle for GuaranteedDeliveryManager {
+    fn signal_shutdown(&mut self) {
+        if let Err(err) = self.peer_connector.unsubscribe(self.subscriber_id) {
+            warn!(
+                "Unable to unsubscribe guaranteed delivery manager from peer manager: {}",
+                err
+            );
+        }
+        self.pacemaker.shutdown_signaler().shutdown();
+        if self.sender.send(DeliveryMessage::Shutdown).is_err() {
+            warn!("Guaranteed delivery manager is no longer running");
+        }
+    }
+
+    fn wait_for_shutdown(self) -> Result<(), InternalError> {
+        debug!("Shutting down guaranteed delivery manager...");
+        self.pacemaker.await_shutdown();
+        self.join_handle.join().map_err(|err| {
+            InternalError::with_message(format!(
+                "Guaranteed delivery manager thread did not shutdown correctly: {:?}",
+                err
+            ))
+        })?;
+        debug!("Shutting down guaranteed delivery manager (complete)");
+        Ok(())
+    }
+}
+
+/// The state owned by the delivery thread
+struct Delivery {
+    node_id: String,
+    store: Box<dyn PendingMessageStore>,
+    routing_table: Box<dyn RoutingTableReader>,
+    message_sender: Box<dyn MessageSender<PeerId>>,
+    connected_peers: Arc<Mutex<HashSet<PeerTokenPair>>>,
+}
+
+impl Delivery {
+    fn run(self, receiver: Receiver<DeliveryMessage>) {
+        loop {
+            let result = match receiver.recv() {
+                Ok(DeliveryMessage::PeerNotification(PeerManagerNotification::Connected {
+                    peer,
+                })) => self
+                    .set_connected(peer.clone(), true)
+                    .and_then(|_| self.deliver(Some(&peer))),
+                Ok(DeliveryMessage::PeerNotification(PeerManagerNotification::Disconnected {
+                    peer,
+                })) => self.set_connected(peer, false),
+                Ok(DeliveryMessage::Queued) => self.deliver(None),
+                Ok(DeliveryMessage::Retry) => {
+                    self.remove_expired().and_then(|_| self.deliver(None))
+                }
+                Ok(DeliveryMessage::Shutdown) => break,
+                Err(_) => {
+                    warn!(
+                        "Guaranteed delivery manager channel dropped, indicating remote thread \
+                        has shutdown"
+                    );
+                    break;
+                }
+            };
+
+            if let Err(err) = result {
+                error!("Unable to deliver stored circuit messages: {}", err);
+            }
+        }
+    }
+
+    fn set_connected(&self, peer: PeerTokenPair, connected: bool) -> Result<(), InternalError> {
+        let mut connected_peers = self
+            .connected_peers
+            .lock()
+            .map_err(|_| InternalError::with_message("Connected peers lock poisoned".into()))?;
+
+        if connected {
+            connected_peers.insert(peer);
+        } else {
+            connected_peers.remove(&peer);
+        }
+
+        Ok(())
+    }
+
+    fn remove_expired(&self) -> Result<(), InternalError> {
+        let removed = self
+            .store
+            .remove_expired_messages(SystemTime::now())
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        if removed > 0 {
+            warn!("Discarded {} expired undelivered circuit messages", removed);
+        }
+
+        Ok(())
+    }
+
+    /// Sends the stored messages for connected nodes. If `only_peer` is provided, only messages
+    /// for that peer are sent.
+    fn deliver(&self, only_peer: Option<&PeerTokenPair>) -> Result<(), InternalError> {
+        let recipients = self
+            .store
+            .list_recipients()
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        for recipient in recipients {
+            let messages = self
+                .store
+                .list_messages(&recipient)
+                .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+            for message in messages {
+                if message.expires_at() <= SystemTime::now() {
+                    continue;
+                }
+
+                let peer = match self.peer_for_message(message.circuit_id(), &recipient)? {
+                    Some(peer) => peer,
+                    None => {
+                        // the circuit or the node no longer exists, so the message can never be
+                        // delivered
+                        self.remove_message(message.id())?;
+                        continue;
+                    }
+                };
+
+                if let Some(only_peer) = only_peer {
+                    if &peer != only_peer {
+                        continue;
+                    }
+                } else if !self
+                    .connected_peers
+                    .lock()
+                    .map_err(|_| {
+                        InternalError::with_message("Connected peers lock poisoned".into())
+                    })?
+                    .contains(&peer)
+                {
+                    continue;
+                }
+
+                match self
+                    .message_sender
+                    .send(peer.into(), message.payload().to_vec())
+                {
+                    Ok(()) => {
+                        debug!(
+                            "Delivered stored message on circuit {} to node {}",
+                            message.circuit_id(),
+                            recipient
+                        );
+                        self.remove_message(message.id())?;
+                    }
+                    Err(_) => {
+                        // stop sending to this node so that messages are delivered in order
+                        warn!("Unable to deliver stored message to node {}", recipient);
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn remove_message(&self, id: i64) -> Result<(), InternalError> {
+        self.store
+            .remove_message(id)
+            .map_err(|err| InternalError::from_source(Box::new(err)))
+    }
+
+    /// Returns the peer token pair that should be used to send a message on the given circuit to
+    /// the given node, or `None` if the circuit or the node no longer exists.
+    fn peer_for_message(
+        &self,
+        circuit_id: &str,
+        node_id: &str,
+    ) -> Result<Option<PeerTokenPair>, InternalError> {
+        let circuit = match self
+            .routing_table
+            .get_circuit(circuit_id)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?
+        {
+            Some(circuit) => circuit,
+            None => return Ok(None),
+        };
+
+        let peer_id = match self
+            .routing_table
+            .get_node(node_id)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?
+        {
+            Some(node) => node
+                .get_peer_auth_token(circuit.authorization_type())
+                .map_err(|err| InternalError::from_source(Box::new(err)))?,
+            None => {
+                warn!(
+                    "Discarding stored message on circuit {}: node {} not in routing table",
+                    circuit_id, node_id
+                );
+                return Ok(None);
+            }
+        };
+
+        let local_id = self
+            .routing_table
+            .get_node(&self.node_id)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?
+            .ok_or_else(|| {
+                InternalError::with_message(format!(
+                    "Local node {} not in routing table",
+                    self.node_id
+                ))
+            })?
+            .get_peer_auth_token(circuit.authorization_type())
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(Some(PeerTokenPair::new(peer_id, local_id)))
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+
+    use std::collections::VecDeque;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use diesel::{
+        r2d2::{ConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+
+    use crate::circuit::delivery::{DieselPendingMessageStore, InsertablePendingMessage};
+    use crate::circuit::routing::{
+        memory::RoutingTable, AuthorizationType, Circuit, CircuitNode, RoutingTableWriter,
+    };
+    use crate::migrations::run_sqlite_migrations;
+    use crate::peer::PeerAuthorizationToken;
+
+    /// Verify that stored messages are delivered, in order, when the recipient node connects:
+    ///
+    /// 1. Store two messages for node "345", which is not connected
+    /// 2. Notify the delivery thread that "345" has connected
+    /// 3. Verify both messages were sent to "345" in the order they were stored, and that they
+    ///    were removed from the store
+    #[test]
+    fn delivers_messages_on_connect() {
+        let store = DieselPendingMessageStore::new(create_connection_pool_and_migrate());
+        let sender = MockSender::default();
+
+        add_message(&store, b"first", Duration::from_secs(60));
+        add_message(&store, b"second", Duration::from_secs(60));
+
+        let (tx, join_handle) = start_delivery(&store, &sender, HashSet::new());
+        tx.send(DeliveryMessage::PeerNotification(
+            PeerManagerNotification::Connected { peer: peer_345() },
+        ))
+        .expect("Unable to send notification");
+        shutdown(tx, join_handle);
+
+        assert_eq!(
+            sender.sent(),
+            vec![
+                (PeerId::from(peer_345()), b"first".to_vec()),
+                (PeerId::from(peer_345()), b"second".to_vec()),
+            ]
+        );
+        assert!(store
+            .list_messages("345")
+            .expect("Unable to list messages")
+            .is_empty());
+    }
+
+    /// Verify that messages that could not be delivered are redelivered once the node reconnects:
+    ///
+    /// 1. Store a message for node "345" and have the sender fail
+    /// 2. Notify the delivery thread that "345" has connected, and verify the message is kept
+    /// 3. Notify the delivery thread that "345" has disconnected and reconnected, with the sender
+    ///    no longer failing
+    /// 4. Verify the message was sent and removed from the store
+    #[test]
+    fn redelivers_messages_on_reconnect() {
+        let store = DieselPendingMessageStore::new(create_connection_pool_and_migrate());
+        let sender = MockSender::default();
+        sender.set_failing(true);
+
+        add_message(&store, b"hello", Duration::from_secs(60));
+
+        let (tx, join_handle) = start_delivery(&store, &sender, HashSet::new());
+        tx.send(DeliveryMessage::PeerNotification(
+            PeerManagerNotification::Connected { peer: peer_345() },
+        ))
+        .expect("Unable to send notification");
+        shutdown(tx, join_handle);
+
+        assert!(sender.sent().is_empty());
+        assert_eq!(
+            store
+                .list_messages("345")
+                .expect("Unable to list messages")
+                .len(),
+            1
+        );
+
+        sender.set_failing(false);
+        let (tx, join_handle) = start_delivery(&store, &sender, HashSet::new());
+        tx.send(DeliveryMessage::PeerNotification(
+            PeerManagerNotification::Disconnected { peer: peer_345() },
+        ))
+        .expect("Unable to send notification");
+        tx.send(DeliveryMessage::PeerNotification(
+            PeerManagerNotification::Connected { peer: peer_345() },
+        ))
+        .expect("Unable to send notification");
+        shutdown(tx, join_handle);
+
+        assert_eq!(
+            sender.sent(),
+            vec![(PeerId::from(peer_345()), b"hello".to_vec())]
+        );
+        assert!(store
+            .list_messages("345")
+            .expect("Unable to list messages")
+            .is_empty());
+    }
+
+    /// Verify that a retry removes expired messages and delivers the rest to connected nodes:
+    ///
+    /// 1. Store an expired message and a current message for node "345", which is connected
+    /// 2. Trigger a retry
+    /// 3. Verify only the current message was sent, and that the store is empty
+    #[test]
+    fn retry_removes_expired_messages() {
+        let store = DieselPendingMessageStore::new(create_connection_pool_and_migrate());
+        let sender = MockSender::default();
+
+        add_message(&store, b"expired", Duration::from_secs(0));
+        add_message(&store, b"current", Duration::from_secs(60));
+
+        let mut connected_peers = HashSet::new();
+        connected_peers.insert(peer_345());
+
+        let (tx, join_handle) = start_delivery(&store, &sender, connected_peers);
+        tx.send(DeliveryMessage::Retry)
+            .expect("Unable to send retry");
+        shutdown(tx, join_handle);
+
+        assert_eq!(
+            sender.sent(),
+            vec![(PeerId::from(peer_345()), b"current".to_vec())]
+        );
+        assert!(!store.has_messages("345").expect("Unable to check messages"));
+    }
+
+    /// Verify that messages stored for a connected node are delivered when the delivery thread is
+    /// told a message was queued, and that messages for disconnected nodes are not:
+    ///
+    /// 1. Store a message for node "345", which is connected, and one for node "567", which is not
+    /// 2. Notify the delivery thread that a message was queued
+    /// 3. Verify only the message for "345" was sent
+    #[test]
+    fn delivers_queued_messages_to_connected_nodes() {
+        let store = DieselPendingMessageStore::new(create_connection_pool_and_migrate());
+        let sender = MockSender::default();
+
+        add_message(&store, b"hello", Duration::from_secs(60));
+        store
+            .add_message(InsertablePendingMessage::new(
+                "Alpha-00000".into(),
+                "567".into(),
+                b"goodbye".to_vec(),
+                Duration::from_secs(60),
+            ))
+            .expect("Unable to add message");
+
+        let mut connected_peers = HashSet::new();
+        connected_peers.insert(peer_345());
+
+        let (tx, join_handle) = start_delivery(&store, &sender, connected_peers);
+        tx.send(DeliveryMessage::Queued)
+            .expect("Unable to send notification");
+        shutdown(tx, join_handle);
+
+        assert_eq!(
+            sender.sent(),
+            vec![(PeerId::from(peer_345()), b"hello".to_vec())]
+        );
+        assert!(store.has_messages("567").expect("Unable to check messages"));
+    }
+
+    /// Verify that a message for a node that is no longer in the routing table is discarded
+    /// without stopping the delivery of the other messages:
+    ///
+    /// 1. Store a message for node "901", which is not in the routing table, and one for node
+    ///    "345", which is connected
+    /// 2. Trigger a retry
+    /// 3. Verify the message for "345" was sent, and that both messages were removed from the
+    ///    store
+    #[test]
+    fn discards_messages_for_unknown_nodes() {
+        let store = DieselPendingMessageStore::new(create_connection_pool_and_migrate());
+        let sender = MockSender::default();
+
+        store
+            .add_message(InsertablePendingMessage::new(
+                "Alpha-00000".into(),
+                "901".into(),
+                b"unknown".to_vec(),
+                Duration::from_secs(60),
+            ))
+            .expect("Unable to add message");
+        add_message(&store, b"hello", Duration::from_secs(60));
+
+        let mut connected_peers = HashSet::new();
+        connected_peers.insert(peer_345());
+
+        let (tx, join_handle) = start_delivery(&store, &sender, connected_peers);
+        tx.send(DeliveryMessage::Retry)
+            .expect("Unable to send retry");
+        shutdown(tx, join_handle);
+
+        assert_eq!(
+            sender.sent(),
+            vec![(PeerId::from(peer_345()), b"hello".to_vec())]
+        );
+        assert!(!store.has_messages("901").expect("Unable to check messages"));
+        assert!(!store.has_messages("345").expect("Unable to check messages"));
+    }
+
+    fn peer_345() -> PeerTokenPair {
+        PeerTokenPair::new(
+            PeerAuthorizationToken::from_peer_id("345"),
+            PeerAuthorizationToken::from_peer_id("123"),
+        )
+    }
+
+    fn add_message(store: &dyn PendingMessageStore, payload: &[u8], ttl: Duration) {
+        store
+            .add_message(InsertablePendingMessage::new(
+                "Alpha-00000".into(),
+                "345".into(),
+                payload.to_vec(),
+                ttl,
+            ))
+            .expect("Unable to add message");
+    }
+
+    /// Starts the delivery thread for local node "123", on a circuit shared with nodes "345" and
+    /// "567"
+    fn start_delivery(
+        store: &dyn PendingMessageStore,
+        sender: &MockSender,
+        connected_peers: HashSet<PeerTokenPair>,
+    ) -> (Sender<DeliveryMessage>, thread::JoinHandle<()>) {
+        let table = RoutingTable::default();
+        let mut writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let circuit = Circuit::new(
+            "Alpha-00000".into(),
+            vec![],
+            vec!["123".into(), "345".into(), "567".into()],
+            AuthorizationType::Trust,
+        );
+        writer
+            .add_circuit(
+                circuit.circuit_id().into(),
+                circuit,
+                vec![
+                    CircuitNode::new("123".into(), vec!["tcp://127.0.0.1:0".into()], None),
+                    CircuitNode::new("345".into(), vec!["tcp://127.0.0.1:1".into()], None),
+                    CircuitNode::new("567".into(), vec!["tcp://127.0.0.1:2".into()], None),
+                ],
+            )
+            .expect("Unable to add circuit");
+
+        let delivery = Delivery {
+            node_id: "123".into(),
+            store: store.clone_box(),
+            routing_table: Box::new(table),
+            message_sender: Box::new(sender.clone()),
+            connected_peers: Arc::new(Mutex::new(connected_peers)),
+        };
+
+        let (tx, rx) = channel();
+        let join_handle = thread::spawn(move || delivery.run(rx));
+
+        (tx, join_handle)
+    }
+
+    fn shutdown(tx: Sender<DeliveryMessage>, join_handle: thread::JoinHandle<()>) {
+        tx.send(DeliveryMessage::Shutdown)
+            .expect("Unable to send shutdown");
+        join_handle.join().expect("Unable to join delivery thread");
+    }
+
+    #[derive(Clone, Default)]
+    struct MockSender {
+        sent: Arc<Mutex<VecDeque<(PeerId, Vec<u8>)>>>,
+        failing: Arc<AtomicBool>,
+    }
+
+    impl MockSender {
+        fn set_failing(&self, failing: bool) {
+            self.failing.store(failing, Ordering::SeqCst);
+        }
+
+        fn sent(&self) -> Vec<(PeerId, Vec<u8>)> {
+            self.sent
+                .lock()
+                .expect("lock was poisoned")
+                .drain(..)
+                .collect()
+        }
+    }
+
+    impl MessageSender<PeerId> for MockSender {
+        fn send(&self, id: PeerId, message: Vec<u8>) -> Result<(), (PeerId, Vec<u8>)> {
+            if self.failing.load(Ordering::SeqCst) {
+                return Err((id, message));
+            }
+
+            self.sent
+                .lock()
+                .expect("lock was poisoned")
+                .push_back((id, message));
+
+            Ok(())
+        }
+    }
+
+    /// Creates a connection pool for an in-memory SQLite database with only a single connection
+    /// available. Each connection is backed by a different in-memory SQLite database, so limiting
+    /// the pool to a single connection ensures that the same DB is used for all operations.
+    fn create_connection_pool_and_migrate() -> Pool<ConnectionManager<SqliteConnection>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        pool
+    }
+}
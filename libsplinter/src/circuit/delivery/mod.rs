@@ -0,0 +1,374 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Guaranteed delivery of circuit messages to remote nodes.
+//!
+//! Circuits with a `GuaranteedDelivery` durability do not drop direct messages when the
+//! recipient's node is not connected. Instead, the [`CircuitDirectMessageHandler`] sends the
+//! message through a [`GuaranteedDeliveryQueue`], which persists it in a [`PendingMessageStore`]
+//! if the node is not connected, if the message could not be sent, or if earlier messages for the
+//! node are still waiting to be delivered. The [`GuaranteedDeliveryManager`] resends the stored
+//! messages, in order, once the peer manager reports that the node has reconnected. Messages
+//! that are not delivered before their time-to-live has passed are discarded.
+//!
+//! [`CircuitDirectMessageHandler`]: ../handlers/struct.CircuitDirectMessageHandler.html
+
+mod manager;
+pub mod store;
+
+pub use manager::{GuaranteedDeliveryManager, GuaranteedDeliveryManagerBuilder};
+#[cfg(feature = "diesel")]
+pub use store::diesel::DieselPendingMessageStore;
+pub use store::{
+    InsertablePendingMessage, PendingMessage, PendingMessageStore, PendingMessageStoreError,
+};
+
+use std::collections::HashSet;
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::error::InternalError;
+use crate::network::dispatch::{MessageSender, PeerId};
+use crate::peer::PeerTokenPair;
+
+use manager::DeliveryMessage;
+
+/// The default amount of time an undelivered message is kept before it is discarded
+pub const DEFAULT_MESSAGE_TTL: Duration = Duration::from_secs(60 * 60 * 24);
+
+/// A handle used to send circuit messages to remote nodes, queueing the messages that cannot be
+/// sent right away.
+///
+/// The set of connected peers is kept up to date by the [`GuaranteedDeliveryManager`] that
+/// created the queue.
+#[derive(Clone)]
+pub struct GuaranteedDeliveryQueue {
+    store: Box<dyn PendingMessageStore>,
+    connected_peers: Arc<Mutex<HashSet<PeerTokenPair>>>,
+    ttl: Duration,
+    // Used to ask the manager to deliver messages that were queued for a connected peer
+    delivery_sender: Option<Sender<DeliveryMessage>>,
+}
+
+impl GuaranteedDeliveryQueue {
+    fn new(
+        store: Box<dyn PendingMessageStore>,
+        connected_peers: Arc<Mutex<HashSet<PeerTokenPair>>>,
+        ttl: Duration,
+        delivery_sender: Option<Sender<DeliveryMessage>>,
+    ) -> Self {
+        Self {
+            store,
+            connected_peers,
+            ttl,
+            delivery_sender,
+        }
+    }
+
+    /// Sends a message to a remote node, or stores it to be delivered later.
+    ///
+    /// The message is sent right away only if the peer is connected and no earlier messages for
+    /// the node are waiting to be delivered, so that messages are delivered in order. If the
+    /// message cannot be sent, it is stored instead of being dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit_id` - The ID of the circuit the message was sent on
+    /// * `recipient_node_id` - The ID of the node the message should be delivered to
+    /// * `peer` - The peer token pair used to send messages to the node
+    /// * `payload` - The network message bytes to be delivered
+    /// * `sender` - The sender used to send the message right away
+    pub fn send(
+        &self,
+        circuit_id: &str,
+        recipient_node_id: &str,
+        peer: &PeerTokenPair,
+        payload: Vec<u8>,
+        sender: &dyn MessageSender<PeerId>,
+    ) -> Result<(), InternalError> {
+        if !self.is_connected(peer)? {
+            debug!(
+                "Node {} is not connected, storing message on {}",
+                recipient_node_id, circuit_id
+            );
+            return self.enqueue(circuit_id, recipient_node_id, payload);
+        }
+
+        let payload = if self.has_messages(recipient_node_id)? {
+            debug!(
+                "Node {} has undelivered messages, storing message on {}",
+                recipient_node_id, circuit_id
+            );
+            payload
+        } else {
+            match sender.send(peer.clone().into(), payload) {
+                Ok(()) => return Ok(()),
+                Err((_, payload)) => {
+                    debug!(
+                        "Unable to send message to node {}, storing message on {}",
+                        recipient_node_id, circuit_id
+                    );
+                    payload
+                }
+            }
+        };
+
+        self.enqueue(circuit_id, recipient_node_id, payload)?;
+
+        // The peer is connected, so the manager will not be told to deliver the message when the
+        // peer connects
+        if let Some(delivery_sender) = &self.delivery_sender {
+            if delivery_sender.send(DeliveryMessage::Queued).is_err() {
+                warn!("Guaranteed delivery manager is no longer running");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns whether or not the given peer is currently connected
+    ///
+    /// # Arguments
+    ///
+    /// * `peer` - The peer token pair of the remote node
+    pub fn is_connected(&self, peer: &PeerTokenPair) -> Result<bool, InternalError> {
+        Ok(self
+            .connected_peers
+            .lock()
+            .map_err(|_| InternalError::with_message("Connected peers lock poisoned".into()))?
+            .contains(peer))
+    }
+
+    /// Returns whether or not the given node has messages waiting to be delivered
+    ///
+    /// # Arguments
+    ///
+    /// * `recipient_node_id` - The ID of the node the messages should be delivered to
+    pub fn has_messages(&self, recipient_node_id: &str) -> Result<bool, InternalError> {
+        self.store
+            .has_messages(recipient_node_id)
+            .map_err(|err| InternalError::from_source(Box::new(err)))
+    }
+
+    /// Stores a message so that it can be delivered once the recipient node reconnects
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit_id` - The ID of the circuit the message was sent on
+    /// * `recipient_node_id` - The ID of the node the message should be delivered to
+    /// * `payload` - The network message bytes to be delivered
+    pub fn enqueue(
+        &self,
+        circuit_id: &str,
+        recipient_node_id: &str,
+        payload: Vec<u8>,
+    ) -> Result<(), InternalError> {
+        self.store
+            .add_message(InsertablePendingMessage::new(
+                circuit_id.to_string(),
+                recipient_node_id.to_string(),
+                payload,
+                self.ttl,
+            ))
+            .map_err(|err| InternalError::from_source(Box::new(err)))
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::mpsc::channel;
+
+    use diesel::{
+        r2d2::{ConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+
+    use crate::migrations::run_sqlite_migrations;
+    use crate::peer::PeerAuthorizationToken;
+
+    /// Verify that the queue reports connected peers and stores messages for disconnected nodes:
+    ///
+    /// 1. Create a queue where only node "acme" is connected
+    /// 2. Verify that "acme" is connected and "bubba" is not
+    /// 3. Enqueue a message for "bubba" and verify it is in the store
+    #[test]
+    fn queue_stores_messages_for_disconnected_nodes() {
+        let store = DieselPendingMessageStore::new(create_connection_pool_and_migrate());
+
+        let acme = PeerTokenPair::new(
+            PeerAuthorizationToken::from_peer_id("acme"),
+            PeerAuthorizationToken::from_peer_id("local"),
+        );
+        let bubba = PeerTokenPair::new(
+            PeerAuthorizationToken::from_peer_id("bubba"),
+            PeerAuthorizationToken::from_peer_id("local"),
+        );
+
+        let mut connected_peers = HashSet::new();
+        connected_peers.insert(acme.clone());
+
+        let queue = GuaranteedDeliveryQueue::new(
+            store.clone_box(),
+            Arc::new(Mutex::new(connected_peers)),
+            DEFAULT_MESSAGE_TTL,
+            None,
+        );
+
+        assert!(queue.is_connected(&acme).expect("Unable to check peer"));
+        assert!(!queue.is_connected(&bubba).expect("Unable to check peer"));
+
+        queue
+            .enqueue("abcde-01234", "bubba", b"hello".to_vec())
+            .expect("Unable to enqueue message");
+
+        let messages = store
+            .list_messages("bubba")
+            .expect("Unable to list messages");
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].circuit_id(), "abcde-01234");
+        assert_eq!(messages[0].payload(), b"hello");
+    }
+
+    /// Verify that the queue only sends messages right away when the node is connected and has
+    /// no earlier messages waiting, and stores them otherwise:
+    ///
+    /// 1. Create a queue where only node "acme" is connected
+    /// 2. Send a message to "acme" and verify it was sent
+    /// 3. Send a message to "acme" while the sender is failing, and verify it was stored and the
+    ///    manager was told to deliver it
+    /// 4. Send another message to "acme" and verify it was stored after the first, rather than
+    ///    sent ahead of it
+    /// 5. Send a message to "bubba" and verify it was stored
+    #[test]
+    fn queue_sends_or_stores_messages_in_order() {
+        let store = DieselPendingMessageStore::new(create_connection_pool_and_migrate());
+        let sender = MockSender::default();
+
+        let acme = PeerTokenPair::new(
+            PeerAuthorizationToken::from_peer_id("acme"),
+            PeerAuthorizationToken::from_peer_id("local"),
+        );
+        let bubba = PeerTokenPair::new(
+            PeerAuthorizationToken::from_peer_id("bubba"),
+            PeerAuthorizationToken::from_peer_id("local"),
+        );
+
+        let mut connected_peers = HashSet::new();
+        connected_peers.insert(acme.clone());
+
+        let (tx, rx) = channel();
+        let queue = GuaranteedDeliveryQueue::new(
+            store.clone_box(),
+            Arc::new(Mutex::new(connected_peers)),
+            DEFAULT_MESSAGE_TTL,
+            Some(tx),
+        );
+
+        queue
+            .send("abcde-01234", "acme", &acme, b"sent".to_vec(), &sender)
+            .expect("Unable to send message");
+        assert_eq!(
+            sender.sent(),
+            vec![(PeerId::from(acme.clone()), b"sent".to_vec())]
+        );
+        assert!(!store
+            .has_messages("acme")
+            .expect("Unable to check messages"));
+
+        sender.set_failing(true);
+        queue
+            .send("abcde-01234", "acme", &acme, b"failed".to_vec(), &sender)
+            .expect("Unable to send message");
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(manager::DeliveryMessage::Queued)
+        ));
+
+        sender.set_failing(false);
+        queue
+            .send("abcde-01234", "acme", &acme, b"waiting".to_vec(), &sender)
+            .expect("Unable to send message");
+        assert!(sender.sent().is_empty());
+
+        let messages = store
+            .list_messages("acme")
+            .expect("Unable to list messages");
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].payload(), b"failed");
+        assert_eq!(messages[1].payload(), b"waiting");
+
+        queue
+            .send("abcde-01234", "bubba", &bubba, b"hello".to_vec(), &sender)
+            .expect("Unable to send message");
+        assert!(sender.sent().is_empty());
+        assert!(store
+            .has_messages("bubba")
+            .expect("Unable to check messages"));
+    }
+
+    #[derive(Clone, Default)]
+    struct MockSender {
+        sent: Arc<Mutex<Vec<(PeerId, Vec<u8>)>>>,
+        failing: Arc<AtomicBool>,
+    }
+
+    impl MockSender {
+        fn set_failing(&self, failing: bool) {
+            self.failing.store(failing, Ordering::SeqCst);
+        }
+
+        fn sent(&self) -> Vec<(PeerId, Vec<u8>)> {
+            self.sent
+                .lock()
+                .expect("lock was poisoned")
+                .drain(..)
+                .collect()
+        }
+    }
+
+    impl MessageSender<PeerId> for MockSender {
+        fn send(&self, id: PeerId, message: Vec<u8>) -> Result<(), (PeerId, Vec<u8>)> {
+            if self.failing.load(Ordering::SeqCst) {
+                return Err((id, message));
+            }
+
+            self.sent
+                .lock()
+                .expect("lock was poisoned")
+                .push((id, message));
+
+            Ok(())
+        }
+    }
+
+    /// Creates a connection pool for an in-memory SQLite database with only a single connection
+    /// available. Each connection is backed by a different in-memory SQLite database, so limiting
+    /// the pool to a single connection ensures that the same DB is used for all operations.
+    fn create_connection_pool_and_migrate() -> Pool<ConnectionManager<SqliteConnection>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        pool
+    }
+}
@@ -0,0 +1,307 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Database-backed implementation of the [PendingMessageStore], powered by [diesel].
+
+mod models;
+mod operations;
+mod schema;
+
+use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
+
+use diesel::r2d2::{ConnectionManager, Pool};
+
+use crate::store::pool::ConnectionPool;
+
+use super::{
+    InsertablePendingMessage, PendingMessage, PendingMessageStore, PendingMessageStoreError,
+};
+
+use operations::{
+    add_message::PendingMessageStoreAddMessage as _,
+    has_messages::PendingMessageStoreHasMessages as _,
+    list_messages::PendingMessageStoreListMessages as _,
+    list_recipients::PendingMessageStoreListRecipients as _,
+    remove_expired_messages::PendingMessageStoreRemoveExpiredMessages as _,
+    remove_message::PendingMessageStoreRemoveMessage as _, PendingMessageStoreOperations,
+};
+
+/// A database-backed [PendingMessageStore], powered by [diesel].
+pub struct DieselPendingMessageStore<C: diesel::Connection + 'static> {
+    connection_pool: ConnectionPool<C>,
+}
+
+impl<C: diesel::Connection + 'static> DieselPendingMessageStore<C> {
+    /// Creates a new `DieselPendingMessageStore`.
+    ///
+    /// # Arguments
+    ///
+    ///  * `connection_pool`: connection pool for the database
+    pub fn new(connection_pool: Pool<ConnectionManager<C>>) -> Self {
+        Self {
+            connection_pool: connection_pool.into(),
+        }
+    }
+
+    /// Create a new `DieselPendingMessageStore` with write exclusivity enabled.
+    ///
+    /// Write exclusivity is enforced by providing a connection pool that is wrapped in a
+    /// [`RwLock`]. This ensures that there may be only one writer, but many readers.
+    ///
+    /// # Arguments
+    ///
+    ///  * `connection_pool`: read-write lock-guarded connection pool for the database
+    pub fn new_with_write_exclusivity(
+        connection_pool: Arc<RwLock<Pool<ConnectionManager<C>>>>,
+    ) -> Self {
+        Self {
+            connection_pool: connection_pool.into(),
+        }
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl PendingMessageStore for DieselPendingMessageStore<diesel::sqlite::SqliteConnection> {
+    fn add_message(
+        &self,
+        message: InsertablePendingMessage,
+    ) -> Result<(), PendingMessageStoreError> {
+        self.connection_pool.execute_write(|connection| {
+            PendingMessageStoreOperations::new(connection).add_message(message)
+        })
+    }
+
+    fn list_messages(
+        &self,
+        recipient_node_id: &str,
+    ) -> Result<Vec<PendingMessage>, PendingMessageStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            PendingMessageStoreOperations::new(connection).list_messages(recipient_node_id)
+        })
+    }
+
+    fn list_recipients(&self) -> Result<Vec<String>, PendingMessageStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            PendingMessageStoreOperations::new(connection).list_recipients()
+        })
+    }
+
+    fn has_messages(&self, recipient_node_id: &str) -> Result<bool, PendingMessageStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            PendingMessageStoreOperations::new(connection).has_messages(recipient_node_id)
+        })
+    }
+
+    fn remove_message(&self, id: i64) -> Result<(), PendingMessageStoreError> {
+        self.connection_pool.execute_write(|connection| {
+            PendingMessageStoreOperations::new(connection).remove_message(id)
+        })
+    }
+
+    fn remove_expired_messages(&self, now: SystemTime) -> Result<usize, PendingMessageStoreError> {
+        self.connection_pool.execute_write(|connection| {
+            PendingMessageStoreOperations::new(connection).remove_expired_messages(now)
+        })
+    }
+
+    fn clone_box(&self) -> Box<dyn PendingMessageStore> {
+        Box::new(Self {
+            connection_pool: self.connection_pool.clone(),
+        })
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl PendingMessageStore for DieselPendingMessageStore<diesel::pg::PgConnection> {
+    fn add_message(
+        &self,
+        message: InsertablePendingMessage,
+    ) -> Result<(), PendingMessageStoreError> {
+        self.connection_pool.execute_write(|connection| {
+            PendingMessageStoreOperations::new(connection).add_message(message)
+        })
+    }
+
+    fn list_messages(
+        &self,
+        recipient_node_id: &str,
+    ) -> Result<Vec<PendingMessage>, PendingMessageStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            PendingMessageStoreOperations::new(connection).list_messages(recipient_node_id)
+        })
+    }
+
+    fn list_recipients(&self) -> Result<Vec<String>, PendingMessageStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            PendingMessageStoreOperations::new(connection).list_recipients()
+        })
+    }
+
+    fn has_messages(&self, recipient_node_id: &str) -> Result<bool, PendingMessageStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            PendingMessageStoreOperations::new(connection).has_messages(recipient_node_id)
+        })
+    }
+
+    fn remove_message(&self, id: i64) -> Result<(), PendingMessageStoreError> {
+        self.connection_pool.execute_write(|connection| {
+            PendingMessageStoreOperations::new(connection).remove_message(id)
+        })
+    }
+
+    fn remove_expired_messages(&self, now: SystemTime) -> Result<usize, PendingMessageStoreError> {
+        self.connection_pool.execute_write(|connection| {
+            PendingMessageStoreOperations::new(connection).remove_expired_messages(now)
+        })
+    }
+
+    fn clone_box(&self) -> Box<dyn PendingMessageStore> {
+        Box::new(Self {
+            connection_pool: self.connection_pool.clone(),
+        })
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+pub mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    use diesel::{
+        r2d2::{ConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+
+    use crate::migrations::run_sqlite_migrations;
+
+    /// Verify that messages can be added, listed in order, and removed from the store:
+    ///
+    /// 1. Add two messages for node "acme" and one for node "bubba"
+    /// 2. Verify the recipients are listed, and that only they have messages
+    /// 3. Verify the messages for "acme" are listed in the order they were added
+    /// 4. Remove the first message and verify only the second remains
+    #[test]
+    fn sqlite_add_list_remove_messages() {
+        let store = DieselPendingMessageStore::new(create_connection_pool_and_migrate());
+
+        store
+            .add_message(InsertablePendingMessage::new(
+                "abcde-01234".into(),
+                "acme".into(),
+                b"first".to_vec(),
+                Duration::from_secs(60),
+            ))
+            .expect("Unable to add first message");
+        store
+            .add_message(InsertablePendingMessage::new(
+                "abcde-01234".into(),
+                "bubba".into(),
+                b"other".to_vec(),
+                Duration::from_secs(60),
+            ))
+            .expect("Unable to add other message");
+        store
+            .add_message(InsertablePendingMessage::new(
+                "abcde-01234".into(),
+                "acme".into(),
+                b"second".to_vec(),
+                Duration::from_secs(60),
+            ))
+            .expect("Unable to add second message");
+
+        let mut recipients = store.list_recipients().expect("Unable to list recipients");
+        recipients.sort();
+        assert_eq!(recipients, vec!["acme".to_string(), "bubba".to_string()]);
+        assert!(store
+            .has_messages("acme")
+            .expect("Unable to check messages"));
+        assert!(!store
+            .has_messages("cargill")
+            .expect("Unable to check messages"));
+
+        let messages = store
+            .list_messages("acme")
+            .expect("Unable to list messages");
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].payload(), b"first");
+        assert_eq!(messages[1].payload(), b"second");
+        assert_eq!(messages[0].circuit_id(), "abcde-01234");
+
+        store
+            .remove_message(messages[0].id())
+            .expect("Unable to remove message");
+
+        let messages = store
+            .list_messages("acme")
+            .expect("Unable to list messages");
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].payload(), b"second");
+    }
+
+    /// Verify that expired messages are removed from the store:
+    ///
+    /// 1. Add a message with a zero TTL and one with a long TTL
+    /// 2. Remove the expired messages
+    /// 3. Verify only the message with the long TTL remains
+    #[test]
+    fn sqlite_remove_expired_messages() {
+        let store = DieselPendingMessageStore::new(create_connection_pool_and_migrate());
+
+        store
+            .add_message(InsertablePendingMessage::new(
+                "abcde-01234".into(),
+                "acme".into(),
+                b"expired".to_vec(),
+                Duration::from_secs(0),
+            ))
+            .expect("Unable to add expired message");
+        store
+            .add_message(InsertablePendingMessage::new(
+                "abcde-01234".into(),
+                "acme".into(),
+                b"current".to_vec(),
+                Duration::from_secs(3600),
+            ))
+            .expect("Unable to add current message");
+
+        let removed = store
+            .remove_expired_messages(SystemTime::now())
+            .expect("Unable to remove expired messages");
+        assert_eq!(removed, 1);
+
+        let messages = store
+            .list_messages("acme")
+            .expect("Unable to list messages");
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].payload(), b"current");
+    }
+
+    /// Creates a connection pool for an in-memory SQLite database with only a single connection
+    /// available. Each connection is backed by a different in-memory SQLite database, so limiting
+    /// the pool to a single connection ensures that the same DB is used for all operations.
+    fn create_connection_pool_and_migrate() -> Pool<ConnectionManager<SqliteConnection>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        pool
+    }
+}
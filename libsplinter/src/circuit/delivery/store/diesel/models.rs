@@ -0,0 +1,107 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::circuit::delivery::store::{
+    InsertablePendingMessage, PendingMessage, PendingMessageStoreError,
+};
+use crate::error::InternalError;
+
+use super::schema::pending_circuit_message;
+
+/// Database model representation of a `PendingMessage`
+#[derive(Debug, PartialEq, Eq, Identifiable, Queryable)]
+#[table_name = "pending_circuit_message"]
+#[primary_key(id)]
+pub struct PendingMessageModel {
+    pub id: i64,
+    pub circuit_id: String,
+    pub recipient_node_id: String,
+    pub payload: Vec<u8>,
+    pub created_at: i64,
+    pub expires_at: i64,
+}
+
+/// Database model representation of an `InsertablePendingMessage`
+#[derive(Debug, PartialEq, Eq, Insertable)]
+#[table_name = "pending_circuit_message"]
+pub struct InsertablePendingMessageModel {
+    pub circuit_id: String,
+    pub recipient_node_id: String,
+    pub payload: Vec<u8>,
+    pub created_at: i64,
+    pub expires_at: i64,
+}
+
+impl TryFrom<InsertablePendingMessage> for InsertablePendingMessageModel {
+    type Error = PendingMessageStoreError;
+
+    fn try_from(message: InsertablePendingMessage) -> Result<Self, Self::Error> {
+        Ok(InsertablePendingMessageModel {
+            circuit_id: message.circuit_id().to_string(),
+            recipient_node_id: message.recipient_node_id().to_string(),
+            created_at: to_timestamp(message.created_at())?,
+            expires_at: to_timestamp(message.expires_at())?,
+            payload: message.payload,
+        })
+    }
+}
+
+impl TryFrom<PendingMessageModel> for PendingMessage {
+    type Error = PendingMessageStoreError;
+
+    fn try_from(model: PendingMessageModel) -> Result<Self, Self::Error> {
+        Ok(PendingMessage::new(
+            model.id,
+            model.circuit_id,
+            model.recipient_node_id,
+            model.payload,
+            from_timestamp(model.created_at)?,
+            from_timestamp(model.expires_at)?,
+        ))
+    }
+}
+
+/// Converts a `SystemTime` into the number of milliseconds since the unix epoch
+pub(super) fn to_timestamp(time: SystemTime) -> Result<i64, PendingMessageStoreError> {
+    let millis = time
+        .duration_since(UNIX_EPOCH)
+        .map_err(|err| InternalError::from_source(Box::new(err)))?
+        .as_millis();
+
+    i64::try_from(millis).map_err(|_| {
+        PendingMessageStoreError::Internal(InternalError::with_message(
+            "timestamp could not be converted from u128 to i64".to_string(),
+        ))
+    })
+}
+
+/// Converts a number of milliseconds since the unix epoch into a `SystemTime`
+fn from_timestamp(timestamp: i64) -> Result<SystemTime, PendingMessageStoreError> {
+    let millis = u64::try_from(timestamp).map_err(|_| {
+        PendingMessageStoreError::Internal(InternalError::with_message(
+            "timestamp could not be converted from i64 to u64".to_string(),
+        ))
+    })?;
+
+    UNIX_EPOCH
+        .checked_add(Duration::from_millis(millis))
+        .ok_or_else(|| {
+            PendingMessageStoreError::Internal(InternalError::with_message(
+                "timestamp could not be represented as a `SystemTime`".to_string(),
+            ))
+        })
+}
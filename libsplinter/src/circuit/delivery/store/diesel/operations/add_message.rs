@@ -0,0 +1,63 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use diesel::{dsl::insert_into, prelude::*};
+
+use crate::circuit::delivery::store::{
+    diesel::{models::InsertablePendingMessageModel, schema::pending_circuit_message},
+    InsertablePendingMessage, PendingMessageStoreError,
+};
+
+use super::PendingMessageStoreOperations;
+
+pub trait PendingMessageStoreAddMessage {
+    fn add_message(
+        &self,
+        message: InsertablePendingMessage,
+    ) -> Result<(), PendingMessageStoreError>;
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> PendingMessageStoreAddMessage
+    for PendingMessageStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn add_message(
+        &self,
+        message: InsertablePendingMessage,
+    ) -> Result<(), PendingMessageStoreError> {
+        insert_into(pending_circuit_message::table)
+            .values(InsertablePendingMessageModel::try_from(message)?)
+            .execute(self.conn)
+            .map(|_| ())
+            .map_err(PendingMessageStoreError::from)
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> PendingMessageStoreAddMessage
+    for PendingMessageStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn add_message(
+        &self,
+        message: InsertablePendingMessage,
+    ) -> Result<(), PendingMessageStoreError> {
+        insert_into(pending_circuit_message::table)
+            .values(InsertablePendingMessageModel::try_from(message)?)
+            .execute(self.conn)
+            .map(|_| ())
+            .map_err(PendingMessageStoreError::from)
+    }
+}
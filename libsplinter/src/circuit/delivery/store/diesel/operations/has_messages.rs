@@ -0,0 +1,41 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use diesel::prelude::*;
+
+use crate::circuit::delivery::store::{
+    diesel::schema::pending_circuit_message, PendingMessageStoreError,
+};
+
+use super::PendingMessageStoreOperations;
+
+pub trait PendingMessageStoreHasMessages {
+    fn has_messages(&self, recipient_node_id: &str) -> Result<bool, PendingMessageStoreError>;
+}
+
+impl<'a, C> PendingMessageStoreHasMessages for PendingMessageStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+{
+    fn has_messages(&self, recipient_node_id: &str) -> Result<bool, PendingMessageStoreError> {
+        pending_circuit_message::table
+            .filter(pending_circuit_message::recipient_node_id.eq(recipient_node_id))
+            .select(pending_circuit_message::id)
+            .first::<i64>(self.conn)
+            .optional()
+            .map(|id| id.is_some())
+            .map_err(PendingMessageStoreError::from)
+    }
+}
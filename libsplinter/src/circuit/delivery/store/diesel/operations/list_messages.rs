@@ -0,0 +1,52 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use diesel::prelude::*;
+
+use crate::circuit::delivery::store::{
+    diesel::{models::PendingMessageModel, schema::pending_circuit_message},
+    PendingMessage, PendingMessageStoreError,
+};
+
+use super::PendingMessageStoreOperations;
+
+pub trait PendingMessageStoreListMessages {
+    fn list_messages(
+        &self,
+        recipient_node_id: &str,
+    ) -> Result<Vec<PendingMessage>, PendingMessageStoreError>;
+}
+
+impl<'a, C> PendingMessageStoreListMessages for PendingMessageStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    Vec<u8>: diesel::deserialize::FromSql<diesel::sql_types::Binary, C::Backend>,
+{
+    fn list_messages(
+        &self,
+        recipient_node_id: &str,
+    ) -> Result<Vec<PendingMessage>, PendingMessageStoreError> {
+        pending_circuit_message::table
+            .filter(pending_circuit_message::recipient_node_id.eq(recipient_node_id))
+            .order(pending_circuit_message::id.asc())
+            .load::<PendingMessageModel>(self.conn)?
+            .into_iter()
+            .map(PendingMessage::try_from)
+            .collect()
+    }
+}
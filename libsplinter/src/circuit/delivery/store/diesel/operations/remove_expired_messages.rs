@@ -0,0 +1,42 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::SystemTime;
+
+use diesel::{dsl::delete, prelude::*};
+
+use crate::circuit::delivery::store::{
+    diesel::{models::to_timestamp, schema::pending_circuit_message},
+    PendingMessageStoreError,
+};
+
+use super::PendingMessageStoreOperations;
+
+pub trait PendingMessageStoreRemoveExpiredMessages {
+    fn remove_expired_messages(&self, now: SystemTime) -> Result<usize, PendingMessageStoreError>;
+}
+
+impl<'a, C> PendingMessageStoreRemoveExpiredMessages for PendingMessageStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    i64: diesel::serialize::ToSql<diesel::sql_types::BigInt, C::Backend>,
+{
+    fn remove_expired_messages(&self, now: SystemTime) -> Result<usize, PendingMessageStoreError> {
+        let now = to_timestamp(now)?;
+
+        delete(pending_circuit_message::table.filter(pending_circuit_message::expires_at.le(now)))
+            .execute(self.conn)
+            .map_err(PendingMessageStoreError::from)
+    }
+}
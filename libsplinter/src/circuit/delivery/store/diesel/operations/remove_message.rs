@@ -0,0 +1,38 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use diesel::{dsl::delete, prelude::*};
+
+use crate::circuit::delivery::store::{
+    diesel::schema::pending_circuit_message, PendingMessageStoreError,
+};
+
+use super::PendingMessageStoreOperations;
+
+pub trait PendingMessageStoreRemoveMessage {
+    fn remove_message(&self, id: i64) -> Result<(), PendingMessageStoreError>;
+}
+
+impl<'a, C> PendingMessageStoreRemoveMessage for PendingMessageStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    i64: diesel::serialize::ToSql<diesel::sql_types::BigInt, C::Backend>,
+{
+    fn remove_message(&self, id: i64) -> Result<(), PendingMessageStoreError> {
+        delete(pending_circuit_message::table.find(id))
+            .execute(self.conn)
+            .map(|_| ())
+            .map_err(PendingMessageStoreError::from)
+    }
+}
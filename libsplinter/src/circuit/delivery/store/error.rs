@@ -0,0 +1,114 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Error types and logic for the PendingMessageStore.
+
+use std::error::Error;
+use std::fmt::Display;
+
+#[cfg(feature = "diesel")]
+use crate::error::ConstraintViolationType;
+use crate::error::{
+    ConstraintViolationError, InternalError, InvalidArgumentError, InvalidStateError,
+    ResourceTemporarilyUnavailableError,
+};
+
+/// Error type for the PendingMessageStore trait.
+#[derive(Debug)]
+pub enum PendingMessageStoreError {
+    ConstraintViolation(ConstraintViolationError),
+    Internal(InternalError),
+    InvalidArgument(InvalidArgumentError),
+    InvalidState(InvalidStateError),
+    ResourceTemporarilyUnavailable(ResourceTemporarilyUnavailableError),
+}
+
+impl Display for PendingMessageStoreError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PendingMessageStoreError::ConstraintViolation(e) => e.fmt(f),
+            PendingMessageStoreError::Internal(e) => e.fmt(f),
+            PendingMessageStoreError::InvalidArgument(e) => e.fmt(f),
+            PendingMessageStoreError::InvalidState(e) => e.fmt(f),
+            PendingMessageStoreError::ResourceTemporarilyUnavailable(e) => e.fmt(f),
+        }
+    }
+}
+
+impl Error for PendingMessageStoreError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PendingMessageStoreError::ConstraintViolation(e) => Some(e),
+            PendingMessageStoreError::Internal(e) => Some(e),
+            PendingMessageStoreError::InvalidArgument(e) => Some(e),
+            PendingMessageStoreError::InvalidState(e) => Some(e),
+            PendingMessageStoreError::ResourceTemporarilyUnavailable(e) => Some(e),
+        }
+    }
+}
+
+impl From<InternalError> for PendingMessageStoreError {
+    fn from(err: InternalError) -> Self {
+        PendingMessageStoreError::Internal(err)
+    }
+}
+
+impl From<InvalidArgumentError> for PendingMessageStoreError {
+    fn from(err: InvalidArgumentError) -> Self {
+        PendingMessageStoreError::InvalidArgument(err)
+    }
+}
+
+impl From<InvalidStateError> for PendingMessageStoreError {
+    fn from(err: InvalidStateError) -> Self {
+        PendingMessageStoreError::InvalidState(err)
+    }
+}
+
+#[cfg(feature = "diesel")]
+impl From<diesel::r2d2::PoolError> for PendingMessageStoreError {
+    fn from(err: diesel::r2d2::PoolError) -> Self {
+        PendingMessageStoreError::ResourceTemporarilyUnavailable(
+            ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
+        )
+    }
+}
+
+#[cfg(feature = "diesel")]
+impl From<diesel::result::Error> for PendingMessageStoreError {
+    fn from(err: diesel::result::Error) -> Self {
+        match err {
+            diesel::result::Error::DatabaseError(db_err_kind, _) => match db_err_kind {
+                diesel::result::DatabaseErrorKind::UniqueViolation => {
+                    PendingMessageStoreError::ConstraintViolation(
+                        ConstraintViolationError::from_source_with_violation_type(
+                            ConstraintViolationType::Unique,
+                            Box::new(err),
+                        ),
+                    )
+                }
+                diesel::result::DatabaseErrorKind::ForeignKeyViolation => {
+                    PendingMessageStoreError::ConstraintViolation(
+                        ConstraintViolationError::from_source_with_violation_type(
+                            ConstraintViolationType::ForeignKey,
+                            Box::new(err),
+                        ),
+                    )
+                }
+                _ => PendingMessageStoreError::Internal(InternalError::from_source(Box::new(err))),
+            },
+            _ => PendingMessageStoreError::Internal(InternalError::from_source(Box::new(err))),
+        }
+    }
+}
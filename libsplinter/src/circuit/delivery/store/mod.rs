@@ -0,0 +1,210 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Defines a store of circuit messages that could not be delivered to a remote node.
+//!
+//! Messages sent on a circuit with a `GuaranteedDelivery` durability are added to the store when
+//! the recipient node is not connected. They are removed once they have been delivered or once
+//! they have expired.
+
+#[cfg(feature = "diesel")]
+pub mod diesel;
+mod error;
+
+use std::time::{Duration, SystemTime};
+
+pub use error::PendingMessageStoreError;
+
+/// A circuit message that is waiting to be delivered to a remote node
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PendingMessage {
+    id: i64,
+    circuit_id: String,
+    recipient_node_id: String,
+    payload: Vec<u8>,
+    created_at: SystemTime,
+    expires_at: SystemTime,
+}
+
+impl PendingMessage {
+    /// Creates a new `PendingMessage`
+    ///
+    /// This constructor should only be used by implementations of the [PendingMessageStore] for
+    /// returning an existing message.
+    pub fn new(
+        id: i64,
+        circuit_id: String,
+        recipient_node_id: String,
+        payload: Vec<u8>,
+        created_at: SystemTime,
+        expires_at: SystemTime,
+    ) -> Self {
+        Self {
+            id,
+            circuit_id,
+            recipient_node_id,
+            payload,
+            created_at,
+            expires_at,
+        }
+    }
+
+    /// Returns the store-assigned ID of the message
+    pub fn id(&self) -> i64 {
+        self.id
+    }
+
+    /// Returns the ID of the circuit the message was sent on
+    pub fn circuit_id(&self) -> &str {
+        &self.circuit_id
+    }
+
+    /// Returns the ID of the node the message should be delivered to
+    pub fn recipient_node_id(&self) -> &str {
+        &self.recipient_node_id
+    }
+
+    /// Returns the network message bytes to be delivered
+    pub fn payload(&self) -> &[u8] {
+        &self.payload
+    }
+
+    /// Returns the time at which the message was added to the store
+    pub fn created_at(&self) -> SystemTime {
+        self.created_at
+    }
+
+    /// Returns the time after which the message should no longer be delivered
+    pub fn expires_at(&self) -> SystemTime {
+        self.expires_at
+    }
+}
+
+/// A circuit message that should be added to a [PendingMessageStore]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InsertablePendingMessage {
+    circuit_id: String,
+    recipient_node_id: String,
+    payload: Vec<u8>,
+    created_at: SystemTime,
+    expires_at: SystemTime,
+}
+
+impl InsertablePendingMessage {
+    /// Creates a new `InsertablePendingMessage` that will expire after the given time-to-live
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit_id` - The ID of the circuit the message was sent on
+    /// * `recipient_node_id` - The ID of the node the message should be delivered to
+    /// * `payload` - The network message bytes to be delivered
+    /// * `ttl` - How long the message should be kept before it is discarded
+    pub fn new(
+        circuit_id: String,
+        recipient_node_id: String,
+        payload: Vec<u8>,
+        ttl: Duration,
+    ) -> Self {
+        let created_at = SystemTime::now();
+        Self {
+            circuit_id,
+            recipient_node_id,
+            payload,
+            created_at,
+            expires_at: created_at + ttl,
+        }
+    }
+
+    /// Returns the ID of the circuit the message was sent on
+    pub fn circuit_id(&self) -> &str {
+        &self.circuit_id
+    }
+
+    /// Returns the ID of the node the message should be delivered to
+    pub fn recipient_node_id(&self) -> &str {
+        &self.recipient_node_id
+    }
+
+    /// Returns the network message bytes to be delivered
+    pub fn payload(&self) -> &[u8] {
+        &self.payload
+    }
+
+    /// Returns the time at which the message was created
+    pub fn created_at(&self) -> SystemTime {
+        self.created_at
+    }
+
+    /// Returns the time after which the message should no longer be delivered
+    pub fn expires_at(&self) -> SystemTime {
+        self.expires_at
+    }
+}
+
+/// Defines methods for CRUD operations on circuit messages awaiting delivery
+pub trait PendingMessageStore: Send + Sync {
+    /// Adds a message to the store
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message to be added
+    fn add_message(
+        &self,
+        message: InsertablePendingMessage,
+    ) -> Result<(), PendingMessageStoreError>;
+
+    /// Returns all messages for the given node, in the order they were added
+    ///
+    /// # Arguments
+    ///
+    /// * `recipient_node_id` - The ID of the node the messages should be delivered to
+    fn list_messages(
+        &self,
+        recipient_node_id: &str,
+    ) -> Result<Vec<PendingMessage>, PendingMessageStoreError>;
+
+    /// Returns the IDs of all nodes that have messages waiting in the store
+    fn list_recipients(&self) -> Result<Vec<String>, PendingMessageStoreError>;
+
+    /// Returns whether or not the given node has any messages waiting in the store
+    ///
+    /// # Arguments
+    ///
+    /// * `recipient_node_id` - The ID of the node the messages should be delivered to
+    fn has_messages(&self, recipient_node_id: &str) -> Result<bool, PendingMessageStoreError>;
+
+    /// Removes a message from the store
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The store-assigned ID of the message
+    fn remove_message(&self, id: i64) -> Result<(), PendingMessageStoreError>;
+
+    /// Removes all messages that expired before the given time, returning the number of messages
+    /// removed
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - The time messages are checked against
+    fn remove_expired_messages(&self, now: SystemTime) -> Result<usize, PendingMessageStoreError>;
+
+    /// Clone into a boxed, dynamically dispatched store
+    fn clone_box(&self) -> Box<dyn PendingMessageStore>;
+}
+
+impl Clone for Box<dyn PendingMessageStore> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
@@ -12,15 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+use std::sync::Mutex;
+
 use protobuf::Message;
 
-use crate::circuit::handlers::create_message;
+use crate::circuit::handlers::{add_forwarding_hop, create_message, select_next_hop};
 use crate::circuit::routing::RoutingTableReader;
 use crate::hex::parse_hex;
 use crate::network::dispatch::{DispatchError, Handler, MessageContext, MessageSender, PeerId};
-use crate::peer::{PeerAuthorizationToken, PeerTokenPair};
+use crate::peer::{PeerAuthorizationToken, PeerManagerConnector, PeerTokenPair};
 use crate::protos::circuit::{
-    AdminDirectMessage, CircuitError, CircuitError_Error, CircuitMessageType,
+    AdminDirectMessage, CircuitError, CircuitError_Error, CircuitMessageType, ForwardingHeader,
 };
 use crate::public_key::PublicKey;
 
@@ -32,6 +35,9 @@ pub struct AdminDirectMessageHandler {
     node_id: String,
     routing_table: Box<dyn RoutingTableReader>,
     public_keys: Vec<PublicKey>,
+    peer_connector: Option<PeerManagerConnector>,
+    // Node ID to the relay nodes that have forwarded admin messages from that node
+    relayed_senders: Mutex<HashMap<String, Vec<String>>>,
 }
 
 impl Handler for AdminDirectMessageHandler {
@@ -70,7 +76,10 @@ impl Handler for AdminDirectMessageHandler {
         // msg bytes will either be message bytes of a direct message or an error message
         // the msg_recipient is either the service/node id to send the message to or is the
         // peer_id to send back the error message
-        let (msg_bytes, msg_recipient) = self.create_response(msg, context)?;
+        let (msg_bytes, msg_recipient) = match self.create_response(msg, context)? {
+            Some(response) => response,
+            None => return Ok(()),
+        };
         // either forward the direct message or send back an error message.
         sender
             .send(msg_recipient, msg_bytes)
@@ -91,14 +100,23 @@ impl AdminDirectMessageHandler {
             node_id,
             routing_table,
             public_keys,
+            peer_connector: None,
+            relayed_senders: Mutex::new(HashMap::new()),
         }
     }
 
+    /// Sets the peer connector used to check which nodes are connected, so that admin messages
+    /// to a node that is not connected are sent through a relay node that is available
+    pub fn with_peer_connector(mut self, peer_connector: PeerManagerConnector) -> Self {
+        self.peer_connector = Some(peer_connector);
+        self
+    }
+
     fn create_response(
         &self,
         msg: AdminDirectMessage,
         context: &MessageContext<PeerId, CircuitMessageType>,
-    ) -> Result<(Vec<u8>, PeerId), DispatchError> {
+    ) -> Result<Option<(Vec<u8>, PeerId)>, DispatchError> {
         let circuit_name = msg.get_circuit();
         let msg_sender = msg.get_sender();
         let recipient = msg.get_recipient();
@@ -116,10 +134,10 @@ impl AdminDirectMessageHandler {
                     msg_sender
                 ),
             )?;
-            return Ok((
+            return Ok(Some((
                 create_message(err_msg_bytes, CircuitMessageType::CIRCUIT_ERROR_MESSAGE)?,
                 context.source_peer_id().clone(),
-            ));
+            )));
         }
 
         if !is_admin_service_id(recipient) {
@@ -131,10 +149,10 @@ impl AdminDirectMessageHandler {
                     recipient
                 ),
             )?;
-            return Ok((
+            return Ok(Some((
                 create_message(err_msg_bytes, CircuitMessageType::CIRCUIT_ERROR_MESSAGE)?,
                 context.source_peer_id().clone(),
-            ));
+            )));
         }
 
         // msg bytes will either be message bytes of a direct message or an error message
@@ -241,14 +259,37 @@ impl AdminDirectMessageHandler {
                 }
             } else {
                 // If the service is on this node send message to the service, otherwise
-                // send the message to the node the service is connected to
+                // send the message to the node the service is connected to, which may be
+                // through a relay node
                 if node_id != self.node_id {
-                    PeerTokenPair::new(
-                        PeerAuthorizationToken::from_peer_id(node_id),
-                        PeerAuthorizationToken::from_peer_id(&self.node_id),
-                    )
-                    .into()
+                    let (next_hop, peer_token_pair) = self.select_next_hop(
+                        circuit_name,
+                        node_id,
+                        msg.get_forwarding_header().get_visited_node_ids(),
+                    )?;
+
+                    // Messages that are relayed through an intermediate node carry a
+                    // forwarding header, which is used to detect routing loops
+                    if next_hop != node_id || msg.has_forwarding_header() {
+                        let mut msg = msg.clone();
+                        let header = if msg.has_forwarding_header() {
+                            Some(msg.take_forwarding_header())
+                        } else {
+                            None
+                        };
+                        match add_forwarding_hop(header, &self.node_id, circuit_name) {
+                            Some(header) => msg.set_forwarding_header(header),
+                            None => return Ok(None),
+                        }
+                        msg_bytes = msg.write_to_bytes().map_err(DispatchError::from)?;
+                    }
+
+                    peer_token_pair.into()
                 } else {
+                    if msg.has_forwarding_header() {
+                        self.add_relayed_sender(msg.get_forwarding_header(), context)?;
+                    }
+
                     // The internal admin service is at the node id with an identical name
                     PeerTokenPair::new(
                         PeerAuthorizationToken::from_peer_id(recipient),
@@ -273,7 +314,90 @@ impl AdminDirectMessageHandler {
                 create_message(msg_bytes, CircuitMessageType::CIRCUIT_ERROR_MESSAGE)?;
             (network_msg_bytes, context.source_peer_id().clone())
         };
-        Ok(response)
+        Ok(Some(response))
+    }
+
+    /// Selects the node an admin message for the target node is sent to next, along with the peer
+    /// it is reached through
+    ///
+    /// If the routing table has no relay route to the target node, relay nodes that have
+    /// forwarded admin messages from the target node are used when the target node is not
+    /// connected. Relay nodes the message has already passed through are skipped.
+    fn select_next_hop(
+        &self,
+        circuit_id: &str,
+        target_node_id: &str,
+        visited_node_ids: &[String],
+    ) -> Result<(String, PeerTokenPair), DispatchError> {
+        let mut next_hops = self
+            .routing_table
+            .next_hops(circuit_id, &self.node_id, target_node_id)
+            .map_err(|err| DispatchError::HandleError(err.to_string()))?;
+        if next_hops.is_empty() {
+            next_hops.push(target_node_id.to_string());
+        }
+
+        if next_hops[0] == target_node_id {
+            if let Some(relay_nodes) = self
+                .relayed_senders
+                .lock()
+                .map_err(|_| DispatchError::HandleError("Relayed senders lock poisoned".into()))?
+                .get(target_node_id)
+            {
+                for relay_node in relay_nodes {
+                    if !next_hops.contains(relay_node) {
+                        next_hops.push(relay_node.to_string());
+                    }
+                }
+            }
+        }
+
+        let next_hops = next_hops
+            .into_iter()
+            .map(|next_hop| {
+                let peer_token_pair = PeerTokenPair::new(
+                    PeerAuthorizationToken::from_peer_id(&next_hop),
+                    PeerAuthorizationToken::from_peer_id(&self.node_id),
+                );
+                (next_hop, peer_token_pair)
+            })
+            .collect();
+
+        select_next_hop(self.peer_connector.as_ref(), next_hops, visited_node_ids)?.ok_or_else(
+            || DispatchError::HandleError(format!("No route to node {}", target_node_id)),
+        )
+    }
+
+    /// Records the relay node that forwarded an admin message to this node, so that replies to
+    /// the node the message came from can be sent back through it
+    ///
+    /// The relay node is only recorded if it is the peer the message was received from.
+    fn add_relayed_sender(
+        &self,
+        header: &ForwardingHeader,
+        context: &MessageContext<PeerId, CircuitMessageType>,
+    ) -> Result<(), DispatchError> {
+        let relay_node = match header.get_visited_node_ids().last() {
+            Some(relay_node) => relay_node,
+            None => return Ok(()),
+        };
+
+        let source_peer = PeerTokenPair::from(context.source_peer_id().clone());
+        if source_peer.peer_id() != &PeerAuthorizationToken::from_peer_id(relay_node) {
+            return Ok(());
+        }
+
+        let mut relayed_senders = self
+            .relayed_senders
+            .lock()
+            .map_err(|_| DispatchError::HandleError("Relayed senders lock poisoned".into()))?;
+        let relay_nodes = relayed_senders
+            .entry(header.get_origin_node_id().to_string())
+            .or_default();
+        if !relay_nodes.contains(relay_node) {
+            relay_nodes.push(relay_node.to_string());
+        }
+        Ok(())
     }
 }
 
@@ -305,14 +429,17 @@ mod tests {
     use super::*;
 
     use std::collections::VecDeque;
+    use std::sync::mpsc::channel;
     use std::sync::{Arc, Mutex};
+    use std::thread;
 
     use crate::circuit::routing::AuthorizationType;
     use crate::circuit::routing::{
         memory::RoutingTable, Circuit, CircuitNode, RoutingTableWriter, Service,
     };
+    use crate::collections::BiHashMap;
     use crate::network::dispatch::Dispatcher;
-    use crate::peer::PeerAuthorizationToken;
+    use crate::peer::{PeerAuthorizationToken, PeerManagerMessage, PeerManagerRequest};
     use crate::protos::circuit::CircuitMessage;
     use crate::protos::network::NetworkMessage;
 
@@ -628,6 +755,228 @@ mod tests {
         )
     }
 
+    /// Send a message to an admin service on a node that is reached through a relay node. Expect
+    /// that the message is sent to the relay node with a forwarding header, and that the relay
+    /// node forwards it to the node that hosts the target admin service.
+    #[test]
+    fn test_send_admin_direct_message_through_relay() {
+        // Set up dispatcher and mock sender
+        let mock_sender = MockSender::new();
+        let mut dispatcher = Dispatcher::new(Box::new(mock_sender.clone()));
+
+        let table = RoutingTable::default();
+        let reader: Box<dyn RoutingTableReader> = Box::new(table.clone());
+        let mut writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+        writer
+            .add_admin_relay_route("5678".into(), vec!["345".into()])
+            .expect("Unable to add relay route");
+
+        let handler = AdminDirectMessageHandler::new("1234".into(), reader, vec![]);
+        dispatcher.set_handler(Box::new(handler));
+
+        let mut direct_message = AdminDirectMessage::new();
+        direct_message.set_circuit("admin".into());
+        direct_message.set_sender("admin::1234".into());
+        direct_message.set_recipient("admin::5678".into());
+        direct_message.set_payload(b"test".to_vec());
+        direct_message.set_correlation_id("random_corr_id".into());
+        let direct_bytes = direct_message.write_to_bytes().unwrap();
+
+        assert!(dispatcher
+            .dispatch(
+                PeerTokenPair::new(
+                    PeerAuthorizationToken::from_peer_id("admin::1234"),
+                    PeerAuthorizationToken::from_peer_id("1234"),
+                )
+                .into(),
+                &CircuitMessageType::ADMIN_DIRECT_MESSAGE,
+                direct_bytes
+            )
+            .is_ok());
+
+        let (id, message) = mock_sender.next_outbound().expect("No message was sent");
+        assert_network_message(
+            message,
+            id.into(),
+            PeerTokenPair::new(
+                PeerAuthorizationToken::from_peer_id("345"),
+                PeerAuthorizationToken::from_peer_id("1234"),
+            ),
+            CircuitMessageType::ADMIN_DIRECT_MESSAGE,
+            |msg: AdminDirectMessage| {
+                assert_eq!(msg.get_recipient(), "admin::5678");
+                let header = msg.get_forwarding_header();
+                assert_eq!(header.get_origin_node_id(), "1234");
+                assert_eq!(
+                    header.get_visited_node_ids().to_vec(),
+                    vec!["1234".to_string()]
+                );
+            },
+        );
+
+        // Forward the message on the relay node, which sends it directly to the target node
+        let relay_reader: Box<dyn RoutingTableReader> = Box::new(RoutingTable::default());
+        let mut relay_dispatcher = Dispatcher::new(Box::new(mock_sender.clone()));
+        relay_dispatcher.set_handler(Box::new(AdminDirectMessageHandler::new(
+            "345".into(),
+            relay_reader,
+            vec![],
+        )));
+
+        let mut header = ForwardingHeader::new();
+        header.set_origin_node_id("1234".into());
+        header.set_visited_node_ids(vec!["1234".to_string()].into());
+        header.set_hops_remaining(7);
+        direct_message.set_forwarding_header(header);
+        let direct_bytes = direct_message.write_to_bytes().unwrap();
+
+        assert!(relay_dispatcher
+            .dispatch(
+                PeerTokenPair::new(
+                    PeerAuthorizationToken::from_peer_id("1234"),
+                    PeerAuthorizationToken::from_peer_id("345"),
+                )
+                .into(),
+                &CircuitMessageType::ADMIN_DIRECT_MESSAGE,
+                direct_bytes
+            )
+            .is_ok());
+
+        let (id, message) = mock_sender.next_outbound().expect("No message was sent");
+        assert_network_message(
+            message,
+            id.into(),
+            PeerTokenPair::new(
+                PeerAuthorizationToken::from_peer_id("5678"),
+                PeerAuthorizationToken::from_peer_id("345"),
+            ),
+            CircuitMessageType::ADMIN_DIRECT_MESSAGE,
+            |msg: AdminDirectMessage| {
+                assert_eq!(msg.get_recipient(), "admin::5678");
+                assert_eq!(msg.get_payload(), b"test");
+                assert_eq!(
+                    msg.get_forwarding_header().get_visited_node_ids().to_vec(),
+                    vec!["1234".to_string(), "345".to_string()]
+                );
+            },
+        );
+    }
+
+    /// Receive an admin message that was forwarded through a relay node and reply to it while
+    /// the sending node is not connected. Expect that the reply is sent back through the relay
+    /// node.
+    #[test]
+    fn test_reply_to_relayed_admin_direct_message() {
+        // Set up dispatcher and mock sender
+        let mock_sender = MockSender::new();
+        let mut dispatcher = Dispatcher::new(Box::new(mock_sender.clone()));
+
+        // Only the relay node is connected
+        let (sender, receiver) = channel();
+        let peer_connector = PeerManagerConnector::new(sender);
+        let join_handle = thread::spawn(move || {
+            for message in receiver.iter() {
+                if let PeerManagerMessage::Request(PeerManagerRequest::ConnectionIds { sender }) =
+                    message
+                {
+                    let mut connection_ids = BiHashMap::new();
+                    connection_ids.insert(
+                        PeerTokenPair::new(
+                            PeerAuthorizationToken::from_peer_id("345"),
+                            PeerAuthorizationToken::from_peer_id("5678"),
+                        ),
+                        "connection-345".to_string(),
+                    );
+                    sender
+                        .send(Ok(connection_ids))
+                        .expect("Unable to send connection IDs");
+                }
+            }
+        });
+
+        let reader: Box<dyn RoutingTableReader> = Box::new(RoutingTable::default());
+        let handler = AdminDirectMessageHandler::new("5678".into(), reader, vec![])
+            .with_peer_connector(peer_connector);
+        dispatcher.set_handler(Box::new(handler));
+
+        let mut header = ForwardingHeader::new();
+        header.set_origin_node_id("1234".into());
+        header.set_visited_node_ids(vec!["1234".to_string(), "345".to_string()].into());
+        header.set_hops_remaining(6);
+
+        let mut direct_message = AdminDirectMessage::new();
+        direct_message.set_circuit("admin".into());
+        direct_message.set_sender("admin::1234".into());
+        direct_message.set_recipient("admin::5678".into());
+        direct_message.set_payload(b"request".to_vec());
+        direct_message.set_forwarding_header(header);
+        let direct_bytes = direct_message.write_to_bytes().unwrap();
+
+        assert!(dispatcher
+            .dispatch(
+                PeerTokenPair::new(
+                    PeerAuthorizationToken::from_peer_id("345"),
+                    PeerAuthorizationToken::from_peer_id("5678"),
+                )
+                .into(),
+                &CircuitMessageType::ADMIN_DIRECT_MESSAGE,
+                direct_bytes
+            )
+            .is_ok());
+
+        let (id, message) = mock_sender.next_outbound().expect("No message was sent");
+        assert_network_message(
+            message,
+            id.into(),
+            PeerTokenPair::new(
+                PeerAuthorizationToken::from_peer_id("admin::5678"),
+                PeerAuthorizationToken::from_peer_id("5678"),
+            ),
+            CircuitMessageType::ADMIN_DIRECT_MESSAGE,
+            |msg: AdminDirectMessage| {
+                assert_eq!(msg.get_payload(), b"request");
+            },
+        );
+
+        let mut reply = AdminDirectMessage::new();
+        reply.set_circuit("admin".into());
+        reply.set_sender("admin::5678".into());
+        reply.set_recipient("admin::1234".into());
+        reply.set_payload(b"reply".to_vec());
+        let reply_bytes = reply.write_to_bytes().unwrap();
+
+        assert!(dispatcher
+            .dispatch(
+                PeerTokenPair::new(
+                    PeerAuthorizationToken::from_peer_id("admin::5678"),
+                    PeerAuthorizationToken::from_peer_id("5678"),
+                )
+                .into(),
+                &CircuitMessageType::ADMIN_DIRECT_MESSAGE,
+                reply_bytes
+            )
+            .is_ok());
+
+        let (id, message) = mock_sender.next_outbound().expect("No message was sent");
+        assert_network_message(
+            message,
+            id.into(),
+            PeerTokenPair::new(
+                PeerAuthorizationToken::from_peer_id("345"),
+                PeerAuthorizationToken::from_peer_id("5678"),
+            ),
+            CircuitMessageType::ADMIN_DIRECT_MESSAGE,
+            |msg: AdminDirectMessage| {
+                assert_eq!(msg.get_recipient(), "admin::1234");
+                assert_eq!(msg.get_payload(), b"reply");
+                assert_eq!(msg.get_forwarding_header().get_origin_node_id(), "5678");
+            },
+        );
+
+        drop(dispatcher);
+        join_handle.join().expect("Unable to join thread");
+    }
+
     /// Send a message to an admin service via the admin circuit using a public key. Expect that
     /// the message is sent to the appropriate node that hosts the target admin service.
     #[test]
@@ -12,10 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::circuit::handlers::create_message;
-use crate::circuit::routing::{RoutingTableReader, ServiceId as RoutingServiceId};
+#[cfg(feature = "circuit-guaranteed-delivery")]
+use crate::circuit::delivery::GuaranteedDeliveryQueue;
+use crate::circuit::handlers::{add_forwarding_hop, create_message, select_next_hop};
+#[cfg(feature = "circuit-rate-limiting")]
+use crate::circuit::rate_limit::{CircuitRateLimiter, RateLimitResult};
+#[cfg(feature = "circuit-guaranteed-delivery")]
+use crate::circuit::routing::DurabilityType;
+use crate::circuit::routing::{
+    AuthorizationType, RoutingTableReader, ServiceId as RoutingServiceId,
+};
 use crate::network::dispatch::{DispatchError, Handler, MessageContext, MessageSender, PeerId};
-use crate::peer::PeerTokenPair;
+use crate::peer::{PeerManagerConnector, PeerTokenPair};
 use crate::protos::circuit::{
     CircuitDirectMessage, CircuitError, CircuitError_Error, CircuitMessageType,
 };
@@ -36,6 +44,11 @@ pub struct CircuitDirectMessageHandler {
     routing_table: Box<dyn RoutingTableReader>,
     #[cfg(feature = "service-message-handler-dispatch")]
     service_dispatcher: ServiceDispatcher,
+    #[cfg(feature = "circuit-guaranteed-delivery")]
+    delivery_queue: Option<GuaranteedDeliveryQueue>,
+    #[cfg(feature = "circuit-rate-limiting")]
+    rate_limiter: Option<CircuitRateLimiter>,
+    peer_connector: Option<PeerManagerConnector>,
 }
 
 impl Handler for CircuitDirectMessageHandler {
@@ -76,6 +89,96 @@ impl Handler for CircuitDirectMessageHandler {
         let recipient = msg.get_recipient();
         let recipient_id = RoutingServiceId::new(circuit_name.to_string(), recipient.to_string());
 
+        let circuit = self
+            .routing_table
+            .get_circuit(circuit_name)
+            .map_err(|err| DispatchError::HandleError(err.to_string()))?;
+
+        // Service messages are not routed while a circuit is suspended
+        if circuit
+            .as_ref()
+            .map(|circuit| circuit.is_suspended())
+            .unwrap_or(false)
+        {
+            debug!(
+                "Rejecting message from {} on suspended circuit {}",
+                msg_sender, circuit_name
+            );
+
+            let mut error_message = CircuitError::new();
+            error_message.set_correlation_id(msg.get_correlation_id().to_string());
+            error_message.set_service_id(msg_sender.into());
+            error_message.set_circuit_name(circuit_name.into());
+            error_message.set_error(CircuitError_Error::ERROR_CIRCUIT_SUSPENDED);
+            error_message.set_error_message(format!("Circuit {} is suspended", circuit_name));
+
+            let msg_bytes = error_message.write_to_bytes()?;
+            let network_msg_bytes =
+                create_message(msg_bytes, CircuitMessageType::CIRCUIT_ERROR_MESSAGE)?;
+            sender
+                .send(context.source_peer_id().clone(), network_msg_bytes)
+                .map_err(|(recipient, payload)| {
+                    DispatchError::NetworkSendError((recipient.into(), payload))
+                })?;
+            return Ok(());
+        }
+
+        // If the sender has exceeded its rate limit, send circuit error instead of routing the
+        // message. Only messages from services in the roster of an existing circuit are counted;
+        // any other message is rejected below, and must not use up the limits of the circuit.
+        #[cfg(feature = "circuit-rate-limiting")]
+        {
+            let is_roster_member = circuit
+                .as_ref()
+                .map(|circuit| {
+                    circuit
+                        .roster()
+                        .iter()
+                        .any(|service| service.service_id() == msg_sender)
+                })
+                .unwrap_or(false);
+
+            if let (Some(rate_limiter), true) = (&self.rate_limiter, is_roster_member) {
+                let error_msg = match rate_limiter.check(circuit_name, msg_sender)? {
+                    RateLimitResult::Allowed => None,
+                    RateLimitResult::CircuitLimitExceeded => {
+                        Some(format!("Rate limit exceeded on circuit {}", circuit_name))
+                    }
+                    RateLimitResult::ServiceLimitExceeded => Some(format!(
+                        "Rate limit exceeded for service {} on circuit {}",
+                        msg_sender, circuit_name
+                    )),
+                };
+
+                if let Some(error_msg) = error_msg {
+                    debug!("Throttling message: {}", error_msg);
+                    counter!(
+                        "splinter.circuit.throttled_messages",
+                        1,
+                        "circuit" => circuit_name.to_string(),
+                        "service" => msg_sender.to_string()
+                    );
+
+                    let mut error_message = CircuitError::new();
+                    error_message.set_correlation_id(msg.get_correlation_id().to_string());
+                    error_message.set_service_id(msg_sender.into());
+                    error_message.set_circuit_name(circuit_name.into());
+                    error_message.set_error(CircuitError_Error::ERROR_RATE_LIMIT_EXCEEDED);
+                    error_message.set_error_message(error_msg);
+
+                    let msg_bytes = error_message.write_to_bytes()?;
+                    let network_msg_bytes =
+                        create_message(msg_bytes, CircuitMessageType::CIRCUIT_ERROR_MESSAGE)?;
+                    sender
+                        .send(context.source_peer_id().clone(), network_msg_bytes)
+                        .map_err(|(recipient, payload)| {
+                            DispatchError::NetworkSendError((recipient.into(), payload))
+                        })?;
+                    return Ok(());
+                }
+            }
+        }
+
         #[cfg(feature = "service-message-handler-dispatch")]
         {
             let to_service = FullyQualifiedServiceId::new(
@@ -104,11 +207,7 @@ impl Handler for CircuitDirectMessageHandler {
         // the msg_recipient is either the service/node id to send the message to or is the
         // peer_id to send back the error message
         let (msg_bytes, msg_recipient) = {
-            if let Some(circuit) = self
-                .routing_table
-                .get_circuit(circuit_name)
-                .map_err(|err| DispatchError::HandleError(err.to_string()))?
-            {
+            if let Some(circuit) = circuit {
                 // Check if the message sender is allowed on the circuit
                 // if the sender is not allowed on the circuit
                 if !circuit
@@ -148,38 +247,72 @@ impl Handler for CircuitDirectMessageHandler {
                         // If the service is on this node send message to the service, otherwise
                         // send the message to the node the service is connected to
                         if node_id != self.node_id {
-                            let node_peer_id: PeerId = {
-                                let peer_id = self
-                                    .routing_table
-                                    .get_node(&node_id)
-                                    .map_err(|err| DispatchError::HandleError(err.to_string()))?
-                                    .ok_or_else(|| {
-                                        DispatchError::HandleError(format!(
-                                            "Node {} not in routing table",
-                                            node_id
-                                        ))
-                                    })?
-                                    .get_peer_auth_token(circuit.authorization_type())
-                                    .map_err(|err| DispatchError::HandleError(err.to_string()))?;
-
-                                let local_peer_id = self
-                                    .routing_table
-                                    .get_node(&self.node_id)
-                                    .map_err(|err| DispatchError::HandleError(err.to_string()))?
-                                    .ok_or_else(|| {
-                                        DispatchError::HandleError(format!(
-                                            "Local Node {} not in routing table",
-                                            node_id
-                                        ))
-                                    })?
-                                    .get_peer_auth_token(circuit.authorization_type())
-                                    .map_err(|err| DispatchError::HandleError(err.to_string()))?;
-
-                                PeerTokenPair::new(peer_id, local_peer_id)
+                            let next_hops = self
+                                .routing_table
+                                .next_hops(circuit_name, &self.node_id, &node_id)
+                                .map_err(|err| DispatchError::HandleError(err.to_string()))?
+                                .into_iter()
+                                .map(|next_hop| {
+                                    let peer_token_pair = self.get_peer_token_pair(
+                                        &next_hop,
+                                        circuit.authorization_type(),
+                                    )?;
+                                    Ok((next_hop, peer_token_pair))
+                                })
+                                .collect::<Result<Vec<_>, DispatchError>>()?;
+                            let (next_hop, peer_token_pair) = select_next_hop(
+                                self.peer_connector.as_ref(),
+                                next_hops,
+                                msg.get_forwarding_header().get_visited_node_ids(),
+                            )?
+                            .ok_or_else(|| {
+                                DispatchError::HandleError(format!(
+                                    "No route to node {} on circuit {}",
+                                    node_id, circuit_name
+                                ))
+                            })?;
+
+                            // Messages that are relayed through an intermediate node carry a
+                            // forwarding header, which is used to detect routing loops
+                            let network_msg_bytes =
+                                if next_hop != node_id || msg.has_forwarding_header() {
+                                    let mut msg = msg.clone();
+                                    let header = if msg.has_forwarding_header() {
+                                        Some(msg.take_forwarding_header())
+                                    } else {
+                                        None
+                                    };
+                                    match add_forwarding_hop(header, &self.node_id, circuit_name) {
+                                        Some(header) => msg.set_forwarding_header(header),
+                                        None => return Ok(()),
+                                    }
+                                    create_message(
+                                        msg.write_to_bytes()?,
+                                        CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+                                    )?
+                                } else {
+                                    network_msg_bytes
+                                };
+
+                            // If the circuit requires guaranteed delivery, the queue stores
+                            // the message to be sent later if it cannot be sent now
+                            #[cfg(feature = "circuit-guaranteed-delivery")]
+                            {
+                                if let (DurabilityType::GuaranteedDelivery, Some(queue)) =
+                                    (circuit.durability(), &self.delivery_queue)
+                                {
+                                    queue.send(
+                                        circuit_name,
+                                        &next_hop,
+                                        &peer_token_pair,
+                                        network_msg_bytes,
+                                        sender,
+                                    )?;
+                                    return Ok(());
+                                }
                             }
-                            .into();
 
-                            (network_msg_bytes, node_peer_id)
+                            (network_msg_bytes, peer_token_pair.into())
                         } else {
                             let peer_id: PeerId = match service.local_peer_id() {
                                 Some(peer_id) => peer_id.clone().into(),
@@ -267,8 +400,68 @@ impl CircuitDirectMessageHandler {
             routing_table,
             #[cfg(feature = "service-message-handler-dispatch")]
             service_dispatcher,
+            #[cfg(feature = "circuit-guaranteed-delivery")]
+            delivery_queue: None,
+            #[cfg(feature = "circuit-rate-limiting")]
+            rate_limiter: None,
+            peer_connector: None,
         }
     }
+
+    /// Returns the peer token pair used to send messages to the provided node on a circuit with
+    /// the given authorization type
+    fn get_peer_token_pair(
+        &self,
+        node_id: &str,
+        authorization_type: &AuthorizationType,
+    ) -> Result<PeerTokenPair, DispatchError> {
+        let peer_id = self
+            .routing_table
+            .get_node(node_id)
+            .map_err(|err| DispatchError::HandleError(err.to_string()))?
+            .ok_or_else(|| {
+                DispatchError::HandleError(format!("Node {} not in routing table", node_id))
+            })?
+            .get_peer_auth_token(authorization_type)
+            .map_err(|err| DispatchError::HandleError(err.to_string()))?;
+
+        let local_peer_id = self
+            .routing_table
+            .get_node(&self.node_id)
+            .map_err(|err| DispatchError::HandleError(err.to_string()))?
+            .ok_or_else(|| {
+                DispatchError::HandleError(format!(
+                    "Local Node {} not in routing table",
+                    self.node_id
+                ))
+            })?
+            .get_peer_auth_token(authorization_type)
+            .map_err(|err| DispatchError::HandleError(err.to_string()))?;
+
+        Ok(PeerTokenPair::new(peer_id, local_peer_id))
+    }
+
+    /// Sets the queue used to store messages for circuits with guaranteed delivery when the
+    /// recipient node is not connected
+    #[cfg(feature = "circuit-guaranteed-delivery")]
+    pub fn with_delivery_queue(mut self, delivery_queue: GuaranteedDeliveryQueue) -> Self {
+        self.delivery_queue = Some(delivery_queue);
+        self
+    }
+
+    /// Sets the rate limiter used to throttle the messages sent by services on circuits
+    #[cfg(feature = "circuit-rate-limiting")]
+    pub fn with_rate_limiter(mut self, rate_limiter: CircuitRateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Sets the peer connector used to check which relay nodes are connected, so that messages
+    /// on circuits with the relay route type are sent through a relay node that is available
+    pub fn with_peer_connector(mut self, peer_connector: PeerManagerConnector) -> Self {
+        self.peer_connector = Some(peer_connector);
+        self
+    }
 }
 
 #[cfg(test)]
@@ -278,13 +471,14 @@ mod tests {
     use std::collections::VecDeque;
     use std::sync::{Arc, Mutex};
 
+    use crate::circuit::handlers::MAX_FORWARDING_HOPS;
     use crate::circuit::routing::AuthorizationType;
     use crate::circuit::routing::{
-        memory::RoutingTable, Circuit, CircuitNode, RoutingTableWriter, Service,
+        memory::RoutingTable, Circuit, CircuitNode, RouteType, RoutingTableWriter, Service,
     };
     use crate::network::dispatch::Dispatcher;
     use crate::peer::PeerAuthorizationToken;
-    use crate::protos::circuit::CircuitMessage;
+    use crate::protos::circuit::{CircuitMessage, ForwardingHeader};
     use crate::protos::network::NetworkMessage;
 
     #[cfg(feature = "service-message-handler-dispatch")]
@@ -505,6 +699,135 @@ mod tests {
         )
     }
 
+    // Test that a direct message on a circuit with a relay route type is forwarded through the
+    // relay node with a forwarding header when neither node is a relay node, and that a message
+    // that has already been forwarded by the local node is dropped
+    #[test]
+    fn test_circuit_direct_message_handler_relay() {
+        // Set up dispatcher and mock sender
+        let mock_sender = MockSender::new();
+        let mut dispatcher = Dispatcher::new(Box::new(mock_sender.clone()));
+
+        let table = RoutingTable::default();
+        let reader: Box<dyn RoutingTableReader> = Box::new(table.clone());
+        let mut writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let node_123 = CircuitNode::new("123".to_string(), vec!["123.0.0.1:0".to_string()], None);
+        let node_345 = CircuitNode::new("345".to_string(), vec!["123.0.0.1:1".to_string()], None);
+        let node_678 = CircuitNode::new("678".to_string(), vec!["123.0.0.1:2".to_string()], None);
+
+        let service_abc = Service::new(
+            "b0001".to_string(),
+            "test".to_string(),
+            "123".to_string(),
+            vec![],
+        );
+        let mut service_def = Service::new(
+            "a0001".to_string(),
+            "test".to_string(),
+            "345".to_string(),
+            vec![],
+        );
+
+        service_def.set_local_peer_id(PeerTokenPair::new(
+            PeerAuthorizationToken::from_peer_id("def_network"),
+            PeerAuthorizationToken::from_peer_id("345"),
+        ));
+
+        // Add a circuit where node 678 relays messages for the other members
+        let circuit = Circuit::new(
+            "Alpha-00000".into(),
+            vec![service_abc.clone(), service_def.clone()],
+            vec!["123".into(), "345".into(), "678".into()],
+            AuthorizationType::Trust,
+        )
+        .with_route_type(RouteType::Relay(vec!["678".to_string()]));
+
+        writer
+            .add_circuit(
+                circuit.circuit_id().into(),
+                circuit,
+                vec![node_123, node_345, node_678],
+            )
+            .expect("Unable to add circuits");
+
+        // Add direct message handler to dispatcher
+        let handler = CircuitDirectMessageHandler::new(
+            "345".to_string(),
+            reader.clone(),
+            #[cfg(feature = "service-message-handler-dispatch")]
+            new_service_dispatcher(mock_sender.clone(), reader),
+        );
+
+        dispatcher.set_handler(Box::new(handler));
+
+        // create dispatch message
+        let mut direct_message = CircuitDirectMessage::new();
+        direct_message.set_circuit("Alpha-00000".into());
+        direct_message.set_sender("a0001".into());
+        direct_message.set_recipient("b0001".into());
+        direct_message.set_payload(b"test".to_vec());
+        direct_message.set_correlation_id("1234".into());
+        let direct_bytes = direct_message.write_to_bytes().unwrap();
+
+        // dispatch the message
+        dispatcher
+            .dispatch(
+                PeerTokenPair::new(
+                    PeerAuthorizationToken::from_peer_id("def"),
+                    PeerAuthorizationToken::from_peer_id("345"),
+                )
+                .into(),
+                &CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+                direct_bytes,
+            )
+            .unwrap();
+
+        let (id, message) = mock_sender.next_outbound().expect("No message was sent");
+        assert_network_message(
+            message,
+            id.into(),
+            PeerTokenPair::new(
+                PeerAuthorizationToken::from_peer_id("678"),
+                PeerAuthorizationToken::from_peer_id("345"),
+            ),
+            CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+            |msg: CircuitDirectMessage| {
+                assert_eq!(msg.get_recipient(), "b0001");
+                assert_eq!(msg.get_payload().to_vec(), b"test".to_vec());
+                let header = msg.get_forwarding_header();
+                assert_eq!(header.get_origin_node_id(), "345");
+                assert_eq!(
+                    header.get_visited_node_ids().to_vec(),
+                    vec!["345".to_string()]
+                );
+                assert_eq!(header.get_hops_remaining(), MAX_FORWARDING_HOPS - 1);
+            },
+        );
+
+        // dispatch a message that has already been forwarded by this node
+        let mut header = ForwardingHeader::new();
+        header.set_origin_node_id("345".into());
+        header.set_visited_node_ids(vec!["345".to_string(), "678".to_string()].into());
+        header.set_hops_remaining(MAX_FORWARDING_HOPS - 2);
+        direct_message.set_forwarding_header(header);
+        let direct_bytes = direct_message.write_to_bytes().unwrap();
+
+        dispatcher
+            .dispatch(
+                PeerTokenPair::new(
+                    PeerAuthorizationToken::from_peer_id("678"),
+                    PeerAuthorizationToken::from_peer_id("345"),
+                )
+                .into(),
+                &CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+                direct_bytes,
+            )
+            .unwrap();
+
+        assert!(mock_sender.next_outbound().is_none());
+    }
+
     // Test that an error message is returned if the sender is not in the circuit roster
     #[test]
     fn test_circuit_direct_message_handler_sender_not_in_circuit_roster() {
@@ -753,6 +1076,226 @@ mod tests {
         )
     }
 
+    // Test that an error message is returned, before the message is routed, if the sender has
+    // exceeded its rate limit, and that messages from services that are not in the circuit's
+    // roster are rejected without using up the circuit's limit
+    #[cfg(feature = "circuit-rate-limiting")]
+    #[test]
+    fn test_circuit_direct_message_handler_rate_limited() {
+        use crate::circuit::rate_limit::{CircuitRateLimiterBuilder, RateLimit};
+
+        // Set up dispatcher and mock sender
+        let mock_sender = MockSender::new();
+        let mut dispatcher = Dispatcher::new(Box::new(mock_sender.clone()));
+
+        let table = RoutingTable::default();
+        let reader: Box<dyn RoutingTableReader> = Box::new(table.clone());
+        let mut writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let node_123 = CircuitNode::new("123".to_string(), vec!["123.0.0.1:0".to_string()], None);
+        let node_345 = CircuitNode::new("345".to_string(), vec!["123.0.0.1:1".to_string()], None);
+
+        let mut service_def = Service::new(
+            "a0001".to_string(),
+            "test".to_string(),
+            "345".to_string(),
+            vec![],
+        );
+
+        service_def.set_local_peer_id(PeerTokenPair::new(
+            PeerAuthorizationToken::from_peer_id("def_network"),
+            PeerAuthorizationToken::from_peer_id("345"),
+        ));
+
+        // Add circuit and service to splinter state
+        let circuit = Circuit::new(
+            "Alpha-00000".into(),
+            vec![service_def.clone()],
+            vec!["123".into(), "345".into()],
+            AuthorizationType::Trust,
+        );
+
+        writer
+            .add_circuit(
+                circuit.circuit_id().into(),
+                circuit,
+                vec![node_123, node_345],
+            )
+            .expect("Unable to add circuits");
+
+        // add direct message handler, allowing a single message on the circuit, to the dispatcher
+        let rate_limiter = CircuitRateLimiterBuilder::new()
+            .with_circuit_limit(RateLimit::new(1, 1).expect("Unable to create rate limit"))
+            .build();
+        let handler = CircuitDirectMessageHandler::new(
+            "345".to_string(),
+            reader.clone(),
+            #[cfg(feature = "service-message-handler-dispatch")]
+            new_service_dispatcher(mock_sender.clone(), reader),
+        )
+        .with_rate_limiter(rate_limiter);
+        dispatcher.set_handler(Box::new(handler));
+
+        let expected_errors = [
+            (
+                "c0001",
+                CircuitError_Error::ERROR_SENDER_NOT_IN_CIRCUIT_ROSTER,
+            ),
+            (
+                "c0001",
+                CircuitError_Error::ERROR_SENDER_NOT_IN_CIRCUIT_ROSTER,
+            ),
+            (
+                "a0001",
+                CircuitError_Error::ERROR_RECIPIENT_NOT_IN_CIRCUIT_ROSTER,
+            ),
+            ("a0001", CircuitError_Error::ERROR_RATE_LIMIT_EXCEEDED),
+        ];
+        for (msg_sender, expected_error) in expected_errors.iter() {
+            // create direct message
+            let mut direct_message = CircuitDirectMessage::new();
+            direct_message.set_circuit("Alpha-00000".into());
+            direct_message.set_sender(msg_sender.to_string());
+            direct_message.set_recipient("b0001".into());
+            direct_message.set_payload(b"test".to_vec());
+            direct_message.set_correlation_id("1234".into());
+            let direct_bytes = direct_message.write_to_bytes().unwrap();
+
+            // dispatch message
+            dispatcher
+                .dispatch(
+                    PeerTokenPair::new(
+                        PeerAuthorizationToken::from_peer_id("def"),
+                        PeerAuthorizationToken::from_peer_id("345"),
+                    )
+                    .into(),
+                    &CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+                    direct_bytes,
+                )
+                .unwrap();
+
+            let (id, message) = mock_sender.next_outbound().expect("No message was sent");
+            assert_network_message(
+                message,
+                id.into(),
+                PeerTokenPair::new(
+                    PeerAuthorizationToken::from_peer_id("def"),
+                    PeerAuthorizationToken::from_peer_id("345"),
+                ),
+                CircuitMessageType::CIRCUIT_ERROR_MESSAGE,
+                |msg: CircuitError| {
+                    assert_eq!(msg.get_service_id(), *msg_sender);
+                    assert_eq!(&msg.get_error(), expected_error);
+                    assert_eq!(msg.get_correlation_id(), "1234");
+                },
+            )
+        }
+    }
+
+    // Test that an error message is returned if the circuit is suspended
+    #[test]
+    fn test_circuit_direct_message_handler_suspended_circuit() {
+        // Set up dispatcher and mock sender
+        let mock_sender = MockSender::new();
+        let mut dispatcher = Dispatcher::new(Box::new(mock_sender.clone()));
+
+        let table = RoutingTable::default();
+        let reader: Box<dyn RoutingTableReader> = Box::new(table.clone());
+        let mut writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let node_123 = CircuitNode::new("123".to_string(), vec!["123.0.0.1:0".to_string()], None);
+        let node_345 = CircuitNode::new("345".to_string(), vec!["123.0.0.1:1".to_string()], None);
+
+        let mut service_abc = Service::new(
+            "abc".to_string(),
+            "test".to_string(),
+            "123".to_string(),
+            vec![],
+        );
+        let mut service_def = Service::new(
+            "def".to_string(),
+            "test".to_string(),
+            "345".to_string(),
+            vec![],
+        );
+
+        service_abc.set_local_peer_id(PeerTokenPair::new(
+            PeerAuthorizationToken::from_peer_id("abc_network"),
+            PeerAuthorizationToken::from_peer_id("123"),
+        ));
+        service_def.set_local_peer_id(PeerTokenPair::new(
+            PeerAuthorizationToken::from_peer_id("def_network"),
+            PeerAuthorizationToken::from_peer_id("345"),
+        ));
+
+        // Add a suspended circuit and services to splinter state
+        let circuit = Circuit::new(
+            "alpha".into(),
+            vec![service_abc.clone(), service_def.clone()],
+            vec!["123".into(), "345".into()],
+            AuthorizationType::Trust,
+        );
+
+        writer
+            .add_circuit(
+                circuit.circuit_id().into(),
+                circuit,
+                vec![node_123, node_345],
+            )
+            .expect("Unable to add circuits");
+        writer
+            .set_circuit_suspended("alpha", true)
+            .expect("Unable to suspend circuit");
+
+        // add direct message handler to the dispatcher
+        let handler = CircuitDirectMessageHandler::new(
+            "123".to_string(),
+            reader.clone(),
+            #[cfg(feature = "service-message-handler-dispatch")]
+            new_service_dispatcher(mock_sender.clone(), reader),
+        );
+
+        dispatcher.set_handler(Box::new(handler));
+
+        // create direct message
+        let mut direct_message = CircuitDirectMessage::new();
+        direct_message.set_circuit("alpha".into());
+        direct_message.set_sender("def".into());
+        direct_message.set_recipient("abc".into());
+        direct_message.set_payload(b"test".to_vec());
+        direct_message.set_correlation_id("1234".into());
+        let direct_bytes = direct_message.write_to_bytes().unwrap();
+
+        // dispatcher message
+        dispatcher
+            .dispatch(
+                PeerTokenPair::new(
+                    PeerAuthorizationToken::from_peer_id("def_network"),
+                    PeerAuthorizationToken::from_peer_id("345"),
+                )
+                .into(),
+                &CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+                direct_bytes,
+            )
+            .unwrap();
+
+        let (id, message) = mock_sender.next_outbound().expect("No message was sent");
+        assert_network_message(
+            message,
+            id.into(),
+            PeerTokenPair::new(
+                PeerAuthorizationToken::from_peer_id("def_network"),
+                PeerAuthorizationToken::from_peer_id("345"),
+            ),
+            CircuitMessageType::CIRCUIT_ERROR_MESSAGE,
+            |msg: CircuitError| {
+                assert_eq!(msg.get_service_id(), "def");
+                assert_eq!(msg.get_error(), CircuitError_Error::ERROR_CIRCUIT_SUSPENDED);
+                assert_eq!(msg.get_correlation_id(), "1234");
+            },
+        )
+    }
+
     #[cfg(feature = "service-message-handler-dispatch")]
     #[test]
     fn test_circuit_direct_message_handler_via_service_dispatcher() {
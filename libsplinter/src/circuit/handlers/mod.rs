@@ -20,9 +20,15 @@ mod service_handlers;
 
 use protobuf::Message;
 
-use crate::protos::circuit::{CircuitMessage, CircuitMessageType};
+use crate::network::dispatch::DispatchError;
+use crate::peer::{PeerManagerConnector, PeerTokenPair};
+use crate::protos::circuit::{CircuitMessage, CircuitMessageType, ForwardingHeader};
 use crate::protos::network::{NetworkMessage, NetworkMessageType};
 
+/// The maximum number of times a message may be forwarded before it is dropped, which bounds the
+/// number of relay nodes a message may pass through
+const MAX_FORWARDING_HOPS: u32 = 8;
+
 pub use self::admin_message::AdminDirectMessageHandler;
 pub use self::circuit_error::CircuitErrorHandler;
 pub use self::circuit_message::CircuitMessageHandler;
@@ -44,3 +50,199 @@ fn create_message(
     network_msg.set_payload(circuit_bytes);
     network_msg.write_to_bytes()
 }
+
+/// Adds the local node to the forwarding header of a message that is sent through a relay node,
+/// creating the header if this node is the first to forward the message.
+///
+/// Returns `None` if the message has already been forwarded by this node or has no hops
+/// remaining, in which case it must be dropped.
+fn add_forwarding_hop(
+    header: Option<ForwardingHeader>,
+    node_id: &str,
+    circuit_id: &str,
+) -> Option<ForwardingHeader> {
+    let mut header = header.unwrap_or_else(|| {
+        let mut header = ForwardingHeader::new();
+        header.set_origin_node_id(node_id.to_string());
+        header.set_hops_remaining(MAX_FORWARDING_HOPS);
+        header
+    });
+
+    if header
+        .get_visited_node_ids()
+        .iter()
+        .any(|visited_node_id| visited_node_id == node_id)
+    {
+        warn!(
+            "Dropping message from {} on {}: message has already been forwarded by this node",
+            header.get_origin_node_id(),
+            circuit_id
+        );
+        return None;
+    }
+
+    if header.get_hops_remaining() == 0 {
+        warn!(
+            "Dropping message from {} on {}: message has no hops remaining",
+            header.get_origin_node_id(),
+            circuit_id
+        );
+        return None;
+    }
+
+    header.mut_visited_node_ids().push(node_id.to_string());
+    header.set_hops_remaining(header.get_hops_remaining() - 1);
+    Some(header)
+}
+
+/// Selects the node a message is sent to from the possible next hops, which are listed in order
+/// of preference along with the peer they are reached through.
+///
+/// Next hops that have already forwarded the message are skipped, unless every next hop has, so
+/// that a message relayed between relay nodes is not sent back to a node it passed through.
+///
+/// If there is more than one next hop and a peer connector is provided, the first next hop the
+/// local node is connected to is selected, so that messages fail over to another relay node when
+/// one is unavailable. Otherwise the first next hop is selected.
+fn select_next_hop(
+    peer_connector: Option<&PeerManagerConnector>,
+    mut next_hops: Vec<(String, PeerTokenPair)>,
+    visited_node_ids: &[String],
+) -> Result<Option<(String, PeerTokenPair)>, DispatchError> {
+    if next_hops
+        .iter()
+        .any(|(node_id, _)| !visited_node_ids.contains(node_id))
+    {
+        next_hops.retain(|(node_id, _)| !visited_node_ids.contains(node_id));
+    }
+
+    if next_hops.len() > 1 {
+        if let Some(peer_connector) = peer_connector {
+            let connection_ids = peer_connector
+                .connection_ids()
+                .map_err(|err| DispatchError::HandleError(err.to_string()))?;
+            if let Some(index) = next_hops
+                .iter()
+                .position(|(_, peer_id)| connection_ids.get_by_key(peer_id).is_some())
+            {
+                return Ok(Some(next_hops.swap_remove(index)));
+            }
+        }
+    }
+
+    Ok(next_hops.into_iter().next())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::mpsc::channel;
+    use std::thread;
+
+    use crate::collections::BiHashMap;
+    use crate::peer::{PeerAuthorizationToken, PeerManagerMessage, PeerManagerRequest};
+
+    // Test that the first connected next hop is selected when a peer connector is provided
+    //
+    // 1. Start a thread that answers connection ID requests with a connection to relay-2 only
+    // 2. Check that relay-2 is selected from relay-1 and relay-2
+    // 3. Check that relay-1 is selected if no peer connector is provided
+    // 4. Check that the first next hop is selected if none of them are connected
+    // 5. Check that a next hop the message has already visited is skipped, unless every next hop
+    //    has been visited
+    #[test]
+    fn test_select_next_hop() {
+        let (sender, receiver) = channel();
+        let peer_connector = PeerManagerConnector::new(sender);
+
+        let relay_1 = (
+            "relay-1".to_string(),
+            PeerTokenPair::new(
+                PeerAuthorizationToken::from_peer_id("relay-1"),
+                PeerAuthorizationToken::from_peer_id("local"),
+            ),
+        );
+        let relay_2 = (
+            "relay-2".to_string(),
+            PeerTokenPair::new(
+                PeerAuthorizationToken::from_peer_id("relay-2"),
+                PeerAuthorizationToken::from_peer_id("local"),
+            ),
+        );
+
+        let connected_peer = relay_2.1.clone();
+        let join_handle = thread::spawn(move || {
+            for message in receiver.iter() {
+                if let PeerManagerMessage::Request(PeerManagerRequest::ConnectionIds { sender }) =
+                    message
+                {
+                    let mut connection_ids = BiHashMap::new();
+                    connection_ids.insert(connected_peer.clone(), "connection-2".to_string());
+                    sender
+                        .send(Ok(connection_ids))
+                        .expect("Unable to send connection IDs");
+                }
+            }
+        });
+
+        assert_eq!(
+            select_next_hop(
+                Some(&peer_connector),
+                vec![relay_1.clone(), relay_2.clone()],
+                &[]
+            )
+            .expect("Unable to select next hop"),
+            Some(relay_2.clone())
+        );
+        assert_eq!(
+            select_next_hop(None, vec![relay_1.clone(), relay_2.clone()], &[])
+                .expect("Unable to select next hop"),
+            Some(relay_1.clone())
+        );
+        assert_eq!(
+            select_next_hop(Some(&peer_connector), vec![relay_1.clone()], &[])
+                .expect("Unable to select next hop"),
+            Some(relay_1.clone())
+        );
+
+        let relay_3 = (
+            "relay-3".to_string(),
+            PeerTokenPair::new(
+                PeerAuthorizationToken::from_peer_id("relay-3"),
+                PeerAuthorizationToken::from_peer_id("local"),
+            ),
+        );
+        assert_eq!(
+            select_next_hop(
+                Some(&peer_connector),
+                vec![relay_3.clone(), relay_1.clone()],
+                &[]
+            )
+            .expect("Unable to select next hop"),
+            Some(relay_3.clone())
+        );
+
+        assert_eq!(
+            select_next_hop(
+                Some(&peer_connector),
+                vec![relay_1.clone(), relay_2.clone(), relay_3.clone()],
+                &["relay-2".to_string()]
+            )
+            .expect("Unable to select next hop"),
+            Some(relay_1.clone())
+        );
+        assert_eq!(
+            select_next_hop(
+                Some(&peer_connector),
+                vec![relay_1, relay_2.clone()],
+                &["relay-1".to_string(), "relay-2".to_string()]
+            )
+            .expect("Unable to select next hop"),
+            Some(relay_2)
+        );
+
+        drop(peer_connector);
+        join_handle.join().expect("Unable to join thread");
+    }
+}
@@ -13,6 +13,8 @@
 // limitations under the License.
 
 use crate::circuit::handlers::create_message;
+#[cfg(feature = "circuit-rate-limiting")]
+use crate::circuit::rate_limit::{CircuitRateLimiter, RateLimitResult};
 use crate::circuit::routing::{RoutingTableReader, RoutingTableWriter, Service, ServiceId};
 use crate::network::dispatch::{DispatchError, Handler, MessageContext, MessageSender, PeerId};
 use crate::peer::PeerTokenPair;
@@ -32,6 +34,8 @@ pub struct ServiceConnectRequestHandler {
     node_id: String,
     routing_table_reader: Box<dyn RoutingTableReader>,
     routing_table_writer: Box<dyn RoutingTableWriter>,
+    #[cfg(feature = "circuit-rate-limiting")]
+    rate_limiter: Option<CircuitRateLimiter>,
 }
 
 impl Handler for ServiceConnectRequestHandler {
@@ -104,6 +108,14 @@ impl Handler for ServiceConnectRequestHandler {
                 } else if service.node_id() != self.node_id {
                     response.set_status(ServiceConnectResponse_Status::ERROR_NOT_AN_ALLOWED_NODE);
                     response.set_error_message(format!("{} is not allowed on this node", unique_id))
+                } else if self.is_rate_limited(circuit_name, service_id)? {
+                    // If the service has exceeded its rate limit, return an error response
+                    // without connecting the service
+                    response.set_status(ServiceConnectResponse_Status::ERROR_RATE_LIMIT_EXCEEDED);
+                    response.set_error_message(format!(
+                        "Rate limit exceeded for service {} on circuit {}",
+                        service_id, circuit_name
+                    ))
                 } else {
                     service
                         .set_local_peer_id(PeerTokenPair::from(context.source_peer_id().clone()));
@@ -157,8 +169,44 @@ impl ServiceConnectRequestHandler {
             node_id,
             routing_table_reader,
             routing_table_writer,
+            #[cfg(feature = "circuit-rate-limiting")]
+            rate_limiter: None,
         }
     }
+
+    /// Sets the rate limiter used to throttle the connection requests of services
+    #[cfg(feature = "circuit-rate-limiting")]
+    pub fn with_rate_limiter(mut self, rate_limiter: CircuitRateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Checks the connection request of a service, which has been validated against its circuit's
+    /// roster, against the configured rate limits. Returns `true` if the request is throttled.
+    #[cfg(feature = "circuit-rate-limiting")]
+    fn is_rate_limited(&self, circuit_name: &str, service_id: &str) -> Result<bool, DispatchError> {
+        let rate_limiter = match &self.rate_limiter {
+            Some(rate_limiter) => rate_limiter,
+            None => return Ok(false),
+        };
+
+        if rate_limiter.check(circuit_name, service_id)? == RateLimitResult::Allowed {
+            return Ok(false);
+        }
+
+        counter!(
+            "splinter.circuit.throttled_messages",
+            1,
+            "circuit" => circuit_name.to_string(),
+            "service" => service_id.to_string()
+        );
+        Ok(true)
+    }
+
+    #[cfg(not(feature = "circuit-rate-limiting"))]
+    fn is_rate_limited(&self, _: &str, _: &str) -> Result<bool, DispatchError> {
+        Ok(false)
+    }
 }
 
 // Implements a handler that handles ServiceDisconnectRequest
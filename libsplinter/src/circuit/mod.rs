@@ -14,7 +14,11 @@
 
 //! Circuit routing and message handling.
 
+#[cfg(feature = "circuit-guaranteed-delivery")]
+pub mod delivery;
 pub mod handlers;
+#[cfg(feature = "circuit-rate-limiting")]
+pub mod rate_limit;
 pub mod routing;
 #[cfg(feature = "circuit-template")]
 pub mod template;
@@ -0,0 +1,449 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Rate limiting of the messages that services send on circuits.
+//!
+//! A [`CircuitRateLimiter`] enforces token-bucket limits on the messages handled by the
+//! [`CircuitDirectMessageHandler`] and the [`ServiceConnectRequestHandler`]. A limit can be set
+//! for all messages on a circuit, for the messages from each service on a circuit, or both.
+//! Default limits apply to every circuit and service, and can be overridden for specific
+//! circuits and services.
+//!
+//! When a limit is exceeded, the handlers reply with an error instead of routing the message.
+//!
+//! [`CircuitDirectMessageHandler`]: ../handlers/struct.CircuitDirectMessageHandler.html
+//! [`ServiceConnectRequestHandler`]: ../handlers/struct.ServiceConnectRequestHandler.html
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use crate::error::{InternalError, InvalidArgumentError};
+
+/// A token-bucket limit on the number of messages that may be sent.
+///
+/// The bucket holds at most `burst` tokens and is refilled at `messages_per_second` tokens per
+/// second. Each message takes one token; a message is throttled if the bucket is empty.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RateLimit {
+    messages_per_second: f64,
+    burst: f64,
+}
+
+impl RateLimit {
+    /// Creates a new `RateLimit`.
+    ///
+    /// # Arguments
+    ///
+    /// * `messages_per_second` - The rate at which the bucket is refilled
+    /// * `burst` - The maximum number of messages that may be sent at once
+    pub fn new(messages_per_second: u32, burst: u32) -> Result<Self, InvalidArgumentError> {
+        if messages_per_second == 0 {
+            return Err(InvalidArgumentError::new(
+                "messages_per_second",
+                "must be greater than 0",
+            ));
+        }
+
+        if burst == 0 {
+            return Err(InvalidArgumentError::new("burst", "must be greater than 0"));
+        }
+
+        Ok(Self {
+            messages_per_second: f64::from(messages_per_second),
+            burst: f64::from(burst),
+        })
+    }
+
+    /// Returns the rate at which the bucket is refilled
+    pub fn messages_per_second(&self) -> u32 {
+        self.messages_per_second as u32
+    }
+
+    /// Returns the maximum number of messages that may be sent at once
+    pub fn burst(&self) -> u32 {
+        self.burst as u32
+    }
+}
+
+/// The result of checking a message against the configured rate limits
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RateLimitResult {
+    /// The message is within the limits and may be routed
+    Allowed,
+    /// The limit for all messages on the circuit has been exceeded
+    CircuitLimitExceeded,
+    /// The limit for messages from the sending service has been exceeded
+    ServiceLimitExceeded,
+}
+
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(limit: &RateLimit, now: Instant) -> Self {
+        Self {
+            tokens: limit.burst,
+            last_refill: now,
+        }
+    }
+
+    fn refill(&mut self, limit: &RateLimit, now: Instant) {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.tokens = (self.tokens + elapsed * limit.messages_per_second).min(limit.burst);
+        self.last_refill = now;
+    }
+
+    fn has_token(&self) -> bool {
+        self.tokens >= 1.0
+    }
+
+    fn take(&mut self) {
+        self.tokens -= 1.0;
+    }
+}
+
+#[derive(Default)]
+struct Limits {
+    circuit_limit: Option<RateLimit>,
+    circuit_limits: HashMap<String, RateLimit>,
+    service_limit: Option<RateLimit>,
+    service_limits: HashMap<(String, String), RateLimit>,
+}
+
+impl Limits {
+    fn circuit_limit(&self, circuit_id: &str) -> Option<RateLimit> {
+        self.circuit_limits
+            .get(circuit_id)
+            .copied()
+            .or(self.circuit_limit)
+    }
+
+    fn service_limit(&self, circuit_id: &str, service_id: &str) -> Option<RateLimit> {
+        self.service_limits
+            .get(&(circuit_id.to_string(), service_id.to_string()))
+            .copied()
+            .or(self.service_limit)
+    }
+}
+
+#[derive(Default)]
+struct Buckets {
+    circuits: HashMap<String, TokenBucket>,
+    services: HashMap<(String, String), TokenBucket>,
+}
+
+/// Enforces the configured rate limits on the messages sent on circuits.
+///
+/// The limiter may be cloned and shared between handlers; all clones share the same buckets.
+#[derive(Clone)]
+pub struct CircuitRateLimiter {
+    limits: Arc<Limits>,
+    buckets: Arc<Mutex<Buckets>>,
+}
+
+impl CircuitRateLimiter {
+    /// Checks whether a message from the given service on the given circuit is within the
+    /// configured limits.
+    ///
+    /// If the message is allowed, a token is taken from both the circuit's and the service's
+    /// bucket; if it is throttled, no tokens are taken.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit_id` - The circuit the message is sent on
+    /// * `service_id` - The service that sent the message
+    pub fn check(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+    ) -> Result<RateLimitResult, InternalError> {
+        self.check_at(circuit_id, service_id, Instant::now())
+    }
+
+    fn check_at(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+        now: Instant,
+    ) -> Result<RateLimitResult, InternalError> {
+        let circuit_limit = self.limits.circuit_limit(circuit_id);
+        let service_limit = self.limits.service_limit(circuit_id, service_id);
+
+        if circuit_limit.is_none() && service_limit.is_none() {
+            return Ok(RateLimitResult::Allowed);
+        }
+
+        let mut buckets = self
+            .buckets
+            .lock()
+            .map_err(|_| InternalError::with_message("Rate limiter lock poisoned".into()))?;
+        let Buckets { circuits, services } = &mut *buckets;
+
+        let mut service_bucket = match service_limit {
+            Some(limit) => {
+                let bucket = services
+                    .entry((circuit_id.to_string(), service_id.to_string()))
+                    .or_insert_with(|| TokenBucket::new(&limit, now));
+                bucket.refill(&limit, now);
+                if !bucket.has_token() {
+                    return Ok(RateLimitResult::ServiceLimitExceeded);
+                }
+                Some(bucket)
+            }
+            None => None,
+        };
+
+        if let Some(limit) = circuit_limit {
+            let bucket = circuits
+                .entry(circuit_id.to_string())
+                .or_insert_with(|| TokenBucket::new(&limit, now));
+            bucket.refill(&limit, now);
+            if !bucket.has_token() {
+                return Ok(RateLimitResult::CircuitLimitExceeded);
+            }
+            bucket.take();
+        }
+
+        if let Some(bucket) = service_bucket.as_mut() {
+            bucket.take();
+        }
+
+        Ok(RateLimitResult::Allowed)
+    }
+
+    /// Removes the buckets of a circuit and its services, for example when the circuit has been
+    /// disbanded.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit_id` - The circuit whose buckets should be removed
+    pub fn remove_circuit(&self, circuit_id: &str) -> Result<(), InternalError> {
+        let mut buckets = self
+            .buckets
+            .lock()
+            .map_err(|_| InternalError::with_message("Rate limiter lock poisoned".into()))?;
+        buckets.circuits.remove(circuit_id);
+        buckets
+            .services
+            .retain(|(service_circuit_id, _), _| service_circuit_id != circuit_id);
+        Ok(())
+    }
+}
+
+/// Builds a new [`CircuitRateLimiter`]
+#[derive(Default)]
+pub struct CircuitRateLimiterBuilder {
+    limits: Limits,
+}
+
+impl CircuitRateLimiterBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the default limit on all messages sent on a circuit
+    pub fn with_circuit_limit(mut self, limit: RateLimit) -> Self {
+        self.limits.circuit_limit = Some(limit);
+        self
+    }
+
+    /// Sets the limit on all messages sent on the given circuit, overriding the default circuit
+    /// limit
+    pub fn with_circuit_limit_for(mut self, circuit_id: &str, limit: RateLimit) -> Self {
+        self.limits
+            .circuit_limits
+            .insert(circuit_id.to_string(), limit);
+        self
+    }
+
+    /// Sets the default limit on the messages sent by a single service on a circuit
+    pub fn with_service_limit(mut self, limit: RateLimit) -> Self {
+        self.limits.service_limit = Some(limit);
+        self
+    }
+
+    /// Sets the limit on the messages sent by the given service on the given circuit,
+    /// overriding the default service limit
+    pub fn with_service_limit_for(
+        mut self,
+        circuit_id: &str,
+        service_id: &str,
+        limit: RateLimit,
+    ) -> Self {
+        self.limits
+            .service_limits
+            .insert((circuit_id.to_string(), service_id.to_string()), limit);
+        self
+    }
+
+    pub fn build(self) -> CircuitRateLimiter {
+        CircuitRateLimiter {
+            limits: Arc::new(self.limits),
+            buckets: Arc::new(Mutex::new(Buckets::default())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    /// Verifies that a service is throttled once its burst is used up and is allowed to send
+    /// again after the bucket has been refilled.
+    #[test]
+    fn test_service_limit() {
+        let limiter = CircuitRateLimiterBuilder::new()
+            .with_service_limit(RateLimit::new(1, 2).expect("Unable to create limit"))
+            .build();
+        let now = Instant::now();
+
+        assert_eq!(
+            limiter.check_at("circuit", "a000", now).unwrap(),
+            RateLimitResult::Allowed
+        );
+        assert_eq!(
+            limiter.check_at("circuit", "a000", now).unwrap(),
+            RateLimitResult::Allowed
+        );
+        assert_eq!(
+            limiter.check_at("circuit", "a000", now).unwrap(),
+            RateLimitResult::ServiceLimitExceeded
+        );
+        // other services have their own bucket
+        assert_eq!(
+            limiter.check_at("circuit", "b000", now).unwrap(),
+            RateLimitResult::Allowed
+        );
+
+        let later = now + Duration::from_secs(1);
+        assert_eq!(
+            limiter.check_at("circuit", "a000", later).unwrap(),
+            RateLimitResult::Allowed
+        );
+        assert_eq!(
+            limiter.check_at("circuit", "a000", later).unwrap(),
+            RateLimitResult::ServiceLimitExceeded
+        );
+    }
+
+    /// Verifies that the circuit limit is shared by all services on the circuit, that a
+    /// circuit-specific limit overrides the default, and that throttled messages do not take a
+    /// token from the service's bucket.
+    #[test]
+    fn test_circuit_limit() {
+        let limiter = CircuitRateLimiterBuilder::new()
+            .with_circuit_limit(RateLimit::new(1, 1).expect("Unable to create limit"))
+            .with_circuit_limit_for(
+                "busy",
+                RateLimit::new(1, 3).expect("Unable to create limit"),
+            )
+            .with_service_limit(RateLimit::new(1, 2).expect("Unable to create limit"))
+            .build();
+        let now = Instant::now();
+
+        assert_eq!(
+            limiter.check_at("circuit", "a000", now).unwrap(),
+            RateLimitResult::Allowed
+        );
+        assert_eq!(
+            limiter.check_at("circuit", "b000", now).unwrap(),
+            RateLimitResult::CircuitLimitExceeded
+        );
+
+        assert_eq!(
+            limiter.check_at("busy", "a000", now).unwrap(),
+            RateLimitResult::Allowed
+        );
+        assert_eq!(
+            limiter.check_at("busy", "b000", now).unwrap(),
+            RateLimitResult::Allowed
+        );
+        assert_eq!(
+            limiter.check_at("busy", "b000", now).unwrap(),
+            RateLimitResult::Allowed
+        );
+        assert_eq!(
+            limiter.check_at("busy", "c000", now).unwrap(),
+            RateLimitResult::CircuitLimitExceeded
+        );
+
+        let later = now + Duration::from_secs(1);
+        // c000's service bucket was not used by the throttled message
+        assert_eq!(
+            limiter.check_at("busy", "c000", later).unwrap(),
+            RateLimitResult::Allowed
+        );
+    }
+
+    /// Verifies that removing a circuit removes the buckets of the circuit and its services, and
+    /// only those buckets.
+    #[test]
+    fn test_remove_circuit() {
+        let limiter = CircuitRateLimiterBuilder::new()
+            .with_circuit_limit(RateLimit::new(1, 1).expect("Unable to create limit"))
+            .with_service_limit(RateLimit::new(1, 1).expect("Unable to create limit"))
+            .build();
+        let now = Instant::now();
+
+        for circuit_id in ["circuit", "other"].iter() {
+            assert_eq!(
+                limiter.check_at(circuit_id, "a000", now).unwrap(),
+                RateLimitResult::Allowed
+            );
+        }
+
+        limiter
+            .remove_circuit("circuit")
+            .expect("Unable to remove circuit");
+        {
+            let buckets = limiter.buckets.lock().unwrap();
+            assert!(!buckets.circuits.contains_key("circuit"));
+            assert!(buckets.circuits.contains_key("other"));
+            assert_eq!(buckets.services.len(), 1);
+        }
+
+        // The circuit starts with full buckets if it is recreated
+        assert_eq!(
+            limiter.check_at("circuit", "a000", now).unwrap(),
+            RateLimitResult::Allowed
+        );
+        assert_eq!(
+            limiter.check_at("other", "a000", now).unwrap(),
+            RateLimitResult::ServiceLimitExceeded
+        );
+    }
+
+    /// Verifies that messages are not limited if no limits are configured and that a rate limit
+    /// must be greater than zero.
+    #[test]
+    fn test_no_limits() {
+        let limiter = CircuitRateLimiterBuilder::new().build();
+        for _ in 0..100 {
+            assert_eq!(
+                limiter.check("circuit", "a000").unwrap(),
+                RateLimitResult::Allowed
+            );
+        }
+
+        assert!(RateLimit::new(0, 1).is_err());
+        assert!(RateLimit::new(1, 0).is_err());
+    }
+}
@@ -18,7 +18,7 @@
 use super::{
     Circuit, CircuitNode, RoutingTable, RoutingTableReader, RoutingTableWriter, Service, ServiceId,
 };
-use crate::circuit::routing::AuthorizationType;
+use crate::circuit::routing::{AuthorizationType, DurabilityType, RouteType};
 
 extern crate test;
 
@@ -79,6 +79,9 @@ fn generate_circuits(num_circuits: i64, total_num_node: i64) -> (Vec<Circuit>, V
             roster,
             members,
             authorization_type: AuthorizationType::Trust,
+            durability: DurabilityType::NoDurability,
+            route_type: RouteType::Any,
+            suspended: false,
         };
         circuits.push(circuit);
     }
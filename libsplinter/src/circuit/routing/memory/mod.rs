@@ -28,8 +28,8 @@ use std::sync::{Arc, RwLock};
 use super::error::RoutingTableReaderError;
 use super::AuthorizationType;
 use super::{
-    Circuit, CircuitIter, CircuitNode, CircuitNodeIter, RoutingTableReader, RoutingTableWriter,
-    Service, ServiceId,
+    Circuit, CircuitIter, CircuitNode, CircuitNodeIter, RouteType, RoutingTableReader,
+    RoutingTableWriter, Service, ServiceId,
 };
 
 use crate::error::{InternalError, InvalidStateError};
@@ -45,6 +45,8 @@ struct RoutingTableState {
     circuits: BTreeMap<String, Circuit>,
     /// Service ID to Service that contains the node the service is connected to. Not persisted.
     service_directory: HashMap<ServiceId, Service>,
+    /// Node ID to the relay nodes admin messages to that node are sent through. Not persisted.
+    admin_relay_routes: BTreeMap<String, Vec<String>>,
 }
 
 // An implementation of a routing table that uses a read-write lock to wrap the state.
@@ -195,6 +197,79 @@ impl RoutingTableReader for RoutingTable {
         }
     }
 
+    /// Returns the IDs of the nodes a message may be sent to next in order to reach the target
+    /// node on the provided circuit, in order of preference
+    ///
+    /// Messages on a circuit with a relay route type that are sent between two members that are
+    /// not relay nodes may be forwarded through any of the relay nodes, and relay nodes may
+    /// forward messages through the other relay nodes. Admin messages are sent through relay
+    /// nodes if relay routes were added for the target node, or if both nodes are members of a
+    /// circuit with a relay route type that are not relay nodes. All other messages are sent
+    /// directly to the target node, or through the other relay nodes of the circuits the local
+    /// node relays messages for.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit_id` - The unique ID for the circuit the message is sent on
+    /// * `local_node_id` - The ID of the node the message is currently on
+    /// * `target_node_id` - The ID of the node the message is meant for
+    ///
+    /// Returns an error if the lock is poisoned
+    fn next_hops(
+        &self,
+        circuit_id: &str,
+        local_node_id: &str,
+        target_node_id: &str,
+    ) -> Result<Vec<String>, RoutingTableReaderError> {
+        let state = self.state.read().map_err(|_| {
+            RoutingTableReaderError::InternalError(InternalError::with_message(String::from(
+                "RoutingTable lock poisoned",
+            )))
+        })?;
+
+        if circuit_id == ADMIN_CIRCUIT_ID {
+            let mut relay_nodes = state
+                .admin_relay_routes
+                .get(target_node_id)
+                .cloned()
+                .unwrap_or_default();
+            let mut fallback_relay_nodes = vec![];
+            for circuit in state.circuits.values() {
+                if let RouteType::Relay(circuit_relay_nodes) = circuit.route_type() {
+                    let next_hops = circuit.next_hops(local_node_id, target_node_id);
+                    if next_hops == *circuit_relay_nodes {
+                        for relay_node in circuit_relay_nodes {
+                            if !relay_nodes.contains(relay_node) {
+                                relay_nodes.push(relay_node.to_string());
+                            }
+                        }
+                    } else {
+                        // The local node is a relay node, which may reach the target node through
+                        // the other relay nodes
+                        for relay_node in next_hops.into_iter().skip(1) {
+                            if !fallback_relay_nodes.contains(&relay_node) {
+                                fallback_relay_nodes.push(relay_node);
+                            }
+                        }
+                    }
+                }
+            }
+
+            if relay_nodes.is_empty() {
+                let mut next_hops = vec![target_node_id.to_string()];
+                next_hops.extend(fallback_relay_nodes);
+                return Ok(next_hops);
+            }
+            return Ok(relay_nodes);
+        }
+
+        Ok(state
+            .circuits
+            .get(circuit_id)
+            .map(|circuit| circuit.next_hops(local_node_id, target_node_id))
+            .unwrap_or_default())
+    }
+
     fn clone_boxed(&self) -> Box<dyn RoutingTableReader> {
         Box::new(self.clone())
     }
@@ -334,6 +409,30 @@ impl RoutingTableWriter for RoutingTable {
         Ok(())
     }
 
+    /// Marks a circuit in the routing table as suspended or active if it exists.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit_id` - The unique ID for the circuit
+    /// * `suspended` - Whether the circuit is suspended
+    ///
+    /// Returns an error if the lock is poisoned
+    fn set_circuit_suspended(
+        &mut self,
+        circuit_id: &str,
+        suspended: bool,
+    ) -> Result<(), InternalError> {
+        let mut state = self
+            .state
+            .write()
+            .map_err(|_| InternalError::with_message(String::from("RoutingTable lock poisoned")))?;
+
+        if let Some(circuit) = state.circuits.get_mut(circuit_id) {
+            circuit.suspended = suspended;
+        }
+        Ok(())
+    }
+
     /// Adds a new node to the routing table
     ///
     /// # Arguments
@@ -387,6 +486,33 @@ impl RoutingTableWriter for RoutingTable {
         Ok(())
     }
 
+    /// Adds relay nodes through which admin messages to the provided node are sent
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The unique ID for the node the admin messages are meant for
+    /// * `relay_node_ids` - The IDs of the nodes the admin messages may be sent through, in order
+    ///   of preference
+    ///
+    /// Returns an error if the lock is poisoned
+    fn add_admin_relay_route(
+        &mut self,
+        node_id: String,
+        relay_node_ids: Vec<String>,
+    ) -> Result<(), InternalError> {
+        let mut state = self
+            .state
+            .write()
+            .map_err(|_| InternalError::with_message(String::from("RoutingTable lock poisoned")))?;
+        let relay_nodes = state.admin_relay_routes.entry(node_id).or_default();
+        for relay_node_id in relay_node_ids {
+            if !relay_nodes.contains(&relay_node_id) {
+                relay_nodes.push(relay_node_id);
+            }
+        }
+        Ok(())
+    }
+
     fn clone_boxed(&self) -> Box<dyn RoutingTableWriter> {
         Box::new(self.clone())
     }
@@ -396,7 +522,7 @@ impl RoutingTableWriter for RoutingTable {
 mod test {
     use super::*;
 
-    use crate::circuit::routing::AuthorizationType;
+    use crate::circuit::routing::{AuthorizationType, DurabilityType, RouteType};
 
     // Test the routing table read and write operations for circuits
     //
@@ -448,12 +574,18 @@ mod test {
             roster: circuit_roster0.clone(),
             members: circuit_members0.clone(),
             authorization_type: AuthorizationType::Trust,
+            durability: DurabilityType::NoDurability,
+            route_type: RouteType::Any,
+            suspended: false,
         };
         let circuit1 = Circuit {
             circuit_id: "345-def".to_string(),
             roster: circuit_roster1.clone(),
             members: circuit_members1.clone(),
             authorization_type: AuthorizationType::Trust,
+            durability: DurabilityType::NoDurability,
+            route_type: RouteType::Any,
+            suspended: false,
         };
 
         let mut expected_nodes = BTreeMap::new();
@@ -606,6 +738,9 @@ mod test {
             roster: vec![service0.clone(), service1.clone()],
             members: vec![node0.node_id.clone(), node1.node_id.clone()],
             authorization_type: AuthorizationType::Trust,
+            durability: DurabilityType::NoDurability,
+            route_type: RouteType::Any,
+            suspended: false,
         };
         let service_id0 = ServiceId::new(
             "012-abc".to_string(),
@@ -738,4 +873,143 @@ mod test {
             expected_nodes
         );
     }
+
+    // Test that the next hops are computed according to the circuit's route type
+    //
+    // 1. Add a circuit with the `Any` route type and a circuit with the `Relay` route type, where
+    //    node-0 and node-3 are the relay nodes
+    // 2. Check that messages on the `Any` circuit are sent directly to the target node
+    // 3. Check that messages on the `Relay` circuit between two non-relay nodes may be sent to
+    //    either relay node, preferring the first one
+    // 4. Check that messages sent to a relay node are sent directly to the target node, and that
+    //    messages sent from a relay node are sent directly to the target node, falling back to the
+    //    other relay node
+    // 5. Check that no route is returned for an unknown circuit or a node that is not a member
+    // 6. Check that admin messages between two non-relay members of the `Relay` circuit are sent
+    //    through the relay nodes, that admin messages from a relay node fall back to the other
+    //    relay node, and that all other admin messages are sent directly
+    // 7. Add a relay route for admin messages to node-5 and check that it is used
+    #[test]
+    fn test_next_hop() {
+        let routing_table = RoutingTable::default();
+        let mut writer: Box<dyn RoutingTableWriter> = Box::new(routing_table.clone());
+        let reader: Box<dyn RoutingTableReader> = Box::new(routing_table.clone());
+
+        let members: Vec<String> = (0..4).map(|x| format!("node-{}", x)).collect();
+        let nodes: Vec<CircuitNode> = members
+            .iter()
+            .map(|node_id| CircuitNode::new(node_id.to_string(), vec![], None))
+            .collect();
+
+        let any_circuit = Circuit::new(
+            "012-abc".to_string(),
+            vec![],
+            members.clone(),
+            AuthorizationType::Trust,
+        );
+        let relay_circuit = Circuit::new(
+            "345-def".to_string(),
+            vec![],
+            members.clone(),
+            AuthorizationType::Trust,
+        )
+        .with_route_type(RouteType::Relay(vec![
+            "node-0".to_string(),
+            "node-3".to_string(),
+        ]));
+
+        writer
+            .add_circuit("012-abc".to_string(), any_circuit, nodes.clone())
+            .expect("Unable to add circuit");
+        writer
+            .add_circuit("345-def".to_string(), relay_circuit, nodes)
+            .expect("Unable to add circuit");
+
+        assert_eq!(
+            reader
+                .next_hop("012-abc", "node-1", "node-2")
+                .expect("Unable to get next hop"),
+            Some("node-2".to_string())
+        );
+
+        assert_eq!(
+            reader
+                .next_hops("345-def", "node-1", "node-2")
+                .expect("Unable to get next hops"),
+            vec!["node-0".to_string(), "node-3".to_string()]
+        );
+        assert_eq!(
+            reader
+                .next_hop("345-def", "node-1", "node-2")
+                .expect("Unable to get next hop"),
+            Some("node-0".to_string())
+        );
+
+        assert_eq!(
+            reader
+                .next_hop("345-def", "node-0", "node-2")
+                .expect("Unable to get next hop"),
+            Some("node-2".to_string())
+        );
+        assert_eq!(
+            reader
+                .next_hops("345-def", "node-0", "node-2")
+                .expect("Unable to get next hops"),
+            vec!["node-2".to_string(), "node-3".to_string()]
+        );
+        assert_eq!(
+            reader
+                .next_hops("345-def", "node-0", "node-3")
+                .expect("Unable to get next hops"),
+            vec!["node-3".to_string()]
+        );
+        assert_eq!(
+            reader
+                .next_hops("345-def", "node-1", "node-3")
+                .expect("Unable to get next hops"),
+            vec!["node-3".to_string()]
+        );
+
+        assert_eq!(
+            reader
+                .next_hop("678-ghi", "node-1", "node-2")
+                .expect("Unable to get next hop"),
+            None
+        );
+        assert_eq!(
+            reader
+                .next_hop("345-def", "node-1", "node-4")
+                .expect("Unable to get next hop"),
+            None
+        );
+
+        assert_eq!(
+            reader
+                .next_hops("admin", "node-1", "node-2")
+                .expect("Unable to get next hops"),
+            vec!["node-0".to_string(), "node-3".to_string()]
+        );
+        assert_eq!(
+            reader
+                .next_hops("admin", "node-0", "node-2")
+                .expect("Unable to get next hops"),
+            vec!["node-2".to_string(), "node-3".to_string()]
+        );
+        assert_eq!(
+            reader
+                .next_hops("admin", "node-1", "node-5")
+                .expect("Unable to get next hops"),
+            vec!["node-5".to_string()]
+        );
+
+        writer
+            .add_admin_relay_route("node-5".to_string(), vec!["node-3".to_string()])
+            .expect("Unable to add relay route");
+        assert_eq!(
+            reader
+                .next_hops("admin", "node-1", "node-5")
+                .expect("Unable to get next hops"),
+            vec!["node-3".to_string()]
+        );
+    }
 }
@@ -93,6 +93,19 @@ pub trait RoutingTableWriter: Send {
     /// * `circuit_id` - The unique ID for the circuit
     fn remove_circuit(&mut self, circuit_id: &str) -> Result<(), InternalError>;
 
+    /// Marks a circuit as suspended or active if it exists. Service messages are not routed on a
+    /// suspended circuit.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit_id` - The unique ID for the circuit
+    /// * `suspended` - Whether the circuit is suspended
+    fn set_circuit_suspended(
+        &mut self,
+        circuit_id: &str,
+        suspended: bool,
+    ) -> Result<(), InternalError>;
+
     /// Adds a new node to the routing table
     ///
     /// # Arguments
@@ -115,6 +128,30 @@ pub trait RoutingTableWriter: Send {
     /// * `node_id` - The unique ID for the node that should be removed
     fn remove_node(&mut self, node_id: &str) -> Result<(), InternalError>;
 
+    /// Adds relay nodes through which admin messages to the provided node are sent
+    ///
+    /// Admin messages are sent directly to the target node unless relay nodes are added for it;
+    /// this is used for the members of a proposed circuit with the relay route type that the
+    /// local node does not connect to directly. The default implementation does not support relay
+    /// routes and returns an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The unique ID for the node the admin messages are meant for
+    /// * `relay_node_ids` - The IDs of the nodes the admin messages may be sent through, in order
+    ///   of preference
+    fn add_admin_relay_route(
+        &mut self,
+        node_id: String,
+        relay_node_ids: Vec<String>,
+    ) -> Result<(), InternalError> {
+        let _ = relay_node_ids;
+        Err(InternalError::with_message(format!(
+            "Unable to add relay route to node {}: relay routes are not supported",
+            node_id
+        )))
+    }
+
     fn clone_boxed(&self) -> Box<dyn RoutingTableWriter>;
 }
 
@@ -173,6 +210,55 @@ pub trait RoutingTableReader: Send {
     /// * `circuit_id` - The unique ID for the circuit to be fetched
     fn get_circuit(&self, circuit_id: &str) -> Result<Option<Circuit>, RoutingTableReaderError>;
 
+    /// Returns the IDs of the nodes a message may be sent to next in order to reach the target
+    /// node on the provided circuit, in order of preference
+    ///
+    /// A message between two members of a circuit with the relay route type that are not relay
+    /// nodes may be sent to any of the circuit's relay nodes, and a relay node may send a message
+    /// through another relay node if it cannot reach the target node directly; every other
+    /// message is sent directly to the target node. Returns an empty list if the circuit does not
+    /// exist or either node is not a member of the circuit.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit_id` - The unique ID for the circuit the message is sent on
+    /// * `local_node_id` - The ID of the node the message is currently on
+    /// * `target_node_id` - The ID of the node the message is meant for
+    fn next_hops(
+        &self,
+        circuit_id: &str,
+        local_node_id: &str,
+        target_node_id: &str,
+    ) -> Result<Vec<String>, RoutingTableReaderError> {
+        Ok(self
+            .get_circuit(circuit_id)?
+            .map(|circuit| circuit.next_hops(local_node_id, target_node_id))
+            .unwrap_or_default())
+    }
+
+    /// Returns the ID of the node a message should be sent to next in order to reach the target
+    /// node on the provided circuit
+    ///
+    /// This is the first node returned by `next_hops`. Returns `None` if the circuit does not
+    /// exist or either node is not a member of the circuit.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit_id` - The unique ID for the circuit the message is sent on
+    /// * `local_node_id` - The ID of the node the message is currently on
+    /// * `target_node_id` - The ID of the node the message is meant for
+    fn next_hop(
+        &self,
+        circuit_id: &str,
+        local_node_id: &str,
+        target_node_id: &str,
+    ) -> Result<Option<String>, RoutingTableReaderError> {
+        Ok(self
+            .next_hops(circuit_id, local_node_id, target_node_id)?
+            .into_iter()
+            .next())
+    }
+
     fn clone_boxed(&self) -> Box<dyn RoutingTableReader>;
 }
 
@@ -190,6 +276,9 @@ pub struct Circuit {
     roster: Vec<Service>,
     members: Vec<String>,
     authorization_type: AuthorizationType,
+    durability: DurabilityType,
+    route_type: RouteType,
+    suspended: bool,
 }
 
 impl Circuit {
@@ -212,9 +301,42 @@ impl Circuit {
             roster,
             members,
             authorization_type,
+            durability: DurabilityType::NoDurability,
+            route_type: RouteType::Any,
+            suspended: false,
         }
     }
 
+    /// Sets the durability requirement for messages sent over the circuit
+    ///
+    /// # Arguments
+    ///
+    /// * `durability` - The durability type used for the circuit
+    pub fn with_durability(mut self, durability: DurabilityType) -> Self {
+        self.durability = durability;
+        self
+    }
+
+    /// Sets how messages are routed between the members of the circuit
+    ///
+    /// # Arguments
+    ///
+    /// * `route_type` - The route type used for the circuit
+    pub fn with_route_type(mut self, route_type: RouteType) -> Self {
+        self.route_type = route_type;
+        self
+    }
+
+    /// Sets whether the circuit is suspended
+    ///
+    /// # Arguments
+    ///
+    /// * `suspended` - Whether service messages should be rejected on the circuit
+    pub fn with_suspended(mut self, suspended: bool) -> Self {
+        self.suspended = suspended;
+        self
+    }
+
     /// Returns the ID of the circuit
     pub fn circuit_id(&self) -> &str {
         &self.circuit_id
@@ -234,6 +356,58 @@ impl Circuit {
     pub fn authorization_type(&self) -> &AuthorizationType {
         &self.authorization_type
     }
+
+    /// Returns the durability type
+    pub fn durability(&self) -> &DurabilityType {
+        &self.durability
+    }
+
+    /// Returns the route type
+    pub fn route_type(&self) -> &RouteType {
+        &self.route_type
+    }
+
+    /// Returns the IDs of the nodes a message may be sent to next in order to reach the target
+    /// node on this circuit, in order of preference
+    ///
+    /// Returns an empty list if either node is not a member of the circuit.
+    ///
+    /// # Arguments
+    ///
+    /// * `local_node_id` - The ID of the node the message is currently on
+    /// * `target_node_id` - The ID of the node the message is meant for
+    pub fn next_hops(&self, local_node_id: &str, target_node_id: &str) -> Vec<String> {
+        let is_member = |node_id: &str| self.members.iter().any(|member| member == node_id);
+        if !is_member(local_node_id) || !is_member(target_node_id) {
+            return vec![];
+        }
+
+        match &self.route_type {
+            RouteType::Any => vec![target_node_id.to_string()],
+            RouteType::Relay(relay_nodes) => {
+                let is_relay = |node_id: &str| relay_nodes.iter().any(|relay| relay == node_id);
+                if is_relay(local_node_id) {
+                    std::iter::once(target_node_id.to_string())
+                        .chain(
+                            relay_nodes
+                                .iter()
+                                .filter(|relay| *relay != local_node_id && *relay != target_node_id)
+                                .cloned(),
+                        )
+                        .collect()
+                } else if is_relay(target_node_id) {
+                    vec![target_node_id.to_string()]
+                } else {
+                    relay_nodes.clone()
+                }
+            }
+        }
+    }
+
+    /// Returns whether the circuit is suspended
+    pub fn is_suspended(&self) -> bool {
+        self.suspended
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -242,6 +416,27 @@ pub enum AuthorizationType {
     Challenge,
 }
 
+/// The durability requirement for messages sent to other nodes on a circuit
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DurabilityType {
+    /// Messages are dropped if the connection to the node is not available
+    NoDurability,
+    /// Messages are stored if the connection to the node is not available and delivered once the
+    /// node has reconnected
+    GuaranteedDelivery,
+}
+
+/// How messages are routed between the members of a circuit
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RouteType {
+    /// Messages are sent directly to the member the recipient is connected to
+    Any,
+    /// Messages between members that are not relay nodes are forwarded through one of the relay
+    /// nodes, preferring them in the order listed; relay nodes send messages directly, or through
+    /// another relay node if they are not connected to the target node
+    Relay(Vec<String>),
+}
+
 /// The routing table representation of a node
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct CircuitNode {
@@ -77,6 +77,16 @@ impl<K: Hash + Eq> RefMap<K> {
             Ok(None)
         }
     }
+
+    /// Returns the current reference count for `ref_id`, or zero if it is not referenced
+    #[cfg(feature = "peer-inspection")]
+    pub fn ref_count<Q: ?Sized>(&self, ref_id: &Q) -> u64
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq,
+    {
+        self.references.get(ref_id).copied().unwrap_or(0)
+    }
 }
 
 #[cfg(test)]
@@ -131,4 +141,23 @@ pub mod tests {
             panic!("remove_ref should have returned an error");
         }
     }
+
+    // Test that `ref_count` reports the current reference count, and zero once the reference has
+    // been removed.
+    #[cfg(feature = "peer-inspection")]
+    #[test]
+    fn test_ref_count() {
+        let mut ref_map = RefMap::new();
+        assert_eq!(ref_map.ref_count("test_id"), 0);
+
+        ref_map.add_ref("test_id".to_string());
+        ref_map.add_ref("test_id".to_string());
+        assert_eq!(ref_map.ref_count("test_id"), 2);
+
+        ref_map.remove_ref("test_id").expect("Unable to remove ref");
+        assert_eq!(ref_map.ref_count("test_id"), 1);
+
+        ref_map.remove_ref("test_id").expect("Unable to remove ref");
+        assert_eq!(ref_map.ref_count("test_id"), 0);
+    }
 }
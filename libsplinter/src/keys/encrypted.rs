@@ -0,0 +1,395 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Passphrase-encrypted private keys.
+//!
+//! An encrypted private key is stored as a single line of text:
+//!
+//! ```text
+//! splinter-encrypted-key:1:scrypt:<log_n>:<r>:<p>:<salt>:aes-256-gcm:<nonce>:<ciphertext>:<tag>
+//! ```
+//!
+//! The encryption key is derived from the passphrase using scrypt with the given parameters and
+//! salt. The private key bytes are encrypted with AES-256-GCM; every field preceding the
+//! ciphertext is authenticated as additional data, so the parameters can not be altered without
+//! the decryption failing. All binary fields are hex-encoded.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use cylinder::PrivateKey;
+use openssl::pkcs5::scrypt;
+use openssl::rand::rand_bytes;
+use openssl::symm::{decrypt_aead, encrypt_aead, Cipher};
+
+use crate::error::InternalError;
+use crate::hex::{parse_hex, to_hex};
+
+use super::EncryptedKeyError;
+
+/// The environment variable that contains the passphrase of encrypted private key files
+pub const KEY_PASSPHRASE_ENV: &str = "SPLINTER_KEY_PASSPHRASE";
+/// The environment variable that names a file containing the passphrase of encrypted private key
+/// files
+pub const KEY_PASSPHRASE_FILE_ENV: &str = "SPLINTER_KEY_PASSPHRASE_FILE";
+
+const ENCRYPTED_KEY_PREFIX: &str = "splinter-encrypted-key";
+const ENCRYPTED_KEY_VERSION: &str = "1";
+const KDF_NAME: &str = "scrypt";
+const CIPHER_NAME: &str = "aes-256-gcm";
+
+const SCRYPT_LOG_N: u8 = 15;
+const SCRYPT_R: u64 = 8;
+const SCRYPT_P: u64 = 1;
+// The maximum amount of memory scrypt may use when decrypting a key; this bounds the parameters
+// that are accepted from a key file.
+const SCRYPT_MAX_MEMORY: u64 = 256 * 1024 * 1024;
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+const KEY_LEN: usize = 32;
+
+/// Loads the passphrase of encrypted private key files.
+///
+/// The passphrase is read from `passphrase_file` if one is given, otherwise from the file named
+/// by `SPLINTER_KEY_PASSPHRASE_FILE`, with any trailing line ending removed. If neither is set,
+/// the passphrase is read from `SPLINTER_KEY_PASSPHRASE`. Returns `None` if no passphrase is set.
+///
+/// # Errors
+///
+/// Returns an `EncryptedKeyError::Io` if the passphrase file could not be read.
+pub fn load_key_passphrase(
+    passphrase_file: Option<&Path>,
+) -> Result<Option<Vec<u8>>, EncryptedKeyError> {
+    let passphrase_file = passphrase_file
+        .map(Path::to_path_buf)
+        .or_else(|| env::var_os(KEY_PASSPHRASE_FILE_ENV).map(Into::into));
+
+    if let Some(passphrase_file) = passphrase_file {
+        let passphrase =
+            fs::read_to_string(&passphrase_file).map_err(|err| EncryptedKeyError::Io {
+                context: format!(
+                    "unable to read passphrase file {}",
+                    passphrase_file.display()
+                ),
+                source: err,
+            })?;
+        return Ok(Some(
+            passphrase
+                .trim_end_matches(&['\r', '\n'][..])
+                .as_bytes()
+                .to_vec(),
+        ));
+    }
+
+    Ok(env::var(KEY_PASSPHRASE_ENV)
+        .ok()
+        .map(|passphrase| passphrase.into_bytes()))
+}
+
+/// Loads a private key file, decrypting it with the passphrase if it is encrypted.
+///
+/// A private key file that is not encrypted contains the hex-encoded private key on its first
+/// line.
+///
+/// # Errors
+///
+/// Returns an `EncryptedKeyError::Io` if the file could not be read, an
+/// `EncryptedKeyError::PassphraseRequired` if the file is encrypted and no passphrase was given,
+/// or any error returned by [`decrypt_private_key`].
+pub fn load_private_key(
+    path: &Path,
+    passphrase: Option<&[u8]>,
+) -> Result<PrivateKey, EncryptedKeyError> {
+    let contents = fs::read_to_string(path).map_err(|err| EncryptedKeyError::Io {
+        context: format!("unable to read private key file {}", path.display()),
+        source: err,
+    })?;
+
+    if !is_encrypted(&contents) {
+        let key_hex = contents.lines().next().unwrap_or_default().trim();
+        return parse_hex(key_hex).map(PrivateKey::new).map_err(|err| {
+            EncryptedKeyError::InvalidFormat(format!(
+                "private key file {} is not valid hex: {}",
+                path.display(),
+                err
+            ))
+        });
+    }
+
+    let passphrase = passphrase.ok_or(EncryptedKeyError::PassphraseRequired)?;
+
+    decrypt_private_key(&contents, passphrase).map(PrivateKey::new)
+}
+
+/// Returns `true` if the given contents are a passphrase-encrypted private key.
+pub fn is_encrypted(contents: &str) -> bool {
+    contents
+        .trim()
+        .starts_with(&format!("{}:", ENCRYPTED_KEY_PREFIX))
+}
+
+/// Encrypts the given private key bytes with the passphrase.
+///
+/// Returns the encrypted key as a single line of text, suitable for writing to a key file.
+///
+/// # Errors
+///
+/// Returns an `EncryptedKeyError::Internal` if the key could not be derived from the passphrase
+/// or the private key could not be encrypted.
+pub fn encrypt_private_key(
+    private_key: &[u8],
+    passphrase: &[u8],
+) -> Result<String, EncryptedKeyError> {
+    let mut salt = [0u8; SALT_LEN];
+    rand_bytes(&mut salt).map_err(|err| InternalError::from_source(Box::new(err)))?;
+    let mut nonce = [0u8; NONCE_LEN];
+    rand_bytes(&mut nonce).map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+    let key = derive_key(passphrase, &salt, SCRYPT_LOG_N, SCRYPT_R, SCRYPT_P)?;
+
+    let aad = [
+        ENCRYPTED_KEY_PREFIX.to_string(),
+        ENCRYPTED_KEY_VERSION.to_string(),
+        KDF_NAME.to_string(),
+        SCRYPT_LOG_N.to_string(),
+        SCRYPT_R.to_string(),
+        SCRYPT_P.to_string(),
+        to_hex(&salt),
+        CIPHER_NAME.to_string(),
+        to_hex(&nonce),
+    ]
+    .join(":");
+
+    let mut tag = [0u8; TAG_LEN];
+    let ciphertext = encrypt_aead(
+        Cipher::aes_256_gcm(),
+        &key,
+        Some(&nonce),
+        aad.as_bytes(),
+        private_key,
+        &mut tag,
+    )
+    .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+    Ok(format!("{}:{}:{}", aad, to_hex(&ciphertext), to_hex(&tag)))
+}
+
+/// Decrypts a passphrase-encrypted private key, returning the private key bytes.
+///
+/// # Errors
+///
+/// Returns an `EncryptedKeyError::InvalidFormat` if the contents are not a supported encrypted
+/// key, or an `EncryptedKeyError::DecryptionFailed` if the key could not be decrypted with the
+/// passphrase.
+pub fn decrypt_private_key(
+    contents: &str,
+    passphrase: &[u8],
+) -> Result<Vec<u8>, EncryptedKeyError> {
+    let fields = contents.trim().split(':').collect::<Vec<_>>();
+    if fields.len() != 11 || fields[0] != ENCRYPTED_KEY_PREFIX {
+        return Err(EncryptedKeyError::InvalidFormat(
+            "not a splinter encrypted key".into(),
+        ));
+    }
+    if fields[1] != ENCRYPTED_KEY_VERSION {
+        return Err(EncryptedKeyError::InvalidFormat(format!(
+            "unsupported version {}",
+            fields[1]
+        )));
+    }
+    if fields[2] != KDF_NAME {
+        return Err(EncryptedKeyError::InvalidFormat(format!(
+            "unsupported key derivation function {}",
+            fields[2]
+        )));
+    }
+    if fields[7] != CIPHER_NAME {
+        return Err(EncryptedKeyError::InvalidFormat(format!(
+            "unsupported cipher {}",
+            fields[7]
+        )));
+    }
+
+    let log_n = parse_param(fields[3], "log_n")?;
+    let r = parse_param(fields[4], "r")?;
+    let p = parse_param(fields[5], "p")?;
+    if log_n == 0 || log_n >= 64 || r == 0 || p == 0 {
+        return Err(EncryptedKeyError::InvalidFormat(
+            "invalid scrypt parameters".into(),
+        ));
+    }
+
+    let salt = parse_field(fields[6], "salt")?;
+    let nonce = parse_field(fields[8], "nonce")?;
+    let ciphertext = parse_field(fields[9], "ciphertext")?;
+    let tag = parse_field(fields[10], "tag")?;
+    if nonce.len() != NONCE_LEN || tag.len() != TAG_LEN {
+        return Err(EncryptedKeyError::InvalidFormat(
+            "invalid nonce or tag length".into(),
+        ));
+    }
+
+    let key = derive_key(passphrase, &salt, log_n as u8, r, p)?;
+
+    let aad = fields[..9].join(":");
+
+    decrypt_aead(
+        Cipher::aes_256_gcm(),
+        &key,
+        Some(&nonce),
+        aad.as_bytes(),
+        &ciphertext,
+        &tag,
+    )
+    .map_err(|_| EncryptedKeyError::DecryptionFailed)
+}
+
+fn derive_key(
+    passphrase: &[u8],
+    salt: &[u8],
+    log_n: u8,
+    r: u64,
+    p: u64,
+) -> Result<[u8; KEY_LEN], EncryptedKeyError> {
+    let n = 1u64 << log_n;
+    // scrypt requires 128 * r * (n + p + 2) bytes of memory
+    let required_memory = n
+        .checked_add(p)
+        .and_then(|v| v.checked_add(2))
+        .and_then(|v| v.checked_mul(r))
+        .and_then(|v| v.checked_mul(128))
+        .filter(|memory| *memory <= SCRYPT_MAX_MEMORY)
+        .ok_or_else(|| {
+            EncryptedKeyError::InvalidFormat(
+                "scrypt parameters exceed the supported memory limit".into(),
+            )
+        })?;
+
+    let mut key = [0u8; KEY_LEN];
+    scrypt(passphrase, salt, n, r, p, required_memory, &mut key)
+        .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+    Ok(key)
+}
+
+fn parse_param(value: &str, name: &str) -> Result<u64, EncryptedKeyError> {
+    value.parse::<u64>().map_err(|_| {
+        EncryptedKeyError::InvalidFormat(format!("invalid scrypt parameter {}: {}", name, value))
+    })
+}
+
+fn parse_field(value: &str, name: &str) -> Result<Vec<u8>, EncryptedKeyError> {
+    parse_hex(value)
+        .map_err(|_| EncryptedKeyError::InvalidFormat(format!("{} is not valid hex", name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRIVATE_KEY: &[u8] = &[
+        0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24,
+        25, 26, 27, 28, 29, 30, 31,
+    ];
+
+    /// Verify that a private key encrypted with a passphrase can be decrypted with the same
+    /// passphrase, and that the encrypted contents are recognized as an encrypted key.
+    #[test]
+    fn test_encrypt_decrypt_round_trip() {
+        let encrypted = encrypt_private_key(PRIVATE_KEY, b"correct horse battery staple")
+            .expect("Unable to encrypt key");
+
+        assert!(is_encrypted(&encrypted));
+        assert!(!encrypted.contains(&to_hex(PRIVATE_KEY)));
+
+        let decrypted = decrypt_private_key(&encrypted, b"correct horse battery staple")
+            .expect("Unable to decrypt key");
+        assert_eq!(PRIVATE_KEY, decrypted.as_slice());
+    }
+
+    /// Verify that decrypting with the wrong passphrase returns `DecryptionFailed`.
+    #[test]
+    fn test_decrypt_wrong_passphrase() {
+        let encrypted =
+            encrypt_private_key(PRIVATE_KEY, b"passphrase").expect("Unable to encrypt key");
+
+        match decrypt_private_key(&encrypted, b"not the passphrase") {
+            Err(EncryptedKeyError::DecryptionFailed) => (),
+            res => panic!("Expected DecryptionFailed, got {:?}", res),
+        }
+    }
+
+    /// Verify that altering the authenticated parameters causes decryption to fail.
+    #[test]
+    fn test_decrypt_tampered_parameters() {
+        let encrypted =
+            encrypt_private_key(PRIVATE_KEY, b"passphrase").expect("Unable to encrypt key");
+        let tampered = encrypted.replacen(":scrypt:15:8:1:", ":scrypt:14:8:1:", 1);
+        assert_ne!(encrypted, tampered);
+
+        match decrypt_private_key(&tampered, b"passphrase") {
+            Err(EncryptedKeyError::DecryptionFailed) => (),
+            res => panic!("Expected DecryptionFailed, got {:?}", res),
+        }
+    }
+
+    /// Verify that a plaintext hex private key is not treated as an encrypted key.
+    #[test]
+    fn test_plaintext_key_is_not_encrypted() {
+        let plaintext = to_hex(PRIVATE_KEY);
+
+        assert!(!is_encrypted(&plaintext));
+        match decrypt_private_key(&plaintext, b"passphrase") {
+            Err(EncryptedKeyError::InvalidFormat(_)) => (),
+            res => panic!("Expected InvalidFormat, got {:?}", res),
+        }
+    }
+
+    /// Verify that private key files are loaded whether or not they are encrypted:
+    ///
+    /// 1. Write a plaintext key file and verify it is loaded without a passphrase
+    /// 2. Write an encrypted key file and verify it is loaded with the passphrase
+    /// 3. Verify that loading the encrypted key file without a passphrase fails with
+    ///    `PassphraseRequired`
+    #[test]
+    fn test_load_private_key() {
+        let temp_dir = tempfile::tempdir().expect("Unable to create temp dir");
+
+        let plaintext_path = temp_dir.path().join("plaintext.priv");
+        fs::write(&plaintext_path, format!("{}\n", to_hex(PRIVATE_KEY)))
+            .expect("Unable to write plaintext key");
+        let private_key =
+            load_private_key(&plaintext_path, None).expect("Unable to load plaintext key");
+        assert_eq!(private_key.as_slice(), PRIVATE_KEY);
+
+        let encrypted_path = temp_dir.path().join("encrypted.priv");
+        fs::write(
+            &encrypted_path,
+            encrypt_private_key(PRIVATE_KEY, b"passphrase").expect("Unable to encrypt key"),
+        )
+        .expect("Unable to write encrypted key");
+        let private_key = load_private_key(&encrypted_path, Some(b"passphrase"))
+            .expect("Unable to load encrypted key");
+        assert_eq!(private_key.as_slice(), PRIVATE_KEY);
+
+        match load_private_key(&encrypted_path, None) {
+            Err(EncryptedKeyError::PassphraseRequired) => (),
+            Err(err) => panic!("Expected PassphraseRequired, got {}", err),
+            Ok(_) => panic!("Expected PassphraseRequired, got a private key"),
+        }
+    }
+}
@@ -16,6 +16,9 @@
 
 use std::error::Error;
 
+#[cfg(feature = "encrypted-keys")]
+use crate::error::InternalError;
+
 /// An error that can occur in the underlying `KeyPermissions` implementation.
 #[derive(Debug)]
 pub struct KeyPermissionError {
@@ -38,3 +41,61 @@ impl std::fmt::Display for KeyPermissionError {
         }
     }
 }
+
+/// An error that can occur while encrypting or decrypting a private key.
+#[cfg(feature = "encrypted-keys")]
+#[derive(Debug)]
+pub enum EncryptedKeyError {
+    /// The contents are not a valid encrypted private key.
+    InvalidFormat(String),
+    /// The private key could not be decrypted, most likely due to an incorrect passphrase.
+    DecryptionFailed,
+    /// The private key is encrypted, but no passphrase was provided.
+    PassphraseRequired,
+    /// A private key or passphrase file could not be read.
+    Io {
+        context: String,
+        source: std::io::Error,
+    },
+    /// An internal error occurred while encrypting or decrypting the private key.
+    Internal(InternalError),
+}
+
+#[cfg(feature = "encrypted-keys")]
+impl std::error::Error for EncryptedKeyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            EncryptedKeyError::InvalidFormat(_) => None,
+            EncryptedKeyError::DecryptionFailed => None,
+            EncryptedKeyError::PassphraseRequired => None,
+            EncryptedKeyError::Io { source, .. } => Some(source),
+            EncryptedKeyError::Internal(err) => Some(err),
+        }
+    }
+}
+
+#[cfg(feature = "encrypted-keys")]
+impl std::fmt::Display for EncryptedKeyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            EncryptedKeyError::InvalidFormat(msg) => {
+                write!(f, "invalid encrypted private key: {}", msg)
+            }
+            EncryptedKeyError::DecryptionFailed => {
+                f.write_str("unable to decrypt private key; the passphrase may be incorrect")
+            }
+            EncryptedKeyError::PassphraseRequired => {
+                f.write_str("private key is encrypted, but no passphrase was provided")
+            }
+            EncryptedKeyError::Io { context, source } => write!(f, "{}: {}", context, source),
+            EncryptedKeyError::Internal(err) => f.write_str(&err.to_string()),
+        }
+    }
+}
+
+#[cfg(feature = "encrypted-keys")]
+impl From<InternalError> for EncryptedKeyError {
+    fn from(err: InternalError) -> Self {
+        EncryptedKeyError::Internal(err)
+    }
+}
@@ -17,10 +17,17 @@
 //! Key permissions, accessed via the `KeyPermissionManager` interface, are queried through a simple
 //! role-based access system.  The underlying implementation determines how those values are set
 //! and modified.
+//!
+//! With the `encrypted-keys` feature, the `encrypted` module provides passphrase-based encryption
+//! of private key files.
 
+#[cfg(feature = "encrypted-keys")]
+pub mod encrypted;
 mod error;
 pub mod insecure;
 
+#[cfg(feature = "encrypted-keys")]
+pub use error::EncryptedKeyError;
 pub use error::KeyPermissionError;
 
 type KeyPermissionResult<T> = Result<T, KeyPermissionError>;
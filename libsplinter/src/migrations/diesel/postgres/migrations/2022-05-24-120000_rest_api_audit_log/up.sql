@@ -0,0 +1,26 @@
+-- Copyright 2018-2022 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+CREATE TABLE IF NOT EXISTS rest_api_audit_log (
+    id                        BIGSERIAL PRIMARY KEY,
+    timestamp                 BIGINT NOT NULL,
+    identity                  TEXT,
+    permission                TEXT,
+    method                    TEXT NOT NULL,
+    route                     TEXT NOT NULL,
+    outcome                   TEXT NOT NULL,
+    circuit_id                TEXT,
+    action                    TEXT
+);
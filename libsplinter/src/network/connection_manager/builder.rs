@@ -14,9 +14,16 @@
 
 use std::sync::mpsc::{channel, Sender};
 use std::thread;
+#[cfg(feature = "peer-inspection")]
+use std::time::{SystemTime, UNIX_EPOCH};
 
+#[cfg(feature = "peer-inspection")]
+use protobuf::Message;
+
+#[cfg(not(feature = "peer-inspection"))]
 use crate::protocol::network::{NetworkHeartbeat, NetworkMessage};
 use crate::protos::network;
+#[cfg(not(feature = "peer-inspection"))]
 use crate::protos::prelude::*;
 use crate::threading::pacemaker;
 use crate::transport::matrix::{ConnectionMatrixLifeCycle, ConnectionMatrixSender};
@@ -261,6 +268,19 @@ fn handle_request<T: ConnectionMatrixLifeCycle, U: ConnectionMatrixSender>(
                 warn!("connector dropped before receiving result of list connections");
             }
         }
+        #[cfg(feature = "peer-inspection")]
+        CmRequest::ListConnectionInfo { sender } => {
+            if sender
+                .send(Ok(state
+                    .connection_metadata()
+                    .values()
+                    .map(|metadata| metadata.info())
+                    .collect()))
+                .is_err()
+            {
+                warn!("connector dropped before receiving result of list connection info");
+            }
+        }
         CmRequest::AddInboundConnection { sender, connection } => {
             state.add_inbound_connection(connection, sender, internal_sender, authorizer)
         }
@@ -358,6 +378,11 @@ fn send_heartbeats<T: ConnectionMatrixLifeCycle, U: ConnectionMatrixSender>(
                             connection_id: metadata.connection_id.clone(),
                         });
                         reconnections.push(metadata.clone());
+                    } else {
+                        #[cfg(feature = "peer-inspection")]
+                        {
+                            metadata.last_heartbeat = Some(SystemTime::now());
+                        }
                     }
                 }
             }
@@ -388,6 +413,10 @@ fn send_heartbeats<T: ConnectionMatrixLifeCycle, U: ConnectionMatrixSender>(
                     }
                 } else {
                     *disconnected = false;
+                    #[cfg(feature = "peer-inspection")]
+                    {
+                        metadata.last_heartbeat = Some(SystemTime::now());
+                    }
                 }
             }
         }
@@ -412,6 +441,7 @@ fn send_heartbeats<T: ConnectionMatrixLifeCycle, U: ConnectionMatrixSender>(
 }
 
 /// Creates NetworkHeartbeat message and serializes it into a byte array.
+#[cfg(not(feature = "peer-inspection"))]
 fn create_heartbeat() -> Result<Vec<u8>, ConnectionManagerError> {
     IntoBytes::<network::NetworkMessage>::into_bytes(NetworkMessage::NetworkHeartbeat(
         NetworkHeartbeat,
@@ -420,3 +450,28 @@ fn create_heartbeat() -> Result<Vec<u8>, ConnectionManagerError> {
         ConnectionManagerError::HeartbeatError("cannot create NetworkHeartbeat message".to_string())
     })
 }
+
+/// Creates NetworkHeartbeat message, stamped with the time it was sent so that the remote node's
+/// reply can be used to measure the round trip time, and serializes it into a byte array.
+#[cfg(feature = "peer-inspection")]
+fn create_heartbeat() -> Result<Vec<u8>, ConnectionManagerError> {
+    let sent_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|_| {
+            ConnectionManagerError::HeartbeatError("system time is before the Unix epoch".into())
+        })?
+        .as_micros() as u64;
+
+    let mut heartbeat = network::NetworkHeartbeat::new();
+    heartbeat.set_sent_at(sent_at);
+
+    let mut message = network::NetworkMessage::new();
+    message.set_message_type(network::NetworkMessageType::NETWORK_HEARTBEAT);
+    message.set_payload(heartbeat.write_to_bytes().map_err(|_| {
+        ConnectionManagerError::HeartbeatError("cannot create NetworkHeartbeat message".to_string())
+    })?);
+
+    message.write_to_bytes().map_err(|_| {
+        ConnectionManagerError::HeartbeatError("cannot create NetworkHeartbeat message".to_string())
+    })
+}
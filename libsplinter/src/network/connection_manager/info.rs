@@ -0,0 +1,112 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Reports of the state of the connections managed by the connection manager.
+
+use std::time::SystemTime;
+
+use crate::network::auth::ConnectionAuthorizationType;
+
+/// Which side of a connection requested it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConnectionDirection {
+    /// The connection was requested by the remote node
+    Inbound,
+    /// The connection was requested by the local node
+    Outbound,
+}
+
+/// The status of a connection.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConnectionStatus {
+    /// The connection is up and heartbeats are being sent successfully
+    Connected,
+    /// An outbound connection was lost and is being reconnected
+    Reconnecting { attempts: u64 },
+    /// An inbound connection was lost; inbound connections are not reconnected
+    Disconnected,
+}
+
+/// A report of the state of a connection managed by the connection manager.
+#[derive(Clone, Debug)]
+pub struct ConnectionInfo {
+    connection_id: String,
+    endpoint: String,
+    identity: ConnectionAuthorizationType,
+    direction: ConnectionDirection,
+    status: ConnectionStatus,
+    connected_since: SystemTime,
+    last_heartbeat: Option<SystemTime>,
+}
+
+impl ConnectionInfo {
+    pub(super) fn new(
+        connection_id: String,
+        endpoint: String,
+        identity: ConnectionAuthorizationType,
+        direction: ConnectionDirection,
+        status: ConnectionStatus,
+        connected_since: SystemTime,
+        last_heartbeat: Option<SystemTime>,
+    ) -> Self {
+        ConnectionInfo {
+            connection_id,
+            endpoint,
+            identity,
+            direction,
+            status,
+            connected_since,
+            last_heartbeat,
+        }
+    }
+
+    /// Returns the ID of the connection.
+    pub fn connection_id(&self) -> &str {
+        &self.connection_id
+    }
+
+    /// Returns the endpoint of the connection.
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// Returns the identity the remote node was authorized with.
+    pub fn identity(&self) -> &ConnectionAuthorizationType {
+        &self.identity
+    }
+
+    /// Returns which side of the connection requested it.
+    pub fn direction(&self) -> &ConnectionDirection {
+        &self.direction
+    }
+
+    /// Returns the status of the connection.
+    pub fn status(&self) -> &ConnectionStatus {
+        &self.status
+    }
+
+    /// Returns when the connection was last authorized.
+    pub fn connected_since(&self) -> SystemTime {
+        self.connected_since
+    }
+
+    /// Returns when a heartbeat was last successfully sent on the connection, if one has been.
+    ///
+    /// This records that the connection accepted the heartbeat rather than that the remote node
+    /// received it. The round trip time of heartbeats the remote node replies to is reported by
+    /// the `PeerManager`, see `PeerInfo::heartbeat_latency`.
+    pub fn last_heartbeat(&self) -> Option<SystemTime> {
+        self.last_heartbeat
+    }
+}
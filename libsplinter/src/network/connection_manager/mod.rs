@@ -15,6 +15,8 @@
 pub mod authorizers;
 mod builder;
 mod error;
+#[cfg(feature = "peer-inspection")]
+mod info;
 mod notification;
 
 use std::cmp::min;
@@ -22,11 +24,15 @@ use std::collections::HashMap;
 use std::sync::mpsc::{channel, Sender};
 use std::thread;
 use std::time::Instant;
+#[cfg(feature = "peer-inspection")]
+use std::time::SystemTime;
 
 use uuid::Uuid;
 
 pub use builder::ConnectionManagerBuilder;
 pub use error::{AuthorizerError, ConnectionManagerError};
+#[cfg(feature = "peer-inspection")]
+pub use info::{ConnectionDirection, ConnectionInfo, ConnectionStatus};
 pub use notification::ConnectionManagerNotification;
 
 use crate::error::InternalError;
@@ -136,6 +142,10 @@ enum CmRequest {
     ListConnections {
         sender: Sender<Result<Vec<String>, ConnectionManagerError>>,
     },
+    #[cfg(feature = "peer-inspection")]
+    ListConnectionInfo {
+        sender: Sender<Result<Vec<ConnectionInfo>, ConnectionManagerError>>,
+    },
     AddInboundConnection {
         connection: Box<dyn Connection>,
         sender: Sender<Result<(), ConnectionManagerError>>,
@@ -364,6 +374,29 @@ impl Connector {
         })?
     }
 
+    /// List the state of the connections available to this Connector instance.
+    ///
+    /// # Errors
+    ///
+    /// Returns a ConnectionManagerError if the connections cannot be queried.
+    #[cfg(feature = "peer-inspection")]
+    pub fn list_connection_info(&self) -> Result<Vec<ConnectionInfo>, ConnectionManagerError> {
+        let (sender, recv) = channel();
+        self.sender
+            .send(CmMessage::Request(CmRequest::ListConnectionInfo { sender }))
+            .map_err(|_| {
+                ConnectionManagerError::SendMessageError(
+                    "The connection manager is no longer running".into(),
+                )
+            })?;
+
+        recv.recv().map_err(|_| {
+            ConnectionManagerError::SendMessageError(
+                "The connection manager is no longer running".into(),
+            )
+        })?
+    }
+
     /// Add a new inbound connection.
     ///
     /// # Error
@@ -424,6 +457,12 @@ struct ConnectionMetadata {
     endpoint: String,
     identity: ConnectionAuthorizationType,
     extended_metadata: ConnectionMetadataExt,
+    /// When the connection was last authorized
+    #[cfg(feature = "peer-inspection")]
+    connected_since: SystemTime,
+    /// When a heartbeat was last successfully sent on the connection
+    #[cfg(feature = "peer-inspection")]
+    last_heartbeat: Option<SystemTime>,
 }
 
 impl ConnectionMetadata {
@@ -445,6 +484,41 @@ impl ConnectionMetadata {
     fn identity(&self) -> &ConnectionAuthorizationType {
         &self.identity
     }
+
+    #[cfg(feature = "peer-inspection")]
+    fn info(&self) -> ConnectionInfo {
+        let (direction, status) = match self.extended_metadata {
+            ConnectionMetadataExt::Outbound {
+                reconnecting: true,
+                reconnection_attempts,
+                ..
+            } => (
+                ConnectionDirection::Outbound,
+                ConnectionStatus::Reconnecting {
+                    attempts: reconnection_attempts,
+                },
+            ),
+            ConnectionMetadataExt::Outbound { .. } => {
+                (ConnectionDirection::Outbound, ConnectionStatus::Connected)
+            }
+            ConnectionMetadataExt::Inbound {
+                disconnected: true, ..
+            } => (ConnectionDirection::Inbound, ConnectionStatus::Disconnected),
+            ConnectionMetadataExt::Inbound { .. } => {
+                (ConnectionDirection::Inbound, ConnectionStatus::Connected)
+            }
+        };
+
+        ConnectionInfo::new(
+            self.connection_id.clone(),
+            self.endpoint.clone(),
+            self.identity.clone(),
+            direction,
+            status,
+            self.connected_since,
+            self.last_heartbeat,
+        )
+    }
 }
 
 /// Enum describing metadata that is specific to the two different connection
@@ -732,6 +806,10 @@ where
                             expected_authorization,
                             local_authorization: local_authorization.clone(),
                         },
+                        #[cfg(feature = "peer-inspection")]
+                        connected_since: SystemTime::now(),
+                        #[cfg(feature = "peer-inspection")]
+                        last_heartbeat: None,
                     },
                 );
 
@@ -805,6 +883,10 @@ where
                             disconnected: false,
                             local_authorization: local_authorization.clone(),
                         },
+                        #[cfg(feature = "peer-inspection")]
+                        connected_since: SystemTime::now(),
+                        #[cfg(feature = "peer-inspection")]
+                        last_heartbeat: None,
                     },
                 );
 
@@ -1117,6 +1199,63 @@ mod tests {
             .expect("Unable to shutdown connection manager");
     }
 
+    /// Test that the connection info reports an authorized outbound connection, and that a
+    /// heartbeat has been recorded for it once the remote side has received one
+    #[cfg(feature = "peer-inspection")]
+    #[test]
+    fn test_list_connection_info() {
+        let mut transport = Box::new(InprocTransport::default());
+        let mut listener = transport.listen("inproc://test").unwrap();
+        let mesh = Mesh::new(512, 128);
+        let mesh_clone = mesh.clone();
+
+        thread::spawn(move || {
+            let conn = listener.accept().unwrap();
+            mesh_clone.add(conn, "test_id".to_string()).unwrap();
+        });
+
+        let mut cm = ConnectionManager::builder()
+            .with_authorizer(Box::new(NoopAuthorizer::new("test_identity")))
+            .with_matrix_life_cycle(mesh.get_life_cycle())
+            .with_matrix_sender(mesh.get_sender())
+            .with_transport(transport)
+            .with_heartbeat_interval(1)
+            .start()
+            .expect("Unable to start Connection Manager");
+
+        let connector = cm.connector();
+
+        connector
+            .request_connection("inproc://test", "test_id", None, None)
+            .expect("A connection could not be created");
+
+        // Wait for the heartbeat to be received
+        mesh.recv().unwrap();
+
+        let connection_info = connector
+            .list_connection_info()
+            .expect("Unable to list connection info");
+        assert_eq!(connection_info.len(), 1);
+        assert_eq!(connection_info[0].connection_id(), "test_id");
+        assert_eq!(connection_info[0].endpoint(), "inproc://test");
+        assert_eq!(
+            connection_info[0].identity(),
+            &ConnectionAuthorizationType::Trust {
+                identity: "test_identity".into()
+            }
+        );
+        assert_eq!(
+            connection_info[0].direction(),
+            &ConnectionDirection::Outbound
+        );
+        assert_eq!(connection_info[0].status(), &ConnectionStatus::Connected);
+        assert!(connection_info[0].last_heartbeat().is_some());
+
+        cm.signal_shutdown();
+        cm.wait_for_shutdown()
+            .expect("Unable to shutdown connection manager");
+    }
+
     /// Test that heartbeats are correctly sent to tcp connections
     #[test]
     fn test_heartbeat_raw_tcp() {
@@ -12,7 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "peer-inspection")]
+use std::time::{Duration, UNIX_EPOCH};
+
+use protobuf::Message;
+
 use crate::network::dispatch::{DispatchError, Handler, MessageContext, MessageSender, PeerId};
+#[cfg(feature = "peer-inspection")]
+use crate::peer::PeerManagerConnector;
 use crate::peer::{PeerAuthorizationToken, PeerTokenPair};
 use crate::protocol::network::{NetworkEcho, NetworkMessage};
 use crate::protos::network;
@@ -84,8 +91,14 @@ impl NetworkEchoHandler {
 }
 
 // Implements a handler that handles NetworkHeartbeat Messages
+//
+// Heartbeats that include the time they were sent are returned to the sender as replies. If the
+// handler has a peer connector, the round trip time of each reply is recorded for the peer.
 #[derive(Default)]
-pub struct NetworkHeartbeatHandler {}
+pub struct NetworkHeartbeatHandler {
+    #[cfg(feature = "peer-inspection")]
+    peer_connector: Option<PeerManagerConnector>,
+}
 
 impl Handler for NetworkHeartbeatHandler {
     type Source = PeerId;
@@ -98,18 +111,87 @@ impl Handler for NetworkHeartbeatHandler {
 
     fn handle(
         &self,
-        _msg: Self::Message,
+        mut msg: Self::Message,
         context: &MessageContext<Self::Source, Self::MessageType>,
-        _sender: &dyn MessageSender<Self::Source>,
+        sender: &dyn MessageSender<Self::Source>,
     ) -> Result<(), DispatchError> {
+        if msg.get_reply() {
+            trace!("Received Heartbeat reply from {}", context.source_peer_id());
+            #[cfg(feature = "peer-inspection")]
+            self.record_heartbeat_latency(msg.get_sent_at(), context.source_peer_id());
+            return Ok(());
+        }
+
         trace!("Received Heartbeat from {}", context.source_peer_id());
+        if msg.get_sent_at() == 0 {
+            return Ok(());
+        }
+
+        msg.set_reply(true);
+        let mut network_msg = network::NetworkMessage::new();
+        network_msg.set_message_type(network::NetworkMessageType::NETWORK_HEARTBEAT);
+        network_msg.set_payload(msg.write_to_bytes().map_err(|err| {
+            DispatchError::SerializationError(format!(
+                "cannot get bytes of NetworkHeartbeat: {}",
+                err
+            ))
+        })?);
+        let network_msg_bytes = network_msg.write_to_bytes().map_err(|err| {
+            DispatchError::SerializationError(format!(
+                "cannot get bytes of NetworkMessage: {}",
+                err
+            ))
+        })?;
+
+        sender
+            .send(context.source_peer_id().clone(), network_msg_bytes)
+            .map_err(|(recipient, payload)| {
+                DispatchError::NetworkSendError((recipient.into(), payload))
+            })?;
         Ok(())
     }
 }
 
 impl NetworkHeartbeatHandler {
     pub fn new() -> Self {
-        NetworkHeartbeatHandler {}
+        NetworkHeartbeatHandler::default()
+    }
+
+    /// Records the round trip time of the heartbeat replies received by this handler, using the
+    /// given peer connector.
+    #[cfg(feature = "peer-inspection")]
+    pub fn with_peer_connector(mut self, peer_connector: PeerManagerConnector) -> Self {
+        self.peer_connector = Some(peer_connector);
+        self
+    }
+
+    #[cfg(feature = "peer-inspection")]
+    fn record_heartbeat_latency(&self, sent_at: u64, peer_id: &PeerTokenPair) {
+        let peer_connector = match &self.peer_connector {
+            Some(peer_connector) => peer_connector,
+            None => return,
+        };
+
+        // the reply carries the local time the heartbeat was sent, so the clocks of the two nodes
+        // do not need to agree
+        let heartbeat_latency = match (UNIX_EPOCH + Duration::from_micros(sent_at)).elapsed() {
+            Ok(heartbeat_latency) => heartbeat_latency,
+            Err(_) => {
+                debug!(
+                    "Ignoring Heartbeat reply from {}, the system time has changed since it was \
+                        sent",
+                    peer_id
+                );
+                return;
+            }
+        };
+
+        if let Err(err) = peer_connector.record_heartbeat_latency(peer_id, heartbeat_latency) {
+            debug!(
+                "Unable to record heartbeat latency for {}: {}",
+                peer_id, err
+            );
+        }
     }
 }
 
@@ -117,13 +199,13 @@ impl NetworkHeartbeatHandler {
 mod tests {
     use super::*;
 
-    use protobuf::Message;
-
     use std::collections::VecDeque;
     use std::sync::{Arc, Mutex};
 
     use crate::network::dispatch::Dispatcher;
-    use crate::protos::network::{NetworkEcho, NetworkMessage, NetworkMessageType};
+    use crate::protos::network::{
+        NetworkEcho, NetworkHeartbeat, NetworkMessage, NetworkMessageType,
+    };
 
     #[test]
     fn dispatch_to_handler() {
@@ -169,6 +251,88 @@ mod tests {
         assert_eq!(echo.get_payload().to_vec(), b"HelloWorld".to_vec());
     }
 
+    // Test that a heartbeat that includes the time it was sent is returned to its sender as a
+    // reply, with the same time
+    #[test]
+    fn heartbeat_reply() {
+        let network_sender = MockSender::new();
+        let mut dispatcher: Dispatcher<NetworkMessageType> =
+            Dispatcher::new(Box::new(network_sender.clone()));
+
+        dispatcher.set_handler(Box::new(NetworkHeartbeatHandler::new()));
+
+        let msg = {
+            let mut heartbeat = NetworkHeartbeat::new();
+            heartbeat.set_sent_at(42);
+            heartbeat
+        };
+
+        let source: PeerId = PeerTokenPair::new(
+            PeerAuthorizationToken::from_peer_id("OTHER_PEER"),
+            PeerAuthorizationToken::from_peer_id("TestPeer"),
+        )
+        .into();
+
+        assert!(dispatcher
+            .dispatch(
+                source.clone(),
+                &NetworkMessageType::NETWORK_HEARTBEAT,
+                msg.write_to_bytes().unwrap()
+            )
+            .is_ok());
+
+        let (recipient, network_message) = network_sender
+            .next_outbound()
+            .expect("Unable to get expected message");
+
+        assert_eq!(recipient, source);
+
+        let network_msg: NetworkMessage = Message::parse_from_bytes(&network_message).unwrap();
+        assert_eq!(
+            network_msg.get_message_type(),
+            NetworkMessageType::NETWORK_HEARTBEAT
+        );
+        let heartbeat: NetworkHeartbeat =
+            Message::parse_from_bytes(network_msg.get_payload()).unwrap();
+
+        assert!(heartbeat.get_reply());
+        assert_eq!(heartbeat.get_sent_at(), 42);
+    }
+
+    // Test that heartbeats without the time they were sent, and heartbeat replies, are not
+    // replied to
+    #[test]
+    fn heartbeat_no_reply() {
+        let network_sender = MockSender::new();
+        let mut dispatcher: Dispatcher<NetworkMessageType> =
+            Dispatcher::new(Box::new(network_sender.clone()));
+
+        dispatcher.set_handler(Box::new(NetworkHeartbeatHandler::new()));
+
+        let reply = {
+            let mut heartbeat = NetworkHeartbeat::new();
+            heartbeat.set_sent_at(42);
+            heartbeat.set_reply(true);
+            heartbeat
+        };
+
+        for msg in [NetworkHeartbeat::new(), reply] {
+            assert!(dispatcher
+                .dispatch(
+                    PeerTokenPair::new(
+                        PeerAuthorizationToken::from_peer_id("OTHER_PEER"),
+                        PeerAuthorizationToken::from_peer_id("TestPeer"),
+                    )
+                    .into(),
+                    &NetworkMessageType::NETWORK_HEARTBEAT,
+                    msg.write_to_bytes().unwrap()
+                )
+                .is_ok());
+        }
+
+        assert!(network_sender.next_outbound().is_none());
+    }
+
     #[derive(Clone)]
     struct MockSender {
         outbound: Arc<Mutex<VecDeque<(PeerId, Vec<u8>)>>>,
@@ -15,6 +15,7 @@
 //! Data structures for communicating with the PeerManager.
 
 use std::sync::mpsc::{channel, Sender};
+use std::time::Duration;
 
 use crate::collections::BiHashMap;
 
@@ -22,7 +23,11 @@ use super::error::{
     PeerConnectionIdError, PeerListError, PeerLookupError, PeerManagerError, PeerRefAddError,
     PeerRefRemoveError, PeerUnknownAddError,
 };
+#[cfg(feature = "peer-inspection")]
+use super::info::PeerInfo;
 use super::notification::{PeerManagerNotification, PeerNotificationIter, SubscriberId};
+#[cfg(feature = "peer-retry-policy")]
+use super::retry::{PeerRetryState, RetryPolicy};
 use super::{EndpointPeerRef, PeerRef};
 use super::{PeerAuthorizationToken, PeerTokenPair};
 use super::{PeerManagerMessage, PeerManagerRequest};
@@ -92,6 +97,54 @@ impl PeerManagerConnector {
             peer_id,
             endpoints,
             required_local_auth,
+            #[cfg(feature = "peer-retry-policy")]
+            retry_policy: None,
+            sender,
+        });
+
+        match self.sender.send(message) {
+            Ok(()) => (),
+            Err(_) => {
+                return Err(PeerRefAddError::InternalError(
+                    "Unable to send message to PeerManager, receiver dropped".to_string(),
+                ))
+            }
+        };
+
+        recv.recv()
+            .map_err(|err| PeerRefAddError::ReceiveError(format!("{:?}", err)))?
+    }
+
+    /// Requests that a peer is added to the `PeerManager`, using the given retry policy when
+    /// connecting to the peer. If a peer already exists, the peer's reference count will be
+    /// incremented and its retry policy will be replaced.
+    ///
+    /// Returns a `PeerRef` that, when dropped, will automatically send a removal request to the
+    /// `PeerManager`.
+    ///
+    /// # Arguments
+    ///
+    /// * `peer_id` -  The unique PeerAuthorizationToken for the peer.
+    /// * `endpoints` -  The list of endpoints associated with the peer. The list should be in
+    ///   order of preference, with the first endpoint being the first attempted.
+    /// * `required_local_auth` - The local authorization that must be used to identify the local
+    ///   node during authorization.
+    /// * `retry_policy` - How the `PeerManager` retries connecting to the peer.
+    #[cfg(feature = "peer-retry-policy")]
+    pub fn add_peer_ref_with_retry_policy(
+        &self,
+        peer_id: PeerAuthorizationToken,
+        endpoints: Vec<String>,
+        required_local_auth: PeerAuthorizationToken,
+        retry_policy: RetryPolicy,
+    ) -> Result<PeerRef, PeerRefAddError> {
+        let (sender, recv) = channel();
+
+        let message = PeerManagerMessage::Request(PeerManagerRequest::AddPeer {
+            peer_id,
+            endpoints,
+            required_local_auth,
+            retry_policy: Some(retry_policy),
             sender,
         });
 
@@ -128,6 +181,49 @@ impl PeerManagerConnector {
         let message = PeerManagerMessage::Request(PeerManagerRequest::AddUnidentified {
             endpoint,
             local_authorization,
+            #[cfg(feature = "peer-retry-policy")]
+            retry_policy: None,
+            sender,
+        });
+
+        match self.sender.send(message) {
+            Ok(()) => (),
+            Err(_) => {
+                return Err(PeerUnknownAddError::InternalError(
+                    "Unable to send message to PeerManager, receiver dropped".to_string(),
+                ))
+            }
+        };
+
+        recv.recv()
+            .map_err(|err| PeerUnknownAddError::ReceiveError(format!("{:?}", err)))?
+    }
+
+    /// Requests that a peer is added to the `PeerManager` by endpoint, using the given retry
+    /// policy when connecting to the endpoint. This function should be used when the peer ID is
+    /// unknown. Once the peer connects, the retry policy applies to the peer.
+    ///
+    /// Returns `Ok(EndpointPeerRef)` if the unidentified peer was added
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint` - The endpoint associated with the peer.
+    /// * `local_authorization` - The required PeerAuthorizationToken that will be used to identify
+    /// *  the local node.
+    /// * `retry_policy` - How the `PeerManager` retries connecting to the endpoint.
+    #[cfg(feature = "peer-retry-policy")]
+    pub fn add_unidentified_peer_with_retry_policy(
+        &self,
+        endpoint: String,
+        local_authorization: PeerAuthorizationToken,
+        retry_policy: RetryPolicy,
+    ) -> Result<EndpointPeerRef, PeerUnknownAddError> {
+        let (sender, recv) = channel();
+
+        let message = PeerManagerMessage::Request(PeerManagerRequest::AddUnidentified {
+            endpoint,
+            local_authorization,
+            retry_policy: Some(retry_policy),
             sender,
         });
 
@@ -187,6 +283,46 @@ impl PeerManagerConnector {
             .map_err(|err| PeerListError::Receive(format!("{:?}", err)))?
     }
 
+    /// Requests the state of every peer, including peers that have connected but are not
+    /// referenced locally.
+    #[cfg(feature = "peer-inspection")]
+    pub fn list_peer_info(&self) -> Result<Vec<PeerInfo>, PeerListError> {
+        let (sender, recv) = channel();
+        let message = PeerManagerMessage::Request(PeerManagerRequest::ListPeerInfo { sender });
+
+        match self.sender.send(message) {
+            Ok(()) => (),
+            Err(_) => {
+                return Err(PeerListError::Internal(
+                    "Unable to send message to PeerManager, receiver dropped".to_string(),
+                ))
+            }
+        };
+
+        recv.recv()
+            .map_err(|err| PeerListError::Receive(format!("{:?}", err)))?
+    }
+
+    /// Requests the retry state of every peer, and of every endpoint that was requested without
+    /// a peer ID and has not yet connected.
+    #[cfg(feature = "peer-retry-policy")]
+    pub fn list_retry_states(&self) -> Result<Vec<PeerRetryState>, PeerListError> {
+        let (sender, recv) = channel();
+        let message = PeerManagerMessage::Request(PeerManagerRequest::ListRetryStates { sender });
+
+        match self.sender.send(message) {
+            Ok(()) => (),
+            Err(_) => {
+                return Err(PeerListError::Internal(
+                    "Unable to send message to PeerManager, receiver dropped".to_string(),
+                ))
+            }
+        };
+
+        recv.recv()
+            .map_err(|err| PeerListError::Receive(format!("{:?}", err)))?
+    }
+
     /// Requests the map of currently connected peers to connection IDs
     ///
     /// Returns a map of peer IDs to connection IDs
@@ -209,6 +345,55 @@ impl PeerManagerConnector {
             .map_err(|err| PeerConnectionIdError::ReceiveError(format!("{:?}", err)))?
     }
 
+    /// Requests how long the given peer's current connection took to establish.
+    ///
+    /// Returns `None` if the peer is not referenced or if its connection was not requested by the
+    /// local node.
+    pub fn connection_latency(
+        &self,
+        peer_id: &PeerTokenPair,
+    ) -> Result<Option<Duration>, PeerLookupError> {
+        let (sender, recv) = channel();
+        let message = PeerManagerMessage::Request(PeerManagerRequest::GetConnectionLatency {
+            peer_id: peer_id.clone(),
+            sender,
+        });
+
+        match self.sender.send(message) {
+            Ok(()) => (),
+            Err(_) => {
+                return Err(PeerLookupError(
+                    "Unable to send message to PeerManager, receiver dropped".to_string(),
+                ))
+            }
+        };
+
+        recv.recv()
+            .map_err(|err| PeerLookupError(format!("{:?}", err)))?
+    }
+
+    /// Records the round trip time of a heartbeat the given peer replied to.
+    ///
+    /// The latency is not recorded if the peer is not connected. This does not wait for the
+    /// `PeerManager` to record the latency.
+    #[cfg(feature = "peer-inspection")]
+    pub fn record_heartbeat_latency(
+        &self,
+        peer_id: &PeerTokenPair,
+        heartbeat_latency: Duration,
+    ) -> Result<(), PeerManagerError> {
+        let message = PeerManagerMessage::Request(PeerManagerRequest::RecordHeartbeatLatency {
+            peer_id: peer_id.clone(),
+            heartbeat_latency,
+        });
+
+        self.sender.send(message).map_err(|_| {
+            PeerManagerError::SendMessageError(
+                "Unable to send message to PeerManager, receiver dropped".to_string(),
+            )
+        })
+    }
+
     /// Subscribes to `PeerManager` notifications.
     ///
     /// Returns a `PeerNotificationIter` that can be used to receive notifications about connected
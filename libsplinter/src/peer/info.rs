@@ -0,0 +1,120 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Reports of the state of the peers known to the `PeerManager`.
+
+use std::time::{Duration, SystemTime};
+
+use super::peer_map::PeerStatus;
+use super::PeerAuthorizationToken;
+
+/// A report of the state of a peer known to the `PeerManager`.
+///
+/// Peers that have connected to the local node but have not been referenced locally are reported
+/// with a reference count of zero.
+#[derive(Clone, Debug)]
+pub struct PeerInfo {
+    peer_id: PeerAuthorizationToken,
+    local_authorization: PeerAuthorizationToken,
+    connection_id: String,
+    endpoints: Vec<String>,
+    active_endpoint: String,
+    status: PeerStatus,
+    ref_count: u64,
+    connected_since: Option<SystemTime>,
+    connection_latency: Option<Duration>,
+    heartbeat_latency: Option<Duration>,
+}
+
+impl PeerInfo {
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn new(
+        peer_id: PeerAuthorizationToken,
+        local_authorization: PeerAuthorizationToken,
+        connection_id: String,
+        endpoints: Vec<String>,
+        active_endpoint: String,
+        status: PeerStatus,
+        ref_count: u64,
+        connected_since: Option<SystemTime>,
+        connection_latency: Option<Duration>,
+        heartbeat_latency: Option<Duration>,
+    ) -> Self {
+        PeerInfo {
+            peer_id,
+            local_authorization,
+            connection_id,
+            endpoints,
+            active_endpoint,
+            status,
+            ref_count,
+            connected_since,
+            connection_latency,
+            heartbeat_latency,
+        }
+    }
+
+    /// Returns the ID of the peer.
+    pub fn peer_id(&self) -> &PeerAuthorizationToken {
+        &self.peer_id
+    }
+
+    /// Returns the authorization the local node uses to identify itself to the peer.
+    pub fn local_authorization(&self) -> &PeerAuthorizationToken {
+        &self.local_authorization
+    }
+
+    /// Returns the ID of the peer's current connection.
+    pub fn connection_id(&self) -> &str {
+        &self.connection_id
+    }
+
+    /// Returns the endpoints the peer is reachable at.
+    pub fn endpoints(&self) -> &[String] {
+        &self.endpoints
+    }
+
+    /// Returns the endpoint of the peer's current connection.
+    pub fn active_endpoint(&self) -> &str {
+        &self.active_endpoint
+    }
+
+    /// Returns the status of the peer.
+    pub fn status(&self) -> &PeerStatus {
+        &self.status
+    }
+
+    /// Returns the number of references held to the peer.
+    pub fn ref_count(&self) -> u64 {
+        self.ref_count
+    }
+
+    /// Returns when the peer's current connection was established, or `None` if it is not
+    /// connected.
+    pub fn connected_since(&self) -> Option<SystemTime> {
+        self.connected_since
+    }
+
+    /// Returns how long the peer's current connection took to establish, if it was requested
+    /// locally.
+    pub fn connection_latency(&self) -> Option<Duration> {
+        self.connection_latency
+    }
+
+    /// Returns the round trip time of the last heartbeat the peer replied to on its current
+    /// connection, if it has replied to one.
+    pub fn heartbeat_latency(&self) -> Option<Duration> {
+        self.heartbeat_latency
+    }
+}
@@ -25,10 +25,14 @@
 mod builder;
 mod connector;
 mod error;
+#[cfg(feature = "peer-inspection")]
+mod info;
 pub mod interconnect;
 mod notification;
 mod peer_map;
 mod peer_ref;
+#[cfg(feature = "peer-retry-policy")]
+mod retry;
 mod token;
 mod unreferenced;
 
@@ -36,7 +40,9 @@ use std::cmp::min;
 use std::io::ErrorKind;
 use std::sync::mpsc::{channel, Sender};
 use std::thread;
-use std::time::Instant;
+#[cfg(feature = "peer-inspection")]
+use std::time::SystemTime;
+use std::time::{Duration, Instant};
 
 use uuid::Uuid;
 
@@ -54,10 +60,20 @@ use self::error::{
     PeerConnectionIdError, PeerListError, PeerLookupError, PeerManagerError, PeerRefAddError,
     PeerRefRemoveError, PeerUnknownAddError,
 };
+#[cfg(feature = "peer-inspection")]
+pub use self::info::PeerInfo;
 pub use self::notification::{PeerManagerNotification, PeerNotificationIter, SubscriberId};
 use self::notification::{Subscriber, SubscriberMap};
-use self::peer_map::{PeerMap, PeerStatus};
+use self::peer_map::PeerMap;
+#[cfg(feature = "peer-inspection")]
+pub use self::peer_map::PeerStatus;
+#[cfg(not(feature = "peer-inspection"))]
+use self::peer_map::PeerStatus;
 pub use self::peer_ref::{EndpointPeerRef, PeerRef};
+#[cfg(feature = "peer-retry-policy")]
+use self::retry::PeerRetry;
+#[cfg(feature = "peer-retry-policy")]
+pub use self::retry::{PeerRetryState, PeerRetryStatus, RetryPolicy, RetryPolicyBuilder};
 pub use self::token::{PeerAuthorizationToken, PeerTokenPair};
 use self::unreferenced::{RequestedEndpoint, UnreferencedPeer, UnreferencedPeerState};
 
@@ -88,11 +104,15 @@ pub(crate) enum PeerManagerRequest {
         peer_id: PeerAuthorizationToken,
         endpoints: Vec<String>,
         required_local_auth: PeerAuthorizationToken,
+        #[cfg(feature = "peer-retry-policy")]
+        retry_policy: Option<RetryPolicy>,
         sender: Sender<Result<PeerRef, PeerRefAddError>>,
     },
     AddUnidentified {
         endpoint: String,
         local_authorization: PeerAuthorizationToken,
+        #[cfg(feature = "peer-retry-policy")]
+        retry_policy: Option<RetryPolicy>,
         sender: Sender<Result<EndpointPeerRef, PeerUnknownAddError>>,
     },
     RemovePeer {
@@ -110,6 +130,14 @@ pub(crate) enum PeerManagerRequest {
     ListUnreferencedPeers {
         sender: Sender<Result<Vec<PeerTokenPair>, PeerListError>>,
     },
+    #[cfg(feature = "peer-inspection")]
+    ListPeerInfo {
+        sender: Sender<Result<Vec<PeerInfo>, PeerListError>>,
+    },
+    #[cfg(feature = "peer-retry-policy")]
+    ListRetryStates {
+        sender: Sender<Result<Vec<PeerRetryState>, PeerListError>>,
+    },
     ConnectionIds {
         sender: Sender<Result<BiHashMap<PeerTokenPair, String>, PeerConnectionIdError>>,
     },
@@ -121,6 +149,15 @@ pub(crate) enum PeerManagerRequest {
         connection_id: String,
         sender: Sender<Result<Option<PeerTokenPair>, PeerLookupError>>,
     },
+    GetConnectionLatency {
+        peer_id: PeerTokenPair,
+        sender: Sender<Result<Option<Duration>, PeerLookupError>>,
+    },
+    #[cfg(feature = "peer-inspection")]
+    RecordHeartbeatLatency {
+        peer_id: PeerTokenPair,
+        heartbeat_latency: Duration,
+    },
     Subscribe {
         sender: Sender<Result<SubscriberId, PeerManagerError>>,
         callback: Subscriber,
@@ -380,6 +417,8 @@ fn handle_request(
             peer_id,
             endpoints,
             required_local_auth,
+            #[cfg(feature = "peer-retry-policy")]
+            retry_policy,
             sender,
         } => {
             if sender
@@ -393,6 +432,8 @@ fn handle_request(
                     ref_map,
                     subscribers,
                     required_local_auth,
+                    #[cfg(feature = "peer-retry-policy")]
+                    retry_policy,
                 ))
                 .is_err()
             {
@@ -402,6 +443,8 @@ fn handle_request(
         PeerManagerRequest::AddUnidentified {
             endpoint,
             local_authorization,
+            #[cfg(feature = "peer-retry-policy")]
+            retry_policy,
             sender,
         } => {
             if sender
@@ -413,6 +456,8 @@ fn handle_request(
                     peers,
                     ref_map,
                     local_authorization,
+                    #[cfg(feature = "peer-retry-policy")]
+                    retry_policy,
                 )))
                 .is_err()
             {
@@ -469,6 +514,24 @@ fn handle_request(
                 warn!("Connector dropped before receiving result of list unreferenced peers");
             }
         }
+        #[cfg(feature = "peer-inspection")]
+        PeerManagerRequest::ListPeerInfo { sender } => {
+            if sender
+                .send(Ok(list_peer_info(peers, unreferenced_peers, ref_map)))
+                .is_err()
+            {
+                warn!("Connector dropped before receiving result of list peer info");
+            }
+        }
+        #[cfg(feature = "peer-retry-policy")]
+        PeerManagerRequest::ListRetryStates { sender } => {
+            if sender
+                .send(Ok(list_retry_states(peers, unreferenced_peers)))
+                .is_err()
+            {
+                warn!("Connector dropped before receiving result of list retry states");
+            }
+        }
         PeerManagerRequest::ConnectionIds { sender } => {
             if sender.send(Ok(peers.connection_ids())).is_err() {
                 warn!("Connector dropped before receiving result of connection IDs");
@@ -506,6 +569,26 @@ fn handle_request(
                 warn!("Connector dropped before receiving result of getting peer ID");
             }
         }
+        PeerManagerRequest::GetConnectionLatency { peer_id, sender } => {
+            let latency = peers
+                .get_by_peer_id(&peer_id)
+                .and_then(|meta| meta.connection_latency);
+
+            if sender.send(Ok(latency)).is_err() {
+                warn!("Connector dropped before receiving result of getting connection latency");
+            }
+        }
+        #[cfg(feature = "peer-inspection")]
+        PeerManagerRequest::RecordHeartbeatLatency {
+            peer_id,
+            heartbeat_latency,
+        } => {
+            if let Some(unreferenced_peer) = unreferenced_peers.peers.get_mut(&peer_id) {
+                unreferenced_peer.heartbeat_latency = Some(heartbeat_latency);
+            } else {
+                peers.set_heartbeat_latency(&peer_id, heartbeat_latency);
+            }
+        }
         PeerManagerRequest::Subscribe { sender, callback } => {
             let subscriber_id = subscribers.add_subscriber(callback);
             if sender.send(Ok(subscriber_id)).is_err() {
@@ -537,6 +620,7 @@ fn add_peer(
     ref_map: &mut RefMap<PeerTokenPair>,
     subscribers: &mut SubscriberMap,
     required_local_auth: PeerAuthorizationToken,
+    #[cfg(feature = "peer-retry-policy")] retry_policy: Option<RetryPolicy>,
 ) -> Result<PeerRef, PeerRefAddError> {
     let peer_token_pair = PeerTokenPair::new(peer_id.clone(), required_local_auth.clone());
 
@@ -596,6 +680,16 @@ fn add_peer(
                 }
             }
 
+            // a policy in the latest request replaces the peer's existing policy
+            #[cfg(feature = "peer-retry-policy")]
+            if let Some(retry_policy) = retry_policy {
+                let mut retry = PeerRetry::new(retry_policy);
+                if peer_metadata.status != PeerStatus::Connected {
+                    retry.restart();
+                }
+                peers.set_retry(&peer_token_pair, retry);
+            }
+
             // notify subscribers this peer is connected
             if peer_metadata.status == PeerStatus::Connected {
                 // Update peer for new state
@@ -620,6 +714,10 @@ fn add_peer(
         connection_id,
         endpoint,
         old_connection_ids,
+        #[cfg(feature = "peer-inspection")]
+        connected_since,
+        #[cfg(feature = "peer-inspection")]
+        heartbeat_latency,
         ..
     }) = unreferenced_peers.peers.remove(&peer_token_pair)
     {
@@ -634,6 +732,19 @@ fn add_peer(
             old_connection_ids,
         );
 
+        #[cfg(feature = "peer-inspection")]
+        {
+            peers.set_connected_since(&peer_token_pair, connected_since);
+            if let Some(heartbeat_latency) = heartbeat_latency {
+                peers.set_heartbeat_latency(&peer_token_pair, heartbeat_latency);
+            }
+        }
+
+        #[cfg(feature = "peer-retry-policy")]
+        if let Some(retry_policy) = retry_policy {
+            peers.set_retry(&peer_token_pair, PeerRetry::new(retry_policy));
+        }
+
         // Update peer for new state
         let notification = PeerManagerNotification::Connected {
             peer: peer_token_pair.clone(),
@@ -691,19 +802,31 @@ fn add_peer(
         required_local_auth,
         vec![],
     );
+
+    #[cfg(feature = "peer-retry-policy")]
+    if let Some(retry_policy) = retry_policy {
+        let mut retry = PeerRetry::new(retry_policy);
+        retry.record_attempt();
+        peers.set_retry(&peer_token_pair, retry);
+    }
+
     let peer_ref = PeerRef::new(peer_token_pair, peer_remover.clone());
     Ok(peer_ref)
 }
 
 // Request a connection, the resulting connection will be treated as an InboundConnection
+// Allow clippy errors for too_many_arguments. The arguments are required
+// to avoid needing a lock in the PeerManager.
+#[allow(clippy::too_many_arguments)]
 fn add_unidentified(
     endpoint: String,
     connector: Connector,
     unreferenced_peers: &mut UnreferencedPeerState,
     peer_remover: &PeerRemover,
-    peers: &PeerMap,
+    peers: &mut PeerMap,
     ref_map: &mut RefMap<PeerTokenPair>,
     local_authorization: PeerAuthorizationToken,
+    #[cfg(feature = "peer-retry-policy")] retry_policy: Option<RetryPolicy>,
 ) -> EndpointPeerRef {
     info!("Attempting to peer with peer by endpoint {}", endpoint);
     if let Some(peer_metadatas) = peers.get_peer_from_endpoint(&endpoint) {
@@ -714,6 +837,16 @@ fn add_unidentified(
                     peer_metadata.id.clone(),
                     peer_metadata.required_local_auth.clone(),
                 );
+                // a policy in the latest request replaces the peer's existing policy
+                #[cfg(feature = "peer-retry-policy")]
+                if let Some(retry_policy) = retry_policy {
+                    let mut retry = PeerRetry::new(retry_policy);
+                    if peer_metadata.status != PeerStatus::Connected {
+                        retry.restart();
+                    }
+                    peers.set_retry(&peer_token_pair, retry);
+                }
+
                 // if there is peer in the peer_map, there is reference in the ref map
                 ref_map.add_ref(peer_token_pair);
                 return EndpointPeerRef::new(
@@ -742,6 +875,12 @@ fn add_unidentified(
         RequestedEndpoint {
             endpoint: endpoint.to_string(),
             local_authorization,
+            #[cfg(feature = "peer-retry-policy")]
+            retry: retry_policy.map(|retry_policy| {
+                let mut retry = PeerRetry::new(retry_policy);
+                retry.record_attempt();
+                retry
+            }),
         },
     );
     EndpointPeerRef::new(endpoint, connection_id, peer_remover.clone())
@@ -1046,7 +1185,15 @@ fn handle_disconnection(
             ),
         };
         info!("Peer {} is currently disconnected", peer_metadata.id);
-        if peer_metadata.endpoints.contains(&endpoint) {
+
+        // a peer with a retry policy is reconnected by the peer manager, instead of by the
+        // connection manager
+        #[cfg(feature = "peer-retry-policy")]
+        let has_retry_policy = peer_metadata.retry.is_some();
+        #[cfg(not(feature = "peer-retry-policy"))]
+        let has_retry_policy = false;
+
+        if peer_metadata.endpoints.contains(&endpoint) && !has_retry_policy {
             // allow peer manager to retry connection to that endpoint until the retry max is
             // reached
 
@@ -1056,9 +1203,9 @@ fn handle_disconnection(
                 error!("Unable to update peer: {}", err);
             }
         } else {
-            // the disconnected endpoint is an inbound connection. This connection should
-            // be removed, peer set to pending and the endpoints in the peer metadata
-            // should be tried
+            // the disconnected endpoint is an inbound connection or the peer has a retry policy.
+            // This connection should be removed, peer set to pending and the endpoints in the
+            // peer metadata should be tried
             if let Err(err) = connector
                 .remove_connection(&peer_metadata.active_endpoint, &peer_metadata.connection_id)
             {
@@ -1079,6 +1226,13 @@ fn handle_disconnection(
                     }
                 }
             }
+
+            #[cfg(feature = "peer-retry-policy")]
+            if let Some(retry) = peer_metadata.retry.as_mut() {
+                retry.reset();
+                retry.record_attempt();
+            }
+
             peer_metadata.status = PeerStatus::Pending;
             if let Err(err) = peers.update_peer(peer_metadata) {
                 error!("Unable to update peer: {}", err);
@@ -1166,6 +1320,10 @@ fn handle_inbound_connection(
         // reset retry settings
         peer_metadata.retry_frequency = retry_frequency;
         peer_metadata.last_connection_attempt = Instant::now();
+        #[cfg(feature = "peer-retry-policy")]
+        if let Some(retry) = peer_metadata.retry.as_mut() {
+            retry.reset();
+        }
 
         let notification = PeerManagerNotification::Connected {
             peer: peer_token_pair.clone(),
@@ -1221,6 +1379,10 @@ fn handle_inbound_connection(
                 endpoint,
                 local_authorization,
                 old_connection_ids,
+                #[cfg(feature = "peer-inspection")]
+                connected_since: SystemTime::now(),
+                #[cfg(feature = "peer-inspection")]
+                heartbeat_latency: None,
             };
         }
     } else {
@@ -1235,6 +1397,10 @@ fn handle_inbound_connection(
                 endpoint,
                 local_authorization,
                 old_connection_ids: vec![],
+                #[cfg(feature = "peer-inspection")]
+                connected_since: SystemTime::now(),
+                #[cfg(feature = "peer-inspection")]
+                heartbeat_latency: None,
             },
         );
     }
@@ -1306,9 +1472,15 @@ fn handle_connected(
         peer_metadata.active_endpoint = endpoint;
         peer_metadata.status = PeerStatus::Connected;
         peer_metadata.connection_id = connection_id.clone();
+        // the connection attempt that just succeeded was started at the last connection attempt
+        peer_metadata.connection_latency = Some(peer_metadata.last_connection_attempt.elapsed());
         // reset retry settings
         peer_metadata.retry_frequency = retry_frequency;
         peer_metadata.last_connection_attempt = Instant::now();
+        #[cfg(feature = "peer-retry-policy")]
+        if let Some(retry) = peer_metadata.retry.as_mut() {
+            retry.reset();
+        }
 
         if let Err(err) = peers.update_peer(peer_metadata) {
             error!("Unable to update peer: {}", err);
@@ -1330,6 +1502,10 @@ fn handle_connected(
             let mut new_peer_endpoint = endpoint.to_string();
             let mut new_peer_connection_id = connection_id.clone();
             let mut old_connection_ids = vec![];
+            #[cfg(feature = "peer-inspection")]
+            let mut connected_since = None;
+            #[cfg(feature = "peer-inspection")]
+            let mut heartbeat_latency = None;
             if let Some(unreferenced_peer) = unreferenced_peers.peers.remove(&peer_token_pair) {
                 if unreferenced_peer.local_authorization < identity {
                     info!(
@@ -1342,6 +1518,11 @@ fn handle_connected(
                     new_peer_connection_id = unreferenced_peer.connection_id.to_string();
                     old_connection_ids = unreferenced_peer.old_connection_ids.clone();
                     old_connection_ids.push(connection_id.clone());
+                    #[cfg(feature = "peer-inspection")]
+                    {
+                        connected_since = Some(unreferenced_peer.connected_since);
+                        heartbeat_latency = unreferenced_peer.heartbeat_latency;
+                    }
 
                     // we are already connected on another connection, remove this connection
                     if endpoint != unreferenced_peer.endpoint {
@@ -1382,6 +1563,25 @@ fn handle_connected(
                 old_connection_ids,
             );
 
+            // keep the time the existing unreferenced connection was established, and its last
+            // heartbeat latency
+            #[cfg(feature = "peer-inspection")]
+            {
+                if let Some(connected_since) = connected_since {
+                    peers.set_connected_since(&peer_token_pair, connected_since);
+                }
+                if let Some(heartbeat_latency) = heartbeat_latency {
+                    peers.set_heartbeat_latency(&peer_token_pair, heartbeat_latency);
+                }
+            }
+
+            // the retry policy of the requested endpoint is carried over to the peer
+            #[cfg(feature = "peer-retry-policy")]
+            if let Some(mut retry) = requested_endpoint.retry.clone() {
+                retry.reset();
+                peers.set_retry(&peer_token_pair, retry);
+            }
+
             let notification = PeerManagerNotification::Connected {
                 peer: peer_token_pair.clone(),
             };
@@ -1426,6 +1626,10 @@ fn handle_connected(
                     endpoint,
                     local_authorization,
                     old_connection_ids,
+                    #[cfg(feature = "peer-inspection")]
+                    connected_since: SystemTime::now(),
+                    #[cfg(feature = "peer-inspection")]
+                    heartbeat_latency: None,
                 };
             }
         } else {
@@ -1440,6 +1644,10 @@ fn handle_connected(
                     endpoint,
                     local_authorization,
                     old_connection_ids: vec![],
+                    #[cfg(feature = "peer-inspection")]
+                    connected_since: SystemTime::now(),
+                    #[cfg(feature = "peer-inspection")]
+                    heartbeat_latency: None,
                 },
             );
         }
@@ -1492,12 +1700,37 @@ fn retry_pending(
 ) {
     let mut to_retry = Vec::new();
     for (_, peer) in peers.get_pending() {
+        #[cfg(feature = "peer-retry-policy")]
+        if let Some(retry) = &peer.retry {
+            if retry.is_due() {
+                to_retry.push(peer.clone());
+            }
+            continue;
+        }
+
         if peer.last_connection_attempt.elapsed().as_secs() > peer.retry_frequency {
             to_retry.push(peer.clone());
         }
     }
 
     for mut peer_metadata in to_retry {
+        #[cfg(feature = "peer-retry-policy")]
+        if let Some(retry) = peer_metadata.retry.as_mut() {
+            if retry.is_exhausted() {
+                warn!(
+                    "Giving up on peer {} after {} attempts",
+                    peer_metadata.id,
+                    retry.attempts()
+                );
+                retry.give_up();
+                if let Err(err) = peers.update_peer(peer_metadata) {
+                    error!("Unable to update peer: {}", err);
+                }
+                continue;
+            }
+            retry.record_attempt();
+        }
+
         debug!("Attempting to peer with pending peer {}", peer_metadata.id);
         for endpoint in peer_metadata.endpoints.iter() {
             match connector.request_connection(
@@ -1521,63 +1754,228 @@ fn retry_pending(
         }
     }
 
-    if unreferenced_peers
+    let retry_requested_endpoints = unreferenced_peers
         .last_connection_attempt
         .elapsed()
         .as_secs()
-        > unreferenced_peers.retry_frequency
-    {
-        for (endpoint, requested_endpoint) in unreferenced_peers.requested_endpoints.iter() {
-            if peers.contains_endpoint(&requested_endpoint.endpoint) {
+        > unreferenced_peers.retry_frequency;
+
+    #[cfg(feature = "peer-retry-policy")]
+    let requested_endpoints = unreferenced_peers.requested_endpoints.iter_mut();
+    #[cfg(not(feature = "peer-retry-policy"))]
+    let requested_endpoints = unreferenced_peers.requested_endpoints.iter();
+
+    for (endpoint, requested_endpoint) in requested_endpoints {
+        if peers.contains_endpoint(&requested_endpoint.endpoint) {
+            continue;
+        }
+
+        #[cfg(feature = "peer-retry-policy")]
+        if let Some(retry) = requested_endpoint.retry.as_mut() {
+            if !retry.is_due() {
                 continue;
             }
-            info!("Attempting to peer with peer by {}", endpoint);
-            let connection_id = format!("{}", Uuid::new_v4());
-            match connector.request_connection(
-                endpoint,
-                &connection_id,
-                None,
-                Some(requested_endpoint.local_authorization.clone().into()),
-            ) {
-                Ok(()) => (),
-                // If request_connection errored we will retry in the future
-                Err(err) => match err {
-                    ConnectionManagerError::ConnectionCreationError {
-                        context,
-                        error_kind: None,
-                    } => {
-                        info!(
-                            "Unable to request connection for peer endpoint {}: {}",
-                            endpoint, context
-                        );
-                    }
-                    ConnectionManagerError::ConnectionCreationError {
-                        context,
-                        error_kind: Some(err_kind),
-                    } => match err_kind {
-                        ErrorKind::ConnectionRefused => info!(
-                            "Received connection refused while attempting to establish a \
+            if retry.is_exhausted() {
+                warn!(
+                    "Giving up on peer by {} after {} attempts",
+                    endpoint,
+                    retry.attempts()
+                );
+                retry.give_up();
+                continue;
+            }
+            retry.record_attempt();
+        } else if !retry_requested_endpoints {
+            continue;
+        }
+        #[cfg(not(feature = "peer-retry-policy"))]
+        if !retry_requested_endpoints {
+            continue;
+        }
+
+        info!("Attempting to peer with peer by {}", endpoint);
+        let connection_id = format!("{}", Uuid::new_v4());
+        match connector.request_connection(
+            endpoint,
+            &connection_id,
+            None,
+            Some(requested_endpoint.local_authorization.clone().into()),
+        ) {
+            Ok(()) => (),
+            // If request_connection errored we will retry in the future
+            Err(err) => match err {
+                ConnectionManagerError::ConnectionCreationError {
+                    context,
+                    error_kind: None,
+                } => {
+                    info!(
+                        "Unable to request connection for peer endpoint {}: {}",
+                        endpoint, context
+                    );
+                }
+                ConnectionManagerError::ConnectionCreationError {
+                    context,
+                    error_kind: Some(err_kind),
+                } => match err_kind {
+                    ErrorKind::ConnectionRefused => info!(
+                        "Received connection refused while attempting to establish a \
                                         connection to peer at endpoint {}",
-                            endpoint
-                        ),
-                        _ => info!(
-                            "Unable to request connection for peer at {}: {}",
-                            endpoint, context,
-                        ),
-                    },
+                        endpoint
+                    ),
                     _ => info!(
-                        "Unable to request connection for peer at endpoint {}: {}",
-                        endpoint,
-                        err.to_string()
+                        "Unable to request connection for peer at {}: {}",
+                        endpoint, context,
                     ),
                 },
-            }
+                _ => info!(
+                    "Unable to request connection for peer at endpoint {}: {}",
+                    endpoint,
+                    err.to_string()
+                ),
+            },
         }
+    }
 
+    if retry_requested_endpoints {
         unreferenced_peers.last_connection_attempt = Instant::now();
     }
 }
 
+// Reports the retry state of every peer, and of every requested endpoint that has not yet
+// connected.
+#[cfg(feature = "peer-retry-policy")]
+fn list_retry_states(
+    peers: &PeerMap,
+    unreferenced_peers: &UnreferencedPeerState,
+) -> Vec<PeerRetryState> {
+    let mut retry_states: Vec<PeerRetryState> = peers
+        .peers()
+        .map(|peer_metadata| {
+            let (status, attempts, next_attempt) =
+                match (&peer_metadata.status, &peer_metadata.retry) {
+                    (PeerStatus::Connected, _) => (PeerRetryStatus::Connected, 0, None),
+                    (_, Some(retry)) if retry.gave_up() => {
+                        (PeerRetryStatus::GaveUp, retry.attempts(), None)
+                    }
+                    (_, Some(retry)) => (
+                        PeerRetryStatus::Retrying,
+                        retry.attempts(),
+                        retry.next_attempt(),
+                    ),
+                    // reconnection is handled by the connection manager
+                    (PeerStatus::Disconnected { retry_attempts }, None) => {
+                        (PeerRetryStatus::Retrying, *retry_attempts, None)
+                    }
+                    (PeerStatus::Pending, None) => (
+                        PeerRetryStatus::Retrying,
+                        0,
+                        Some(
+                            peer_metadata.last_connection_attempt
+                                + Duration::from_secs(peer_metadata.retry_frequency),
+                        ),
+                    ),
+                };
+
+            PeerRetryState::new(
+                Some(peer_metadata.id.clone()),
+                peer_metadata.endpoints.clone(),
+                status,
+                peer_metadata
+                    .retry
+                    .as_ref()
+                    .map(|retry| retry.policy().clone()),
+                attempts,
+                next_attempt,
+            )
+        })
+        .collect();
+
+    retry_states.extend(
+        unreferenced_peers
+            .requested_endpoints
+            .values()
+            .filter(|requested_endpoint| !peers.contains_endpoint(&requested_endpoint.endpoint))
+            .map(|requested_endpoint| match &requested_endpoint.retry {
+                Some(retry) => PeerRetryState::new(
+                    None,
+                    vec![requested_endpoint.endpoint.clone()],
+                    if retry.gave_up() {
+                        PeerRetryStatus::GaveUp
+                    } else {
+                        PeerRetryStatus::Retrying
+                    },
+                    Some(retry.policy().clone()),
+                    retry.attempts(),
+                    retry.next_attempt(),
+                ),
+                None => PeerRetryState::new(
+                    None,
+                    vec![requested_endpoint.endpoint.clone()],
+                    PeerRetryStatus::Retrying,
+                    None,
+                    0,
+                    Some(
+                        unreferenced_peers.last_connection_attempt
+                            + Duration::from_secs(unreferenced_peers.retry_frequency),
+                    ),
+                ),
+            }),
+    );
+
+    retry_states
+}
+
+// Reports the state of every peer, including unreferenced peers, which are reported with a
+// reference count of zero.
+#[cfg(feature = "peer-inspection")]
+fn list_peer_info(
+    peers: &PeerMap,
+    unreferenced_peers: &UnreferencedPeerState,
+    ref_map: &RefMap<PeerTokenPair>,
+) -> Vec<PeerInfo> {
+    let mut peer_info: Vec<PeerInfo> = peers
+        .peers()
+        .map(|peer_metadata| {
+            let peer_token_pair = PeerTokenPair::new(
+                peer_metadata.id.clone(),
+                peer_metadata.required_local_auth.clone(),
+            );
+
+            PeerInfo::new(
+                peer_metadata.id.clone(),
+                peer_metadata.required_local_auth.clone(),
+                peer_metadata.connection_id.clone(),
+                peer_metadata.endpoints.clone(),
+                peer_metadata.active_endpoint.clone(),
+                peer_metadata.status.clone(),
+                ref_map.ref_count(&peer_token_pair),
+                peer_metadata.connected_since,
+                peer_metadata.connection_latency,
+                peer_metadata.heartbeat_latency,
+            )
+        })
+        .collect();
+
+    peer_info.extend(unreferenced_peers.peers.iter().map(
+        |(peer_token_pair, unreferenced_peer)| {
+            PeerInfo::new(
+                peer_token_pair.peer_id().clone(),
+                unreferenced_peer.local_authorization.clone(),
+                unreferenced_peer.connection_id.clone(),
+                vec![unreferenced_peer.endpoint.clone()],
+                unreferenced_peer.endpoint.clone(),
+                PeerStatus::Connected,
+                0,
+                Some(unreferenced_peer.connected_since),
+                None,
+                unreferenced_peer.heartbeat_latency,
+            )
+        },
+    ));
+
+    peer_info
+}
+
 fn log_connect_request_err(
     err: ConnectionManagerError,
     peer_id: &PeerAuthorizationToken,
@@ -2160,6 +2558,123 @@ pub mod tests {
         mesh.wait_for_shutdown().expect("Unable to shutdown mesh");
     }
 
+    // Test that list_peer_info reports the state and reference count of a peer
+    //
+    // 1. add test_peer twice
+    // 2. verify that a Connected notification is received
+    // 3. record a heartbeat latency for test_peer
+    // 4. call list_peer_info
+    // 5. verify that test_peer is reported as connected on its endpoint with two references and
+    //    the recorded heartbeat latency
+    #[cfg(feature = "peer-inspection")]
+    #[test]
+    fn test_peer_manager_list_peer_info() {
+        let mut transport = Box::new(InprocTransport::default());
+        let mut listener = transport.listen("inproc://test").unwrap();
+
+        thread::spawn(move || {
+            listener.accept().unwrap();
+        });
+
+        let mut mesh = Mesh::new(512, 128);
+        let mut cm = ConnectionManager::builder()
+            .with_authorizer(Box::new(NoopAuthorizer::new("test_peer")))
+            .with_matrix_life_cycle(mesh.get_life_cycle())
+            .with_matrix_sender(mesh.get_sender())
+            .with_transport(transport)
+            .start()
+            .expect("Unable to start Connection Manager");
+
+        let connector = cm.connector();
+        let mut peer_manager = PeerManager::builder()
+            .with_connector(connector)
+            .with_retry_interval(1)
+            .with_identity("my_id".to_string())
+            .with_strict_ref_counts(true)
+            .start()
+            .expect("Cannot start peer_manager");
+        let peer_connector = peer_manager.connector();
+        let (tx, notification_rx): (
+            Sender<PeerManagerNotification>,
+            mpsc::Receiver<PeerManagerNotification>,
+        ) = channel();
+        peer_connector
+            .subscribe_sender(tx)
+            .expect("Unable to get subscriber");
+        let _peer_ref_1 = peer_connector
+            .add_peer_ref(
+                PeerAuthorizationToken::from_peer_id("test_peer"),
+                vec!["inproc://test".to_string()],
+                PeerAuthorizationToken::from_peer_id("my_id"),
+            )
+            .expect("Unable to add peer");
+
+        // timeout after 60 seconds
+        let notification = notification_rx
+            .recv_timeout(Duration::from_secs(60))
+            .expect("Unable to get new notifications");
+        assert!(
+            notification
+                == PeerManagerNotification::Connected {
+                    peer: PeerTokenPair::new(
+                        PeerAuthorizationToken::from_peer_id("test_peer"),
+                        PeerAuthorizationToken::from_peer_id("my_id"),
+                    )
+                }
+        );
+
+        let _peer_ref_2 = peer_connector
+            .add_peer_ref(
+                PeerAuthorizationToken::from_peer_id("test_peer"),
+                vec!["inproc://test".to_string()],
+                PeerAuthorizationToken::from_peer_id("my_id"),
+            )
+            .expect("Unable to add peer");
+
+        peer_connector
+            .record_heartbeat_latency(
+                &PeerTokenPair::new(
+                    PeerAuthorizationToken::from_peer_id("test_peer"),
+                    PeerAuthorizationToken::from_peer_id("my_id"),
+                ),
+                Duration::from_millis(20),
+            )
+            .expect("Unable to record heartbeat latency");
+
+        let peer_info = peer_connector
+            .list_peer_info()
+            .expect("Unable to list peer info");
+
+        assert_eq!(peer_info.len(), 1);
+        assert_eq!(
+            peer_info[0].peer_id(),
+            &PeerAuthorizationToken::from_peer_id("test_peer")
+        );
+        assert_eq!(
+            peer_info[0].local_authorization(),
+            &PeerAuthorizationToken::from_peer_id("my_id")
+        );
+        assert_eq!(peer_info[0].endpoints(), &["inproc://test".to_string()]);
+        assert_eq!(peer_info[0].active_endpoint(), "inproc://test");
+        assert_eq!(peer_info[0].status(), &PeerStatus::Connected);
+        assert_eq!(peer_info[0].ref_count(), 2);
+        assert!(peer_info[0].connected_since().is_some());
+        assert_eq!(
+            peer_info[0].heartbeat_latency(),
+            Some(Duration::from_millis(20))
+        );
+
+        peer_manager.signal_shutdown();
+        cm.signal_shutdown();
+        peer_manager
+            .wait_for_shutdown()
+            .expect("Unable to shutdown peer manager");
+        cm.wait_for_shutdown()
+            .expect("Unable to shutdown connection manager");
+        mesh.signal_shutdown();
+        mesh.wait_for_shutdown().expect("Unable to shutdown mesh");
+    }
+
     // Test that list_peer returns the correct list of connection IDs
     //
     // 1. add test_peer
@@ -2935,6 +3450,94 @@ pub mod tests {
         mesh.wait_for_shutdown().expect("Unable to shutdown mesh");
     }
 
+    // Test that a peer with a retry policy is given up on once the policy's attempts are
+    // exhausted.
+    //
+    // 1. add test_peer with a retry policy of two attempts, at an endpoint that is not listening
+    // 2. verify that the peer's retry state is reported with the policy
+    // 3. verify that the peer is reported as given up on after two attempts, with no next
+    //    attempt
+    #[cfg(feature = "peer-retry-policy")]
+    #[test]
+    fn test_peer_manager_retry_policy_give_up() {
+        let transport = Box::new(InprocTransport::default());
+
+        let mut mesh = Mesh::new(512, 128);
+        let mut cm = ConnectionManager::builder()
+            .with_authorizer(Box::new(NoopAuthorizer::new("test_peer")))
+            .with_matrix_life_cycle(mesh.get_life_cycle())
+            .with_matrix_sender(mesh.get_sender())
+            .with_transport(transport)
+            .start()
+            .expect("Unable to start Connection Manager");
+
+        let connector = cm.connector();
+        let mut peer_manager = PeerManager::builder()
+            .with_connector(connector)
+            .with_retry_interval(1)
+            .with_identity("my_id".to_string())
+            .with_strict_ref_counts(true)
+            .start()
+            .expect("Cannot start peer_manager");
+        let peer_connector = peer_manager.connector();
+
+        let retry_policy = RetryPolicy::builder()
+            .with_initial_interval(Duration::from_millis(10))
+            .with_jitter(0.0)
+            .with_max_attempts(2)
+            .build()
+            .expect("Unable to build retry policy");
+
+        let _peer_ref = peer_connector
+            .add_peer_ref_with_retry_policy(
+                PeerAuthorizationToken::from_peer_id("test_peer"),
+                vec!["inproc://missing".to_string()],
+                PeerAuthorizationToken::from_peer_id("my_id"),
+                retry_policy.clone(),
+            )
+            .expect("Unable to add peer");
+
+        let retry_states = peer_connector
+            .list_retry_states()
+            .expect("Unable to list retry states");
+        assert_eq!(retry_states.len(), 1);
+        assert_eq!(
+            retry_states[0].peer_id(),
+            Some(&PeerAuthorizationToken::from_peer_id("test_peer"))
+        );
+        assert_eq!(retry_states[0].policy(), Some(&retry_policy));
+
+        // timeout after 60 seconds
+        let start = Instant::now();
+        let retry_state = loop {
+            let retry_state = peer_connector
+                .list_retry_states()
+                .expect("Unable to list retry states")
+                .remove(0);
+            if retry_state.status() == &PeerRetryStatus::GaveUp {
+                break retry_state;
+            }
+            assert!(
+                start.elapsed() < Duration::from_secs(60),
+                "Peer was not given up on"
+            );
+            thread::sleep(Duration::from_millis(100));
+        };
+
+        assert_eq!(retry_state.attempts(), 2);
+        assert_eq!(retry_state.next_attempt(), None);
+
+        peer_manager.signal_shutdown();
+        cm.signal_shutdown();
+        peer_manager
+            .wait_for_shutdown()
+            .expect("Unable to shutdown peer manager");
+        cm.wait_for_shutdown()
+            .expect("Unable to shutdown connection manager");
+        mesh.signal_shutdown();
+        mesh.wait_for_shutdown().expect("Unable to shutdown mesh");
+    }
+
     #[derive(PartialEq)]
     enum TestEnum {
         Notification(PeerManagerNotification),
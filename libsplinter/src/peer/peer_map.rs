@@ -16,11 +16,15 @@
 
 use std::collections::hash_map::Entry::Occupied;
 use std::collections::{HashMap, HashSet};
-use std::time::Instant;
+#[cfg(feature = "peer-inspection")]
+use std::time::SystemTime;
+use std::time::{Duration, Instant};
 
 use crate::collections::BiHashMap;
 
 use super::error::PeerUpdateError;
+#[cfg(feature = "peer-retry-policy")]
+use super::retry::PeerRetry;
 use super::{PeerAuthorizationToken, PeerTokenPair};
 
 /// Enum for the current status of a peer
@@ -49,10 +53,21 @@ pub struct PeerMetadata {
     pub status: PeerStatus,
     /// The last time that a peer was attempted to be connected to
     pub last_connection_attempt: Instant,
+    /// How long the peer's current connection took to establish, if it was requested locally
+    pub connection_latency: Option<Duration>,
     /// How long to wait before trying to reconnect to a peer
     pub retry_frequency: u64,
     /// The required way the local node must be identified, this is required on retry
     pub required_local_auth: PeerAuthorizationToken,
+    /// The retry state of the peer, if it has a retry policy attached
+    #[cfg(feature = "peer-retry-policy")]
+    pub retry: Option<PeerRetry>,
+    /// When the peer's current connection was established, if it is connected
+    #[cfg(feature = "peer-inspection")]
+    pub connected_since: Option<SystemTime>,
+    /// The round trip time of the last heartbeat replied to on the peer's current connection
+    #[cfg(feature = "peer-inspection")]
+    pub heartbeat_latency: Option<Duration>,
 }
 
 /// A map of peer IDs to peer metadata, which also maintains a redirect table for updated peer IDs.
@@ -125,6 +140,12 @@ impl PeerMap {
         required_local_auth: PeerAuthorizationToken,
         removed_connection_ids: Vec<String>,
     ) {
+        #[cfg(feature = "peer-inspection")]
+        let connected_since = match status {
+            PeerStatus::Connected => Some(SystemTime::now()),
+            _ => None,
+        };
+
         let peer_metadata = PeerMetadata {
             id: peer_id.clone(),
             endpoints: endpoints.clone(),
@@ -132,8 +153,15 @@ impl PeerMap {
             status,
             connection_id,
             last_connection_attempt: Instant::now(),
+            connection_latency: None,
             retry_frequency: self.initial_retry_frequency,
             required_local_auth: required_local_auth.clone(),
+            #[cfg(feature = "peer-retry-policy")]
+            retry: None,
+            #[cfg(feature = "peer-inspection")]
+            connected_since,
+            #[cfg(feature = "peer-inspection")]
+            heartbeat_latency: None,
         };
 
         let peer_token_pair = PeerTokenPair::new(peer_id, required_local_auth);
@@ -188,6 +216,11 @@ impl PeerMap {
 
     /// Updates an existing peer. All fields can be updated except `peer_id`.
     ///
+    /// If the `peer-inspection` feature is enabled, `connected_since` is maintained by the
+    /// `PeerMap`: it is set when the peer becomes connected or its connection changes, and cleared
+    /// when the peer is no longer connected. `heartbeat_latency` is kept while the peer's
+    /// connection is unchanged, and cleared otherwise.
+    ///
     /// # Arguments
     ///
     /// * `peer_metadata` - The updated peer metadata for the peer
@@ -208,6 +241,26 @@ impl PeerMap {
                 }
             }
 
+            #[cfg(feature = "peer-inspection")]
+            let peer_metadata = {
+                let mut peer_metadata = peer_metadata;
+                let previous = peer_entry.get();
+                let same_connection = peer_metadata.status == PeerStatus::Connected
+                    && previous.status == PeerStatus::Connected
+                    && previous.connection_id == peer_metadata.connection_id;
+                if same_connection {
+                    peer_metadata.connected_since = previous.connected_since;
+                    peer_metadata.heartbeat_latency = previous.heartbeat_latency;
+                } else {
+                    peer_metadata.connected_since = match peer_metadata.status {
+                        PeerStatus::Connected => Some(SystemTime::now()),
+                        _ => None,
+                    };
+                    peer_metadata.heartbeat_latency = None;
+                }
+                peer_metadata
+            };
+
             if peer_metadata.connection_id != peer_entry.get().connection_id {
                 self.removed_connection_ids
                     .insert(peer_entry.get().connection_id.to_string(), peer_token_pair);
@@ -245,6 +298,43 @@ impl PeerMap {
         }
     }
 
+    /// Returns the metadata for all peers
+    #[cfg(any(feature = "peer-inspection", feature = "peer-retry-policy"))]
+    pub fn peers(&self) -> impl Iterator<Item = &PeerMetadata> {
+        self.peers.values()
+    }
+
+    /// Sets the retry state of an existing peer
+    #[cfg(feature = "peer-retry-policy")]
+    pub fn set_retry(&mut self, peer_id: &PeerTokenPair, retry: PeerRetry) {
+        if let Some(peer_metadata) = self.peers.get_mut(peer_id) {
+            peer_metadata.retry = Some(retry);
+        }
+    }
+
+    /// Sets when the connection of an existing, connected peer was established
+    ///
+    /// This is used when a connection that was established before the peer was referenced is
+    /// taken over by the peer.
+    #[cfg(feature = "peer-inspection")]
+    pub fn set_connected_since(&mut self, peer_id: &PeerTokenPair, connected_since: SystemTime) {
+        if let Some(peer_metadata) = self.peers.get_mut(peer_id) {
+            if peer_metadata.status == PeerStatus::Connected {
+                peer_metadata.connected_since = Some(connected_since);
+            }
+        }
+    }
+
+    /// Sets the round trip time of the last heartbeat replied to by an existing, connected peer
+    #[cfg(feature = "peer-inspection")]
+    pub fn set_heartbeat_latency(&mut self, peer_id: &PeerTokenPair, heartbeat_latency: Duration) {
+        if let Some(peer_metadata) = self.peers.get_mut(peer_id) {
+            if peer_metadata.status == PeerStatus::Connected {
+                peer_metadata.heartbeat_latency = Some(heartbeat_latency);
+            }
+        }
+    }
+
     /// Returns the metadata for a peer from the provided peer ID
     pub fn get_by_peer_id(&self, peer_id: &PeerTokenPair) -> Option<&PeerMetadata> {
         self.peers.get(peer_id)
@@ -581,8 +671,15 @@ pub mod tests {
             active_endpoint: "test_endpoint1".to_string(),
             status: PeerStatus::Connected,
             last_connection_attempt: Instant::now(),
+            connection_latency: None,
             retry_frequency: 10,
             required_local_auth: PeerAuthorizationToken::from_peer_id("my_id"),
+            #[cfg(feature = "peer-retry-policy")]
+            retry: None,
+            #[cfg(feature = "peer-inspection")]
+            connected_since: None,
+            #[cfg(feature = "peer-inspection")]
+            heartbeat_latency: None,
         };
 
         if let Ok(()) = peer_map.update_peer(no_peer_metadata) {
@@ -654,4 +751,82 @@ pub mod tests {
             PeerStatus::Disconnected { retry_attempts: 5 }
         );
     }
+
+    // Test that connected_since and heartbeat_latency are maintained as the peer's status and
+    // connection change
+    //  1. Insert a connected peer and check that connected_since is set, then set its heartbeat
+    //     latency
+    //  2. Update the peer without changing its connection and check connected_since and the
+    //     heartbeat latency are unchanged
+    //  3. Update the peer to disconnected and check that connected_since and the heartbeat latency
+    //     are cleared
+    //  4. Update the peer to connected on a new connection and check connected_since is set
+    #[cfg(feature = "peer-inspection")]
+    #[test]
+    fn test_connected_since() {
+        let mut peer_map = PeerMap::new(10);
+        let peer_token_pair = PeerTokenPair::new(
+            PeerAuthorizationToken::from_peer_id("test_peer"),
+            PeerAuthorizationToken::from_peer_id("my_id"),
+        );
+
+        peer_map.insert(
+            PeerAuthorizationToken::from_peer_id("test_peer"),
+            "connection_id".to_string(),
+            vec!["test_endpoint1".to_string()],
+            "test_endpoint1".to_string(),
+            PeerStatus::Connected,
+            PeerAuthorizationToken::from_peer_id("my_id"),
+            vec![],
+        );
+
+        peer_map.set_heartbeat_latency(&peer_token_pair, Duration::from_millis(20));
+
+        let mut peer_metadata = peer_map
+            .get_by_peer_id(&peer_token_pair)
+            .cloned()
+            .expect("Missing peer_metadata");
+        let connected_since = peer_metadata
+            .connected_since
+            .expect("Connected peer is missing connected_since");
+        assert_eq!(
+            peer_metadata.heartbeat_latency,
+            Some(Duration::from_millis(20))
+        );
+
+        peer_metadata.endpoints.push("test_endpoint2".to_string());
+        peer_map
+            .update_peer(peer_metadata)
+            .expect("Unable to update peer");
+        let mut peer_metadata = peer_map
+            .get_by_peer_id(&peer_token_pair)
+            .cloned()
+            .expect("Missing peer_metadata");
+        assert_eq!(peer_metadata.connected_since, Some(connected_since));
+        assert_eq!(
+            peer_metadata.heartbeat_latency,
+            Some(Duration::from_millis(20))
+        );
+
+        peer_metadata.status = PeerStatus::Disconnected { retry_attempts: 1 };
+        peer_map
+            .update_peer(peer_metadata)
+            .expect("Unable to update peer");
+        let mut peer_metadata = peer_map
+            .get_by_peer_id(&peer_token_pair)
+            .cloned()
+            .expect("Missing peer_metadata");
+        assert_eq!(peer_metadata.connected_since, None);
+        assert_eq!(peer_metadata.heartbeat_latency, None);
+
+        peer_metadata.status = PeerStatus::Connected;
+        peer_metadata.connection_id = "new_connection_id".to_string();
+        peer_map
+            .update_peer(peer_metadata)
+            .expect("Unable to update peer");
+        let peer_metadata = peer_map
+            .get_by_peer_id(&peer_token_pair)
+            .expect("Missing peer_metadata");
+        assert!(peer_metadata.connected_since.is_some());
+    }
 }
@@ -0,0 +1,490 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Per-peer retry policies for the `PeerManager`.
+//!
+//! By default, the `PeerManager` retries all peers using the values configured on the
+//! `PeerManagerBuilder`. A [`RetryPolicy`] may be attached to an individual peer, or to an
+//! endpoint requested without a peer ID, when it is requested through the
+//! `PeerManagerConnector`. The policy then controls how often connections to that peer are
+//! retried and whether the `PeerManager` eventually gives up on the peer.
+
+use std::time::{Duration, Instant, SystemTime};
+
+use rand::Rng;
+
+use crate::error::InvalidArgumentError;
+
+use super::PeerAuthorizationToken;
+
+// Default initial value for how long to wait before retrying a peer
+const DEFAULT_INITIAL_INTERVAL: Duration = Duration::from_secs(10);
+// Default value for maximum time between retries
+const DEFAULT_MAX_INTERVAL: Duration = Duration::from_secs(300);
+// Default factor by which the interval grows after each attempt
+const DEFAULT_MULTIPLIER: u32 = 2;
+// Default fraction of the interval that is randomly added or subtracted
+const DEFAULT_JITTER: f64 = 0.1;
+
+/// Describes how the `PeerManager` retries connecting to a peer.
+///
+/// The interval between attempts starts at `initial_interval` and is multiplied by `multiplier`
+/// after each attempt, up to `max_interval`. Each interval is randomly adjusted by up to `jitter`
+/// (a fraction of the interval) so that peers which were disconnected together do not all retry
+/// at the same time.
+///
+/// If `max_attempts` is set, the `PeerManager` gives up on the peer once that many attempts have
+/// failed; otherwise it keeps trying forever. A peer that has been given up on remains
+/// referenced and will still be connected if the remote node connects to the local node.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RetryPolicy {
+    initial_interval: Duration,
+    max_interval: Duration,
+    multiplier: u32,
+    jitter: f64,
+    max_attempts: Option<u64>,
+}
+
+impl RetryPolicy {
+    /// Returns a builder for a `RetryPolicy`.
+    pub fn builder() -> RetryPolicyBuilder {
+        RetryPolicyBuilder::new()
+    }
+
+    /// Returns the interval before the first retry.
+    pub fn initial_interval(&self) -> Duration {
+        self.initial_interval
+    }
+
+    /// Returns the maximum interval between retries.
+    pub fn max_interval(&self) -> Duration {
+        self.max_interval
+    }
+
+    /// Returns the factor by which the interval grows after each attempt.
+    pub fn multiplier(&self) -> u32 {
+        self.multiplier
+    }
+
+    /// Returns the fraction of each interval that is randomly added or subtracted.
+    pub fn jitter(&self) -> f64 {
+        self.jitter
+    }
+
+    /// Returns the number of attempts after which the peer is given up on, if any.
+    pub fn max_attempts(&self) -> Option<u64> {
+        self.max_attempts
+    }
+
+    /// Returns the interval to wait after the given number of attempts, without jitter.
+    pub fn interval(&self, attempts: u64) -> Duration {
+        let mut interval = self.initial_interval;
+        for _ in 1..attempts {
+            interval = interval.saturating_mul(self.multiplier);
+            if interval >= self.max_interval {
+                return self.max_interval;
+            }
+        }
+
+        interval.min(self.max_interval)
+    }
+
+    /// Returns the interval to wait after the given number of attempts, with jitter applied.
+    fn jittered_interval(&self, attempts: u64) -> Duration {
+        let interval = self.interval(attempts);
+        if self.jitter == 0.0 {
+            return interval;
+        }
+
+        let factor = rand::thread_rng().gen_range((1.0 - self.jitter)..=(1.0 + self.jitter));
+        interval.mul_f64(factor)
+    }
+}
+
+// The jitter is validated to be between 0 and 1, so it is never NaN
+impl Eq for RetryPolicy {}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            initial_interval: DEFAULT_INITIAL_INTERVAL,
+            max_interval: DEFAULT_MAX_INTERVAL,
+            multiplier: DEFAULT_MULTIPLIER,
+            jitter: DEFAULT_JITTER,
+            max_attempts: None,
+        }
+    }
+}
+
+/// Builds a [`RetryPolicy`].
+///
+/// Any values that are not set use the defaults of the `PeerManager`: an initial interval of 10
+/// seconds that doubles up to 5 minutes, 10% jitter, and no limit on the number of attempts.
+#[derive(Default)]
+pub struct RetryPolicyBuilder {
+    initial_interval: Option<Duration>,
+    max_interval: Option<Duration>,
+    multiplier: Option<u32>,
+    jitter: Option<f64>,
+    max_attempts: Option<u64>,
+}
+
+impl RetryPolicyBuilder {
+    /// Constructs a new builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the interval before the first retry.
+    pub fn with_initial_interval(mut self, initial_interval: Duration) -> Self {
+        self.initial_interval = Some(initial_interval);
+        self
+    }
+
+    /// Sets the maximum interval between retries.
+    pub fn with_max_interval(mut self, max_interval: Duration) -> Self {
+        self.max_interval = Some(max_interval);
+        self
+    }
+
+    /// Sets the factor by which the interval grows after each attempt. A multiplier of 1 retries
+    /// at a fixed interval.
+    pub fn with_multiplier(mut self, multiplier: u32) -> Self {
+        self.multiplier = Some(multiplier);
+        self
+    }
+
+    /// Sets the fraction (between 0 and 1) of each interval that is randomly added or
+    /// subtracted.
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = Some(jitter);
+        self
+    }
+
+    /// Sets the number of attempts after which the peer is given up on. If not set, the peer is
+    /// retried forever.
+    pub fn with_max_attempts(mut self, max_attempts: u64) -> Self {
+        self.max_attempts = Some(max_attempts);
+        self
+    }
+
+    /// Builds the `RetryPolicy`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidArgumentError` if any of the values are out of range.
+    pub fn build(self) -> Result<RetryPolicy, InvalidArgumentError> {
+        let initial_interval = self.initial_interval.unwrap_or(DEFAULT_INITIAL_INTERVAL);
+        let max_interval = self
+            .max_interval
+            .unwrap_or_else(|| DEFAULT_MAX_INTERVAL.max(initial_interval));
+
+        if initial_interval == Duration::from_secs(0) {
+            return Err(InvalidArgumentError::new(
+                "initial_interval",
+                "must be greater than zero",
+            ));
+        }
+
+        if max_interval < initial_interval {
+            return Err(InvalidArgumentError::new(
+                "max_interval",
+                "must not be less than the initial interval",
+            ));
+        }
+
+        let multiplier = self.multiplier.unwrap_or(DEFAULT_MULTIPLIER);
+        if multiplier == 0 {
+            return Err(InvalidArgumentError::new(
+                "multiplier",
+                "must be greater than zero",
+            ));
+        }
+
+        let jitter = self.jitter.unwrap_or(DEFAULT_JITTER);
+        if !(0.0..=1.0).contains(&jitter) {
+            return Err(InvalidArgumentError::new(
+                "jitter",
+                "must be between 0 and 1",
+            ));
+        }
+
+        if self.max_attempts == Some(0) {
+            return Err(InvalidArgumentError::new(
+                "max_attempts",
+                "must be greater than zero",
+            ));
+        }
+
+        Ok(RetryPolicy {
+            initial_interval,
+            max_interval,
+            multiplier,
+            jitter,
+            max_attempts: self.max_attempts,
+        })
+    }
+}
+
+/// The retry status of a peer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PeerRetryStatus {
+    /// The peer is connected and is not being retried
+    Connected,
+    /// The peer is not connected and connecting to it is being retried
+    Retrying,
+    /// The peer's retry policy has run out of attempts; it is no longer being retried
+    GaveUp,
+}
+
+/// A report of the retry state of a peer, or of an endpoint that was requested without a peer
+/// ID and has not yet connected.
+#[derive(Clone, Debug)]
+pub struct PeerRetryState {
+    peer_id: Option<PeerAuthorizationToken>,
+    endpoints: Vec<String>,
+    status: PeerRetryStatus,
+    policy: Option<RetryPolicy>,
+    attempts: u64,
+    next_attempt: Option<SystemTime>,
+}
+
+impl PeerRetryState {
+    pub(super) fn new(
+        peer_id: Option<PeerAuthorizationToken>,
+        endpoints: Vec<String>,
+        status: PeerRetryStatus,
+        policy: Option<RetryPolicy>,
+        attempts: u64,
+        next_attempt: Option<Instant>,
+    ) -> Self {
+        let now = Instant::now();
+        let next_attempt = next_attempt
+            .map(|next_attempt| SystemTime::now() + next_attempt.saturating_duration_since(now));
+
+        PeerRetryState {
+            peer_id,
+            endpoints,
+            status,
+            policy,
+            attempts,
+            next_attempt,
+        }
+    }
+
+    /// Returns the ID of the peer, or `None` if it was requested by endpoint and has not yet
+    /// connected.
+    pub fn peer_id(&self) -> Option<&PeerAuthorizationToken> {
+        self.peer_id.as_ref()
+    }
+
+    /// Returns the endpoints the peer is retried at.
+    pub fn endpoints(&self) -> &[String] {
+        &self.endpoints
+    }
+
+    /// Returns the retry status of the peer.
+    pub fn status(&self) -> &PeerRetryStatus {
+        &self.status
+    }
+
+    /// Returns the retry policy attached to the peer, or `None` if the peer uses the
+    /// `PeerManager` defaults.
+    pub fn policy(&self) -> Option<&RetryPolicy> {
+        self.policy.as_ref()
+    }
+
+    /// Returns the number of attempts made since the peer was last connected.
+    pub fn attempts(&self) -> u64 {
+        self.attempts
+    }
+
+    /// Returns when the peer will next be retried, if known.
+    ///
+    /// This is `None` if the peer is connected, has been given up on, or is being reconnected
+    /// by the connection manager.
+    pub fn next_attempt(&self) -> Option<SystemTime> {
+        self.next_attempt
+    }
+}
+
+/// Tracks the retries of a peer that has a retry policy attached.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct PeerRetry {
+    policy: RetryPolicy,
+    attempts: u64,
+    next_attempt: Option<Instant>,
+    gave_up: bool,
+}
+
+impl PeerRetry {
+    pub fn new(policy: RetryPolicy) -> Self {
+        PeerRetry {
+            policy,
+            attempts: 0,
+            next_attempt: None,
+            gave_up: false,
+        }
+    }
+
+    pub fn policy(&self) -> &RetryPolicy {
+        &self.policy
+    }
+
+    pub fn attempts(&self) -> u64 {
+        self.attempts
+    }
+
+    pub fn next_attempt(&self) -> Option<Instant> {
+        self.next_attempt
+    }
+
+    pub fn gave_up(&self) -> bool {
+        self.gave_up
+    }
+
+    /// Returns true if the next attempt is due.
+    pub fn is_due(&self) -> bool {
+        !self.gave_up
+            && self
+                .next_attempt
+                .map(|next_attempt| Instant::now() >= next_attempt)
+                .unwrap_or(false)
+    }
+
+    /// Returns true if the policy does not allow any more attempts.
+    pub fn is_exhausted(&self) -> bool {
+        self.policy
+            .max_attempts
+            .map(|max_attempts| self.attempts >= max_attempts)
+            .unwrap_or(false)
+    }
+
+    /// Records that an attempt was made and schedules the next one.
+    pub fn record_attempt(&mut self) {
+        self.attempts += 1;
+        self.next_attempt = Some(Instant::now() + self.policy.jittered_interval(self.attempts));
+    }
+
+    /// Stops retrying.
+    pub fn give_up(&mut self) {
+        self.gave_up = true;
+        self.next_attempt = None;
+    }
+
+    /// Schedules an immediate attempt, starting a new round of retries.
+    pub fn restart(&mut self) {
+        self.reset();
+        self.next_attempt = Some(Instant::now());
+    }
+
+    /// Clears the retry state, after the peer has connected.
+    pub fn reset(&mut self) {
+        self.attempts = 0;
+        self.next_attempt = None;
+        self.gave_up = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test that the interval grows by the multiplier after each attempt and is capped at the
+    // maximum interval.
+    #[test]
+    fn test_interval_backoff() {
+        let policy = RetryPolicy::builder()
+            .with_initial_interval(Duration::from_secs(1))
+            .with_max_interval(Duration::from_secs(10))
+            .with_multiplier(3)
+            .build()
+            .expect("Unable to build policy");
+
+        assert_eq!(policy.interval(1), Duration::from_secs(1));
+        assert_eq!(policy.interval(2), Duration::from_secs(3));
+        assert_eq!(policy.interval(3), Duration::from_secs(9));
+        assert_eq!(policy.interval(4), Duration::from_secs(10));
+        assert_eq!(policy.interval(u64::MAX), Duration::from_secs(10));
+    }
+
+    // Test that jitter keeps the interval within the configured fraction.
+    #[test]
+    fn test_jittered_interval() {
+        let policy = RetryPolicy::builder()
+            .with_initial_interval(Duration::from_secs(100))
+            .with_jitter(0.5)
+            .build()
+            .expect("Unable to build policy");
+
+        for _ in 0..100 {
+            let interval = policy.jittered_interval(1);
+            assert!(interval >= Duration::from_secs(50));
+            assert!(interval <= Duration::from_secs(150));
+        }
+    }
+
+    // Test that invalid values are rejected by the builder.
+    #[test]
+    fn test_builder_invalid() {
+        assert!(RetryPolicy::builder()
+            .with_initial_interval(Duration::from_secs(0))
+            .build()
+            .is_err());
+        assert!(RetryPolicy::builder()
+            .with_initial_interval(Duration::from_secs(10))
+            .with_max_interval(Duration::from_secs(5))
+            .build()
+            .is_err());
+        assert!(RetryPolicy::builder().with_multiplier(0).build().is_err());
+        assert!(RetryPolicy::builder().with_jitter(1.5).build().is_err());
+        assert!(RetryPolicy::builder().with_max_attempts(0).build().is_err());
+        assert_eq!(
+            RetryPolicy::builder()
+                .build()
+                .expect("Unable to build policy"),
+            RetryPolicy::default()
+        );
+    }
+
+    // Test that a peer retry gives up once its attempts are exhausted and starts over when
+    // restarted.
+    //  1. Record two attempts on a policy with a max of two attempts
+    //  2. Verify the retry is exhausted and that giving up stops scheduling attempts
+    //  3. Restart the retry and verify an attempt is due immediately
+    #[test]
+    fn test_peer_retry_give_up() {
+        let policy = RetryPolicy::builder()
+            .with_max_attempts(2)
+            .build()
+            .expect("Unable to build policy");
+        let mut retry = PeerRetry::new(policy);
+
+        retry.record_attempt();
+        assert!(!retry.is_exhausted());
+        assert!(!retry.is_due());
+        retry.record_attempt();
+        assert!(retry.is_exhausted());
+
+        retry.give_up();
+        assert!(retry.gave_up());
+        assert!(!retry.is_due());
+        assert_eq!(retry.next_attempt(), None);
+
+        retry.restart();
+        assert!(!retry.gave_up());
+        assert_eq!(retry.attempts(), 0);
+        assert!(retry.is_due());
+    }
+}
@@ -16,7 +16,11 @@
 
 use std::collections::HashMap;
 use std::time::Instant;
+#[cfg(feature = "peer-inspection")]
+use std::time::{Duration, SystemTime};
 
+#[cfg(feature = "peer-retry-policy")]
+use super::retry::PeerRetry;
 use super::PeerAuthorizationToken;
 use super::PeerTokenPair;
 
@@ -28,6 +32,12 @@ pub struct UnreferencedPeer {
     pub connection_id: String,
     pub local_authorization: PeerAuthorizationToken,
     pub old_connection_ids: Vec<String>,
+    // When the peer's current connection was established
+    #[cfg(feature = "peer-inspection")]
+    pub connected_since: SystemTime,
+    // The round trip time of the last heartbeat replied to on the peer's current connection
+    #[cfg(feature = "peer-inspection")]
+    pub heartbeat_latency: Option<Duration>,
 }
 
 /// An entry for a peer that was only requested by endpoint.
@@ -35,6 +45,9 @@ pub struct UnreferencedPeer {
 pub struct RequestedEndpoint {
     pub endpoint: String,
     pub local_authorization: PeerAuthorizationToken,
+    // The retry state of the endpoint, if it has a retry policy attached
+    #[cfg(feature = "peer-retry-policy")]
+    pub retry: Option<PeerRetry>,
 }
 
 pub struct UnreferencedPeerState {
@@ -14,6 +14,7 @@
 
 use crate::error::InvalidStateError;
 use crate::rbac::store::Assignment;
+use crate::rbac::store::AssignmentScope;
 use crate::rbac::store::Identity;
 
 // Constructs new Assignments.
@@ -21,6 +22,7 @@ use crate::rbac::store::Identity;
 pub struct AssignmentBuilder {
     identity: Option<Identity>,
     roles: Vec<String>,
+    scopes: Vec<AssignmentScope>,
 }
 
 impl AssignmentBuilder {
@@ -41,6 +43,13 @@ impl AssignmentBuilder {
         self
     }
 
+    /// Sets the scopes the assignment is restricted to. If none are provided, the assignment
+    /// applies node-wide.
+    pub fn with_scopes(mut self, scopes: Vec<AssignmentScope>) -> Self {
+        self.scopes = scopes;
+        self
+    }
+
     /// Builds a new assignment.
     ///
     /// # Errors
@@ -60,6 +69,7 @@ impl AssignmentBuilder {
                 InvalidStateError::with_message("An assignment requires an identity field".into())
             })?,
             roles: self.roles,
+            scopes: self.scopes,
         })
     }
 }
@@ -13,18 +13,24 @@
 // limitations under the License.
 
 mod builder;
+mod scope;
 mod update_builder;
 
 use crate::rbac::store::Identity;
 
 pub use builder::AssignmentBuilder;
+pub use scope::AssignmentScope;
 pub use update_builder::AssignmentUpdateBuilder;
 
 /// An assignment of roles to a particular identity.
+///
+/// An assignment with no scopes grants its roles node-wide. An assignment with scopes only grants
+/// its roles for requests concerning a circuit that matches at least one of the scopes.
 #[derive(Clone)]
 pub struct Assignment {
     identity: Identity,
     roles: Vec<String>,
+    scopes: Vec<AssignmentScope>,
 }
 
 impl Assignment {
@@ -38,10 +44,22 @@ impl Assignment {
         &self.roles
     }
 
+    /// Returns the scopes the assignment is restricted to. If empty, the assignment applies
+    /// node-wide.
+    pub fn scopes(&self) -> &[AssignmentScope] {
+        &self.scopes
+    }
+
     /// Convert this assignment back into a builder, in order to update its values.
     pub fn into_update_builder(self) -> AssignmentUpdateBuilder {
-        let Assignment { identity, roles } = self;
-        AssignmentUpdateBuilder::new(identity).with_roles(roles)
+        let Assignment {
+            identity,
+            roles,
+            scopes,
+        } = self;
+        AssignmentUpdateBuilder::new(identity)
+            .with_roles(roles)
+            .with_scopes(scopes)
     }
 
     /// Converts this assignment into it's constituent parts.  These parts are in the tuple:
@@ -50,7 +68,15 @@ impl Assignment {
         (self.identity, self.roles)
     }
 
-    pub(super) fn new_unchecked(identity: Identity, roles: Vec<String>) -> Self {
-        Self { identity, roles }
+    pub(super) fn new_unchecked(
+        identity: Identity,
+        roles: Vec<String>,
+        scopes: Vec<AssignmentScope>,
+    ) -> Self {
+        Self {
+            identity,
+            roles,
+            scopes,
+        }
     }
 }
@@ -0,0 +1,109 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::InvalidArgumentError;
+
+const CIRCUIT_SCOPE_PREFIX: &str = "circuit:";
+const CIRCUIT_MANAGEMENT_TYPE_SCOPE_PREFIX: &str = "management-type:";
+
+/// Restricts an assignment to requests that concern particular circuits.
+///
+/// The string form of a scope is `circuit:<circuit_id>` or `management-type:<type>`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum AssignmentScope {
+    /// The circuit with the given ID
+    Circuit(String),
+    /// Any circuit with the given circuit management type
+    CircuitManagementType(String),
+}
+
+impl AssignmentScope {
+    /// Returns the type of the scope, as used in its string form.
+    pub fn scope_type(&self) -> &str {
+        match self {
+            AssignmentScope::Circuit(_) => "circuit",
+            AssignmentScope::CircuitManagementType(_) => "management-type",
+        }
+    }
+
+    /// Returns the circuit ID or circuit management type the scope is restricted to.
+    pub fn value(&self) -> &str {
+        match self {
+            AssignmentScope::Circuit(value) => value,
+            AssignmentScope::CircuitManagementType(value) => value,
+        }
+    }
+}
+
+impl fmt::Display for AssignmentScope {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.scope_type(), self.value())
+    }
+}
+
+impl FromStr for AssignmentScope {
+    type Err = InvalidArgumentError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let scope = if let Some(circuit_id) = s.strip_prefix(CIRCUIT_SCOPE_PREFIX) {
+            AssignmentScope::Circuit(circuit_id.to_string())
+        } else if let Some(management_type) = s.strip_prefix(CIRCUIT_MANAGEMENT_TYPE_SCOPE_PREFIX) {
+            AssignmentScope::CircuitManagementType(management_type.to_string())
+        } else {
+            return Err(InvalidArgumentError::new(
+                "scope",
+                format!(
+                    "'{}' must be in the form 'circuit:<circuit_id>' or 'management-type:<type>'",
+                    s
+                ),
+            ));
+        };
+
+        if scope.value().is_empty() {
+            return Err(InvalidArgumentError::new(
+                "scope",
+                format!("'{}' does not specify a value", s),
+            ));
+        }
+
+        Ok(scope)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that scopes are parsed from, and formatted to, their string form.
+    #[test]
+    fn test_scope_string_form() {
+        let scope: AssignmentScope = "circuit:abcde-01234".parse().expect("Unable to parse");
+        assert_eq!(AssignmentScope::Circuit("abcde-01234".into()), scope);
+        assert_eq!("circuit:abcde-01234", &scope.to_string());
+
+        let scope: AssignmentScope = "management-type:gameroom".parse().expect("Unable to parse");
+        assert_eq!(
+            AssignmentScope::CircuitManagementType("gameroom".into()),
+            scope
+        );
+        assert_eq!("management-type:gameroom", &scope.to_string());
+
+        assert!("circuit:".parse::<AssignmentScope>().is_err());
+        assert!("node:abc".parse::<AssignmentScope>().is_err());
+        assert!("abcde-01234".parse::<AssignmentScope>().is_err());
+    }
+}
@@ -14,6 +14,7 @@
 
 use crate::error::InvalidStateError;
 use crate::rbac::store::Assignment;
+use crate::rbac::store::AssignmentScope;
 use crate::rbac::store::Identity;
 
 /// Updates an existing assignment.
@@ -22,6 +23,7 @@ use crate::rbac::store::Identity;
 pub struct AssignmentUpdateBuilder {
     identity: Identity,
     roles: Vec<String>,
+    scopes: Vec<AssignmentScope>,
 }
 
 impl AssignmentUpdateBuilder {
@@ -29,6 +31,7 @@ impl AssignmentUpdateBuilder {
         Self {
             identity,
             roles: Vec::new(),
+            scopes: Vec::new(),
         }
     }
     /// Updates the assigned roles.
@@ -37,6 +40,12 @@ impl AssignmentUpdateBuilder {
         self
     }
 
+    /// Updates the scopes the assignment is restricted to.
+    pub fn with_scopes(mut self, scopes: Vec<AssignmentScope>) -> Self {
+        self.scopes = scopes;
+        self
+    }
+
     /// Builds the updated assignment.
     ///
     /// # Errors
@@ -53,6 +62,7 @@ impl AssignmentUpdateBuilder {
         Ok(Assignment {
             identity: self.identity,
             roles: self.roles,
+            scopes: self.scopes,
         })
     }
 }
@@ -27,8 +27,8 @@ use crate::store::pool::ConnectionPool;
 use diesel::r2d2::{ConnectionManager, Pool};
 
 use super::{
-    Assignment, Identity, Role, RoleBasedAuthorizationStore, RoleBasedAuthorizationStoreError,
-    RoleBuilder, ADMIN_ROLE_ID,
+    Assignment, AssignmentScope, Identity, Role, RoleBasedAuthorizationStore,
+    RoleBasedAuthorizationStoreError, RoleBuilder, ADMIN_ROLE_ID,
 };
 
 use operations::add_assignment::RoleBasedAuthorizationStoreAddAssignment as _;
@@ -401,8 +401,15 @@ impl TryFrom<(models::RoleModel, Vec<models::RolePermissionModel>)> for Role {
     }
 }
 
-impl From<Assignment> for (models::IdentityModel, Vec<models::AssignmentModel>) {
+impl From<Assignment>
+    for (
+        models::IdentityModel,
+        Vec<models::AssignmentModel>,
+        Vec<models::AssignmentScopeModel>,
+    )
+{
     fn from(assignment: Assignment) -> Self {
+        let scopes = assignment.scopes().to_vec();
         let (identity, roles) = assignment.into_parts();
 
         let identity_model = match identity {
@@ -424,15 +431,34 @@ impl From<Assignment> for (models::IdentityModel, Vec<models::AssignmentModel>)
             })
             .collect::<Vec<_>>();
 
-        (identity_model, role_models)
+        let scope_models = scopes
+            .iter()
+            .map(|scope| models::AssignmentScopeModel {
+                identity: identity_model.identity.clone(),
+                scope_type: scope.scope_type().to_string(),
+                scope_value: scope.value().to_string(),
+            })
+            .collect::<Vec<_>>();
+
+        (identity_model, role_models, scope_models)
     }
 }
 
-impl TryFrom<(models::IdentityModel, Vec<models::AssignmentModel>)> for Assignment {
+impl
+    TryFrom<(
+        models::IdentityModel,
+        Vec<models::AssignmentModel>,
+        Vec<models::AssignmentScopeModel>,
+    )> for Assignment
+{
     type Error = InvalidStateError;
 
     fn try_from(
-        (identity_model, assignments): (models::IdentityModel, Vec<models::AssignmentModel>),
+        (identity_model, assignments, scopes): (
+            models::IdentityModel,
+            Vec<models::AssignmentModel>,
+            Vec<models::AssignmentScopeModel>,
+        ),
     ) -> Result<Self, Self::Error> {
         let models::IdentityModel {
             identity,
@@ -442,6 +468,20 @@ impl TryFrom<(models::IdentityModel, Vec<models::AssignmentModel>)> for Assignme
             models::IdentityModelType::Key => Identity::Key(identity),
             models::IdentityModelType::User => Identity::User(identity),
         };
+        let scopes = scopes
+            .into_iter()
+            .map(
+                |models::AssignmentScopeModel {
+                     scope_type,
+                     scope_value,
+                     ..
+                 }| {
+                    format!("{}:{}", scope_type, scope_value)
+                        .parse::<AssignmentScope>()
+                        .map_err(|err| InvalidStateError::with_message(err.to_string()))
+                },
+            )
+            .collect::<Result<Vec<_>, _>>()?;
         // We create the with the unchecked constructor, vs using the builder,
         // as a deleted role may result in an empty assignment.  The builder
         // prevents the library user from constructing an assignment with no
@@ -453,6 +493,7 @@ impl TryFrom<(models::IdentityModel, Vec<models::AssignmentModel>)> for Assignme
                 .into_iter()
                 .map(|models::AssignmentModel { role_id, .. }| role_id)
                 .collect(),
+            scopes,
         ))
     }
 }
@@ -1028,6 +1069,88 @@ mod tests {
         assert_eq!(&vec!["test-role-2".to_string()], stored_assignment.roles());
     }
 
+    /// This tests verifies the following:
+    /// 1. Adds an assignment with scopes and verifies that the scopes are returned with it
+    /// 2. Update the assignment's scopes and verifies that they have been replaced
+    /// 3. Remove the assignment and verifies that its scope records have been removed
+    #[test]
+    fn sqlite_assignment_scopes() {
+        let pool = create_connection_pool_and_migrate();
+
+        let role_based_auth_store = DieselRoleBasedAuthorizationStore::new(pool.clone());
+
+        let role = RoleBuilder::new()
+            .with_id("test-role".into())
+            .with_display_name("Test Role".into())
+            .with_permissions(vec!["a".to_string()])
+            .build()
+            .expect("Unable to build role");
+
+        role_based_auth_store
+            .add_role(role)
+            .expect("Unable to add role");
+
+        let assignment = AssignmentBuilder::new()
+            .with_identity(Identity::User("some-user-id".into()))
+            .with_roles(vec!["test-role".to_string()])
+            .with_scopes(vec![
+                AssignmentScope::Circuit("abcde-01234".into()),
+                AssignmentScope::CircuitManagementType("gameroom".into()),
+            ])
+            .build()
+            .expect("Unable to build assignment");
+
+        role_based_auth_store
+            .add_assignment(assignment)
+            .expect("Unable to add assignment");
+
+        let stored_assignment = role_based_auth_store
+            .get_assignment(&Identity::User("some-user-id".into()))
+            .expect("Unable to get assignment")
+            .expect("Assignment was not found");
+
+        assert_eq!(
+            &[
+                AssignmentScope::Circuit("abcde-01234".into()),
+                AssignmentScope::CircuitManagementType("gameroom".into()),
+            ],
+            stored_assignment.scopes()
+        );
+
+        let updated_assignment = stored_assignment
+            .into_update_builder()
+            .with_scopes(vec![AssignmentScope::Circuit("fghij-56789".into())])
+            .build()
+            .expect("Unable to build updated assignment");
+
+        role_based_auth_store
+            .update_assignment(updated_assignment)
+            .expect("Unable to update assignment");
+
+        let stored_assignment = role_based_auth_store
+            .list_assignments()
+            .expect("Unable to list assignments")
+            .next()
+            .expect("Assignment was not found");
+
+        assert_eq!(&vec!["test-role".to_string()], stored_assignment.roles());
+        assert_eq!(
+            &[AssignmentScope::Circuit("fghij-56789".into())],
+            stored_assignment.scopes()
+        );
+
+        role_based_auth_store
+            .remove_assignment(&Identity::User("some-user-id".into()))
+            .expect("Unable to remove assignment");
+
+        let conn = pool.get().expect("Unable to get connection");
+        let count = schema::rbac_assignment_scopes::table
+            .count()
+            .get_result::<i64>(&*conn)
+            .expect("Unable to count scopes");
+        assert_eq!(0, count);
+    }
+
     #[test]
     fn sqlite_test_update_nonexistent_assignment() {
         let pool = create_connection_pool_and_migrate();
@@ -35,7 +35,9 @@ use diesel::pg::Pg;
 #[cfg(feature = "sqlite")]
 use diesel::sqlite::Sqlite;
 
-use super::schema::{rbac_assignments, rbac_identities, rbac_role_permissions, rbac_roles};
+use super::schema::{
+    rbac_assignment_scopes, rbac_assignments, rbac_identities, rbac_role_permissions, rbac_roles,
+};
 
 #[derive(Debug, PartialEq, Associations, Identifiable, Insertable, Queryable)]
 #[table_name = "rbac_roles"]
@@ -222,3 +224,13 @@ pub(super) struct AssignmentModel {
     pub identity: String,
     pub role_id: String,
 }
+
+#[derive(Debug, PartialEq, Associations, Identifiable, Insertable, Queryable)]
+#[table_name = "rbac_assignment_scopes"]
+#[belongs_to(IdentityModel, foreign_key = "identity")]
+#[primary_key(identity, scope_type, scope_value)]
+pub(super) struct AssignmentScopeModel {
+    pub identity: String,
+    pub scope_type: String,
+    pub scope_value: String,
+}
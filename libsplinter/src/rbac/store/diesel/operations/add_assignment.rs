@@ -16,8 +16,8 @@ use diesel::{dsl::insert_into, prelude::*};
 
 use crate::rbac::store::{
     diesel::{
-        models::{AssignmentModel, IdentityModel},
-        schema::{rbac_assignments, rbac_identities},
+        models::{AssignmentModel, AssignmentScopeModel, IdentityModel},
+        schema::{rbac_assignment_scopes, rbac_assignments, rbac_identities},
     },
     Assignment, RoleBasedAuthorizationStoreError,
 };
@@ -39,7 +39,11 @@ impl<'a> RoleBasedAuthorizationStoreAddAssignment
         &self,
         assignment: Assignment,
     ) -> Result<(), RoleBasedAuthorizationStoreError> {
-        let (identity, assignments): (IdentityModel, Vec<AssignmentModel>) = assignment.into();
+        let (identity, assignments, scopes): (
+            IdentityModel,
+            Vec<AssignmentModel>,
+            Vec<AssignmentScopeModel>,
+        ) = assignment.into();
         self.conn.transaction::<_, _, _>(|| {
             insert_into(rbac_identities::table)
                 .values(identity)
@@ -49,6 +53,10 @@ impl<'a> RoleBasedAuthorizationStoreAddAssignment
                 .values(assignments)
                 .execute(self.conn)?;
 
+            insert_into(rbac_assignment_scopes::table)
+                .values(scopes)
+                .execute(self.conn)?;
+
             Ok(())
         })
     }
@@ -62,7 +70,11 @@ impl<'a> RoleBasedAuthorizationStoreAddAssignment
         &self,
         assignment: Assignment,
     ) -> Result<(), RoleBasedAuthorizationStoreError> {
-        let (identity, assignments): (IdentityModel, Vec<AssignmentModel>) = assignment.into();
+        let (identity, assignments, scopes): (
+            IdentityModel,
+            Vec<AssignmentModel>,
+            Vec<AssignmentScopeModel>,
+        ) = assignment.into();
         self.conn.transaction::<_, _, _>(|| {
             insert_into(rbac_identities::table)
                 .values(identity)
@@ -72,6 +84,10 @@ impl<'a> RoleBasedAuthorizationStoreAddAssignment
                 .values(assignments)
                 .execute(self.conn)?;
 
+            insert_into(rbac_assignment_scopes::table)
+                .values(scopes)
+                .execute(self.conn)?;
+
             Ok(())
         })
     }
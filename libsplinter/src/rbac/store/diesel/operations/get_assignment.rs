@@ -18,7 +18,10 @@ use diesel::prelude::*;
 
 use crate::rbac::store::{
     diesel::{
-        models::{AssignmentModel, IdentityModel, IdentityModelType, IdentityModelTypeMapping},
+        models::{
+            AssignmentModel, AssignmentScopeModel, IdentityModel, IdentityModelType,
+            IdentityModelTypeMapping,
+        },
         schema::rbac_identities,
     },
     Assignment, Identity, RoleBasedAuthorizationStoreError,
@@ -59,11 +62,16 @@ where
                 .load::<AssignmentModel>(self.conn)?
                 .grouped_by(&identities);
 
+            let scopes = AssignmentScopeModel::belonging_to(&identities)
+                .load::<AssignmentScopeModel>(self.conn)?
+                .grouped_by(&identities);
+
             identities
                 .into_iter()
                 .zip(assignments)
+                .zip(scopes)
                 .next()
-                .map(|model| model.try_into())
+                .map(|((identity, assignments), scopes)| (identity, assignments, scopes).try_into())
                 .transpose()
                 .map_err(RoleBasedAuthorizationStoreError::from)
         })
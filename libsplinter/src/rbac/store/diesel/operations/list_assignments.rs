@@ -18,7 +18,10 @@ use diesel::prelude::*;
 
 use crate::rbac::store::{
     diesel::{
-        models::{AssignmentModel, IdentityModel, IdentityModelType, IdentityModelTypeMapping},
+        models::{
+            AssignmentModel, AssignmentScopeModel, IdentityModel, IdentityModelType,
+            IdentityModelTypeMapping,
+        },
         schema::rbac_identities,
     },
     Assignment, RoleBasedAuthorizationStoreError,
@@ -53,11 +56,18 @@ where
                     .load::<AssignmentModel>(self.conn)?
                     .grouped_by(&identities);
 
+                let scopes = AssignmentScopeModel::belonging_to(&identities)
+                    .load::<AssignmentScopeModel>(self.conn)?
+                    .grouped_by(&identities);
+
                 Ok(Box::new(
                     identities
                         .into_iter()
                         .zip(assignments)
-                        .map(|models| models.try_into())
+                        .zip(scopes)
+                        .map(|((identity, assignments), scopes)| {
+                            (identity, assignments, scopes).try_into()
+                        })
                         .collect::<Result<Vec<_>, _>>()
                         .map_err(RoleBasedAuthorizationStoreError::from)?
                         .into_iter(),
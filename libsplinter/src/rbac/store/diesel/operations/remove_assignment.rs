@@ -17,7 +17,7 @@ use diesel::{dsl::delete, prelude::*};
 use crate::rbac::store::{
     diesel::{
         models::IdentityModelTypeMapping,
-        schema::{rbac_assignments, rbac_identities},
+        schema::{rbac_assignment_scopes, rbac_assignments, rbac_identities},
     },
     Identity, RoleBasedAuthorizationStoreError,
 };
@@ -49,6 +49,11 @@ where
         self.conn.transaction::<_, _, _>(|| {
             delete(rbac_assignments::table.filter(rbac_assignments::identity.eq(search_identity)))
                 .execute(self.conn)?;
+            delete(
+                rbac_assignment_scopes::table
+                    .filter(rbac_assignment_scopes::identity.eq(search_identity)),
+            )
+            .execute(self.conn)?;
             delete(rbac_identities::table.filter(rbac_identities::identity.eq(search_identity)))
                 .execute(self.conn)?;
 
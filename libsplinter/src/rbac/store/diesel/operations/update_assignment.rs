@@ -20,8 +20,8 @@ use diesel::{
 use crate::error::{ConstraintViolationError, ConstraintViolationType};
 use crate::rbac::store::{
     diesel::{
-        models::{AssignmentModel, IdentityModel},
-        schema::{rbac_assignments, rbac_identities},
+        models::{AssignmentModel, AssignmentScopeModel, IdentityModel},
+        schema::{rbac_assignment_scopes, rbac_assignments, rbac_identities},
     },
     Assignment, RoleBasedAuthorizationStoreError,
 };
@@ -43,7 +43,11 @@ impl<'a> RoleBasedAuthorizationStoreUpdateAssignment
         &self,
         assignment: Assignment,
     ) -> Result<(), RoleBasedAuthorizationStoreError> {
-        let (identity, roles): (IdentityModel, Vec<AssignmentModel>) = assignment.into();
+        let (identity, roles, scopes): (
+            IdentityModel,
+            Vec<AssignmentModel>,
+            Vec<AssignmentScopeModel>,
+        ) = assignment.into();
         self.conn.transaction::<_, _, _>(|| {
             let count = rbac_identities::table
                 .filter(
@@ -71,6 +75,16 @@ impl<'a> RoleBasedAuthorizationStoreUpdateAssignment
                 .values(roles)
                 .execute(self.conn)?;
 
+            delete(
+                rbac_assignment_scopes::table
+                    .filter(rbac_assignment_scopes::identity.eq(&identity.identity)),
+            )
+            .execute(self.conn)?;
+
+            insert_into(rbac_assignment_scopes::table)
+                .values(scopes)
+                .execute(self.conn)?;
+
             Ok(())
         })
     }
@@ -84,7 +98,11 @@ impl<'a> RoleBasedAuthorizationStoreUpdateAssignment
         &self,
         assignment: Assignment,
     ) -> Result<(), RoleBasedAuthorizationStoreError> {
-        let (identity, roles): (IdentityModel, Vec<AssignmentModel>) = assignment.into();
+        let (identity, roles, scopes): (
+            IdentityModel,
+            Vec<AssignmentModel>,
+            Vec<AssignmentScopeModel>,
+        ) = assignment.into();
         self.conn.transaction::<_, _, _>(|| {
             let count = rbac_identities::table
                 .filter(
@@ -112,6 +130,16 @@ impl<'a> RoleBasedAuthorizationStoreUpdateAssignment
                 .values(roles)
                 .execute(self.conn)?;
 
+            delete(
+                rbac_assignment_scopes::table
+                    .filter(rbac_assignment_scopes::identity.eq(&identity.identity)),
+            )
+            .execute(self.conn)?;
+
+            insert_into(rbac_assignment_scopes::table)
+                .values(scopes)
+                .execute(self.conn)?;
+
             Ok(())
         })
     }
@@ -44,3 +44,11 @@ table! {
         role_id -> Text,
     }
 }
+
+table! {
+    rbac_assignment_scopes (identity, scope_type, scope_value) {
+        identity -> Text,
+        scope_type -> Text,
+        scope_value -> Text,
+    }
+}
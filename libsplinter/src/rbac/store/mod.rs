@@ -21,7 +21,7 @@ mod error;
 mod identity;
 mod role;
 
-pub use assignment::{Assignment, AssignmentBuilder, AssignmentUpdateBuilder};
+pub use assignment::{Assignment, AssignmentBuilder, AssignmentScope, AssignmentUpdateBuilder};
 pub use identity::Identity;
 pub use role::{Role, RoleBuilder, RoleUpdateBuilder};
 
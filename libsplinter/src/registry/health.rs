@@ -0,0 +1,362 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tracking of the reachability of registry nodes.
+//!
+//! The [`NodeHealthTracker`] stores the connection history of nodes, keyed by node identity. It is
+//! kept up to date by the [`NodeHealthMonitor`], which subscribes to the notifications of a
+//! peer manager.
+//!
+//! [`NodeHealthTracker`]: struct.NodeHealthTracker.html
+//! [`NodeHealthMonitor`]: struct.NodeHealthMonitor.html
+
+use std::collections::HashMap;
+use std::sync::mpsc::{channel, Receiver};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::error::InternalError;
+use crate::hex::to_hex;
+use crate::peer::{
+    PeerAuthorizationToken, PeerManagerConnector, PeerManagerNotification, PeerTokenPair,
+    SubscriberId,
+};
+use crate::threading::lifecycle::ShutdownHandle;
+
+use super::RegistryReader;
+
+/// The reachability of a node, as observed by the local node.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NodeHealth {
+    reachable: bool,
+    last_connected: Option<SystemTime>,
+    last_failure: Option<SystemTime>,
+    latency: Option<Duration>,
+}
+
+impl NodeHealth {
+    /// Returns `true` if the local node currently has a connection to the node.
+    pub fn is_reachable(&self) -> bool {
+        self.reachable
+    }
+
+    /// Returns the last time a connection to the node was established.
+    pub fn last_connected(&self) -> Option<SystemTime> {
+        self.last_connected
+    }
+
+    /// Returns the last time the connection to the node was lost.
+    pub fn last_failure(&self) -> Option<SystemTime> {
+        self.last_failure
+    }
+
+    /// Returns how long the most recent connection to the node took to establish, if it was
+    /// requested by the local node.
+    pub fn latency(&self) -> Option<Duration> {
+        self.latency
+    }
+}
+
+/// A thread-safe store of the [`NodeHealth`] of nodes, keyed by node identity.
+///
+/// Clones of a `NodeHealthTracker` share the same underlying store.
+///
+/// [`NodeHealth`]: struct.NodeHealth.html
+#[derive(Clone, Default)]
+pub struct NodeHealthTracker {
+    health: Arc<Mutex<HashMap<String, NodeHealth>>>,
+}
+
+impl NodeHealthTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that a connection to the node with the given identity was established.
+    ///
+    /// # Arguments
+    ///
+    /// * `identity` - The identity of the node.
+    /// * `latency` - How long the connection took to establish, if known.
+    pub fn record_connected(
+        &self,
+        identity: &str,
+        latency: Option<Duration>,
+    ) -> Result<(), InternalError> {
+        let mut health = self.lock()?;
+        let entry = health.entry(identity.to_string()).or_default();
+        entry.reachable = true;
+        entry.last_connected = Some(SystemTime::now());
+        if latency.is_some() {
+            entry.latency = latency;
+        }
+        Ok(())
+    }
+
+    /// Records that the connection to the node with the given identity was lost.
+    ///
+    /// # Arguments
+    ///
+    /// * `identity` - The identity of the node.
+    pub fn record_failure(&self, identity: &str) -> Result<(), InternalError> {
+        let mut health = self.lock()?;
+        let entry = health.entry(identity.to_string()).or_default();
+        entry.reachable = false;
+        entry.last_failure = Some(SystemTime::now());
+        Ok(())
+    }
+
+    /// Returns the health of the node with the given identity, if any connection events have
+    /// been recorded for it.
+    ///
+    /// # Arguments
+    ///
+    /// * `identity` - The identity of the node.
+    pub fn get_health(&self, identity: &str) -> Result<Option<NodeHealth>, InternalError> {
+        Ok(self.lock()?.get(identity).cloned())
+    }
+
+    fn lock(&self) -> Result<MutexGuard<HashMap<String, NodeHealth>>, InternalError> {
+        self.health
+            .lock()
+            .map_err(|_| InternalError::with_message("Node health lock poisoned".into()))
+    }
+}
+
+/// Updates a [`NodeHealthTracker`] from peer manager notifications.
+///
+/// Peers that are identified by a public key are matched to the registry node that has that key.
+/// Notifications for peers that cannot be matched to a node are ignored.
+///
+/// [`NodeHealthTracker`]: struct.NodeHealthTracker.html
+pub struct NodeHealthMonitor {
+    peer_connector: PeerManagerConnector,
+    subscriber_id: Option<SubscriberId>,
+    join_handle: thread::JoinHandle<()>,
+}
+
+impl NodeHealthMonitor {
+    /// Starts a background thread that records connection events for the peers of the given peer
+    /// manager in the tracker.
+    ///
+    /// # Arguments
+    ///
+    /// * `tracker` - The tracker to update.
+    /// * `peer_connector` - The connector of the peer manager to subscribe to.
+    /// * `registry` - The registry used to resolve the node identity of a peer's public key.
+    pub fn start(
+        tracker: NodeHealthTracker,
+        peer_connector: PeerManagerConnector,
+        registry: Box<dyn RegistryReader>,
+    ) -> Result<Self, InternalError> {
+        let (sender, receiver) = channel();
+        let subscriber_id = peer_connector
+            .subscribe_sender(sender)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        let thread_connector = peer_connector.clone();
+        let join_handle = thread::Builder::new()
+            .name("Registry Node Health Monitor".into())
+            .spawn(move || monitor_loop(receiver, tracker, thread_connector, registry))
+            .map_err(|err| {
+                InternalError::from_source_with_message(
+                    Box::new(err),
+                    "Failed to spawn registry node health monitor thread".into(),
+                )
+            })?;
+
+        Ok(Self {
+            peer_connector,
+            subscriber_id: Some(subscriber_id),
+            join_handle,
+        })
+    }
+}
+
+impl ShutdownHandle for NodeHealthMonitor {
+    fn signal_shutdown(&mut self) {
+        // Unsubscribing drops the notification sender, which ends the monitor loop
+        if let Some(subscriber_id) = self.subscriber_id.take() {
+            if let Err(err) = self.peer_connector.unsubscribe(subscriber_id) {
+                debug!(
+                    "Unable to unsubscribe registry node health monitor: {}",
+                    err
+                );
+            }
+        }
+    }
+
+    fn wait_for_shutdown(self) -> Result<(), InternalError> {
+        self.join_handle.join().map_err(|_| {
+            InternalError::with_message(
+                "Registry node health monitor thread did not shutdown correctly".into(),
+            )
+        })
+    }
+}
+
+fn monitor_loop(
+    receiver: Receiver<PeerManagerNotification>,
+    tracker: NodeHealthTracker,
+    peer_connector: PeerManagerConnector,
+    registry: Box<dyn RegistryReader>,
+) {
+    while let Ok(notification) = receiver.recv() {
+        let result = match notification {
+            PeerManagerNotification::Connected { peer } => {
+                let latency = peer_connector
+                    .connection_latency(&peer)
+                    .unwrap_or_else(|err| {
+                        debug!("Unable to get connection latency for {}: {}", peer, err);
+                        None
+                    });
+                resolve_identity(&peer, &*registry)
+                    .map(|identity| tracker.record_connected(&identity, latency))
+            }
+            PeerManagerNotification::Disconnected { peer } => resolve_identity(&peer, &*registry)
+                .map(|identity| tracker.record_failure(&identity)),
+        };
+
+        if let Some(Err(err)) = result {
+            error!("Unable to record node health: {}", err);
+        }
+    }
+
+    debug!("Exiting registry node health monitor");
+}
+
+/// Returns the node identity of the given peer, if it can be determined.
+fn resolve_identity(peer: &PeerTokenPair, registry: &dyn RegistryReader) -> Option<String> {
+    match peer.peer_id() {
+        PeerAuthorizationToken::Trust { peer_id } => Some(peer_id.clone()),
+        PeerAuthorizationToken::Challenge { public_key } => {
+            let key = to_hex(public_key.as_slice());
+            registry
+                .list_nodes(&[])
+                .map_err(|err| debug!("Unable to list registry nodes: {}", err))
+                .ok()?
+                .find(|node| node.keys().contains(&key))
+                .map(|node| node.identity().to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::public_key::PublicKey;
+    use crate::registry::{MetadataPredicate, Node, NodeIter, RegistryError};
+
+    /// Verifies that the tracker records connections and failures for a node, keeping the last
+    /// known latency when a connection's latency is unknown.
+    #[test]
+    fn test_tracker_records_events() {
+        let tracker = NodeHealthTracker::new();
+        assert_eq!(tracker.get_health("node-1").expect("failed to get"), None);
+
+        tracker
+            .record_connected("node-1", Some(Duration::from_millis(20)))
+            .expect("failed to record");
+        let health = tracker
+            .get_health("node-1")
+            .expect("failed to get")
+            .expect("health not found");
+        assert!(health.is_reachable());
+        assert!(health.last_connected().is_some());
+        assert_eq!(health.last_failure(), None);
+        assert_eq!(health.latency(), Some(Duration::from_millis(20)));
+
+        tracker.record_failure("node-1").expect("failed to record");
+        let health = tracker
+            .get_health("node-1")
+            .expect("failed to get")
+            .expect("health not found");
+        assert!(!health.is_reachable());
+        assert!(health.last_failure().is_some());
+
+        tracker
+            .record_connected("node-1", None)
+            .expect("failed to record");
+        let health = tracker
+            .get_health("node-1")
+            .expect("failed to get")
+            .expect("health not found");
+        assert!(health.is_reachable());
+        assert_eq!(health.latency(), Some(Duration::from_millis(20)));
+
+        // Clones share the same store
+        assert_eq!(
+            tracker.clone().get_health("node-1").expect("failed to get"),
+            Some(health)
+        );
+    }
+
+    /// Verifies that trust peers resolve to their peer ID and challenge peers resolve to the
+    /// registry node with their public key.
+    #[test]
+    fn test_resolve_identity() {
+        let registry = SingleNodeRegistry(
+            Node::builder("node-1")
+                .with_endpoint("tcp://localhost:8080")
+                .with_key("0102")
+                .build()
+                .expect("failed to build node"),
+        );
+        let local = PeerAuthorizationToken::from_peer_id("local");
+
+        let trust = PeerTokenPair::new(
+            PeerAuthorizationToken::from_peer_id("node-2"),
+            local.clone(),
+        );
+        assert_eq!(
+            resolve_identity(&trust, &registry),
+            Some("node-2".to_string())
+        );
+
+        let challenge = PeerTokenPair::new(
+            PeerAuthorizationToken::Challenge {
+                public_key: PublicKey::from_bytes(vec![1, 2]),
+            },
+            local.clone(),
+        );
+        assert_eq!(
+            resolve_identity(&challenge, &registry),
+            Some("node-1".to_string())
+        );
+
+        let unknown = PeerTokenPair::new(PeerAuthorizationToken::from_public_key(&[3, 4]), local);
+        assert_eq!(resolve_identity(&unknown, &registry), None);
+    }
+
+    struct SingleNodeRegistry(Node);
+
+    impl RegistryReader for SingleNodeRegistry {
+        fn list_nodes<'a, 'b: 'a>(
+            &'b self,
+            _predicates: &'a [MetadataPredicate],
+        ) -> Result<NodeIter<'a>, RegistryError> {
+            Ok(Box::new(vec![self.0.clone()].into_iter()))
+        }
+
+        fn count_nodes(&self, _predicates: &[MetadataPredicate]) -> Result<u32, RegistryError> {
+            Ok(1)
+        }
+
+        fn get_node(&self, identity: &str) -> Result<Option<Node>, RegistryError> {
+            Ok(Some(self.0.clone()).filter(|node| node.identity() == identity))
+        }
+    }
+}
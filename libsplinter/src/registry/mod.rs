@@ -29,6 +29,8 @@ pub mod client;
 #[cfg(feature = "diesel")]
 mod diesel;
 mod error;
+#[cfg(feature = "registry-node-health")]
+mod health;
 mod unified;
 mod yaml;
 
@@ -38,6 +40,8 @@ use std::iter::ExactSizeIterator;
 #[cfg(feature = "diesel")]
 pub use self::diesel::DieselRegistry;
 pub use error::{InvalidNodeError, RegistryError};
+#[cfg(feature = "registry-node-health")]
+pub use health::{NodeHealth, NodeHealthMonitor, NodeHealthTracker};
 pub use unified::UnifiedRegistry;
 pub use yaml::{LocalYamlRegistry, YamlNode};
 #[cfg(feature = "registry-remote")]
@@ -277,6 +281,18 @@ pub trait RegistryReader: Send + Sync {
     fn has_node(&self, identity: &str) -> Result<bool, RegistryError> {
         self.get_node(identity).map(|opt| opt.is_some())
     }
+
+    /// Returns the health of the node with the given identity, if the registry tracks it.
+    ///
+    /// By default, registries do not track node health and `None` is returned.
+    ///
+    /// # Arguments
+    ///
+    ///  * `identity` - The identity of the node.
+    #[cfg(feature = "registry-node-health")]
+    fn get_node_health(&self, _identity: &str) -> Result<Option<NodeHealth>, RegistryError> {
+        Ok(None)
+    }
 }
 
 /// Defines registry write capabilities.
@@ -360,6 +376,11 @@ where
     fn has_node(&self, identity: &str) -> Result<bool, RegistryError> {
         (**self).has_node(identity)
     }
+
+    #[cfg(feature = "registry-node-health")]
+    fn get_node_health(&self, identity: &str) -> Result<Option<NodeHealth>, RegistryError> {
+        (**self).get_node_health(identity)
+    }
 }
 
 impl<NW> RegistryWriter for Box<NW>
@@ -26,6 +26,8 @@ use std::sync::Arc;
 use super::{
     MetadataPredicate, Node, NodeIter, RegistryError, RegistryReader, RegistryWriter, RwRegistry,
 };
+#[cfg(feature = "registry-node-health")]
+use super::{NodeHealth, NodeHealthTracker};
 
 /// A registry with multiple sources.
 ///
@@ -61,6 +63,11 @@ use super::{
 /// If the same metadata key is set for the node in different registires, the value for that key
 /// from the highest-precedence registry will be used.
 ///
+/// # Node Health
+///
+/// If a [`NodeHealthTracker`] is provided with [`with_health_tracker`], the health recorded for a
+/// node is returned by [`get_node_health`].
+///
 /// [`RegistryReader`]: ../trait.RegistryReader.html
 /// [`RegistryWriter`]: ../trait.RegistryWriter.html
 /// [`RwRegistry`]: ../trait.RwRegistry.html
@@ -68,10 +75,15 @@ use super::{
 /// [`identity`]: ../struct.Node.html#structfield.identity
 /// [`metadata`]: ../struct.Node.html#structfield.metadata
 /// [`Metadata Merging`]: #metadata-merging
+/// [`NodeHealthTracker`]: ../struct.NodeHealthTracker.html
+/// [`with_health_tracker`]: #method.with_health_tracker
+/// [`get_node_health`]: ../trait.RegistryReader.html#method.get_node_health
 #[derive(Clone)]
 pub struct UnifiedRegistry {
     internal_source: Arc<dyn RwRegistry>,
     external_sources: Vec<Arc<dyn RegistryReader>>,
+    #[cfg(feature = "registry-node-health")]
+    health_tracker: Option<NodeHealthTracker>,
 }
 
 impl UnifiedRegistry {
@@ -84,9 +96,18 @@ impl UnifiedRegistry {
         Self {
             internal_source: internal_source.into(),
             external_sources: external_sources.into_iter().map(Arc::from).collect(),
+            #[cfg(feature = "registry-node-health")]
+            health_tracker: None,
         }
     }
 
+    /// Sets the tracker that provides the health of the registry's nodes.
+    #[cfg(feature = "registry-node-health")]
+    pub fn with_health_tracker(mut self, health_tracker: NodeHealthTracker) -> Self {
+        self.health_tracker = Some(health_tracker);
+        self
+    }
+
     /// Gets all nodes from all sources (in ascending order of precedence) without deduplication.
     fn all_nodes<'a>(&'a self) -> Box<dyn Iterator<Item = Node> + 'a> {
         Box::new(
@@ -197,6 +218,14 @@ impl RegistryReader for UnifiedRegistry {
                 })
             }))
     }
+
+    #[cfg(feature = "registry-node-health")]
+    fn get_node_health(&self, identity: &str) -> Result<Option<NodeHealth>, RegistryError> {
+        match &self.health_tracker {
+            Some(tracker) => Ok(tracker.get_health(identity)?),
+            None => Ok(None),
+        }
+    }
 }
 
 impl RegistryWriter for UnifiedRegistry {
@@ -600,6 +629,38 @@ mod test {
         assert_eq!(None, nodes.next());
     }
 
+    /// Verify that node health is only reported when a health tracker is provided, and that the
+    /// tracker's recorded health is returned by the unified registry.
+    #[cfg(feature = "registry-node-health")]
+    #[test]
+    fn node_health() {
+        let unified = UnifiedRegistry::new(Box::new(MemRegistry::default()), vec![]);
+        assert_eq!(
+            None,
+            unified
+                .get_node_health("node1")
+                .expect("Unable to get node health")
+        );
+
+        let tracker = NodeHealthTracker::new();
+        let unified = unified.with_health_tracker(tracker.clone());
+        assert_eq!(
+            None,
+            unified
+                .get_node_health("node1")
+                .expect("Unable to get node health")
+        );
+
+        tracker
+            .record_connected("node1", None)
+            .expect("Unable to record connection");
+        let health = unified
+            .get_node_health("node1")
+            .expect("Unable to get node health")
+            .expect("Node health not found");
+        assert!(health.is_reachable());
+    }
+
     /// Verify that the `NodeRegistryWriter` implementation affects only the internal registry.
     #[test]
     fn write_nodes() {
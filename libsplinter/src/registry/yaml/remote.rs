@@ -29,6 +29,8 @@ use std::sync::{
 use std::thread;
 use std::time::{Duration, Instant};
 
+#[cfg(feature = "registry-remote-signed")]
+use cylinder::{PublicKey, Signature, Verifier};
 use openssl::hash::{hash, MessageDigest};
 
 use crate::error::{InternalError, InvalidStateError};
@@ -71,9 +73,18 @@ use super::{LocalYamlRegistry, YamlNode};
 /// and the previously cached registry values will continue to be used. The next time the registry
 /// is read, it will try again to refresh the cache.
 ///
+/// # Signed Registries
+///
+/// A registry constructed with [`new_signed`] only accepts a remote file if it has a detached
+/// signature from one of the trusted keys. The signature is fetched from the remote file's URL
+/// with `.sig` appended, and must be the hex-encoded signature of the exact bytes of the remote
+/// file. A file with a missing or invalid signature is treated like any other failed refresh: it
+/// is rejected and the previously cached registry values continue to be used.
+///
 /// [`Node`]: struct.Node.html
 /// [`RegistryReader`]: trait.RegistryReader.html
 /// [`constructor`]: struct.RemoteYamlRegistry.html#method.new
+/// [`new_signed`]: struct.RemoteYamlRegistry.html#method.new_signed
 pub struct RemoteYamlRegistry {
     internal: Arc<Mutex<Internal>>,
     shutdown_handle: Option<RemoteYamlShutdownHandle>,
@@ -97,11 +108,78 @@ impl RemoteYamlRegistry {
         cache_dir: &str,
         automatic_refresh_period: Option<Duration>,
         forced_refresh_period: Option<Duration>,
+    ) -> Result<Self, RegistryError> {
+        Self::create(
+            url,
+            cache_dir,
+            automatic_refresh_period,
+            forced_refresh_period,
+            #[cfg(feature = "registry-remote-signed")]
+            None,
+        )
+    }
+
+    /// Construct a new `RemoteYamlRegistry` that only accepts remote files signed by one of the
+    /// `trusted_keys`.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - URL of the registry's backing YAML file.
+    /// * `cache_dir` - Directory that the local registry cache will be stored in.
+    /// * `automatic_refresh_period` - Amount of time between attempts to automatically fetch and
+    ///   cache the remote YAML file in the background. If `None`, background refreshes will be
+    ///   disabled. The automatic refresh occurs with a tolerance of +/- 1 second.
+    /// * `forced_refresh_period` - Amount of time since the last successful cache refresh before
+    ///   attempting to refresh on every read operation. If `None`, forced refreshes will be
+    ///   disabled.
+    /// * `verifier` - Verifier for the remote file's signature.
+    /// * `trusted_keys` - The public keys that may sign the remote file; at least one key must be
+    ///   provided.
+    #[cfg(feature = "registry-remote-signed")]
+    pub fn new_signed(
+        url: &str,
+        cache_dir: &str,
+        automatic_refresh_period: Option<Duration>,
+        forced_refresh_period: Option<Duration>,
+        verifier: Box<dyn Verifier>,
+        trusted_keys: Vec<PublicKey>,
+    ) -> Result<Self, RegistryError> {
+        if trusted_keys.is_empty() {
+            return Err(RegistryError::InvalidStateError(
+                InvalidStateError::with_message(format!(
+                    "At least one trusted key is required for signed remote registry '{}'",
+                    url
+                )),
+            ));
+        }
+
+        Self::create(
+            url,
+            cache_dir,
+            automatic_refresh_period,
+            forced_refresh_period,
+            Some(SignatureVerification {
+                verifier,
+                trusted_keys,
+            }),
+        )
+    }
+
+    fn create(
+        url: &str,
+        cache_dir: &str,
+        automatic_refresh_period: Option<Duration>,
+        forced_refresh_period: Option<Duration>,
+        #[cfg(feature = "registry-remote-signed")] signature_verification: Option<
+            SignatureVerification,
+        >,
     ) -> Result<Self, RegistryError> {
         let internal = Arc::new(Mutex::new(Internal::new(
             url,
             cache_dir,
             forced_refresh_period,
+            #[cfg(feature = "registry-remote-signed")]
+            signature_verification,
         )?));
 
         let (running, join_handle) = {
@@ -190,6 +268,14 @@ impl RegistryReader for RemoteYamlRegistry {
     }
 }
 
+/// The keys that are trusted to sign a remote registry file, and the verifier used to check the
+/// file's signature.
+#[cfg(feature = "registry-remote-signed")]
+struct SignatureVerification {
+    verifier: Box<dyn Verifier>,
+    trusted_keys: Vec<PublicKey>,
+}
+
 /// Holds the internal state of the remote registry.
 struct Internal {
     url: String,
@@ -197,6 +283,8 @@ struct Internal {
     last_refresh_successful: bool,
     forced_refresh_period: Option<Duration>,
     next_forced_refresh: Option<Instant>,
+    #[cfg(feature = "registry-remote-signed")]
+    signature_verification: Option<SignatureVerification>,
 }
 
 impl Internal {
@@ -205,6 +293,9 @@ impl Internal {
         url: &str,
         cache_dir: &str,
         forced_refresh_period: Option<Duration>,
+        #[cfg(feature = "registry-remote-signed")] signature_verification: Option<
+            SignatureVerification,
+        >,
     ) -> Result<Self, RegistryError> {
         let url = url.to_string();
 
@@ -216,6 +307,8 @@ impl Internal {
             last_refresh_successful: false,
             forced_refresh_period,
             next_forced_refresh: None,
+            #[cfg(feature = "registry-remote-signed")]
+            signature_verification,
         };
 
         // If initial fetch/cache fails, it will be re-attempted on the next registry read, so just
@@ -230,9 +323,18 @@ impl Internal {
         Ok(internal)
     }
 
+    /// Fetch the nodes from the remote file, verifying its signature if configured.
+    fn fetch_nodes(&self) -> Result<Vec<Node>, RegistryError> {
+        fetch_nodes_from_remote(
+            &self.url,
+            #[cfg(feature = "registry-remote-signed")]
+            self.signature_verification.as_ref(),
+        )
+    }
+
     /// Attempt to refresh the internal cache and update state accordingly.
     fn refresh_cache(&mut self) -> Result<(), RegistryError> {
-        fetch_nodes_from_remote(&self.url)
+        self.fetch_nodes()
             .and_then(|nodes| self.cache.write_nodes(nodes))
             .map_err(|err| {
                 self.last_refresh_successful = false;
@@ -312,23 +414,30 @@ fn compute_cache_filename(url: &str, cache_dir: &str) -> Result<String, Registry
         .to_string())
 }
 
-/// Fetch, parse, and validate the YAML registry file at the given URL.
-fn fetch_nodes_from_remote(url: &str) -> Result<Vec<Node>, RegistryError> {
-    let bytes = reqwest::blocking::get(url)
-        .and_then(|response| response.error_for_status())
-        .map_err(|err| {
-            RegistryError::InternalError(InternalError::from_source_with_message(
-                Box::new(err),
-                format!("Failed to fetch remote registry file from {}", url),
-            ))
-        })?
-        .bytes()
-        .map_err(|err| {
-            RegistryError::InternalError(InternalError::from_source_with_message(
-                Box::new(err),
-                "Failed to get bytes from remote registry file HTTP response".into(),
-            ))
-        })?;
+/// Fetch, parse, and validate the YAML registry file at the given URL. If `signature_verification`
+/// is provided, the file's detached signature is fetched and verified before the file is parsed.
+fn fetch_nodes_from_remote(
+    url: &str,
+    #[cfg(feature = "registry-remote-signed")] signature_verification: Option<
+        &SignatureVerification,
+    >,
+) -> Result<Vec<Node>, RegistryError> {
+    let bytes = fetch_remote_file(url, "registry file")?;
+
+    #[cfg(feature = "registry-remote-signed")]
+    {
+        if let Some(signature_verification) = signature_verification {
+            let signature_url = format!("{}.sig", url);
+            let signature = fetch_remote_file(&signature_url, "registry signature file")?;
+            verify_signature(&bytes, &signature, signature_verification).map_err(|err| {
+                RegistryError::InvalidStateError(InvalidStateError::with_message(format!(
+                    "Rejected remote registry file from {}: {}",
+                    url, err
+                )))
+            })?;
+        }
+    }
+
     let yaml_nodes: Vec<YamlNode> = serde_yaml::from_slice(&bytes).map_err(|_| {
         RegistryError::InternalError(InternalError::with_message(
             "Failed to deserialize remote registry file: Not a valid YAML sequence of nodes".into(),
@@ -353,6 +462,62 @@ fn fetch_nodes_from_remote(url: &str) -> Result<Vec<Node>, RegistryError> {
     Ok(nodes)
 }
 
+/// Fetch the bytes of the file at the given URL.
+fn fetch_remote_file(url: &str, description: &str) -> Result<Vec<u8>, RegistryError> {
+    reqwest::blocking::get(url)
+        .and_then(|response| response.error_for_status())
+        .map_err(|err| {
+            RegistryError::InternalError(InternalError::from_source_with_message(
+                Box::new(err),
+                format!("Failed to fetch remote {} from {}", description, url),
+            ))
+        })?
+        .bytes()
+        .map(|bytes| bytes.to_vec())
+        .map_err(|err| {
+            RegistryError::InternalError(InternalError::from_source_with_message(
+                Box::new(err),
+                format!(
+                    "Failed to get bytes from remote {} HTTP response",
+                    description
+                ),
+            ))
+        })
+}
+
+/// Verify that `signature`, a hex-encoded detached signature, is a valid signature of `bytes` by
+/// one of the trusted keys. Returns a description of the problem if it is not.
+#[cfg(feature = "registry-remote-signed")]
+fn verify_signature(
+    bytes: &[u8],
+    signature: &[u8],
+    signature_verification: &SignatureVerification,
+) -> Result<(), String> {
+    let signature = std::str::from_utf8(signature)
+        .map_err(|_| "signature file is not valid UTF-8".to_string())
+        .and_then(|signature| {
+            Signature::from_hex(signature.trim())
+                .map_err(|err| format!("signature file is not a valid signature: {}", err))
+        })?;
+
+    for key in &signature_verification.trusted_keys {
+        match signature_verification
+            .verifier
+            .verify(bytes, &signature, key)
+        {
+            Ok(true) => return Ok(()),
+            Ok(false) => (),
+            Err(err) => debug!(
+                "Unable to verify remote registry signature with key {}: {}",
+                key.as_hex(),
+                err
+            ),
+        }
+    }
+
+    Err("file is not signed by a trusted key".into())
+}
+
 /// Infinitely loop, attempting to refresh the `internal` cache every `refresh_period`, until no
 /// longer `running`.
 fn automatic_refresh_loop(
@@ -436,6 +601,8 @@ mod tests {
     use std::fs::File;
 
     use actix_web::HttpResponse;
+    #[cfg(feature = "registry-remote-signed")]
+    use cylinder::{secp256k1::Secp256k1Context, Context};
     use futures::future::IntoFuture;
     use tempfile::{Builder, TempDir};
 
@@ -891,6 +1058,85 @@ mod tests {
         test_config.shutdown();
     }
 
+    /// Verifies that a signed remote file is only accepted if the signature is valid for the
+    /// exact bytes of the file and was made by one of the trusted keys.
+    #[cfg(feature = "registry-remote-signed")]
+    #[test]
+    fn verify_registry_signature() {
+        let context = Secp256k1Context::new();
+        let signer = context.new_signer(context.new_random_private_key());
+        let other_signer = context.new_signer(context.new_random_private_key());
+
+        let signature_verification = SignatureVerification {
+            verifier: context.new_verifier(),
+            trusted_keys: vec![
+                other_signer.public_key().expect("Failed to get public key"),
+                signer.public_key().expect("Failed to get public key"),
+            ],
+        };
+
+        let bytes = serde_yaml::to_vec(
+            &mock_registry()
+                .into_iter()
+                .map(YamlNode::from)
+                .collect::<Vec<_>>(),
+        )
+        .expect("Failed to serialize registry file");
+        let signature = signer.sign(&bytes).expect("Failed to sign registry file");
+
+        // A signature by any of the trusted keys is accepted, even with trailing whitespace
+        assert!(verify_signature(
+            &bytes,
+            format!("{}\n", signature.as_hex()).as_bytes(),
+            &signature_verification
+        )
+        .is_ok());
+
+        // A tampered file is rejected
+        let mut tampered = bytes.clone();
+        tampered.extend_from_slice(b"\n- identity: Node-999");
+        assert!(verify_signature(
+            &tampered,
+            signature.as_hex().as_bytes(),
+            &signature_verification
+        )
+        .is_err());
+
+        // A signature by an untrusted key is rejected
+        let untrusted_signer = context.new_signer(context.new_random_private_key());
+        let untrusted_signature = untrusted_signer
+            .sign(&bytes)
+            .expect("Failed to sign registry file");
+        assert!(verify_signature(
+            &bytes,
+            untrusted_signature.as_hex().as_bytes(),
+            &signature_verification
+        )
+        .is_err());
+
+        // A malformed signature is rejected
+        assert!(verify_signature(&bytes, b"not a signature", &signature_verification).is_err());
+    }
+
+    /// Verifies that a signed registry requires at least one trusted key.
+    #[cfg(feature = "registry-remote-signed")]
+    #[test]
+    fn signed_registry_requires_trusted_keys() {
+        let test_config = TestConfig::setup("signed_registry_requires_trusted_keys", None);
+
+        assert!(RemoteYamlRegistry::new_signed(
+            test_config.url(),
+            test_config.path(),
+            None,
+            None,
+            Secp256k1Context::new().new_verifier(),
+            vec![],
+        )
+        .is_err());
+
+        test_config.shutdown();
+    }
+
     /// Verifies that when the remote file is available at startup, it's fetched and cached
     /// successfully. The internal list of nodes and the backing file should match the remote file.
     #[test]
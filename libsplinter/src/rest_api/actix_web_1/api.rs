@@ -12,12 +12,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(all(feature = "https-bind", feature = "tls-reload"))]
+use std::path::PathBuf;
 use std::sync::mpsc;
+#[cfg(all(feature = "https-bind", feature = "tls-reload"))]
+use std::sync::{Arc, RwLock};
 use std::thread;
 
 use actix_web::{middleware, App, HttpServer};
 use futures::Future;
+#[cfg(all(feature = "https-bind", feature = "tls-reload"))]
+use openssl::ssl::{SniError, SslContext};
 
+#[cfg(feature = "rest-api-audit-log")]
+use crate::rest_api::audit::AuditLogStore;
 #[cfg(feature = "authorization")]
 use crate::rest_api::auth::authorization::{
     routes::AuthorizationResourceProvider, AuthorizationHandler, PermissionMap,
@@ -26,6 +34,8 @@ use crate::rest_api::auth::{actix::Authorization, identity::IdentityProvider};
 #[cfg(feature = "rest-api-cors")]
 use crate::rest_api::cors::Cors;
 use crate::rest_api::{BindConfig, RestApiServerError};
+#[cfg(all(feature = "https-bind", feature = "tls-reload"))]
+use crate::{error::InternalError, transport::tls::reload::TlsReloader};
 
 use super::Resource;
 #[cfg(feature = "authorization")]
@@ -57,6 +67,11 @@ pub struct RestApi {
     pub(super) identity_providers: Vec<Box<dyn IdentityProvider>>,
     #[cfg(feature = "authorization")]
     pub(super) authorization_handlers: Vec<Box<dyn AuthorizationHandler>>,
+    #[cfg(feature = "rest-api-audit-log")]
+    pub(super) audit_log_store: Option<Box<dyn AuditLogStore>>,
+    /// The reloaded TLS context, which replaces the context the server was started with
+    #[cfg(all(feature = "https-bind", feature = "tls-reload"))]
+    pub(super) tls_context: Arc<RwLock<Option<SslContext>>>,
 }
 
 impl RestApi {
@@ -72,6 +87,27 @@ impl RestApi {
         self
     }
 
+    /// Returns a reloader which rebuilds the TLS context of the REST API from its certificate and
+    /// key files, if the REST API is bound with HTTPS.
+    ///
+    /// Connections accepted after a reload use the new certificate; existing connections are not
+    /// affected.
+    #[cfg(all(feature = "https-bind", feature = "tls-reload"))]
+    pub fn tls_reloader(&self) -> Option<RestApiTlsReloader> {
+        match &self.bind {
+            BindConfig::Https {
+                cert_path,
+                key_path,
+                ..
+            } => Some(RestApiTlsReloader {
+                cert_path: cert_path.clone(),
+                key_path: key_path.clone(),
+                tls_context: self.tls_context.clone(),
+            }),
+            BindConfig::Http(_) => None,
+        }
+    }
+
     pub fn run(
         self,
     ) -> Result<(RestApiShutdownHandle, thread::JoinHandle<()>), RestApiServerError> {
@@ -86,6 +122,11 @@ impl RestApi {
             #[cfg(feature = "authorization")]
             self.authorization_handlers.to_owned(),
         );
+        #[cfg(feature = "rest-api-audit-log")]
+        let authorization = match self.audit_log_store {
+            Some(audit_log_store) => authorization.with_audit_log_store(audit_log_store),
+            None => authorization,
+        };
 
         #[cfg(feature = "rest-api-cors")]
         let cors = match &allow_list {
@@ -100,11 +141,24 @@ impl RestApi {
                 cert_path,
                 key_path,
             } => {
-                let mut acceptor =
-                    openssl::ssl::SslAcceptor::mozilla_modern(openssl::ssl::SslMethod::tls())?;
-                acceptor.set_private_key_file(key_path, openssl::ssl::SslFiletype::PEM)?;
-                acceptor.set_certificate_chain_file(&cert_path)?;
-                acceptor.check_private_key()?;
+                #[allow(unused_mut)]
+                let mut acceptor = build_acceptor(&cert_path, &key_path)?;
+
+                // Switch to the reloaded context, if any, before the handshake is completed
+                #[cfg(feature = "tls-reload")]
+                {
+                    let tls_context = self.tls_context;
+                    acceptor.set_servername_callback(move |ssl, _| {
+                        let tls_context = tls_context
+                            .read()
+                            .unwrap_or_else(|poisoned| poisoned.into_inner());
+                        if let Some(context) = tls_context.as_ref() {
+                            ssl.set_ssl_context(context)
+                                .map_err(|_| SniError::ALERT_FATAL)?;
+                        }
+                        Ok(())
+                    });
+                }
 
                 (bind, Some(acceptor))
             }
@@ -345,3 +399,49 @@ impl RestApi {
         ))
     }
 }
+
+#[cfg(feature = "https-bind")]
+fn build_acceptor(
+    cert_path: &str,
+    key_path: &str,
+) -> Result<openssl::ssl::SslAcceptorBuilder, openssl::error::ErrorStack> {
+    let mut acceptor = openssl::ssl::SslAcceptor::mozilla_modern(openssl::ssl::SslMethod::tls())?;
+    acceptor.set_private_key_file(key_path, openssl::ssl::SslFiletype::PEM)?;
+    acceptor.set_certificate_chain_file(cert_path)?;
+    acceptor.check_private_key()?;
+    Ok(acceptor)
+}
+
+/// Rebuilds the TLS context of a `RestApi` from its certificate and key files.
+#[cfg(all(feature = "https-bind", feature = "tls-reload"))]
+pub struct RestApiTlsReloader {
+    cert_path: String,
+    key_path: String,
+    tls_context: Arc<RwLock<Option<SslContext>>>,
+}
+
+#[cfg(all(feature = "https-bind", feature = "tls-reload"))]
+impl TlsReloader for RestApiTlsReloader {
+    fn name(&self) -> &str {
+        "rest-api"
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        vec![
+            PathBuf::from(&self.cert_path),
+            PathBuf::from(&self.key_path),
+        ]
+    }
+
+    fn reload(&self) -> Result<(), InternalError> {
+        let context = build_acceptor(&self.cert_path, &self.key_path)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?
+            .build()
+            .into_context();
+        *self
+            .tls_context
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(context);
+        Ok(())
+    }
+}
@@ -20,6 +20,8 @@ use std::sync::Mutex;
 use crate::error::InvalidStateError;
 #[cfg(feature = "oauth")]
 use crate::oauth::{GithubOAuthClientBuilder, OpenIdOAuthClientBuilder};
+#[cfg(feature = "rest-api-audit-log")]
+use crate::rest_api::audit::AuditLogStore;
 #[cfg(feature = "authorization")]
 use crate::rest_api::auth::authorization::AuthorizationHandler;
 #[cfg(feature = "cylinder-jwt")]
@@ -45,6 +47,8 @@ pub struct RestApiBuilder {
     auth_configs: Vec<AuthConfig>,
     #[cfg(feature = "authorization")]
     authorization_handlers: Vec<Box<dyn AuthorizationHandler>>,
+    #[cfg(feature = "rest-api-audit-log")]
+    audit_log_store: Option<Box<dyn AuditLogStore>>,
 }
 
 impl RestApiBuilder {
@@ -110,6 +114,13 @@ impl RestApiBuilder {
         self
     }
 
+    /// Records the requests that modify the node in the given audit log
+    #[cfg(feature = "rest-api-audit-log")]
+    pub fn with_audit_log_store(mut self, audit_log_store: Box<dyn AuditLogStore>) -> Self {
+        self.audit_log_store = Some(audit_log_store);
+        self
+    }
+
     // Allowing unused_mut because self must be mutable if feature `auth` is enabled
     #[allow(unused_mut)]
     pub fn build(mut self) -> Result<RestApi, RestApiServerError> {
@@ -261,6 +272,10 @@ impl RestApiBuilder {
             identity_providers,
             #[cfg(feature = "authorization")]
             authorization_handlers: self.authorization_handlers,
+            #[cfg(feature = "rest-api-audit-log")]
+            audit_log_store: self.audit_log_store,
+            #[cfg(all(feature = "https-bind", feature = "tls-reload"))]
+            tls_context: Default::default(),
         })
     }
 }
@@ -352,6 +367,10 @@ mod test {
                 identity_providers: vec![],
                 #[cfg(feature = "authorization")]
                 authorization_handlers: vec![],
+                #[cfg(feature = "rest-api-audit-log")]
+                audit_log_store: None,
+                #[cfg(all(feature = "https-bind", feature = "tls-reload"))]
+                tls_context: Default::default(),
             })
         }
     }
@@ -131,6 +131,8 @@ pub struct Resource {
     methods: Vec<ResourceMethod>,
     #[cfg(not(feature = "authorization"))]
     methods: Vec<(Method, Arc<HandlerFunction>)>,
+    #[cfg(feature = "authorization-handler-rbac-scopes")]
+    payload_scoped: bool,
 }
 
 impl Resource {
@@ -154,6 +156,8 @@ impl Resource {
             route: route.to_string(),
             methods: vec![],
             request_guards: vec![],
+            #[cfg(feature = "authorization-handler-rbac-scopes")]
+            payload_scoped: false,
         }
     }
 
@@ -233,6 +237,20 @@ impl Resource {
         self
     }
 
+    /// Marks the methods of this resource as ones whose circuit scope is determined from the
+    /// request's payload.
+    ///
+    /// If a client has not been granted a method's permission node-wide, the request is passed to
+    /// the handler with a [`PayloadScopeAuthorization`] in its extensions. The handler must use it
+    /// to check the permission against the circuit its payload concerns.
+    ///
+    /// [`PayloadScopeAuthorization`]: crate::rest_api::auth::authorization::PayloadScopeAuthorization
+    #[cfg(feature = "authorization-handler-rbac-scopes")]
+    pub fn with_payload_scope(mut self) -> Self {
+        self.payload_scoped = true;
+        self
+    }
+
     #[cfg(feature = "authorization")]
     pub(super) fn into_route(self) -> (actix_web::Resource, PermissionMap<Method>) {
         let mut resource = web::resource(&self.route);
@@ -257,6 +275,8 @@ impl Resource {
         let request_guards = self.request_guards;
         let mut permission_map = PermissionMap::new();
         let route = self.route.clone();
+        #[cfg(feature = "authorization-handler-rbac-scopes")]
+        let payload_scoped = self.payload_scoped;
         let resource = self.methods.into_iter().fold(
             resource,
             |resource,
@@ -279,6 +299,10 @@ impl Resource {
                     (handler)(r, p)
                 };
                 permission_map.add_permission(method, &route, permission);
+                #[cfg(feature = "authorization-handler-rbac-scopes")]
+                if payload_scoped {
+                    permission_map.add_payload_scoped_endpoint(method, &route);
+                }
                 resource.route(match method {
                     Method::Get => web::get().to_async(func),
                     Method::Post => web::post().to_async(func),
@@ -0,0 +1,220 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Database-backed implementation of the [AuditLogStore], powered by [diesel].
+
+mod models;
+mod operations;
+mod schema;
+
+use std::sync::{Arc, RwLock};
+
+use diesel::r2d2::{ConnectionManager, Pool};
+
+use crate::store::pool::ConnectionPool;
+
+use super::{AuditLogEntry, AuditLogStore, AuditLogStoreError, InsertableAuditLogEntry};
+
+use operations::{
+    add_entry::AuditLogStoreAddEntry as _, count_entries::AuditLogStoreCountEntries as _,
+    list_entries::AuditLogStoreListEntries as _, AuditLogStoreOperations,
+};
+
+/// A database-backed [AuditLogStore], powered by [diesel].
+pub struct DieselAuditLogStore<C: diesel::Connection + 'static> {
+    connection_pool: ConnectionPool<C>,
+}
+
+impl<C: diesel::Connection + 'static> DieselAuditLogStore<C> {
+    /// Creates a new `DieselAuditLogStore`.
+    ///
+    /// # Arguments
+    ///
+    ///  * `connection_pool`: connection pool for the database
+    pub fn new(connection_pool: Pool<ConnectionManager<C>>) -> Self {
+        Self {
+            connection_pool: connection_pool.into(),
+        }
+    }
+
+    /// Create a new `DieselAuditLogStore` with write exclusivity enabled.
+    ///
+    /// Write exclusivity is enforced by providing a connection pool that is wrapped in a
+    /// [`RwLock`]. This ensures that there may be only one writer, but many readers.
+    ///
+    /// # Arguments
+    ///
+    ///  * `connection_pool`: read-write lock-guarded connection pool for the database
+    pub fn new_with_write_exclusivity(
+        connection_pool: Arc<RwLock<Pool<ConnectionManager<C>>>>,
+    ) -> Self {
+        Self {
+            connection_pool: connection_pool.into(),
+        }
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl AuditLogStore for DieselAuditLogStore<diesel::sqlite::SqliteConnection> {
+    fn add_entry(&self, entry: InsertableAuditLogEntry) -> Result<(), AuditLogStoreError> {
+        self.connection_pool
+            .execute_write(|connection| AuditLogStoreOperations::new(connection).add_entry(entry))
+    }
+
+    fn list_entries(
+        &self,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<AuditLogEntry>, AuditLogStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            AuditLogStoreOperations::new(connection).list_entries(offset, limit)
+        })
+    }
+
+    fn count_entries(&self) -> Result<usize, AuditLogStoreError> {
+        self.connection_pool
+            .execute_read(|connection| AuditLogStoreOperations::new(connection).count_entries())
+    }
+
+    fn clone_box(&self) -> Box<dyn AuditLogStore> {
+        Box::new(Self {
+            connection_pool: self.connection_pool.clone(),
+        })
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl AuditLogStore for DieselAuditLogStore<diesel::pg::PgConnection> {
+    fn add_entry(&self, entry: InsertableAuditLogEntry) -> Result<(), AuditLogStoreError> {
+        self.connection_pool
+            .execute_write(|connection| AuditLogStoreOperations::new(connection).add_entry(entry))
+    }
+
+    fn list_entries(
+        &self,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<AuditLogEntry>, AuditLogStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            AuditLogStoreOperations::new(connection).list_entries(offset, limit)
+        })
+    }
+
+    fn count_entries(&self) -> Result<usize, AuditLogStoreError> {
+        self.connection_pool
+            .execute_read(|connection| AuditLogStoreOperations::new(connection).count_entries())
+    }
+
+    fn clone_box(&self) -> Box<dyn AuditLogStore> {
+        Box::new(Self {
+            connection_pool: self.connection_pool.clone(),
+        })
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+pub mod tests {
+    use super::*;
+
+    use diesel::{
+        r2d2::{ConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+
+    use crate::migrations::run_sqlite_migrations;
+    use crate::rest_api::audit::{AuditDetails, AuditOutcome};
+
+    /// Verify that entries can be added to the store and listed a page at a time:
+    ///
+    /// 1. Add three entries, one of which has details of the circuit it acted on and one of which
+    ///    was denied and has no identity
+    /// 2. Verify the entries are counted
+    /// 3. Verify the first page contains the first two entries, in the order they were added, and
+    ///    that the details were stored
+    /// 4. Verify the second page contains the last entry
+    #[test]
+    fn sqlite_add_list_entries() {
+        let store = DieselAuditLogStore::new(create_connection_pool_and_migrate());
+
+        store
+            .add_entry(
+                InsertableAuditLogEntry::new(
+                    Some("key:0123".into()),
+                    Some("circuit.write".into()),
+                    "POST".into(),
+                    "/admin/submit".into(),
+                    AuditOutcome::Allowed,
+                )
+                .with_details(
+                    AuditDetails::new()
+                        .with_circuit_id("abcde-01234")
+                        .with_action("circuit_proposal_vote"),
+                ),
+            )
+            .expect("Unable to add first entry");
+        store
+            .add_entry(InsertableAuditLogEntry::new(
+                None,
+                Some("circuit.write".into()),
+                "POST".into(),
+                "/admin/submit".into(),
+                AuditOutcome::Denied,
+            ))
+            .expect("Unable to add second entry");
+        store
+            .add_entry(InsertableAuditLogEntry::new(
+                Some("user:abcd".into()),
+                Some("authorization.rbac.write".into()),
+                "DELETE".into(),
+                "/authorization/roles/admin".into(),
+                AuditOutcome::Allowed,
+            ))
+            .expect("Unable to add third entry");
+
+        assert_eq!(store.count_entries().expect("Unable to count entries"), 3);
+
+        let entries = store.list_entries(0, 2).expect("Unable to list entries");
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].identity(), Some("key:0123"));
+        assert_eq!(entries[0].outcome(), AuditOutcome::Allowed);
+        assert_eq!(entries[0].details().circuit_id(), Some("abcde-01234"));
+        assert_eq!(entries[0].details().action(), Some("circuit_proposal_vote"));
+        assert_eq!(entries[1].identity(), None);
+        assert_eq!(entries[1].details(), &AuditDetails::new());
+        assert_eq!(entries[1].permission(), Some("circuit.write"));
+        assert_eq!(entries[1].route(), "/admin/submit");
+        assert_eq!(entries[1].outcome(), AuditOutcome::Denied);
+
+        let entries = store.list_entries(2, 2).expect("Unable to list entries");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].method(), "DELETE");
+        assert_eq!(entries[0].route(), "/authorization/roles/admin");
+    }
+
+    /// Creates a connection pool for an in-memory SQLite database with only a single connection
+    /// available. Each connection is backed by a different in-memory SQLite database, so limiting
+    /// the pool to a single connection ensures that the same DB is used for all operations.
+    fn create_connection_pool_and_migrate() -> Pool<ConnectionManager<SqliteConnection>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        pool
+    }
+}
@@ -0,0 +1,128 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::error::InternalError;
+use crate::rest_api::audit::{
+    AuditDetails, AuditLogEntry, AuditLogStoreError, InsertableAuditLogEntry,
+};
+
+use super::schema::rest_api_audit_log;
+
+/// Database model representation of an `AuditLogEntry`
+#[derive(Debug, PartialEq, Eq, Identifiable, Queryable)]
+#[table_name = "rest_api_audit_log"]
+#[primary_key(id)]
+pub struct AuditLogEntryModel {
+    pub id: i64,
+    pub timestamp: i64,
+    pub identity: Option<String>,
+    pub permission: Option<String>,
+    pub method: String,
+    pub route: String,
+    pub outcome: String,
+    pub circuit_id: Option<String>,
+    pub action: Option<String>,
+}
+
+/// Database model representation of an `InsertableAuditLogEntry`
+#[derive(Debug, PartialEq, Eq, Insertable)]
+#[table_name = "rest_api_audit_log"]
+pub struct InsertableAuditLogEntryModel {
+    pub timestamp: i64,
+    pub identity: Option<String>,
+    pub permission: Option<String>,
+    pub method: String,
+    pub route: String,
+    pub outcome: String,
+    pub circuit_id: Option<String>,
+    pub action: Option<String>,
+}
+
+impl TryFrom<InsertableAuditLogEntry> for InsertableAuditLogEntryModel {
+    type Error = AuditLogStoreError;
+
+    fn try_from(entry: InsertableAuditLogEntry) -> Result<Self, Self::Error> {
+        Ok(InsertableAuditLogEntryModel {
+            timestamp: to_timestamp(entry.timestamp)?,
+            outcome: entry.outcome.to_string(),
+            identity: entry.identity,
+            permission: entry.permission,
+            method: entry.method,
+            route: entry.route,
+            circuit_id: entry.details.circuit_id,
+            action: entry.details.action,
+        })
+    }
+}
+
+impl TryFrom<AuditLogEntryModel> for AuditLogEntry {
+    type Error = AuditLogStoreError;
+
+    fn try_from(model: AuditLogEntryModel) -> Result<Self, Self::Error> {
+        let mut details = AuditDetails::new();
+        if let Some(circuit_id) = model.circuit_id {
+            details = details.with_circuit_id(circuit_id);
+        }
+        if let Some(action) = model.action {
+            details = details.with_action(action);
+        }
+
+        Ok(AuditLogEntry::new(
+            model.id,
+            from_timestamp(model.timestamp)?,
+            model.identity,
+            model.permission,
+            model.method,
+            model.route,
+            model.outcome.parse().map_err(|err| {
+                AuditLogStoreError::Internal(InternalError::from_source(Box::new(err)))
+            })?,
+        )
+        .with_details(details))
+    }
+}
+
+/// Converts a `SystemTime` into the number of milliseconds since the unix epoch
+fn to_timestamp(time: SystemTime) -> Result<i64, AuditLogStoreError> {
+    let millis = time
+        .duration_since(UNIX_EPOCH)
+        .map_err(|err| InternalError::from_source(Box::new(err)))?
+        .as_millis();
+
+    i64::try_from(millis).map_err(|_| {
+        AuditLogStoreError::Internal(InternalError::with_message(
+            "timestamp could not be converted from u128 to i64".to_string(),
+        ))
+    })
+}
+
+/// Converts a number of milliseconds since the unix epoch into a `SystemTime`
+fn from_timestamp(timestamp: i64) -> Result<SystemTime, AuditLogStoreError> {
+    let millis = u64::try_from(timestamp).map_err(|_| {
+        AuditLogStoreError::Internal(InternalError::with_message(
+            "timestamp could not be converted from i64 to u64".to_string(),
+        ))
+    })?;
+
+    UNIX_EPOCH
+        .checked_add(Duration::from_millis(millis))
+        .ok_or_else(|| {
+            AuditLogStoreError::Internal(InternalError::with_message(
+                "timestamp could not be represented as a `SystemTime`".to_string(),
+            ))
+        })
+}
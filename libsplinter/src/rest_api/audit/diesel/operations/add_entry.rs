@@ -0,0 +1,50 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use diesel::{dsl::insert_into, prelude::*};
+
+use crate::rest_api::audit::{
+    diesel::{models::InsertableAuditLogEntryModel, schema::rest_api_audit_log},
+    AuditLogStoreError, InsertableAuditLogEntry,
+};
+
+use super::AuditLogStoreOperations;
+
+pub trait AuditLogStoreAddEntry {
+    fn add_entry(&self, entry: InsertableAuditLogEntry) -> Result<(), AuditLogStoreError>;
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> AuditLogStoreAddEntry for AuditLogStoreOperations<'a, diesel::sqlite::SqliteConnection> {
+    fn add_entry(&self, entry: InsertableAuditLogEntry) -> Result<(), AuditLogStoreError> {
+        insert_into(rest_api_audit_log::table)
+            .values(InsertableAuditLogEntryModel::try_from(entry)?)
+            .execute(self.conn)
+            .map(|_| ())
+            .map_err(AuditLogStoreError::from)
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> AuditLogStoreAddEntry for AuditLogStoreOperations<'a, diesel::pg::PgConnection> {
+    fn add_entry(&self, entry: InsertableAuditLogEntry) -> Result<(), AuditLogStoreError> {
+        insert_into(rest_api_audit_log::table)
+            .values(InsertableAuditLogEntryModel::try_from(entry)?)
+            .execute(self.conn)
+            .map(|_| ())
+            .map_err(AuditLogStoreError::from)
+    }
+}
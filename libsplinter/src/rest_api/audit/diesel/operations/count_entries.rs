@@ -0,0 +1,45 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use diesel::prelude::*;
+
+use crate::error::InternalError;
+use crate::rest_api::audit::{diesel::schema::rest_api_audit_log, AuditLogStoreError};
+
+use super::AuditLogStoreOperations;
+
+pub trait AuditLogStoreCountEntries {
+    fn count_entries(&self) -> Result<usize, AuditLogStoreError>;
+}
+
+impl<'a, C> AuditLogStoreCountEntries for AuditLogStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+{
+    fn count_entries(&self) -> Result<usize, AuditLogStoreError> {
+        let count = rest_api_audit_log::table
+            .count()
+            // Parse as an i64 here because Diesel knows how to convert a `BigInt` into an i64
+            .get_result::<i64>(self.conn)?;
+
+        usize::try_from(count).map_err(|_| {
+            AuditLogStoreError::Internal(InternalError::with_message(
+                "The number of audit log entries is larger than the max usize".to_string(),
+            ))
+        })
+    }
+}
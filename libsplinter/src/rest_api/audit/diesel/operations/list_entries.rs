@@ -0,0 +1,79 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use diesel::prelude::*;
+
+use crate::error::InternalError;
+use crate::rest_api::audit::{
+    diesel::{models::AuditLogEntryModel, schema::rest_api_audit_log},
+    AuditLogEntry, AuditLogStoreError,
+};
+
+use super::AuditLogStoreOperations;
+
+pub trait AuditLogStoreListEntries {
+    fn list_entries(
+        &self,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<AuditLogEntry>, AuditLogStoreError>;
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> AuditLogStoreListEntries
+    for AuditLogStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn list_entries(
+        &self,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<AuditLogEntry>, AuditLogStoreError> {
+        rest_api_audit_log::table
+            .order(rest_api_audit_log::id.asc())
+            .offset(to_i64(offset)?)
+            .limit(to_i64(limit)?)
+            .load::<AuditLogEntryModel>(self.conn)?
+            .into_iter()
+            .map(AuditLogEntry::try_from)
+            .collect()
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> AuditLogStoreListEntries for AuditLogStoreOperations<'a, diesel::pg::PgConnection> {
+    fn list_entries(
+        &self,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<AuditLogEntry>, AuditLogStoreError> {
+        rest_api_audit_log::table
+            .order(rest_api_audit_log::id.asc())
+            .offset(to_i64(offset)?)
+            .limit(to_i64(limit)?)
+            .load::<AuditLogEntryModel>(self.conn)?
+            .into_iter()
+            .map(AuditLogEntry::try_from)
+            .collect()
+    }
+}
+
+fn to_i64(value: usize) -> Result<i64, AuditLogStoreError> {
+    i64::try_from(value).map_err(|_| {
+        AuditLogStoreError::Internal(InternalError::with_message(
+            "paging value could not be converted from usize to i64".to_string(),
+        ))
+    })
+}
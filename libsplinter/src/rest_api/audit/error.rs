@@ -0,0 +1,114 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Error types and logic for the AuditLogStore.
+
+use std::error::Error;
+use std::fmt::Display;
+
+#[cfg(feature = "diesel")]
+use crate::error::ConstraintViolationType;
+use crate::error::{
+    ConstraintViolationError, InternalError, InvalidArgumentError, InvalidStateError,
+    ResourceTemporarilyUnavailableError,
+};
+
+/// Error type for the AuditLogStore trait.
+#[derive(Debug)]
+pub enum AuditLogStoreError {
+    ConstraintViolation(ConstraintViolationError),
+    Internal(InternalError),
+    InvalidArgument(InvalidArgumentError),
+    InvalidState(InvalidStateError),
+    ResourceTemporarilyUnavailable(ResourceTemporarilyUnavailableError),
+}
+
+impl Display for AuditLogStoreError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuditLogStoreError::ConstraintViolation(e) => e.fmt(f),
+            AuditLogStoreError::Internal(e) => e.fmt(f),
+            AuditLogStoreError::InvalidArgument(e) => e.fmt(f),
+            AuditLogStoreError::InvalidState(e) => e.fmt(f),
+            AuditLogStoreError::ResourceTemporarilyUnavailable(e) => e.fmt(f),
+        }
+    }
+}
+
+impl Error for AuditLogStoreError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AuditLogStoreError::ConstraintViolation(e) => Some(e),
+            AuditLogStoreError::Internal(e) => Some(e),
+            AuditLogStoreError::InvalidArgument(e) => Some(e),
+            AuditLogStoreError::InvalidState(e) => Some(e),
+            AuditLogStoreError::ResourceTemporarilyUnavailable(e) => Some(e),
+        }
+    }
+}
+
+impl From<InternalError> for AuditLogStoreError {
+    fn from(err: InternalError) -> Self {
+        AuditLogStoreError::Internal(err)
+    }
+}
+
+impl From<InvalidArgumentError> for AuditLogStoreError {
+    fn from(err: InvalidArgumentError) -> Self {
+        AuditLogStoreError::InvalidArgument(err)
+    }
+}
+
+impl From<InvalidStateError> for AuditLogStoreError {
+    fn from(err: InvalidStateError) -> Self {
+        AuditLogStoreError::InvalidState(err)
+    }
+}
+
+#[cfg(feature = "diesel")]
+impl From<diesel::r2d2::PoolError> for AuditLogStoreError {
+    fn from(err: diesel::r2d2::PoolError) -> Self {
+        AuditLogStoreError::ResourceTemporarilyUnavailable(
+            ResourceTemporarilyUnavailableError::from_source(Box::new(err)),
+        )
+    }
+}
+
+#[cfg(feature = "diesel")]
+impl From<diesel::result::Error> for AuditLogStoreError {
+    fn from(err: diesel::result::Error) -> Self {
+        match err {
+            diesel::result::Error::DatabaseError(db_err_kind, _) => match db_err_kind {
+                diesel::result::DatabaseErrorKind::UniqueViolation => {
+                    AuditLogStoreError::ConstraintViolation(
+                        ConstraintViolationError::from_source_with_violation_type(
+                            ConstraintViolationType::Unique,
+                            Box::new(err),
+                        ),
+                    )
+                }
+                diesel::result::DatabaseErrorKind::ForeignKeyViolation => {
+                    AuditLogStoreError::ConstraintViolation(
+                        ConstraintViolationError::from_source_with_violation_type(
+                            ConstraintViolationType::ForeignKey,
+                            Box::new(err),
+                        ),
+                    )
+                }
+                _ => AuditLogStoreError::Internal(InternalError::from_source(Box::new(err))),
+            },
+            _ => AuditLogStoreError::Internal(InternalError::from_source(Box::new(err))),
+        }
+    }
+}
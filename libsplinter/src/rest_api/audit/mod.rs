@@ -0,0 +1,311 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Defines a durable audit log of the REST API requests that modify a node.
+//!
+//! When an [AuditLogStore] is provided to the REST API, the authorization middleware records an
+//! entry for every `POST`, `PUT`, `PATCH`, and `DELETE` request to an endpoint that requires
+//! authentication, whether the request was allowed or denied. Requests that are denied before
+//! the client's identity could be determined are not recorded.
+//!
+//! An endpoint may add [AuditDetails] to the request's extensions to record what the request
+//! acted on, such as the circuit and action of a submitted circuit management payload.
+
+#[cfg(feature = "diesel")]
+pub mod diesel;
+mod error;
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::SystemTime;
+
+use crate::error::InvalidArgumentError;
+
+#[cfg(feature = "diesel")]
+pub use self::diesel::DieselAuditLogStore;
+pub use error::AuditLogStoreError;
+
+/// Whether an audited request was allowed to proceed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuditOutcome {
+    /// The client was authorized to make the request
+    Allowed,
+    /// The client was not authorized to make the request
+    Denied,
+}
+
+impl fmt::Display for AuditOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AuditOutcome::Allowed => f.write_str("allowed"),
+            AuditOutcome::Denied => f.write_str("denied"),
+        }
+    }
+}
+
+impl FromStr for AuditOutcome {
+    type Err = InvalidArgumentError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "allowed" => Ok(AuditOutcome::Allowed),
+            "denied" => Ok(AuditOutcome::Denied),
+            _ => Err(InvalidArgumentError::new(
+                "outcome",
+                format!("'{}' is not a valid audit outcome", s),
+            )),
+        }
+    }
+}
+
+/// Details of what an audited request acted on
+///
+/// An endpoint handler inserts this into the request's extensions once it has parsed the request,
+/// and the authorization middleware attaches it to the recorded entry.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AuditDetails {
+    circuit_id: Option<String>,
+    action: Option<String>,
+}
+
+impl AuditDetails {
+    /// Creates a new, empty `AuditDetails`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the ID of the circuit the request acted on
+    pub fn with_circuit_id<S: Into<String>>(mut self, circuit_id: S) -> Self {
+        self.circuit_id = Some(circuit_id.into());
+        self
+    }
+
+    /// Sets the action the request performed, such as `circuit_proposal_vote`
+    pub fn with_action<S: Into<String>>(mut self, action: S) -> Self {
+        self.action = Some(action.into());
+        self
+    }
+
+    /// Returns the ID of the circuit the request acted on, if any
+    pub fn circuit_id(&self) -> Option<&str> {
+        self.circuit_id.as_deref()
+    }
+
+    /// Returns the action the request performed, if any
+    pub fn action(&self) -> Option<&str> {
+        self.action.as_deref()
+    }
+}
+
+/// A REST API request that has been recorded in an [AuditLogStore]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AuditLogEntry {
+    id: i64,
+    timestamp: SystemTime,
+    identity: Option<String>,
+    permission: Option<String>,
+    method: String,
+    route: String,
+    outcome: AuditOutcome,
+    details: AuditDetails,
+}
+
+impl AuditLogEntry {
+    /// Creates a new `AuditLogEntry`
+    ///
+    /// This constructor should only be used by implementations of the [AuditLogStore] for
+    /// returning an existing entry.
+    pub fn new(
+        id: i64,
+        timestamp: SystemTime,
+        identity: Option<String>,
+        permission: Option<String>,
+        method: String,
+        route: String,
+        outcome: AuditOutcome,
+    ) -> Self {
+        Self {
+            id,
+            timestamp,
+            identity,
+            permission,
+            method,
+            route,
+            outcome,
+            details: AuditDetails::default(),
+        }
+    }
+
+    /// Sets the details of what the request acted on
+    pub fn with_details(mut self, details: AuditDetails) -> Self {
+        self.details = details;
+        self
+    }
+
+    /// Returns the store-assigned ID of the entry
+    pub fn id(&self) -> i64 {
+        self.id
+    }
+
+    /// Returns the time at which the request was recorded
+    pub fn timestamp(&self) -> SystemTime {
+        self.timestamp
+    }
+
+    /// Returns the identity of the client, if it could be determined
+    pub fn identity(&self) -> Option<&str> {
+        self.identity.as_deref()
+    }
+
+    /// Returns the ID of the permission the request required, if any
+    pub fn permission(&self) -> Option<&str> {
+        self.permission.as_deref()
+    }
+
+    /// Returns the HTTP method of the request
+    pub fn method(&self) -> &str {
+        &self.method
+    }
+
+    /// Returns the path that was requested
+    pub fn route(&self) -> &str {
+        &self.route
+    }
+
+    /// Returns whether the request was allowed or denied
+    pub fn outcome(&self) -> AuditOutcome {
+        self.outcome
+    }
+
+    /// Returns the details of what the request acted on
+    pub fn details(&self) -> &AuditDetails {
+        &self.details
+    }
+}
+
+/// A REST API request that should be added to an [AuditLogStore]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InsertableAuditLogEntry {
+    timestamp: SystemTime,
+    identity: Option<String>,
+    permission: Option<String>,
+    method: String,
+    route: String,
+    outcome: AuditOutcome,
+    details: AuditDetails,
+}
+
+impl InsertableAuditLogEntry {
+    /// Creates a new `InsertableAuditLogEntry`, recorded at the current time
+    ///
+    /// # Arguments
+    ///
+    /// * `identity` - The identity of the client, if it could be determined
+    /// * `permission` - The ID of the permission the request required, if any
+    /// * `method` - The HTTP method of the request
+    /// * `route` - The path that was requested
+    /// * `outcome` - Whether the request was allowed or denied
+    pub fn new(
+        identity: Option<String>,
+        permission: Option<String>,
+        method: String,
+        route: String,
+        outcome: AuditOutcome,
+    ) -> Self {
+        Self {
+            timestamp: SystemTime::now(),
+            identity,
+            permission,
+            method,
+            route,
+            outcome,
+            details: AuditDetails::default(),
+        }
+    }
+
+    /// Sets the details of what the request acted on
+    pub fn with_details(mut self, details: AuditDetails) -> Self {
+        self.details = details;
+        self
+    }
+
+    /// Returns the time at which the request was recorded
+    pub fn timestamp(&self) -> SystemTime {
+        self.timestamp
+    }
+
+    /// Returns the identity of the client, if it could be determined
+    pub fn identity(&self) -> Option<&str> {
+        self.identity.as_deref()
+    }
+
+    /// Returns the ID of the permission the request required, if any
+    pub fn permission(&self) -> Option<&str> {
+        self.permission.as_deref()
+    }
+
+    /// Returns the HTTP method of the request
+    pub fn method(&self) -> &str {
+        &self.method
+    }
+
+    /// Returns the path that was requested
+    pub fn route(&self) -> &str {
+        &self.route
+    }
+
+    /// Returns whether the request was allowed or denied
+    pub fn outcome(&self) -> AuditOutcome {
+        self.outcome
+    }
+
+    /// Returns the details of what the request acted on
+    pub fn details(&self) -> &AuditDetails {
+        &self.details
+    }
+}
+
+/// Defines methods for recording and reading REST API audit log entries
+pub trait AuditLogStore: Send + Sync {
+    /// Adds an entry to the log
+    ///
+    /// # Arguments
+    ///
+    /// * `entry` - The entry to be added
+    fn add_entry(&self, entry: InsertableAuditLogEntry) -> Result<(), AuditLogStoreError>;
+
+    /// Returns a page of entries, in the order they were added
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - The number of entries to skip
+    /// * `limit` - The maximum number of entries to return
+    fn list_entries(
+        &self,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<AuditLogEntry>, AuditLogStoreError>;
+
+    /// Returns the total number of entries in the log
+    fn count_entries(&self) -> Result<usize, AuditLogStoreError>;
+
+    /// Clone into a boxed, dynamically dispatched store
+    fn clone_box(&self) -> Box<dyn AuditLogStore>;
+}
+
+impl Clone for Box<dyn AuditLogStore> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
@@ -22,8 +22,14 @@ use actix_web::{
 };
 use futures::{Future, IntoFuture, Poll};
 
+#[cfg(feature = "rest-api-audit-log")]
+use crate::rest_api::audit::{AuditDetails, AuditLogStore, AuditOutcome, InsertableAuditLogEntry};
+#[cfg(feature = "authorization-handler-rbac-scopes")]
+use crate::rest_api::auth::authorization::PayloadScopeAuthorization;
 #[cfg(feature = "authorization")]
 use crate::rest_api::auth::authorization::{AuthorizationHandler, PermissionMap};
+#[cfg(feature = "rest-api-audit-log")]
+use crate::rest_api::auth::{authorization::Permission, get_identity};
 use crate::rest_api::auth::{authorize, identity::IdentityProvider, AuthorizationResult};
 use crate::rest_api::ErrorResponse;
 #[cfg(feature = "authorization")]
@@ -33,6 +39,8 @@ pub struct AuthorizationMiddleware<S> {
     pub(super) identity_providers: Vec<Box<dyn IdentityProvider>>,
     #[cfg(feature = "authorization")]
     pub(super) authorization_handlers: Vec<Box<dyn AuthorizationHandler>>,
+    #[cfg(feature = "rest-api-audit-log")]
+    pub(super) audit_log_store: Option<Box<dyn AuditLogStore>>,
     pub(super) service: S,
 }
 
@@ -122,6 +130,22 @@ where
             }
         };
 
+        // Requests that modify the node are recorded in the audit log, if one is configured
+        #[cfg(feature = "rest-api-audit-log")]
+        let audit_log_store = self.audit_log_store.clone().filter(|_| {
+            matches!(
+                method,
+                Method::Post | Method::Put | Method::Patch | Method::Delete
+            )
+        });
+        #[cfg(feature = "rest-api-audit-log")]
+        let audit_permission = match permission_map.get_permission(&method, req.path()) {
+            Some(Permission::Check { permission_id, .. }) => Some(permission_id.to_string()),
+            _ => None,
+        };
+        #[cfg(feature = "rest-api-audit-log")]
+        let mut audit_identity = None;
+
         match authorize(
             #[cfg(feature = "authorization")]
             &method,
@@ -135,6 +159,24 @@ where
         ) {
             AuthorizationResult::Authorized(identity) => {
                 debug!("Authenticated user {:?}", identity);
+                #[cfg(feature = "rest-api-audit-log")]
+                {
+                    audit_identity = Some(identity.to_string());
+                }
+                req.extensions_mut().insert(identity);
+            }
+            #[cfg(feature = "authorization-handler-rbac-scopes")]
+            AuthorizationResult::PayloadScopeRequired(identity, permission_id) => {
+                debug!("Authenticated user {:?}", identity);
+                #[cfg(feature = "rest-api-audit-log")]
+                {
+                    audit_identity = Some(identity.to_string());
+                }
+                req.extensions_mut().insert(PayloadScopeAuthorization::new(
+                    identity.clone(),
+                    permission_id,
+                    self.authorization_handlers.clone(),
+                ));
                 req.extensions_mut().insert(identity);
             }
             #[cfg(any(
@@ -144,6 +186,29 @@ where
             ))]
             AuthorizationResult::NoAuthorizationNecessary => {}
             AuthorizationResult::Unauthorized => {
+                // Denied requests are only recorded if the client could be identified, so that
+                // unauthenticated clients cannot flood the audit log
+                #[cfg(feature = "rest-api-audit-log")]
+                if let Some(store) = audit_log_store {
+                    match get_identity(auth_header, &self.identity_providers) {
+                        Some(identity) => record_audit_entry(
+                            &*store,
+                            InsertableAuditLogEntry::new(
+                                Some(identity.to_string()),
+                                audit_permission,
+                                req.method().to_string(),
+                                req.path().to_string(),
+                                AuditOutcome::Denied,
+                            ),
+                        ),
+                        None => debug!(
+                            "Not recording denied request from unidentified client: {} {}",
+                            req.method(),
+                            req.path()
+                        ),
+                    }
+                }
+
                 return Box::new(
                     req.into_response(
                         HttpResponse::Unauthorized()
@@ -151,7 +216,7 @@ where
                             .into_body(),
                     )
                     .into_future(),
-                )
+                );
             }
             #[cfg(feature = "authorization")]
             AuthorizationResult::UnknownEndpoint => {
@@ -166,13 +231,64 @@ where
             }
         }
 
-        Box::new(self.service.call(req).and_then(|mut res| {
+        // Only authenticated requests are recorded once they have been handled; requests that do
+        // not require authentication are not audited
+        #[cfg(feature = "rest-api-audit-log")]
+        let audit = match (audit_log_store, audit_identity) {
+            (Some(store), Some(identity)) => Some((
+                store,
+                identity,
+                audit_permission,
+                req.method().to_string(),
+                req.path().to_string(),
+            )),
+            _ => None,
+        };
+
+        Box::new(self.service.call(req).and_then(move |mut res| {
             res.headers_mut().insert(
                 header::ACCESS_CONTROL_ALLOW_CREDENTIALS,
                 HeaderValue::from_static("true"),
             );
 
+            // The endpoint may still deny the request, such as when its permission is scoped to
+            // the circuit in its payload
+            #[cfg(feature = "rest-api-audit-log")]
+            if let Some((store, identity, permission, method, route)) = audit {
+                let outcome = match res.status().as_u16() {
+                    401 | 403 => AuditOutcome::Denied,
+                    _ => AuditOutcome::Allowed,
+                };
+                // The endpoint may have recorded what the request acted on
+                let details = res
+                    .request()
+                    .extensions()
+                    .get::<AuditDetails>()
+                    .cloned()
+                    .unwrap_or_default();
+                record_audit_entry(
+                    &*store,
+                    InsertableAuditLogEntry::new(
+                        Some(identity),
+                        permission,
+                        method,
+                        route,
+                        outcome,
+                    )
+                    .with_details(details),
+                );
+            }
+
             res
         }))
     }
 }
+
+/// Adds an entry to the audit log. Failing to record an entry is logged, but does not affect the
+/// response to the request.
+#[cfg(feature = "rest-api-audit-log")]
+fn record_audit_entry(store: &dyn AuditLogStore, entry: InsertableAuditLogEntry) {
+    if let Err(err) = store.add_entry(entry) {
+        error!("Unable to record REST API audit log entry: {}", err);
+    }
+}
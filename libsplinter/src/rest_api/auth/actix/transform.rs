@@ -16,6 +16,8 @@ use actix_web::dev::*;
 use actix_web::Error as ActixError;
 use futures::future::{ok, FutureResult};
 
+#[cfg(feature = "rest-api-audit-log")]
+use crate::rest_api::audit::AuditLogStore;
 use crate::rest_api::auth::actix::AuthorizationMiddleware;
 #[cfg(feature = "authorization")]
 use crate::rest_api::auth::authorization::AuthorizationHandler;
@@ -27,6 +29,8 @@ pub struct Authorization {
     identity_providers: Vec<Box<dyn IdentityProvider>>,
     #[cfg(feature = "authorization")]
     authorization_handlers: Vec<Box<dyn AuthorizationHandler>>,
+    #[cfg(feature = "rest-api-audit-log")]
+    audit_log_store: Option<Box<dyn AuditLogStore>>,
 }
 
 impl Authorization {
@@ -40,8 +44,17 @@ impl Authorization {
             identity_providers,
             #[cfg(feature = "authorization")]
             authorization_handlers,
+            #[cfg(feature = "rest-api-audit-log")]
+            audit_log_store: None,
         }
     }
+
+    /// Records the requests that modify the node in the given audit log
+    #[cfg(feature = "rest-api-audit-log")]
+    pub fn with_audit_log_store(mut self, audit_log_store: Box<dyn AuditLogStore>) -> Self {
+        self.audit_log_store = Some(audit_log_store);
+        self
+    }
 }
 
 impl<S, B> Transform<S> for Authorization
@@ -62,6 +75,8 @@ where
             identity_providers: self.identity_providers.clone(),
             #[cfg(feature = "authorization")]
             authorization_handlers: self.authorization_handlers.clone(),
+            #[cfg(feature = "rest-api-audit-log")]
+            audit_log_store: self.audit_log_store.clone(),
             service,
         })
     }
@@ -82,7 +82,8 @@ impl AuthorizationHandler for MaintenanceModeAuthorizationHandler {
         permission_id: &str,
     ) -> Result<AuthorizationHandlerResult, InternalError> {
         if !permission_id.ends_with(".read") && self.maintenance_mode.load(Ordering::Relaxed) {
-            // Check if the client has the "admin" role, in which case they're not denied permission
+            // Check if the client has the "admin" role node-wide, in which case they're not denied
+            // permission
             #[cfg(feature = "authorization-handler-rbac")]
             {
                 let is_admin = self
@@ -90,13 +91,10 @@ impl AuthorizationHandler for MaintenanceModeAuthorizationHandler {
                     .as_ref()
                     .and_then(|store| {
                         let rbac_identity: Option<RBACIdentity> = identity.into();
+                        let assignment = store.get_assignment(&rbac_identity?).ok()??;
                         Some(
-                            store
-                                .get_assignment(&rbac_identity?)
-                                .ok()??
-                                .roles()
-                                .iter()
-                                .any(|role| role == ADMIN_ROLE_ID),
+                            assignment.scopes().is_empty()
+                                && assignment.roles().iter().any(|role| role == ADMIN_ROLE_ID),
                         )
                     })
                     .unwrap_or(false);
@@ -24,6 +24,8 @@ mod permission_map;
 #[cfg(feature = "authorization-handler-rbac")]
 pub mod rbac;
 pub(in crate::rest_api) mod routes;
+#[cfg(feature = "authorization-handler-rbac-scopes")]
+mod scope;
 
 use crate::error::InternalError;
 
@@ -32,6 +34,8 @@ use super::identity::Identity;
 pub use authorization_handler_result::AuthorizationHandlerResult;
 pub use permission::Permission;
 pub use permission_map::PermissionMap;
+#[cfg(feature = "authorization-handler-rbac-scopes")]
+pub use scope::{PayloadScopeAuthorization, RequestScope};
 
 #[cfg(test)]
 pub use permission_map::Method;
@@ -45,6 +49,21 @@ pub trait AuthorizationHandler: Send + Sync {
         permission_id: &str,
     ) -> Result<AuthorizationHandlerResult, InternalError>;
 
+    /// Determines if the given identity has the requested permission for a request that concerns
+    /// the given circuit.
+    ///
+    /// Handlers that do not support scoped permissions do not need to implement this; by default
+    /// the scope is ignored and [`AuthorizationHandler::has_permission`] is used.
+    #[cfg(feature = "authorization-handler-rbac-scopes")]
+    fn has_scoped_permission(
+        &self,
+        identity: &Identity,
+        permission_id: &str,
+        _scope: &RequestScope,
+    ) -> Result<AuthorizationHandlerResult, InternalError> {
+        self.has_permission(identity, permission_id)
+    }
+
     /// Clone implementation for `AuthorizationHandler`. The implementation of the `Clone` trait for
     /// `Box<dyn AuthorizationHandler>` calls this method.
     fn clone_box(&self) -> Box<dyn AuthorizationHandler>;
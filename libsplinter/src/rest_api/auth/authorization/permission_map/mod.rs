@@ -30,6 +30,8 @@ use request_definition::RequestDefinition;
 /// A map used to correlate requests with the permissions that guard them.
 pub struct PermissionMap<M> {
     internal: Vec<(RequestDefinition<M>, Permission)>,
+    #[cfg(feature = "authorization-handler-rbac-scopes")]
+    payload_scoped: Vec<RequestDefinition<M>>,
 }
 
 impl<M: PartialEq + Clone> Default for PermissionMap<M> {
@@ -43,6 +45,8 @@ impl<M: PartialEq + Clone> PermissionMap<M> {
     pub fn new() -> Self {
         Self {
             internal: Vec::new(),
+            #[cfg(feature = "authorization-handler-rbac-scopes")]
+            payload_scoped: Vec::new(),
         }
     }
 
@@ -54,7 +58,9 @@ impl<M: PartialEq + Clone> PermissionMap<M> {
     /// Takes the contents of another `PermissionMap` and merges them into itself. This consumes the
     /// contents of the other map.
     pub fn append(&mut self, other: &mut PermissionMap<M>) {
-        self.internal.append(&mut other.internal)
+        self.internal.append(&mut other.internal);
+        #[cfg(feature = "authorization-handler-rbac-scopes")]
+        self.payload_scoped.append(&mut other.payload_scoped);
     }
 
     /// Sets the permission for the given (method, endpoint) pair. The endpoint may contain path
@@ -75,6 +81,44 @@ impl<M: PartialEq + Clone> PermissionMap<M> {
             .find(|(req, _)| req.matches(method.borrow(), endpoint))
             .map(|(_, perm)| perm)
     }
+
+    /// Gets the value of the named path variable for a request. This will attempt to match the
+    /// method and endpoint to a known (method, endpoint) pair, and return the component of the
+    /// endpoint in the position of the variable.
+    pub fn get_path_variable<'a, O>(
+        &self,
+        method: &O,
+        endpoint: &'a str,
+        name: &str,
+    ) -> Option<&'a str>
+    where
+        O: Borrow<M>,
+    {
+        self.internal
+            .iter()
+            .find(|(req, _)| req.matches(method.borrow(), endpoint))
+            .and_then(|(req, _)| req.path_variable(endpoint, name))
+    }
+
+    /// Marks the given (method, endpoint) pair as one whose circuit scope is determined from the
+    /// request's payload, rather than from its path. The endpoint may contain path variables
+    /// surrounded by `{}`.
+    #[cfg(feature = "authorization-handler-rbac-scopes")]
+    pub fn add_payload_scoped_endpoint(&mut self, method: M, endpoint: &str) {
+        self.payload_scoped
+            .push(RequestDefinition::new(method, endpoint));
+    }
+
+    /// Determines if the circuit scope of a request is determined from the request's payload.
+    #[cfg(feature = "authorization-handler-rbac-scopes")]
+    pub fn is_payload_scoped<O>(&self, method: &O, endpoint: &str) -> bool
+    where
+        O: Borrow<M>,
+    {
+        self.payload_scoped
+            .iter()
+            .any(|req| req.matches(method.borrow(), endpoint))
+    }
 }
 
 #[cfg(test)]
@@ -88,14 +132,14 @@ mod tests {
     fn path_component_parse() {
         assert!(PathComponent::from("") == PathComponent::Text("".into()));
         assert!(PathComponent::from("test") == PathComponent::Text("test".into()));
-        assert!(PathComponent::from("{test}") == PathComponent::Variable);
+        assert!(PathComponent::from("{test}") == PathComponent::Variable("test".into()));
     }
 
     /// Verifies that a `PathComponent` can be correctly compared with a `&str`
     #[test]
     fn path_component_str_comparison() {
-        assert!(PathComponent::Variable == "test1");
-        assert!(PathComponent::Variable == "test2");
+        assert!(PathComponent::Variable("test".into()) == "test1");
+        assert!(PathComponent::Variable("test".into()) == "test2");
         assert!(PathComponent::Text("test1".into()) == "test1");
         assert!(PathComponent::Text("test1".into()) != "test2");
     }
@@ -169,4 +213,44 @@ mod tests {
             None
         );
     }
+
+    /// Verifies that the `PermissionMap` returns the values of named path variables
+    #[test]
+    fn permission_map_path_variable() {
+        let mut map = PermissionMap::new();
+        map.add_permission(
+            Actix1Method::Get,
+            "/test/{circuit_id}/endpoint/{service_id}",
+            Permission::AllowAuthenticated,
+        );
+
+        assert_eq!(
+            map.get_path_variable(
+                &Actix1Method::Get,
+                "/test/abcde/endpoint/a000",
+                "circuit_id"
+            ),
+            Some("abcde")
+        );
+        assert_eq!(
+            map.get_path_variable(
+                &Actix1Method::Get,
+                "/test/abcde/endpoint/a000",
+                "service_id"
+            ),
+            Some("a000")
+        );
+        assert_eq!(
+            map.get_path_variable(&Actix1Method::Get, "/test/abcde/endpoint/a000", "other"),
+            None
+        );
+        assert_eq!(
+            map.get_path_variable(
+                &Actix1Method::Put,
+                "/test/abcde/endpoint/a000",
+                "circuit_id"
+            ),
+            None
+        );
+    }
 }
@@ -17,14 +17,15 @@
 pub enum PathComponent {
     /// A standard path component where matching is done on the internal string
     Text(String),
-    /// A variable path component that matches any string
-    Variable,
+    /// A variable path component that matches any string; the internal string is the name of the
+    /// variable
+    Variable(String),
 }
 
 impl From<&str> for PathComponent {
     fn from(component: &str) -> Self {
         if component.starts_with('{') && component.ends_with('}') {
-            PathComponent::Variable
+            PathComponent::Variable(component[1..component.len() - 1].into())
         } else {
             PathComponent::Text(component.into())
         }
@@ -34,7 +35,7 @@ impl From<&str> for PathComponent {
 impl PartialEq<&str> for PathComponent {
     fn eq(&self, other: &&str) -> bool {
         match self {
-            PathComponent::Variable => true,
+            PathComponent::Variable(_) => true,
             PathComponent::Text(component) => other == component,
         }
     }
@@ -57,4 +57,18 @@ impl<M: PartialEq + Clone> RequestDefinition<M> {
                     .unwrap_or(false)
             })
     }
+
+    /// Gets the value of the path variable with the given name from an endpoint that matches this
+    /// definition.
+    pub fn path_variable<'a>(&self, endpoint: &'a str, name: &str) -> Option<&'a str> {
+        let idx = self.path.iter().position(|path_component| {
+            matches!(path_component, PathComponent::Variable(variable) if variable == name)
+        })?;
+
+        endpoint
+            .strip_prefix('/')
+            .unwrap_or(endpoint)
+            .split('/')
+            .nth(idx)
+    }
 }
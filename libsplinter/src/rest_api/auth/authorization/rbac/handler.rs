@@ -12,14 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "authorization-handler-rbac-scopes")]
+use crate::admin::store::AdminServiceStore;
 use crate::error::InternalError;
 
+#[cfg(feature = "authorization-handler-rbac-scopes")]
+use crate::rest_api::auth::authorization::RequestScope;
 use crate::rest_api::auth::{
     authorization::{AuthorizationHandler, AuthorizationHandlerResult},
     identity::Identity,
 };
 
-use crate::rbac::store::{RoleBasedAuthorizationStore, ADMIN_ROLE_ID};
+use crate::rbac::store::{AssignmentScope, RoleBasedAuthorizationStore, ADMIN_ROLE_ID};
 
 /// A Role-based authorization handler.
 ///
@@ -27,9 +31,14 @@ use crate::rbac::store::{RoleBasedAuthorizationStore, ADMIN_ROLE_ID};
 /// it has been assigned.  If one of the identity's assigned roles contains the permission, then
 /// the identity is allowed access. If not, the handler defers to the next handler in the chain.
 ///
+/// An assignment that is restricted to scopes only applies to requests concerning a circuit that
+/// matches one of its scopes; it never grants a permission node-wide.
+///
 /// It currently does not deny any permissions.
 pub struct RoleBasedAuthorizationHandler {
     role_based_auth_store: Box<dyn RoleBasedAuthorizationStore>,
+    #[cfg(feature = "authorization-handler-rbac-scopes")]
+    admin_store: Option<Box<dyn AdminServiceStore>>,
 }
 
 impl RoleBasedAuthorizationHandler {
@@ -37,7 +46,84 @@ impl RoleBasedAuthorizationHandler {
     pub fn new(role_based_auth_store: Box<dyn RoleBasedAuthorizationStore>) -> Self {
         Self {
             role_based_auth_store,
+            #[cfg(feature = "authorization-handler-rbac-scopes")]
+            admin_store: None,
+        }
+    }
+
+    /// Sets the admin service store used to look up the management type of a circuit or circuit
+    /// proposal, so that assignments scoped to a circuit management type can be applied to
+    /// requests that only identify the circuit.
+    #[cfg(feature = "authorization-handler-rbac-scopes")]
+    pub fn with_admin_store(mut self, admin_store: Box<dyn AdminServiceStore>) -> Self {
+        self.admin_store = Some(admin_store);
+        self
+    }
+
+    /// Checks the identity's assigned roles for the permission, if the identity's assignment
+    /// applies according to `applies`.
+    fn check_assigned_roles<F>(
+        &self,
+        identity: &Identity,
+        permission_id: &str,
+        applies: F,
+    ) -> Result<AuthorizationHandlerResult, InternalError>
+    where
+        F: Fn(&[AssignmentScope]) -> bool,
+    {
+        let identity = match identity.into() {
+            Some(identity) => identity,
+            None => return Ok(AuthorizationHandlerResult::Continue),
+        };
+
+        match self
+            .role_based_auth_store
+            .get_assignment(&identity)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?
+        {
+            Some(assignment) if applies(assignment.scopes()) => (),
+            _ => return Ok(AuthorizationHandlerResult::Continue),
+        }
+
+        Ok(self
+            .role_based_auth_store
+            .get_assigned_roles(&identity)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?
+            .find(|role| {
+                role.id() == ADMIN_ROLE_ID
+                    || role.permissions().iter().any(|perm| perm == permission_id)
+            })
+            .map(|_| AuthorizationHandlerResult::Allow)
+            .unwrap_or(AuthorizationHandlerResult::Continue))
+    }
+
+    /// Returns the management type of the circuit the request concerns, either as given by the
+    /// scope or, if an admin store was provided, by looking up the circuit or circuit proposal.
+    #[cfg(feature = "authorization-handler-rbac-scopes")]
+    fn circuit_management_type(
+        &self,
+        scope: &RequestScope,
+    ) -> Result<Option<String>, InternalError> {
+        if let Some(circuit_management_type) = scope.circuit_management_type() {
+            return Ok(Some(circuit_management_type.to_string()));
+        }
+
+        let (admin_store, circuit_id) = match (&self.admin_store, scope.circuit_id()) {
+            (Some(admin_store), Some(circuit_id)) => (admin_store, circuit_id),
+            _ => return Ok(None),
+        };
+
+        if let Some(circuit) = admin_store
+            .get_circuit(circuit_id)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?
+        {
+            return Ok(Some(circuit.circuit_management_type().to_string()));
         }
+
+        Ok(admin_store
+            .get_proposal(circuit_id)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?
+            .map(|proposal| proposal.circuit().circuit_management_type().to_string()))
     }
 }
 
@@ -47,24 +133,41 @@ impl AuthorizationHandler for RoleBasedAuthorizationHandler {
         identity: &Identity,
         permission_id: &str,
     ) -> Result<AuthorizationHandlerResult, InternalError> {
-        match identity.into() {
-            Some(identity) => Ok(self
-                .role_based_auth_store
-                .get_assigned_roles(&identity)
-                .map_err(|err| InternalError::from_source(Box::new(err)))?
-                .find(|role| {
-                    role.id() == ADMIN_ROLE_ID
-                        || role.permissions().iter().any(|perm| perm == permission_id)
-                })
-                .map(|_| AuthorizationHandlerResult::Allow)
-                .unwrap_or(AuthorizationHandlerResult::Continue)),
-            None => Ok(AuthorizationHandlerResult::Continue),
-        }
+        self.check_assigned_roles(identity, permission_id, |scopes| scopes.is_empty())
+    }
+
+    #[cfg(feature = "authorization-handler-rbac-scopes")]
+    fn has_scoped_permission(
+        &self,
+        identity: &Identity,
+        permission_id: &str,
+        scope: &RequestScope,
+    ) -> Result<AuthorizationHandlerResult, InternalError> {
+        let circuit_management_type = self.circuit_management_type(scope)?;
+
+        self.check_assigned_roles(identity, permission_id, |scopes| {
+            scopes.is_empty()
+                || scopes
+                    .iter()
+                    .any(|assignment_scope| match assignment_scope {
+                        AssignmentScope::Circuit(circuit_id) => {
+                            scope.circuit_id() == Some(circuit_id.as_str())
+                        }
+                        AssignmentScope::CircuitManagementType(management_type) => {
+                            circuit_management_type.as_deref() == Some(management_type.as_str())
+                        }
+                    })
+        })
     }
 
     fn clone_box(&self) -> Box<dyn AuthorizationHandler> {
         Box::new(RoleBasedAuthorizationHandler {
             role_based_auth_store: self.role_based_auth_store.clone_box(),
+            #[cfg(feature = "authorization-handler-rbac-scopes")]
+            admin_store: self
+                .admin_store
+                .as_ref()
+                .map(|admin_store| admin_store.clone_boxed()),
         })
     }
 }
@@ -291,6 +394,108 @@ mod tests {
         assert!(matches!(result, AuthorizationHandlerResult::Continue));
     }
 
+    /// This test checks that an assignment restricted to a scope does not grant its permissions
+    /// node-wide.
+    #[test]
+    fn continue_scoped_assignment_without_scope() {
+        let role_based_auth_store = create_scoped_assignment_store();
+
+        let handler = RoleBasedAuthorizationHandler::new(role_based_auth_store);
+        let result = handler
+            .has_permission(&Identity::User("some-user-id".into()), "a")
+            .expect("Should have returned an auth result");
+
+        assert!(matches!(result, AuthorizationHandlerResult::Continue));
+    }
+
+    /// This test checks that an assignment restricted to scopes only grants its permissions for
+    /// requests concerning a circuit that matches one of the scopes.
+    #[cfg(feature = "authorization-handler-rbac-scopes")]
+    #[test]
+    fn allow_scoped_assignment_in_scope() {
+        let role_based_auth_store = create_scoped_assignment_store();
+
+        let handler = RoleBasedAuthorizationHandler::new(role_based_auth_store);
+        let identity = Identity::User("some-user-id".into());
+
+        // The circuit matches the circuit scope
+        let result = handler
+            .has_scoped_permission(
+                &identity,
+                "a",
+                &RequestScope::new().with_circuit_id("abcde-01234"),
+            )
+            .expect("Should have returned an auth result");
+        assert!(matches!(result, AuthorizationHandlerResult::Allow));
+
+        // The circuit's management type matches the management type scope
+        let result = handler
+            .has_scoped_permission(
+                &identity,
+                "a",
+                &RequestScope::new()
+                    .with_circuit_id("fghij-56789")
+                    .with_circuit_management_type("gameroom"),
+            )
+            .expect("Should have returned an auth result");
+        assert!(matches!(result, AuthorizationHandlerResult::Allow));
+
+        // The circuit does not match any scope
+        let result = handler
+            .has_scoped_permission(
+                &identity,
+                "a",
+                &RequestScope::new()
+                    .with_circuit_id("fghij-56789")
+                    .with_circuit_management_type("other"),
+            )
+            .expect("Should have returned an auth result");
+        assert!(matches!(result, AuthorizationHandlerResult::Continue));
+
+        // The permission is not in the assigned role
+        let result = handler
+            .has_scoped_permission(
+                &identity,
+                "x",
+                &RequestScope::new().with_circuit_id("abcde-01234"),
+            )
+            .expect("Should have returned an auth result");
+        assert!(matches!(result, AuthorizationHandlerResult::Continue));
+    }
+
+    /// Creates a RoleBasedAuthorizationStore with a role that has the permission "a", assigned to
+    /// the user "some-user-id" for the circuit "abcde-01234" and the management type "gameroom".
+    fn create_scoped_assignment_store() -> Box<dyn RoleBasedAuthorizationStore> {
+        let role_based_auth_store = create_role_based_authorization_store();
+
+        let role = RoleBuilder::new()
+            .with_id("test-role".into())
+            .with_display_name("Test Role".into())
+            .with_permissions(vec!["a".to_string()])
+            .build()
+            .expect("Unable to build role");
+
+        role_based_auth_store
+            .add_role(role)
+            .expect("Unable to add role");
+
+        let assignment = AssignmentBuilder::new()
+            .with_identity(StoreIdentity::User("some-user-id".into()))
+            .with_roles(vec!["test-role".to_string()])
+            .with_scopes(vec![
+                AssignmentScope::Circuit("abcde-01234".into()),
+                AssignmentScope::CircuitManagementType("gameroom".into()),
+            ])
+            .build()
+            .expect("Unable to build assignment");
+
+        role_based_auth_store
+            .add_assignment(assignment)
+            .expect("Unable to add assignment");
+
+        role_based_auth_store
+    }
+
     /// Creates a RoleBasedAuthorizationStore
     fn create_role_based_authorization_store() -> Box<dyn RoleBasedAuthorizationStore> {
         let pool = create_connection_pool_and_migrate();
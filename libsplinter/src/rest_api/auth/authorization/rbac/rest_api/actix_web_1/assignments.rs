@@ -24,7 +24,7 @@ use crate::rest_api::{
     auth::authorization::rbac::rest_api::{
         resources::{
             assignments::{
-                AssignmentPayload, AssignmentResponse, AssignmentUpdatePayload,
+                parse_scopes, AssignmentPayload, AssignmentResponse, AssignmentUpdatePayload,
                 ListAssignmentsResponse,
             },
             PagingQuery,
@@ -310,16 +310,24 @@ fn patch_assignment(
 fn update_assignment(
     role_based_auth_store: &dyn RoleBasedAuthorizationStore,
     identity: &Identity,
-    AssignmentUpdatePayload { roles }: AssignmentUpdatePayload,
+    AssignmentUpdatePayload { roles, scopes }: AssignmentUpdatePayload,
 ) -> Result<(), SendableRoleBasedAuthorizationStoreError> {
     role_based_auth_store
         .get_assignment(identity)
         .map_err(SendableRoleBasedAuthorizationStoreError::from)
         .and_then(|assignment_opt| {
             if let Some(assignment) = assignment_opt {
-                let updated_assignment = assignment
-                    .into_update_builder()
-                    .with_roles(roles)
+                let builder = assignment.into_update_builder().with_roles(roles);
+
+                let builder = match scopes {
+                    Some(scopes) => builder.with_scopes(
+                        parse_scopes(scopes)
+                            .map_err(SendableRoleBasedAuthorizationStoreError::InvalidState)?,
+                    ),
+                    None => builder,
+                };
+
+                let updated_assignment = builder
                     .build()
                     .map_err(SendableRoleBasedAuthorizationStoreError::InvalidState)?;
 
@@ -14,8 +14,8 @@
 
 use std::convert::TryFrom;
 
-use crate::error::InvalidStateError;
-use crate::rbac::store::{Assignment, AssignmentBuilder, Identity};
+use crate::error::{InvalidArgumentError, InvalidStateError};
+use crate::rbac::store::{Assignment, AssignmentBuilder, AssignmentScope, Identity};
 use crate::rest_api::paging::Paging;
 
 #[derive(Serialize)]
@@ -29,6 +29,8 @@ pub struct AssignmentResponse<'a> {
     #[serde(flatten)]
     identity: IdentityResponse<'a>,
     roles: &'a [String],
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    scopes: Vec<String>,
 }
 
 #[derive(Serialize)]
@@ -44,6 +46,11 @@ impl<'a> From<&'a Assignment> for AssignmentResponse<'a> {
         Self {
             identity: assignment.identity().into(),
             roles: assignment.roles(),
+            scopes: assignment
+                .scopes()
+                .iter()
+                .map(|scope| scope.to_string())
+                .collect(),
         }
     }
 }
@@ -62,6 +69,8 @@ pub struct AssignmentPayload {
     #[serde(flatten)]
     identity: IdentityPayload,
     roles: Vec<String>,
+    #[serde(default)]
+    scopes: Vec<String>,
 }
 
 #[derive(Deserialize)]
@@ -76,7 +85,11 @@ impl TryFrom<AssignmentPayload> for Assignment {
     type Error = InvalidStateError;
 
     fn try_from(
-        AssignmentPayload { identity, roles }: AssignmentPayload,
+        AssignmentPayload {
+            identity,
+            roles,
+            scopes,
+        }: AssignmentPayload,
     ) -> Result<Self, Self::Error> {
         AssignmentBuilder::new()
             .with_identity(match identity {
@@ -84,6 +97,7 @@ impl TryFrom<AssignmentPayload> for Assignment {
                 IdentityPayload::User(user) => Identity::User(user),
             })
             .with_roles(roles)
+            .with_scopes(parse_scopes(scopes)?)
             .build()
     }
 }
@@ -91,4 +105,19 @@ impl TryFrom<AssignmentPayload> for Assignment {
 #[derive(Deserialize)]
 pub struct AssignmentUpdatePayload {
     pub roles: Vec<String>,
+    /// The updated scopes; if not provided, the assignment's scopes are left unchanged
+    #[serde(default)]
+    pub scopes: Option<Vec<String>>,
+}
+
+/// Parses scopes from their string form, `circuit:<circuit_id>` or `management-type:<type>`.
+pub fn parse_scopes(scopes: Vec<String>) -> Result<Vec<AssignmentScope>, InvalidStateError> {
+    scopes
+        .iter()
+        .map(|scope| {
+            scope.parse().map_err(|err: InvalidArgumentError| {
+                InvalidStateError::with_message(err.to_string())
+            })
+        })
+        .collect()
 }
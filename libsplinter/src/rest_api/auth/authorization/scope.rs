@@ -0,0 +1,184 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::admin::messages::circuit_management_action;
+use crate::error::InvalidArgumentError;
+use crate::protos::admin::{CircuitManagementPayload, CircuitManagementPayload_Action};
+use crate::rest_api::auth::identity::Identity;
+
+use super::{AuthorizationHandler, AuthorizationHandlerResult};
+
+/// The circuit that a request concerns, used to check permissions that may be restricted to
+/// particular circuits.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RequestScope {
+    circuit_id: Option<String>,
+    circuit_management_type: Option<String>,
+}
+
+impl RequestScope {
+    /// Creates a new, empty request scope
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the ID of the circuit the request concerns
+    pub fn with_circuit_id(mut self, circuit_id: &str) -> Self {
+        self.circuit_id = Some(circuit_id.into());
+        self
+    }
+
+    /// Sets the management type of the circuit the request concerns
+    pub fn with_circuit_management_type(mut self, circuit_management_type: &str) -> Self {
+        self.circuit_management_type = Some(circuit_management_type.into());
+        self
+    }
+
+    /// Returns the ID of the circuit the request concerns, if known
+    pub fn circuit_id(&self) -> Option<&str> {
+        self.circuit_id.as_deref()
+    }
+
+    /// Returns the management type of the circuit the request concerns, if known
+    pub fn circuit_management_type(&self) -> Option<&str> {
+        self.circuit_management_type.as_deref()
+    }
+
+    /// Determines the scope of a circuit management payload from the circuit its action
+    /// concerns. The circuit management type is only known for circuit create and join requests,
+    /// which include the circuit definition.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidArgumentError`] if the payload's header cannot be parsed or does not
+    /// specify an action.
+    pub fn from_circuit_management_payload(
+        payload: &CircuitManagementPayload,
+    ) -> Result<Self, InvalidArgumentError> {
+        let (action, circuit_id) = circuit_management_action(payload)?;
+
+        let scope = RequestScope::new().with_circuit_id(circuit_id);
+        let scope = match action {
+            CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST => scope
+                .with_circuit_management_type(
+                    payload
+                        .get_circuit_create_request()
+                        .get_circuit()
+                        .get_circuit_management_type(),
+                ),
+            CircuitManagementPayload_Action::CIRCUIT_JOIN_REQUEST => scope
+                .with_circuit_management_type(
+                    payload
+                        .get_circuit_join_request()
+                        .get_circuit()
+                        .get_circuit_management_type(),
+                ),
+            _ => scope,
+        };
+
+        Ok(scope)
+    }
+}
+
+/// A permission check that must be completed by an endpoint once it has read its payload.
+///
+/// This is added to the request's extensions when the request is for an endpoint whose circuit
+/// scope is determined from its payload, and the client's identity has not been granted the
+/// endpoint's permission node-wide. The endpoint must call [`PayloadScopeAuthorization::authorize`]
+/// with the scope of the payload and reject the request if it returns `false`.
+#[derive(Clone)]
+pub struct PayloadScopeAuthorization {
+    identity: Identity,
+    permission_id: &'static str,
+    authorization_handlers: Vec<Box<dyn AuthorizationHandler>>,
+}
+
+impl PayloadScopeAuthorization {
+    /// Creates the permission check for the given identity and permission. This is used by the
+    /// REST API backends' authorization middleware.
+    pub fn new(
+        identity: Identity,
+        permission_id: &'static str,
+        authorization_handlers: Vec<Box<dyn AuthorizationHandler>>,
+    ) -> Self {
+        Self {
+            identity,
+            permission_id,
+            authorization_handlers,
+        }
+    }
+
+    /// Determines if the client has the endpoint's permission for the given scope.
+    pub fn authorize(&self, scope: &RequestScope) -> bool {
+        for handler in &self.authorization_handlers {
+            match handler.has_scoped_permission(&self.identity, self.permission_id, scope) {
+                Ok(AuthorizationHandlerResult::Allow) => return true,
+                Ok(AuthorizationHandlerResult::Deny) => return false,
+                Ok(AuthorizationHandlerResult::Continue) => {}
+                Err(err) => error!("{}", err),
+            }
+        }
+        // No handler allowed the request, so deny by default
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::protos::admin::{Circuit, CircuitCreateRequest, CircuitProposalVote};
+
+    /// Verifies that the scope of a circuit management payload is determined from the action in
+    /// its header, not from whichever requests happen to be set in the payload.
+    #[test]
+    fn scope_from_circuit_management_payload() {
+        let mut circuit = Circuit::new();
+        circuit.set_circuit_id("abcde-01234".into());
+        circuit.set_circuit_management_type("gameroom".into());
+        let mut create_request = CircuitCreateRequest::new();
+        create_request.set_circuit(circuit);
+
+        let mut vote = CircuitProposalVote::new();
+        vote.set_circuit_id("fghij-56789".into());
+
+        let mut payload = CircuitManagementPayload::new();
+        payload.set_circuit_create_request(create_request);
+        payload.set_circuit_proposal_vote(vote);
+
+        let mut header = CircuitManagementPayload_Header::new();
+        header.set_action(CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST);
+        payload.set_header(header.write_to_bytes().expect("Unable to serialize header"));
+
+        assert_eq!(
+            RequestScope::from_circuit_management_payload(&payload).expect("Unable to get scope"),
+            RequestScope::new()
+                .with_circuit_id("abcde-01234")
+                .with_circuit_management_type("gameroom")
+        );
+
+        header.set_action(CircuitManagementPayload_Action::CIRCUIT_PROPOSAL_VOTE);
+        payload.set_header(header.write_to_bytes().expect("Unable to serialize header"));
+
+        assert_eq!(
+            RequestScope::from_circuit_management_payload(&payload).expect("Unable to get scope"),
+            RequestScope::new().with_circuit_id("fghij-56789")
+        );
+
+        header.set_action(CircuitManagementPayload_Action::ACTION_UNSET);
+        payload.set_header(header.write_to_bytes().expect("Unable to serialize header"));
+
+        assert!(RequestScope::from_circuit_management_payload(&payload).is_err());
+    }
+}
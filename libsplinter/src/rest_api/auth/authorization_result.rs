@@ -30,4 +30,8 @@ pub enum AuthorizationResult {
     /// The request endpoint is not defined
     #[cfg(feature = "authorization")]
     UnknownEndpoint,
+    /// The client was authenticated to the given identity, but whether it has the given
+    /// permission depends on the circuit the request's payload concerns
+    #[cfg(feature = "authorization-handler-rbac-scopes")]
+    PayloadScopeRequired(Identity, &'static str),
 }
@@ -21,12 +21,14 @@ pub mod cylinder;
 #[cfg(feature = "oauth")]
 pub mod oauth;
 
+use std::fmt;
+
 use crate::error::InternalError;
 
 use super::AuthorizationHeader;
 
 /// A REST API client's identity as determined by an [IdentityProvider]
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Identity {
     /// A custom identity
     Custom(String),
@@ -36,6 +38,16 @@ pub enum Identity {
     User(String),
 }
 
+impl fmt::Display for Identity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Identity::Custom(custom) => write!(f, "custom:{}", custom),
+            Identity::Key(key) => write!(f, "key:{}", key),
+            Identity::User(user_id) => write!(f, "user:{}", user_id),
+        }
+    }
+}
+
 /// A service that fetches identities from a backing provider
 pub trait IdentityProvider: Send + Sync {
     /// Attempts to get the identity that corresponds to the given authorization header. This method
@@ -18,14 +18,12 @@
 pub(crate) mod actix;
 #[cfg(feature = "authorization")]
 pub mod authorization;
-#[cfg(feature = "rest-api-actix-web-1")]
 mod authorization_header;
 #[cfg(feature = "rest-api-actix-web-1")]
 mod authorization_result;
 mod bearer_token;
 pub mod identity;
 
-#[cfg(feature = "rest-api-actix-web-1")]
 pub use authorization_header::AuthorizationHeader;
 #[cfg(feature = "rest-api-actix-web-1")]
 pub use authorization_result::AuthorizationResult;
@@ -36,11 +34,17 @@ use crate::error::InvalidArgumentError;
 #[cfg(feature = "authorization")]
 use super::Method;
 
+#[cfg(feature = "authorization-handler-rbac-scopes")]
+use authorization::RequestScope;
 #[cfg(feature = "authorization")]
 use authorization::{AuthorizationHandler, AuthorizationHandlerResult, Permission, PermissionMap};
 #[cfg(feature = "rest-api-actix-web-1")]
 use identity::{Identity, IdentityProvider};
 
+/// The names of the path variables that identify the circuit a request concerns
+#[cfg(feature = "authorization-handler-rbac-scopes")]
+const CIRCUIT_PATH_VARIABLES: &[&str] = &["circuit_id", "circuit"];
+
 /// Uses the given identity providers to check authorization for the request. This function is
 /// backend-agnostic and intended as a helper for the backend REST API implementations.
 ///
@@ -89,8 +93,28 @@ fn authorize(
             Permission::Check { permission_id, .. } => {
                 match get_identity(auth_header, identity_providers) {
                     Some(identity) => {
+                        // If the request concerns a particular circuit, permissions that are
+                        // restricted to that circuit are also considered
+                        #[cfg(feature = "authorization-handler-rbac-scopes")]
+                        let scope = CIRCUIT_PATH_VARIABLES
+                            .iter()
+                            .find_map(|name| {
+                                permission_map.get_path_variable(method, endpoint, name)
+                            })
+                            .map(|circuit_id| RequestScope::new().with_circuit_id(circuit_id));
+
                         for handler in authorization_handlers {
-                            match handler.has_permission(&identity, permission_id) {
+                            #[cfg(feature = "authorization-handler-rbac-scopes")]
+                            let result = match scope {
+                                Some(ref scope) => {
+                                    handler.has_scoped_permission(&identity, permission_id, scope)
+                                }
+                                None => handler.has_permission(&identity, permission_id),
+                            };
+                            #[cfg(not(feature = "authorization-handler-rbac-scopes"))]
+                            let result = handler.has_permission(&identity, permission_id);
+
+                            match result {
                                 Ok(AuthorizationHandlerResult::Allow) => {
                                     return AuthorizationResult::Authorized(identity)
                                 }
@@ -101,6 +125,17 @@ fn authorize(
                                 Err(err) => error!("{}", err),
                             }
                         }
+
+                        // The scope of some requests can only be determined from their payload,
+                        // so the check is completed by the endpoint
+                        #[cfg(feature = "authorization-handler-rbac-scopes")]
+                        if scope.is_none() && permission_map.is_payload_scoped(method, endpoint) {
+                            return AuthorizationResult::PayloadScopeRequired(
+                                identity,
+                                permission_id,
+                            );
+                        }
+
                         // No handler allowed the request, so deny by default
                         AuthorizationResult::Unauthorized
                     }
@@ -69,6 +69,8 @@
 
 #[cfg(feature = "rest-api-actix-web-1")]
 pub mod actix_web_1;
+#[cfg(feature = "rest-api-audit-log")]
+pub mod audit;
 pub mod auth;
 mod bind_config;
 #[cfg(feature = "rest-api-cors")]
@@ -93,6 +95,12 @@ pub use oauth_config::OAuthConfig;
 
 pub use response_models::ErrorResponse;
 
+#[cfg(all(
+    feature = "rest-api-actix-web-1",
+    feature = "https-bind",
+    feature = "tls-reload"
+))]
+pub use actix_web_1::RestApiTlsReloader;
 #[cfg(feature = "rest-api-actix-web-1")]
 pub use actix_web_1::{
     get_authorization_token, into_bytes, into_protobuf, new_websocket_event_sender, require_header,
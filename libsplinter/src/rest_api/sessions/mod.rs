@@ -19,7 +19,7 @@ mod error;
 mod token_issuer;
 
 #[cfg(feature = "biome-credentials")]
-use jsonwebtoken::Validation;
+use jsonwebtoken::{decode, DecodingKey, Validation};
 use serde::Serialize;
 
 pub use claims::{Claims, ClaimsBuilder};
@@ -38,8 +38,9 @@ pub trait TokenIssuer<T: Serialize> {
     fn issue_refresh_token_with_claims(&self, claims: T) -> Result<String, TokenIssuerError>;
 }
 
+/// Validates authorization tokens issued by the given issuer
 #[cfg(feature = "biome-credentials")]
-pub(crate) fn default_validation(issuer: &str) -> Validation {
+pub fn default_validation(issuer: &str) -> Validation {
     Validation {
         leeway: DEFAULT_LEEWAY,
         iss: Some(issuer.to_string()),
@@ -49,7 +50,7 @@ pub(crate) fn default_validation(issuer: &str) -> Validation {
 
 /// Validates authorization token but ignores the expiration date
 #[cfg(feature = "biome-credentials")]
-pub(crate) fn ignore_exp_validation(issuer: &str) -> Validation {
+pub fn ignore_exp_validation(issuer: &str) -> Validation {
     Validation {
         leeway: DEFAULT_LEEWAY,
         iss: Some(issuer.to_string()),
@@ -57,3 +58,18 @@ pub(crate) fn ignore_exp_validation(issuer: &str) -> Validation {
         ..Default::default()
     }
 }
+
+/// Decodes a JWT signed with the given secret and returns its claims if it passes validation
+#[cfg(feature = "biome-credentials")]
+pub fn validate_token(
+    token: &str,
+    secret: &str,
+    validation: &Validation,
+) -> Result<Claims, TokenValidationError> {
+    Ok(decode::<Claims>(
+        token,
+        &DecodingKey::from_secret(secret.as_ref()),
+        validation,
+    )?
+    .claims)
+}
@@ -156,6 +156,13 @@ impl ServiceOrchestrator {
             })
             .ok_or(InitializeServiceError::UnknownType)?;
 
+        // A previously stopped instance of the service, such as one on a circuit that has been
+        // resumed, is replaced by the new instance.
+        self.stopped_services
+            .lock()
+            .map_err(|_| InitializeServiceError::LockPoisoned)?
+            .remove(&service_definition);
+
         // Create the service.
         let mut service = factory.create_orchestratable_service(
             service_definition.service_id.clone(),
@@ -336,7 +343,7 @@ impl ServiceOrchestrator {
         &self.service_factories
     }
 
-    #[cfg(feature = "rest-api-actix-web-1")]
+    #[cfg(feature = "rest-api")]
     pub fn services(&self) -> Arc<Mutex<HashMap<ServiceDefinition, ManagedService>>> {
         self.services.clone()
     }
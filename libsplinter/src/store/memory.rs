@@ -172,4 +172,18 @@ impl StoreFactory for MemoryStoreFactory {
             self.pool.clone(),
         ))
     }
+
+    #[cfg(feature = "circuit-guaranteed-delivery")]
+    fn get_pending_message_store(&self) -> Box<dyn crate::circuit::delivery::PendingMessageStore> {
+        Box::new(crate::circuit::delivery::DieselPendingMessageStore::new(
+            self.pool.clone(),
+        ))
+    }
+
+    #[cfg(feature = "rest-api-audit-log")]
+    fn get_audit_log_store(&self) -> Box<dyn crate::rest_api::audit::AuditLogStore> {
+        Box::new(crate::rest_api::audit::DieselAuditLogStore::new(
+            self.pool.clone(),
+        ))
+    }
 }
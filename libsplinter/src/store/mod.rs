@@ -67,4 +67,12 @@ pub trait StoreFactory {
 
     #[cfg(feature = "service-lifecycle-store")]
     fn get_lifecycle_store(&self) -> Box<dyn crate::runtime::service::LifecycleStore + Send>;
+
+    /// Get a new `PendingMessageStore`
+    #[cfg(feature = "circuit-guaranteed-delivery")]
+    fn get_pending_message_store(&self) -> Box<dyn crate::circuit::delivery::PendingMessageStore>;
+
+    /// Get a new `AuditLogStore`
+    #[cfg(feature = "rest-api-audit-log")]
+    fn get_audit_log_store(&self) -> Box<dyn crate::rest_api::audit::AuditLogStore>;
 }
@@ -197,6 +197,24 @@ impl StoreFactory for SqliteStoreFactory {
             ),
         )
     }
+
+    #[cfg(feature = "circuit-guaranteed-delivery")]
+    fn get_pending_message_store(&self) -> Box<dyn crate::circuit::delivery::PendingMessageStore> {
+        Box::new(
+            crate::circuit::delivery::DieselPendingMessageStore::new_with_write_exclusivity(
+                self.pool.clone(),
+            ),
+        )
+    }
+
+    #[cfg(feature = "rest-api-audit-log")]
+    fn get_audit_log_store(&self) -> Box<dyn crate::rest_api::audit::AuditLogStore> {
+        Box::new(
+            crate::rest_api::audit::DieselAuditLogStore::new_with_write_exclusivity(
+                self.pool.clone(),
+            ),
+        )
+    }
 }
 
 #[derive(Default, Debug)]
@@ -16,6 +16,8 @@
 //!
 //! Includes a default no-op implementation.
 //! The `metrics` feature turns an implementation for sending metrics to an InfluxDB instance.
+//! The `tap-prometheus` feature adds an implementation for exposing metrics to a Prometheus
+//! server.
 //!
 //! The following macros are available:
 //! - `counter`: Increments a counter.
@@ -24,6 +26,8 @@
 
 #[cfg(feature = "tap")]
 pub mod influx;
+#[cfg(feature = "tap-prometheus")]
+pub mod prometheus;
 
 /// no-op `counter` macro for when the `metrics` feature is not enabled
 #[cfg(not(feature = "tap"))]
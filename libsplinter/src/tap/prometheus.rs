@@ -0,0 +1,478 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Contains a Prometheus specific implementation of the
+//! [metrics::Recorder](https://docs.rs/metrics/0.17.0/metrics/trait.Recorder.html) trait.
+//! PrometheusRecorder keeps the current value of each metric in memory so it can be rendered in
+//! the Prometheus text exposition format, for example by a `/metrics` REST API endpoint that is
+//! scraped by a Prometheus server.
+//!
+//! Available if the `tap-prometheus` feature is enabled
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+
+use metrics::{GaugeValue, Key, Recorder, Unit};
+
+use crate::error::{InternalError, InvalidArgumentError};
+
+/// The default upper bounds of the histogram buckets; these match the defaults used by the
+/// official Prometheus client libraries.
+pub const DEFAULT_HISTOGRAM_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// The content type of the Prometheus text exposition format returned by
+/// [`PrometheusHandle::render`]
+pub const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+type Labels = Vec<(String, String)>;
+
+struct Histogram {
+    // Cumulative count of the observations that are less than or equal to each bucket bound
+    bucket_counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn new(buckets: &[f64]) -> Self {
+        Self {
+            bucket_counts: vec![0; buckets.len()],
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    fn observe(&mut self, buckets: &[f64], value: f64) {
+        for (bound, bucket_count) in buckets.iter().zip(self.bucket_counts.iter_mut()) {
+            if value <= *bound {
+                *bucket_count += 1;
+            }
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+}
+
+#[derive(Default)]
+struct Metrics {
+    descriptions: HashMap<String, &'static str>,
+    counters: BTreeMap<String, BTreeMap<Labels, u64>>,
+    gauges: BTreeMap<String, BTreeMap<Labels, f64>>,
+    histograms: BTreeMap<String, BTreeMap<Labels, Histogram>>,
+}
+
+/// Enables using the metrics macros and exposing the metrics data in the Prometheus text
+/// exposition format
+pub struct PrometheusRecorder {
+    metrics: Arc<Mutex<Metrics>>,
+    buckets: Arc<Vec<f64>>,
+}
+
+impl PrometheusRecorder {
+    /// Creates a new PrometheusRecorder.
+    ///
+    /// # Arguments
+    ///
+    /// * `buckets` - The upper bounds of the buckets used for histograms; these must be finite
+    ///   and in strictly increasing order.
+    pub fn new(buckets: Vec<f64>) -> Result<Self, InvalidArgumentError> {
+        if buckets.is_empty() {
+            return Err(InvalidArgumentError::new(
+                "buckets",
+                "at least one histogram bucket is required",
+            ));
+        }
+
+        if buckets.iter().any(|bound| !bound.is_finite()) {
+            return Err(InvalidArgumentError::new(
+                "buckets",
+                "histogram buckets must be finite",
+            ));
+        }
+
+        if buckets.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err(InvalidArgumentError::new(
+                "buckets",
+                "histogram buckets must be in strictly increasing order",
+            ));
+        }
+
+        Ok(Self {
+            metrics: Arc::new(Mutex::new(Metrics::default())),
+            buckets: Arc::new(buckets),
+        })
+    }
+
+    /// Returns a handle that can be used to render the metrics recorded by this recorder.
+    pub fn handle(&self) -> PrometheusHandle {
+        PrometheusHandle {
+            metrics: self.metrics.clone(),
+            buckets: self.buckets.clone(),
+        }
+    }
+
+    /// Initialize metric collection by creating the PrometheusRecorder and adding it to the
+    /// metrics library as the recorder. The returned handle is used to render the collected
+    /// metrics.
+    ///
+    /// # Arguments
+    ///
+    /// * `buckets` - The upper bounds of the buckets used for histograms. If `None`, the
+    ///   [`DEFAULT_HISTOGRAM_BUCKETS`] are used.
+    pub fn init(buckets: Option<Vec<f64>>) -> Result<PrometheusHandle, InternalError> {
+        let recorder = Self::new(buckets.unwrap_or_else(|| DEFAULT_HISTOGRAM_BUCKETS.to_vec()))
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+        let handle = recorder.handle();
+        metrics::set_boxed_recorder(Box::new(recorder))
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+        Ok(handle)
+    }
+
+    fn with_metrics<F>(&self, f: F)
+    where
+        F: FnOnce(&mut Metrics),
+    {
+        match self.metrics.lock() {
+            Ok(mut metrics) => f(&mut metrics),
+            Err(_) => error!("Unable to record metric, PrometheusRecorder lock was poisoned"),
+        }
+    }
+
+    fn register_description(&self, key: &Key, description: Option<&'static str>) {
+        if let Some(description) = description {
+            let (name, _) = split_key(key);
+            self.with_metrics(|metrics| {
+                metrics.descriptions.insert(name, description);
+            });
+        }
+    }
+}
+
+impl Recorder for PrometheusRecorder {
+    fn increment_counter(&self, key: &Key, value: u64) {
+        let (name, labels) = split_key(key);
+        self.with_metrics(|metrics| {
+            *metrics
+                .counters
+                .entry(name)
+                .or_default()
+                .entry(labels)
+                .or_default() += value;
+        });
+    }
+
+    fn update_gauge(&self, key: &Key, value: GaugeValue) {
+        let (name, labels) = split_key(key);
+        self.with_metrics(|metrics| {
+            let gauge = metrics
+                .gauges
+                .entry(name)
+                .or_default()
+                .entry(labels)
+                .or_default();
+            match value {
+                GaugeValue::Absolute(total) => *gauge = total,
+                GaugeValue::Increment(amount) => *gauge += amount,
+                GaugeValue::Decrement(amount) => *gauge -= amount,
+            }
+        });
+    }
+
+    fn record_histogram(&self, key: &Key, value: f64) {
+        let (name, labels) = split_key(key);
+        let buckets = &self.buckets;
+        self.with_metrics(|metrics| {
+            metrics
+                .histograms
+                .entry(name)
+                .or_default()
+                .entry(labels)
+                .or_insert_with(|| Histogram::new(buckets))
+                .observe(buckets, value);
+        });
+    }
+
+    fn register_counter(&self, key: &Key, _unit: Option<Unit>, description: Option<&'static str>) {
+        self.register_description(key, description);
+        self.increment_counter(key, 0);
+    }
+
+    fn register_gauge(&self, key: &Key, _unit: Option<Unit>, description: Option<&'static str>) {
+        self.register_description(key, description);
+        let (name, labels) = split_key(key);
+        self.with_metrics(|metrics| {
+            metrics
+                .gauges
+                .entry(name)
+                .or_default()
+                .entry(labels)
+                .or_default();
+        });
+    }
+
+    fn register_histogram(
+        &self,
+        key: &Key,
+        _unit: Option<Unit>,
+        description: Option<&'static str>,
+    ) {
+        self.register_description(key, description);
+        let (name, labels) = split_key(key);
+        let buckets = &self.buckets;
+        self.with_metrics(|metrics| {
+            metrics
+                .histograms
+                .entry(name)
+                .or_default()
+                .entry(labels)
+                .or_insert_with(|| Histogram::new(buckets));
+        });
+    }
+}
+
+/// Renders the metrics collected by a [`PrometheusRecorder`]
+#[derive(Clone)]
+pub struct PrometheusHandle {
+    metrics: Arc<Mutex<Metrics>>,
+    buckets: Arc<Vec<f64>>,
+}
+
+impl PrometheusHandle {
+    /// Returns the current value of all metrics in the Prometheus text exposition format.
+    pub fn render(&self) -> Result<String, InternalError> {
+        let metrics = self.metrics.lock().map_err(|_| {
+            InternalError::with_message("PrometheusRecorder lock was poisoned".into())
+        })?;
+
+        let mut output = String::new();
+
+        for (name, counters) in &metrics.counters {
+            write_header(&mut output, name, "counter", &metrics.descriptions);
+            for (labels, value) in counters {
+                output.push_str(&format!(
+                    "{}{} {}\n",
+                    name,
+                    format_labels(labels, None),
+                    value
+                ));
+            }
+        }
+
+        for (name, gauges) in &metrics.gauges {
+            write_header(&mut output, name, "gauge", &metrics.descriptions);
+            for (labels, value) in gauges {
+                output.push_str(&format!(
+                    "{}{} {}\n",
+                    name,
+                    format_labels(labels, None),
+                    format_float(*value)
+                ));
+            }
+        }
+
+        for (name, histograms) in &metrics.histograms {
+            write_header(&mut output, name, "histogram", &metrics.descriptions);
+            for (labels, histogram) in histograms {
+                for (bound, count) in self.buckets.iter().zip(histogram.bucket_counts.iter()) {
+                    output.push_str(&format!(
+                        "{}_bucket{} {}\n",
+                        name,
+                        format_labels(labels, Some(&format_float(*bound))),
+                        count
+                    ));
+                }
+                output.push_str(&format!(
+                    "{}_bucket{} {}\n",
+                    name,
+                    format_labels(labels, Some("+Inf")),
+                    histogram.count
+                ));
+                output.push_str(&format!(
+                    "{}_sum{} {}\n",
+                    name,
+                    format_labels(labels, None),
+                    format_float(histogram.sum)
+                ));
+                output.push_str(&format!(
+                    "{}_count{} {}\n",
+                    name,
+                    format_labels(labels, None),
+                    histogram.count
+                ));
+            }
+        }
+
+        Ok(output)
+    }
+}
+
+/// Splits a metrics key into a sanitized metric name and its sanitized labels, sorted by label
+/// name so the same set of labels always refers to the same time series.
+fn split_key(key: &Key) -> (String, Labels) {
+    let (name, labels) = key.clone().into_parts();
+    let mut labels: Labels = labels
+        .iter()
+        .map(|label| (sanitize(label.key(), false), label.value().to_string()))
+        .collect();
+    labels.sort();
+    (sanitize(&name, true), labels)
+}
+
+/// Replaces the characters that are not valid in a Prometheus metric or label name with `_`;
+/// for example, `splinter.peer_manager.peers` becomes `splinter_peer_manager_peers`. Colons
+/// are only valid in metric names.
+fn sanitize(name: &str, allow_colon: bool) -> String {
+    let mut sanitized: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || (allow_colon && c == ':') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if sanitized.starts_with(|c: char| c.is_ascii_digit()) || sanitized.is_empty() {
+        sanitized.insert(0, '_');
+    }
+    sanitized
+}
+
+fn write_header(
+    output: &mut String,
+    name: &str,
+    metric_type: &str,
+    descriptions: &HashMap<String, &'static str>,
+) {
+    if let Some(description) = descriptions.get(name) {
+        output.push_str(&format!(
+            "# HELP {} {}\n",
+            name,
+            description.replace('\\', "\\\\").replace('\n', "\\n")
+        ));
+    }
+    output.push_str(&format!("# TYPE {} {}\n", name, metric_type));
+}
+
+fn format_labels(labels: &[(String, String)], le: Option<&str>) -> String {
+    let mut formatted: Vec<String> = labels
+        .iter()
+        .map(|(key, value)| format!("{}=\"{}\"", key, escape_label_value(value)))
+        .collect();
+    if let Some(le) = le {
+        formatted.push(format!("le=\"{}\"", le));
+    }
+
+    if formatted.is_empty() {
+        String::new()
+    } else {
+        format!("{{{}}}", formatted.join(","))
+    }
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn format_float(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".into()
+    } else if value.is_infinite() {
+        if value.is_sign_positive() {
+            "+Inf".into()
+        } else {
+            "-Inf".into()
+        }
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use metrics::Label;
+
+    /// Verifies that counters and gauges are rendered in the Prometheus text exposition format,
+    /// with the metric names and labels sanitized.
+    #[test]
+    fn test_render_counters_and_gauges() {
+        let recorder = PrometheusRecorder::new(DEFAULT_HISTOGRAM_BUCKETS.to_vec())
+            .expect("Unable to create recorder");
+        let handle = recorder.handle();
+
+        let counter_key = Key::from_parts(
+            "splinter.scabbard.committed_batches",
+            vec![Label::new("service", "circuit::a\"b")],
+        );
+        recorder.register_counter(&counter_key, None, Some("Number of committed batches"));
+        recorder.increment_counter(&counter_key, 2);
+        recorder.increment_counter(&counter_key, 3);
+
+        let gauge_key = Key::from_name("splinter.peer_manager.peers");
+        recorder.update_gauge(&gauge_key, GaugeValue::Absolute(4.0));
+        recorder.update_gauge(&gauge_key, GaugeValue::Decrement(1.5));
+
+        let output = handle.render().expect("Unable to render metrics");
+
+        assert_eq!(
+            output,
+            "# HELP splinter_scabbard_committed_batches Number of committed batches\n\
+             # TYPE splinter_scabbard_committed_batches counter\n\
+             splinter_scabbard_committed_batches{service=\"circuit::a\\\"b\"} 5\n\
+             # TYPE splinter_peer_manager_peers gauge\n\
+             splinter_peer_manager_peers 2.5\n"
+        );
+    }
+
+    /// Verifies that histograms are rendered with cumulative buckets, a `+Inf` bucket, a sum and
+    /// a count.
+    #[test]
+    fn test_render_histogram() {
+        let recorder = PrometheusRecorder::new(vec![1.0, 5.0]).expect("Unable to create recorder");
+        let handle = recorder.handle();
+
+        let key = Key::from_name("latency");
+        recorder.record_histogram(&key, 0.5);
+        recorder.record_histogram(&key, 2.0);
+        recorder.record_histogram(&key, 10.0);
+
+        let output = handle.render().expect("Unable to render metrics");
+
+        assert_eq!(
+            output,
+            "# TYPE latency histogram\n\
+             latency_bucket{le=\"1\"} 1\n\
+             latency_bucket{le=\"5\"} 2\n\
+             latency_bucket{le=\"+Inf\"} 3\n\
+             latency_sum 12.5\n\
+             latency_count 3\n"
+        );
+    }
+
+    /// Verifies that a recorder cannot be created with empty, non-finite or unordered buckets.
+    #[test]
+    fn test_invalid_buckets() {
+        assert!(PrometheusRecorder::new(vec![]).is_err());
+        assert!(PrometheusRecorder::new(vec![1.0, f64::INFINITY]).is_err());
+        assert!(PrometheusRecorder::new(vec![1.0, 1.0]).is_err());
+        assert!(PrometheusRecorder::new(vec![2.0, 1.0]).is_err());
+    }
+}
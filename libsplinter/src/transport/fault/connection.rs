@@ -0,0 +1,364 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A connection wrapper that applies the faults configured in a `FaultController`.
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::io;
+use std::sync::mpsc::TryRecvError;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use mio::{Evented, Events, Poll, PollOpt, Ready, Token};
+use mio_extras::channel as mio_channel;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::transport::{Connection, DisconnectError, RecvError, SendError};
+
+use super::FaultController;
+
+const INNER_TOKEN: Token = Token(0);
+const OUTGOING_TOKEN: Token = Token(1);
+
+// The longest the link thread waits between checks of its queues
+const MAX_POLL_TIMEOUT: Duration = Duration::from_millis(100);
+// How long the link thread waits before retrying a send that would have blocked
+const BLOCKED_SEND_RETRY: Duration = Duration::from_millis(10);
+
+/// A `Connection` whose messages, in both directions, are subject to the faults configured in a
+/// `FaultController`.
+///
+/// The wrapped connection is owned by a background thread, which holds each message until its
+/// scheduled delivery time.
+pub struct FaultConnection {
+    remote_endpoint: String,
+    local_endpoint: String,
+    outgoing: Option<mio_channel::Sender<Vec<u8>>>,
+    incoming: mio_channel::Receiver<Vec<u8>>,
+    join_handle: Option<thread::JoinHandle<()>>,
+}
+
+impl FaultConnection {
+    pub(super) fn new(
+        inner: Box<dyn Connection>,
+        local_name: String,
+        remote_name: String,
+        controller: FaultController,
+    ) -> Result<Self, io::Error> {
+        let remote_endpoint = inner.remote_endpoint();
+        let local_endpoint = inner.local_endpoint();
+
+        let (outgoing_tx, outgoing_rx) = mio_channel::channel();
+        let (incoming_tx, incoming_rx) = mio_channel::channel();
+
+        let seed = controller.next_connection_seed(&local_name, &remote_name);
+        let disconnect_count = controller.disconnect_count(&local_name, &remote_name);
+        let link = FaultLink {
+            disconnect_count,
+            local_name,
+            remote_name,
+            controller,
+            rng: StdRng::seed_from_u64(seed),
+            sequence: 0,
+            last_outbound: Instant::now(),
+            last_inbound: Instant::now(),
+            scheduled: BinaryHeap::new(),
+        };
+
+        let join_handle = thread::Builder::new()
+            .name(format!(
+                "FaultConnection {} -> {}",
+                link.local_name, link.remote_name
+            ))
+            .spawn(move || link.run(inner, outgoing_rx, incoming_tx))?;
+
+        Ok(Self {
+            remote_endpoint,
+            local_endpoint,
+            outgoing: Some(outgoing_tx),
+            incoming: incoming_rx,
+            join_handle: Some(join_handle),
+        })
+    }
+}
+
+impl Connection for FaultConnection {
+    fn send(&mut self, message: &[u8]) -> Result<(), SendError> {
+        self.outgoing
+            .as_ref()
+            .ok_or(SendError::Disconnected)?
+            .send(message.to_vec())
+            .map_err(|_| SendError::Disconnected)
+    }
+
+    fn recv(&mut self) -> Result<Vec<u8>, RecvError> {
+        match self.incoming.try_recv() {
+            Ok(message) => Ok(message),
+            Err(TryRecvError::Empty) => Err(RecvError::WouldBlock),
+            Err(TryRecvError::Disconnected) => Err(RecvError::Disconnected),
+        }
+    }
+
+    fn remote_endpoint(&self) -> String {
+        self.remote_endpoint.clone()
+    }
+
+    fn local_endpoint(&self) -> String {
+        self.local_endpoint.clone()
+    }
+
+    fn disconnect(&mut self) -> Result<(), DisconnectError> {
+        // Dropping the sender signals the link thread to disconnect the wrapped connection
+        self.outgoing.take();
+        if let Some(join_handle) = self.join_handle.take() {
+            join_handle.join().map_err(|_| {
+                DisconnectError::ProtocolError("Fault connection thread panicked".into())
+            })?;
+        }
+        Ok(())
+    }
+
+    fn evented(&self) -> &dyn Evented {
+        &self.incoming
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Direction {
+    Inbound,
+    Outbound,
+}
+
+/// A message waiting for its delivery time
+#[derive(Debug, PartialEq, Eq)]
+struct Scheduled {
+    deliver_at: Instant,
+    sequence: u64,
+    direction: Direction,
+    payload: Vec<u8>,
+}
+
+impl Ord for Scheduled {
+    // Reversed, so that the `BinaryHeap` yields the earliest delivery first
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .deliver_at
+            .cmp(&self.deliver_at)
+            .then_with(|| other.sequence.cmp(&self.sequence))
+    }
+}
+
+impl PartialOrd for Scheduled {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// The state of the background thread of a `FaultConnection`
+struct FaultLink {
+    // The controller's disconnect count for the link when the connection was created
+    disconnect_count: u64,
+    local_name: String,
+    remote_name: String,
+    controller: FaultController,
+    rng: StdRng,
+    sequence: u64,
+    last_outbound: Instant,
+    last_inbound: Instant,
+    scheduled: BinaryHeap<Scheduled>,
+}
+
+impl FaultLink {
+    fn run(
+        mut self,
+        mut inner: Box<dyn Connection>,
+        outgoing: mio_channel::Receiver<Vec<u8>>,
+        incoming: mio_channel::Sender<Vec<u8>>,
+    ) {
+        if let Err(err) = self.run_loop(&mut *inner, &outgoing, &incoming) {
+            debug!(
+                "Fault connection {} -> {} closed: {}",
+                self.local_name, self.remote_name, err
+            );
+        }
+
+        if let Err(err) = inner.disconnect() {
+            debug!(
+                "Unable to disconnect fault connection {} -> {}: {}",
+                self.local_name, self.remote_name, err
+            );
+        }
+    }
+
+    fn run_loop(
+        &mut self,
+        inner: &mut dyn Connection,
+        outgoing: &mio_channel::Receiver<Vec<u8>>,
+        incoming: &mio_channel::Sender<Vec<u8>>,
+    ) -> Result<(), String> {
+        let poll = Poll::new().map_err(|err| err.to_string())?;
+        poll.register(
+            inner.evented(),
+            INNER_TOKEN,
+            Ready::readable(),
+            PollOpt::level(),
+        )
+        .map_err(|err| err.to_string())?;
+        poll.register(
+            outgoing,
+            OUTGOING_TOKEN,
+            Ready::readable(),
+            PollOpt::level(),
+        )
+        .map_err(|err| err.to_string())?;
+
+        let mut events = Events::with_capacity(16);
+        let mut send_blocked = false;
+
+        loop {
+            let timeout = if send_blocked {
+                BLOCKED_SEND_RETRY
+            } else {
+                self.scheduled
+                    .peek()
+                    .map(|next| next.deliver_at.saturating_duration_since(Instant::now()))
+                    .unwrap_or(MAX_POLL_TIMEOUT)
+                    .min(MAX_POLL_TIMEOUT)
+            };
+            poll.poll(&mut events, Some(timeout))
+                .map_err(|err| err.to_string())?;
+
+            if self
+                .controller
+                .disconnect_count(&self.local_name, &self.remote_name)
+                != self.disconnect_count
+            {
+                return Err("connection was closed by the fault controller".into());
+            }
+
+            loop {
+                match outgoing.try_recv() {
+                    Ok(payload) => self.schedule(Direction::Outbound, payload),
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => {
+                        return Err("connection was disconnected locally".into())
+                    }
+                }
+            }
+
+            loop {
+                match inner.recv() {
+                    Ok(payload) => self.schedule(Direction::Inbound, payload),
+                    Err(RecvError::WouldBlock) => break,
+                    Err(err) => return Err(err.to_string()),
+                }
+            }
+
+            send_blocked = self.deliver(inner, incoming)?;
+        }
+    }
+
+    /// Applies the current policy to a message, either dropping it or scheduling its delivery.
+    fn schedule(&mut self, direction: Direction, payload: Vec<u8>) {
+        if self
+            .controller
+            .is_partitioned(&self.local_name, &self.remote_name)
+        {
+            trace!(
+                "Dropping {:?} message between partitioned endpoints {} and {}",
+                direction,
+                self.local_name,
+                self.remote_name
+            );
+            return;
+        }
+
+        let policy = self.controller.policy(&self.local_name, &self.remote_name);
+
+        if self.rng.gen_bool(policy.drop_rate()) {
+            trace!(
+                "Dropping {:?} message between {} and {}",
+                direction,
+                self.local_name,
+                self.remote_name
+            );
+            return;
+        }
+
+        let jitter = policy.jitter();
+        let delay = if jitter > Duration::from_secs(0) {
+            policy.latency() - jitter + self.rng.gen_range(Duration::from_secs(0)..=jitter * 2)
+        } else {
+            policy.latency()
+        };
+        let mut deliver_at = Instant::now() + delay;
+
+        let last = match direction {
+            Direction::Inbound => &mut self.last_inbound,
+            Direction::Outbound => &mut self.last_outbound,
+        };
+        if self.rng.gen_bool(policy.reorder_rate()) {
+            deliver_at += policy.reorder_delay();
+        } else {
+            // Messages are delivered in order, unless they were selected for reordering
+            deliver_at = deliver_at.max(*last);
+            *last = deliver_at;
+        }
+
+        self.sequence += 1;
+        self.scheduled.push(Scheduled {
+            deliver_at,
+            sequence: self.sequence,
+            direction,
+            payload,
+        });
+    }
+
+    /// Delivers all messages whose time has come. Returns true if the wrapped connection could
+    /// not accept an outbound message without blocking.
+    fn deliver(
+        &mut self,
+        inner: &mut dyn Connection,
+        incoming: &mio_channel::Sender<Vec<u8>>,
+    ) -> Result<bool, String> {
+        let partitioned = self
+            .controller
+            .is_partitioned(&self.local_name, &self.remote_name);
+        let now = Instant::now();
+
+        while let Some(next) = self.scheduled.peek() {
+            if next.deliver_at > now {
+                break;
+            }
+
+            if !partitioned {
+                match next.direction {
+                    Direction::Outbound => match inner.send(&next.payload) {
+                        Ok(()) => (),
+                        Err(SendError::WouldBlock) => return Ok(true),
+                        Err(err) => return Err(err.to_string()),
+                    },
+                    Direction::Inbound => incoming
+                        .send(next.payload.clone())
+                        .map_err(|_| "connection was dropped locally".to_string())?,
+                }
+            }
+
+            self.scheduled.pop();
+        }
+
+        Ok(false)
+    }
+}
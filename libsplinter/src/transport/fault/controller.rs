@@ -0,0 +1,273 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Shared control over the faults injected by a set of fault transports.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+use std::time::Duration;
+
+use crate::error::InternalError;
+
+use super::FaultPolicy;
+
+/// A pair of named endpoints. Links are undirected, so the names are stored in sorted order.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct Link(String, String);
+
+impl Link {
+    fn new(a: &str, b: &str) -> Self {
+        if a <= b {
+            Link(a.to_string(), b.to_string())
+        } else {
+            Link(b.to_string(), a.to_string())
+        }
+    }
+}
+
+#[derive(Default)]
+struct FaultState {
+    seed: u64,
+    names: HashMap<String, String>,
+    default_policy: FaultPolicy,
+    link_policies: HashMap<Link, FaultPolicy>,
+    partitions: HashSet<Link>,
+    connection_counts: HashMap<Link, u64>,
+    disconnect_counts: HashMap<Link, u64>,
+}
+
+/// Controls the faults injected into the connections of one or more `FaultTransport`s.
+///
+/// Endpoints are referred to by name, such as a node ID. Each `FaultTransport` is given the name
+/// of the endpoint it belongs to; the names of the endpoints it connects to are registered with
+/// [`FaultController::add_endpoint`]. Endpoints that have not been registered are referred to by
+/// their address.
+///
+/// The controller is cheaply cloneable; all clones share the same state, so a test may keep a
+/// clone to partition and heal links while the network is running.
+#[derive(Clone, Default)]
+pub struct FaultController {
+    state: Arc<Mutex<FaultState>>,
+}
+
+impl FaultController {
+    /// Creates a new `FaultController`.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed for the random faults. Connections between the same pair of named
+    ///   endpoints, made in the same order, will have the same faults applied to the same
+    ///   messages.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(FaultState {
+                seed,
+                ..Default::default()
+            })),
+        }
+    }
+
+    /// Registers the name of an endpoint address.
+    pub fn add_endpoint(&self, name: &str, endpoint: &str) {
+        self.state()
+            .names
+            .insert(endpoint.to_string(), name.to_string());
+    }
+
+    /// Sets the policy for links that do not have their own policy.
+    pub fn set_default_policy(&self, policy: FaultPolicy) {
+        self.state().default_policy = policy;
+    }
+
+    /// Sets the policy for the link between two named endpoints, in both directions.
+    pub fn set_link_policy(&self, a: &str, b: &str, policy: FaultPolicy) {
+        self.state().link_policies.insert(Link::new(a, b), policy);
+    }
+
+    /// Removes the policy for the link between two named endpoints, so that the default policy
+    /// applies.
+    pub fn clear_link_policy(&self, a: &str, b: &str) {
+        self.state().link_policies.remove(&Link::new(a, b));
+    }
+
+    /// Partitions two named endpoints from each other.
+    ///
+    /// While partitioned, all messages between the endpoints are dropped and new connections
+    /// between them are refused. Existing connections are not closed; they will be detected as
+    /// failed by whatever heartbeat mechanism the connection's owner uses.
+    pub fn partition(&self, a: &str, b: &str) {
+        self.state().partitions.insert(Link::new(a, b));
+    }
+
+    /// Removes the partition between two named endpoints.
+    pub fn heal(&self, a: &str, b: &str) {
+        self.state().partitions.remove(&Link::new(a, b));
+    }
+
+    /// Removes all partitions.
+    pub fn heal_all(&self) {
+        self.state().partitions.clear();
+    }
+
+    /// Closes the current connections between two named endpoints.
+    ///
+    /// Both ends of each connection see it as disconnected, as if the network between them had
+    /// failed. New connections between the endpoints are not affected; combine this with
+    /// [`FaultController::partition`] to keep them from reconnecting.
+    pub fn disconnect(&self, a: &str, b: &str) {
+        *self
+            .state()
+            .disconnect_counts
+            .entry(Link::new(a, b))
+            .or_insert(0) += 1;
+    }
+
+    /// Returns true if the two named endpoints are partitioned from each other.
+    pub fn is_partitioned(&self, a: &str, b: &str) -> bool {
+        self.state().partitions.contains(&Link::new(a, b))
+    }
+
+    /// Applies a single scripted action.
+    pub fn apply(&self, action: &FaultAction) {
+        match action {
+            FaultAction::Partition(a, b) => self.partition(a, b),
+            FaultAction::Heal(a, b) => self.heal(a, b),
+            FaultAction::HealAll => self.heal_all(),
+            FaultAction::Disconnect(a, b) => self.disconnect(a, b),
+            FaultAction::SetDefaultPolicy(policy) => self.set_default_policy(*policy),
+            FaultAction::SetLinkPolicy(a, b, policy) => self.set_link_policy(a, b, *policy),
+            FaultAction::ClearLinkPolicy(a, b) => self.clear_link_policy(a, b),
+        }
+    }
+
+    /// Returns the name registered for the given address, or the address itself.
+    pub(super) fn resolve_name(&self, endpoint: &str) -> String {
+        self.state()
+            .names
+            .get(endpoint)
+            .cloned()
+            .unwrap_or_else(|| endpoint.to_string())
+    }
+
+    /// Returns the policy currently in effect between two named endpoints.
+    pub(super) fn policy(&self, a: &str, b: &str) -> FaultPolicy {
+        let state = self.state();
+        state
+            .link_policies
+            .get(&Link::new(a, b))
+            .copied()
+            .unwrap_or(state.default_policy)
+    }
+
+    /// Returns the number of times the connections between two named endpoints have been closed.
+    /// A connection is closed when this changes from the value when it was created.
+    pub(super) fn disconnect_count(&self, a: &str, b: &str) -> u64 {
+        self.state()
+            .disconnect_counts
+            .get(&Link::new(a, b))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Returns the seed for the next connection between two named endpoints.
+    pub(super) fn next_connection_seed(&self, a: &str, b: &str) -> u64 {
+        let mut state = self.state();
+        let link = Link::new(a, b);
+        let seed = state.seed;
+        let count = state.connection_counts.entry(link.clone()).or_insert(0);
+        *count += 1;
+
+        let mut hasher = DefaultHasher::new();
+        seed.hash(&mut hasher);
+        link.hash(&mut hasher);
+        count.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    // The state is left consistent by every method, so a poisoned lock can still be used.
+    fn state(&self) -> MutexGuard<FaultState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// A change to the faults injected by a `FaultController`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FaultAction {
+    Partition(String, String),
+    Heal(String, String),
+    HealAll,
+    Disconnect(String, String),
+    SetDefaultPolicy(FaultPolicy),
+    SetLinkPolicy(String, String, FaultPolicy),
+    ClearLinkPolicy(String, String),
+}
+
+/// A sequence of `FaultAction`s, applied to a `FaultController` at fixed points in time.
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use splinter::transport::fault::{FaultAction, FaultController, FaultScript};
+///
+/// let controller = FaultController::new(0);
+/// let handle = FaultScript::new()
+///     .then(
+///         Duration::from_millis(10),
+///         FaultAction::Partition("alpha".into(), "beta".into()),
+///     )
+///     .then(Duration::from_millis(10), FaultAction::HealAll)
+///     .run(controller.clone())
+///     .expect("Unable to start fault script");
+///
+/// handle.join().expect("Fault script panicked");
+/// assert!(!controller.is_partitioned("alpha", "beta"));
+/// ```
+#[derive(Default)]
+pub struct FaultScript {
+    steps: Vec<(Duration, FaultAction)>,
+}
+
+impl FaultScript {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an action, applied after the given delay from the previous action (or from the start
+    /// of the script, for the first action).
+    pub fn then(mut self, delay: Duration, action: FaultAction) -> Self {
+        self.steps.push((delay, action));
+        self
+    }
+
+    /// Runs the script against the given controller on a background thread.
+    ///
+    /// The returned handle may be joined to wait for the last action to be applied.
+    pub fn run(self, controller: FaultController) -> Result<thread::JoinHandle<()>, InternalError> {
+        thread::Builder::new()
+            .name("FaultScript".into())
+            .spawn(move || {
+                for (delay, action) in self.steps {
+                    thread::sleep(delay);
+                    debug!("Applying scripted fault action {:?}", action);
+                    controller.apply(&action);
+                }
+            })
+            .map_err(|err| InternalError::from_source(Box::new(err)))
+    }
+}
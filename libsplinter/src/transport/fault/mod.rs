@@ -0,0 +1,317 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A transport wrapper that simulates an unreliable network, for testing.
+//!
+//! A [`FaultTransport`] wraps another [`Transport`] and applies the faults configured in a shared
+//! [`FaultController`] to the connections it creates: latency, jitter, dropped messages,
+//! reordered messages, partitions between named endpoints, and closed connections. Random faults
+//! are drawn from a seeded generator, so a test run with the same seed and the same sequence of
+//! connections sees the same faults.
+//!
+//! Faults are applied to both directions of the connections created by
+//! [`FaultTransport::connect`]. Connections accepted by a listener are not wrapped, so that a link
+//! between two endpoints which both use a `FaultTransport` only has its faults applied once.
+//!
+//! [`Transport`]: ../trait.Transport.html
+
+mod connection;
+mod controller;
+mod policy;
+
+use std::io;
+
+use super::{ConnectError, Connection, ListenError, Listener, Transport};
+
+pub use connection::FaultConnection;
+pub use controller::{FaultAction, FaultController, FaultScript};
+pub use policy::{FaultPolicy, FaultPolicyBuilder};
+
+/// A `Transport` that applies the faults configured in a `FaultController` to the connections it
+/// creates.
+pub struct FaultTransport {
+    name: String,
+    inner: Box<dyn Transport + Send>,
+    controller: FaultController,
+}
+
+impl FaultTransport {
+    /// Creates a new `FaultTransport`.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the endpoint that uses this transport, such as a node ID
+    /// * `inner` - The transport used to create the underlying connections
+    /// * `controller` - The controller for the faults to apply
+    pub fn new(name: &str, inner: Box<dyn Transport + Send>, controller: FaultController) -> Self {
+        Self {
+            name: name.to_string(),
+            inner,
+            controller,
+        }
+    }
+}
+
+impl Transport for FaultTransport {
+    fn accepts(&self, address: &str) -> bool {
+        self.inner.accepts(address)
+    }
+
+    fn connect(&mut self, endpoint: &str) -> Result<Box<dyn Connection>, ConnectError> {
+        let remote_name = self.controller.resolve_name(endpoint);
+        if self.controller.is_partitioned(&self.name, &remote_name) {
+            return Err(ConnectError::IoError(io::Error::new(
+                io::ErrorKind::ConnectionRefused,
+                format!("{} is partitioned from {}", self.name, remote_name),
+            )));
+        }
+
+        let connection = self.inner.connect(endpoint)?;
+
+        Ok(Box::new(FaultConnection::new(
+            connection,
+            self.name.clone(),
+            remote_name,
+            self.controller.clone(),
+        )?))
+    }
+
+    fn listen(&mut self, bind: &str) -> Result<Box<dyn Listener>, ListenError> {
+        self.inner.listen(bind)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    use crate::transport::{inproc::InprocTransport, tests, RecvError, SendError};
+
+    macro_rules! block {
+        ($op:expr, $err:ident) => {{
+            let start = Instant::now();
+            loop {
+                assert!(
+                    start.elapsed() < Duration::from_secs(10),
+                    "blocked for too long"
+                );
+                match $op {
+                    Err($err::WouldBlock) => {
+                        thread::sleep(Duration::from_millis(5));
+                        continue;
+                    }
+                    Err(err) => break Err(err),
+                    Ok(ok) => break Ok(ok),
+                }
+            }
+        }};
+    }
+
+    /// Creates a fault transport named "alpha" over an inproc transport, along with a listener
+    /// named "beta".
+    fn create_transport(controller: &FaultController) -> (FaultTransport, Box<dyn Listener>) {
+        let mut inproc = InprocTransport::default();
+        let listener = inproc.listen("inproc://beta").expect("Unable to listen");
+        controller.add_endpoint("beta", "inproc://beta");
+
+        (
+            FaultTransport::new("alpha", Box::new(inproc), controller.clone()),
+            listener,
+        )
+    }
+
+    /// Test that a fault transport with the default policy behaves as a standard transport.
+    #[test]
+    fn test_transport() {
+        let transport = FaultTransport::new(
+            "alpha",
+            Box::new(InprocTransport::default()),
+            FaultController::new(0),
+        );
+        tests::test_transport(transport, "test");
+    }
+
+    /// Test that messages are delayed by at least the configured latency, in both directions, and
+    /// that they are delivered in order.
+    #[test]
+    fn test_latency() {
+        let controller = FaultController::new(0);
+        controller.set_default_policy(
+            FaultPolicyBuilder::new()
+                .with_latency(Duration::from_millis(100))
+                .with_jitter(Duration::from_millis(50))
+                .build()
+                .expect("Unable to build policy"),
+        );
+        let (mut transport, mut listener) = create_transport(&controller);
+
+        let mut client = transport
+            .connect("inproc://beta")
+            .expect("Unable to connect");
+        let mut server = listener.accept().expect("Unable to accept");
+
+        let start = Instant::now();
+        for i in 0..10u8 {
+            client.send(&[i]).expect("Unable to send");
+        }
+        for i in 0..10u8 {
+            assert_eq!(vec![i], block!(server.recv(), RecvError).unwrap());
+        }
+        assert!(start.elapsed() >= Duration::from_millis(50));
+
+        let start = Instant::now();
+        block!(server.send(b"reply"), SendError).unwrap();
+        assert_eq!(b"reply".to_vec(), block!(client.recv(), RecvError).unwrap());
+        assert!(start.elapsed() >= Duration::from_millis(50));
+    }
+
+    /// Test that every message is dropped with a drop rate of 1.0, and that messages flow again
+    /// once the policy is cleared.
+    #[test]
+    fn test_drop_rate() {
+        let controller = FaultController::new(0);
+        let (mut transport, mut listener) = create_transport(&controller);
+
+        let mut client = transport
+            .connect("inproc://beta")
+            .expect("Unable to connect");
+        let mut server = listener.accept().expect("Unable to accept");
+
+        controller.set_link_policy(
+            "alpha",
+            "beta",
+            FaultPolicyBuilder::new()
+                .with_drop_rate(1.0)
+                .build()
+                .expect("Unable to build policy"),
+        );
+        client.send(b"dropped").expect("Unable to send");
+        thread::sleep(Duration::from_millis(200));
+        assert!(matches!(server.recv(), Err(RecvError::WouldBlock)));
+
+        controller.clear_link_policy("alpha", "beta");
+        client.send(b"delivered").expect("Unable to send");
+        assert_eq!(
+            b"delivered".to_vec(),
+            block!(server.recv(), RecvError).unwrap()
+        );
+    }
+
+    /// Test that a reordered message is delivered after the message that follows it.
+    #[test]
+    fn test_reordering() {
+        let controller = FaultController::new(0);
+        let (mut transport, mut listener) = create_transport(&controller);
+
+        let mut client = transport
+            .connect("inproc://beta")
+            .expect("Unable to connect");
+        let mut server = listener.accept().expect("Unable to accept");
+
+        controller.set_default_policy(
+            FaultPolicyBuilder::new()
+                .with_reordering(1.0, Duration::from_millis(200))
+                .build()
+                .expect("Unable to build policy"),
+        );
+        client.send(b"first").expect("Unable to send");
+        thread::sleep(Duration::from_millis(50));
+
+        controller.set_default_policy(FaultPolicy::default());
+        client.send(b"second").expect("Unable to send");
+
+        assert_eq!(
+            b"second".to_vec(),
+            block!(server.recv(), RecvError).unwrap()
+        );
+        assert_eq!(b"first".to_vec(), block!(server.recv(), RecvError).unwrap());
+    }
+
+    /// Test that partitioned endpoints cannot connect or exchange messages, and that they can once
+    /// the partition is healed.
+    #[test]
+    fn test_partition() {
+        let controller = FaultController::new(0);
+        let (mut transport, mut listener) = create_transport(&controller);
+
+        let mut client = transport
+            .connect("inproc://beta")
+            .expect("Unable to connect");
+        let mut server = listener.accept().expect("Unable to accept");
+
+        controller.partition("beta", "alpha");
+        assert!(matches!(
+            transport.connect("inproc://beta"),
+            Err(ConnectError::IoError(_))
+        ));
+
+        client.send(b"dropped").expect("Unable to send");
+        block!(server.send(b"dropped"), SendError).unwrap();
+        thread::sleep(Duration::from_millis(200));
+        assert!(matches!(server.recv(), Err(RecvError::WouldBlock)));
+        assert!(matches!(client.recv(), Err(RecvError::WouldBlock)));
+
+        controller.heal("alpha", "beta");
+        client.send(b"delivered").expect("Unable to send");
+        assert_eq!(
+            b"delivered".to_vec(),
+            block!(server.recv(), RecvError).unwrap()
+        );
+    }
+
+    /// Test that disconnected endpoints have their existing connection closed, and that they can
+    /// connect again.
+    #[test]
+    fn test_disconnect() {
+        let controller = FaultController::new(0);
+        let (mut transport, mut listener) = create_transport(&controller);
+
+        let mut client = transport
+            .connect("inproc://beta")
+            .expect("Unable to connect");
+        let _server = listener.accept().expect("Unable to accept");
+
+        controller.disconnect("beta", "alpha");
+        assert!(matches!(
+            block!(client.recv(), RecvError),
+            Err(RecvError::Disconnected)
+        ));
+
+        let mut client = transport
+            .connect("inproc://beta")
+            .expect("Unable to reconnect");
+        let mut server = listener.accept().expect("Unable to accept");
+        client.send(b"delivered").expect("Unable to send");
+        assert_eq!(
+            b"delivered".to_vec(),
+            block!(server.recv(), RecvError).unwrap()
+        );
+    }
+
+    /// Test that a policy with an invalid drop rate cannot be built.
+    #[test]
+    fn test_invalid_policy() {
+        assert!(FaultPolicyBuilder::new()
+            .with_drop_rate(1.5)
+            .build()
+            .is_err());
+        assert!(FaultPolicyBuilder::new()
+            .with_jitter(Duration::from_millis(10))
+            .build()
+            .is_err());
+    }
+}
@@ -0,0 +1,147 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The faults applied to the messages passed over a link.
+
+use std::time::Duration;
+
+use crate::error::InvalidStateError;
+
+/// The faults applied to each message passed between two endpoints.
+///
+/// The default policy does not alter messages in any way.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FaultPolicy {
+    latency: Duration,
+    jitter: Duration,
+    drop_rate: f64,
+    reorder_rate: f64,
+    reorder_delay: Duration,
+}
+
+impl FaultPolicy {
+    /// Returns the delay added to every message
+    pub fn latency(&self) -> Duration {
+        self.latency
+    }
+
+    /// Returns the maximum random delay added to, or removed from, the latency of each message
+    pub fn jitter(&self) -> Duration {
+        self.jitter
+    }
+
+    /// Returns the probability, between 0.0 and 1.0, that a message is dropped
+    pub fn drop_rate(&self) -> f64 {
+        self.drop_rate
+    }
+
+    /// Returns the probability, between 0.0 and 1.0, that a message is held back and delivered
+    /// after the messages that follow it
+    pub fn reorder_rate(&self) -> f64 {
+        self.reorder_rate
+    }
+
+    /// Returns the additional delay of a message that is held back for reordering
+    pub fn reorder_delay(&self) -> Duration {
+        self.reorder_delay
+    }
+}
+
+/// Builder for creating a `FaultPolicy`
+#[derive(Default)]
+pub struct FaultPolicyBuilder {
+    latency: Option<Duration>,
+    jitter: Option<Duration>,
+    drop_rate: Option<f64>,
+    reorder_rate: Option<f64>,
+    reorder_delay: Option<Duration>,
+}
+
+impl FaultPolicyBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the delay added to every message. Defaults to no delay.
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = Some(latency);
+        self
+    }
+
+    /// Sets the maximum random delay added to, or removed from, the latency of each message.
+    /// Defaults to no jitter.
+    ///
+    /// Jitter does not reorder messages; a message is never delivered before the message sent
+    /// ahead of it, unless it is selected for reordering.
+    pub fn with_jitter(mut self, jitter: Duration) -> Self {
+        self.jitter = Some(jitter);
+        self
+    }
+
+    /// Sets the probability, between 0.0 and 1.0, that a message is dropped. Defaults to 0.0.
+    pub fn with_drop_rate(mut self, drop_rate: f64) -> Self {
+        self.drop_rate = Some(drop_rate);
+        self
+    }
+
+    /// Sets the probability, between 0.0 and 1.0, that a message is held back for the given delay
+    /// so that the messages that follow it are delivered first. Defaults to no reordering.
+    pub fn with_reordering(mut self, reorder_rate: f64, reorder_delay: Duration) -> Self {
+        self.reorder_rate = Some(reorder_rate);
+        self.reorder_delay = Some(reorder_delay);
+        self
+    }
+
+    /// Builds the `FaultPolicy`
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidStateError` if the drop rate or the reorder rate is not between 0.0 and
+    /// 1.0, or if the jitter is greater than the latency.
+    pub fn build(self) -> Result<FaultPolicy, InvalidStateError> {
+        let latency = self.latency.unwrap_or_default();
+        let jitter = self.jitter.unwrap_or_default();
+        let drop_rate = self.drop_rate.unwrap_or_default();
+        let reorder_rate = self.reorder_rate.unwrap_or_default();
+        let reorder_delay = self.reorder_delay.unwrap_or_default();
+
+        if !(0.0..=1.0).contains(&drop_rate) {
+            return Err(InvalidStateError::with_message(format!(
+                "Drop rate must be between 0.0 and 1.0, was {}",
+                drop_rate
+            )));
+        }
+
+        if !(0.0..=1.0).contains(&reorder_rate) {
+            return Err(InvalidStateError::with_message(format!(
+                "Reorder rate must be between 0.0 and 1.0, was {}",
+                reorder_rate
+            )));
+        }
+
+        if jitter > latency {
+            return Err(InvalidStateError::with_message(
+                "Jitter must not be greater than the latency".to_string(),
+            ));
+        }
+
+        Ok(FaultPolicy {
+            latency,
+            jitter,
+            drop_rate,
+            reorder_rate,
+            reorder_delay,
+        })
+    }
+}
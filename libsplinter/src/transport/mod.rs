@@ -36,6 +36,8 @@
 //! [`Transport`]: trait.Transport.html
 
 mod error;
+#[cfg(feature = "transport-fault")]
+pub mod fault;
 pub mod inproc;
 pub(crate) mod matrix;
 pub mod multi;
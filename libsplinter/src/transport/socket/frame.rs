@@ -12,20 +12,51 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "transport-compression")]
+use std::borrow::Cow;
 use std::io::{self, Cursor, Read, Write};
 use std::thread;
 use std::time::Duration;
 
 use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+#[cfg(feature = "transport-compression")]
+use flate2::{read::DeflateDecoder, write::DeflateEncoder, Compression};
 
 const HEADER_LENGTH: usize = 6;
+// version (2 bytes) + length (4 bytes) + compression (1 byte) + payload checksum (4 bytes)
+#[cfg(feature = "transport-compression")]
+const V2_HEADER_LENGTH: usize = 11;
+
+// Payloads smaller than this are not worth the cost of compressing
+#[cfg(feature = "transport-compression")]
+const COMPRESSION_THRESHOLD: usize = 1024;
+
+// The largest payload a compressed frame may decompress to; larger payloads are sent uncompressed
+#[cfg(feature = "transport-compression")]
+const MAX_FRAME_PAYLOAD: usize = 64 * 1024 * 1024;
+
+/// The latest frame version supported by this build
+#[cfg(feature = "transport-compression")]
+pub const LATEST_FRAME_VERSION: FrameVersion = FrameVersion::V2;
+/// The latest frame version supported by this build
+#[cfg(not(feature = "transport-compression"))]
+pub const LATEST_FRAME_VERSION: FrameVersion = FrameVersion::V1;
 
 /// An error that may be returned during frame-related operations
 #[derive(Debug)]
 pub enum FrameError {
     IoError(io::Error),
     InvalidChecksum,
-    InvalidHeaderLength(usize),
+    InvalidHeaderLength {
+        expected: usize,
+        actual: usize,
+    },
+    #[cfg(feature = "transport-compression")]
+    InvalidPayloadChecksum,
+    #[cfg(feature = "transport-compression")]
+    UnsupportedCompression(u8),
+    #[cfg(feature = "transport-compression")]
+    DecompressionFailure(String),
     UnsupportedVersion,
     HandshakeFailure(String),
 }
@@ -35,11 +66,21 @@ impl std::fmt::Display for FrameError {
         match self {
             FrameError::IoError(err) => f.write_str(&err.to_string()),
             FrameError::InvalidChecksum => f.write_str("Invalid checksum in frame header"),
-            FrameError::InvalidHeaderLength(n) => write!(
+            FrameError::InvalidHeaderLength { expected, actual } => write!(
                 f,
                 "Invalid header length expected {} but was {}",
-                HEADER_LENGTH, n
+                expected, actual
             ),
+            #[cfg(feature = "transport-compression")]
+            FrameError::InvalidPayloadChecksum => f.write_str("Invalid checksum for frame payload"),
+            #[cfg(feature = "transport-compression")]
+            FrameError::UnsupportedCompression(compression) => {
+                write!(f, "Unsupported frame compression {}", compression)
+            }
+            #[cfg(feature = "transport-compression")]
+            FrameError::DecompressionFailure(msg) => {
+                write!(f, "Unable to decompress frame payload: {}", msg)
+            }
             FrameError::UnsupportedVersion => f.write_str("Unsupported frame version"),
             FrameError::HandshakeFailure(msg) => f.write_str(msg),
         }
@@ -51,7 +92,13 @@ impl std::error::Error for FrameError {
         match self {
             FrameError::IoError(err) => Some(err),
             FrameError::InvalidChecksum => None,
-            FrameError::InvalidHeaderLength(_) => None,
+            FrameError::InvalidHeaderLength { .. } => None,
+            #[cfg(feature = "transport-compression")]
+            FrameError::InvalidPayloadChecksum => None,
+            #[cfg(feature = "transport-compression")]
+            FrameError::UnsupportedCompression(_) => None,
+            #[cfg(feature = "transport-compression")]
+            FrameError::DecompressionFailure(_) => None,
             FrameError::UnsupportedVersion => None,
             FrameError::HandshakeFailure(_) => None,
         }
@@ -71,6 +118,21 @@ impl From<io::Error> for FrameError {
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub enum FrameVersion {
     V1 = 1,
+    /// Adds per-frame compression of the payload, and a checksum of the transmitted payload
+    #[cfg(feature = "transport-compression")]
+    V2 = 2,
+}
+
+impl FrameVersion {
+    /// Returns the frame version with the given wire value, if it is supported.
+    fn from_u16(version: u16) -> Option<Self> {
+        match version {
+            1 => Some(FrameVersion::V1),
+            #[cfg(feature = "transport-compression")]
+            2 => Some(FrameVersion::V2),
+            _ => None,
+        }
+    }
 }
 
 impl std::fmt::Display for FrameVersion {
@@ -79,6 +141,25 @@ impl std::fmt::Display for FrameVersion {
     }
 }
 
+/// The compression applied to the payload of a version 2 frame.
+#[cfg(feature = "transport-compression")]
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+enum FrameCompression {
+    None = 0,
+    Deflate = 1,
+}
+
+#[cfg(feature = "transport-compression")]
+impl FrameCompression {
+    fn from_u8(compression: u8) -> Result<Self, FrameError> {
+        match compression {
+            0 => Ok(FrameCompression::None),
+            1 => Ok(FrameCompression::Deflate),
+            _ => Err(FrameError::UnsupportedCompression(compression)),
+        }
+    }
+}
+
 /// A complete Frame of transmitted data.
 ///
 /// This struct owns the data that has been transmitted.  It is essentially a receiving frame.
@@ -100,6 +181,7 @@ impl Frame {
     ///
     /// - the header is malformed
     /// - the data length doesn't match the header length
+    /// - the payload doesn't match its checksum, or cannot be decompressed
     /// - an IO error occurs
     pub fn read<R: Read>(reader: &mut R) -> Result<Self, FrameError> {
         let frame_header = loop {
@@ -114,37 +196,73 @@ impl Frame {
         };
 
         match frame_header {
-            FrameHeader::V1 { length } => {
-                let mut buffer = vec![0; length as usize];
-                let mut remaining = &mut buffer[..];
-
-                while !remaining.is_empty() {
-                    match reader.read(remaining) {
-                        Ok(0) => break,
-                        Ok(n) => {
-                            let tmp = remaining;
-                            remaining = &mut tmp[n..];
-                        }
-                        Err(ref e) if e.kind() == std::io::ErrorKind::Interrupted => {}
-                        Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
-                            thread::sleep(Duration::from_millis(100));
+            FrameHeader::V1 { length } => Ok(Self {
+                data: read_payload(reader, length)?,
+            }),
+            #[cfg(feature = "transport-compression")]
+            FrameHeader::V2 {
+                length,
+                compression,
+                checksum,
+            } => {
+                let payload = read_payload(reader, length)?;
+                if crc32fast::hash(&payload) != checksum {
+                    return Err(FrameError::InvalidPayloadChecksum);
+                }
+
+                let data = match compression {
+                    FrameCompression::None => payload,
+                    FrameCompression::Deflate => {
+                        let mut data = Vec::with_capacity(payload.len() * 2);
+                        DeflateDecoder::new(&payload[..])
+                            .take(MAX_FRAME_PAYLOAD as u64 + 1)
+                            .read_to_end(&mut data)
+                            .map_err(|err| FrameError::DecompressionFailure(err.to_string()))?;
+                        if data.len() > MAX_FRAME_PAYLOAD {
+                            return Err(FrameError::DecompressionFailure(format!(
+                                "Decompressed payload exceeds the maximum of {} bytes",
+                                MAX_FRAME_PAYLOAD
+                            )));
                         }
-                        Err(e) => return Err(FrameError::IoError(e)),
+                        data
                     }
-                }
-                if !remaining.is_empty() {
-                    Err(FrameError::IoError(io::Error::new(
-                        io::ErrorKind::UnexpectedEof,
-                        "Could not receive complete frame",
-                    )))
-                } else {
-                    Ok(Self { data: buffer })
-                }
+                };
+
+                Ok(Self { data })
             }
         }
     }
 }
 
+/// Read a payload of the given length from the given reader.
+fn read_payload<R: Read>(reader: &mut R, length: u32) -> Result<Vec<u8>, FrameError> {
+    let mut buffer = vec![0; length as usize];
+    let mut remaining = &mut buffer[..];
+
+    while !remaining.is_empty() {
+        match reader.read(remaining) {
+            Ok(0) => break,
+            Ok(n) => {
+                let tmp = remaining;
+                remaining = &mut tmp[n..];
+            }
+            Err(ref e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                thread::sleep(Duration::from_millis(100));
+            }
+            Err(e) => return Err(FrameError::IoError(e)),
+        }
+    }
+    if !remaining.is_empty() {
+        Err(FrameError::IoError(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "Could not receive complete frame",
+        )))
+    } else {
+        Ok(buffer)
+    }
+}
+
 /// A Frame of referenced data to be transmitted using a specified version.
 ///
 /// This struct references the data that has been transmitted.  It is essentially a sending frame.
@@ -162,13 +280,31 @@ impl<'a> FrameRef<'a> {
 
     /// Write the frame to the given writer.
     ///
+    /// With version 2 frames, the data is compressed if it is large enough to benefit.
+    ///
     /// # Errors
     ///
     /// Returns a FrameError if an IO error occurs.
     pub fn write<W: Write>(self, writer: &mut W) -> Result<(), FrameError> {
-        let frame_header = match self.version {
-            FrameVersion::V1 => FrameHeader::v1(self.data.len() as u32),
+        #[cfg(not(feature = "transport-compression"))]
+        let (frame_header, payload) = match self.version {
+            FrameVersion::V1 => (FrameHeader::v1(self.data.len() as u32), self.data),
+        };
+        #[cfg(feature = "transport-compression")]
+        let (frame_header, payload) = match self.version {
+            FrameVersion::V1 => (
+                FrameHeader::v1(self.data.len() as u32),
+                Cow::Borrowed(self.data),
+            ),
+            FrameVersion::V2 => {
+                let (compression, payload) = compress(self.data)?;
+                (
+                    FrameHeader::v2(payload.len() as u32, compression, crc32fast::hash(&payload)),
+                    payload,
+                )
+            }
         };
+
         loop {
             match frame_header.write(writer) {
                 Err(FrameError::IoError(ref e)) if e.kind() == std::io::ErrorKind::WouldBlock => {
@@ -180,7 +316,7 @@ impl<'a> FrameRef<'a> {
             }
         }
 
-        let mut buffer = self.data;
+        let mut buffer = &payload[..];
         while !buffer.is_empty() {
             match writer.write(buffer) {
                 Ok(0) => {
@@ -202,12 +338,41 @@ impl<'a> FrameRef<'a> {
     }
 }
 
+/// Compress the given data, if it is large enough to benefit from compression and small enough to
+/// be decompressed by the receiver.
+///
+/// Returns the compression that was applied, along with the resulting payload.
+#[cfg(feature = "transport-compression")]
+fn compress(data: &[u8]) -> Result<(FrameCompression, Cow<[u8]>), FrameError> {
+    if data.len() < COMPRESSION_THRESHOLD || data.len() > MAX_FRAME_PAYLOAD {
+        return Ok((FrameCompression::None, Cow::Borrowed(data)));
+    }
+
+    let mut encoder = DeflateEncoder::new(Vec::with_capacity(data.len() / 2), Compression::fast());
+    encoder.write_all(data)?;
+    let compressed = encoder.finish()?;
+
+    if compressed.len() < data.len() {
+        Ok((FrameCompression::Deflate, Cow::Owned(compressed)))
+    } else {
+        Ok((FrameCompression::None, Cow::Borrowed(data)))
+    }
+}
+
 /// A FrameHeader.
 ///
 /// Each variant corresponds to the implementation for a given version.
 #[derive(Debug, PartialEq)]
 enum FrameHeader {
-    V1 { length: u32 },
+    V1 {
+        length: u32,
+    },
+    #[cfg(feature = "transport-compression")]
+    V2 {
+        length: u32,
+        compression: FrameCompression,
+        checksum: u32,
+    },
 }
 
 impl FrameHeader {
@@ -216,6 +381,16 @@ impl FrameHeader {
         FrameHeader::V1 { length }
     }
 
+    /// Construct a version 2 frame header.
+    #[cfg(feature = "transport-compression")]
+    fn v2(length: u32, compression: FrameCompression, checksum: u32) -> Self {
+        FrameHeader::V2 {
+            length,
+            compression,
+            checksum,
+        }
+    }
+
     /// Read a FrameHeader from the given reader.
     ///
     /// This function uses the first 2 bytes of the stream to read the version, and constructs the
@@ -235,23 +410,25 @@ impl FrameHeader {
             1 => {
                 // Header length + checksum byte
                 let mut buffer = [0u8; HEADER_LENGTH + 1];
-                let mut cursor = Cursor::new(&mut buffer[..]);
-                cursor.write_u16::<BigEndian>(1u16)?;
-
-                let n = reader.read(&mut cursor.get_mut()[std::mem::size_of::<u16>()..])?;
-                if n != HEADER_LENGTH + 1 - std::mem::size_of::<u16>() {
-                    return Err(FrameError::InvalidHeaderLength(n));
-                }
-
-                let checksum = compute_checksum(&cursor.get_ref()[..HEADER_LENGTH]);
-                if checksum != cursor.get_ref()[HEADER_LENGTH] {
-                    return Err(FrameError::InvalidChecksum);
-                }
-
+                let mut cursor = read_header_bytes(reader, 1, &mut buffer)?;
                 Ok(FrameHeader::V1 {
                     length: cursor.read_u32::<BigEndian>()?,
                 })
             }
+            #[cfg(feature = "transport-compression")]
+            2 => {
+                // Header length + checksum byte
+                let mut buffer = [0u8; V2_HEADER_LENGTH + 1];
+                let mut cursor = read_header_bytes(reader, 2, &mut buffer)?;
+                let length = cursor.read_u32::<BigEndian>()?;
+                let compression = FrameCompression::from_u8(cursor.read_u8()?)?;
+                let checksum = cursor.read_u32::<BigEndian>()?;
+                Ok(FrameHeader::V2 {
+                    length,
+                    compression,
+                    checksum,
+                })
+            }
             _ => Err(FrameError::UnsupportedVersion),
         }
     }
@@ -279,12 +456,63 @@ impl FrameHeader {
 
                 writer.write_all(cursor.into_inner())?;
             }
+            #[cfg(feature = "transport-compression")]
+            FrameHeader::V2 {
+                length,
+                compression,
+                checksum,
+            } => {
+                let mut header_bytes = [0u8; V2_HEADER_LENGTH + 1];
+                let mut cursor = Cursor::new(&mut header_bytes[..]);
+
+                cursor.write_u16::<BigEndian>(2)?;
+                cursor.write_u32::<BigEndian>(length)?;
+                cursor.write_u8(compression as u8)?;
+                cursor.write_u32::<BigEndian>(checksum)?;
+
+                cursor.get_mut()[V2_HEADER_LENGTH] =
+                    compute_checksum(&cursor.get_ref()[..V2_HEADER_LENGTH]);
+
+                writer.write_all(cursor.into_inner())?;
+            }
         }
 
         Ok(())
     }
 }
 
+/// Read the remainder of a header, whose version has already been read, into the given buffer
+/// and verify its checksum.
+///
+/// The buffer must be the length of the header plus its checksum byte. Returns a cursor over the
+/// buffer, positioned after the version.
+fn read_header_bytes<'a, R: Read>(
+    reader: &mut R,
+    version: u16,
+    buffer: &'a mut [u8],
+) -> Result<Cursor<&'a [u8]>, FrameError> {
+    let header_length = buffer.len() - 1;
+    let mut cursor = Cursor::new(&mut buffer[..]);
+    cursor.write_u16::<BigEndian>(version)?;
+
+    let n = reader.read(&mut cursor.get_mut()[std::mem::size_of::<u16>()..])?;
+    if n != header_length + 1 - std::mem::size_of::<u16>() {
+        return Err(FrameError::InvalidHeaderLength {
+            expected: header_length + 1 - std::mem::size_of::<u16>(),
+            actual: n,
+        });
+    }
+
+    let checksum = compute_checksum(&buffer[..header_length]);
+    if checksum != buffer[header_length] {
+        return Err(FrameError::InvalidChecksum);
+    }
+
+    let mut cursor = Cursor::new(&*buffer);
+    cursor.set_position(std::mem::size_of::<u16>() as u64);
+    Ok(cursor)
+}
+
 /// Compute a longitudinal check-sum
 fn compute_checksum(buffer: &[u8]) -> u8 {
     // International standard ISO 1155[7] states that a longitudinal redundancy check for a
@@ -313,8 +541,9 @@ pub enum FrameNegotiation {
         min: FrameVersion,
         max: FrameVersion,
     },
-    /// The Inbound variant transmits receives the min and max and decides if it should send its
-    /// version or `0`, depending on whether or not it falls in the range.
+    /// The Inbound variant receives the min and max and responds with the highest version, up to
+    /// and including its own version, that falls in the range, or `0` if there is none. Every
+    /// version before its own is also supported, so that peers with older versions may connect.
     Inbound { version: FrameVersion },
 }
 
@@ -349,23 +578,28 @@ impl FrameNegotiation {
 
                 let frame_version = stream.read_u16::<BigEndian>().map_err(Self::map_io_err)?;
 
-                match frame_version {
-                    0 => Err(FrameError::UnsupportedVersion),
-                    1 => Ok(FrameVersion::V1),
-                    _ => Err(FrameError::UnsupportedVersion),
+                if frame_version < min as u16 || frame_version > max as u16 {
+                    return Err(FrameError::UnsupportedVersion);
                 }
+
+                FrameVersion::from_u16(frame_version).ok_or(FrameError::UnsupportedVersion)
             }
             FrameNegotiation::Inbound { version } => {
                 let min = stream.read_u16::<BigEndian>().map_err(Self::map_io_err)?;
                 let max = stream.read_u16::<BigEndian>().map_err(Self::map_io_err)?;
-                if min > version as u16 || max < version as u16 {
-                    stream.write_u16::<BigEndian>(0).map_err(Self::map_io_err)?;
-                    Err(FrameError::UnsupportedVersion)
-                } else {
-                    stream
-                        .write_u16::<BigEndian>(version as u16)
-                        .map_err(Self::map_io_err)?;
-                    Ok(version)
+
+                let agreed = (version as u16).min(max);
+                match FrameVersion::from_u16(agreed).filter(|_| agreed >= min) {
+                    Some(agreed_version) => {
+                        stream
+                            .write_u16::<BigEndian>(agreed)
+                            .map_err(Self::map_io_err)?;
+                        Ok(agreed_version)
+                    }
+                    None => {
+                        stream.write_u16::<BigEndian>(0).map_err(Self::map_io_err)?;
+                        Err(FrameError::UnsupportedVersion)
+                    }
                 }
             }
         }
@@ -498,10 +732,9 @@ mod tests {
             .expect("Unable to write frame header");
 
         header_cursor.set_position(0);
-        let FrameHeader::V1 { length } =
-            FrameHeader::read(&mut header_cursor).expect("Unable to read header");
+        let frame_header = FrameHeader::read(&mut header_cursor).expect("Unable to read header");
 
-        assert_eq!(100, length);
+        assert_eq!(FrameHeader::v1(100), frame_header);
     }
 
     /// Test that outbound frame version negotiation works:
@@ -676,6 +909,158 @@ mod tests {
         assert_eq!(input.to_vec(), frame.data);
     }
 
+    /// Test that an outbound negotiation offering up to V2 falls back to V1 when the inbound end
+    /// only supports V1, and that an inbound end supporting V2 agrees to V1 with an outbound end
+    /// that only offers V1.
+    #[cfg(feature = "transport-compression")]
+    #[test]
+    fn negotiation_fallback_to_v1() {
+        let (mut tx, mut rx) = stream::byte_stream_pair();
+        let join_handle = thread::spawn(move || {
+            FrameNegotiation::inbound(FrameVersion::V1)
+                .negotiate(&mut rx)
+                .expect("Should have successfully negotiated")
+        });
+        let version = FrameNegotiation::outbound(FrameVersion::V1, FrameVersion::V2)
+            .negotiate(&mut tx)
+            .expect("Unable to negotiate a valid version");
+        assert_eq!(FrameVersion::V1, version);
+        assert_eq!(
+            FrameVersion::V1,
+            join_handle.join().expect("Unable to join thread")
+        );
+
+        let (mut tx, mut rx) = stream::byte_stream_pair();
+        let join_handle = thread::spawn(move || {
+            FrameNegotiation::inbound(FrameVersion::V2)
+                .negotiate(&mut rx)
+                .expect("Should have successfully negotiated")
+        });
+        let version = FrameNegotiation::outbound(FrameVersion::V1, FrameVersion::V1)
+            .negotiate(&mut tx)
+            .expect("Unable to negotiate a valid version");
+        assert_eq!(FrameVersion::V1, version);
+        assert_eq!(
+            FrameVersion::V1,
+            join_handle.join().expect("Unable to join thread")
+        );
+    }
+
+    /// Test that two ends supporting V2 agree on V2.
+    #[cfg(feature = "transport-compression")]
+    #[test]
+    fn negotiation_v2() {
+        let (mut tx, mut rx) = stream::byte_stream_pair();
+        let join_handle = thread::spawn(move || {
+            FrameNegotiation::inbound(FrameVersion::V2)
+                .negotiate(&mut rx)
+                .expect("Should have successfully negotiated")
+        });
+        let version = FrameNegotiation::outbound(FrameVersion::V1, FrameVersion::V2)
+            .negotiate(&mut tx)
+            .expect("Unable to negotiate a valid version");
+        assert_eq!(FrameVersion::V2, version);
+        assert_eq!(
+            FrameVersion::V2,
+            join_handle.join().expect("Unable to join thread")
+        );
+    }
+
+    /// Test that a large, compressible payload is written compressed in a V2 frame, and is read
+    /// back as the original data.
+    #[cfg(feature = "transport-compression")]
+    #[test]
+    fn frame_round_trip_v2_compressed() {
+        let input = b"hello world ".repeat(1000);
+        let mut cursor = Cursor::new(vec![]);
+
+        FrameRef::new(FrameVersion::V2, &input)
+            .write(&mut cursor)
+            .expect("Unable to write data");
+
+        assert!(cursor.get_ref().len() < input.len());
+        assert_eq!(FrameCompression::Deflate as u8, cursor.get_ref()[6]);
+
+        cursor.set_position(0);
+        let frame = Frame::read(&mut cursor).expect("Unable to read frame");
+
+        assert_eq!(input, frame.into_inner());
+    }
+
+    /// Test that a small payload is written uncompressed in a V2 frame, and is read back as the
+    /// original data.
+    #[cfg(feature = "transport-compression")]
+    #[test]
+    fn frame_round_trip_v2_uncompressed() {
+        let input = b"hello world";
+        let mut cursor = Cursor::new(vec![]);
+
+        FrameRef::new(FrameVersion::V2, input)
+            .write(&mut cursor)
+            .expect("Unable to write data");
+
+        assert_eq!(V2_HEADER_LENGTH + 1 + input.len(), cursor.get_ref().len());
+        assert_eq!(FrameCompression::None as u8, cursor.get_ref()[6]);
+
+        cursor.set_position(0);
+        let frame = Frame::read(&mut cursor).expect("Unable to read frame");
+
+        assert_eq!(input.to_vec(), frame.into_inner());
+    }
+
+    /// Test that a V2 frame whose payload decompresses to more than the maximum payload size is
+    /// rejected, rather than being decompressed in full.
+    #[cfg(feature = "transport-compression")]
+    #[test]
+    fn frame_v2_decompression_bomb() {
+        let chunk = vec![0u8; 1024 * 1024];
+        let mut encoder = DeflateEncoder::new(vec![], Compression::best());
+        for _ in 0..(MAX_FRAME_PAYLOAD / chunk.len() + 1) {
+            encoder.write_all(&chunk).expect("Unable to compress chunk");
+        }
+        let payload = encoder.finish().expect("Unable to finish compression");
+        assert!(payload.len() < MAX_FRAME_PAYLOAD / 100);
+
+        let mut cursor = Cursor::new(vec![]);
+        FrameHeader::v2(
+            payload.len() as u32,
+            FrameCompression::Deflate,
+            crc32fast::hash(&payload),
+        )
+        .write(&mut cursor)
+        .expect("Unable to write header");
+        cursor.write_all(&payload).expect("Unable to write payload");
+
+        cursor.set_position(0);
+        match Frame::read(&mut cursor) {
+            Err(FrameError::DecompressionFailure(_)) => (),
+            Err(err) => panic!("Produced invalid error: {}", err),
+            Ok(_) => panic!("Should not have produced a frame"),
+        }
+    }
+
+    /// Test that a V2 frame whose payload was corrupted in transit fails its payload checksum.
+    #[cfg(feature = "transport-compression")]
+    #[test]
+    fn frame_v2_invalid_payload_checksum() {
+        let input = b"hello world ".repeat(1000);
+        let mut cursor = Cursor::new(vec![]);
+
+        FrameRef::new(FrameVersion::V2, &input)
+            .write(&mut cursor)
+            .expect("Unable to write data");
+
+        let last = cursor.get_ref().len() - 1;
+        cursor.get_mut()[last] ^= 0xff;
+
+        cursor.set_position(0);
+        match Frame::read(&mut cursor) {
+            Err(FrameError::InvalidPayloadChecksum) => (),
+            Err(err) => panic!("Produced invalid error: {}", err),
+            Ok(_) => panic!("Should not have produced a frame"),
+        }
+    }
+
     #[cfg(not(target_os = "unix"))]
     mod stream {
         use std::io::{Error as IoError, Read, Write};
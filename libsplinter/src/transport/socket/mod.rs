@@ -17,6 +17,8 @@ mod tcp;
 mod tls;
 
 pub use tcp::TcpTransport;
+#[cfg(feature = "tls-reload")]
+pub use tls::TlsTransportReloader;
 pub use tls::{TlsConnection, TlsInitError, TlsTransport};
 
 #[cfg(test)]
@@ -26,19 +26,46 @@ use std::io;
 use std::net::{Ipv4Addr, Ipv6Addr, TcpListener, TcpStream};
 use std::os::unix::io::{AsRawFd, RawFd};
 use std::path::Path;
-
+#[cfg(feature = "tls-reload")]
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+
+#[cfg(feature = "tls-reload")]
+use crate::error::InternalError;
+#[cfg(feature = "tls-reload")]
+use crate::transport::tls::reload::TlsReloader;
 use crate::transport::{
     AcceptError, ConnectError, Connection, DisconnectError, ListenError, Listener, RecvError,
     SendError, Transport,
 };
 
-use super::frame::{Frame, FrameError, FrameNegotiation, FrameRef, FrameVersion};
+use super::frame::{
+    Frame, FrameError, FrameNegotiation, FrameRef, FrameVersion, LATEST_FRAME_VERSION,
+};
 
 /// tls:// is deprecated, tcps:// should be used instead
 const DEPRECATED_PROTOCOL_PREFIX: &str = "tls://";
 const PROTOCOL_PREFIX: &str = "tcps://";
 
 pub struct TlsTransport {
+    #[cfg(feature = "tls-reload")]
+    files: TlsFiles,
+    contexts: Arc<RwLock<TlsContexts>>,
+}
+
+/// The files the TLS connector and acceptor are loaded from.
+#[derive(Clone)]
+struct TlsFiles {
+    ca_cert: Option<String>,
+    client_key: String,
+    client_cert: String,
+    server_key: String,
+    server_cert: String,
+}
+
+/// The TLS connector and acceptor used for new connections.
+#[derive(Clone)]
+struct TlsContexts {
     connector: SslConnector,
     acceptor: SslAcceptor,
 }
@@ -51,44 +78,119 @@ impl TlsTransport {
         server_key: String,
         server_cert: String,
     ) -> Result<Self, TlsInitError> {
-        let client_cert_path = Path::new(&client_cert);
-        let client_key_path = Path::new(&client_key);
-        let server_cert_path = Path::new(&server_cert);
-        let server_key_path = Path::new(&server_key);
-
-        // Build TLS Connector
-        let mut connector = SslConnector::builder(SslMethod::tls())?;
-        connector.set_private_key_file(&client_key_path, SslFiletype::PEM)?;
-        connector.set_certificate_chain_file(client_cert_path)?;
-        connector.check_private_key()?;
-
-        // Build TLS Acceptor
-        let mut acceptor = SslAcceptor::mozilla_modern(SslMethod::tls())?;
-        acceptor.set_private_key_file(server_key_path, SslFiletype::PEM)?;
-        acceptor.set_certificate_chain_file(&server_cert_path)?;
-        acceptor.check_private_key()?;
-
-        // if ca_cert is provided set as accept cert, otherwise set verify to none
-        let (acceptor, connector) = {
-            if let Some(ca_cert) = ca_cert {
-                let ca_cert_path = Path::new(&ca_cert);
-                acceptor.set_ca_file(ca_cert_path)?;
-                connector.set_ca_file(ca_cert_path)?;
-                connector.set_verify(SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT);
-                acceptor.set_verify(SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT);
-            } else {
-                connector.set_verify(SslVerifyMode::NONE);
-                acceptor.set_verify(SslVerifyMode::NONE);
-            }
-
-            (acceptor.build(), connector.build())
+        let files = TlsFiles {
+            ca_cert,
+            client_key,
+            client_cert,
+            server_key,
+            server_cert,
         };
+        let contexts = Arc::new(RwLock::new(build_contexts(&files)?));
 
         Ok(TlsTransport {
-            connector,
-            acceptor,
+            #[cfg(feature = "tls-reload")]
+            files,
+            contexts,
         })
     }
+
+    /// Returns a reloader which rebuilds the TLS connector and acceptor of this transport, and of
+    /// its listeners, from the transport's files.
+    ///
+    /// Connections made or accepted after a reload use the new certificates; existing connections
+    /// are not affected.
+    #[cfg(feature = "tls-reload")]
+    pub fn reloader(&self) -> TlsTransportReloader {
+        TlsTransportReloader {
+            files: self.files.clone(),
+            contexts: self.contexts.clone(),
+        }
+    }
+
+    fn contexts(&self) -> TlsContexts {
+        read_contexts(&self.contexts)
+    }
+}
+
+fn build_contexts(files: &TlsFiles) -> Result<TlsContexts, TlsInitError> {
+    let client_cert_path = Path::new(&files.client_cert);
+    let client_key_path = Path::new(&files.client_key);
+    let server_cert_path = Path::new(&files.server_cert);
+    let server_key_path = Path::new(&files.server_key);
+
+    // Build TLS Connector
+    let mut connector = SslConnector::builder(SslMethod::tls())?;
+    connector.set_private_key_file(&client_key_path, SslFiletype::PEM)?;
+    connector.set_certificate_chain_file(client_cert_path)?;
+    connector.check_private_key()?;
+
+    // Build TLS Acceptor
+    let mut acceptor = SslAcceptor::mozilla_modern(SslMethod::tls())?;
+    acceptor.set_private_key_file(server_key_path, SslFiletype::PEM)?;
+    acceptor.set_certificate_chain_file(&server_cert_path)?;
+    acceptor.check_private_key()?;
+
+    // if ca_cert is provided set as accept cert, otherwise set verify to none
+    if let Some(ca_cert) = &files.ca_cert {
+        let ca_cert_path = Path::new(ca_cert);
+        acceptor.set_ca_file(ca_cert_path)?;
+        connector.set_ca_file(ca_cert_path)?;
+        connector.set_verify(SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT);
+        acceptor.set_verify(SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT);
+    } else {
+        connector.set_verify(SslVerifyMode::NONE);
+        acceptor.set_verify(SslVerifyMode::NONE);
+    }
+
+    Ok(TlsContexts {
+        connector: connector.build(),
+        acceptor: acceptor.build(),
+    })
+}
+
+// The contexts are only ever replaced whole, so a poisoned lock can still be used.
+fn read_contexts(contexts: &RwLock<TlsContexts>) -> TlsContexts {
+    contexts
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+}
+
+/// Rebuilds the TLS connector and acceptor of a `TlsTransport` from its files.
+#[cfg(feature = "tls-reload")]
+pub struct TlsTransportReloader {
+    files: TlsFiles,
+    contexts: Arc<RwLock<TlsContexts>>,
+}
+
+#[cfg(feature = "tls-reload")]
+impl TlsReloader for TlsTransportReloader {
+    fn name(&self) -> &str {
+        "network"
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        let mut files = vec![
+            PathBuf::from(&self.files.client_key),
+            PathBuf::from(&self.files.client_cert),
+            PathBuf::from(&self.files.server_key),
+            PathBuf::from(&self.files.server_cert),
+        ];
+        if let Some(ca_cert) = &self.files.ca_cert {
+            files.push(PathBuf::from(ca_cert));
+        }
+        files
+    }
+
+    fn reload(&self) -> Result<(), InternalError> {
+        let contexts =
+            build_contexts(&self.files).map_err(|err| InternalError::from_source(Box::new(err)))?;
+        *self
+            .contexts
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = contexts;
+        Ok(())
+    }
 }
 
 fn endpoint_to_dns_name(endpoint: &str) -> Result<String, ParseError> {
@@ -130,9 +232,9 @@ impl Transport for TlsTransport {
         let dns_name = endpoint_to_dns_name(address)?;
 
         let stream = TcpStream::connect(address)?;
-        let mut tls_stream = self.connector.connect(&dns_name, stream)?;
+        let mut tls_stream = self.contexts().connector.connect(&dns_name, stream)?;
 
-        let frame_version = FrameNegotiation::outbound(FrameVersion::V1, FrameVersion::V1)
+        let frame_version = FrameNegotiation::outbound(FrameVersion::V1, LATEST_FRAME_VERSION)
             .negotiate(&mut tls_stream)
             .map_err(|err| match err {
                 FrameError::UnsupportedVersion => ConnectError::ProtocolError(
@@ -170,28 +272,27 @@ impl Transport for TlsTransport {
             listener: TcpListener::bind(address).map_err(|err| {
                 ListenError::IoError(format!("Failed to bind to {}", address), err)
             })?,
-            acceptor: self.acceptor.clone(),
+            contexts: self.contexts.clone(),
         }))
     }
 }
 
 pub struct TlsListener {
     listener: TcpListener,
-    acceptor: SslAcceptor,
+    contexts: Arc<RwLock<TlsContexts>>,
 }
 
 impl Listener for TlsListener {
     fn accept(&mut self) -> Result<Box<dyn Connection>, AcceptError> {
         let (stream, _) = self.listener.accept()?;
-        let mut tls_stream = self.acceptor.accept(stream)?;
+        let mut tls_stream = read_contexts(&self.contexts).acceptor.accept(stream)?;
 
-        let frame_version = FrameNegotiation::inbound(FrameVersion::V1)
+        let frame_version = FrameNegotiation::inbound(LATEST_FRAME_VERSION)
             .negotiate(&mut tls_stream)
             .map_err(|err| match err {
                 FrameError::UnsupportedVersion => AcceptError::ProtocolError(format!(
                     "Local {} protocol version {} not supported by remote",
-                    PROTOCOL_PREFIX,
-                    FrameVersion::V1
+                    PROTOCOL_PREFIX, LATEST_FRAME_VERSION
                 )),
                 FrameError::IoError(err) => AcceptError::from(err),
                 err => AcceptError::ProtocolError(format!("Unexpected protocol error: {}", err)),
@@ -456,6 +557,16 @@ pub(crate) mod tests {
         tests::test_transport(transport, "127.0.0.1:0");
     }
 
+    /// Test that a failed reload leaves the transport using its previous certificates.
+    #[cfg(feature = "tls-reload")]
+    #[test]
+    fn test_failed_reload() {
+        // The certificate files are removed once the transport has been created
+        let transport = create_test_tls_transport(false);
+        assert!(transport.reloader().reload().is_err());
+        tests::test_transport(transport, "127.0.0.1:0");
+    }
+
     #[cfg(not(unix))]
     #[test]
     fn test_poll_no_verify() {
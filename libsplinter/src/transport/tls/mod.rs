@@ -12,6 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "tls-reload")]
+pub mod reload;
+
 #[cfg(feature = "ws-transport")]
 use std::path::Path;
 
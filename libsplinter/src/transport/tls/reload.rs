@@ -0,0 +1,410 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Reloading of TLS certificates and keys while a node is running.
+//!
+//! A [`TlsReloader`] rebuilds a TLS context from the files it was loaded from. Only connections
+//! made after a reload use the new context; established connections keep the context they were
+//! created with. If a reload fails, the previous context remains in use.
+//!
+//! A [`TlsReloadWatcher`] reloads its contexts when their files change, or when its reload trigger
+//! is set (for example, by a SIGHUP handler), and records the outcome of every reload in a
+//! [`TlsReloadStatus`].
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::error::InternalError;
+use crate::threading::lifecycle::ShutdownHandle;
+
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A TLS context that can be rebuilt from the files it was loaded from.
+pub trait TlsReloader: Send {
+    /// Returns the name of the TLS context, used when logging and reporting its status.
+    fn name(&self) -> &str;
+
+    /// Returns the files the TLS context is loaded from.
+    fn files(&self) -> Vec<PathBuf>;
+
+    /// Rebuilds the TLS context from its files.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InternalError` if the files could not be loaded; the previous context remains
+    /// in use.
+    fn reload(&self) -> Result<(), InternalError>;
+}
+
+/// The outcome of the reloads of a single TLS context.
+#[derive(Clone, Debug)]
+pub struct TlsReloadReport {
+    name: String,
+    last_attempt: Option<SystemTime>,
+    last_success: Option<SystemTime>,
+    last_error: Option<String>,
+}
+
+impl TlsReloadReport {
+    /// Returns the name of the TLS context
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the time of the most recent reload, if any
+    pub fn last_attempt(&self) -> Option<SystemTime> {
+        self.last_attempt
+    }
+
+    /// Returns the time of the most recent successful reload, if any
+    pub fn last_success(&self) -> Option<SystemTime> {
+        self.last_success
+    }
+
+    /// Returns the error of the most recent reload, if it failed
+    pub fn last_error(&self) -> Option<&str> {
+        self.last_error.as_deref()
+    }
+}
+
+/// The reload status of a set of TLS contexts.
+///
+/// The status is cheaply cloneable; all clones share the same reports, so a clone may be given to
+/// the REST API before the watcher that updates it is started.
+#[derive(Clone, Default)]
+pub struct TlsReloadStatus {
+    reports: Arc<Mutex<BTreeMap<String, TlsReloadReport>>>,
+}
+
+impl TlsReloadStatus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the report of each TLS context, ordered by name.
+    pub fn reports(&self) -> Vec<TlsReloadReport> {
+        self.reports_mut().values().cloned().collect()
+    }
+
+    fn add_context(&self, name: &str) {
+        self.reports_mut()
+            .entry(name.to_string())
+            .or_insert_with(|| TlsReloadReport {
+                name: name.to_string(),
+                last_attempt: None,
+                last_success: None,
+                last_error: None,
+            });
+    }
+
+    fn record(&self, name: &str, result: &Result<(), InternalError>) {
+        self.add_context(name);
+        let mut reports = self.reports_mut();
+        if let Some(report) = reports.get_mut(name) {
+            let now = SystemTime::now();
+            report.last_attempt = Some(now);
+            match result {
+                Ok(()) => {
+                    report.last_success = Some(now);
+                    report.last_error = None;
+                }
+                Err(err) => report.last_error = Some(err.to_string()),
+            }
+        }
+    }
+
+    // The reports are left consistent by every method, so a poisoned lock can still be used.
+    fn reports_mut(&self) -> MutexGuard<BTreeMap<String, TlsReloadReport>> {
+        self.reports
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Builder for creating a `TlsReloadWatcher`
+#[derive(Default)]
+pub struct TlsReloadWatcherBuilder {
+    reloaders: Vec<Box<dyn TlsReloader>>,
+    poll_interval: Option<Duration>,
+    status: Option<TlsReloadStatus>,
+}
+
+impl TlsReloadWatcherBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a TLS context to watch.
+    pub fn with_reloader(mut self, reloader: Box<dyn TlsReloader>) -> Self {
+        self.reloaders.push(reloader);
+        self
+    }
+
+    /// Sets how often the files of the TLS contexts are checked for changes. Defaults to 5
+    /// seconds.
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = Some(poll_interval);
+        self
+    }
+
+    /// Sets the status in which the outcome of each reload is recorded. Defaults to a new status.
+    pub fn with_status(mut self, status: TlsReloadStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Starts the `TlsReloadWatcher`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InternalError` if the watcher thread could not be started.
+    pub fn start(self) -> Result<TlsReloadWatcher, InternalError> {
+        let poll_interval = self.poll_interval.unwrap_or(DEFAULT_POLL_INTERVAL);
+        let status = self.status.unwrap_or_default();
+        let trigger = Arc::new(AtomicBool::new(false));
+
+        let mut watched = self
+            .reloaders
+            .into_iter()
+            .map(|reloader| {
+                status.add_context(reloader.name());
+                let modified = modified_times(&*reloader);
+                (reloader, modified)
+            })
+            .collect::<Vec<_>>();
+
+        let (shutdown_tx, shutdown_rx) = channel();
+        let thread_status = status.clone();
+        let thread_trigger = trigger.clone();
+        let join_handle = thread::Builder::new()
+            .name("TlsReloadWatcher".into())
+            .spawn(move || loop {
+                match shutdown_rx.recv_timeout(poll_interval) {
+                    Err(RecvTimeoutError::Timeout) => (),
+                    Ok(()) | Err(RecvTimeoutError::Disconnected) => break,
+                }
+
+                let triggered = thread_trigger.swap(false, Ordering::SeqCst);
+                for (reloader, modified) in watched.iter_mut() {
+                    let current = modified_times(&**reloader);
+                    if triggered {
+                        info!("Reloading TLS context {} on request", reloader.name());
+                    } else if current != *modified {
+                        info!(
+                            "Reloading TLS context {} after its files changed",
+                            reloader.name()
+                        );
+                    } else {
+                        continue;
+                    }
+                    *modified = current;
+
+                    let result = reloader.reload();
+                    match &result {
+                        Ok(()) => info!("Reloaded TLS context {}", reloader.name()),
+                        Err(err) => error!(
+                            "Unable to reload TLS context {}, continuing to use the previous \
+                            context: {}",
+                            reloader.name(),
+                            err
+                        ),
+                    }
+                    thread_status.record(reloader.name(), &result);
+                }
+            })
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(TlsReloadWatcher {
+            join_handle,
+            shutdown_tx,
+            trigger,
+            status,
+        })
+    }
+}
+
+/// Reloads a set of TLS contexts when their files change, or when requested.
+pub struct TlsReloadWatcher {
+    join_handle: thread::JoinHandle<()>,
+    shutdown_tx: Sender<()>,
+    trigger: Arc<AtomicBool>,
+    status: TlsReloadStatus,
+}
+
+impl TlsReloadWatcher {
+    /// Returns the trigger that requests a reload of every TLS context.
+    ///
+    /// Setting the trigger to `true` causes all contexts to be reloaded at the next poll, whether
+    /// or not their files have changed. The trigger is an `AtomicBool` so that it may be set from
+    /// a signal handler.
+    pub fn reload_trigger(&self) -> Arc<AtomicBool> {
+        self.trigger.clone()
+    }
+
+    /// Returns the status in which the outcome of each reload is recorded.
+    pub fn status(&self) -> TlsReloadStatus {
+        self.status.clone()
+    }
+}
+
+impl ShutdownHandle for TlsReloadWatcher {
+    fn signal_shutdown(&mut self) {
+        if self.shutdown_tx.send(()).is_err() {
+            warn!("TLS reload watcher is no longer running");
+        }
+    }
+
+    fn wait_for_shutdown(self) -> Result<(), InternalError> {
+        debug!("Shutting down TLS reload watcher...");
+        self.join_handle.join().map_err(|err| {
+            InternalError::with_message(format!(
+                "TLS reload watcher thread did not shutdown correctly: {:?}",
+                err
+            ))
+        })?;
+        debug!("Shutting down TLS reload watcher (complete)");
+        Ok(())
+    }
+}
+
+/// Returns the modification time of each file of the given context. Files that cannot be read
+/// have no modification time, so that their reappearance is detected as a change.
+fn modified_times(reloader: &dyn TlsReloader) -> Vec<Option<SystemTime>> {
+    reloader
+        .files()
+        .iter()
+        .map(|path| fs::metadata(path).and_then(|meta| meta.modified()).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::AtomicUsize;
+    use std::time::Instant;
+
+    use tempfile::Builder;
+
+    const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+    /// A reloader that counts its reloads, and fails if its file cannot be read.
+    struct MockReloader {
+        path: PathBuf,
+        reloads: Arc<AtomicUsize>,
+    }
+
+    impl TlsReloader for MockReloader {
+        fn name(&self) -> &str {
+            "mock"
+        }
+
+        fn files(&self) -> Vec<PathBuf> {
+            vec![self.path.clone()]
+        }
+
+        fn reload(&self) -> Result<(), InternalError> {
+            self.reloads.fetch_add(1, Ordering::SeqCst);
+            fs::read(&self.path)
+                .map(|_| ())
+                .map_err(|err| InternalError::from_source(Box::new(err)))
+        }
+    }
+
+    /// Waits until the given condition holds, failing the test after 10 seconds.
+    fn wait_for<F: Fn() -> bool>(condition: F) {
+        let start = Instant::now();
+        while !condition() {
+            assert!(
+                start.elapsed() < Duration::from_secs(10),
+                "waited for too long"
+            );
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    /// Test that a context is reloaded on request, and that the reload is reported.
+    #[test]
+    fn test_reload_on_trigger() {
+        let temp_dir = Builder::new().prefix("tls-reload").tempdir().unwrap();
+        let path = temp_dir.path().join("server.crt");
+        fs::write(&path, "cert").unwrap();
+
+        let reloads = Arc::new(AtomicUsize::new(0));
+        let mut watcher = TlsReloadWatcherBuilder::new()
+            .with_reloader(Box::new(MockReloader {
+                path,
+                reloads: reloads.clone(),
+            }))
+            .with_poll_interval(POLL_INTERVAL)
+            .start()
+            .expect("Unable to start watcher");
+
+        let reports = watcher.status().reports();
+        assert_eq!(1, reports.len());
+        assert_eq!("mock", reports[0].name());
+        assert!(reports[0].last_attempt().is_none());
+
+        watcher.reload_trigger().store(true, Ordering::SeqCst);
+        wait_for(|| reloads.load(Ordering::SeqCst) == 1);
+        wait_for(|| watcher.status().reports()[0].last_success().is_some());
+        assert!(watcher.status().reports()[0].last_error().is_none());
+
+        watcher.signal_shutdown();
+        watcher
+            .wait_for_shutdown()
+            .expect("Unable to shutdown watcher");
+    }
+
+    /// Test that a context is reloaded when its files change, and that a failed reload is
+    /// reported until the next successful reload.
+    #[test]
+    fn test_reload_on_change() {
+        let temp_dir = Builder::new().prefix("tls-reload").tempdir().unwrap();
+        let path = temp_dir.path().join("server.crt");
+        fs::write(&path, "cert").unwrap();
+
+        let reloads = Arc::new(AtomicUsize::new(0));
+        let mut watcher = TlsReloadWatcherBuilder::new()
+            .with_reloader(Box::new(MockReloader {
+                path: path.clone(),
+                reloads: reloads.clone(),
+            }))
+            .with_poll_interval(POLL_INTERVAL)
+            .start()
+            .expect("Unable to start watcher");
+
+        // Removing the file is a change, which fails to reload
+        fs::remove_file(&path).unwrap();
+        wait_for(|| reloads.load(Ordering::SeqCst) == 1);
+        wait_for(|| watcher.status().reports()[0].last_error().is_some());
+        assert!(watcher.status().reports()[0].last_success().is_none());
+
+        fs::write(&path, "new cert").unwrap();
+        wait_for(|| reloads.load(Ordering::SeqCst) == 2);
+        wait_for(|| watcher.status().reports()[0].last_success().is_some());
+        assert!(watcher.status().reports()[0].last_error().is_none());
+
+        watcher.signal_shutdown();
+        watcher
+            .wait_for_shutdown()
+            .expect("Unable to shutdown watcher");
+    }
+}
@@ -276,7 +276,7 @@ mod tests {
     use splinter::admin::{
         messages::{
             AuthorizationType, CircuitProposal, CircuitStatus, CreateCircuit, DurabilityType,
-            PersistenceType, ProposalType, RouteType, SplinterNode,
+            PersistenceType, ProposalType, RouteType, SplinterNode, VotingPolicy,
         },
         service::proposal_store::{
             error::ProposalStoreError, proposal_iter::ProposalIter, ProposalStore,
@@ -832,6 +832,8 @@ mod tests {
                 persistence: PersistenceType::Any,
                 durability: DurabilityType::NoDurability,
                 routes: RouteType::Any,
+                relay_nodes: vec![],
+                voting_policy: VotingPolicy::default(),
                 circuit_management_type: "mgmt_type_1".into(),
                 application_metadata: vec![],
                 comments: Some("mock circuit 1".into()),
@@ -842,6 +844,7 @@ mod tests {
             votes: vec![],
             requester: vec![],
             requester_node_id: "node_id".into(),
+            expires_at: None,
         }
     }
 
@@ -858,6 +861,8 @@ mod tests {
                 persistence: PersistenceType::Any,
                 durability: DurabilityType::NoDurability,
                 routes: RouteType::Any,
+                relay_nodes: vec![],
+                voting_policy: VotingPolicy::default(),
                 circuit_management_type: "mgmt_type_2".into(),
                 application_metadata: vec![],
                 comments: Some("mock circuit 2".into()),
@@ -868,6 +873,7 @@ mod tests {
             votes: vec![],
             requester: vec![],
             requester_node_id: "node_id".into(),
+            expires_at: None,
         }
     }
 
@@ -888,6 +894,8 @@ mod tests {
                 persistence: PersistenceType::Any,
                 durability: DurabilityType::NoDurability,
                 routes: RouteType::Any,
+                relay_nodes: vec![],
+                voting_policy: VotingPolicy::default(),
                 circuit_management_type: "mgmt_type_2".into(),
                 application_metadata: vec![],
                 comments: Some("mock circuit 3".into()),
@@ -898,6 +906,7 @@ mod tests {
             votes: vec![],
             requester: vec![],
             requester_node_id: "node_id".into(),
+            expires_at: None,
         }
     }
 
@@ -147,7 +147,7 @@ mod tests {
     use splinter::admin::{
         messages::{
             AuthorizationType, CircuitProposal, CircuitStatus, CreateCircuit, DurabilityType,
-            PersistenceType, ProposalType, RouteType,
+            PersistenceType, ProposalType, RouteType, VotingPolicy,
         },
         service::proposal_store::{
             error::ProposalStoreError, proposal_iter::ProposalIter, ProposalStore,
@@ -306,6 +306,8 @@ mod tests {
                 persistence: PersistenceType::Any,
                 durability: DurabilityType::NoDurability,
                 routes: RouteType::Any,
+                relay_nodes: vec![],
+                voting_policy: VotingPolicy::default(),
                 circuit_management_type: "mgmt_type".into(),
                 application_metadata: vec![],
                 comments: Some("mock circuit".into()),
@@ -316,6 +318,7 @@ mod tests {
             votes: vec![],
             requester: vec![],
             requester_node_id: "node_id".into(),
+            expires_at: None,
         }
     }
 
@@ -38,6 +38,9 @@ impl<'a> From<&'a CircuitProposal> for ProposalResponse<'a> {
             ProposalType::AddNode => "AddNode",
             ProposalType::RemoveNode => "RemoveNode",
             ProposalType::Disband => "Disband",
+            ProposalType::Suspend => "Suspend",
+            ProposalType::Resume => "Resume",
+            ProposalType::UpdateNodeKeys => "UpdateNodeKeys",
         };
 
         Self {
@@ -32,6 +32,8 @@ pub(crate) struct ProposalResponse<'a> {
     #[serde(serialize_with = "as_hex")]
     pub requester: &'a [u8],
     pub requester_node_id: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
 }
 
 impl<'a> TryFrom<&'a CircuitProposal> for ProposalResponse<'a> {
@@ -44,6 +46,9 @@ impl<'a> TryFrom<&'a CircuitProposal> for ProposalResponse<'a> {
             ProposalType::AddNode => "AddNode",
             ProposalType::RemoveNode => "RemoveNode",
             ProposalType::Disband => "Disband",
+            ProposalType::Suspend => "Suspend",
+            ProposalType::Resume => "Resume",
+            ProposalType::UpdateNodeKeys => "UpdateNodeKeys",
         };
 
         Ok(Self {
@@ -54,6 +59,7 @@ impl<'a> TryFrom<&'a CircuitProposal> for ProposalResponse<'a> {
             votes: proposal.votes.iter().map(VoteResponse::from).collect(),
             requester: &proposal.requester,
             requester_node_id: &proposal.requester_node_id,
+            expires_at: proposal.expires_at,
         })
     }
 }
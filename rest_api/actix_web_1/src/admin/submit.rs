@@ -14,9 +14,18 @@
 use actix_web::HttpResponse;
 use futures::{Future, IntoFuture};
 
+#[cfg(feature = "audit-log")]
+use splinter::admin::messages::circuit_management_action;
 use splinter::admin::service::{AdminCommands, AdminServiceError};
 use splinter::protos::admin::CircuitManagementPayload;
 use splinter::rest_api::actix_web_1::{into_protobuf, Method, ProtocolVersionRangeGuard, Resource};
+#[cfg(feature = "audit-log")]
+use splinter::rest_api::audit::AuditDetails;
+#[cfg(feature = "authorization-handler-rbac-scopes")]
+use splinter::rest_api::{
+    auth::authorization::{PayloadScopeAuthorization, RequestScope},
+    ErrorResponse,
+};
 use splinter::service::instance::ServiceError;
 use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
 
@@ -32,37 +41,83 @@ pub fn make_submit_route<A: AdminCommands + Clone + 'static>(admin_commands: A)
 
     #[cfg(feature = "authorization")]
     {
-        resource.add_method(Method::Post, CIRCUIT_WRITE_PERMISSION, move |_, payload| {
-            let admin_commands = admin_commands.clone();
-            Box::new(
-                into_protobuf::<CircuitManagementPayload>(payload).and_then(move |payload| {
-                    match admin_commands.submit_circuit_change(payload) {
-                        Ok(()) => HttpResponse::Accepted().finish().into_future(),
-                        Err(AdminServiceError::ServiceError(
-                            ServiceError::UnableToHandleMessage(err),
-                        )) => {
-                            debug!("{}", err);
-                            HttpResponse::BadRequest()
+        // The circuit a payload concerns is used to check permissions that are restricted to
+        // particular circuits
+        #[cfg(feature = "authorization-handler-rbac-scopes")]
+        let resource = resource.with_payload_scope();
+
+        resource.add_method(
+            Method::Post,
+            CIRCUIT_WRITE_PERMISSION,
+            // Allow `unused_variables` in case `authorization-handler-rbac-scopes` feature is not
+            // enabled
+            move |#[allow(unused_variables)] request, payload| {
+                let admin_commands = admin_commands.clone();
+                #[cfg(feature = "authorization-handler-rbac-scopes")]
+                let payload_scope_authorization = request
+                    .extensions()
+                    .get::<PayloadScopeAuthorization>()
+                    .cloned();
+                Box::new(into_protobuf::<CircuitManagementPayload>(payload).and_then(
+                    move |payload| {
+                        // Record the circuit and action of the payload in the audit log entry
+                        // for this request
+                        #[cfg(feature = "audit-log")]
+                        if let Ok((action, circuit_id)) = circuit_management_action(&payload) {
+                            request.extensions_mut().insert(
+                                AuditDetails::new()
+                                    .with_circuit_id(circuit_id)
+                                    .with_action(format!("{:?}", action).to_lowercase()),
+                            );
+                        }
+
+                        #[cfg(feature = "authorization-handler-rbac-scopes")]
+                        if let Some(authorization) = payload_scope_authorization {
+                            match RequestScope::from_circuit_management_payload(&payload) {
+                                Ok(scope) if authorization.authorize(&scope) => (),
+                                Ok(_) => {
+                                    return HttpResponse::Unauthorized()
+                                        .json(ErrorResponse::unauthorized())
+                                        .into_future()
+                                }
+                                Err(err) => {
+                                    return HttpResponse::BadRequest()
+                                        .json(json!({
+                                            "message": format!("Failed to parse payload: {}", err)
+                                        }))
+                                        .into_future()
+                                }
+                            }
+                        }
+
+                        match admin_commands.submit_circuit_change(payload) {
+                            Ok(()) => HttpResponse::Accepted().finish().into_future(),
+                            Err(AdminServiceError::ServiceError(
+                                ServiceError::UnableToHandleMessage(err),
+                            )) => {
+                                debug!("{}", err);
+                                HttpResponse::BadRequest()
+                                    .json(json!({
+                                        "message": format!("Unable to handle message: {}", err)
+                                    }))
+                                    .into_future()
+                            }
+                            Err(AdminServiceError::ServiceError(
+                                ServiceError::InvalidMessageFormat(err),
+                            )) => HttpResponse::BadRequest()
                                 .json(json!({
-                                    "message": format!("Unable to handle message: {}", err)
+                                    "message": format!("Failed to parse payload: {}", err)
                                 }))
-                                .into_future()
-                        }
-                        Err(AdminServiceError::ServiceError(
-                            ServiceError::InvalidMessageFormat(err),
-                        )) => HttpResponse::BadRequest()
-                            .json(json!({
-                                "message": format!("Failed to parse payload: {}", err)
-                            }))
-                            .into_future(),
-                        Err(err) => {
-                            error!("{}", err);
-                            HttpResponse::InternalServerError().finish().into_future()
+                                .into_future(),
+                            Err(err) => {
+                                error!("{}", err);
+                                HttpResponse::InternalServerError().finish().into_future()
+                            }
                         }
-                    }
-                }),
-            )
-        })
+                    },
+                ))
+            },
+        )
     }
     #[cfg(not(feature = "authorization"))]
     {
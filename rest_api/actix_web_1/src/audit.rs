@@ -0,0 +1,186 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the `GET /audit` endpoint, which lists the REST API audit log.
+
+use std::collections::HashMap;
+use std::time::UNIX_EPOCH;
+
+use actix_web::{error::BlockingError, web, Error, HttpRequest, HttpResponse};
+use futures::{future::IntoFuture, Future};
+use serde::Serialize;
+use splinter::rest_api::{
+    actix_web_1::{Method, ProtocolVersionRangeGuard, Resource, RestResourceProvider},
+    audit::{AuditLogEntry, AuditLogStore, AuditLogStoreError},
+    auth::authorization::Permission,
+    paging::{Paging, PagingBuilder, DEFAULT_LIMIT, DEFAULT_OFFSET},
+    ErrorResponse,
+};
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+const AUDIT_LOG_LIST_MIN: u32 = 1;
+
+const AUDIT_READ_PERMISSION: Permission = Permission::Check {
+    permission_id: "audit.read",
+    permission_display_name: "Audit log read",
+    permission_description: "Allows the client to read the REST API audit log",
+};
+
+/// The `AuditLogResourceProvider` struct provides the following endpoints as REST API resources:
+///
+/// * `GET /audit` - List the entries in the REST API audit log, in the order they were recorded
+pub struct AuditLogResourceProvider {
+    resources: Vec<Resource>,
+}
+
+impl AuditLogResourceProvider {
+    pub fn new(audit_log_store: Box<dyn AuditLogStore>) -> Self {
+        let resource = Resource::build("/audit")
+            .add_request_guard(ProtocolVersionRangeGuard::new(
+                AUDIT_LOG_LIST_MIN,
+                SPLINTER_PROTOCOL_VERSION,
+            ))
+            .add_method(Method::Get, AUDIT_READ_PERMISSION, move |r, _| {
+                list_entries(r, audit_log_store.clone())
+            });
+
+        Self {
+            resources: vec![resource],
+        }
+    }
+}
+
+impl RestResourceProvider for AuditLogResourceProvider {
+    fn resources(&self) -> Vec<Resource> {
+        self.resources.clone()
+    }
+}
+
+#[derive(Serialize)]
+struct ListAuditLogResponse<'a> {
+    data: Vec<AuditLogEntryResponse<'a>>,
+    paging: Paging,
+}
+
+#[derive(Serialize)]
+struct AuditLogEntryResponse<'a> {
+    id: i64,
+    /// The time the request was recorded, in seconds since the Unix epoch
+    timestamp: u64,
+    identity: Option<&'a str>,
+    permission: Option<&'a str>,
+    method: &'a str,
+    route: &'a str,
+    outcome: String,
+    circuit_id: Option<&'a str>,
+    action: Option<&'a str>,
+}
+
+impl<'a> From<&'a AuditLogEntry> for AuditLogEntryResponse<'a> {
+    fn from(entry: &'a AuditLogEntry) -> Self {
+        Self {
+            id: entry.id(),
+            timestamp: entry
+                .timestamp()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .unwrap_or(0),
+            identity: entry.identity(),
+            permission: entry.permission(),
+            method: entry.method(),
+            route: entry.route(),
+            outcome: entry.outcome().to_string(),
+            circuit_id: entry.details().circuit_id(),
+            action: entry.details().action(),
+        }
+    }
+}
+
+fn list_entries(
+    req: HttpRequest,
+    audit_log_store: Box<dyn AuditLogStore>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let query: web::Query<HashMap<String, String>> =
+        if let Ok(q) = web::Query::from_query(req.query_string()) {
+            q
+        } else {
+            return Box::new(
+                HttpResponse::BadRequest()
+                    .json(ErrorResponse::bad_request("Invalid query"))
+                    .into_future(),
+            );
+        };
+
+    let offset = match query.get("offset") {
+        Some(value) => match value.parse::<usize>() {
+            Ok(val) => val,
+            Err(err) => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&format!(
+                            "Invalid offset value passed: {}. Error: {}",
+                            value, err
+                        )))
+                        .into_future(),
+                )
+            }
+        },
+        None => DEFAULT_OFFSET,
+    };
+
+    let limit = match query.get("limit") {
+        Some(value) => match value.parse::<usize>() {
+            Ok(val) => val,
+            Err(err) => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&format!(
+                            "Invalid limit value passed: {}. Error: {}",
+                            value, err
+                        )))
+                        .into_future(),
+                )
+            }
+        },
+        None => DEFAULT_LIMIT,
+    };
+
+    let link = format!("{}?", req.uri().path());
+
+    Box::new(
+        web::block(move || {
+            let total = audit_log_store.count_entries()?;
+            let entries = audit_log_store.list_entries(offset, limit)?;
+            Ok((entries, total))
+        })
+        .then(
+            move |res: Result<_, BlockingError<AuditLogStoreError>>| match res {
+                Ok((entries, total)) => {
+                    let paging = PagingBuilder::new(link, total)
+                        .with_limit(limit)
+                        .with_offset(offset)
+                        .build();
+                    Ok(HttpResponse::Ok().json(ListAuditLogResponse {
+                        data: entries.iter().map(AuditLogEntryResponse::from).collect(),
+                        paging,
+                    }))
+                }
+                Err(err) => {
+                    error!("Unable to list audit log entries: {}", err);
+                    Ok(HttpResponse::InternalServerError().json(ErrorResponse::internal_error()))
+                }
+            },
+        ),
+    )
+}
@@ -13,7 +13,14 @@
 // limitations under the License.
 
 #[macro_use]
-#[cfg(any(feature = "admin-service", feature = "service"))]
+#[cfg(any(
+    feature = "admin-service",
+    feature = "audit-log",
+    feature = "metrics",
+    feature = "peer-inspection",
+    feature = "peer-retry-policy",
+    feature = "service"
+))]
 extern crate log;
 #[macro_use]
 #[cfg(feature = "admin-service")]
@@ -24,9 +31,15 @@ extern crate serde_json;
 
 #[cfg(feature = "admin-service")]
 pub mod admin;
+#[cfg(feature = "audit-log")]
+pub mod audit;
 #[cfg(feature = "biome")]
 pub mod biome;
+#[cfg(feature = "metrics")]
+pub mod metrics;
 pub mod open_api;
+#[cfg(any(feature = "peer-inspection", feature = "peer-retry-policy"))]
+pub mod peer;
 #[cfg(feature = "registry")]
 pub mod registry;
 #[cfg(feature = "scabbard-service")]
@@ -0,0 +1,61 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the `/metrics` endpoint, which exposes the node's metrics in the Prometheus text
+//! exposition format.
+
+use actix_web::HttpResponse;
+use futures::IntoFuture;
+use splinter::rest_api::{ErrorResponse, Method, Resource, RestResourceProvider};
+use splinter::tap::prometheus::{PrometheusHandle, PROMETHEUS_CONTENT_TYPE};
+#[cfg(feature = "authorization")]
+use splinter_rest_api_common::metrics::METRICS_READ_PERMISSION;
+
+pub struct MetricsResourceProvider {
+    resources: Vec<Resource>,
+}
+
+impl MetricsResourceProvider {
+    pub fn new(prometheus_handle: PrometheusHandle) -> Self {
+        let handle = move |_, _| {
+            let response = match prometheus_handle.render() {
+                Ok(metrics) => HttpResponse::Ok()
+                    .content_type(PROMETHEUS_CONTENT_TYPE)
+                    .body(metrics),
+                Err(err) => {
+                    error!("Unable to render metrics: {}", err);
+                    HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+                }
+            };
+            Box::new(response.into_future())
+        };
+
+        let resource = Resource::build("/metrics").add_method(
+            Method::Get,
+            #[cfg(feature = "authorization")]
+            METRICS_READ_PERMISSION,
+            handle,
+        );
+
+        Self {
+            resources: vec![resource],
+        }
+    }
+}
+
+impl RestResourceProvider for MetricsResourceProvider {
+    fn resources(&self) -> Vec<Resource> {
+        self.resources.clone()
+    }
+}
@@ -0,0 +1,121 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the `GET /connections` endpoint, which lists the connections maintained by the node's
+//! connection manager.
+
+use std::sync::{Arc, Mutex};
+
+use actix_web::{web, Error, HttpResponse};
+use futures::{future::IntoFuture, Future};
+use serde::Serialize;
+use splinter::network::connection_manager::{
+    ConnectionDirection, ConnectionInfo, ConnectionStatus, Connector,
+};
+use splinter::peer::PeerAuthorizationToken;
+use splinter::rest_api::{
+    actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
+    ErrorResponse,
+};
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+use super::{to_unix_secs, PEER_READ_PERMISSION};
+
+const CONNECTIONS_LIST_MIN: u32 = 1;
+
+pub fn make_connections_resource(connection_connector: Arc<Mutex<Connector>>) -> Resource {
+    Resource::build("/connections")
+        .add_request_guard(ProtocolVersionRangeGuard::new(
+            CONNECTIONS_LIST_MIN,
+            SPLINTER_PROTOCOL_VERSION,
+        ))
+        .add_method(Method::Get, PEER_READ_PERMISSION, move |_, _| {
+            list_connections(connection_connector.clone())
+        })
+}
+
+#[derive(Serialize)]
+struct ListConnectionsResponse {
+    data: Vec<ConnectionResponse>,
+}
+
+#[derive(Serialize)]
+struct ConnectionResponse {
+    connection_id: String,
+    endpoint: String,
+    /// The identity the remote node was authorized with
+    identity: String,
+    direction: &'static str,
+    status: &'static str,
+    /// The number of reconnection attempts, if the connection is reconnecting
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reconnection_attempts: Option<u64>,
+    /// When the connection was established, in seconds since the Unix epoch
+    connected_since: u64,
+    /// When a heartbeat was last sent on the connection, in seconds since the Unix epoch
+    last_heartbeat: Option<u64>,
+}
+
+impl From<&ConnectionInfo> for ConnectionResponse {
+    fn from(connection_info: &ConnectionInfo) -> Self {
+        let (status, reconnection_attempts) = match connection_info.status() {
+            ConnectionStatus::Connected => ("connected", None),
+            ConnectionStatus::Reconnecting { attempts } => ("reconnecting", Some(*attempts)),
+            ConnectionStatus::Disconnected => ("disconnected", None),
+        };
+
+        Self {
+            connection_id: connection_info.connection_id().to_string(),
+            endpoint: connection_info.endpoint().to_string(),
+            identity: PeerAuthorizationToken::from(connection_info.identity().clone())
+                .id_as_string(),
+            direction: match connection_info.direction() {
+                ConnectionDirection::Inbound => "inbound",
+                ConnectionDirection::Outbound => "outbound",
+            },
+            status,
+            reconnection_attempts,
+            connected_since: to_unix_secs(connection_info.connected_since()),
+            last_heartbeat: connection_info.last_heartbeat().map(to_unix_secs),
+        }
+    }
+}
+
+fn list_connections(
+    connection_connector: Arc<Mutex<Connector>>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let connection_connector = match connection_connector.lock() {
+        Ok(connection_connector) => connection_connector.clone(),
+        Err(_) => {
+            error!("Connection connector lock was poisoned");
+            return Box::new(
+                HttpResponse::InternalServerError()
+                    .json(ErrorResponse::internal_error())
+                    .into_future(),
+            );
+        }
+    };
+
+    Box::new(
+        web::block(move || connection_connector.list_connection_info()).then(|res| match res {
+            Ok(connections) => Ok(HttpResponse::Ok().json(ListConnectionsResponse {
+                data: connections.iter().map(ConnectionResponse::from).collect(),
+            })),
+            Err(err) => {
+                error!("Unable to list connections: {}", err);
+                Ok(HttpResponse::InternalServerError().json(ErrorResponse::internal_error()))
+            }
+        }),
+    )
+}
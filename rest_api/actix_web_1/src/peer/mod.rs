@@ -0,0 +1,105 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module defines the REST API endpoints for inspecting the node's peers.
+
+#[cfg(feature = "peer-inspection")]
+mod connections;
+#[cfg(feature = "peer-inspection")]
+mod peers;
+#[cfg(feature = "peer-inspection")]
+mod peers_id;
+#[cfg(feature = "peer-retry-policy")]
+mod retries;
+
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[cfg(feature = "peer-inspection")]
+use splinter::network::connection_manager::Connector;
+use splinter::peer::PeerManagerConnector;
+use splinter::rest_api::actix_web_1::{Resource, RestResourceProvider};
+use splinter::rest_api::auth::authorization::Permission;
+
+#[cfg(feature = "peer-inspection")]
+const PEER_READ_PERMISSION: Permission = Permission::Check {
+    permission_id: "peer.read",
+    permission_display_name: "Peer read",
+    permission_description: "Allows the client to read the node's peers and connections",
+};
+
+#[cfg(feature = "peer-retry-policy")]
+const PEER_RETRY_READ_PERMISSION: Permission = Permission::Check {
+    permission_id: "peer.retry.read",
+    permission_display_name: "Peer retry read",
+    permission_description: "Allows the client to read the retry state of the node's peers",
+};
+
+/// The `PeerResourceProvider` struct provides the following endpoints as REST API resources:
+///
+/// * `GET /peers/retries` - List the retry state of the node's peers
+/// * `GET /peers` - List the node's peers
+/// * `GET /peers/{peer_id}` - Show a peer
+/// * `GET /connections` - List the node's connections
+///
+/// `GET /peers/retries` is only provided if the `peer-retry-policy` feature is enabled; the
+/// others are only provided if the `peer-inspection` feature is enabled.
+pub struct PeerResourceProvider {
+    resources: Vec<Resource>,
+}
+
+impl PeerResourceProvider {
+    pub fn new(
+        peer_connector: PeerManagerConnector,
+        #[cfg(feature = "peer-inspection")] connection_connector: Connector,
+    ) -> Self {
+        let peer_connector = Arc::new(Mutex::new(peer_connector));
+        #[cfg(feature = "peer-inspection")]
+        let connection_connector = Arc::new(Mutex::new(connection_connector));
+
+        let mut resources = vec![];
+
+        // `/peers/retries` must be registered before `/peers/{peer_id}` so it is not matched as a
+        // peer ID
+        #[cfg(feature = "peer-retry-policy")]
+        resources.push(retries::make_retries_resource(peer_connector.clone()));
+
+        #[cfg(feature = "peer-inspection")]
+        {
+            resources.push(peers::make_peers_resource(
+                peer_connector.clone(),
+                connection_connector.clone(),
+            ));
+            resources.push(peers_id::make_peers_id_resource(
+                peer_connector,
+                connection_connector.clone(),
+            ));
+            resources.push(connections::make_connections_resource(connection_connector));
+        }
+
+        Self { resources }
+    }
+}
+
+impl RestResourceProvider for PeerResourceProvider {
+    fn resources(&self) -> Vec<Resource> {
+        self.resources.clone()
+    }
+}
+
+fn to_unix_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
@@ -0,0 +1,179 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the `GET /peers` endpoint, which lists the node's peers.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use actix_web::{error::BlockingError, web, Error, HttpResponse};
+use futures::{future::IntoFuture, Future};
+use serde::Serialize;
+use splinter::network::connection_manager::Connector;
+use splinter::peer::{PeerInfo, PeerManagerConnector, PeerStatus};
+use splinter::rest_api::{
+    actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
+    ErrorResponse,
+};
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+use super::{to_unix_secs, PEER_READ_PERMISSION};
+
+const PEERS_LIST_MIN: u32 = 1;
+
+pub fn make_peers_resource(
+    peer_connector: Arc<Mutex<PeerManagerConnector>>,
+    connection_connector: Arc<Mutex<Connector>>,
+) -> Resource {
+    Resource::build("/peers")
+        .add_request_guard(ProtocolVersionRangeGuard::new(
+            PEERS_LIST_MIN,
+            SPLINTER_PROTOCOL_VERSION,
+        ))
+        .add_method(Method::Get, PEER_READ_PERMISSION, move |_, _| {
+            list_peers(peer_connector.clone(), connection_connector.clone())
+        })
+}
+
+#[derive(Serialize)]
+struct ListPeersResponse {
+    data: Vec<PeerResponse>,
+}
+
+#[derive(Serialize)]
+pub(super) struct PeerResponse {
+    peer_id: String,
+    /// The ID the local node uses to identify itself to the peer
+    local_id: String,
+    connection_id: String,
+    endpoints: Vec<String>,
+    active_endpoint: String,
+    status: &'static str,
+    /// The number of reconnection attempts, if the peer is disconnected
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reconnection_attempts: Option<u64>,
+    ref_count: u64,
+    /// When the peer's connection was established, in seconds since the Unix epoch
+    connected_since: Option<u64>,
+    /// How long the peer's connection took to establish, if it was requested locally
+    connection_latency_millis: Option<u64>,
+    /// When a heartbeat was last sent to the peer, in seconds since the Unix epoch
+    last_heartbeat: Option<u64>,
+    /// The round trip time of the last heartbeat the peer replied to on its current connection
+    last_heartbeat_latency_millis: Option<u64>,
+}
+
+impl PeerResponse {
+    pub(super) fn peer_id(&self) -> &str {
+        &self.peer_id
+    }
+
+    pub(super) fn ref_count(&self) -> u64 {
+        self.ref_count
+    }
+}
+
+/// Fetches the node's peers, along with the heartbeat state of their connections.
+pub(super) fn fetch_peers(
+    peer_connector: &PeerManagerConnector,
+    connection_connector: &Connector,
+) -> Result<Vec<PeerResponse>, String> {
+    let peer_info = peer_connector
+        .list_peer_info()
+        .map_err(|err| format!("Unable to list peers: {}", err))?;
+    let last_heartbeats = connection_connector
+        .list_connection_info()
+        .map_err(|err| format!("Unable to list connections: {}", err))?
+        .into_iter()
+        .map(|connection_info| {
+            (
+                connection_info.connection_id().to_string(),
+                connection_info.last_heartbeat(),
+            )
+        })
+        .collect::<HashMap<_, _>>();
+
+    Ok(peer_info
+        .iter()
+        .map(|peer_info| PeerResponse {
+            peer_id: peer_info.peer_id().id_as_string(),
+            local_id: peer_info.local_authorization().id_as_string(),
+            connection_id: peer_info.connection_id().to_string(),
+            endpoints: peer_info.endpoints().to_vec(),
+            active_endpoint: peer_info.active_endpoint().to_string(),
+            status: status_name(peer_info),
+            reconnection_attempts: match peer_info.status() {
+                PeerStatus::Disconnected { retry_attempts } => Some(*retry_attempts),
+                _ => None,
+            },
+            ref_count: peer_info.ref_count(),
+            connected_since: peer_info.connected_since().map(to_unix_secs),
+            connection_latency_millis: peer_info
+                .connection_latency()
+                .map(|latency| latency.as_millis() as u64),
+            last_heartbeat: last_heartbeats
+                .get(peer_info.connection_id())
+                .copied()
+                .flatten()
+                .map(to_unix_secs),
+            last_heartbeat_latency_millis: peer_info
+                .heartbeat_latency()
+                .map(|latency| latency.as_millis() as u64),
+        })
+        .collect())
+}
+
+fn status_name(peer_info: &PeerInfo) -> &'static str {
+    match peer_info.status() {
+        PeerStatus::Connected => "connected",
+        PeerStatus::Pending => "pending",
+        PeerStatus::Disconnected { .. } => "disconnected",
+    }
+}
+
+fn list_peers(
+    peer_connector: Arc<Mutex<PeerManagerConnector>>,
+    connection_connector: Arc<Mutex<Connector>>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let (peer_connector, connection_connector) =
+        match (peer_connector.lock(), connection_connector.lock()) {
+            (Ok(peer_connector), Ok(connection_connector)) => {
+                (peer_connector.clone(), connection_connector.clone())
+            }
+            _ => {
+                error!("Peer or connection connector lock was poisoned");
+                return Box::new(
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future(),
+                );
+            }
+        };
+
+    Box::new(
+        web::block(move || fetch_peers(&peer_connector, &connection_connector)).then(
+            |res| match res {
+                Ok(peers) => Ok(HttpResponse::Ok().json(ListPeersResponse { data: peers })),
+                Err(BlockingError::Error(err)) => {
+                    error!("{}", err);
+                    Ok(HttpResponse::InternalServerError().json(ErrorResponse::internal_error()))
+                }
+                Err(BlockingError::Canceled) => {
+                    error!("Listing peers was canceled");
+                    Ok(HttpResponse::InternalServerError().json(ErrorResponse::internal_error()))
+                }
+            },
+        ),
+    )
+}
@@ -0,0 +1,102 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the `GET /peers/{peer_id}` endpoint, which shows one of the node's peers.
+
+use std::sync::{Arc, Mutex};
+
+use actix_web::{error::BlockingError, web, Error, HttpRequest, HttpResponse};
+use futures::{future::IntoFuture, Future};
+use splinter::network::connection_manager::Connector;
+use splinter::peer::PeerManagerConnector;
+use splinter::rest_api::{
+    actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
+    ErrorResponse,
+};
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+use super::peers::fetch_peers;
+use super::PEER_READ_PERMISSION;
+
+const PEERS_FETCH_MIN: u32 = 1;
+
+pub fn make_peers_id_resource(
+    peer_connector: Arc<Mutex<PeerManagerConnector>>,
+    connection_connector: Arc<Mutex<Connector>>,
+) -> Resource {
+    Resource::build("/peers/{peer_id}")
+        .add_request_guard(ProtocolVersionRangeGuard::new(
+            PEERS_FETCH_MIN,
+            SPLINTER_PROTOCOL_VERSION,
+        ))
+        .add_method(Method::Get, PEER_READ_PERMISSION, move |r, _| {
+            fetch_peer(r, peer_connector.clone(), connection_connector.clone())
+        })
+}
+
+// If the peer is known under more than one local identity, the one with the most references is
+// returned.
+fn fetch_peer(
+    request: HttpRequest,
+    peer_connector: Arc<Mutex<PeerManagerConnector>>,
+    connection_connector: Arc<Mutex<Connector>>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let peer_id = request
+        .match_info()
+        .get("peer_id")
+        .unwrap_or("")
+        .to_string();
+
+    let (peer_connector, connection_connector) =
+        match (peer_connector.lock(), connection_connector.lock()) {
+            (Ok(peer_connector), Ok(connection_connector)) => {
+                (peer_connector.clone(), connection_connector.clone())
+            }
+            _ => {
+                error!("Peer or connection connector lock was poisoned");
+                return Box::new(
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future(),
+                );
+            }
+        };
+
+    Box::new(
+        web::block(move || {
+            fetch_peers(&peer_connector, &connection_connector).map(|peers| {
+                peers
+                    .into_iter()
+                    .filter(|peer| peer.peer_id() == peer_id)
+                    .max_by_key(|peer| peer.ref_count())
+            })
+        })
+        .then(|res| {
+            Ok(match res {
+                Ok(Some(peer)) => HttpResponse::Ok().json(peer),
+                Ok(None) => {
+                    HttpResponse::NotFound().json(ErrorResponse::not_found("Peer not found"))
+                }
+                Err(BlockingError::Error(err)) => {
+                    error!("{}", err);
+                    HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+                }
+                Err(BlockingError::Canceled) => {
+                    error!("Fetching peer was canceled");
+                    HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+                }
+            })
+        }),
+    )
+}
@@ -0,0 +1,126 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the `GET /peers/retries` endpoint, which lists the retry state of the node's peers.
+
+use std::sync::{Arc, Mutex};
+
+use actix_web::{web, Error, HttpResponse};
+use futures::{future::IntoFuture, Future};
+use serde::Serialize;
+use splinter::peer::{PeerManagerConnector, PeerRetryState, PeerRetryStatus, RetryPolicy};
+use splinter::rest_api::{
+    actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
+    ErrorResponse,
+};
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+use super::{to_unix_secs, PEER_RETRY_READ_PERMISSION};
+
+const PEER_RETRIES_LIST_MIN: u32 = 1;
+
+pub fn make_retries_resource(peer_connector: Arc<Mutex<PeerManagerConnector>>) -> Resource {
+    Resource::build("/peers/retries")
+        .add_request_guard(ProtocolVersionRangeGuard::new(
+            PEER_RETRIES_LIST_MIN,
+            SPLINTER_PROTOCOL_VERSION,
+        ))
+        .add_method(Method::Get, PEER_RETRY_READ_PERMISSION, move |_, _| {
+            list_retries(peer_connector.clone())
+        })
+}
+
+#[derive(Serialize)]
+struct ListPeerRetriesResponse {
+    data: Vec<PeerRetryResponse>,
+}
+
+#[derive(Serialize)]
+struct PeerRetryResponse {
+    /// The peer's ID, or `None` if it was requested by endpoint and has not yet connected
+    peer_id: Option<String>,
+    endpoints: Vec<String>,
+    status: &'static str,
+    attempts: u64,
+    /// The time of the next attempt, in seconds since the Unix epoch
+    next_attempt: Option<u64>,
+    /// The peer's retry policy, or `None` if the peer uses the node's defaults
+    policy: Option<RetryPolicyResponse>,
+}
+
+#[derive(Serialize)]
+struct RetryPolicyResponse {
+    initial_interval_millis: u64,
+    max_interval_millis: u64,
+    multiplier: u32,
+    jitter: f64,
+    max_attempts: Option<u64>,
+}
+
+impl From<&PeerRetryState> for PeerRetryResponse {
+    fn from(retry_state: &PeerRetryState) -> Self {
+        Self {
+            peer_id: retry_state.peer_id().map(|peer_id| peer_id.id_as_string()),
+            endpoints: retry_state.endpoints().to_vec(),
+            status: match retry_state.status() {
+                PeerRetryStatus::Connected => "connected",
+                PeerRetryStatus::Retrying => "retrying",
+                PeerRetryStatus::GaveUp => "gave_up",
+            },
+            attempts: retry_state.attempts(),
+            next_attempt: retry_state.next_attempt().map(to_unix_secs),
+            policy: retry_state.policy().map(RetryPolicyResponse::from),
+        }
+    }
+}
+
+impl From<&RetryPolicy> for RetryPolicyResponse {
+    fn from(policy: &RetryPolicy) -> Self {
+        Self {
+            initial_interval_millis: policy.initial_interval().as_millis() as u64,
+            max_interval_millis: policy.max_interval().as_millis() as u64,
+            multiplier: policy.multiplier(),
+            jitter: policy.jitter(),
+            max_attempts: policy.max_attempts(),
+        }
+    }
+}
+
+fn list_retries(
+    peer_connector: Arc<Mutex<PeerManagerConnector>>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let peer_connector = match peer_connector.lock() {
+        Ok(peer_connector) => peer_connector.clone(),
+        Err(_) => {
+            error!("Peer connector lock was poisoned");
+            return Box::new(
+                HttpResponse::InternalServerError()
+                    .json(ErrorResponse::internal_error())
+                    .into_future(),
+            );
+        }
+    };
+
+    Box::new(
+        web::block(move || peer_connector.list_retry_states()).then(|res| match res {
+            Ok(retry_states) => Ok(HttpResponse::Ok().json(ListPeerRetriesResponse {
+                data: retry_states.iter().map(PeerRetryResponse::from).collect(),
+            })),
+            Err(err) => {
+                error!("Unable to list peer retry states: {}", err);
+                Ok(HttpResponse::InternalServerError().json(ErrorResponse::internal_error()))
+            }
+        }),
+    )
+}
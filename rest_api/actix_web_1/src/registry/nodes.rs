@@ -25,6 +25,8 @@ use actix_web::{error::BlockingError, web, Error, HttpRequest, HttpResponse};
 use futures::{future::IntoFuture, stream::Stream, Future};
 use splinter::error::InvalidStateError;
 use splinter::registry::{MetadataPredicate, Node, RegistryReader, RegistryWriter, RwRegistry};
+#[cfg(feature = "registry-node-health")]
+use splinter::registry::{NodeIter, RegistryError};
 use splinter::rest_api::{
     actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
     paging::{PagingBuilder, DEFAULT_LIMIT, DEFAULT_OFFSET},
@@ -159,10 +161,39 @@ fn list_nodes(
         }
     };
 
+    #[cfg(feature = "registry-node-health")]
+    let reachable = match query.get("reachable") {
+        Some(value) => match value.parse::<bool>() {
+            Ok(val) => {
+                if let Err(e) = write!(link, "reachable={}&", val) {
+                    return Box::new(
+                        HttpResponse::InternalServerError()
+                            .body(e.to_string())
+                            .into_future(),
+                    );
+                }
+                Some(val)
+            }
+            Err(err) => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&format!(
+                            "Invalid reachable value passed: {}. Error: {}",
+                            value, err
+                        )))
+                        .into_future(),
+                )
+            }
+        },
+        None => None,
+    };
+
     Box::new(query_list_nodes(
         registry,
         link,
         predicates,
+        #[cfg(feature = "registry-node-health")]
+        reachable,
         Some(offset),
         Some(limit),
     ))
@@ -172,13 +203,19 @@ fn query_list_nodes(
     registry: web::Data<Box<dyn RegistryReader>>,
     link: String,
     filters: Vec<MetadataPredicate>,
+    #[cfg(feature = "registry-node-health")] reachable: Option<bool>,
     offset: Option<usize>,
     limit: Option<usize>,
 ) -> impl Future<Item = HttpResponse, Error = Error> {
+    #[cfg(feature = "registry-node-health")]
+    let health_registry = registry.clone();
     web::block(move || {
         let nodes = registry
             .list_nodes(&filters)
             .map_err(RegistryRestApiError::from)?;
+        #[cfg(feature = "registry-node-health")]
+        let nodes = filter_by_reachability(&**registry, nodes, reachable)
+            .map_err(RegistryRestApiError::from)?;
         let offset_value = offset.unwrap_or(0);
         let total = nodes.len();
         let limit_value = limit.unwrap_or(total as usize);
@@ -204,8 +241,15 @@ fn query_list_nodes(
                 } else {
                     paging
                 };
+                #[cfg(not(feature = "registry-node-health"))]
+                let data = nodes.iter().map(NodeResponse::from).collect();
+                #[cfg(feature = "registry-node-health")]
+                let data = nodes
+                    .iter()
+                    .map(|node| to_node_response(node, &**health_registry))
+                    .collect();
                 Ok(HttpResponse::Ok().json(ListNodesResponse {
-                    data: nodes.iter().map(NodeResponse::from).collect(),
+                    data,
                     paging: paging.build(),
                 }))
             }
@@ -217,6 +261,41 @@ fn query_list_nodes(
     )
 }
 
+/// Retains the nodes whose reachability matches `reachable`, if it is set. Nodes without any
+/// recorded health are considered unreachable.
+#[cfg(feature = "registry-node-health")]
+fn filter_by_reachability(
+    registry: &dyn RegistryReader,
+    nodes: NodeIter,
+    reachable: Option<bool>,
+) -> Result<std::vec::IntoIter<Node>, RegistryError> {
+    let mut filtered = vec![];
+    for node in nodes {
+        if let Some(reachable) = reachable {
+            let is_reachable = registry
+                .get_node_health(node.identity())?
+                .map(|health| health.is_reachable())
+                .unwrap_or(false);
+            if is_reachable != reachable {
+                continue;
+            }
+        }
+        filtered.push(node);
+    }
+    Ok(filtered.into_iter())
+}
+
+#[cfg(feature = "registry-node-health")]
+fn to_node_response<'a>(node: &'a Node, registry: &dyn RegistryReader) -> NodeResponse<'a> {
+    let health = registry
+        .get_node_health(node.identity())
+        .unwrap_or_else(|err| {
+            error!("Unable to get health of node {}: {}", node.identity(), err);
+            None
+        });
+    NodeResponse::from(node).with_health(health.as_ref())
+}
+
 fn to_predicates(filters: Option<Filter>) -> Result<Vec<MetadataPredicate>, String> {
     match filters {
         Some(filters) => filters
@@ -296,12 +375,16 @@ mod tests {
     use super::*;
 
     use std::sync::{Arc, Mutex};
+    #[cfg(feature = "registry-node-health")]
+    use std::time::Duration;
 
     use reqwest::{blocking::Client, StatusCode, Url};
     use serde_json::{to_value, Value as JsonValue};
 
     use splinter::error::InternalError;
     use splinter::error::InvalidStateError;
+    #[cfg(feature = "registry-node-health")]
+    use splinter::registry::{NodeHealthTracker, UnifiedRegistry};
     use splinter::registry::{NodeIter, RegistryError};
     use splinter::rest_api::actix_web_1::AuthConfig;
     use splinter::rest_api::auth::authorization::{
@@ -445,6 +528,62 @@ mod tests {
         join_handle.join().expect("Unable to join rest api thread");
     }
 
+    #[cfg(feature = "registry-node-health")]
+    #[test]
+    /// Tests a GET /registry/nodes request with the reachable filter returns only the nodes with
+    /// the requested reachability, along with their health.
+    fn test_list_nodes_reachable_filter() {
+        let tracker = NodeHealthTracker::new();
+        tracker
+            .record_connected("Node-123", Some(Duration::from_millis(15)))
+            .expect("Failed to record connection");
+        let registry = UnifiedRegistry::new(
+            Box::new(MemRegistry::new(vec![get_node_1(), get_node_2()])),
+            vec![],
+        )
+        .with_health_tracker(tracker);
+
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_nodes_resource(Box::new(registry))]);
+
+        let list = |reachable: &str| -> Vec<JsonValue> {
+            let url = Url::parse(&format!(
+                "http://{}/registry/nodes?reachable={}",
+                bind_url, reachable
+            ))
+            .expect("Failed to parse URL");
+            let resp = Client::new()
+                .get(url)
+                .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+                .header("Authorization", "custom")
+                .send()
+                .expect("Failed to perform request");
+            assert_eq!(resp.status(), StatusCode::OK);
+            let body: JsonValue = resp.json().expect("Failed to deserialize body");
+            body.get("data")
+                .expect("No data field in response")
+                .as_array()
+                .expect("data field is not an array")
+                .to_vec()
+        };
+
+        let nodes = list("true");
+        assert_eq!(1, nodes.len());
+        assert_eq!(nodes[0]["identity"], "Node-123");
+        assert_eq!(nodes[0]["health"]["reachable"], true);
+        assert_eq!(nodes[0]["health"]["latency_millis"], 15);
+
+        let nodes = list("false");
+        assert_eq!(1, nodes.len());
+        assert_eq!(nodes[0]["identity"], "Node-456");
+        assert!(nodes[0].get("health").is_none());
+
+        shutdown_handle
+            .shutdown()
+            .expect("Unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
     #[test]
     /// Test the POST /registry/nodes route for adding a node to the registry.
     fn test_add_node() {
@@ -14,8 +14,12 @@
 
 use std::collections::HashMap;
 use std::convert::TryFrom;
+#[cfg(feature = "registry-node-health")]
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "registry-node-health")]
+use splinter::registry::NodeHealth;
 use splinter::registry::{InvalidNodeError, Node};
 use splinter::rest_api::paging::Paging;
 
@@ -32,6 +36,17 @@ pub struct NodeResponse<'a> {
     pub display_name: &'a str,
     pub keys: &'a [String],
     pub metadata: &'a HashMap<String, String>,
+    #[cfg(feature = "registry-node-health")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health: Option<NodeHealthResponse>,
+}
+
+#[cfg(feature = "registry-node-health")]
+impl<'a> NodeResponse<'a> {
+    pub fn with_health(mut self, health: Option<&NodeHealth>) -> Self {
+        self.health = health.map(NodeHealthResponse::from);
+        self
+    }
 }
 
 impl<'a> From<&'a Node> for NodeResponse<'a> {
@@ -42,10 +57,41 @@ impl<'a> From<&'a Node> for NodeResponse<'a> {
             display_name: node.display_name(),
             keys: node.keys(),
             metadata: node.metadata(),
+            #[cfg(feature = "registry-node-health")]
+            health: None,
         }
     }
 }
 
+/// The reachability of a node; times are in seconds since the Unix epoch.
+#[cfg(feature = "registry-node-health")]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NodeHealthResponse {
+    pub reachable: bool,
+    pub last_connected: Option<u64>,
+    pub last_failure: Option<u64>,
+    pub latency_millis: Option<u64>,
+}
+
+#[cfg(feature = "registry-node-health")]
+impl From<&NodeHealth> for NodeHealthResponse {
+    fn from(health: &NodeHealth) -> Self {
+        Self {
+            reachable: health.is_reachable(),
+            last_connected: health.last_connected().map(to_unix_secs),
+            last_failure: health.last_failure().map(to_unix_secs),
+            latency_millis: health.latency().map(|latency| latency.as_millis() as u64),
+        }
+    }
+}
+
+#[cfg(feature = "registry-node-health")]
+fn to_unix_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
 /// Used to deserialize add and update requests
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct NewNode {
@@ -14,9 +14,11 @@
 
 pub mod batch_statuses;
 pub mod batches;
+pub mod receipts;
 pub mod state;
 pub mod state_address;
 pub mod state_root;
+pub mod state_snapshot;
 pub mod ws_subscribe;
 
 use splinter::service::rest_api::{ServiceEndpoint, ServiceEndpointProvider};
@@ -46,6 +48,9 @@ impl Default for ScabbardServiceEndpointProvider {
             state_address::make_get_state_at_address_endpoint(),
             state::make_get_state_with_prefix_endpoint(),
             state_root::make_get_state_root_endpoint(),
+            state_snapshot::make_get_state_snapshot_endpoint(),
+            receipts::make_get_receipt_endpoint(),
+            receipts::make_list_receipts_endpoint(),
         ];
         Self::new(endpoints)
     }
@@ -0,0 +1,153 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use actix_web::{web, HttpResponse};
+use futures::IntoFuture;
+use splinter::{
+    rest_api::{paging::DEFAULT_LIMIT, ErrorResponse, Method, ProtocolVersionRangeGuard},
+    service::rest_api::ServiceEndpoint,
+};
+
+use scabbard::protocol;
+use scabbard::service::{Scabbard, SERVICE_TYPE};
+#[cfg(feature = "authorization")]
+use splinter_rest_api_common::scabbard::SCABBARD_READ_PERMISSION;
+use splinter_rest_api_common::scabbard::{
+    SCABBARD_GET_RECEIPT_PROTOCOL_MIN, SCABBARD_LIST_RECEIPTS_MAX_LIMIT,
+    SCABBARD_LIST_RECEIPTS_PROTOCOL_MIN,
+};
+
+pub fn make_get_receipt_endpoint() -> ServiceEndpoint {
+    ServiceEndpoint {
+        service_type: SERVICE_TYPE.into(),
+        route: "/receipts/{transaction_id}".into(),
+        method: Method::Get,
+        handler: Arc::new(move |request, _, service| {
+            let scabbard = match service.as_any().downcast_ref::<Scabbard>() {
+                Some(s) => s,
+                None => {
+                    error!("Failed to downcast to scabbard service");
+                    return Box::new(
+                        HttpResponse::InternalServerError()
+                            .json(ErrorResponse::internal_error())
+                            .into_future(),
+                    );
+                }
+            };
+
+            let transaction_id = match request.match_info().get("transaction_id") {
+                Some(transaction_id) => transaction_id,
+                None => {
+                    // All of this should be unreachable if actix routing is working.
+                    error!("transaction_id can not be none");
+                    return Box::new(
+                        HttpResponse::BadRequest()
+                            .json(ErrorResponse::bad_request("transaction_id must be set"))
+                            .into_future(),
+                    );
+                }
+            };
+
+            Box::new(match scabbard.get_receipt(transaction_id) {
+                Ok(Some(receipt)) => HttpResponse::Ok().json(receipt).into_future(),
+                Ok(None) => HttpResponse::NotFound()
+                    .json(ErrorResponse::not_found("Receipt not found"))
+                    .into_future(),
+                Err(err) => {
+                    error!("Failed to get transaction receipt: {}", err);
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future()
+                }
+            })
+        }),
+        request_guards: vec![Arc::new(ProtocolVersionRangeGuard::new(
+            SCABBARD_GET_RECEIPT_PROTOCOL_MIN,
+            protocol::SCABBARD_PROTOCOL_VERSION,
+        ))],
+        #[cfg(feature = "authorization")]
+        permission: SCABBARD_READ_PERMISSION,
+    }
+}
+
+pub fn make_list_receipts_endpoint() -> ServiceEndpoint {
+    ServiceEndpoint {
+        service_type: SERVICE_TYPE.into(),
+        route: "/receipts".into(),
+        method: Method::Get,
+        handler: Arc::new(move |request, _, service| {
+            let scabbard = match service.as_any().downcast_ref::<Scabbard>() {
+                Some(s) => s,
+                None => {
+                    error!("Failed to downcast to scabbard service");
+                    return Box::new(
+                        HttpResponse::InternalServerError()
+                            .json(ErrorResponse::internal_error())
+                            .into_future(),
+                    );
+                }
+            };
+
+            let query: web::Query<HashMap<String, String>> =
+                if let Ok(q) = web::Query::from_query(request.query_string()) {
+                    q
+                } else {
+                    return Box::new(
+                        HttpResponse::BadRequest()
+                            .json(ErrorResponse::bad_request("Invalid query"))
+                            .into_future(),
+                    );
+                };
+
+            let since = query.get("since").cloned();
+
+            let limit = match query.get("limit").map(|limit| limit.parse::<usize>()) {
+                Some(Ok(limit)) if limit > 0 && limit <= SCABBARD_LIST_RECEIPTS_MAX_LIMIT => limit,
+                Some(_) => {
+                    return Box::new(
+                        HttpResponse::BadRequest()
+                            .json(ErrorResponse::bad_request(&format!(
+                                "limit must be a positive integer no greater than {}",
+                                SCABBARD_LIST_RECEIPTS_MAX_LIMIT
+                            )))
+                            .into_future(),
+                    )
+                }
+                None => DEFAULT_LIMIT,
+            };
+
+            Box::new(match scabbard.list_receipts_since(since, limit) {
+                Ok(Some(receipts)) => HttpResponse::Ok().json(receipts).into_future(),
+                Ok(None) => HttpResponse::NotFound()
+                    .json(ErrorResponse::not_found("Receipt given by since not found"))
+                    .into_future(),
+                Err(err) => {
+                    error!("Failed to list transaction receipts: {}", err);
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future()
+                }
+            })
+        }),
+        request_guards: vec![Arc::new(ProtocolVersionRangeGuard::new(
+            SCABBARD_LIST_RECEIPTS_PROTOCOL_MIN,
+            protocol::SCABBARD_PROTOCOL_VERSION,
+        ))],
+        #[cfg(feature = "authorization")]
+        permission: SCABBARD_READ_PERMISSION,
+    }
+}
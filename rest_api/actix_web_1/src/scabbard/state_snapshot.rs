@@ -0,0 +1,64 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use actix_web::HttpResponse;
+use futures::IntoFuture;
+use splinter::{
+    rest_api::{ErrorResponse, Method, ProtocolVersionRangeGuard},
+    service::rest_api::ServiceEndpoint,
+};
+
+use scabbard::protocol;
+use scabbard::service::{Scabbard, SERVICE_TYPE};
+#[cfg(feature = "authorization")]
+use splinter_rest_api_common::scabbard::SCABBARD_READ_PERMISSION;
+
+pub fn make_get_state_snapshot_endpoint() -> ServiceEndpoint {
+    ServiceEndpoint {
+        service_type: SERVICE_TYPE.into(),
+        route: "/state_snapshot".into(),
+        method: Method::Get,
+        handler: Arc::new(move |_, _, service| {
+            let scabbard = match service.as_any().downcast_ref::<Scabbard>() {
+                Some(s) => s,
+                None => {
+                    error!("Failed to downcast to scabbard service");
+                    return Box::new(
+                        HttpResponse::InternalServerError()
+                            .json(ErrorResponse::internal_error())
+                            .into_future(),
+                    );
+                }
+            };
+
+            Box::new(match scabbard.export_state_snapshot() {
+                Ok(snapshot) => HttpResponse::Ok().json(snapshot).into_future(),
+                Err(err) => {
+                    error!("Failed to export state snapshot: {}", err);
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future()
+                }
+            })
+        }),
+        request_guards: vec![Arc::new(ProtocolVersionRangeGuard::new(
+            splinter_rest_api_common::scabbard::SCABBARD_STATE_SNAPSHOT_PROTOCOL_MIN,
+            protocol::SCABBARD_PROTOCOL_VERSION,
+        ))],
+        #[cfg(feature = "authorization")]
+        permission: SCABBARD_READ_PERMISSION,
+    }
+}
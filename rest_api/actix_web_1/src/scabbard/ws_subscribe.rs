@@ -12,7 +12,6 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::HashMap;
 use std::sync::Arc;
 
 use actix_web::{web, HttpResponse};
@@ -27,7 +26,8 @@ use splinter::{
 
 use scabbard::protocol;
 use scabbard::service::{
-    Scabbard, StateChangeEvent, StateSubscriber, StateSubscriberError, SERVICE_TYPE,
+    Scabbard, StateChangeEvent, StateChangeFilter, StateChangeKind, StateSubscriber,
+    StateSubscriberError, SERVICE_TYPE,
 };
 #[cfg(feature = "authorization")]
 use splinter_rest_api_common::scabbard::SCABBARD_READ_PERMISSION;
@@ -66,9 +66,9 @@ pub fn make_subscribe_endpoint() -> ServiceEndpoint {
                 }
             };
 
-            let mut query =
-                match web::Query::<HashMap<String, String>>::from_query(request.query_string()) {
-                    Ok(query) => query,
+            let query =
+                match web::Query::<Vec<(String, String)>>::from_query(request.query_string()) {
+                    Ok(query) => query.into_inner(),
                     Err(_) => {
                         return Box::new(
                             HttpResponse::BadRequest()
@@ -78,7 +78,21 @@ pub fn make_subscribe_endpoint() -> ServiceEndpoint {
                     }
                 };
 
-            let last_seen_event_id = query.remove("last_seen_event");
+            let last_seen_event_id = query
+                .iter()
+                .find(|(key, _)| key == "last_seen_event")
+                .map(|(_, value)| value.clone());
+
+            let filter = match parse_filter(&query) {
+                Ok(filter) => filter,
+                Err(msg) => {
+                    return Box::new(
+                        HttpResponse::BadRequest()
+                            .json(ErrorResponse::bad_request(&msg))
+                            .into_future(),
+                    )
+                }
+            };
 
             match last_seen_event_id {
                 Some(ref id) if id.trim().is_empty() => {
@@ -94,24 +108,26 @@ pub fn make_subscribe_endpoint() -> ServiceEndpoint {
                 None => debug!("Getting all state-delta events"),
             }
 
-            let unseen_events = match scabbard.get_events_since(last_seen_event_id) {
-                Ok(events) => events,
-                Err(err) => {
-                    error!("Unable to load unseen scabbard events: {}", err);
-                    return Box::new(
-                        HttpResponse::InternalServerError()
-                            .json(ErrorResponse::internal_error())
-                            .into_future(),
-                    );
-                }
-            };
+            let unseen_events =
+                match scabbard.get_filtered_events_since(last_seen_event_id, filter.clone()) {
+                    Ok(events) => events,
+                    Err(err) => {
+                        error!("Unable to load unseen scabbard events: {}", err);
+                        return Box::new(
+                            HttpResponse::InternalServerError()
+                                .json(ErrorResponse::internal_error())
+                                .into_future(),
+                        );
+                    }
+                };
 
             let request = Request::from((request, payload));
             match new_websocket_event_sender(request, Box::new(unseen_events)) {
                 Ok((sender, res)) => {
-                    if let Err(err) =
-                        scabbard.add_state_subscriber(Box::new(WsStateSubscriber { sender }))
-                    {
+                    if let Err(err) = scabbard.add_filtered_state_subscriber(
+                        Box::new(WsStateSubscriber { sender }),
+                        filter,
+                    ) {
                         error!("Unable to add scabbard event sender: {}", err);
                         return Box::new(
                             HttpResponse::InternalServerError()
@@ -139,3 +155,71 @@ pub fn make_subscribe_endpoint() -> ServiceEndpoint {
         permission: SCABBARD_READ_PERMISSION,
     }
 }
+
+/// Parses the state change filter from the `address_prefix` query parameters, which may be
+/// repeated, and the optional `change_kind` query parameter (`set` or `delete`).
+fn parse_filter(query: &[(String, String)]) -> Result<StateChangeFilter, String> {
+    let address_prefixes = query
+        .iter()
+        .filter(|(key, _)| key == "address_prefix")
+        .map(|(_, prefix)| {
+            if prefix.trim().is_empty() {
+                Err("address_prefix must not be empty".to_string())
+            } else {
+                Ok(prefix.clone())
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let filter = StateChangeFilter::new().with_address_prefixes(address_prefixes);
+
+    match query
+        .iter()
+        .find(|(key, _)| key == "change_kind")
+        .map(|(_, kind)| kind.as_str())
+    {
+        Some("set") => Ok(filter.with_kind(StateChangeKind::Set)),
+        Some("delete") => Ok(filter.with_kind(StateChangeKind::Delete)),
+        Some(kind) => Err(format!(
+            "Invalid change_kind \"{}\": must be \"set\" or \"delete\"",
+            kind
+        )),
+        None => Ok(filter),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(params: &[(&str, &str)]) -> Vec<(String, String)> {
+        params
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    /// Verifies that the filter is built from the repeated `address_prefix` parameters and the
+    /// `change_kind` parameter, and that invalid values are rejected.
+    #[test]
+    fn test_parse_filter() {
+        assert_eq!(
+            parse_filter(&query(&[("last_seen_event", "abc")])),
+            Ok(StateChangeFilter::new())
+        );
+
+        assert_eq!(
+            parse_filter(&query(&[
+                ("address_prefix", "aa"),
+                ("address_prefix", "bb"),
+                ("change_kind", "delete"),
+            ])),
+            Ok(StateChangeFilter::new()
+                .with_address_prefixes(vec!["aa".into(), "bb".into()])
+                .with_kind(StateChangeKind::Delete))
+        );
+
+        assert!(parse_filter(&query(&[("address_prefix", "")])).is_err());
+        assert!(parse_filter(&query(&[("change_kind", "update")])).is_err());
+    }
+}
@@ -19,6 +19,8 @@ use actix_web::{Error, HttpResponse};
 use futures::{Future, IntoFuture};
 #[cfg(feature = "authorization")]
 use splinter::rest_api::auth::authorization::Permission;
+#[cfg(feature = "tls-reload")]
+use splinter::transport::tls::reload::TlsReloadStatus;
 use splinter_rest_api_common::status::Status;
 
 pub use resource_provider::StatusResourceProvider;
@@ -36,6 +38,7 @@ pub fn get_status(
     #[cfg(feature = "service-endpoint")] service_endpoint: String,
     network_endpoints: Vec<String>,
     advertised_endpoints: Vec<String>,
+    #[cfg(feature = "tls-reload")] tls_reload_status: Option<TlsReloadStatus>,
 ) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
     let status = Status::new(
         node_id,
@@ -46,5 +49,11 @@ pub fn get_status(
         advertised_endpoints,
     );
 
+    #[cfg(feature = "tls-reload")]
+    let status = match tls_reload_status {
+        Some(tls_reload_status) => status.with_tls_contexts(&tls_reload_status.reports()),
+        None => status,
+    };
+
     Box::new(HttpResponse::Ok().json(status).into_future())
 }
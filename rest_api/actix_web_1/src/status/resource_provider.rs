@@ -14,13 +14,22 @@
 // limitations under the License.
 
 use splinter::rest_api::{Resource, RestResourceProvider};
+#[cfg(feature = "tls-reload")]
+use splinter::transport::tls::reload::TlsReloadStatus;
 
 use super::get_status;
 #[cfg(feature = "authorization")]
 use super::STATUS_READ_PERMISSION;
 
 pub struct StatusResourceProvider {
-    resources: Vec<Resource>,
+    node_id: String,
+    display_name: String,
+    #[cfg(feature = "service-endpoint")]
+    service_endpoint: String,
+    network_endpoints: Vec<String>,
+    advertised_endpoints: Vec<String>,
+    #[cfg(feature = "tls-reload")]
+    tls_reload_status: Option<TlsReloadStatus>,
 }
 
 impl StatusResourceProvider {
@@ -31,6 +40,37 @@ impl StatusResourceProvider {
         network_endpoints: Vec<String>,
         advertised_endpoints: Vec<String>,
     ) -> Self {
+        Self {
+            node_id,
+            display_name,
+            #[cfg(feature = "service-endpoint")]
+            service_endpoint,
+            network_endpoints,
+            advertised_endpoints,
+            #[cfg(feature = "tls-reload")]
+            tls_reload_status: None,
+        }
+    }
+
+    /// Includes the reload status of the node's TLS contexts in the node status
+    #[cfg(feature = "tls-reload")]
+    pub fn with_tls_reload_status(mut self, tls_reload_status: TlsReloadStatus) -> Self {
+        self.tls_reload_status = Some(tls_reload_status);
+        self
+    }
+}
+
+impl RestResourceProvider for StatusResourceProvider {
+    fn resources(&self) -> Vec<splinter::rest_api::Resource> {
+        let node_id = self.node_id.clone();
+        let display_name = self.display_name.clone();
+        #[cfg(feature = "service-endpoint")]
+        let service_endpoint = self.service_endpoint.clone();
+        let network_endpoints = self.network_endpoints.clone();
+        let advertised_endpoints = self.advertised_endpoints.clone();
+        #[cfg(feature = "tls-reload")]
+        let tls_reload_status = self.tls_reload_status.clone();
+
         let handle = move |_, _| {
             get_status(
                 node_id.clone(),
@@ -39,30 +79,21 @@ impl StatusResourceProvider {
                 service_endpoint.clone(),
                 network_endpoints.clone(),
                 advertised_endpoints.clone(),
+                #[cfg(feature = "tls-reload")]
+                tls_reload_status.clone(),
             )
         };
         #[cfg(feature = "authorization")]
         {
-            let status_resource = Resource::build("/status").add_method(
+            vec![Resource::build("/status").add_method(
                 splinter::rest_api::Method::Get,
                 STATUS_READ_PERMISSION,
                 handle,
-            );
-            let resources = vec![status_resource];
-            Self { resources }
+            )]
         }
         #[cfg(not(feature = "authorization"))]
         {
-            let status_resource =
-                Resource::build("/status").add_method(splinter::rest_api::Method::Get, handle);
-            let resources = vec![status_resource];
-            Self { resources }
+            vec![Resource::build("/status").add_method(splinter::rest_api::Method::Get, handle)]
         }
     }
 }
-
-impl RestResourceProvider for StatusResourceProvider {
-    fn resources(&self) -> Vec<splinter::rest_api::Resource> {
-        self.resources.clone()
-    }
-}
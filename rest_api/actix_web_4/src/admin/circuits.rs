@@ -0,0 +1,130 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use actix_web::{http::Method, web, HttpRequest, HttpResponse};
+use splinter::admin::store::{AdminServiceStore, Circuit, CircuitPredicate, CircuitStatus};
+#[cfg(feature = "authorization")]
+use splinter_rest_api_common::admin::CIRCUIT_READ_PERMISSION;
+use splinter_rest_api_common::{
+    admin::{resources, ADMIN_LIST_CIRCUITS_PROTOCOL_MIN},
+    error::ResponseError,
+    paging::v1::PagingBuilder,
+    SPLINTER_PROTOCOL_VERSION,
+};
+
+use crate::paging::{parse_offset_and_limit, parse_query};
+use crate::{HandlerError, ProtocolVersion, Resource};
+
+type ListCircuitsProtocolVersion =
+    ProtocolVersion<ADMIN_LIST_CIRCUITS_PROTOCOL_MIN, SPLINTER_PROTOCOL_VERSION>;
+
+pub fn make_list_circuits_resource(store: Box<dyn AdminServiceStore>) -> Resource {
+    Resource::build("/admin/circuits").add_method(
+        Method::GET,
+        #[cfg(feature = "authorization")]
+        CIRCUIT_READ_PERMISSION,
+        move |req: HttpRequest, version: ListCircuitsProtocolVersion| {
+            list_circuits(req, version, store.clone())
+        },
+    )
+}
+
+async fn list_circuits(
+    req: HttpRequest,
+    version: ListCircuitsProtocolVersion,
+    store: Box<dyn AdminServiceStore>,
+) -> Result<HttpResponse, HandlerError> {
+    let query = parse_query(&req)?;
+    let (offset, limit) = parse_offset_and_limit(&query)?;
+    let link = make_link(&req, &query);
+
+    let member_filter = query.get("filter").cloned();
+    let status_filter = query.get("status").cloned();
+
+    let (circuits, total) =
+        web::block(move || query_circuits(&*store, member_filter, status_filter, offset, limit))
+            .await??;
+
+    let paging = PagingBuilder::new(link, total)
+        .with_limit(limit)
+        .with_offset(offset)
+        .build();
+
+    match version.version() {
+        1 => Ok(
+            HttpResponse::Ok().json(resources::v1::circuits::ListCircuitsResponse {
+                data: circuits
+                    .iter()
+                    .map(resources::v1::circuits::CircuitResponse::from)
+                    .collect(),
+                paging,
+            }),
+        ),
+        _ => Ok(
+            HttpResponse::Ok().json(resources::v2::circuits::ListCircuitsResponse {
+                data: circuits
+                    .iter()
+                    .map(resources::v2::circuits::CircuitResponse::from)
+                    .collect(),
+                paging,
+            }),
+        ),
+    }
+}
+
+fn query_circuits(
+    store: &dyn AdminServiceStore,
+    member_filter: Option<String>,
+    status_filter: Option<String>,
+    offset: usize,
+    limit: usize,
+) -> Result<(Vec<Circuit>, usize), ResponseError> {
+    let mut filters = match member_filter {
+        Some(member) => vec![CircuitPredicate::MembersInclude(vec![member])],
+        None => vec![],
+    };
+    if let Some(status) = status_filter {
+        filters.push(CircuitPredicate::CircuitStatus(
+            CircuitStatus::try_from(status).map_err(|err| {
+                ResponseError::internal_error(
+                    format!("Invalid circuit status filter: {}", err),
+                    None,
+                )
+            })?,
+        ));
+    }
+
+    let circuits = store.list_circuits(&filters).map_err(|err| {
+        ResponseError::internal_error(format!("Unable to list circuits: {}", err), None)
+    })?;
+
+    let total = circuits.len();
+    Ok((circuits.skip(offset).take(limit).collect(), total))
+}
+
+fn make_link(req: &HttpRequest, query: &HashMap<String, String>) -> String {
+    let new_queries = ["filter", "status"]
+        .iter()
+        .filter_map(|key| query.get(*key).map(|value| format!("{}={}", key, value)))
+        .collect::<Vec<_>>();
+
+    let path = req.uri().path();
+    if new_queries.is_empty() {
+        path.to_string()
+    } else {
+        format!("{}?{}&", path, new_queries.join("&"))
+    }
+}
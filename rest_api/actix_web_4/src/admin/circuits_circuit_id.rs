@@ -0,0 +1,62 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use actix_web::{http::Method, web, HttpResponse};
+use splinter::admin::store::AdminServiceStore;
+#[cfg(feature = "authorization")]
+use splinter_rest_api_common::admin::CIRCUIT_READ_PERMISSION;
+use splinter_rest_api_common::{
+    admin::{resources, ADMIN_FETCH_CIRCUIT_PROTOCOL_MIN},
+    error::ResponseError,
+    SPLINTER_PROTOCOL_VERSION,
+};
+
+use crate::{HandlerError, ProtocolVersion, Resource};
+
+type FetchCircuitProtocolVersion =
+    ProtocolVersion<ADMIN_FETCH_CIRCUIT_PROTOCOL_MIN, SPLINTER_PROTOCOL_VERSION>;
+
+pub fn make_fetch_circuit_resource(store: Box<dyn AdminServiceStore>) -> Resource {
+    Resource::build("/admin/circuits/{circuit_id}").add_method(
+        Method::GET,
+        #[cfg(feature = "authorization")]
+        CIRCUIT_READ_PERMISSION,
+        move |circuit_id: web::Path<String>, version: FetchCircuitProtocolVersion| {
+            fetch_circuit(circuit_id.into_inner(), version, store.clone())
+        },
+    )
+}
+
+async fn fetch_circuit(
+    circuit_id: String,
+    version: FetchCircuitProtocolVersion,
+    store: Box<dyn AdminServiceStore>,
+) -> Result<HttpResponse, HandlerError> {
+    let circuit = web::block(move || {
+        store
+            .get_circuit(&circuit_id)
+            .map_err(|err| {
+                ResponseError::internal_error(format!("Unable to fetch circuit: {}", err), None)
+            })?
+            .ok_or_else(|| {
+                ResponseError::not_found(format!("Unable to find circuit: {}", circuit_id))
+            })
+    })
+    .await??;
+
+    match version.version() {
+        1 => Ok(HttpResponse::Ok().json(resources::v1::circuits::CircuitResponse::from(&circuit))),
+        _ => Ok(HttpResponse::Ok().json(resources::v2::circuits::CircuitResponse::from(&circuit))),
+    }
+}
@@ -0,0 +1,83 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the admin service and circuit REST API endpoints.
+
+mod circuits;
+mod circuits_circuit_id;
+mod proposals;
+mod proposals_circuit_id;
+mod submit;
+
+use splinter::admin::service::AdminService;
+use splinter::admin::store::AdminServiceStore;
+
+use crate::{Resource, RestResourceProvider};
+
+/// Provides the REST API [`Resource`] definitions for submitting circuit management payloads and
+/// listing and fetching circuit proposals.
+///
+/// The following endpoints are provided:
+///
+/// * `POST /admin/submit` - Submit a circuit management payload
+/// * `GET /admin/proposals` - List circuit proposals
+/// * `GET /admin/proposals/{circuit_id}` - Fetch a specific circuit proposal by circuit ID
+pub struct AdminServiceRestProvider {
+    resources: Vec<Resource>,
+}
+
+impl AdminServiceRestProvider {
+    pub fn new(source: &AdminService) -> Self {
+        let resources = vec![
+            submit::make_submit_resource(source.commands()),
+            proposals_circuit_id::make_fetch_proposal_resource(source.proposal_store_factory()),
+            proposals::make_list_proposals_resource(source.proposal_store_factory()),
+        ];
+        Self { resources }
+    }
+}
+
+impl RestResourceProvider for AdminServiceRestProvider {
+    fn resources(&self) -> Vec<Resource> {
+        self.resources.clone()
+    }
+}
+
+/// Provides the REST API [`Resource`] definitions for listing and fetching the circuits in the
+/// splinter node's state.
+///
+/// The following endpoints are provided:
+///
+/// * `GET /admin/circuits` - List circuits in Splinter's state
+/// * `GET /admin/circuits/{circuit_id}` - Fetch a specific circuit in Splinter's state by circuit
+///   ID
+#[derive(Clone)]
+pub struct CircuitResourceProvider {
+    store: Box<dyn AdminServiceStore>,
+}
+
+impl CircuitResourceProvider {
+    pub fn new(store: Box<dyn AdminServiceStore>) -> Self {
+        Self { store }
+    }
+}
+
+impl RestResourceProvider for CircuitResourceProvider {
+    fn resources(&self) -> Vec<Resource> {
+        vec![
+            circuits_circuit_id::make_fetch_circuit_resource(self.store.clone()),
+            circuits::make_list_circuits_resource(self.store.clone()),
+        ]
+    }
+}
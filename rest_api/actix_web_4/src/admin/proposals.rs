@@ -0,0 +1,127 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use actix_web::{http::Method, web, HttpRequest, HttpResponse};
+use splinter::admin::messages::CircuitProposal;
+use splinter::admin::service::proposal_store::ProposalStoreFactory;
+use splinter::admin::store::CircuitPredicate;
+#[cfg(feature = "authorization")]
+use splinter_rest_api_common::admin::CIRCUIT_READ_PERMISSION;
+use splinter_rest_api_common::{
+    admin::{resources, ADMIN_LIST_PROPOSALS_PROTOCOL_MIN},
+    error::ResponseError,
+    paging::v1::PagingBuilder,
+    SPLINTER_PROTOCOL_VERSION,
+};
+
+use crate::paging::{parse_offset_and_limit, parse_query};
+use crate::{HandlerError, ProtocolVersion, Resource};
+
+type ListProposalsProtocolVersion =
+    ProtocolVersion<ADMIN_LIST_PROPOSALS_PROTOCOL_MIN, SPLINTER_PROTOCOL_VERSION>;
+
+pub fn make_list_proposals_resource<PSF: ProposalStoreFactory + 'static>(
+    proposal_store_factory: PSF,
+) -> Resource {
+    Resource::build("/admin/proposals").add_method(
+        Method::GET,
+        #[cfg(feature = "authorization")]
+        CIRCUIT_READ_PERMISSION,
+        move |req: HttpRequest, version: ListProposalsProtocolVersion| {
+            list_proposals(req, version, proposal_store_factory.clone())
+        },
+    )
+}
+
+async fn list_proposals<PSF: ProposalStoreFactory + 'static>(
+    req: HttpRequest,
+    version: ListProposalsProtocolVersion,
+    proposal_store_factory: PSF,
+) -> Result<HttpResponse, HandlerError> {
+    let query = parse_query(&req)?;
+    let (offset, limit) = parse_offset_and_limit(&query)?;
+    let link = make_link(&req, &query);
+
+    let mut filters = vec![];
+    if let Some(management_type) = query.get("management_type") {
+        filters.push(CircuitPredicate::ManagementTypeEq(management_type.clone()));
+    }
+    if let Some(member) = query.get("member") {
+        filters.push(CircuitPredicate::MembersInclude(vec![member.clone()]));
+    }
+
+    let (proposals, total) =
+        web::block(move || query_proposals(&proposal_store_factory, filters, offset, limit))
+            .await??;
+
+    let paging = PagingBuilder::new(link, total)
+        .with_limit(limit)
+        .with_offset(offset)
+        .build();
+
+    match version.version() {
+        1 => Ok(
+            HttpResponse::Ok().json(resources::v1::proposals::ListProposalsResponse {
+                data: proposals
+                    .iter()
+                    .map(resources::v1::proposals::ProposalResponse::from)
+                    .collect(),
+                paging,
+            }),
+        ),
+        _ => {
+            let data = proposals
+                .iter()
+                .map(resources::v2::proposals::ProposalResponse::try_from)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|err| ResponseError::internal_error(err, None))?;
+            Ok(HttpResponse::Ok()
+                .json(resources::v2::proposals::ListProposalsResponse { data, paging }))
+        }
+    }
+}
+
+fn query_proposals<PSF: ProposalStoreFactory>(
+    proposal_store_factory: &PSF,
+    filters: Vec<CircuitPredicate>,
+    offset: usize,
+    limit: usize,
+) -> Result<(Vec<CircuitProposal>, usize), ResponseError> {
+    let proposals = proposal_store_factory
+        .new_proposal_store()
+        .proposals(filters)
+        .map_err(|err| {
+            ResponseError::internal_error(format!("Unable to list proposals: {}", err), None)
+        })?;
+
+    let total = proposals.total() as usize;
+    Ok((proposals.skip(offset).take(limit).collect(), total))
+}
+
+fn make_link(req: &HttpRequest, query: &HashMap<String, String>) -> String {
+    let new_queries = ["management_type", "member"]
+        .iter()
+        .filter_map(|key| query.get(*key).map(|value| format!("{}={}", key, value)))
+        .collect::<Vec<_>>();
+
+    let path = req.uri().path();
+    if new_queries.is_empty() {
+        path.to_string()
+    } else {
+        format!("{}?{}&", path, new_queries.join("&"))
+    }
+}
@@ -0,0 +1,77 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use actix_web::{http::Method, web, HttpResponse};
+use splinter::admin::service::proposal_store::ProposalStoreFactory;
+#[cfg(feature = "authorization")]
+use splinter_rest_api_common::admin::CIRCUIT_READ_PERMISSION;
+use splinter_rest_api_common::{
+    admin::{resources, ADMIN_FETCH_PROPOSAL_PROTOCOL_MIN},
+    error::ResponseError,
+    SPLINTER_PROTOCOL_VERSION,
+};
+
+use crate::{HandlerError, ProtocolVersion, Resource};
+
+type FetchProposalProtocolVersion =
+    ProtocolVersion<ADMIN_FETCH_PROPOSAL_PROTOCOL_MIN, SPLINTER_PROTOCOL_VERSION>;
+
+pub fn make_fetch_proposal_resource<PSF: ProposalStoreFactory + 'static>(
+    proposal_store_factory: PSF,
+) -> Resource {
+    Resource::build("/admin/proposals/{circuit_id}").add_method(
+        Method::GET,
+        #[cfg(feature = "authorization")]
+        CIRCUIT_READ_PERMISSION,
+        move |circuit_id: web::Path<String>, version: FetchProposalProtocolVersion| {
+            fetch_proposal(
+                circuit_id.into_inner(),
+                version,
+                proposal_store_factory.clone(),
+            )
+        },
+    )
+}
+
+async fn fetch_proposal<PSF: ProposalStoreFactory + 'static>(
+    circuit_id: String,
+    version: FetchProposalProtocolVersion,
+    proposal_store_factory: PSF,
+) -> Result<HttpResponse, HandlerError> {
+    let proposal = web::block(move || {
+        proposal_store_factory
+            .new_proposal_store()
+            .proposal(&circuit_id)
+            .map_err(|err| {
+                ResponseError::internal_error(format!("Unable to fetch proposal: {}", err), None)
+            })?
+            .ok_or_else(|| {
+                ResponseError::not_found(format!("Unable to find proposal: {}", circuit_id))
+            })
+    })
+    .await??;
+
+    match version.version() {
+        1 => Ok(
+            HttpResponse::Ok().json(resources::v1::proposals::ProposalResponse::from(&proposal))
+        ),
+        _ => {
+            let proposal_response = resources::v2::proposals::ProposalResponse::try_from(&proposal)
+                .map_err(|err| ResponseError::internal_error(err, None))?;
+            Ok(HttpResponse::Ok().json(proposal_response))
+        }
+    }
+}
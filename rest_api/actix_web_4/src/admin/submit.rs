@@ -0,0 +1,102 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(feature = "authorization-handler-rbac-scopes")]
+use actix_web::HttpMessage;
+use actix_web::{http::Method, web, HttpRequest, HttpResponse};
+use protobuf::Message;
+use splinter::admin::service::{AdminCommands, AdminServiceError};
+use splinter::protos::admin::CircuitManagementPayload;
+#[cfg(feature = "authorization-handler-rbac-scopes")]
+use splinter::rest_api::auth::authorization::{PayloadScopeAuthorization, RequestScope};
+use splinter::service::instance::ServiceError;
+#[cfg(feature = "authorization")]
+use splinter_rest_api_common::admin::CIRCUIT_WRITE_PERMISSION;
+use splinter_rest_api_common::{
+    admin::ADMIN_SUBMIT_PROTOCOL_MIN, error::ResponseError, SPLINTER_PROTOCOL_VERSION,
+};
+
+use crate::{HandlerError, ProtocolVersion, Resource};
+
+type SubmitProtocolVersion = ProtocolVersion<ADMIN_SUBMIT_PROTOCOL_MIN, SPLINTER_PROTOCOL_VERSION>;
+
+pub fn make_submit_resource<A: AdminCommands + Clone + 'static>(admin_commands: A) -> Resource {
+    let resource = Resource::build("/admin/submit");
+    // The circuit a payload concerns is used to check permissions that are restricted to
+    // particular circuits
+    #[cfg(feature = "authorization-handler-rbac-scopes")]
+    let resource = resource.with_payload_scope();
+
+    resource.add_method(
+        Method::POST,
+        #[cfg(feature = "authorization")]
+        CIRCUIT_WRITE_PERMISSION,
+        move |req: HttpRequest, body: web::Bytes, _: SubmitProtocolVersion| {
+            submit(req, body, admin_commands.clone())
+        },
+    )
+}
+
+async fn submit<A: AdminCommands + 'static>(
+    #[allow(unused_variables)] req: HttpRequest,
+    body: web::Bytes,
+    admin_commands: A,
+) -> Result<HttpResponse, HandlerError> {
+    let payload = match CircuitManagementPayload::parse_from_bytes(&body) {
+        Ok(payload) => payload,
+        Err(err) => {
+            return Ok(HttpResponse::BadRequest().json(json!({ "message": format!("{}", err) })))
+        }
+    };
+
+    #[cfg(feature = "authorization-handler-rbac-scopes")]
+    {
+        let payload_scope_authorization =
+            req.extensions().get::<PayloadScopeAuthorization>().cloned();
+        if let Some(authorization) = payload_scope_authorization {
+            match RequestScope::from_circuit_management_payload(&payload) {
+                Ok(scope) if authorization.authorize(&scope) => (),
+                Ok(_) => return Err(ResponseError::NotAuthorized.into()),
+                Err(err) => {
+                    return Ok(HttpResponse::BadRequest().json(json!({
+                        "message": format!("Failed to parse payload: {}", err)
+                    })))
+                }
+            }
+        }
+    }
+
+    let result = web::block(
+        move || match admin_commands.submit_circuit_change(payload) {
+            Ok(()) => Ok(Ok(())),
+            Err(AdminServiceError::ServiceError(ServiceError::UnableToHandleMessage(err))) => {
+                debug!("{}", err);
+                Ok(Err(format!("Unable to handle message: {}", err)))
+            }
+            Err(AdminServiceError::ServiceError(ServiceError::InvalidMessageFormat(err))) => {
+                Ok(Err(format!("Failed to parse payload: {}", err)))
+            }
+            Err(err) => Err(ResponseError::internal_error(
+                format!("Unable to submit circuit management payload: {}", err),
+                None,
+            )),
+        },
+    )
+    .await??;
+
+    match result {
+        Ok(()) => Ok(HttpResponse::Accepted().finish()),
+        Err(message) => Ok(HttpResponse::BadRequest().json(json!({ "message": message }))),
+    }
+}
@@ -0,0 +1,292 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(all(feature = "https-bind", feature = "tls-reload"))]
+use std::path::PathBuf;
+use std::sync::mpsc;
+#[cfg(all(feature = "https-bind", feature = "tls-reload"))]
+use std::sync::{Arc, RwLock};
+use std::thread;
+
+#[cfg(feature = "authorization")]
+use actix_web::http::Method;
+use actix_web::{dev::ServerHandle, middleware, rt::System, App, HttpServer};
+#[cfg(all(feature = "https-bind", feature = "tls-reload"))]
+use openssl::ssl::{SniError, SslContext};
+#[cfg(all(feature = "https-bind", feature = "tls-reload"))]
+use splinter::error::InternalError;
+#[cfg(feature = "authorization")]
+use splinter::rest_api::auth::authorization::{AuthorizationHandler, PermissionMap};
+use splinter::rest_api::{auth::identity::IdentityProvider, BindConfig, RestApiServerError};
+#[cfg(all(feature = "https-bind", feature = "tls-reload"))]
+use splinter::transport::tls::reload::TlsReloader;
+
+use crate::auth::Authorization;
+
+use super::Resource;
+
+/// Shutdown handle returned by `RestApi::run`. Allows rest api instance to be shut down
+/// gracefully.
+pub struct RestApiShutdownHandle {
+    server_handle: ServerHandle,
+    port_numbers: Vec<u16>,
+}
+
+impl RestApiShutdownHandle {
+    pub fn shutdown(&self) -> Result<(), RestApiServerError> {
+        debug!("Shutting down Rest API");
+        System::new().block_on(self.server_handle.stop(true));
+        debug!("Graceful signal sent to Rest API");
+
+        Ok(())
+    }
+
+    pub fn port_numbers(&self) -> Vec<u16> {
+        self.port_numbers.clone()
+    }
+}
+
+/// `RestApi` is used to create an instance of a restful web server.
+pub struct RestApi {
+    pub(super) resources: Vec<Resource>,
+    pub(super) bind: BindConfig,
+    pub(super) identity_providers: Vec<Box<dyn IdentityProvider>>,
+    #[cfg(feature = "authorization")]
+    pub(super) authorization_handlers: Vec<Box<dyn AuthorizationHandler>>,
+    /// The reloaded TLS context, which replaces the context the server was started with
+    #[cfg(all(feature = "https-bind", feature = "tls-reload"))]
+    pub(super) tls_context: Arc<RwLock<Option<SslContext>>>,
+}
+
+impl RestApi {
+    /// An additional Resource may be added before running the RestApi
+    pub fn add_resource(mut self, value: Resource) -> Self {
+        self.resources.push(value);
+        self
+    }
+
+    /// Additional Resources may be added before running the RestApi
+    pub fn add_resources(mut self, mut values: Vec<Resource>) -> Self {
+        self.resources.append(&mut values);
+        self
+    }
+
+    /// Returns a reloader which rebuilds the TLS context of the REST API from its certificate and
+    /// key files, if the REST API is bound with HTTPS.
+    ///
+    /// Connections accepted after a reload use the new certificate; existing connections are not
+    /// affected.
+    #[cfg(all(feature = "https-bind", feature = "tls-reload"))]
+    pub fn tls_reloader(&self) -> Option<RestApiTlsReloader> {
+        match &self.bind {
+            BindConfig::Https {
+                cert_path,
+                key_path,
+                ..
+            } => Some(RestApiTlsReloader {
+                cert_path: cert_path.clone(),
+                key_path: key_path.clone(),
+                tls_context: self.tls_context.clone(),
+            }),
+            BindConfig::Http(_) => None,
+        }
+    }
+
+    pub fn run(
+        self,
+    ) -> Result<(RestApiShutdownHandle, thread::JoinHandle<()>), RestApiServerError> {
+        let (tx, rx) = mpsc::channel();
+
+        let bind_config_for_err = self.bind.clone();
+        let resources = self.resources;
+
+        #[cfg(feature = "authorization")]
+        let permission_map = resources.iter().fold(
+            PermissionMap::<Method>::new(),
+            |mut permission_map, resource| {
+                permission_map.append(&mut resource.permissions());
+                permission_map
+            },
+        );
+
+        let authorization = Authorization::new(
+            self.identity_providers,
+            #[cfg(feature = "authorization")]
+            self.authorization_handlers,
+            #[cfg(feature = "authorization")]
+            permission_map,
+        );
+
+        #[cfg(feature = "https-bind")]
+        let bind_info = match self.bind {
+            BindConfig::Https {
+                bind,
+                cert_path,
+                key_path,
+            } => {
+                #[allow(unused_mut)]
+                let mut acceptor = build_acceptor(&cert_path, &key_path)?;
+
+                // Switch to the reloaded context, if any, before the handshake is completed
+                #[cfg(feature = "tls-reload")]
+                {
+                    let tls_context = self.tls_context;
+                    acceptor.set_servername_callback(move |ssl, _| {
+                        let tls_context = tls_context
+                            .read()
+                            .unwrap_or_else(|poisoned| poisoned.into_inner());
+                        if let Some(context) = tls_context.as_ref() {
+                            ssl.set_ssl_context(context)
+                                .map_err(|_| SniError::ALERT_FATAL)?;
+                        }
+                        Ok(())
+                    });
+                }
+
+                (bind, Some(acceptor))
+            }
+            BindConfig::Http(bind) => (bind, None),
+        };
+
+        #[cfg(not(feature = "https-bind"))]
+        let BindConfig::Http(bind_info) = self.bind;
+
+        let join_handle = thread::Builder::new()
+            .name("SplinterDRestApi".into())
+            .spawn(move || {
+                let result = System::new().block_on(async move {
+                    let server = HttpServer::new(move || {
+                        resources.iter().fold(
+                            App::new()
+                                .wrap(authorization.clone())
+                                .wrap(middleware::Logger::default()),
+                            |app, resource| app.service(resource.to_actix_resource()),
+                        )
+                    });
+
+                    #[cfg(feature = "https-bind")]
+                    let (bind_url, opt_acceptor) = bind_info;
+                    #[cfg(not(feature = "https-bind"))]
+                    let bind_url = bind_info;
+
+                    #[cfg(feature = "https-bind")]
+                    let server = if let Some(acceptor) = opt_acceptor {
+                        server.bind_openssl(&bind_url, acceptor)
+                    } else {
+                        server.bind(&bind_url)
+                    };
+
+                    #[cfg(not(feature = "https-bind"))]
+                    let server = server.bind(&bind_url);
+
+                    let server = match server {
+                        Ok(server) => server,
+                        Err(err) => {
+                            let error_msg = format!("Invalid REST API bind {}: {}", bind_url, err);
+                            error!("{}", error_msg);
+                            if let Err(err) = tx.send(Err(error_msg)) {
+                                error!("Failed to notify receiver of bind error: {}", err);
+                            }
+                            return Ok(());
+                        }
+                    };
+                    let port_numbers: Vec<u16> =
+                        server.addrs().iter().map(|addrs| addrs.port()).collect();
+
+                    let server = server.disable_signals().run();
+
+                    if let Err(err) = tx.send(Ok((server.handle(), port_numbers))) {
+                        error!("Unable to send Server Handle: {}", err);
+                    }
+
+                    server.await
+                });
+
+                if let Err(err) = result {
+                    error!("REST Api unexpectedly exiting: {}", err);
+                };
+
+                info!("Rest API terminating");
+            })?;
+
+        let (server_handle, port_numbers) = rx
+            .recv()
+            .map_err(|err| {
+                RestApiServerError::StartUpError(format!(
+                    "Unable to receive Server Handle: {}",
+                    err
+                ))
+            })?
+            .map_err(|err| {
+                RestApiServerError::BindError(format!(
+                    "Failed to bind to URL {}: {}",
+                    bind_config_for_err, err
+                ))
+            })?;
+
+        Ok((
+            RestApiShutdownHandle {
+                server_handle,
+                port_numbers,
+            },
+            join_handle,
+        ))
+    }
+}
+
+#[cfg(feature = "https-bind")]
+fn build_acceptor(
+    cert_path: &str,
+    key_path: &str,
+) -> Result<openssl::ssl::SslAcceptorBuilder, openssl::error::ErrorStack> {
+    let mut acceptor = openssl::ssl::SslAcceptor::mozilla_modern(openssl::ssl::SslMethod::tls())?;
+    acceptor.set_private_key_file(key_path, openssl::ssl::SslFiletype::PEM)?;
+    acceptor.set_certificate_chain_file(cert_path)?;
+    acceptor.check_private_key()?;
+    Ok(acceptor)
+}
+
+/// Rebuilds the TLS context of a `RestApi` from its certificate and key files.
+#[cfg(all(feature = "https-bind", feature = "tls-reload"))]
+pub struct RestApiTlsReloader {
+    cert_path: String,
+    key_path: String,
+    tls_context: Arc<RwLock<Option<SslContext>>>,
+}
+
+#[cfg(all(feature = "https-bind", feature = "tls-reload"))]
+impl TlsReloader for RestApiTlsReloader {
+    fn name(&self) -> &str {
+        "rest-api"
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        vec![
+            PathBuf::from(&self.cert_path),
+            PathBuf::from(&self.key_path),
+        ]
+    }
+
+    fn reload(&self) -> Result<(), InternalError> {
+        let context = build_acceptor(&self.cert_path, &self.key_path)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?
+            .build()
+            .into_context();
+        *self
+            .tls_context
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(context);
+        Ok(())
+    }
+}
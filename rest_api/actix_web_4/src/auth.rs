@@ -0,0 +1,527 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Authorization middleware for the REST API.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use actix_utils::future::{ready, Ready};
+use actix_web::{
+    body::EitherBody,
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    http::{
+        header::{self, HeaderValue},
+        Method,
+    },
+    Error as ActixError, HttpMessage, HttpResponse,
+};
+#[cfg(feature = "authorization")]
+use splinter::rest_api::auth::authorization::{
+    AuthorizationHandler, AuthorizationHandlerResult, Permission, PermissionMap,
+};
+#[cfg(feature = "authorization-handler-rbac-scopes")]
+use splinter::rest_api::auth::authorization::{PayloadScopeAuthorization, RequestScope};
+use splinter::rest_api::auth::identity::{Identity, IdentityProvider};
+use splinter::rest_api::ErrorResponse;
+
+/// The names of the path variables that identify the circuit a request concerns
+#[cfg(feature = "authorization-handler-rbac-scopes")]
+const CIRCUIT_PATH_VARIABLES: &[&str] = &["circuit_id", "circuit"];
+
+/// Wrapper for the authorization middleware
+#[derive(Clone)]
+pub(crate) struct Authorization {
+    identity_providers: Arc<Vec<Box<dyn IdentityProvider>>>,
+    #[cfg(feature = "authorization")]
+    authorization_handlers: Arc<Vec<Box<dyn AuthorizationHandler>>>,
+    #[cfg(feature = "authorization")]
+    permission_map: Arc<PermissionMap<Method>>,
+}
+
+impl Authorization {
+    pub(crate) fn new(
+        identity_providers: Vec<Box<dyn IdentityProvider>>,
+        #[cfg(feature = "authorization")] authorization_handlers: Vec<
+            Box<dyn AuthorizationHandler>,
+        >,
+        #[cfg(feature = "authorization")] permission_map: PermissionMap<Method>,
+    ) -> Self {
+        Self {
+            identity_providers: Arc::new(identity_providers),
+            #[cfg(feature = "authorization")]
+            authorization_handlers: Arc::new(authorization_handlers),
+            #[cfg(feature = "authorization")]
+            permission_map: Arc::new(permission_map),
+        }
+    }
+
+    fn authorize(&self, req: &ServiceRequest, auth_header: Option<&str>) -> AuthorizationResult {
+        #[cfg(feature = "authorization")]
+        {
+            // Get the permission that applies to this request
+            let permission = match self.permission_map.get_permission(req.method(), req.path()) {
+                Some(perm) => perm,
+                None => return AuthorizationResult::UnknownEndpoint,
+            };
+
+            match *permission {
+                Permission::AllowUnauthenticated => AuthorizationResult::NoAuthorizationNecessary,
+                Permission::AllowAuthenticated => match self.get_identity(auth_header) {
+                    Some(identity) => AuthorizationResult::Authorized(identity),
+                    None => AuthorizationResult::Unauthorized,
+                },
+                Permission::Check { permission_id, .. } => match self.get_identity(auth_header) {
+                    Some(identity) => {
+                        // If the request concerns a particular circuit, permissions that are
+                        // restricted to that circuit are also considered
+                        #[cfg(feature = "authorization-handler-rbac-scopes")]
+                        let scope = CIRCUIT_PATH_VARIABLES
+                            .iter()
+                            .find_map(|name| {
+                                self.permission_map.get_path_variable(
+                                    req.method(),
+                                    req.path(),
+                                    name,
+                                )
+                            })
+                            .map(|circuit_id| RequestScope::new().with_circuit_id(circuit_id));
+
+                        for handler in self.authorization_handlers.iter() {
+                            #[cfg(feature = "authorization-handler-rbac-scopes")]
+                            let result = match scope {
+                                Some(ref scope) => {
+                                    handler.has_scoped_permission(&identity, permission_id, scope)
+                                }
+                                None => handler.has_permission(&identity, permission_id),
+                            };
+                            #[cfg(not(feature = "authorization-handler-rbac-scopes"))]
+                            let result = handler.has_permission(&identity, permission_id);
+
+                            match result {
+                                Ok(AuthorizationHandlerResult::Allow) => {
+                                    return AuthorizationResult::Authorized(identity)
+                                }
+                                Ok(AuthorizationHandlerResult::Deny) => {
+                                    return AuthorizationResult::Unauthorized
+                                }
+                                Ok(AuthorizationHandlerResult::Continue) => {}
+                                Err(err) => error!("{}", err),
+                            }
+                        }
+
+                        // The scope of some requests can only be determined from their payload,
+                        // so the check is completed by the endpoint
+                        #[cfg(feature = "authorization-handler-rbac-scopes")]
+                        if scope.is_none()
+                            && self
+                                .permission_map
+                                .is_payload_scoped(req.method(), req.path())
+                        {
+                            return AuthorizationResult::PayloadScopeRequired(
+                                identity,
+                                permission_id,
+                            );
+                        }
+
+                        // No handler allowed the request, so deny by default
+                        AuthorizationResult::Unauthorized
+                    }
+                    None => AuthorizationResult::Unauthorized,
+                },
+            }
+        }
+        #[cfg(not(feature = "authorization"))]
+        {
+            let _ = req;
+            match self.get_identity(auth_header) {
+                Some(identity) => AuthorizationResult::Authorized(identity),
+                None => AuthorizationResult::Unauthorized,
+            }
+        }
+    }
+
+    fn get_identity(&self, auth_header: Option<&str>) -> Option<Identity> {
+        let authorization = auth_header?.parse().ok()?;
+        self.identity_providers.iter().find_map(|provider| {
+            provider.get_identity(&authorization).unwrap_or_else(|err| {
+                error!("{}", err);
+                None
+            })
+        })
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for Authorization
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = ActixError> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = ActixError;
+    type InitError = ();
+    type Transform = AuthorizationMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(AuthorizationMiddleware {
+            authorization: self.clone(),
+            service,
+        }))
+    }
+}
+
+pub(crate) struct AuthorizationMiddleware<S> {
+    authorization: Authorization,
+    service: S,
+}
+
+impl<S, B> Service<ServiceRequest> for AuthorizationMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = ActixError> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = ActixError;
+    #[allow(clippy::type_complexity)]
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        if req.method() == Method::OPTIONS {
+            return self.forward(req);
+        }
+
+        #[cfg(feature = "authorization")]
+        if !matches!(
+            *req.method(),
+            Method::GET
+                | Method::POST
+                | Method::PUT
+                | Method::PATCH
+                | Method::DELETE
+                | Method::HEAD
+        ) {
+            return respond(
+                req,
+                HttpResponse::BadRequest().json(ErrorResponse::bad_request(
+                    "HTTP method not supported by Splinter REST API",
+                )),
+            );
+        }
+
+        let auth_header = match req
+            .headers()
+            .get("Authorization")
+            .map(|auth| auth.to_str())
+            .transpose()
+        {
+            Ok(opt) => opt.map(String::from),
+            // Not including the error since it could leak secrets from the Authorization header
+            Err(_) => {
+                return respond(
+                    req,
+                    HttpResponse::BadRequest().json(ErrorResponse::bad_request(
+                        "Authorization header must contain only visible ASCII characters",
+                    )),
+                )
+            }
+        };
+
+        match self.authorization.authorize(&req, auth_header.as_deref()) {
+            AuthorizationResult::Authorized(identity) => {
+                debug!("Authenticated user {:?}", identity);
+                req.extensions_mut().insert(identity);
+            }
+            #[cfg(feature = "authorization-handler-rbac-scopes")]
+            AuthorizationResult::PayloadScopeRequired(identity, permission_id) => {
+                debug!("Authenticated user {:?}", identity);
+                req.extensions_mut().insert(PayloadScopeAuthorization::new(
+                    identity.clone(),
+                    permission_id,
+                    self.authorization.authorization_handlers.to_vec(),
+                ));
+                req.extensions_mut().insert(identity);
+            }
+            #[cfg(feature = "authorization")]
+            AuthorizationResult::NoAuthorizationNecessary => {}
+            AuthorizationResult::Unauthorized => {
+                return respond(
+                    req,
+                    HttpResponse::Unauthorized().json(ErrorResponse::unauthorized()),
+                )
+            }
+            #[cfg(feature = "authorization")]
+            AuthorizationResult::UnknownEndpoint => {
+                return respond(
+                    req,
+                    HttpResponse::NotFound().json(ErrorResponse::not_found("endpoint not found")),
+                )
+            }
+        }
+
+        self.forward(req)
+    }
+}
+
+impl<S, B> AuthorizationMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = ActixError> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    /// Passes the request on to the wrapped service, adding the
+    /// `Access-Control-Allow-Credentials: true` header to the response.
+    #[allow(clippy::type_complexity)]
+    fn forward(
+        &self,
+        req: ServiceRequest,
+    ) -> Pin<Box<dyn Future<Output = Result<ServiceResponse<EitherBody<B>>, ActixError>>>> {
+        let fut = self.service.call(req);
+        Box::pin(async move {
+            let mut res = fut.await?;
+            res.headers_mut().insert(
+                header::ACCESS_CONTROL_ALLOW_CREDENTIALS,
+                HeaderValue::from_static("true"),
+            );
+            Ok(res.map_into_left_body())
+        })
+    }
+}
+
+/// Responds to the request without passing it on to the wrapped service.
+#[allow(clippy::type_complexity)]
+fn respond<B: 'static>(
+    req: ServiceRequest,
+    response: HttpResponse,
+) -> Pin<Box<dyn Future<Output = Result<ServiceResponse<EitherBody<B>>, ActixError>>>> {
+    Box::pin(ready(Ok(req.into_response(response).map_into_right_body())))
+}
+
+/// The result of checking a request's authorization
+enum AuthorizationResult {
+    /// The client was authorized to the given identity based on the authorization header
+    Authorized(Identity),
+    /// The client was authenticated to the given identity, but whether it has the given
+    /// permission depends on the circuit the request's payload concerns
+    #[cfg(feature = "authorization-handler-rbac-scopes")]
+    PayloadScopeRequired(Identity, &'static str),
+    /// The requested endpoint does not require authorization
+    #[cfg(feature = "authorization")]
+    NoAuthorizationNecessary,
+    /// The authorization header is empty or invalid
+    Unauthorized,
+    /// The request endpoint is not defined
+    #[cfg(feature = "authorization")]
+    UnknownEndpoint,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "authorization-handler-rbac-scopes")]
+    use actix_web::HttpRequest;
+    use actix_web::{http::StatusCode, test, web, App};
+    #[cfg(feature = "authorization-handler-rbac-scopes")]
+    use splinter::{error::InternalError, rest_api::auth::AuthorizationHeader};
+
+    /// Verifies that the authorization middleware sets the `Access-Control-Allow-Credentials: true`
+    /// header for `OPTIONS` requests.
+    #[actix_web::test]
+    async fn auth_middleware_options_request_header() {
+        let app = test::init_service(
+            App::new()
+                .wrap(Authorization::new(
+                    vec![],
+                    #[cfg(feature = "authorization")]
+                    vec![],
+                    #[cfg(feature = "authorization")]
+                    PermissionMap::new(),
+                ))
+                .route(
+                    "/",
+                    web::route().method(Method::OPTIONS).to(HttpResponse::Ok),
+                ),
+        )
+        .await;
+
+        let req = test::TestRequest::with_uri("/")
+            .method(Method::OPTIONS)
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            resp.headers().get("Access-Control-Allow-Credentials"),
+            Some(&HeaderValue::from_static("true"))
+        );
+    }
+
+    /// Verifies that the authorization middleware returns a `401 Unauthorized` response when no
+    /// identity can be determined for the request. This is simulated by not configuring any
+    /// identity providers.
+    #[actix_web::test]
+    async fn auth_middleware_unauthorized() {
+        #[cfg(feature = "authorization")]
+        let permission_map = {
+            let mut permission_map = PermissionMap::new();
+            permission_map.add_permission(Method::GET, "/", Permission::AllowAuthenticated);
+            permission_map
+        };
+
+        let app = test::init_service(
+            App::new()
+                .wrap(Authorization::new(
+                    vec![],
+                    #[cfg(feature = "authorization")]
+                    vec![],
+                    #[cfg(feature = "authorization")]
+                    permission_map,
+                ))
+                .route("/", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        let req = test::TestRequest::with_uri("/").to_request();
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    /// Verifies that the authorization middleware checks permissions against the circuit a request
+    /// concerns:
+    ///
+    /// 1. A request whose path identifies a circuit the client has been granted the permission for
+    ///    is allowed
+    /// 2. A request whose path identifies any other circuit is denied
+    /// 3. A request to a payload-scoped endpoint is passed to the endpoint along with a
+    ///    `PayloadScopeAuthorization` that only allows the granted circuit
+    #[cfg(feature = "authorization-handler-rbac-scopes")]
+    #[actix_web::test]
+    async fn auth_middleware_scoped_permission() {
+        let permission = Permission::Check {
+            permission_id: "circuit.read",
+            permission_display_name: "Circuit read",
+            permission_description: "Allows the client to read circuits",
+        };
+        let mut permission_map = PermissionMap::new();
+        permission_map.add_permission(Method::GET, "/circuits/{circuit_id}", permission);
+        permission_map.add_permission(Method::POST, "/submit", permission);
+        permission_map.add_payload_scoped_endpoint(Method::POST, "/submit");
+
+        let app = test::init_service(
+            App::new()
+                .wrap(Authorization::new(
+                    vec![Box::new(AlwaysAcceptIdentityProvider)],
+                    vec![Box::new(CircuitScopedAuthorizationHandler)],
+                    permission_map,
+                ))
+                .route("/circuits/{circuit_id}", web::get().to(HttpResponse::Ok))
+                .route(
+                    "/submit",
+                    web::post().to(|req: HttpRequest| async move {
+                        let authorized = req
+                            .extensions()
+                            .get::<PayloadScopeAuthorization>()
+                            .map(|authorization| {
+                                authorization
+                                    .authorize(&RequestScope::new().with_circuit_id("abcde-01234"))
+                                    && !authorization.authorize(
+                                        &RequestScope::new().with_circuit_id("fghij-56789"),
+                                    )
+                            })
+                            .unwrap_or(false);
+                        if authorized {
+                            HttpResponse::Ok().finish()
+                        } else {
+                            HttpResponse::Unauthorized().finish()
+                        }
+                    }),
+                ),
+        )
+        .await;
+
+        let req = test::TestRequest::with_uri("/circuits/abcde-01234")
+            .insert_header(("Authorization", "test"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let req = test::TestRequest::with_uri("/circuits/fghij-56789")
+            .insert_header(("Authorization", "test"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+        let req = test::TestRequest::post()
+            .uri("/submit")
+            .insert_header(("Authorization", "test"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    /// An identity provider that always returns the same identity
+    #[cfg(feature = "authorization-handler-rbac-scopes")]
+    #[derive(Clone)]
+    struct AlwaysAcceptIdentityProvider;
+
+    #[cfg(feature = "authorization-handler-rbac-scopes")]
+    impl IdentityProvider for AlwaysAcceptIdentityProvider {
+        fn get_identity(&self, _: &AuthorizationHeader) -> Result<Option<Identity>, InternalError> {
+            Ok(Some(Identity::Custom("identity".into())))
+        }
+
+        fn clone_box(&self) -> Box<dyn IdentityProvider> {
+            Box::new(self.clone())
+        }
+    }
+
+    /// An authorization handler that only grants permissions for the circuit `abcde-01234`
+    #[cfg(feature = "authorization-handler-rbac-scopes")]
+    #[derive(Clone)]
+    struct CircuitScopedAuthorizationHandler;
+
+    #[cfg(feature = "authorization-handler-rbac-scopes")]
+    impl AuthorizationHandler for CircuitScopedAuthorizationHandler {
+        fn has_permission(
+            &self,
+            _: &Identity,
+            _: &str,
+        ) -> Result<AuthorizationHandlerResult, InternalError> {
+            Ok(AuthorizationHandlerResult::Continue)
+        }
+
+        fn has_scoped_permission(
+            &self,
+            _: &Identity,
+            _: &str,
+            scope: &RequestScope,
+        ) -> Result<AuthorizationHandlerResult, InternalError> {
+            match scope.circuit_id() {
+                Some("abcde-01234") => Ok(AuthorizationHandlerResult::Allow),
+                _ => Ok(AuthorizationHandlerResult::Continue),
+            }
+        }
+
+        fn clone_box(&self) -> Box<dyn AuthorizationHandler> {
+            Box::new(self.clone())
+        }
+    }
+}
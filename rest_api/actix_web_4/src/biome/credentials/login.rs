@@ -0,0 +1,180 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::sync::Arc;
+
+use actix_web::{http::Method, web, HttpResponse};
+use splinter::biome::credentials::{
+    rest_api::BiomeCredentialsRestConfig,
+    store::{CredentialsStore, CredentialsStoreError},
+};
+#[cfg(feature = "biome-credentials-totp")]
+use splinter::biome::credentials::{store::TotpSecret, totp};
+use splinter::biome::refresh_tokens::store::RefreshTokenStore;
+#[cfg(feature = "authorization")]
+use splinter::rest_api::auth::authorization::Permission;
+use splinter::rest_api::sessions::{AccessTokenIssuer, ClaimsBuilder, TokenIssuer};
+use splinter_rest_api_common::biome::credentials::{UsernamePassword, BIOME_LOGIN_PROTOCOL_MIN};
+use splinter_rest_api_common::{error::ResponseError, SPLINTER_PROTOCOL_VERSION};
+
+#[cfg(feature = "biome-credentials-totp")]
+use super::unix_time_secs;
+use super::{internal_error, parse_payload};
+use crate::{HandlerError, ProtocolVersion, Resource};
+
+type LoginProtocolVersion = ProtocolVersion<BIOME_LOGIN_PROTOCOL_MIN, SPLINTER_PROTOCOL_VERSION>;
+
+/// Defines a REST endpoint for login
+///
+/// The payload should be in the JSON format:
+///   {
+///       "username": <existing username of the user>
+///       "hashed_password": <hash of the user's existing password>
+///       "totp_code": <current TOTP code, required if the user has enabled TOTP>
+///   }
+pub fn make_login_route(
+    credentials_store: Arc<dyn CredentialsStore>,
+    refresh_token_store: Arc<dyn RefreshTokenStore>,
+    rest_config: Arc<BiomeCredentialsRestConfig>,
+    token_issuer: Arc<AccessTokenIssuer>,
+) -> Resource {
+    Resource::build("/biome/login").add_method(
+        Method::POST,
+        #[cfg(feature = "authorization")]
+        Permission::AllowUnauthenticated,
+        move |body: web::Bytes, _: LoginProtocolVersion| {
+            handle_login(
+                body,
+                credentials_store.clone(),
+                refresh_token_store.clone(),
+                rest_config.clone(),
+                token_issuer.clone(),
+            )
+        },
+    )
+}
+
+async fn handle_login(
+    body: web::Bytes,
+    credentials_store: Arc<dyn CredentialsStore>,
+    refresh_token_store: Arc<dyn RefreshTokenStore>,
+    rest_config: Arc<BiomeCredentialsRestConfig>,
+    token_issuer: Arc<AccessTokenIssuer>,
+) -> Result<HttpResponse, HandlerError> {
+    let username_password = parse_payload::<UsernamePassword>(&body)?;
+
+    let (user_id, token, refresh_token) = web::block(move || {
+        let credentials = credentials_store
+            .fetch_credential_by_username(&username_password.username)
+            .map_err(|err| match err {
+                CredentialsStoreError::NotFoundError(_) => ResponseError::bad_request(format!(
+                    "Username not found: {}",
+                    username_password.username
+                )),
+                err => internal_error("Failed to fetch credentials", err),
+            })?;
+
+        let is_valid = credentials
+            .verify_password(&username_password.hashed_password)
+            .map_err(|err| internal_error("Failed to verify password", err))?;
+        if !is_valid {
+            return Err(ResponseError::bad_request("Invalid password"));
+        }
+
+        #[cfg(feature = "biome-credentials-totp")]
+        check_totp_code(
+            &*credentials_store,
+            &credentials.user_id,
+            username_password.totp_code.as_deref(),
+        )?;
+
+        let claims = ClaimsBuilder::default()
+            .with_user_id(&credentials.user_id)
+            .with_issuer(&rest_config.issuer())
+            .with_duration(rest_config.access_token_duration())
+            .build()
+            .map_err(|err| internal_error("Failed to build claim", err))?;
+        let token = token_issuer
+            .issue_token_with_claims(claims)
+            .map(|token| format!("Biome:{}", token))
+            .map_err(|err| internal_error("Failed to issue token", err))?;
+
+        let refresh_claims = ClaimsBuilder::default()
+            .with_user_id(&credentials.user_id)
+            .with_issuer(&rest_config.issuer())
+            .with_duration(rest_config.refresh_token_duration())
+            .build()
+            .map_err(|err| internal_error("Failed to build refresh claim", err))?;
+        let refresh_token = token_issuer
+            .issue_refresh_token_with_claims(refresh_claims)
+            .map_err(|err| internal_error("Failed to issue refresh token", err))?;
+
+        refresh_token_store
+            .add_token(&credentials.user_id, &refresh_token)
+            .map_err(|err| internal_error("Failed to store refresh token", err))?;
+
+        Ok((credentials.user_id, token, refresh_token))
+    })
+    .await??;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "message": "Successful login",
+        "user_id": user_id,
+        "token": token,
+        "refresh_token": refresh_token,
+    })))
+}
+
+/// Checks the TOTP code provided at login if the user has enabled TOTP, recording the time step
+/// the code was accepted for so that it can not be used again.
+#[cfg(feature = "biome-credentials-totp")]
+fn check_totp_code(
+    credentials_store: &dyn CredentialsStore,
+    user_id: &str,
+    code: Option<&str>,
+) -> Result<(), ResponseError> {
+    let secret = match credentials_store
+        .fetch_totp_secret(user_id)
+        .map_err(|err| internal_error("Failed to fetch TOTP secret", err))?
+    {
+        Some(secret) if secret.enabled => secret,
+        _ => return Ok(()),
+    };
+
+    let code = code.ok_or_else(|| ResponseError::bad_request("TOTP code required"))?;
+    let step = verify_totp_code(&secret, code)?
+        .ok_or_else(|| ResponseError::bad_request("Invalid TOTP code"))?;
+
+    credentials_store
+        .set_totp_secret(TotpSecret {
+            last_used_step: Some(step),
+            ..secret
+        })
+        .map_err(|err| internal_error("Failed to update TOTP secret", err))
+}
+
+/// Verifies a code against the user's secret at the current time, returning the time step the
+/// code was accepted for.
+#[cfg(feature = "biome-credentials-totp")]
+pub(super) fn verify_totp_code(
+    secret: &TotpSecret,
+    code: &str,
+) -> Result<Option<i64>, ResponseError> {
+    totp::verify_code(
+        &secret.secret,
+        code,
+        unix_time_secs()?,
+        secret.last_used_step,
+    )
+    .map_err(|err| internal_error("Failed to verify TOTP code", err))
+}
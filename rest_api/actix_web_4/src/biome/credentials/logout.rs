@@ -0,0 +1,59 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::sync::Arc;
+
+use actix_web::{http::Method, web, HttpRequest, HttpResponse};
+use splinter::biome::refresh_tokens::store::{RefreshTokenError, RefreshTokenStore};
+#[cfg(feature = "authorization")]
+use splinter::rest_api::auth::authorization::Permission;
+use splinter_rest_api_common::biome::credentials::BIOME_LOGOUT_PROTOCOL_MIN;
+use splinter_rest_api_common::{error::ResponseError, SPLINTER_PROTOCOL_VERSION};
+
+use super::{get_user, internal_error};
+use crate::{HandlerError, ProtocolVersion, Resource};
+
+type LogoutProtocolVersion = ProtocolVersion<BIOME_LOGOUT_PROTOCOL_MIN, SPLINTER_PROTOCOL_VERSION>;
+
+/// Defines a REST endpoint to remove any refresh tokens belonging to the user.
+pub fn make_logout_route(refresh_token_store: Arc<dyn RefreshTokenStore>) -> Resource {
+    Resource::build("/biome/logout").add_method(
+        Method::PATCH,
+        #[cfg(feature = "authorization")]
+        Permission::AllowAuthenticated,
+        move |req: HttpRequest, _: LogoutProtocolVersion| {
+            handle_logout(req, refresh_token_store.clone())
+        },
+    )
+}
+
+async fn handle_logout(
+    req: HttpRequest,
+    refresh_token_store: Arc<dyn RefreshTokenStore>,
+) -> Result<HttpResponse, HandlerError> {
+    let user_id = get_user(&req)?;
+
+    web::block(move || {
+        refresh_token_store
+            .remove_token(&user_id)
+            .map_err(|err| match err {
+                RefreshTokenError::NotFoundError(_) => {
+                    ResponseError::bad_request(format!("User not found: {}", user_id))
+                }
+                err => internal_error("Failed to remove refresh token", err),
+            })
+    })
+    .await??;
+
+    Ok(HttpResponse::Ok().json(json!({ "message": "User successfully logged out" })))
+}
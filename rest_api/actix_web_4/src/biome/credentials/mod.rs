@@ -0,0 +1,390 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Provides the Biome credentials endpoints for registering and authenticating users with a
+//! username and password.
+
+mod login;
+mod logout;
+#[cfg(feature = "biome-credentials-reset")]
+mod password_reset;
+mod register;
+mod token;
+#[cfg(feature = "biome-credentials-totp")]
+mod totp;
+mod user;
+mod verify;
+
+use std::sync::Arc;
+#[cfg(any(
+    feature = "biome-credentials-reset",
+    feature = "biome-credentials-totp"
+))]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use actix_web::HttpRequest;
+#[cfg(feature = "biome-key-management")]
+use splinter::biome::key_management::store::KeyStore;
+use splinter::biome::{
+    credentials::{
+        rest_api::{BiomeCredentialsRestConfig, BiomeCredentialsRestConfigBuilder},
+        store::CredentialsStore,
+    },
+    refresh_tokens::store::RefreshTokenStore,
+};
+use splinter::error::InvalidStateError;
+use splinter::rest_api::{
+    auth::identity::{biome::BiomeUserIdentityProvider, Identity},
+    secrets::{AutoSecretManager, SecretManager},
+    sessions::{default_validation, AccessTokenIssuer},
+};
+use splinter_rest_api_common::error::ResponseError;
+
+use crate::{Resource, RestResourceProvider};
+
+/// Provides the following REST API endpoints for Biome credentials:
+///
+/// * `POST /biome/login` - Login endpoint for getting access tokens and refresh tokens
+/// * `PATCH /biome/logout` - Logout endpoint for removing refresh tokens
+/// * `POST /biome/register` - Creates credentials for a user
+/// * `POST /biome/token` - Creates a new access token for the authorized user
+/// * `POST /biome/verify` - Verify a user's password
+/// * `GET /biome/users` - Get a list of all users in biome
+/// * `PUT /biome/users/{id}` - Update user with specified ID (requires the `biome-key-management`
+///   feature)
+/// * `GET /biome/users/{id}` - Retrieve user with specified ID (requires the
+///   `biome-key-management` feature)
+/// * `DELETE /biome/users/{id}` - Remove user with specified ID (requires the
+///   `biome-key-management` feature)
+/// * `POST /biome/users/{id}/password_reset` - Issue a password reset token for the user with
+///   specified ID (requires the `biome-credentials-reset` feature)
+/// * `POST /biome/password_reset` - Reset a user's password using a password reset token
+///   (requires the `biome-credentials-reset` feature)
+/// * `POST`, `PUT` and `DELETE /biome/totp` - Start, confirm and disable TOTP for the authorized
+///   user (requires the `biome-credentials-totp` feature)
+///
+/// The payloads and responses are the same as those of the Actix Web v1 implementation.
+pub struct BiomeCredentialsRestResourceProvider {
+    #[cfg(feature = "biome-key-management")]
+    key_store: Arc<dyn KeyStore>,
+    credentials_config: Arc<BiomeCredentialsRestConfig>,
+    token_secret_manager: Arc<dyn SecretManager>,
+    refresh_token_secret_manager: Arc<dyn SecretManager>,
+    refresh_token_store: Arc<dyn RefreshTokenStore>,
+    credentials_store: Arc<dyn CredentialsStore>,
+}
+
+impl BiomeCredentialsRestResourceProvider {
+    /// Creates a new Biome user identity provider for the Splinter REST API
+    pub fn get_identity_provider(&self) -> BiomeUserIdentityProvider {
+        BiomeUserIdentityProvider::new(
+            self.token_secret_manager.clone(),
+            default_validation(&self.credentials_config.issuer()),
+        )
+    }
+}
+
+impl RestResourceProvider for BiomeCredentialsRestResourceProvider {
+    fn resources(&self) -> Vec<Resource> {
+        let token_issuer = Arc::new(AccessTokenIssuer::new(
+            self.token_secret_manager.clone(),
+            self.refresh_token_secret_manager.clone(),
+        ));
+
+        vec![
+            user::make_list_route(self.credentials_store.clone()),
+            verify::make_verify_route(self.credentials_store.clone()),
+            login::make_login_route(
+                self.credentials_store.clone(),
+                self.refresh_token_store.clone(),
+                self.credentials_config.clone(),
+                token_issuer.clone(),
+            ),
+            token::make_token_route(
+                self.refresh_token_store.clone(),
+                self.refresh_token_secret_manager.clone(),
+                token_issuer,
+                self.credentials_config.clone(),
+            ),
+            logout::make_logout_route(self.refresh_token_store.clone()),
+            register::make_register_route(
+                self.credentials_store.clone(),
+                self.credentials_config.clone(),
+            ),
+            #[cfg(feature = "biome-key-management")]
+            user::make_user_routes(
+                self.credentials_config.clone(),
+                self.credentials_store.clone(),
+                self.key_store.clone(),
+            ),
+            #[cfg(feature = "biome-credentials-reset")]
+            password_reset::make_issue_password_reset_token_route(
+                self.credentials_store.clone(),
+                self.credentials_config.clone(),
+            ),
+            #[cfg(feature = "biome-credentials-reset")]
+            password_reset::make_password_reset_route(
+                self.credentials_store.clone(),
+                self.refresh_token_store.clone(),
+                self.credentials_config.clone(),
+            ),
+            #[cfg(feature = "biome-credentials-totp")]
+            totp::make_totp_route(
+                self.credentials_store.clone(),
+                self.credentials_config.clone(),
+            ),
+        ]
+    }
+}
+
+/// Builder for BiomeCredentialsRestResourceProvider
+#[derive(Default)]
+pub struct BiomeCredentialsRestResourceProviderBuilder {
+    #[cfg(feature = "biome-key-management")]
+    key_store: Option<Arc<dyn KeyStore>>,
+    credentials_config: Option<BiomeCredentialsRestConfig>,
+    token_secret_manager: Option<Arc<dyn SecretManager>>,
+    refresh_token_secret_manager: Option<Arc<dyn SecretManager>>,
+    refresh_token_store: Option<Arc<dyn RefreshTokenStore>>,
+    credentials_store: Option<Arc<dyn CredentialsStore>>,
+}
+
+impl BiomeCredentialsRestResourceProviderBuilder {
+    /// Sets the key store used by the `/biome/users/{id}` endpoints.
+    #[cfg(feature = "biome-key-management")]
+    pub fn with_key_store(mut self, store: impl KeyStore + 'static) -> Self {
+        self.key_store = Some(Arc::new(store));
+        self
+    }
+
+    /// Sets the configuration for the Biome credentials endpoints.
+    pub fn with_credentials_config(mut self, config: BiomeCredentialsRestConfig) -> Self {
+        self.credentials_config = Some(config);
+        self
+    }
+
+    /// Sets the store for user credentials.
+    pub fn with_credentials_store(mut self, store: impl CredentialsStore + 'static) -> Self {
+        self.credentials_store = Some(Arc::new(store));
+        self
+    }
+
+    /// Sets the secret manager used to sign and verify access tokens.
+    pub fn with_token_secret_manager(
+        mut self,
+        secret_manager: impl SecretManager + 'static,
+    ) -> Self {
+        self.token_secret_manager = Some(Arc::new(secret_manager));
+        self
+    }
+
+    /// Sets the secret manager used to sign and verify refresh tokens.
+    pub fn with_refresh_token_secret_manager(
+        mut self,
+        secret_manager: impl SecretManager + 'static,
+    ) -> Self {
+        self.refresh_token_secret_manager = Some(Arc::new(secret_manager));
+        self
+    }
+
+    /// Sets the store for refresh tokens.
+    pub fn with_refresh_token_store(mut self, store: impl RefreshTokenStore + 'static) -> Self {
+        self.refresh_token_store = Some(Arc::new(store));
+        self
+    }
+
+    /// Consumes the builder and returns a BiomeCredentialsRestResourceProvider
+    pub fn build(self) -> Result<BiomeCredentialsRestResourceProvider, InvalidStateError> {
+        #[cfg(feature = "biome-key-management")]
+        let key_store = self
+            .key_store
+            .ok_or_else(|| InvalidStateError::with_message("Missing key store".to_string()))?;
+
+        let credentials_config = match self.credentials_config {
+            Some(config) => config,
+            None => BiomeCredentialsRestConfigBuilder::default().build()?,
+        };
+
+        let refresh_token_store = self.refresh_token_store.ok_or_else(|| {
+            InvalidStateError::with_message("Missing refresh token store".to_string())
+        })?;
+
+        let credentials_store = self.credentials_store.ok_or_else(|| {
+            InvalidStateError::with_message("Missing credentials store".to_string())
+        })?;
+
+        Ok(BiomeCredentialsRestResourceProvider {
+            #[cfg(feature = "biome-key-management")]
+            key_store,
+            credentials_config: Arc::new(credentials_config),
+            token_secret_manager: self
+                .token_secret_manager
+                .unwrap_or_else(|| Arc::new(AutoSecretManager::default())),
+            refresh_token_secret_manager: self
+                .refresh_token_secret_manager
+                .unwrap_or_else(|| Arc::new(AutoSecretManager::default())),
+            refresh_token_store,
+            credentials_store,
+        })
+    }
+}
+
+/// Gets the ID of the Biome user that made the request.
+fn get_user(req: &HttpRequest) -> Result<String, ResponseError> {
+    match req.extensions().get::<Identity>() {
+        Some(Identity::User(user)) => Ok(user.clone()),
+        _ => Err(ResponseError::NotAuthorized),
+    }
+}
+
+fn parse_payload<T: serde::de::DeserializeOwned>(body: &[u8]) -> Result<T, ResponseError> {
+    serde_json::from_slice::<T>(body).map_err(|err| {
+        debug!("Error parsing payload {}", err);
+        ResponseError::bad_request(format!("Failed to parse payload: {}", err))
+    })
+}
+
+fn internal_error<E: std::fmt::Display>(context: &str, err: E) -> ResponseError {
+    ResponseError::internal_error(format!("{}: {}", context, err), None)
+}
+
+/// Returns the current time in seconds since the Unix epoch
+#[cfg(any(
+    feature = "biome-credentials-reset",
+    feature = "biome-credentials-totp"
+))]
+fn unix_time_secs() -> Result<u64, ResponseError> {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .map_err(|err| internal_error("Failed to get the current time", err))
+}
+
+#[cfg(all(test, feature = "authorization"))]
+mod tests {
+    use super::*;
+
+    use actix_web::{http::StatusCode, test, App};
+    #[cfg(feature = "biome-key-management")]
+    use splinter::biome::MemoryKeyStore;
+    use splinter::biome::{MemoryCredentialsStore, MemoryRefreshTokenStore};
+    use splinter::rest_api::auth::authorization::PermissionMap;
+
+    use crate::auth::Authorization;
+
+    /// Verifies that a user can register and log in, that the access token issued at login
+    /// authenticates requests to the Biome endpoints, and that logging out removes the refresh
+    /// token so that no new access tokens can be requested with it.
+    #[actix_web::test]
+    async fn register_login_and_logout() {
+        let credentials_store = MemoryCredentialsStore::new();
+        let builder = BiomeCredentialsRestResourceProviderBuilder::default()
+            .with_credentials_config(
+                BiomeCredentialsRestConfigBuilder::default()
+                    .with_password_encryption_cost("low")
+                    .build()
+                    .expect("Failed to build config"),
+            )
+            .with_credentials_store(credentials_store.clone())
+            .with_refresh_token_store(MemoryRefreshTokenStore::new());
+        #[cfg(feature = "biome-key-management")]
+        let builder = builder.with_key_store(MemoryKeyStore::new(credentials_store));
+        let provider = builder.build().expect("Failed to build provider");
+
+        let resources = provider.resources();
+        let permission_map =
+            resources
+                .iter()
+                .fold(PermissionMap::new(), |mut permission_map, resource| {
+                    permission_map.append(&mut resource.permissions());
+                    permission_map
+                });
+        let app = test::init_service(resources.iter().fold(
+            App::new().wrap(Authorization::new(
+                vec![Box::new(provider.get_identity_provider())],
+                vec![],
+                permission_map,
+            )),
+            |app, resource| app.service(resource.to_actix_resource()),
+        ))
+        .await;
+
+        let credentials = json!({ "username": "user", "hashed_password": "password" });
+
+        let req = test::TestRequest::post()
+            .uri("/biome/register")
+            .set_json(&credentials)
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        // A wrong password is rejected
+        let req = test::TestRequest::post()
+            .uri("/biome/login")
+            .set_json(&json!({ "username": "user", "hashed_password": "wrong" }))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let req = test::TestRequest::post()
+            .uri("/biome/login")
+            .set_json(&credentials)
+            .to_request();
+        let login: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        let token = login["token"].as_str().expect("No token").to_string();
+        let refresh_token = login["refresh_token"]
+            .as_str()
+            .expect("No refresh token")
+            .to_string();
+        let auth_header = ("Authorization", format!("Bearer {}", token));
+
+        // The verify endpoint requires an access token
+        let req = test::TestRequest::post()
+            .uri("/biome/verify")
+            .set_json(&credentials)
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+        let req = test::TestRequest::post()
+            .uri("/biome/verify")
+            .insert_header(auth_header.clone())
+            .set_json(&credentials)
+            .to_request();
+        let verify: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(verify["user_id"], login["user_id"]);
+
+        let req = test::TestRequest::post()
+            .uri("/biome/token")
+            .insert_header(auth_header.clone())
+            .set_json(&json!({ "token": refresh_token }))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let req = test::TestRequest::patch()
+            .uri("/biome/logout")
+            .insert_header(auth_header.clone())
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let req = test::TestRequest::post()
+            .uri("/biome/token")
+            .insert_header(auth_header)
+            .set_json(&json!({ "token": refresh_token }))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    }
+}
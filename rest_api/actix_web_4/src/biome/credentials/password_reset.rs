@@ -0,0 +1,182 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::sync::Arc;
+
+use actix_web::{http::Method, web, HttpResponse};
+use splinter::biome::credentials::{
+    reset::{generate_token, hash_token},
+    rest_api::BiomeCredentialsRestConfig,
+    store::{CredentialsStore, CredentialsStoreError, PasswordResetToken},
+};
+use splinter::biome::refresh_tokens::store::{RefreshTokenError, RefreshTokenStore};
+#[cfg(feature = "authorization")]
+use splinter::rest_api::auth::authorization::Permission;
+#[cfg(feature = "authorization")]
+use splinter_rest_api_common::biome::credentials::BIOME_USER_WRITE_PERMISSION;
+use splinter_rest_api_common::biome::credentials::{
+    PasswordReset, BIOME_PASSWORD_RESET_PROTOCOL_MIN,
+};
+use splinter_rest_api_common::{error::ResponseError, SPLINTER_PROTOCOL_VERSION};
+
+use super::{internal_error, parse_payload, unix_time_secs};
+use crate::{HandlerError, ProtocolVersion, Resource};
+
+type PasswordResetProtocolVersion =
+    ProtocolVersion<BIOME_PASSWORD_RESET_PROTOCOL_MIN, SPLINTER_PROTOCOL_VERSION>;
+
+const INVALID_TOKEN_MESSAGE: &str = "Invalid or expired password reset token";
+
+/// Defines a REST endpoint for an administrator to issue a password reset token for a user
+///
+/// The token is returned once, in the response, and may be used a single time before it expires.
+/// Issuing a new token for a user replaces any token previously issued for them.
+pub fn make_issue_password_reset_token_route(
+    credentials_store: Arc<dyn CredentialsStore>,
+    rest_config: Arc<BiomeCredentialsRestConfig>,
+) -> Resource {
+    Resource::build("/biome/users/{id}/password_reset").add_method(
+        Method::POST,
+        #[cfg(feature = "authorization")]
+        BIOME_USER_WRITE_PERMISSION,
+        move |user_id: web::Path<String>, _: PasswordResetProtocolVersion| {
+            handle_issue_password_reset_token(
+                user_id.into_inner(),
+                credentials_store.clone(),
+                rest_config.clone(),
+            )
+        },
+    )
+}
+
+async fn handle_issue_password_reset_token(
+    user_id: String,
+    credentials_store: Arc<dyn CredentialsStore>,
+    rest_config: Arc<BiomeCredentialsRestConfig>,
+) -> Result<HttpResponse, HandlerError> {
+    let (user_id, token, expires_at) = web::block(move || {
+        credentials_store
+            .fetch_credential_by_user_id(&user_id)
+            .map_err(|err| match err {
+                CredentialsStoreError::NotFoundError(_) => {
+                    ResponseError::not_found(format!("User not found: {}", user_id))
+                }
+                err => internal_error("Failed to fetch credentials", err),
+            })?;
+
+        let token = generate_token()
+            .map_err(|err| internal_error("Failed to generate password reset token", err))?;
+        let expires_at =
+            (unix_time_secs()? + rest_config.password_reset_token_duration().as_secs()) as i64;
+
+        credentials_store
+            .add_password_reset_token(PasswordResetToken {
+                user_id: user_id.clone(),
+                token_hash: hash_token(&token),
+                expires_at,
+            })
+            .map_err(|err| internal_error("Failed to add password reset token", err))?;
+
+        Ok::<_, ResponseError>((user_id, token, expires_at))
+    })
+    .await??;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "message": "Password reset token issued",
+        "user_id": user_id,
+        "reset_token": token,
+        "expires_at": expires_at,
+    })))
+}
+
+/// Defines a REST endpoint to reset a user's password using a password reset token
+///
+/// The payload should be in the JSON format:
+///   {
+///       "reset_token": <password reset token issued for the user>
+///       "new_password": <hash of the user's new password>
+///   }
+///
+/// A successful reset also removes the user's refresh tokens, so existing sessions can not be
+/// renewed.
+pub fn make_password_reset_route(
+    credentials_store: Arc<dyn CredentialsStore>,
+    refresh_token_store: Arc<dyn RefreshTokenStore>,
+    rest_config: Arc<BiomeCredentialsRestConfig>,
+) -> Resource {
+    Resource::build("/biome/password_reset").add_method(
+        Method::POST,
+        #[cfg(feature = "authorization")]
+        Permission::AllowUnauthenticated,
+        move |body: web::Bytes, _: PasswordResetProtocolVersion| {
+            handle_password_reset(
+                body,
+                credentials_store.clone(),
+                refresh_token_store.clone(),
+                rest_config.clone(),
+            )
+        },
+    )
+}
+
+async fn handle_password_reset(
+    body: web::Bytes,
+    credentials_store: Arc<dyn CredentialsStore>,
+    refresh_token_store: Arc<dyn RefreshTokenStore>,
+    rest_config: Arc<BiomeCredentialsRestConfig>,
+) -> Result<HttpResponse, HandlerError> {
+    let password_reset = parse_payload::<PasswordReset>(&body)?;
+
+    web::block(move || {
+        // The token is taken from the store before it is checked, so that it can not be used
+        // again whether or not the reset succeeds
+        let token = credentials_store
+            .take_password_reset_token(&hash_token(&password_reset.reset_token))
+            .map_err(|err| match err {
+                CredentialsStoreError::NotFoundError(_) => {
+                    ResponseError::bad_request(INVALID_TOKEN_MESSAGE)
+                }
+                err => internal_error("Failed to take password reset token", err),
+            })?;
+
+        if (unix_time_secs()? as i64) >= token.expires_at {
+            return Err(ResponseError::bad_request(INVALID_TOKEN_MESSAGE));
+        }
+
+        let credentials = credentials_store
+            .fetch_credential_by_user_id(&token.user_id)
+            .map_err(|err| match err {
+                CredentialsStoreError::NotFoundError(_) => {
+                    ResponseError::bad_request(INVALID_TOKEN_MESSAGE)
+                }
+                err => internal_error("Failed to fetch credentials", err),
+            })?;
+
+        credentials_store
+            .update_credentials(
+                &credentials.user_id,
+                &credentials.username,
+                &password_reset.new_password,
+                rest_config.password_encryption_cost(),
+            )
+            .map_err(|err| internal_error("Failed to update credentials", err))?;
+
+        match refresh_token_store.remove_token(&credentials.user_id) {
+            Ok(()) | Err(RefreshTokenError::NotFoundError(_)) => Ok(()),
+            Err(err) => Err(internal_error("Failed to remove refresh token", err)),
+        }
+    })
+    .await??;
+
+    Ok(HttpResponse::Ok().json(json!({ "message": "Password reset successfully" })))
+}
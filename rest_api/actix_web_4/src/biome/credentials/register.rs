@@ -0,0 +1,98 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::sync::Arc;
+
+use actix_web::{http::Method, web, HttpResponse};
+use splinter::biome::credentials::{
+    rest_api::BiomeCredentialsRestConfig,
+    store::{CredentialsBuilder, CredentialsStore, CredentialsStoreError},
+};
+#[cfg(feature = "authorization")]
+use splinter::rest_api::auth::authorization::Permission;
+use splinter_rest_api_common::biome::credentials::{
+    NewUser, UsernamePassword, BIOME_REGISTER_PROTOCOL_MIN,
+};
+use splinter_rest_api_common::{error::ResponseError, SPLINTER_PROTOCOL_VERSION};
+use uuid::Uuid;
+
+use super::{internal_error, parse_payload};
+use crate::{HandlerError, ProtocolVersion, Resource};
+
+/// This is the UUID namespace for Biome user IDs generated for users that register with Biome
+/// credentials. It must match the namespace used by the Actix Web v1 implementation, so that both
+/// generate IDs from the same space.
+const UUID_NAMESPACE: Uuid = Uuid::from_u128(140899893353887994607859851180695869034);
+
+type RegisterProtocolVersion =
+    ProtocolVersion<BIOME_REGISTER_PROTOCOL_MIN, SPLINTER_PROTOCOL_VERSION>;
+
+/// Defines a REST endpoint to add a user and credentials to the database
+///
+/// The payload should be in the JSON format:
+///   {
+///       "username": <username of new user>
+///       "hashed_password": <hash of the password the user will use to log in>
+///   }
+pub fn make_register_route(
+    credentials_store: Arc<dyn CredentialsStore>,
+    rest_config: Arc<BiomeCredentialsRestConfig>,
+) -> Resource {
+    Resource::build("/biome/register").add_method(
+        Method::POST,
+        #[cfg(feature = "authorization")]
+        Permission::AllowUnauthenticated,
+        move |body: web::Bytes, _: RegisterProtocolVersion| {
+            handle_register(body, credentials_store.clone(), rest_config.clone())
+        },
+    )
+}
+
+async fn handle_register(
+    body: web::Bytes,
+    credentials_store: Arc<dyn CredentialsStore>,
+    rest_config: Arc<BiomeCredentialsRestConfig>,
+) -> Result<HttpResponse, HandlerError> {
+    let username_password = parse_payload::<UsernamePassword>(&body)?;
+    let user_id = Uuid::new_v5(&UUID_NAMESPACE, Uuid::new_v4().as_bytes()).to_string();
+
+    let (user_id, username) = web::block(move || {
+        let credentials = CredentialsBuilder::default()
+            .with_user_id(&user_id)
+            .with_username(&username_password.username)
+            .with_password(&username_password.hashed_password)
+            .with_password_encryption_cost(rest_config.password_encryption_cost())
+            .build()
+            .map_err(|err| internal_error("Failed to create credentials", err))?;
+
+        credentials_store
+            .add_credentials(credentials)
+            .map_err(|err| match err {
+                CredentialsStoreError::DuplicateError(err) => {
+                    ResponseError::bad_request(format!("Failed to create user: {}", err))
+                }
+                err => internal_error("Failed to add new credentials to database", err),
+            })?;
+
+        Ok::<_, ResponseError>((user_id, username_password.username))
+    })
+    .await??;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "message": "User created successfully",
+        "data": NewUser {
+            user_id: &user_id,
+            username: &username,
+        },
+    })))
+}
@@ -0,0 +1,123 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::sync::Arc;
+
+use actix_web::{http::Method, web, HttpRequest, HttpResponse};
+use splinter::biome::credentials::rest_api::BiomeCredentialsRestConfig;
+use splinter::biome::refresh_tokens::store::{RefreshTokenError, RefreshTokenStore};
+#[cfg(feature = "authorization")]
+use splinter::rest_api::auth::authorization::Permission;
+use splinter::rest_api::{
+    secrets::SecretManager,
+    sessions::{default_validation, validate_token, AccessTokenIssuer, ClaimsBuilder, TokenIssuer},
+};
+use splinter_rest_api_common::biome::credentials::{RefreshToken, BIOME_TOKEN_PROTOCOL_MIN};
+use splinter_rest_api_common::{error::ResponseError, SPLINTER_PROTOCOL_VERSION};
+
+use super::{get_user, internal_error};
+use crate::{HandlerError, ProtocolVersion, Resource};
+
+type TokenProtocolVersion = ProtocolVersion<BIOME_TOKEN_PROTOCOL_MIN, SPLINTER_PROTOCOL_VERSION>;
+
+/// Defines a REST endpoint for requesting a new authorization token
+///
+/// The payload should be in the JSON format:
+///   {
+///       "token": <refresh token for requesting a new auth token>
+///   }
+///
+/// Endpoint returns a payload containing a new auth token
+///   {
+///     "token": <new auth token>
+///   }
+pub fn make_token_route(
+    refresh_token_store: Arc<dyn RefreshTokenStore>,
+    refresh_token_secret_manager: Arc<dyn SecretManager>,
+    token_issuer: Arc<AccessTokenIssuer>,
+    rest_config: Arc<BiomeCredentialsRestConfig>,
+) -> Resource {
+    Resource::build("/biome/token").add_method(
+        Method::POST,
+        #[cfg(feature = "authorization")]
+        Permission::AllowAuthenticated,
+        move |req: HttpRequest, body: web::Bytes, _: TokenProtocolVersion| {
+            handle_token(
+                req,
+                body,
+                refresh_token_store.clone(),
+                refresh_token_secret_manager.clone(),
+                token_issuer.clone(),
+                rest_config.clone(),
+            )
+        },
+    )
+}
+
+async fn handle_token(
+    req: HttpRequest,
+    body: web::Bytes,
+    refresh_token_store: Arc<dyn RefreshTokenStore>,
+    refresh_token_secret_manager: Arc<dyn SecretManager>,
+    token_issuer: Arc<AccessTokenIssuer>,
+    rest_config: Arc<BiomeCredentialsRestConfig>,
+) -> Result<HttpResponse, HandlerError> {
+    let user_id = get_user(&req)?;
+    let refresh_token = serde_json::from_slice::<RefreshToken>(&body)
+        .map_err(|err| ResponseError::bad_request(format!("Malformed payload {}", err)))?
+        .token;
+
+    let token = web::block(move || {
+        let refresh_token_from_db =
+            refresh_token_store
+                .fetch_token(&user_id)
+                .map_err(|err| match err {
+                    RefreshTokenError::NotFoundError(msg) => ResponseError::forbidden(msg),
+                    err => internal_error("Failed to retrieve user refresh token", err),
+                })?;
+
+        if refresh_token != refresh_token_from_db {
+            return Err(ResponseError::forbidden("Invalid Refresh Token"));
+        }
+
+        let secret = refresh_token_secret_manager
+            .secret()
+            .map_err(|err| internal_error("Failed to fetch secret", err))?;
+        if let Err(err) = validate_token(
+            &refresh_token,
+            &secret,
+            &default_validation(&rest_config.issuer()),
+        ) {
+            debug!("Invalid refresh token: {}", err);
+            refresh_token_store
+                .remove_token(&user_id)
+                .map_err(|err| internal_error("Failed to delete refresh token", err))?;
+            return Err(ResponseError::NotAuthorized);
+        }
+
+        let claims = ClaimsBuilder::default()
+            .with_user_id(&user_id)
+            .with_issuer(&rest_config.issuer())
+            .with_duration(rest_config.access_token_duration())
+            .build()
+            .map_err(|err| internal_error("Failed to build claim", err))?;
+
+        token_issuer
+            .issue_token_with_claims(claims)
+            .map(|token| format!("Biome:{}", token))
+            .map_err(|err| internal_error("Failed to issue token", err))
+    })
+    .await??;
+
+    Ok(HttpResponse::Ok().json(json!({ "token": token })))
+}
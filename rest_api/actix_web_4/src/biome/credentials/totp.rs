@@ -0,0 +1,190 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::sync::Arc;
+
+use actix_web::{http::Method, web, HttpRequest, HttpResponse};
+use splinter::biome::credentials::{
+    rest_api::BiomeCredentialsRestConfig,
+    store::{CredentialsStore, TotpSecret},
+    totp,
+};
+#[cfg(feature = "authorization")]
+use splinter::rest_api::auth::authorization::Permission;
+use splinter_rest_api_common::biome::credentials::{TotpCode, BIOME_TOTP_PROTOCOL_MIN};
+use splinter_rest_api_common::{error::ResponseError, SPLINTER_PROTOCOL_VERSION};
+
+use super::login::verify_totp_code;
+use super::{get_user, internal_error, parse_payload};
+use crate::{HandlerError, ProtocolVersion, Resource};
+
+type TotpProtocolVersion = ProtocolVersion<BIOME_TOTP_PROTOCOL_MIN, SPLINTER_PROTOCOL_VERSION>;
+
+/// Defines the `/biome/totp` REST resource for managing the authorized user's TOTP second factor
+///
+/// * `POST` starts enrollment, returning a new secret. The secret is not required at login until
+///   enrollment is confirmed.
+/// * `PUT` confirms enrollment with a code generated from the new secret.
+/// * `DELETE` disables TOTP, with a code generated from the enabled secret.
+///
+/// The `PUT` and `DELETE` payloads should be in the JSON format:
+///   {
+///       "code": <code generated from the user's TOTP secret>
+///   }
+pub fn make_totp_route(
+    credentials_store: Arc<dyn CredentialsStore>,
+    rest_config: Arc<BiomeCredentialsRestConfig>,
+) -> Resource {
+    let credentials_store1 = credentials_store.clone();
+    let credentials_store2 = credentials_store.clone();
+    Resource::build("/biome/totp")
+        .add_method(
+            Method::POST,
+            #[cfg(feature = "authorization")]
+            Permission::AllowAuthenticated,
+            move |req: HttpRequest, _: TotpProtocolVersion| {
+                handle_enroll(req, credentials_store.clone(), rest_config.clone())
+            },
+        )
+        .add_method(
+            Method::PUT,
+            #[cfg(feature = "authorization")]
+            Permission::AllowAuthenticated,
+            move |req: HttpRequest, body: web::Bytes, _: TotpProtocolVersion| {
+                handle_confirm(req, body, credentials_store1.clone())
+            },
+        )
+        .add_method(
+            Method::DELETE,
+            #[cfg(feature = "authorization")]
+            Permission::AllowAuthenticated,
+            move |req: HttpRequest, body: web::Bytes, _: TotpProtocolVersion| {
+                handle_disable(req, body, credentials_store2.clone())
+            },
+        )
+}
+
+async fn handle_enroll(
+    req: HttpRequest,
+    credentials_store: Arc<dyn CredentialsStore>,
+    rest_config: Arc<BiomeCredentialsRestConfig>,
+) -> Result<HttpResponse, HandlerError> {
+    let user_id = get_user(&req)?;
+
+    let (encoded_secret, uri) = web::block(move || {
+        match credentials_store.fetch_totp_secret(&user_id) {
+            Ok(Some(existing)) if existing.enabled => {
+                return Err(ResponseError::bad_request(
+                    "TOTP is already enabled; it must be disabled before enrolling again",
+                ))
+            }
+            Ok(_) => (),
+            Err(err) => return Err(internal_error("Failed to fetch TOTP secret", err)),
+        }
+
+        let username = credentials_store
+            .fetch_username_by_id(&user_id)
+            .map_err(|err| internal_error("Failed to fetch username", err))?
+            .username;
+
+        let secret = totp::generate_secret()
+            .map_err(|err| internal_error("Failed to generate TOTP secret", err))?;
+        let encoded_secret = totp::encode_secret(&secret);
+        let uri = totp::provisioning_uri(&rest_config.issuer(), &username, &secret);
+
+        credentials_store
+            .set_totp_secret(TotpSecret {
+                user_id,
+                secret,
+                enabled: false,
+                last_used_step: None,
+            })
+            .map_err(|err| internal_error("Failed to set TOTP secret", err))?;
+
+        Ok((encoded_secret, uri))
+    })
+    .await??;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "message": "TOTP enrollment started; confirm with a code to enable",
+        "secret": encoded_secret,
+        "uri": uri,
+    })))
+}
+
+async fn handle_confirm(
+    req: HttpRequest,
+    body: web::Bytes,
+    credentials_store: Arc<dyn CredentialsStore>,
+) -> Result<HttpResponse, HandlerError> {
+    let user_id = get_user(&req)?;
+    let totp_code = parse_payload::<TotpCode>(&body)?;
+
+    web::block(move || {
+        let secret = match credentials_store
+            .fetch_totp_secret(&user_id)
+            .map_err(|err| internal_error("Failed to fetch TOTP secret", err))?
+        {
+            Some(secret) if !secret.enabled => secret,
+            Some(_) => return Err(ResponseError::bad_request("TOTP is already enabled")),
+            None => {
+                return Err(ResponseError::bad_request(
+                    "TOTP enrollment has not been started",
+                ))
+            }
+        };
+
+        let step = verify_totp_code(&secret, &totp_code.code)?
+            .ok_or_else(|| ResponseError::bad_request("Invalid TOTP code"))?;
+
+        credentials_store
+            .set_totp_secret(TotpSecret {
+                enabled: true,
+                last_used_step: Some(step),
+                ..secret
+            })
+            .map_err(|err| internal_error("Failed to set TOTP secret", err))
+    })
+    .await??;
+
+    Ok(HttpResponse::Ok().json(json!({ "message": "TOTP enabled" })))
+}
+
+async fn handle_disable(
+    req: HttpRequest,
+    body: web::Bytes,
+    credentials_store: Arc<dyn CredentialsStore>,
+) -> Result<HttpResponse, HandlerError> {
+    let user_id = get_user(&req)?;
+    let totp_code = parse_payload::<TotpCode>(&body)?;
+
+    web::block(move || {
+        let secret = credentials_store
+            .fetch_totp_secret(&user_id)
+            .map_err(|err| internal_error("Failed to fetch TOTP secret", err))?
+            .ok_or_else(|| ResponseError::bad_request("TOTP is not enabled"))?;
+
+        // A pending enrollment has not protected anything yet, so it may be abandoned without a
+        // code
+        if secret.enabled && verify_totp_code(&secret, &totp_code.code)?.is_none() {
+            return Err(ResponseError::bad_request("Invalid TOTP code"));
+        }
+
+        credentials_store
+            .remove_totp_secret(&user_id)
+            .map_err(|err| internal_error("Failed to remove TOTP secret", err))
+    })
+    .await??;
+
+    Ok(HttpResponse::Ok().json(json!({ "message": "TOTP disabled" })))
+}
@@ -0,0 +1,239 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::sync::Arc;
+
+use actix_web::{http::Method, web, HttpResponse};
+#[cfg(feature = "biome-key-management")]
+use splinter::biome::credentials::rest_api::BiomeCredentialsRestConfig;
+use splinter::biome::credentials::store::CredentialsStore;
+#[cfg(feature = "biome-key-management")]
+use splinter::biome::credentials::store::CredentialsStoreError;
+#[cfg(feature = "biome-key-management")]
+use splinter::biome::key_management::{
+    store::{KeyStore, KeyStoreError},
+    Key,
+};
+#[cfg(feature = "authorization")]
+use splinter_rest_api_common::biome::credentials::{
+    BIOME_USER_READ_PERMISSION, BIOME_USER_WRITE_PERMISSION,
+};
+#[cfg(feature = "biome-key-management")]
+use splinter_rest_api_common::biome::{
+    credentials::{ModifyUser, BIOME_USER_PROTOCOL_MIN},
+    key_management::ResponseKey,
+};
+#[cfg(feature = "biome-key-management")]
+use splinter_rest_api_common::error::ResponseError;
+use splinter_rest_api_common::{
+    biome::credentials::BIOME_LIST_USERS_PROTOCOL_MIN, SPLINTER_PROTOCOL_VERSION,
+};
+
+use super::internal_error;
+#[cfg(feature = "biome-key-management")]
+use super::parse_payload;
+use crate::{HandlerError, ProtocolVersion, Resource};
+
+type ListUsersProtocolVersion =
+    ProtocolVersion<BIOME_LIST_USERS_PROTOCOL_MIN, SPLINTER_PROTOCOL_VERSION>;
+#[cfg(feature = "biome-key-management")]
+type UserProtocolVersion = ProtocolVersion<BIOME_USER_PROTOCOL_MIN, SPLINTER_PROTOCOL_VERSION>;
+
+/// Defines a REST endpoint to list users from the db
+pub fn make_list_route(credentials_store: Arc<dyn CredentialsStore>) -> Resource {
+    Resource::build("/biome/users").add_method(
+        Method::GET,
+        #[cfg(feature = "authorization")]
+        BIOME_USER_READ_PERMISSION,
+        move |_: ListUsersProtocolVersion| handle_list(credentials_store.clone()),
+    )
+}
+
+async fn handle_list(
+    credentials_store: Arc<dyn CredentialsStore>,
+) -> Result<HttpResponse, HandlerError> {
+    let users = web::block(move || {
+        credentials_store
+            .list_usernames()
+            .map_err(|err| internal_error("Failed to get users from the database", err))
+    })
+    .await??;
+
+    Ok(HttpResponse::Ok().json(users))
+}
+
+/// Defines the `/biome/users/{id}` REST resource for managing users
+#[cfg(feature = "biome-key-management")]
+pub fn make_user_routes(
+    rest_config: Arc<BiomeCredentialsRestConfig>,
+    credentials_store: Arc<dyn CredentialsStore>,
+    key_store: Arc<dyn KeyStore>,
+) -> Resource {
+    let credentials_store1 = credentials_store.clone();
+    let credentials_store2 = credentials_store.clone();
+    Resource::build("/biome/users/{id}")
+        .add_method(
+            Method::PUT,
+            #[cfg(feature = "authorization")]
+            BIOME_USER_WRITE_PERMISSION,
+            move |user_id: web::Path<String>, body: web::Bytes, _: UserProtocolVersion| {
+                handle_modify(
+                    user_id.into_inner(),
+                    body,
+                    credentials_store.clone(),
+                    rest_config.clone(),
+                    key_store.clone(),
+                )
+            },
+        )
+        .add_method(
+            Method::GET,
+            #[cfg(feature = "authorization")]
+            BIOME_USER_READ_PERMISSION,
+            move |user_id: web::Path<String>, _: UserProtocolVersion| {
+                handle_fetch(user_id.into_inner(), credentials_store1.clone())
+            },
+        )
+        .add_method(
+            Method::DELETE,
+            #[cfg(feature = "authorization")]
+            BIOME_USER_WRITE_PERMISSION,
+            move |user_id: web::Path<String>, _: UserProtocolVersion| {
+                handle_delete(user_id.into_inner(), credentials_store2.clone())
+            },
+        )
+}
+
+/// Fetches a user from the database, returning the user's ID and username
+#[cfg(feature = "biome-key-management")]
+async fn handle_fetch(
+    user_id: String,
+    credentials_store: Arc<dyn CredentialsStore>,
+) -> Result<HttpResponse, HandlerError> {
+    let user = web::block(move || {
+        credentials_store
+            .fetch_username_by_id(&user_id)
+            .map_err(|err| match err {
+                CredentialsStoreError::NotFoundError(_) => {
+                    ResponseError::not_found(format!("User ID not found: {}", user_id))
+                }
+                err => internal_error("Failed to get user from the database", err),
+            })
+    })
+    .await??;
+
+    Ok(HttpResponse::Ok().json(user))
+}
+
+/// Updates a user's password and keys
+///
+/// The payload should be in the JSON format:
+///   {
+///       "username": <existing username of the user>
+///       "hashed_password": <hash of the user's existing password>
+///       "new_password": <hash of the user's updated password>
+///       "new_key_pairs":
+///       [
+///           {
+///               "display_name": <display name for key>
+///               "public_key": <public key of the user>
+///               "encrypted_private_key": <updated encrypted private key of the the user>
+///           },
+///           { ... }, { ... }, ...
+///       ]
+///   }
+#[cfg(feature = "biome-key-management")]
+async fn handle_modify(
+    user_id: String,
+    body: web::Bytes,
+    credentials_store: Arc<dyn CredentialsStore>,
+    rest_config: Arc<BiomeCredentialsRestConfig>,
+    key_store: Arc<dyn KeyStore>,
+) -> Result<HttpResponse, HandlerError> {
+    let modify_user = parse_payload::<ModifyUser>(&body)?;
+    let new_key_pairs = modify_user
+        .new_key_pairs
+        .iter()
+        .map(|new_key| {
+            Key::new(
+                &new_key.public_key,
+                &new_key.encrypted_private_key,
+                &user_id,
+                &new_key.display_name,
+            )
+        })
+        .collect::<Vec<Key>>();
+
+    let new_key_pairs = web::block(move || {
+        let credentials = credentials_store
+            .fetch_credential_by_username(&modify_user.username)
+            .map_err(|err| match err {
+                CredentialsStoreError::NotFoundError(_) => ResponseError::not_found(format!(
+                    "Username not found: {}",
+                    modify_user.username
+                )),
+                err => internal_error("Failed to fetch credentials", err),
+            })?;
+
+        match credentials.verify_password(&modify_user.hashed_password) {
+            Ok(true) => (),
+            Ok(false) => return Err(ResponseError::bad_request("Invalid password")),
+            Err(err) => return Err(internal_error("Failed to verify password", err)),
+        }
+
+        // If no new password, pull old password for update operation
+        let new_password = modify_user.new_password.unwrap_or(credentials.password);
+
+        key_store
+            .update_keys_and_password(
+                &user_id,
+                &new_password,
+                rest_config.password_encryption_cost(),
+                &new_key_pairs,
+            )
+            .map_err(|err| match err {
+                KeyStoreError::DuplicateKeyError(msg)
+                | KeyStoreError::UserDoesNotExistError(msg) => ResponseError::bad_request(msg),
+                err => internal_error("Failed to update credentials and keys", err),
+            })?;
+
+        Ok(new_key_pairs)
+    })
+    .await??;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "message": "Credentials and key updated successfully",
+        "data": new_key_pairs.iter().map(ResponseKey::from).collect::<Vec<ResponseKey>>(),
+    })))
+}
+
+/// Removes a user from the database
+#[cfg(feature = "biome-key-management")]
+async fn handle_delete(
+    user_id: String,
+    credentials_store: Arc<dyn CredentialsStore>,
+) -> Result<HttpResponse, HandlerError> {
+    web::block(move || {
+        credentials_store
+            .remove_credentials(&user_id)
+            .map_err(|err| match err {
+                CredentialsStoreError::NotFoundError(_) => {
+                    ResponseError::not_found(format!("User ID not found: {}", user_id))
+                }
+                err => internal_error("Failed to delete user in database", err),
+            })
+    })
+    .await??;
+
+    Ok(HttpResponse::Ok().json(json!({ "message": "User deleted successfully" })))
+}
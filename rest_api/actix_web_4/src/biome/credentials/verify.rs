@@ -0,0 +1,78 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::sync::Arc;
+
+use actix_web::{http::Method, web, HttpRequest, HttpResponse};
+use splinter::biome::credentials::store::{CredentialsStore, CredentialsStoreError};
+#[cfg(feature = "authorization")]
+use splinter::rest_api::auth::authorization::Permission;
+use splinter_rest_api_common::biome::credentials::{UsernamePassword, BIOME_VERIFY_PROTOCOL_MIN};
+use splinter_rest_api_common::{error::ResponseError, SPLINTER_PROTOCOL_VERSION};
+
+use super::{get_user, internal_error, parse_payload};
+use crate::{HandlerError, ProtocolVersion, Resource};
+
+type VerifyProtocolVersion = ProtocolVersion<BIOME_VERIFY_PROTOCOL_MIN, SPLINTER_PROTOCOL_VERSION>;
+
+/// Defines a REST endpoint to verify a user's password
+///
+/// The payload should be in the JSON format:
+///   {
+///       "username": <existing username of the user>
+///       "hashed_password": <hash of the user's existing password>
+///   }
+pub fn make_verify_route(credentials_store: Arc<dyn CredentialsStore>) -> Resource {
+    Resource::build("/biome/verify").add_method(
+        Method::POST,
+        #[cfg(feature = "authorization")]
+        Permission::AllowAuthenticated,
+        move |req: HttpRequest, body: web::Bytes, _: VerifyProtocolVersion| {
+            handle_verify(req, body, credentials_store.clone())
+        },
+    )
+}
+
+async fn handle_verify(
+    req: HttpRequest,
+    body: web::Bytes,
+    credentials_store: Arc<dyn CredentialsStore>,
+) -> Result<HttpResponse, HandlerError> {
+    // Only a logged in Biome user may verify a password
+    get_user(&req)?;
+    let username_password = parse_payload::<UsernamePassword>(&body)?;
+
+    let user_id = web::block(move || {
+        let credentials = credentials_store
+            .fetch_credential_by_username(&username_password.username)
+            .map_err(|err| match err {
+                CredentialsStoreError::NotFoundError(_) => ResponseError::bad_request(format!(
+                    "Username not found: {}",
+                    username_password.username
+                )),
+                err => internal_error("Failed to fetch credentials", err),
+            })?;
+
+        match credentials.verify_password(&username_password.hashed_password) {
+            Ok(true) => Ok(credentials.user_id),
+            Ok(false) => Err(ResponseError::bad_request("Invalid password")),
+            Err(err) => Err(internal_error("Failed to verify password", err)),
+        }
+    })
+    .await??;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "message": "Successful verification",
+        "user_id": user_id,
+    })))
+}
@@ -0,0 +1,248 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use actix_web::{http::Method, web, HttpRequest, HttpResponse};
+use splinter::biome::key_management::store::{KeyStore, KeyStoreError};
+use splinter::biome::key_management::Key;
+#[cfg(feature = "authorization")]
+use splinter::rest_api::auth::authorization::Permission;
+use splinter::rest_api::auth::identity::Identity;
+use splinter_rest_api_common::biome::key_management::{
+    NewKey, ResponseKey, UpdatedKey, BIOME_KEYS_PROTOCOL_MIN, BIOME_REPLACE_KEYS_PROTOCOL_MIN,
+};
+use splinter_rest_api_common::{error::ResponseError, SPLINTER_PROTOCOL_VERSION};
+
+use crate::{HandlerError, ProtocolVersion, Resource};
+
+type KeysProtocolVersion = ProtocolVersion<BIOME_KEYS_PROTOCOL_MIN, SPLINTER_PROTOCOL_VERSION>;
+type ReplaceKeysProtocolVersion =
+    ProtocolVersion<BIOME_REPLACE_KEYS_PROTOCOL_MIN, SPLINTER_PROTOCOL_VERSION>;
+
+pub fn make_key_management_route(key_store: Arc<dyn KeyStore>) -> Resource {
+    let key_store1 = key_store.clone();
+    let key_store2 = key_store.clone();
+    let key_store3 = key_store.clone();
+    Resource::build("/biome/keys")
+        .add_method(
+            Method::PUT,
+            #[cfg(feature = "authorization")]
+            Permission::AllowAuthenticated,
+            move |req: HttpRequest, body: web::Bytes, _: ReplaceKeysProtocolVersion| {
+                handle_put(req, body, key_store.clone())
+            },
+        )
+        .add_method(
+            Method::POST,
+            #[cfg(feature = "authorization")]
+            Permission::AllowAuthenticated,
+            move |req: HttpRequest, body: web::Bytes, _: KeysProtocolVersion| {
+                handle_post(req, body, key_store1.clone())
+            },
+        )
+        .add_method(
+            Method::GET,
+            #[cfg(feature = "authorization")]
+            Permission::AllowAuthenticated,
+            move |req: HttpRequest, _: KeysProtocolVersion| handle_get(req, key_store2.clone()),
+        )
+        .add_method(
+            Method::PATCH,
+            #[cfg(feature = "authorization")]
+            Permission::AllowAuthenticated,
+            move |req: HttpRequest, body: web::Bytes, _: KeysProtocolVersion| {
+                handle_patch(req, body, key_store3.clone())
+            },
+        )
+}
+
+async fn handle_post(
+    req: HttpRequest,
+    body: web::Bytes,
+    key_store: Arc<dyn KeyStore>,
+) -> Result<HttpResponse, HandlerError> {
+    let user = get_user(&req)?;
+    let new_key = parse_payload::<NewKey>(&body)?;
+
+    let key = Key::new(
+        &new_key.public_key,
+        &new_key.encrypted_private_key,
+        &user,
+        &new_key.display_name,
+    );
+
+    let key = web::block(move || {
+        key_store
+            .add_key(key.clone())
+            .map(|_| key)
+            .map_err(|err| to_response_error(err, "Failed to add new key to database"))
+    })
+    .await??;
+
+    Ok(HttpResponse::Ok()
+        .json(json!({ "message": "Key added successfully", "data": ResponseKey::from(&key) })))
+}
+
+async fn handle_get(
+    req: HttpRequest,
+    key_store: Arc<dyn KeyStore>,
+) -> Result<HttpResponse, HandlerError> {
+    let user = get_user(&req)?;
+
+    let keys = web::block(move || {
+        key_store
+            .list_keys(Some(&user))
+            .map_err(|err| to_response_error(err, "Failed to fetch keys"))
+    })
+    .await??;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "data": keys.iter().map(ResponseKey::from).collect::<Vec<ResponseKey>>()
+    })))
+}
+
+async fn handle_put(
+    req: HttpRequest,
+    body: web::Bytes,
+    key_store: Arc<dyn KeyStore>,
+) -> Result<HttpResponse, HandlerError> {
+    let user = get_user(&req)?;
+    let new_keys = parse_payload::<Vec<NewKey>>(&body)?
+        .iter()
+        .map(|new_key| {
+            Key::new(
+                &new_key.public_key,
+                &new_key.encrypted_private_key,
+                &user,
+                &new_key.display_name,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    web::block(move || {
+        key_store
+            .replace_keys(&user, &new_keys)
+            .map_err(|err| to_response_error(err, "Failed to replace keys in database"))
+    })
+    .await??;
+
+    Ok(HttpResponse::Ok().json(json!({ "message": "Keys replaced successfully" })))
+}
+
+async fn handle_patch(
+    req: HttpRequest,
+    body: web::Bytes,
+    key_store: Arc<dyn KeyStore>,
+) -> Result<HttpResponse, HandlerError> {
+    let user = get_user(&req)?;
+    let updated_key = parse_payload::<UpdatedKey>(&body)?;
+
+    web::block(move || {
+        key_store
+            .update_key(
+                &updated_key.public_key,
+                &user,
+                &updated_key.new_display_name,
+            )
+            .map_err(|err| to_response_error(err, "Failed to update key"))
+    })
+    .await??;
+
+    Ok(HttpResponse::Ok().json(json!({ "message": "Key updated successfully" })))
+}
+
+pub fn make_key_management_route_with_public_key(key_store: Arc<dyn KeyStore>) -> Resource {
+    let key_store1 = key_store.clone();
+    Resource::build("/biome/keys/{public_key}")
+        .add_method(
+            Method::GET,
+            #[cfg(feature = "authorization")]
+            Permission::AllowAuthenticated,
+            move |req: HttpRequest, public_key: web::Path<String>, _: KeysProtocolVersion| {
+                handle_fetch(req, public_key.into_inner(), key_store.clone())
+            },
+        )
+        .add_method(
+            Method::DELETE,
+            #[cfg(feature = "authorization")]
+            Permission::AllowAuthenticated,
+            move |req: HttpRequest, public_key: web::Path<String>, _: KeysProtocolVersion| {
+                handle_delete(req, public_key.into_inner(), key_store1.clone())
+            },
+        )
+}
+
+async fn handle_fetch(
+    req: HttpRequest,
+    public_key: String,
+    key_store: Arc<dyn KeyStore>,
+) -> Result<HttpResponse, HandlerError> {
+    let user = get_user(&req)?;
+
+    let key = web::block(move || {
+        key_store
+            .fetch_key(&public_key, &user)
+            .map_err(|err| to_response_error(err, "Failed to fetch key"))
+    })
+    .await??;
+
+    Ok(HttpResponse::Ok().json(json!({ "data": ResponseKey::from(&key) })))
+}
+
+async fn handle_delete(
+    req: HttpRequest,
+    public_key: String,
+    key_store: Arc<dyn KeyStore>,
+) -> Result<HttpResponse, HandlerError> {
+    let user = get_user(&req)?;
+
+    let key = web::block(move || {
+        key_store
+            .remove_key(&public_key, &user)
+            .map_err(|err| to_response_error(err, "Failed to delete key"))
+    })
+    .await??;
+
+    Ok(HttpResponse::Ok().json(json!({
+        "message": "Key successfully deleted",
+        "data": ResponseKey::from(&key)
+    })))
+}
+
+/// Gets the ID of the Biome user that made the request.
+fn get_user(req: &HttpRequest) -> Result<String, ResponseError> {
+    match req.extensions().get::<Identity>() {
+        Some(Identity::User(user)) => Ok(user.clone()),
+        _ => Err(ResponseError::NotAuthorized),
+    }
+}
+
+fn parse_payload<T: serde::de::DeserializeOwned>(body: &[u8]) -> Result<T, ResponseError> {
+    serde_json::from_slice::<T>(body).map_err(|err| {
+        debug!("Error parsing payload {}", err);
+        ResponseError::bad_request(format!("Failed to parse payload: {}", err))
+    })
+}
+
+fn to_response_error(err: KeyStoreError, context: &str) -> ResponseError {
+    debug!("{}: {}", context, err);
+    match err {
+        KeyStoreError::DuplicateKeyError(msg) | KeyStoreError::UserDoesNotExistError(msg) => {
+            ResponseError::bad_request(msg)
+        }
+        KeyStoreError::NotFoundError(msg) => ResponseError::not_found(msg),
+        err => ResponseError::internal_error(format!("{}: {}", context, err), None),
+    }
+}
@@ -0,0 +1,42 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the `/biome/keys` endpoints for managing a Biome user's keys.
+
+mod endpoints;
+
+use std::sync::Arc;
+
+use splinter::biome::key_management::store::KeyStore;
+
+use crate::{Resource, RestResourceProvider};
+
+pub struct BiomeKeyManagementRestResourceProvider {
+    key_store: Arc<dyn KeyStore>,
+}
+
+impl BiomeKeyManagementRestResourceProvider {
+    pub fn new(key_store: Arc<dyn KeyStore>) -> Self {
+        Self { key_store }
+    }
+}
+
+impl RestResourceProvider for BiomeKeyManagementRestResourceProvider {
+    fn resources(&self) -> Vec<Resource> {
+        vec![
+            endpoints::make_key_management_route(self.key_store.clone()),
+            endpoints::make_key_management_route_with_public_key(self.key_store.clone()),
+        ]
+    }
+}
@@ -0,0 +1,117 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(feature = "authorization")]
+use splinter::rest_api::auth::authorization::AuthorizationHandler;
+use splinter::{
+    error::InvalidStateError,
+    rest_api::{auth::identity::IdentityProvider, BindConfig, RestApiServerError},
+};
+
+use super::{Resource, RestApi};
+
+/// Builder `struct` for `RestApi`.
+#[derive(Default)]
+pub struct RestApiBuilder {
+    resources: Vec<Resource>,
+    bind: Option<BindConfig>,
+    identity_providers: Vec<Box<dyn IdentityProvider>>,
+    #[cfg(feature = "authorization")]
+    authorization_handlers: Vec<Box<dyn AuthorizationHandler>>,
+}
+
+impl RestApiBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_bind(mut self, value: BindConfig) -> Self {
+        self.bind = Some(value);
+        self
+    }
+
+    pub fn add_resource(mut self, value: Resource) -> Self {
+        self.resources.push(value);
+        self
+    }
+
+    pub fn add_resources(mut self, mut values: Vec<Resource>) -> Self {
+        self.resources.append(&mut values);
+        self
+    }
+
+    /// Sets the identity providers used to determine the identity of the client for each request.
+    pub fn with_identity_providers(
+        mut self,
+        identity_providers: Vec<Box<dyn IdentityProvider>>,
+    ) -> Self {
+        self.identity_providers = identity_providers;
+        self
+    }
+
+    #[cfg(feature = "authorization")]
+    pub fn with_authorization_handlers(
+        mut self,
+        authorization_handlers: Vec<Box<dyn AuthorizationHandler>>,
+    ) -> Self {
+        self.authorization_handlers = authorization_handlers;
+        self
+    }
+
+    pub fn build(self) -> Result<RestApi, RestApiServerError> {
+        let bind = self
+            .bind
+            .ok_or_else(|| RestApiServerError::MissingField("bind".to_string()))?;
+
+        if self.identity_providers.is_empty() {
+            return Err(RestApiServerError::InvalidStateError(
+                InvalidStateError::with_message(
+                    "REST API auth is enabled, but no identity providers have been configured"
+                        .to_string(),
+                ),
+            ));
+        }
+
+        Ok(RestApi {
+            resources: self.resources,
+            bind,
+            identity_providers: self.identity_providers,
+            #[cfg(feature = "authorization")]
+            authorization_handlers: self.authorization_handlers,
+            #[cfg(all(feature = "https-bind", feature = "tls-reload"))]
+            tls_context: Default::default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verifies that the builder fails without a bind config and without identity providers.
+    #[test]
+    fn build_missing_fields() {
+        assert!(matches!(
+            RestApiBuilder::new().build(),
+            Err(RestApiServerError::MissingField(_))
+        ));
+
+        assert!(matches!(
+            RestApiBuilder::new()
+                .with_bind(BindConfig::Http("127.0.0.1:0".into()))
+                .build(),
+            Err(RestApiServerError::InvalidStateError(_))
+        ));
+    }
+}
@@ -0,0 +1,73 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use actix_web::{error::BlockingError, http::StatusCode, HttpResponse};
+use splinter::rest_api::ErrorResponse;
+use splinter_rest_api_common::error::ResponseError;
+
+/// The error returned by request handlers.
+///
+/// This wraps the framework-agnostic `ResponseError` so that it can be converted into an HTTP
+/// response with the same JSON body as the Actix Web v1 implementation.
+#[derive(Debug)]
+pub struct HandlerError(ResponseError);
+
+impl From<ResponseError> for HandlerError {
+    fn from(err: ResponseError) -> Self {
+        Self(err)
+    }
+}
+
+impl From<BlockingError> for HandlerError {
+    fn from(err: BlockingError) -> Self {
+        Self(ResponseError::internal_error(
+            "Blocking operation failed",
+            Some(Box::new(err)),
+        ))
+    }
+}
+
+impl fmt::Display for HandlerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl actix_web::ResponseError for HandlerError {
+    fn status_code(&self) -> StatusCode {
+        match self.0 {
+            ResponseError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            ResponseError::NotFound(_) => StatusCode::NOT_FOUND,
+            ResponseError::Forbidden(_) => StatusCode::FORBIDDEN,
+            ResponseError::NotAuthorized => StatusCode::UNAUTHORIZED,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        let mut response = HttpResponse::build(self.status_code());
+        match &self.0 {
+            ResponseError::BadRequest(msg) => response.json(ErrorResponse::bad_request(msg)),
+            ResponseError::NotFound(msg) => response.json(ErrorResponse::not_found(msg)),
+            ResponseError::Forbidden(msg) => response.json(ErrorResponse::forbidden(msg)),
+            ResponseError::NotAuthorized => response.json(ErrorResponse::unauthorized()),
+            err => {
+                error!("{}", err);
+                response.json(ErrorResponse::internal_error())
+            }
+        }
+    }
+}
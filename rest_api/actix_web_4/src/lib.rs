@@ -11,3 +11,46 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
+
+//! Splinter REST API implementation written using Actix Web v4.
+//!
+//! Endpoints are defined as [`Resource`]s with async handlers and are served by a [`RestApi`],
+//! which runs an actix-web 4 server on its own thread. The response models are shared with the
+//! Actix Web v1 implementation through `splinter-rest-api-common`, so clients see the same
+//! payloads regardless of which implementation a node is running.
+//!
+//! The admin service event websockets, OAuth endpoints, peer inspection and retry state
+//! endpoints, and REST API audit log have not been ported yet and are only available from the
+//! Actix Web v1 implementation.
+
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate serde_json;
+
+#[cfg(feature = "admin-service")]
+pub mod admin;
+mod api;
+mod auth;
+#[cfg(any(feature = "biome-credentials", feature = "biome-key-management"))]
+pub mod biome;
+mod builder;
+mod error;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+mod paging;
+mod protocol_version;
+#[cfg(feature = "registry")]
+pub mod registry;
+mod resource;
+#[cfg(feature = "scabbard-service")]
+pub mod scabbard;
+pub mod status;
+
+#[cfg(all(feature = "https-bind", feature = "tls-reload"))]
+pub use api::RestApiTlsReloader;
+pub use api::{RestApi, RestApiShutdownHandle};
+pub use builder::RestApiBuilder;
+pub use error::HandlerError;
+pub use protocol_version::{ProtocolVersion, ProtocolVersionError};
+pub use resource::{Resource, RestResourceProvider};
@@ -0,0 +1,66 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the `/metrics` endpoint, which exposes the node's metrics in the Prometheus text
+//! exposition format.
+
+use actix_web::{http::Method, HttpResponse};
+use splinter::tap::prometheus::{PrometheusHandle, PROMETHEUS_CONTENT_TYPE};
+use splinter_rest_api_common::error::ResponseError;
+#[cfg(feature = "authorization")]
+use splinter_rest_api_common::metrics::METRICS_READ_PERMISSION;
+
+use crate::{HandlerError, Resource, RestResourceProvider};
+
+pub struct MetricsResourceProvider {
+    resources: Vec<Resource>,
+}
+
+impl MetricsResourceProvider {
+    pub fn new(prometheus_handle: PrometheusHandle) -> Self {
+        let handle = move || {
+            let prometheus_handle = prometheus_handle.clone();
+            async move {
+                let metrics = prometheus_handle.render().map_err(|err| {
+                    ResponseError::internal_error(
+                        format!("Unable to render metrics: {}", err),
+                        None,
+                    )
+                })?;
+                Ok::<_, HandlerError>(
+                    HttpResponse::Ok()
+                        .content_type(PROMETHEUS_CONTENT_TYPE)
+                        .body(metrics),
+                )
+            }
+        };
+
+        let resource = Resource::build("/metrics").add_method(
+            Method::GET,
+            #[cfg(feature = "authorization")]
+            METRICS_READ_PERMISSION,
+            handle,
+        );
+
+        Self {
+            resources: vec![resource],
+        }
+    }
+}
+
+impl RestResourceProvider for MetricsResourceProvider {
+    fn resources(&self) -> Vec<Resource> {
+        self.resources.clone()
+    }
+}
@@ -0,0 +1,56 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers for parsing the query parameters of list endpoints.
+
+#![cfg(any(
+    feature = "admin-service",
+    feature = "registry",
+    feature = "scabbard-service"
+))]
+
+use std::collections::HashMap;
+
+use actix_web::{web, HttpRequest};
+use splinter_rest_api_common::error::ResponseError;
+use splinter_rest_api_common::paging::v1::{DEFAULT_LIMIT, DEFAULT_OFFSET};
+
+pub(crate) fn parse_query(req: &HttpRequest) -> Result<HashMap<String, String>, ResponseError> {
+    web::Query::<HashMap<String, String>>::from_query(req.query_string())
+        .map(web::Query::into_inner)
+        .map_err(|_| ResponseError::bad_request("Invalid query"))
+}
+
+/// Returns the offset and limit from the query, falling back to the defaults if they are not set.
+pub(crate) fn parse_offset_and_limit(
+    query: &HashMap<String, String>,
+) -> Result<(usize, usize), ResponseError> {
+    let offset = parse_usize(query, "offset")?.unwrap_or(DEFAULT_OFFSET);
+    let limit = parse_usize(query, "limit")?.unwrap_or(DEFAULT_LIMIT);
+    Ok((offset, limit))
+}
+
+fn parse_usize(query: &HashMap<String, String>, key: &str) -> Result<Option<usize>, ResponseError> {
+    query
+        .get(key)
+        .map(|value| {
+            value.parse::<usize>().map_err(|err| {
+                ResponseError::bad_request(format!(
+                    "Invalid {} value passed: {}. Error: {}",
+                    key, value, err
+                ))
+            })
+        })
+        .transpose()
+}
@@ -0,0 +1,175 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use actix_utils::future::{ready, Ready};
+use actix_web::{dev::Payload, http::StatusCode, FromRequest, HttpRequest, HttpResponse};
+
+const PROTOCOL_VERSION_HEADER: &str = "SplinterProtocolVersion";
+
+/// The protocol version requested by the client with the `SplinterProtocolVersion` header.
+///
+/// Extracting a `ProtocolVersion` fails with a `400 Bad Request` if the requested version is
+/// outside of the supported `MIN..=MAX` range. If the header is not set, the version defaults to
+/// `MAX`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProtocolVersion<const MIN: u32, const MAX: u32>(u32);
+
+impl<const MIN: u32, const MAX: u32> ProtocolVersion<MIN, MAX> {
+    pub fn version(&self) -> u32 {
+        self.0
+    }
+}
+
+impl<const MIN: u32, const MAX: u32> FromRequest for ProtocolVersion<MIN, MAX> {
+    type Error = ProtocolVersionError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        ready(parse_protocol_version(req, MIN, MAX).map(Self))
+    }
+}
+
+fn parse_protocol_version(
+    req: &HttpRequest,
+    min: u32,
+    max: u32,
+) -> Result<u32, ProtocolVersionError> {
+    let version = match req.headers().get(PROTOCOL_VERSION_HEADER) {
+        Some(header_value) => header_value
+            .to_str()
+            .map_err(|err| {
+                ProtocolVersionError::invalid(format!(
+                    "Invalid characters in SplinterProtocolVersion header: {}",
+                    err
+                ))
+            })?
+            .parse::<u32>()
+            .map_err(|_| {
+                ProtocolVersionError::invalid(
+                    "SplinterProtocolVersion must be a valid positive integer".into(),
+                )
+            })?,
+        None => return Ok(max),
+    };
+
+    if version < min {
+        Err(ProtocolVersionError::unsupported(
+            format!("Client must support protocol version {} or greater.", min),
+            version,
+            max,
+        ))
+    } else if version > max {
+        Err(ProtocolVersionError::unsupported(
+            format!(
+                "Client requires a newer protocol than can be provided: {} > {}",
+                version, max
+            ),
+            version,
+            max,
+        ))
+    } else {
+        Ok(version)
+    }
+}
+
+/// Returned when the client requests a protocol version that is not supported by an endpoint.
+#[derive(Debug)]
+pub struct ProtocolVersionError {
+    message: String,
+    versions: Option<(u32, u32)>,
+}
+
+impl ProtocolVersionError {
+    fn invalid(message: String) -> Self {
+        Self {
+            message,
+            versions: None,
+        }
+    }
+
+    fn unsupported(message: String, requested_protocol: u32, splinter_protocol: u32) -> Self {
+        Self {
+            message,
+            versions: Some((requested_protocol, splinter_protocol)),
+        }
+    }
+}
+
+impl fmt::Display for ProtocolVersionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl actix_web::ResponseError for ProtocolVersionError {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::BAD_REQUEST
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        match self.versions {
+            Some((requested_protocol, splinter_protocol)) => {
+                HttpResponse::BadRequest().json(json!({
+                    "message": self.message,
+                    "requested_protocol": requested_protocol,
+                    "splinter_protocol": splinter_protocol,
+                    "libsplinter_version": format!(
+                        "{}.{}.{}",
+                        env!("CARGO_PKG_VERSION_MAJOR"),
+                        env!("CARGO_PKG_VERSION_MINOR"),
+                        env!("CARGO_PKG_VERSION_PATCH")
+                    )
+                }))
+            }
+            None => HttpResponse::BadRequest().json(json!({ "message": self.message })),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use actix_web::test::TestRequest;
+
+    /// Verifies that the protocol version defaults to the maximum supported version when the
+    /// header is not set.
+    #[test]
+    fn protocol_version_defaults_to_max() {
+        let req = TestRequest::default().to_http_request();
+        assert_eq!(parse_protocol_version(&req, 1, 2).unwrap(), 2);
+    }
+
+    /// Verifies that protocol versions are only accepted within the supported range.
+    #[test]
+    fn protocol_version_range() {
+        let req = TestRequest::default()
+            .insert_header((PROTOCOL_VERSION_HEADER, "1"))
+            .to_http_request();
+        assert_eq!(parse_protocol_version(&req, 1, 2).unwrap(), 1);
+        assert!(parse_protocol_version(&req, 2, 2).is_err());
+
+        let req = TestRequest::default()
+            .insert_header((PROTOCOL_VERSION_HEADER, "3"))
+            .to_http_request();
+        assert!(parse_protocol_version(&req, 1, 2).is_err());
+
+        let req = TestRequest::default()
+            .insert_header((PROTOCOL_VERSION_HEADER, "two"))
+            .to_http_request();
+        assert!(parse_protocol_version(&req, 1, 2).is_err());
+    }
+}
@@ -0,0 +1,63 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the registry REST API endpoints.
+
+mod nodes;
+mod nodes_identity;
+
+use splinter::registry::{RegistryError, RwRegistry};
+use splinter_rest_api_common::error::ResponseError;
+
+use crate::{Resource, RestResourceProvider};
+
+/// Provides the REST API [`Resource`] definitions for reading and modifying the registry.
+///
+/// The following endpoints are provided:
+///
+/// * `GET /registry/nodes` - List the nodes in the registry
+/// * `POST /registry/nodes` - Add a node to the registry
+/// * `GET /registry/nodes/{identity}` - Fetch a node by its identity
+/// * `PUT /registry/nodes/{identity}` - Replace a node in the registry
+/// * `DELETE /registry/nodes/{identity}` - Remove a node from the registry
+pub struct RwRegistryRestResourceProvider {
+    resources: Vec<Resource>,
+}
+
+impl RwRegistryRestResourceProvider {
+    pub fn new(registry: &dyn RwRegistry) -> Self {
+        let resources = vec![
+            nodes_identity::make_nodes_identity_resource(registry.clone_box()),
+            nodes::make_nodes_resource(registry.clone_box()),
+        ];
+        Self { resources }
+    }
+}
+
+impl RestResourceProvider for RwRegistryRestResourceProvider {
+    fn resources(&self) -> Vec<Resource> {
+        self.resources.clone()
+    }
+}
+
+/// Converts a registry error into a response error. Invalid state errors are caused by invalid
+/// nodes, so these are reported to the client.
+fn to_response_error(err: RegistryError, context: &str) -> ResponseError {
+    match err {
+        RegistryError::InvalidStateError(err) => {
+            ResponseError::bad_request(format!("Invalid node: {}", err))
+        }
+        err => ResponseError::internal_error(format!("{}: {}", context, err), None),
+    }
+}
@@ -0,0 +1,208 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use actix_web::{http::Method, web, HttpRequest, HttpResponse};
+use splinter::registry::{MetadataPredicate, Node, RwRegistry};
+#[cfg(feature = "registry-node-health")]
+use splinter::registry::{NodeIter, RegistryError, RegistryReader};
+use splinter::rest_api::percent_encode_filter_query;
+#[cfg(feature = "authorization")]
+use splinter_rest_api_common::registry::{REGISTRY_READ_PERMISSION, REGISTRY_WRITE_PERMISSION};
+use splinter_rest_api_common::{
+    error::ResponseError,
+    paging::v1::PagingBuilder,
+    registry::{
+        resources::{ListNodesResponse, NewNode, NodeResponse},
+        REGISTRY_LIST_NODES_PROTOCOL_MIN,
+    },
+    SPLINTER_PROTOCOL_VERSION,
+};
+
+use crate::paging::{parse_offset_and_limit, parse_query};
+use crate::{HandlerError, ProtocolVersion, Resource};
+
+use super::to_response_error;
+
+type ListNodesProtocolVersion =
+    ProtocolVersion<REGISTRY_LIST_NODES_PROTOCOL_MIN, SPLINTER_PROTOCOL_VERSION>;
+
+type Filter = HashMap<String, (String, String)>;
+
+pub fn make_nodes_resource(registry: Box<dyn RwRegistry>) -> Resource {
+    let registry1 = registry.clone();
+    Resource::build("/registry/nodes")
+        .add_method(
+            Method::GET,
+            #[cfg(feature = "authorization")]
+            REGISTRY_READ_PERMISSION,
+            move |req: HttpRequest, _: ListNodesProtocolVersion| list_nodes(req, registry.clone()),
+        )
+        .add_method(
+            Method::POST,
+            #[cfg(feature = "authorization")]
+            REGISTRY_WRITE_PERMISSION,
+            move |body: web::Bytes, _: ListNodesProtocolVersion| add_node(body, registry1.clone()),
+        )
+}
+
+async fn list_nodes(
+    req: HttpRequest,
+    registry: Box<dyn RwRegistry>,
+) -> Result<HttpResponse, HandlerError> {
+    let query = parse_query(&req)?;
+    let (offset, limit) = parse_offset_and_limit(&query)?;
+
+    let mut link = format!("{}?", req.uri().path());
+
+    let filters = match query.get("filter") {
+        Some(value) => {
+            let filters = serde_json::from_str::<Filter>(value).map_err(|err| {
+                ResponseError::bad_request(format!(
+                    "Invalid filter value passed: {}. Error: {}",
+                    value, err
+                ))
+            })?;
+            link.push_str(&format!("filter={}&", percent_encode_filter_query(value)));
+            Some(filters)
+        }
+        None => None,
+    };
+
+    let predicates = to_predicates(filters)
+        .map_err(|err| ResponseError::bad_request(format!("Invalid predicate: {}", err)))?;
+
+    #[cfg(feature = "registry-node-health")]
+    let reachable = match query.get("reachable") {
+        Some(value) => {
+            let reachable = value.parse::<bool>().map_err(|err| {
+                ResponseError::bad_request(format!(
+                    "Invalid reachable value passed: {}. Error: {}",
+                    value, err
+                ))
+            })?;
+            link.push_str(&format!("reachable={}&", reachable));
+            Some(reachable)
+        }
+        None => None,
+    };
+
+    #[cfg(feature = "registry-node-health")]
+    let health_registry = registry.clone();
+    let (nodes, total) = web::block(move || {
+        let nodes = registry
+            .list_nodes(&predicates)
+            .map_err(|err| to_response_error(err, "Unable to list nodes"))?;
+        #[cfg(feature = "registry-node-health")]
+        let nodes = filter_by_reachability(&registry, nodes, reachable)
+            .map_err(|err| to_response_error(err, "Unable to list nodes"))?;
+        let total = nodes.len();
+        Ok::<_, ResponseError>((nodes.skip(offset).take(limit).collect::<Vec<_>>(), total))
+    })
+    .await??;
+
+    #[cfg(not(feature = "registry-node-health"))]
+    let data = nodes.iter().map(NodeResponse::from).collect();
+    #[cfg(feature = "registry-node-health")]
+    let data = nodes
+        .iter()
+        .map(|node| to_node_response(node, &health_registry))
+        .collect();
+
+    Ok(HttpResponse::Ok().json(ListNodesResponse {
+        data,
+        paging: PagingBuilder::new(link, total)
+            .with_limit(limit)
+            .with_offset(offset)
+            .build(),
+    }))
+}
+
+/// Retains the nodes whose reachability matches `reachable`, if it is set. Nodes without any
+/// recorded health are considered unreachable.
+#[cfg(feature = "registry-node-health")]
+fn filter_by_reachability(
+    registry: &dyn RegistryReader,
+    nodes: NodeIter,
+    reachable: Option<bool>,
+) -> Result<std::vec::IntoIter<Node>, RegistryError> {
+    let mut filtered = vec![];
+    for node in nodes {
+        if let Some(reachable) = reachable {
+            let is_reachable = registry
+                .get_node_health(node.identity())?
+                .map(|health| health.is_reachable())
+                .unwrap_or(false);
+            if is_reachable != reachable {
+                continue;
+            }
+        }
+        filtered.push(node);
+    }
+    Ok(filtered.into_iter())
+}
+
+#[cfg(feature = "registry-node-health")]
+fn to_node_response<'a>(node: &'a Node, registry: &dyn RegistryReader) -> NodeResponse<'a> {
+    let health = registry
+        .get_node_health(node.identity())
+        .unwrap_or_else(|err| {
+            error!("Unable to get health of node {}: {}", node.identity(), err);
+            None
+        });
+    NodeResponse::from(node).with_health(health.as_ref())
+}
+
+fn to_predicates(filters: Option<Filter>) -> Result<Vec<MetadataPredicate>, String> {
+    match filters {
+        Some(filters) => filters
+            .into_iter()
+            .map(|(key, (operator, value))| match operator.as_str() {
+                "=" => Ok(MetadataPredicate::Eq(key, value)),
+                ">" => Ok(MetadataPredicate::Gt(key, value)),
+                "<" => Ok(MetadataPredicate::Lt(key, value)),
+                ">=" => Ok(MetadataPredicate::Ge(key, value)),
+                "<=" => Ok(MetadataPredicate::Le(key, value)),
+                "!=" => Ok(MetadataPredicate::Ne(key, value)),
+                _ => Err(format!("{} is not a valid operator", operator)),
+            })
+            .collect(),
+        None => Ok(vec![]),
+    }
+}
+
+async fn add_node(
+    body: web::Bytes,
+    registry: Box<dyn RwRegistry>,
+) -> Result<HttpResponse, HandlerError> {
+    let node = serde_json::from_slice::<NewNode>(&body)
+        .map_err(|err| ResponseError::bad_request(format!("Invalid node: {}", err)))?;
+
+    web::block(move || {
+        let new_node = Node::try_from(node).map_err(|err| {
+            ResponseError::bad_request(format!(
+                "Invalid node: Failed to add node, node is invalid: {}",
+                err
+            ))
+        })?;
+        registry
+            .add_node(new_node)
+            .map_err(|err| to_response_error(err, "Unable to add node"))
+    })
+    .await??;
+
+    Ok(HttpResponse::Ok().finish())
+}
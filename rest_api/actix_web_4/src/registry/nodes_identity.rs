@@ -0,0 +1,127 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use actix_web::{http::Method, web, HttpResponse};
+use splinter::registry::{Node, RwRegistry};
+#[cfg(feature = "authorization")]
+use splinter_rest_api_common::registry::{REGISTRY_READ_PERMISSION, REGISTRY_WRITE_PERMISSION};
+use splinter_rest_api_common::{
+    error::ResponseError,
+    registry::{
+        resources::{NewNode, NodeResponse},
+        REGISTRY_FETCH_NODE_PROTOCOL_MIN,
+    },
+    SPLINTER_PROTOCOL_VERSION,
+};
+
+use crate::{HandlerError, ProtocolVersion, Resource};
+
+use super::to_response_error;
+
+type FetchNodeProtocolVersion =
+    ProtocolVersion<REGISTRY_FETCH_NODE_PROTOCOL_MIN, SPLINTER_PROTOCOL_VERSION>;
+
+pub fn make_nodes_identity_resource(registry: Box<dyn RwRegistry>) -> Resource {
+    let registry1 = registry.clone();
+    let registry2 = registry.clone();
+    Resource::build("/registry/nodes/{identity}")
+        .add_method(
+            Method::GET,
+            #[cfg(feature = "authorization")]
+            REGISTRY_READ_PERMISSION,
+            move |identity: web::Path<String>, _: FetchNodeProtocolVersion| {
+                fetch_node(identity.into_inner(), registry.clone())
+            },
+        )
+        .add_method(
+            Method::PUT,
+            #[cfg(feature = "authorization")]
+            REGISTRY_WRITE_PERMISSION,
+            move |identity: web::Path<String>, body: web::Bytes, _: FetchNodeProtocolVersion| {
+                put_node(identity.into_inner(), body, registry1.clone())
+            },
+        )
+        .add_method(
+            Method::DELETE,
+            #[cfg(feature = "authorization")]
+            REGISTRY_WRITE_PERMISSION,
+            move |identity: web::Path<String>, _: FetchNodeProtocolVersion| {
+                delete_node(identity.into_inner(), registry2.clone())
+            },
+        )
+}
+
+async fn fetch_node(
+    identity: String,
+    registry: Box<dyn RwRegistry>,
+) -> Result<HttpResponse, HandlerError> {
+    let node = web::block(move || {
+        registry
+            .get_node(&identity)
+            .map_err(|err| to_response_error(err, "Unable to fetch node"))?
+            .ok_or_else(|| ResponseError::not_found("Node not found"))
+    })
+    .await??;
+
+    Ok(HttpResponse::Ok().json(NodeResponse::from(&node)))
+}
+
+async fn put_node(
+    path_identity: String,
+    body: web::Bytes,
+    registry: Box<dyn RwRegistry>,
+) -> Result<HttpResponse, HandlerError> {
+    let node = serde_json::from_slice::<NewNode>(&body)
+        .map_err(|err| ResponseError::bad_request(format!("Invalid node: {}", err)))?;
+
+    web::block(move || {
+        let update_node = Node::try_from(node).map_err(|err| {
+            ResponseError::bad_request(format!(
+                "Invalid node: Failed to update node, node is invalid: {}",
+                err
+            ))
+        })?;
+
+        if update_node.identity() != path_identity {
+            return Err(ResponseError::bad_request(format!(
+                "Invalid node: Node identity cannot be changed: {}",
+                update_node.identity()
+            )));
+        }
+
+        registry
+            .update_node(update_node)
+            .map_err(|err| to_response_error(err, "Unable to put node"))
+    })
+    .await??;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+async fn delete_node(
+    identity: String,
+    registry: Box<dyn RwRegistry>,
+) -> Result<HttpResponse, HandlerError> {
+    web::block(move || {
+        registry
+            .delete_node(&identity)
+            .map_err(|err| to_response_error(err, "Unable to delete node"))?
+            .ok_or_else(|| ResponseError::not_found("Node not found"))
+    })
+    .await??;
+
+    Ok(HttpResponse::Ok().finish())
+}
@@ -0,0 +1,126 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use actix_web::{http::Method, web, FromRequest, Handler, Responder, Route};
+#[cfg(feature = "authorization")]
+use splinter::rest_api::auth::authorization::{Permission, PermissionMap};
+
+/// Creates a new actix-web route for a handler. actix-web 4 builds a fresh `App` for every worker
+/// thread, so routes are created on demand rather than being shared.
+type RouteFactory = Arc<dyn Fn() -> Route + Send + Sync>;
+
+/// Provides a set of resources to be served by the REST API.
+pub trait RestResourceProvider {
+    fn resources(&self) -> Vec<Resource>;
+}
+
+/// A REST API endpoint, defined by its route and the handlers for each supported method.
+#[derive(Clone)]
+pub struct Resource {
+    route: String,
+    methods: Vec<ResourceMethod>,
+    #[cfg(feature = "authorization-handler-rbac-scopes")]
+    payload_scoped: bool,
+}
+
+#[derive(Clone)]
+struct ResourceMethod {
+    method: Method,
+    #[cfg(feature = "authorization")]
+    permission: Permission,
+    route_factory: RouteFactory,
+}
+
+impl Resource {
+    /// Starts building a resource for the given route. The route may contain path variables
+    /// surrounded by `{}`.
+    pub fn build(route: &str) -> Self {
+        Self {
+            route: route.to_string(),
+            methods: vec![],
+            #[cfg(feature = "authorization-handler-rbac-scopes")]
+            payload_scoped: false,
+        }
+    }
+
+    /// Adds an async handler for the given method. The handler may take any actix-web extractors
+    /// as arguments.
+    pub fn add_method<F, Args>(
+        mut self,
+        method: Method,
+        #[cfg(feature = "authorization")] permission: Permission,
+        handler: F,
+    ) -> Self
+    where
+        F: Handler<Args> + Send + Sync,
+        Args: FromRequest + 'static,
+        F::Output: Responder + 'static,
+    {
+        let route_method = method.clone();
+        self.methods.push(ResourceMethod {
+            method,
+            #[cfg(feature = "authorization")]
+            permission,
+            route_factory: Arc::new(move || web::method(route_method.clone()).to(handler.clone())),
+        });
+        self
+    }
+
+    /// Marks the methods of this resource as ones whose circuit scope is determined from the
+    /// request's payload.
+    ///
+    /// If a client has not been granted a method's permission node-wide, the request is passed to
+    /// the handler with a [`PayloadScopeAuthorization`] in its extensions. The handler must use it
+    /// to check the permission against the circuit its payload concerns.
+    ///
+    /// [`PayloadScopeAuthorization`]: splinter::rest_api::auth::authorization::PayloadScopeAuthorization
+    #[cfg(feature = "authorization-handler-rbac-scopes")]
+    pub fn with_payload_scope(mut self) -> Self {
+        self.payload_scoped = true;
+        self
+    }
+
+    pub fn route(&self) -> &str {
+        &self.route
+    }
+
+    /// Returns the permissions that guard each of the resource's methods.
+    #[cfg(feature = "authorization")]
+    pub(crate) fn permissions(&self) -> PermissionMap<Method> {
+        let mut permission_map = PermissionMap::new();
+        for resource_method in &self.methods {
+            permission_map.add_permission(
+                resource_method.method.clone(),
+                &self.route,
+                resource_method.permission,
+            );
+            #[cfg(feature = "authorization-handler-rbac-scopes")]
+            if self.payload_scoped {
+                permission_map
+                    .add_payload_scoped_endpoint(resource_method.method.clone(), &self.route);
+            }
+        }
+        permission_map
+    }
+
+    pub(crate) fn to_actix_resource(&self) -> actix_web::Resource {
+        self.methods
+            .iter()
+            .fold(web::resource(&self.route), |resource, resource_method| {
+                resource.route((resource_method.route_factory)())
+            })
+    }
+}
@@ -0,0 +1,109 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use actix_web::{http::Method, web, HttpRequest, HttpResponse};
+use scabbard::protocol::SCABBARD_PROTOCOL_VERSION;
+use splinter::rest_api::ErrorResponse;
+use splinter_rest_api_common::error::ResponseError;
+use splinter_rest_api_common::scabbard::batch_statuses::BatchInfoResponse;
+use splinter_rest_api_common::scabbard::SCABBARD_BATCH_STATUSES_PROTOCOL_MIN;
+#[cfg(feature = "authorization")]
+use splinter_rest_api_common::scabbard::SCABBARD_READ_PERMISSION;
+
+use crate::paging::parse_query;
+use crate::{HandlerError, ProtocolVersion, Resource};
+
+use super::{lookup_scabbard, route, Services};
+
+const DEFAULT_BATCH_STATUS_WAIT_SECS: u64 = 300;
+
+type BatchStatusesProtocolVersion =
+    ProtocolVersion<SCABBARD_BATCH_STATUSES_PROTOCOL_MIN, SCABBARD_PROTOCOL_VERSION>;
+
+pub fn make_batch_statuses_resource(services: Services) -> Resource {
+    Resource::build(&route("/batch_statuses")).add_method(
+        Method::GET,
+        #[cfg(feature = "authorization")]
+        SCABBARD_READ_PERMISSION,
+        move |req: HttpRequest,
+              path: web::Path<(String, String)>,
+              _: BatchStatusesProtocolVersion| {
+            batch_statuses(req, path.into_inner(), services.clone())
+        },
+    )
+}
+
+async fn batch_statuses(
+    req: HttpRequest,
+    (circuit, service_id): (String, String),
+    services: Services,
+) -> Result<HttpResponse, HandlerError> {
+    let scabbard = lookup_scabbard(&services, &circuit, &service_id)?;
+
+    let query = parse_query(&req)?;
+
+    let ids = query
+        .get("ids")
+        .ok_or_else(|| ResponseError::bad_request("No batch IDs specified"))?
+        .split(',')
+        .map(String::from)
+        .collect();
+
+    let wait = query
+        .get("wait")
+        .and_then(|wait_str| {
+            if wait_str.as_str() == "false" {
+                None
+            } else {
+                wait_str
+                    .parse()
+                    .ok()
+                    .or(Some(DEFAULT_BATCH_STATUS_WAIT_SECS))
+            }
+        })
+        .map(Duration::from_secs);
+
+    let batch_infos = web::block(move || {
+        let batch_info_iter = scabbard.get_batch_info(ids, wait).map_err(|err| {
+            ResponseError::internal_error(
+                format!("Failed to get batch statuses iterator: {}", err),
+                None,
+            )
+        })?;
+
+        Ok::<_, ResponseError>(
+            batch_info_iter
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|err| err.to_string()),
+        )
+    })
+    .await??;
+
+    match batch_infos {
+        Ok(batch_infos) => Ok(HttpResponse::Ok().json(
+            batch_infos
+                .iter()
+                .map(BatchInfoResponse::from)
+                .collect::<Vec<_>>(),
+        )),
+        Err(err) => Ok(
+            HttpResponse::RequestTimeout().json(ErrorResponse::request_timeout(&format!(
+                "Failed to get batch statuses before timeout: {}",
+                err
+            ))),
+        ),
+    }
+}
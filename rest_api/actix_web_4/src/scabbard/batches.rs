@@ -0,0 +1,82 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use actix_web::{http::Method, web, HttpResponse};
+use scabbard::protocol::SCABBARD_PROTOCOL_VERSION;
+use splinter::rest_api::ErrorResponse;
+use splinter_rest_api_common::error::ResponseError;
+use splinter_rest_api_common::scabbard::batches::BatchLinkResponse;
+use splinter_rest_api_common::scabbard::SCABBARD_ADD_BATCHES_PROTOCOL_MIN;
+#[cfg(feature = "authorization")]
+use splinter_rest_api_common::scabbard::SCABBARD_WRITE_PERMISSION;
+use transact::protocol::batch::BatchPair;
+use transact::protos::FromBytes;
+
+use crate::{HandlerError, ProtocolVersion, Resource};
+
+use super::{lookup_scabbard, route, Services};
+
+type AddBatchesProtocolVersion =
+    ProtocolVersion<SCABBARD_ADD_BATCHES_PROTOCOL_MIN, SCABBARD_PROTOCOL_VERSION>;
+
+pub fn make_add_batches_resource(services: Services) -> Resource {
+    Resource::build(&route("/batches")).add_method(
+        Method::POST,
+        #[cfg(feature = "authorization")]
+        SCABBARD_WRITE_PERMISSION,
+        move |path: web::Path<(String, String)>, body: web::Bytes, _: AddBatchesProtocolVersion| {
+            add_batches(path.into_inner(), body, services.clone())
+        },
+    )
+}
+
+async fn add_batches(
+    (circuit, service_id): (String, String),
+    body: web::Bytes,
+    services: Services,
+) -> Result<HttpResponse, HandlerError> {
+    let scabbard = lookup_scabbard(&services, &circuit, &service_id)?;
+
+    let batches: Vec<BatchPair> = match Vec::from_bytes(&body) {
+        Ok(batches) => batches,
+        Err(_) => {
+            return Err(
+                ResponseError::bad_request("Invalid body: not a valid list of batches").into(),
+            )
+        }
+    };
+
+    let link = web::block(move || {
+        if !scabbard.accepting_batches().map_err(|err| {
+            ResponseError::internal_error(format!("Failed to add batches: {}", err), None)
+        })? {
+            return Ok(Err(()));
+        }
+
+        scabbard.add_batches(batches).map(Ok).map_err(|err| {
+            ResponseError::internal_error(format!("Failed to add batches: {}", err), None)
+        })
+    })
+    .await??;
+
+    match link {
+        Ok(Some(link)) => Ok(HttpResponse::Accepted().json(BatchLinkResponse::from(link.as_str()))),
+        Ok(None) => Ok(HttpResponse::BadRequest()
+            .json(ErrorResponse::bad_request("No valid batches provided"))),
+        Err(()) => {
+            warn!("Rejecting submitted batch, too many pending batches");
+            Ok(HttpResponse::TooManyRequests().finish())
+        }
+    }
+}
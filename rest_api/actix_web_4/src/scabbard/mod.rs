@@ -0,0 +1,123 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the scabbard service REST API endpoints.
+
+mod batch_statuses;
+mod batches;
+mod receipts;
+mod state;
+mod state_address;
+mod state_root;
+mod state_snapshot;
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use scabbard::service::{Scabbard, SERVICE_TYPE};
+use splinter::runtime::service::instance::{
+    ManagedService, ServiceDefinition, ServiceOrchestrator,
+};
+use splinter::service::instance::ServiceInstance;
+use splinter_rest_api_common::error::ResponseError;
+
+use crate::{Resource, RestResourceProvider};
+
+type Services = Arc<Mutex<HashMap<ServiceDefinition, ManagedService>>>;
+
+/// Provides the REST API [`Resource`] definitions for the scabbard services run by a
+/// [`ServiceOrchestrator`].
+///
+/// The following endpoints are provided:
+///
+/// * `POST /scabbard/{circuit}/{service_id}/batches` - Submit batches to a scabbard service
+/// * `GET /scabbard/{circuit}/{service_id}/batch_statuses` - Get the statuses of batches
+/// * `GET /scabbard/{circuit}/{service_id}/state` - List state entries, optionally by prefix
+/// * `GET /scabbard/{circuit}/{service_id}/state/{address}` - Get the state entry at an address
+/// * `GET /scabbard/{circuit}/{service_id}/state_root` - Get the current state root hash
+/// * `GET /scabbard/{circuit}/{service_id}/state_snapshot` - Get a snapshot of all state entries
+///   at the current state root
+/// * `GET /scabbard/{circuit}/{service_id}/receipts` - List transaction receipts, optionally since
+///   a transaction
+/// * `GET /scabbard/{circuit}/{service_id}/receipts/{transaction_id}` - Get the receipt of a
+///   transaction
+pub struct ScabbardRestResourceProvider {
+    resources: Vec<Resource>,
+}
+
+impl ScabbardRestResourceProvider {
+    pub fn new(orchestrator: &ServiceOrchestrator) -> Self {
+        let services = orchestrator.services();
+        let resources = vec![
+            batches::make_add_batches_resource(services.clone()),
+            batch_statuses::make_batch_statuses_resource(services.clone()),
+            state_address::make_state_address_resource(services.clone()),
+            state::make_state_resource(services.clone()),
+            state_root::make_state_root_resource(services.clone()),
+            state_snapshot::make_state_snapshot_resource(services.clone()),
+            receipts::make_receipts_resource(services.clone()),
+            receipts::make_receipt_resource(services),
+        ];
+        Self { resources }
+    }
+}
+
+impl RestResourceProvider for ScabbardRestResourceProvider {
+    fn resources(&self) -> Vec<Resource> {
+        self.resources.clone()
+    }
+}
+
+/// Builds the route for a scabbard endpoint.
+fn route(endpoint: &str) -> String {
+    format!("/{}/{{circuit}}/{{service_id}}{}", SERVICE_TYPE, endpoint)
+}
+
+/// Finds the scabbard service with the given ID on the given circuit.
+fn lookup_scabbard(
+    services: &Services,
+    circuit: &str,
+    service_id: &str,
+) -> Result<Scabbard, ResponseError> {
+    let services = services.lock().map_err(|_| {
+        ResponseError::internal_error("Orchestrator's service lock is poisoned", None)
+    })?;
+
+    let service = services
+        .iter()
+        .find_map(|(service_def, managed_service)| {
+            if service_def.service_type == SERVICE_TYPE
+                && service_def.circuit == circuit
+                && service_def.service_id == service_id
+            {
+                Some(&managed_service.service)
+            } else {
+                None
+            }
+        })
+        .ok_or_else(|| {
+            ResponseError::not_found(format!(
+                "{} service {} on circuit {} not found",
+                SERVICE_TYPE, service_id, circuit
+            ))
+        })?;
+
+    service
+        .as_any()
+        .downcast_ref::<Scabbard>()
+        .cloned()
+        .ok_or_else(|| {
+            ResponseError::internal_error("Failed to downcast to scabbard service", None)
+        })
+}
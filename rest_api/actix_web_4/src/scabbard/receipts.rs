@@ -0,0 +1,119 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use actix_web::{http::Method, web, HttpRequest, HttpResponse};
+use scabbard::protocol::SCABBARD_PROTOCOL_VERSION;
+use splinter_rest_api_common::error::ResponseError;
+use splinter_rest_api_common::paging::v1::DEFAULT_LIMIT;
+#[cfg(feature = "authorization")]
+use splinter_rest_api_common::scabbard::SCABBARD_READ_PERMISSION;
+use splinter_rest_api_common::scabbard::{
+    SCABBARD_GET_RECEIPT_PROTOCOL_MIN, SCABBARD_LIST_RECEIPTS_MAX_LIMIT,
+    SCABBARD_LIST_RECEIPTS_PROTOCOL_MIN,
+};
+
+use crate::paging::parse_query;
+use crate::{HandlerError, ProtocolVersion, Resource};
+
+use super::{lookup_scabbard, route, Services};
+
+type GetReceiptProtocolVersion =
+    ProtocolVersion<SCABBARD_GET_RECEIPT_PROTOCOL_MIN, SCABBARD_PROTOCOL_VERSION>;
+type ListReceiptsProtocolVersion =
+    ProtocolVersion<SCABBARD_LIST_RECEIPTS_PROTOCOL_MIN, SCABBARD_PROTOCOL_VERSION>;
+
+pub fn make_receipt_resource(services: Services) -> Resource {
+    Resource::build(&route("/receipts/{transaction_id}")).add_method(
+        Method::GET,
+        #[cfg(feature = "authorization")]
+        SCABBARD_READ_PERMISSION,
+        move |path: web::Path<(String, String, String)>, _: GetReceiptProtocolVersion| {
+            get_receipt(path.into_inner(), services.clone())
+        },
+    )
+}
+
+pub fn make_receipts_resource(services: Services) -> Resource {
+    Resource::build(&route("/receipts")).add_method(
+        Method::GET,
+        #[cfg(feature = "authorization")]
+        SCABBARD_READ_PERMISSION,
+        move |req: HttpRequest,
+              path: web::Path<(String, String)>,
+              _: ListReceiptsProtocolVersion| {
+            list_receipts(req, path.into_inner(), services.clone())
+        },
+    )
+}
+
+async fn get_receipt(
+    (circuit, service_id, transaction_id): (String, String, String),
+    services: Services,
+) -> Result<HttpResponse, HandlerError> {
+    let scabbard = lookup_scabbard(&services, &circuit, &service_id)?;
+
+    let receipt = web::block(move || {
+        scabbard
+            .get_receipt(&transaction_id)
+            .map_err(|err| {
+                ResponseError::internal_error(
+                    format!("Failed to get transaction receipt: {}", err),
+                    None,
+                )
+            })?
+            .ok_or_else(|| ResponseError::not_found("Receipt not found"))
+    })
+    .await??;
+
+    Ok(HttpResponse::Ok().json(receipt))
+}
+
+async fn list_receipts(
+    req: HttpRequest,
+    (circuit, service_id): (String, String),
+    services: Services,
+) -> Result<HttpResponse, HandlerError> {
+    let scabbard = lookup_scabbard(&services, &circuit, &service_id)?;
+
+    let mut query = parse_query(&req)?;
+    let since = query.remove("since");
+    let limit = match query.remove("limit") {
+        Some(limit) => match limit.parse::<usize>() {
+            Ok(limit) if limit > 0 && limit <= SCABBARD_LIST_RECEIPTS_MAX_LIMIT => limit,
+            _ => {
+                return Err(ResponseError::bad_request(format!(
+                    "limit must be a positive integer no greater than {}",
+                    SCABBARD_LIST_RECEIPTS_MAX_LIMIT
+                ))
+                .into())
+            }
+        },
+        None => DEFAULT_LIMIT,
+    };
+
+    let receipts = web::block(move || {
+        scabbard
+            .list_receipts_since(since, limit)
+            .map_err(|err| {
+                ResponseError::internal_error(
+                    format!("Failed to list transaction receipts: {}", err),
+                    None,
+                )
+            })?
+            .ok_or_else(|| ResponseError::not_found("Receipt given by since not found"))
+    })
+    .await??;
+
+    Ok(HttpResponse::Ok().json(receipts))
+}
@@ -0,0 +1,77 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use actix_web::{http::Method, web, HttpRequest, HttpResponse};
+use scabbard::protocol::SCABBARD_PROTOCOL_VERSION;
+use splinter_rest_api_common::error::ResponseError;
+#[cfg(feature = "authorization")]
+use splinter_rest_api_common::scabbard::SCABBARD_READ_PERMISSION;
+use splinter_rest_api_common::scabbard::{
+    state::StateEntryResponse, SCABBARD_LIST_STATE_PROTOCOL_MIN,
+};
+
+use crate::paging::parse_query;
+use crate::{HandlerError, ProtocolVersion, Resource};
+
+use super::{lookup_scabbard, route, Services};
+
+type ListStateProtocolVersion =
+    ProtocolVersion<SCABBARD_LIST_STATE_PROTOCOL_MIN, SCABBARD_PROTOCOL_VERSION>;
+
+pub fn make_state_resource(services: Services) -> Resource {
+    Resource::build(&route("/state")).add_method(
+        Method::GET,
+        #[cfg(feature = "authorization")]
+        SCABBARD_READ_PERMISSION,
+        move |req: HttpRequest, path: web::Path<(String, String)>, _: ListStateProtocolVersion| {
+            list_state(req, path.into_inner(), services.clone())
+        },
+    )
+}
+
+async fn list_state(
+    req: HttpRequest,
+    (circuit, service_id): (String, String),
+    services: Services,
+) -> Result<HttpResponse, HandlerError> {
+    let scabbard = lookup_scabbard(&services, &circuit, &service_id)?;
+
+    let prefix = parse_query(&req)?.remove("prefix");
+
+    let entries = web::block(move || {
+        scabbard
+            .get_state_with_prefix(prefix.as_deref())
+            .map_err(|err| {
+                ResponseError::internal_error(
+                    format!("Failed to get state with prefix: {}", err),
+                    None,
+                )
+            })?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| {
+                ResponseError::internal_error(
+                    format!("Failed to consume state iterator: {}", err),
+                    None,
+                )
+            })
+    })
+    .await??;
+
+    Ok(HttpResponse::Ok().json(
+        entries
+            .iter()
+            .map(StateEntryResponse::from)
+            .collect::<Vec<_>>(),
+    ))
+}
@@ -0,0 +1,60 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use actix_web::{http::Method, web, HttpResponse};
+use scabbard::protocol::SCABBARD_PROTOCOL_VERSION;
+use splinter_rest_api_common::error::ResponseError;
+use splinter_rest_api_common::scabbard::SCABBARD_GET_STATE_PROTOCOL_MIN;
+#[cfg(feature = "authorization")]
+use splinter_rest_api_common::scabbard::SCABBARD_READ_PERMISSION;
+
+use crate::{HandlerError, ProtocolVersion, Resource};
+
+use super::{lookup_scabbard, route, Services};
+
+type GetStateProtocolVersion =
+    ProtocolVersion<SCABBARD_GET_STATE_PROTOCOL_MIN, SCABBARD_PROTOCOL_VERSION>;
+
+pub fn make_state_address_resource(services: Services) -> Resource {
+    Resource::build(&route("/state/{address}")).add_method(
+        Method::GET,
+        #[cfg(feature = "authorization")]
+        SCABBARD_READ_PERMISSION,
+        move |path: web::Path<(String, String, String)>, _: GetStateProtocolVersion| {
+            get_state_at_address(path.into_inner(), services.clone())
+        },
+    )
+}
+
+async fn get_state_at_address(
+    (circuit, service_id, address): (String, String, String),
+    services: Services,
+) -> Result<HttpResponse, HandlerError> {
+    let scabbard = lookup_scabbard(&services, &circuit, &service_id)?;
+
+    let value = web::block(move || {
+        scabbard
+            .get_state_at_address(&address)
+            .map_err(|err| {
+                ResponseError::internal_error(
+                    format!("Failed to get state at address: {}", err),
+                    None,
+                )
+            })?
+            .ok_or_else(|| ResponseError::not_found("Address not set"))
+    })
+    .await??;
+
+    Ok(HttpResponse::Ok().json(value))
+}
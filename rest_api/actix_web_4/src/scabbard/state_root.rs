@@ -0,0 +1,57 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use actix_web::{http::Method, web, HttpResponse};
+use scabbard::protocol::SCABBARD_PROTOCOL_VERSION;
+use splinter_rest_api_common::error::ResponseError;
+#[cfg(feature = "authorization")]
+use splinter_rest_api_common::scabbard::SCABBARD_READ_PERMISSION;
+use splinter_rest_api_common::scabbard::SCABBARD_STATE_ROOT_PROTOCOL_MIN;
+
+use crate::{HandlerError, ProtocolVersion, Resource};
+
+use super::{lookup_scabbard, route, Services};
+
+type StateRootProtocolVersion =
+    ProtocolVersion<SCABBARD_STATE_ROOT_PROTOCOL_MIN, SCABBARD_PROTOCOL_VERSION>;
+
+pub fn make_state_root_resource(services: Services) -> Resource {
+    Resource::build(&route("/state_root")).add_method(
+        Method::GET,
+        #[cfg(feature = "authorization")]
+        SCABBARD_READ_PERMISSION,
+        move |path: web::Path<(String, String)>, _: StateRootProtocolVersion| {
+            get_state_root(path.into_inner(), services.clone())
+        },
+    )
+}
+
+async fn get_state_root(
+    (circuit, service_id): (String, String),
+    services: Services,
+) -> Result<HttpResponse, HandlerError> {
+    let scabbard = lookup_scabbard(&services, &circuit, &service_id)?;
+
+    let state_root = web::block(move || {
+        scabbard.get_current_state_root().map_err(|err| {
+            ResponseError::internal_error(
+                format!("Failed to get current state root: {}", err),
+                None,
+            )
+        })
+    })
+    .await??;
+
+    Ok(HttpResponse::Ok().json(state_root))
+}
@@ -0,0 +1,100 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the `/status` endpoint.
+
+use actix_web::{http::Method, HttpResponse};
+#[cfg(feature = "tls-reload")]
+use splinter::transport::tls::reload::TlsReloadStatus;
+use splinter_rest_api_common::status::Status;
+#[cfg(feature = "authorization")]
+use splinter_rest_api_common::status::STATUS_READ_PERMISSION;
+
+use crate::{Resource, RestResourceProvider};
+
+pub struct StatusResourceProvider {
+    node_id: String,
+    display_name: String,
+    #[cfg(feature = "service-endpoint")]
+    service_endpoint: String,
+    network_endpoints: Vec<String>,
+    advertised_endpoints: Vec<String>,
+    #[cfg(feature = "tls-reload")]
+    tls_reload_status: Option<TlsReloadStatus>,
+}
+
+impl StatusResourceProvider {
+    pub fn new(
+        node_id: String,
+        display_name: String,
+        #[cfg(feature = "service-endpoint")] service_endpoint: String,
+        network_endpoints: Vec<String>,
+        advertised_endpoints: Vec<String>,
+    ) -> Self {
+        Self {
+            node_id,
+            display_name,
+            #[cfg(feature = "service-endpoint")]
+            service_endpoint,
+            network_endpoints,
+            advertised_endpoints,
+            #[cfg(feature = "tls-reload")]
+            tls_reload_status: None,
+        }
+    }
+
+    /// Includes the reload status of the node's TLS contexts in the node status
+    #[cfg(feature = "tls-reload")]
+    pub fn with_tls_reload_status(mut self, tls_reload_status: TlsReloadStatus) -> Self {
+        self.tls_reload_status = Some(tls_reload_status);
+        self
+    }
+}
+
+impl RestResourceProvider for StatusResourceProvider {
+    fn resources(&self) -> Vec<Resource> {
+        let node_id = self.node_id.clone();
+        let display_name = self.display_name.clone();
+        #[cfg(feature = "service-endpoint")]
+        let service_endpoint = self.service_endpoint.clone();
+        let network_endpoints = self.network_endpoints.clone();
+        let advertised_endpoints = self.advertised_endpoints.clone();
+        #[cfg(feature = "tls-reload")]
+        let tls_reload_status = self.tls_reload_status.clone();
+
+        let handle = move || {
+            let status = Status::new(
+                node_id.clone(),
+                display_name.clone(),
+                #[cfg(feature = "service-endpoint")]
+                service_endpoint.clone(),
+                network_endpoints.clone(),
+                advertised_endpoints.clone(),
+            );
+            #[cfg(feature = "tls-reload")]
+            let status = match &tls_reload_status {
+                Some(tls_reload_status) => status.with_tls_contexts(&tls_reload_status.reports()),
+                None => status,
+            };
+            async move { HttpResponse::Ok().json(status) }
+        };
+
+        vec![Resource::build("/status").add_method(
+            Method::GET,
+            #[cfg(feature = "authorization")]
+            STATUS_READ_PERMISSION,
+            handle,
+        )]
+    }
+}
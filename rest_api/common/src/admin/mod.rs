@@ -0,0 +1,37 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub mod resources;
+
+#[cfg(feature = "authorization")]
+use splinter::rest_api::auth::authorization::Permission;
+
+#[cfg(feature = "authorization")]
+pub const CIRCUIT_READ_PERMISSION: Permission = Permission::Check {
+    permission_id: "circuit.read",
+    permission_display_name: "Circuit read",
+    permission_description: "Allows the client to read circuit state",
+};
+#[cfg(feature = "authorization")]
+pub const CIRCUIT_WRITE_PERMISSION: Permission = Permission::Check {
+    permission_id: "circuit.write",
+    permission_display_name: "Circuit write",
+    permission_description: "Allows the client to modify circuit state",
+};
+
+pub const ADMIN_SUBMIT_PROTOCOL_MIN: u32 = 1;
+pub const ADMIN_LIST_CIRCUITS_PROTOCOL_MIN: u32 = 1;
+pub const ADMIN_FETCH_CIRCUIT_PROTOCOL_MIN: u32 = 1;
+pub const ADMIN_LIST_PROPOSALS_PROTOCOL_MIN: u32 = 1;
+pub const ADMIN_FETCH_PROPOSAL_PROTOCOL_MIN: u32 = 1;
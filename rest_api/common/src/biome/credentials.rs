@@ -0,0 +1,82 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "authorization")]
+use splinter::rest_api::auth::authorization::Permission;
+
+#[cfg(feature = "biome-key-management")]
+use super::key_management::NewKey;
+
+#[cfg(feature = "authorization")]
+pub const BIOME_USER_READ_PERMISSION: Permission = Permission::Check {
+    permission_id: "biome.user.read",
+    permission_display_name: "Biome user read",
+    permission_description: "Allows the client to view all Biome users",
+};
+#[cfg(feature = "authorization")]
+pub const BIOME_USER_WRITE_PERMISSION: Permission = Permission::Check {
+    permission_id: "biome.user.write",
+    permission_display_name: "Biome user write",
+    permission_description: "Allows the client to modify all Biome users",
+};
+
+pub const BIOME_REGISTER_PROTOCOL_MIN: u32 = 1;
+pub const BIOME_LOGIN_PROTOCOL_MIN: u32 = 1;
+pub const BIOME_LOGOUT_PROTOCOL_MIN: u32 = 1;
+pub const BIOME_TOKEN_PROTOCOL_MIN: u32 = 1;
+pub const BIOME_VERIFY_PROTOCOL_MIN: u32 = 1;
+pub const BIOME_LIST_USERS_PROTOCOL_MIN: u32 = 1;
+pub const BIOME_USER_PROTOCOL_MIN: u32 = 1;
+pub const BIOME_PASSWORD_RESET_PROTOCOL_MIN: u32 = 1;
+pub const BIOME_TOTP_PROTOCOL_MIN: u32 = 1;
+
+#[derive(Deserialize)]
+pub struct UsernamePassword {
+    pub username: String,
+    pub hashed_password: String,
+    /// Only required at login for users that have enabled TOTP
+    #[serde(default)]
+    pub totp_code: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct NewUser<'a> {
+    pub user_id: &'a str,
+    pub username: &'a str,
+}
+
+#[derive(Deserialize)]
+pub struct RefreshToken {
+    pub token: String,
+}
+
+#[cfg(feature = "biome-key-management")]
+#[derive(Deserialize)]
+pub struct ModifyUser {
+    pub username: String,
+    pub hashed_password: String,
+    pub new_password: Option<String>,
+    pub new_key_pairs: Vec<NewKey>,
+}
+
+#[derive(Deserialize)]
+pub struct PasswordReset {
+    pub reset_token: String,
+    pub new_password: String,
+}
+
+#[derive(Deserialize)]
+pub struct TotpCode {
+    pub code: String,
+}
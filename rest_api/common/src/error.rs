@@ -20,7 +20,8 @@ use std::fmt::Display;
 pub enum ResponseError {
     BadRequest(String),
     NotFound(String),
-    InternalError(String, Option<Box<dyn Error>>),
+    Forbidden(String),
+    InternalError(String, Option<Box<dyn Error + Send>>),
     NotAuthorized,
 }
 
@@ -31,6 +32,7 @@ impl Display for ResponseError {
         match self {
             ResponseError::BadRequest(msg) => write!(f, "Bad Request: {}", msg),
             ResponseError::NotFound(url) => write!(f, "Could not find resource for: {}", url),
+            ResponseError::Forbidden(msg) => write!(f, "Forbidden: {}", msg),
             ResponseError::InternalError(msg, Some(err)) => {
                 write!(f, "Internal Error: {}: {}", msg, err)
             }
@@ -49,7 +51,11 @@ impl ResponseError {
         Self::NotFound(url.into())
     }
 
-    pub fn internal_error<S: Into<String>>(msg: S, err: Option<Box<dyn Error>>) -> Self {
+    pub fn forbidden<S: Into<String>>(msg: S) -> Self {
+        Self::Forbidden(msg.into())
+    }
+
+    pub fn internal_error<S: Into<String>>(msg: S, err: Option<Box<dyn Error + Send>>) -> Self {
         Self::InternalError(msg.into(), err)
     }
 }
@@ -12,8 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "admin-service")]
+pub mod admin;
+#[cfg(any(feature = "biome-credentials", feature = "biome-key-management"))]
+pub mod biome;
 pub mod error;
+#[cfg(feature = "admin-service")]
+mod hex;
+#[cfg(feature = "authorization")]
+pub mod metrics;
 pub mod paging;
+#[cfg(feature = "registry")]
+pub mod registry;
 #[cfg(feature = "scabbard")]
 pub mod scabbard;
 pub mod status;
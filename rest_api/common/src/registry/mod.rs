@@ -0,0 +1,34 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub mod resources;
+
+#[cfg(feature = "authorization")]
+use splinter::rest_api::auth::authorization::Permission;
+
+#[cfg(feature = "authorization")]
+pub const REGISTRY_READ_PERMISSION: Permission = Permission::Check {
+    permission_id: "registry.read",
+    permission_display_name: "Registry read",
+    permission_description: "Allows the client to read the registry",
+};
+#[cfg(feature = "authorization")]
+pub const REGISTRY_WRITE_PERMISSION: Permission = Permission::Check {
+    permission_id: "registry.write",
+    permission_display_name: "Registry write",
+    permission_description: "Allows the client to modify the registry",
+};
+
+pub const REGISTRY_LIST_NODES_PROTOCOL_MIN: u32 = 1;
+pub const REGISTRY_FETCH_NODE_PROTOCOL_MIN: u32 = 1;
@@ -0,0 +1,128 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+#[cfg(feature = "registry-node-health")]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "registry-node-health")]
+use splinter::registry::NodeHealth;
+use splinter::registry::{InvalidNodeError, Node};
+
+use crate::paging::v1::Paging;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ListNodesResponse<'a> {
+    pub data: Vec<NodeResponse<'a>>,
+    pub paging: Paging,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NodeResponse<'a> {
+    pub identity: &'a str,
+    pub endpoints: &'a [String],
+    pub display_name: &'a str,
+    pub keys: &'a [String],
+    pub metadata: &'a HashMap<String, String>,
+    #[cfg(feature = "registry-node-health")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health: Option<NodeHealthResponse>,
+}
+
+#[cfg(feature = "registry-node-health")]
+impl<'a> NodeResponse<'a> {
+    pub fn with_health(mut self, health: Option<&NodeHealth>) -> Self {
+        self.health = health.map(NodeHealthResponse::from);
+        self
+    }
+}
+
+impl<'a> From<&'a Node> for NodeResponse<'a> {
+    fn from(node: &'a Node) -> Self {
+        Self {
+            identity: node.identity(),
+            endpoints: node.endpoints(),
+            display_name: node.display_name(),
+            keys: node.keys(),
+            metadata: node.metadata(),
+            #[cfg(feature = "registry-node-health")]
+            health: None,
+        }
+    }
+}
+
+/// The reachability of a node; times are in seconds since the Unix epoch.
+#[cfg(feature = "registry-node-health")]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NodeHealthResponse {
+    pub reachable: bool,
+    pub last_connected: Option<u64>,
+    pub last_failure: Option<u64>,
+    pub latency_millis: Option<u64>,
+}
+
+#[cfg(feature = "registry-node-health")]
+impl From<&NodeHealth> for NodeHealthResponse {
+    fn from(health: &NodeHealth) -> Self {
+        Self {
+            reachable: health.is_reachable(),
+            last_connected: health.last_connected().map(to_unix_secs),
+            last_failure: health.last_failure().map(to_unix_secs),
+            latency_millis: health.latency().map(|latency| latency.as_millis() as u64),
+        }
+    }
+}
+
+#[cfg(feature = "registry-node-health")]
+fn to_unix_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+/// Used to deserialize add and update requests
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NewNode {
+    /// The Splinter identity of the node; must be non-empty and unique in the registry.
+    pub identity: String,
+    /// The endpoints the node can be reached at; at least one endpoint must be provided, and each
+    /// endpoint must be non-empty and unique in the registry.
+    pub endpoints: Vec<String>,
+    /// A human-readable name for the node; must be non-empty.
+    pub display_name: String,
+    /// The list of public keys that are permitted to act on behalf of the node; at least one key
+    /// must be provided, and each key must be non-empty.
+    pub keys: Vec<String>,
+    /// A map with node metadata.
+    pub metadata: HashMap<String, String>,
+}
+
+impl TryFrom<NewNode> for Node {
+    type Error = InvalidNodeError;
+
+    fn try_from(node: NewNode) -> Result<Self, Self::Error> {
+        let mut builder = Node::builder(node.identity)
+            .with_endpoints(node.endpoints)
+            .with_display_name(node.display_name)
+            .with_keys(node.keys);
+
+        for (k, v) in node.metadata {
+            builder = builder.with_metadata(k, v);
+        }
+
+        builder.build()
+    }
+}
@@ -38,3 +38,9 @@ pub const SCABBARD_BATCH_STATUSES_PROTOCOL_MIN: u32 = 1;
 pub const SCABBARD_GET_STATE_PROTOCOL_MIN: u32 = 1;
 pub const SCABBARD_LIST_STATE_PROTOCOL_MIN: u32 = 1;
 pub const SCABBARD_STATE_ROOT_PROTOCOL_MIN: u32 = 1;
+pub const SCABBARD_STATE_SNAPSHOT_PROTOCOL_MIN: u32 = 1;
+pub const SCABBARD_GET_RECEIPT_PROTOCOL_MIN: u32 = 1;
+pub const SCABBARD_LIST_RECEIPTS_PROTOCOL_MIN: u32 = 1;
+
+/// The maximum number of receipts that may be requested from the list receipts endpoint at once.
+pub const SCABBARD_LIST_RECEIPTS_MAX_LIMIT: usize = 1000;
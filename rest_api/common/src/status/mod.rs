@@ -13,7 +13,21 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "tls-reload")]
+use std::time::{SystemTime, UNIX_EPOCH};
+
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "authorization")]
+use splinter::rest_api::auth::authorization::Permission;
+#[cfg(feature = "tls-reload")]
+use splinter::transport::tls::reload::TlsReloadReport;
+
+#[cfg(feature = "authorization")]
+pub const STATUS_READ_PERMISSION: Permission = Permission::Check {
+    permission_id: "status.read",
+    permission_display_name: "Status read",
+    permission_description: "Allows the client to get node status info",
+};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Status {
@@ -24,6 +38,9 @@ pub struct Status {
     network_endpoints: Vec<String>,
     advertised_endpoints: Vec<String>,
     version: String,
+    #[cfg(feature = "tls-reload")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tls_contexts: Vec<TlsContextStatus>,
 }
 
 impl Status {
@@ -42,10 +59,48 @@ impl Status {
             network_endpoints,
             advertised_endpoints,
             version: get_version(),
+            #[cfg(feature = "tls-reload")]
+            tls_contexts: vec![],
+        }
+    }
+
+    /// Adds the reload status of the node's TLS contexts
+    #[cfg(feature = "tls-reload")]
+    pub fn with_tls_contexts(mut self, reports: &[TlsReloadReport]) -> Self {
+        self.tls_contexts = reports.iter().map(TlsContextStatus::from).collect();
+        self
+    }
+}
+
+/// The reload status of a TLS context; times are in seconds since the Unix epoch.
+#[cfg(feature = "tls-reload")]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TlsContextStatus {
+    name: String,
+    last_reload: Option<u64>,
+    last_successful_reload: Option<u64>,
+    last_reload_error: Option<String>,
+}
+
+#[cfg(feature = "tls-reload")]
+impl From<&TlsReloadReport> for TlsContextStatus {
+    fn from(report: &TlsReloadReport) -> Self {
+        Self {
+            name: report.name().to_string(),
+            last_reload: report.last_attempt().map(to_unix_secs),
+            last_successful_reload: report.last_success().map(to_unix_secs),
+            last_reload_error: report.last_error().map(String::from),
         }
     }
 }
 
+#[cfg(feature = "tls-reload")]
+fn to_unix_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
 fn get_version() -> String {
     format!(
         "{}.{}.{}",
@@ -42,7 +42,10 @@ use sabre_sdk::{
     },
     protos::FromBytes,
 };
-use scabbard::client::{ReqwestScabbardClientBuilder, ScabbardClient, ServiceId};
+use scabbard::client::{
+    Receipt, ReceiptResult, ReceiptStateChange, ReqwestScabbardClientBuilder, ScabbardClient,
+    ServiceId,
+};
 use transact::contract::archive::{default_scar_path, SmartContractArchive};
 
 use error::CliError;
@@ -572,6 +575,75 @@ fn run() -> Result<(), CliError> {
                                 .help("Name or path of private key"),
                         ]),
                 ),
+        )
+        .subcommand(
+            SubCommand::with_name("receipt")
+                .about("Get the receipts of committed transactions")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(
+                    SubCommand::with_name("list")
+                        .about("List the receipts of committed transactions")
+                        .args(&[
+                            Arg::with_name("url")
+                                .help("URL to the scabbard REST API")
+                                .short("U")
+                                .long("url")
+                                .takes_value(true),
+                            Arg::with_name("service-id")
+                                .long_help(
+                                    "Fully-qualified service ID of the scabbard service (must be \
+                                     of the form 'circuit_id::service_id')",
+                                )
+                                .long("service-id")
+                                .takes_value(true)
+                                .required(true),
+                            Arg::with_name("key")
+                                .short("k")
+                                .long("key")
+                                .takes_value(true)
+                                .help("Name or path of private key"),
+                            Arg::with_name("since")
+                                .help("Only list receipts of transactions committed after this one")
+                                .long("since")
+                                .value_name("transaction-id")
+                                .takes_value(true),
+                            Arg::with_name("limit")
+                                .help("Maximum number of receipts to list")
+                                .long("limit")
+                                .takes_value(true),
+                        ]),
+                )
+                .subcommand(
+                    SubCommand::with_name("show")
+                        .about(
+                            "Show the receipt of a transaction, including its state changes and \
+                             events",
+                        )
+                        .args(&[
+                            Arg::with_name("url")
+                                .help("URL to the scabbard REST API")
+                                .short("U")
+                                .long("url")
+                                .takes_value(true),
+                            Arg::with_name("service-id")
+                                .long_help(
+                                    "Fully-qualified service ID of the scabbard service (must be \
+                                     of the form 'circuit_id::service_id')",
+                                )
+                                .long("service-id")
+                                .takes_value(true)
+                                .required(true),
+                            Arg::with_name("key")
+                                .short("k")
+                                .long("key")
+                                .takes_value(true)
+                                .help("Name or path of private key"),
+                            Arg::with_name("transaction-id")
+                                .help("ID of the transaction")
+                                .takes_value(true)
+                                .required(true),
+                        ]),
+                ),
         );
 
     let matches = app.get_matches();
@@ -1183,6 +1255,105 @@ fn run() -> Result<(), CliError> {
             }
             _ => Err(CliError::InvalidSubcommand),
         },
+        ("receipt", Some(matches)) => match matches.subcommand() {
+            ("list", Some(matches)) => {
+                let url = matches
+                    .value_of("url")
+                    .map(ToOwned::to_owned)
+                    .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+                    .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+
+                let signer = load_signer(matches.value_of("key"))?;
+
+                let client = ReqwestScabbardClientBuilder::new()
+                    .with_url(&url)
+                    .with_auth(&create_cylinder_jwt_auth(signer)?)
+                    .build()?;
+
+                let full_service_id = matches
+                    .value_of("service-id")
+                    .ok_or_else(|| CliError::MissingArgument("service-id".into()))?;
+                let service_id = ServiceId::from_string(full_service_id)?;
+
+                let limit = matches
+                    .value_of("limit")
+                    .map(|limit| {
+                        limit.parse::<usize>().map_err(|_| {
+                            CliError::InvalidArgument(format!(
+                                "'limit' must be a non-negative integer: {}",
+                                limit
+                            ))
+                        })
+                    })
+                    .transpose()?;
+
+                let receipts =
+                    client.list_receipts(&service_id, matches.value_of("since"), limit)?;
+
+                let mut data = vec![vec![
+                    "TRANSACTION ID".to_string(),
+                    "RESULT".to_string(),
+                    "STATE CHANGES".to_string(),
+                    "EVENTS".to_string(),
+                ]];
+                receipts.iter().for_each(|receipt| {
+                    let (result, state_changes, events) = match receipt.result() {
+                        ReceiptResult::Valid {
+                            state_changes,
+                            events,
+                            ..
+                        } => ("valid", state_changes.len(), events.len()),
+                        ReceiptResult::Invalid { .. } => ("invalid", 0, 0),
+                    };
+                    data.push(vec![
+                        receipt.transaction_id().to_string(),
+                        result.to_string(),
+                        state_changes.to_string(),
+                        events.to_string(),
+                    ]);
+                });
+                print_table(data);
+
+                Ok(())
+            }
+            ("show", Some(matches)) => {
+                let url = matches
+                    .value_of("url")
+                    .map(ToOwned::to_owned)
+                    .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+                    .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+
+                let signer = load_signer(matches.value_of("key"))?;
+
+                let client = ReqwestScabbardClientBuilder::new()
+                    .with_url(&url)
+                    .with_auth(&create_cylinder_jwt_auth(signer)?)
+                    .build()?;
+
+                let full_service_id = matches
+                    .value_of("service-id")
+                    .ok_or_else(|| CliError::MissingArgument("service-id".into()))?;
+                let service_id = ServiceId::from_string(full_service_id)?;
+
+                let transaction_id = matches
+                    .value_of("transaction-id")
+                    .ok_or_else(|| CliError::MissingArgument("transaction-id".into()))?;
+
+                let receipt = client
+                    .get_receipt(&service_id, transaction_id)?
+                    .ok_or_else(|| {
+                        CliError::action_error(&format!(
+                            "receipt not found for transaction {}",
+                            transaction_id
+                        ))
+                    })?;
+
+                print_receipt(&receipt);
+
+                Ok(())
+            }
+            _ => Err(CliError::InvalidSubcommand),
+        },
         _ => Err(CliError::InvalidSubcommand),
     }
 }
@@ -1266,6 +1437,55 @@ fn print_table(table: Vec<Vec<String>>) {
     }
 }
 
+/// Prints the details of a transaction receipt.
+fn print_receipt(receipt: &Receipt) {
+    println!("Transaction {}", receipt.transaction_id());
+    match receipt.result() {
+        ReceiptResult::Valid {
+            state_changes,
+            events,
+            data,
+        } => {
+            println!("Result: valid");
+            println!("State changes:");
+            for change in state_changes {
+                match change {
+                    ReceiptStateChange::Set { address, value } => {
+                        println!("    set {} ({} bytes)", address, value.len())
+                    }
+                    ReceiptStateChange::Delete { address } => println!("    delete {}", address),
+                }
+            }
+            println!("Events:");
+            for event in events {
+                println!("    {}", event.event_type());
+                for (key, value) in event.attributes() {
+                    println!("        {}: {}", key, value);
+                }
+                if !event.data().is_empty() {
+                    println!("        data: {}", to_hex(event.data()));
+                }
+            }
+            if !data.is_empty() {
+                println!("Data:");
+                for entry in data {
+                    println!("    {}", to_hex(entry));
+                }
+            }
+        }
+        ReceiptResult::Invalid {
+            error_message,
+            error_data,
+        } => {
+            println!("Result: invalid");
+            println!("Error: {}", error_message);
+            if !error_data.is_empty() {
+                println!("Error data: {}", to_hex(error_data));
+            }
+        }
+    }
+}
+
 fn to_hex(bytes: &[u8]) -> String {
     bytes
         .iter()
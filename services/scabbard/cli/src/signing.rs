@@ -12,29 +12,70 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::{env, path::Path};
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
 
+#[cfg(not(feature = "encrypted-keys"))]
+use cylinder::load_key_from_path;
 use cylinder::{
-    current_user_key_name, current_user_search_path, jwt::JsonWebTokenBuilder, load_key,
-    load_key_from_path, secp256k1::Secp256k1Context, Context, PrivateKey, Signer,
+    current_user_key_name, current_user_search_path, jwt::JsonWebTokenBuilder,
+    secp256k1::Secp256k1Context, Context, PrivateKey, Signer,
 };
+#[cfg(feature = "encrypted-keys")]
+use splinter::keys::{encrypted, EncryptedKeyError};
 
 use crate::error::CliError;
 
+// Returns the path of the first private key file with the given name in the search path.
+fn find_key(key_name: &str, paths: &[PathBuf]) -> Option<PathBuf> {
+    paths
+        .iter()
+        .map(|path| path.join(format!("{}.priv", key_name)))
+        .find(|path| path.is_file())
+}
+
+#[cfg(feature = "encrypted-keys")]
+fn load_key_file(path: &Path) -> Result<PrivateKey, CliError> {
+    let passphrase = encrypted::load_key_passphrase(None).map_err(|err| {
+        CliError::action_error_with_source("Unable to load passphrase", Box::new(err))
+    })?;
+
+    encrypted::load_private_key(path, passphrase.as_deref()).map_err(|err| match err {
+        EncryptedKeyError::PassphraseRequired => CliError::ActionError {
+            context: format!(
+                "Private key file '{}' is encrypted; set {} or {} to decrypt it",
+                path.display(),
+                encrypted::KEY_PASSPHRASE_ENV,
+                encrypted::KEY_PASSPHRASE_FILE_ENV
+            ),
+            source: None,
+        },
+        err => CliError::ActionError {
+            context: format!("Unable to load private key file '{}'", path.display()),
+            source: Some(Box::new(err)),
+        },
+    })
+}
+
+#[cfg(not(feature = "encrypted-keys"))]
+fn load_key_file(path: &Path) -> Result<PrivateKey, CliError> {
+    load_key_from_path(path).map_err(|err| CliError::ActionError {
+        context: err.to_string(),
+        source: None,
+    })
+}
+
 fn load_private_key(key_name: Option<&str>) -> Result<PrivateKey, CliError> {
     let private_key = if let Some(key_name) = key_name {
         if key_name.contains('/') {
-            load_key_from_path(Path::new(key_name)).map_err(|err| CliError::ActionError {
-                context: err.to_string(),
-                source: None,
-            })?
+            load_key_file(Path::new(key_name))?
         } else {
             let path = &current_user_search_path();
-            load_key(key_name, path)
-                .map_err(|err| CliError::ActionError {
-                    context: err.to_string(),
-                    source: None,
-                })?
+            find_key(key_name, path)
+                .map(|key_path| load_key_file(&key_path))
+                .transpose()?
                 .ok_or_else(|| CliError::ActionError {
                     context: format!(
                         "No signing key found in {}. Either specify the --key argument or \
@@ -64,11 +105,9 @@ fn load_private_key(key_name: Option<&str>) -> Result<PrivateKey, CliError> {
                 paths
             }
         };
-        load_key(&current_user_key_name(), &path)
-            .map_err(|err| CliError::ActionError {
-                context: err.to_string(),
-                source: None,
-            })?
+        find_key(&current_user_key_name(), &path)
+            .map(|key_path| load_key_file(&key_path))
+            .transpose()?
             .ok_or_else(|| CliError::ActionError {
                 context: format!(
                     "No signing key found in {}. Either specify the --key argument or \
@@ -114,6 +114,74 @@ impl StateEntry {
     }
 }
 
+/// The outcome of executing a committed transaction, as recorded by a scabbard service.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Receipt {
+    transaction_id: String,
+    result: ReceiptResult,
+}
+
+impl Receipt {
+    /// Get the ID of the transaction.
+    pub fn transaction_id(&self) -> &str {
+        &self.transaction_id
+    }
+
+    /// Get the result of executing the transaction.
+    pub fn result(&self) -> &ReceiptResult {
+        &self.result
+    }
+}
+
+/// The result of executing a transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReceiptResult {
+    /// The transaction was valid; contains the state changes it made and the events and data it
+    /// emitted.
+    Valid {
+        state_changes: Vec<ReceiptStateChange>,
+        events: Vec<ReceiptEvent>,
+        data: Vec<Vec<u8>>,
+    },
+    /// The transaction was invalid; contains the error reported by the transaction handler.
+    Invalid {
+        error_message: String,
+        error_data: Vec<u8>,
+    },
+}
+
+/// A change made to a scabbard service's state by a transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReceiptStateChange {
+    Set { address: String, value: Vec<u8> },
+    Delete { address: String },
+}
+
+/// An event emitted by a transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReceiptEvent {
+    event_type: String,
+    attributes: Vec<(String, String)>,
+    data: Vec<u8>,
+}
+
+impl ReceiptEvent {
+    /// Get the type of the event.
+    pub fn event_type(&self) -> &str {
+        &self.event_type
+    }
+
+    /// Get the attributes of the event.
+    pub fn attributes(&self) -> &[(String, String)] {
+        &self.attributes
+    }
+
+    /// Get the data of the event.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
 pub trait ScabbardClient {
     /// Submit the given `batches` to the scabbard service with the given `service_id`. If a `wait`
     /// time is specified, wait the given amount of time for the batches to commit.
@@ -170,6 +238,39 @@ pub trait ScabbardClient {
     /// * An internal error based on the underlying implementation
     fn get_current_state_root(&self, service_id: &ServiceId)
         -> Result<String, ScabbardClientError>;
+
+    /// Get the receipt of the transaction with the given `transaction_id` from the scabbard
+    /// instance with the given `service_id`. Returns `None` if the transaction has not been
+    /// committed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error in any of the following cases:
+    /// * An internal server error occurred in the scabbard service
+    /// * An internal error based on the underlying implementation
+    fn get_receipt(
+        &self,
+        service_id: &ServiceId,
+        transaction_id: &str,
+    ) -> Result<Option<Receipt>, ScabbardClientError>;
+
+    /// List the receipts of the transactions committed by the scabbard instance with the given
+    /// `service_id`, in commit order. If `since` is provided, only the receipts of transactions
+    /// committed after that transaction are listed. At most `limit` receipts are returned; if no
+    /// limit is provided, the service's default limit is used.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error in any of the following cases:
+    /// * The `since` transaction has not been committed
+    /// * An internal server error occurred in the scabbard service
+    /// * An internal error based on the underlying implementation
+    fn list_receipts(
+        &self,
+        service_id: &ServiceId,
+        since: Option<&str>,
+        limit: Option<usize>,
+    ) -> Result<Vec<Receipt>, ScabbardClientError>;
 }
 
 #[cfg(test)]
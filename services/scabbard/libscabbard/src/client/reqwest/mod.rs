@@ -30,7 +30,7 @@ use crate::protocol::SCABBARD_PROTOCOL_VERSION;
 
 use super::error::ScabbardClientError;
 use super::ScabbardClient;
-use super::{ServiceId, StateEntry};
+use super::{Receipt, ReceiptEvent, ReceiptResult, ReceiptStateChange, ServiceId, StateEntry};
 
 pub use builder::ReqwestScabbardClientBuilder;
 
@@ -253,6 +253,114 @@ impl ScabbardClient for ReqwestScabbardClient {
             )))
         }
     }
+
+    /// Get the receipt of the transaction with the given `transaction_id` from the scabbard
+    /// instance with the given `service_id`. Returns `None` if the transaction has not been
+    /// committed.
+    fn get_receipt(
+        &self,
+        service_id: &ServiceId,
+        transaction_id: &str,
+    ) -> Result<Option<Receipt>, ScabbardClientError> {
+        let url = Url::parse(&format!(
+            "{}/scabbard/{}/{}/receipts/{}",
+            &self.url,
+            service_id.circuit(),
+            service_id.service_id(),
+            transaction_id
+        ))
+        .map_err(|err| ScabbardClientError::new_with_source("invalid URL", err.into()))?;
+
+        let response = Client::new()
+            .get(url)
+            .header("SplinterProtocolVersion", SCABBARD_PROTOCOL_VERSION)
+            .header("Authorization", &self.auth)
+            .send()
+            .map_err(|err| ScabbardClientError::new_with_source("request failed", err.into()))?;
+
+        if response.status().is_success() {
+            response
+                .json::<JsonReceipt>()
+                .map(|receipt| Some(Receipt::from(receipt)))
+                .map_err(|err| {
+                    ScabbardClientError::new_with_source(
+                        "failed to deserialize response body",
+                        err.into(),
+                    )
+                })
+        } else if response.status().as_u16() == 404 {
+            Ok(None)
+        } else {
+            let status = response.status();
+            let msg: ErrorResponse = response.json().map_err(|err| {
+                ScabbardClientError::new_with_source(
+                    "failed to deserialize error response body",
+                    err.into(),
+                )
+            })?;
+            Err(ScabbardClientError::new(&format!(
+                "failed to get receipt: {}: {}",
+                status, msg
+            )))
+        }
+    }
+
+    /// List the receipts of the transactions committed by the scabbard instance with the given
+    /// `service_id`, optionally since the given transaction.
+    fn list_receipts(
+        &self,
+        service_id: &ServiceId,
+        since: Option<&str>,
+        limit: Option<usize>,
+    ) -> Result<Vec<Receipt>, ScabbardClientError> {
+        let mut url = Url::parse(&format!(
+            "{}/scabbard/{}/{}/receipts",
+            &self.url,
+            service_id.circuit(),
+            service_id.service_id()
+        ))
+        .map_err(|err| ScabbardClientError::new_with_source("invalid URL", err.into()))?;
+        {
+            let mut query = url.query_pairs_mut();
+            if let Some(since) = since {
+                query.append_pair("since", since);
+            }
+            if let Some(limit) = limit {
+                query.append_pair("limit", &limit.to_string());
+            }
+        }
+
+        let response = Client::new()
+            .get(url)
+            .header("SplinterProtocolVersion", SCABBARD_PROTOCOL_VERSION)
+            .header("Authorization", &self.auth)
+            .send()
+            .map_err(|err| ScabbardClientError::new_with_source("request failed", err.into()))?;
+
+        if response.status().is_success() {
+            response
+                .json::<Vec<JsonReceipt>>()
+                .map(|receipts| receipts.into_iter().map(Receipt::from).collect())
+                .map_err(|err| {
+                    ScabbardClientError::new_with_source(
+                        "failed to deserialize response body",
+                        err.into(),
+                    )
+                })
+        } else {
+            let status = response.status();
+            let msg: ErrorResponse = response.json().map_err(|err| {
+                ScabbardClientError::new_with_source(
+                    "failed to deserialize error response body",
+                    err.into(),
+                )
+            })?;
+            Err(ScabbardClientError::new(&format!(
+                "failed to list receipts: {}: {}",
+                status, msg
+            )))
+        }
+    }
 }
 
 /// Using the given `base_url` and `batch_link` to check batch statuses, `wait` the given duration
@@ -376,6 +484,85 @@ impl From<JsonStateEntry> for StateEntry {
     }
 }
 
+/// Used for deserializing transaction receipts provided by the Scabbard REST API.
+#[derive(Deserialize)]
+struct JsonReceipt {
+    transaction_id: String,
+    result: JsonReceiptResult,
+}
+
+#[derive(Deserialize)]
+enum JsonReceiptResult {
+    Valid {
+        state_changes: Vec<JsonStateChange>,
+        events: Vec<JsonReceiptEvent>,
+        data: Vec<Vec<u8>>,
+    },
+    Invalid {
+        error_message: String,
+        error_data: Vec<u8>,
+    },
+}
+
+#[derive(Deserialize)]
+enum JsonStateChange {
+    Set { key: String, value: Vec<u8> },
+    Delete { key: String },
+}
+
+#[derive(Deserialize)]
+struct JsonReceiptEvent {
+    event_type: String,
+    attributes: Vec<(String, String)>,
+    data: Vec<u8>,
+}
+
+impl From<JsonReceipt> for Receipt {
+    fn from(json: JsonReceipt) -> Self {
+        let result = match json.result {
+            JsonReceiptResult::Valid {
+                state_changes,
+                events,
+                data,
+            } => ReceiptResult::Valid {
+                state_changes: state_changes
+                    .into_iter()
+                    .map(|change| match change {
+                        JsonStateChange::Set { key, value } => ReceiptStateChange::Set {
+                            address: key,
+                            value,
+                        },
+                        JsonStateChange::Delete { key } => {
+                            ReceiptStateChange::Delete { address: key }
+                        }
+                    })
+                    .collect(),
+                events: events
+                    .into_iter()
+                    .map(|event| ReceiptEvent {
+                        event_type: event.event_type,
+                        attributes: event.attributes,
+                        data: event.data,
+                    })
+                    .collect(),
+                data,
+            },
+            JsonReceiptResult::Invalid {
+                error_message,
+                error_data,
+            } => ReceiptResult::Invalid {
+                error_message,
+                error_data,
+            },
+        };
+
+        Self {
+            transaction_id: json.transaction_id,
+            result,
+        }
+    }
+}
+
 /// Used for deserializing the batch link provided by the Scabbard REST API.
 #[derive(Debug, Serialize, Deserialize)]
 struct Link {
@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use std::error::Error;
-use std::fmt;
+use std::fmt::{self, Write};
 
 pub fn parse_hex(hex: &str) -> Result<Vec<u8>, HexError> {
     if hex.len() % 2 != 0 {
@@ -36,6 +36,15 @@ pub fn parse_hex(hex: &str) -> Result<Vec<u8>, HexError> {
     Ok(res)
 }
 
+pub fn to_hex(bytes: &[u8]) -> String {
+    let mut buf = String::new();
+    for b in bytes {
+        write!(&mut buf, "{:02x}", b).expect("Unable to write to string");
+    }
+
+    buf
+}
+
 #[derive(Debug)]
 pub struct HexError {
     context: String,
@@ -85,6 +94,12 @@ mod tests {
         // check that invalid digits fails
         assert!(parse_hex("0G").is_err());
 
+        // check round trip
+        assert_eq!(
+            "0163ff",
+            to_hex(&parse_hex("0163ff").expect("unable to parse 0163ff"))
+        );
+
         // check empty parses
         let empty: Vec<u8> = Vec::with_capacity(0);
         assert_eq!(empty, parse_hex("").expect("unable to parse empty"));
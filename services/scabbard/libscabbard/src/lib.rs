@@ -45,4 +45,5 @@ pub mod protocol;
 pub mod protos;
 #[cfg(feature = "splinter-service")]
 pub mod service;
+pub mod snapshot;
 pub mod store;
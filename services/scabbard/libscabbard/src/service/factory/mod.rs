@@ -53,7 +53,10 @@ use crate::service::ScabbardStatePurgeHandler;
 #[cfg(any(feature = "postgres", feature = "sqlite"))]
 use crate::service::{
     error::ScabbardError,
-    state::merkle_state::{self, MerkleState, MerkleStateConfig},
+    state::{
+        merkle_state::{self, MerkleState, MerkleStateConfig},
+        DEFAULT_RETAINED_STATE_ROOTS,
+    },
     Scabbard, ScabbardVersion, SERVICE_TYPE,
 };
 #[cfg(feature = "diesel")]
@@ -161,6 +164,7 @@ pub struct ScabbardFactoryBuilder {
     storage_configuration: Option<ScabbardStorageConfiguration>,
     signature_verifier_factory: Option<Arc<Mutex<Box<dyn VerifierFactory>>>>,
     enable_state_autocleanup: Option<bool>,
+    retained_state_roots: Option<usize>,
 }
 
 impl ScabbardFactoryBuilder {
@@ -227,6 +231,13 @@ impl ScabbardFactoryBuilder {
         self
     }
 
+    /// Sets the number of committed state roots that services retain; older roots are pruned in
+    /// the background. Defaults to 1, which only retains the current state root.
+    pub fn with_retained_state_roots(mut self, retained_state_roots: usize) -> Self {
+        self.retained_state_roots = Some(retained_state_roots);
+        self
+    }
+
     pub fn with_storage_configuration(
         mut self,
         storage_configuration: ScabbardStorageConfiguration,
@@ -315,6 +326,15 @@ impl ScabbardFactoryBuilder {
 
         let state_autocleanup_enabled = self.enable_state_autocleanup.unwrap_or_default();
 
+        let retained_state_roots = self
+            .retained_state_roots
+            .unwrap_or(DEFAULT_RETAINED_STATE_ROOTS);
+        if retained_state_roots == 0 {
+            return Err(InvalidStateError::with_message(
+                "At least one state root must be retained".into(),
+            ));
+        }
+
         Ok(ScabbardFactory {
             service_types: vec![SERVICE_TYPE.into()],
             #[cfg(feature = "lmdb")]
@@ -322,6 +342,7 @@ impl ScabbardFactoryBuilder {
             #[cfg(feature = "lmdb")]
             enable_lmdb_state: state_storage_configuration.enable_lmdb,
             state_autocleanup_enabled,
+            retained_state_roots,
             store_factory_config,
             signature_verifier_factory,
         })
@@ -493,6 +514,8 @@ pub struct ScabbardFactory {
     signature_verifier_factory: Arc<Mutex<Box<dyn VerifierFactory>>>,
     #[cfg(any(feature = "postgres", feature = "sqlite"))]
     state_autocleanup_enabled: bool,
+    #[cfg(any(feature = "postgres", feature = "sqlite"))]
+    retained_state_roots: usize,
 }
 
 pub struct ScabbardArgValidator;
@@ -766,6 +789,10 @@ impl ScabbardFactory {
             admin_keys,
             coordinator_timeout,
         )
+        .and_then(|scabbard| {
+            scabbard.set_retained_state_roots(self.retained_state_roots)?;
+            Ok(scabbard)
+        })
         .map_err(|err| FactoryCreateError::CreationFailed(Box::new(err)))
     }
 
@@ -1124,6 +1151,7 @@ mod tests {
             ),
             enable_lmdb_state: false,
             state_autocleanup_enabled: false,
+            retained_state_roots: DEFAULT_RETAINED_STATE_ROOTS,
             store_factory_config,
             signature_verifier_factory: Arc::new(Mutex::new(Box::new(Secp256k1Context::new()))),
         }
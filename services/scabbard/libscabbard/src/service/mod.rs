@@ -46,6 +46,7 @@ use transact::{
     protos::{FromBytes, IntoBytes},
 };
 
+use crate::snapshot::StateSnapshot;
 use crate::store::CommitHashStore;
 
 use super::protos::scabbard::{ScabbardMessage, ScabbardMessage_Type};
@@ -60,8 +61,9 @@ use shared::ScabbardShared;
 use state::merkle_state::MerkleState;
 use state::ScabbardState;
 pub use state::{
-    BatchInfo, BatchInfoIter, BatchStatus, Events, InvalidTransaction, StateChange,
-    StateChangeEvent, StateIter, StateSubscriber, ValidTransaction,
+    BatchInfo, BatchInfoIter, BatchStatus, Events, InvalidTransaction, Receipt, ReceiptEvent,
+    ReceiptResult, StateChange, StateChangeEvent, StateChangeFilter, StateChangeKind, StateIter,
+    StateSubscriber, ValidTransaction,
 };
 
 pub const SERVICE_TYPE: &str = "scabbard";
@@ -200,6 +202,25 @@ impl Scabbard {
             .to_string())
     }
 
+    /// Set the number of committed state roots the service retains; older roots are pruned in the
+    /// background as new roots are committed.
+    pub fn set_retained_state_roots(&self, retained_roots: usize) -> Result<(), ScabbardError> {
+        Ok(self
+            .state
+            .lock()
+            .map_err(|_| ScabbardError::LockPoisoned)?
+            .set_retained_state_roots(retained_roots)?)
+    }
+
+    /// Take a snapshot of the service's state at the current state root.
+    pub fn export_state_snapshot(&self) -> Result<StateSnapshot, ScabbardError> {
+        Ok(self
+            .state
+            .lock()
+            .map_err(|_| ScabbardError::LockPoisoned)?
+            .export_snapshot()?)
+    }
+
     /// Get whether the service is currently accepting batches
     pub fn accepting_batches(&self) -> Result<bool, ScabbardError> {
         let shared = self
@@ -310,6 +331,57 @@ impl Scabbard {
 
         Ok(())
     }
+
+    /// Returns the receipt of the transaction with the given ID, if it has been committed.
+    pub fn get_receipt(&self, transaction_id: &str) -> Result<Option<Receipt>, ScabbardError> {
+        Ok(self
+            .state
+            .lock()
+            .map_err(|_| ScabbardError::LockPoisoned)?
+            .get_receipt(transaction_id)?)
+    }
+
+    /// Returns up to `limit` receipts of the transactions committed after the transaction with the
+    /// given ID. Returns `None` if there is no receipt for the given ID.
+    pub fn list_receipts_since(
+        &self,
+        transaction_id: Option<String>,
+        limit: usize,
+    ) -> Result<Option<Vec<Receipt>>, ScabbardError> {
+        Ok(self
+            .state
+            .lock()
+            .map_err(|_| ScabbardError::LockPoisoned)?
+            .list_receipts_since(transaction_id, limit)?)
+    }
+
+    /// Returns the events since the given event, with only the state changes that match the
+    /// filter.
+    pub fn get_filtered_events_since(
+        &self,
+        event_id: Option<String>,
+        filter: StateChangeFilter,
+    ) -> Result<Events, ScabbardError> {
+        Ok(self
+            .state
+            .lock()
+            .map_err(|_| ScabbardError::LockPoisoned)?
+            .get_filtered_events_since(event_id, filter)?)
+    }
+
+    /// Adds a subscriber that only receives the state changes that match the filter.
+    pub fn add_filtered_state_subscriber(
+        &self,
+        subscriber: Box<dyn StateSubscriber>,
+        filter: StateChangeFilter,
+    ) -> Result<(), ScabbardError> {
+        self.state
+            .lock()
+            .map_err(|_| ScabbardError::LockPoisoned)?
+            .add_filtered_subscriber(subscriber, filter);
+
+        Ok(())
+    }
 }
 
 impl ServiceInstance for Scabbard {
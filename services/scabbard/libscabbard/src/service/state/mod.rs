@@ -13,6 +13,7 @@
 // limitations under the License.
 
 pub mod merkle_state;
+mod pruning;
 
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::convert::TryFrom;
@@ -47,12 +48,16 @@ use transact::{
     scheduler::{serial::SerialScheduler, BatchExecutionResult, Scheduler},
     state::{
         merkle::{MerkleRadixLeafReadError, MerkleRadixLeafReader},
-        Prune, Read, StateChange as TransactStateChange, Write,
+        Read, StateChange as TransactStateChange, Write,
     },
 };
 
 use crate::protos::scabbard::{Setting, Setting_Entry};
 use crate::service::error::{ScabbardStateError, StateSubscriberError};
+use crate::snapshot::StateSnapshot;
+
+use self::pruning::StatePruner;
+pub use self::pruning::DEFAULT_RETAINED_STATE_ROOTS;
 use crate::store::CommitHashStore;
 
 const EXECUTION_TIMEOUT: u64 = 300; // five minutes
@@ -65,14 +70,14 @@ pub type StateIter = Box<dyn Iterator<Item = Result<(String, Vec<u8>), ScabbardS
 
 pub struct ScabbardState {
     merkle_state: merkle_state::MerkleState,
-    state_autocleanup_enabled: bool,
+    pruner: StatePruner,
     commit_hash_store: Arc<dyn CommitHashStore + Sync + Send>,
     context_manager: ContextManager,
     executor: Option<Executor>,
     current_state_root: String,
     receipt_store: Arc<dyn ReceiptStore>,
     pending_changes: Option<(String, Vec<TransactionReceipt>)>,
-    event_subscribers: Vec<Box<dyn StateSubscriber>>,
+    event_subscribers: Vec<(StateChangeFilter, Box<dyn StateSubscriber>)>,
     #[cfg(feature = "metrics")]
     service_id: String,
     #[cfg(feature = "metrics")]
@@ -131,6 +136,13 @@ impl ScabbardState {
             new_state_root
         };
 
+        let pruner = StatePruner::start(
+            merkle_state.clone(),
+            commit_hash_store.clone(),
+            current_state_root.clone(),
+            state_autocleanup_enabled,
+        )?;
+
         // Initialize transact
         let context_manager = ContextManager::new(Box::new(merkle_state.clone()));
         // initialize committed_batches metric
@@ -141,7 +153,7 @@ impl ScabbardState {
 
         Ok(ScabbardState {
             merkle_state,
-            state_autocleanup_enabled,
+            pruner,
             commit_hash_store,
             context_manager,
             executor: None,
@@ -220,11 +232,39 @@ impl ScabbardState {
         ))
     }
 
+    /// Set the number of committed state roots to retain; older roots, including those retained
+    /// before a restart, are pruned in the background. At least one root must be retained.
+    pub fn set_retained_state_roots(
+        &mut self,
+        retained_roots: usize,
+    ) -> Result<(), ScabbardStateError> {
+        self.pruner.set_retained_roots(retained_roots)
+    }
+
     /// Get the current state root hash.
     pub fn current_state_root(&self) -> &str {
         &self.current_state_root
     }
 
+    /// Take a snapshot of every entry in state at the current state root.
+    pub fn export_snapshot(&self) -> Result<StateSnapshot, ScabbardStateError> {
+        let commit_hash = self
+            .commit_hash_store
+            .get_current_commit_hash()
+            .map_err(|err| ScabbardStateError(err.to_string()))?
+            .ok_or_else(|| ScabbardStateError("no commit hash has been recorded".into()))?;
+
+        let leaves = self
+            .get_state_with_prefix(None)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(StateSnapshot::new(
+            self.current_state_root.clone(),
+            commit_hash,
+            leaves,
+        ))
+    }
+
     pub fn prepare_change(&mut self, batch: BatchPair) -> Result<String, ScabbardStateError> {
         let executor = self.executor.as_ref().ok_or_else(|| {
             ScabbardStateError("attempting to prepare a change on a stopped service".into())
@@ -296,7 +336,6 @@ impl ScabbardState {
             Some((signature, txn_receipts)) => {
                 let state_changes = receipts_into_transact_state_changes(&txn_receipts)?;
 
-                let previous_state_root = self.current_state_root.clone();
                 self.current_state_root = self
                     .merkle_state
                     .commit(&self.current_state_root, &state_changes)?;
@@ -325,8 +364,12 @@ impl ScabbardState {
                     })?;
 
                 for event in events {
-                    self.event_subscribers.retain(|subscriber| {
-                        match subscriber.handle_event(event.clone()) {
+                    self.event_subscribers.retain(|(filter, subscriber)| {
+                        let event = match filter.apply(event.clone()) {
+                            Some(event) => event,
+                            None => return true,
+                        };
+                        match subscriber.handle_event(event) {
                             Ok(()) => true,
                             Err(StateSubscriberError::Unsubscribe) => false,
                             Err(err @ StateSubscriberError::UnableToHandleEvent(_)) => {
@@ -343,25 +386,8 @@ impl ScabbardState {
                     "service" => format!("{}::{}", &self.circuit_id, &self.service_id)
                 );
 
-                if previous_state_root != self.current_state_root {
-                    self.merkle_state
-                        .prune(vec![previous_state_root.clone()])
-                        .map_err(|err| {
-                            ScabbardStateError(format!(
-                                "failed to prune previous state {}: {}",
-                                previous_state_root, err
-                            ))
-                        })?;
-
-                    if self.state_autocleanup_enabled {
-                        if let Err(err) = self.merkle_state.remove_pruned_entries() {
-                            error!(
-                                "failed to cleanup pruned state for root {}: {}",
-                                previous_state_root, err
-                            )
-                        }
-                    }
-                }
+                self.pruner
+                    .add_committed_root(self.current_state_root.clone())?;
 
                 Ok(())
             }
@@ -389,8 +415,73 @@ impl ScabbardState {
         Events::new(self.receipt_store.clone(), event_id)
     }
 
+    /// Returns the events since the given event, with only the state changes that match the
+    /// filter. Events without any matching state changes are skipped.
+    pub fn get_filtered_events_since(
+        &self,
+        event_id: Option<String>,
+        filter: StateChangeFilter,
+    ) -> Result<Events, ScabbardStateError> {
+        Events::new(self.receipt_store.clone(), event_id).map(|events| events.with_filter(filter))
+    }
+
+    /// Returns the receipt of the transaction with the given ID, if it has been committed.
+    pub fn get_receipt(&self, transaction_id: &str) -> Result<Option<Receipt>, ScabbardStateError> {
+        self.receipt_store
+            .get_txn_receipt_by_id(transaction_id.into())
+            .map(|receipt| receipt.map(Receipt::from))
+            .map_err(|err| {
+                ScabbardStateError(format!(
+                    "failed to get transaction receipt from store: {}",
+                    err
+                ))
+            })
+    }
+
+    /// Returns up to `limit` receipts of the transactions committed after the transaction with the
+    /// given ID, in commit order. If no ID is given, the receipts are listed from the first
+    /// committed transaction. Returns `None` if there is no receipt for the given ID.
+    pub fn list_receipts_since(
+        &self,
+        transaction_id: Option<String>,
+        limit: usize,
+    ) -> Result<Option<Vec<Receipt>>, ScabbardStateError> {
+        if let Some(id) = transaction_id.as_ref() {
+            if self.get_receipt(id)?.is_none() {
+                return Ok(None);
+            }
+        }
+
+        self.receipt_store
+            .list_receipts_since(transaction_id)
+            .map_err(|err| {
+                ScabbardStateError(format!(
+                    "failed to list transaction receipts from store: {}",
+                    err
+                ))
+            })?
+            .take(limit)
+            .map(|res| {
+                res.map(Receipt::from).map_err(|err| {
+                    ScabbardStateError(format!("failed to get transaction receipt: {}", err))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(Some)
+    }
+
     pub fn add_subscriber(&mut self, subscriber: Box<dyn StateSubscriber>) {
-        self.event_subscribers.push(subscriber);
+        self.add_filtered_subscriber(subscriber, StateChangeFilter::default());
+    }
+
+    /// Adds a subscriber that only receives the state changes that match the filter. The
+    /// subscriber is not notified of events without any matching state changes.
+    pub fn add_filtered_subscriber(
+        &mut self,
+        subscriber: Box<dyn StateSubscriber>,
+        filter: StateChangeFilter,
+    ) {
+        self.event_subscribers.push((filter, subscriber));
     }
 
     pub fn clear_subscribers(&mut self) {
@@ -484,6 +575,161 @@ impl From<transact::protocol::receipt::StateChange> for StateChange {
     }
 }
 
+/// The outcome of executing a committed transaction.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Receipt {
+    pub transaction_id: String,
+    pub result: ReceiptResult,
+}
+
+/// The result recorded in a `Receipt`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum ReceiptResult {
+    /// The transaction was valid; contains the state changes it made and the events and data it
+    /// emitted.
+    Valid {
+        state_changes: Vec<StateChange>,
+        events: Vec<ReceiptEvent>,
+        data: Vec<Vec<u8>>,
+    },
+    /// The transaction was invalid; contains the error reported by the transaction handler.
+    Invalid {
+        error_message: String,
+        error_data: Vec<u8>,
+    },
+}
+
+/// An event emitted by a transaction.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReceiptEvent {
+    pub event_type: String,
+    pub attributes: Vec<(String, String)>,
+    pub data: Vec<u8>,
+}
+
+impl From<TransactionReceipt> for Receipt {
+    fn from(receipt: TransactionReceipt) -> Self {
+        let TransactionReceipt {
+            transaction_id,
+            transaction_result,
+        } = receipt;
+
+        let result = match transaction_result {
+            TransactionResult::Valid {
+                state_changes,
+                events,
+                data,
+            } => ReceiptResult::Valid {
+                state_changes: state_changes.into_iter().map(StateChange::from).collect(),
+                events: events
+                    .into_iter()
+                    .map(|event| ReceiptEvent {
+                        event_type: event.event_type,
+                        attributes: event.attributes,
+                        data: event.data,
+                    })
+                    .collect(),
+                data,
+            },
+            TransactionResult::Invalid {
+                error_message,
+                error_data,
+            } => ReceiptResult::Invalid {
+                error_message,
+                error_data,
+            },
+        };
+
+        Receipt {
+            transaction_id,
+            result,
+        }
+    }
+}
+
+impl StateChange {
+    /// Returns the address that is changed.
+    pub fn key(&self) -> &str {
+        match self {
+            StateChange::Set { key, .. } | StateChange::Delete { key } => key,
+        }
+    }
+
+    /// Returns the kind of the change.
+    pub fn kind(&self) -> StateChangeKind {
+        match self {
+            StateChange::Set { .. } => StateChangeKind::Set,
+            StateChange::Delete { .. } => StateChangeKind::Delete,
+        }
+    }
+}
+
+/// The kinds of `StateChange`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StateChangeKind {
+    Set,
+    Delete,
+}
+
+/// Selects the state changes of a `StateChangeEvent` that are delivered to a subscriber.
+///
+/// A state change matches the filter if its address starts with any of the filter's address
+/// prefixes and it is of the filter's kind. A filter without any address prefixes matches all
+/// addresses, and a filter without a kind matches both sets and deletes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StateChangeFilter {
+    address_prefixes: Vec<String>,
+    kind: Option<StateChangeKind>,
+}
+
+impl StateChangeFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only match state changes with an address that starts with one of the given prefixes.
+    pub fn with_address_prefixes(mut self, address_prefixes: Vec<String>) -> Self {
+        self.address_prefixes = address_prefixes;
+        self
+    }
+
+    /// Only match state changes of the given kind.
+    pub fn with_kind(mut self, kind: StateChangeKind) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    /// Returns `true` if the filter matches every state change.
+    pub fn is_empty(&self) -> bool {
+        self.address_prefixes.is_empty() && self.kind.is_none()
+    }
+
+    /// Returns `true` if the state change matches the filter.
+    pub fn matches(&self, change: &StateChange) -> bool {
+        self.kind.map(|kind| kind == change.kind()).unwrap_or(true)
+            && (self.address_prefixes.is_empty()
+                || self
+                    .address_prefixes
+                    .iter()
+                    .any(|prefix| change.key().starts_with(prefix.as_str())))
+    }
+
+    /// Removes the state changes of the event that do not match the filter, returning `None` if
+    /// none of them match. An empty filter returns the event unchanged.
+    pub fn apply(&self, mut event: StateChangeEvent) -> Option<StateChangeEvent> {
+        if self.is_empty() {
+            return Some(event);
+        }
+
+        event.state_changes.retain(|change| self.matches(change));
+        if event.state_changes.is_empty() {
+            None
+        } else {
+            Some(event)
+        }
+    }
+}
+
 pub trait StateSubscriber: Send {
     fn handle_event(&self, event: StateChangeEvent) -> Result<(), StateSubscriberError>;
 }
@@ -500,6 +746,7 @@ pub struct Events {
     receipt_store: Arc<dyn ReceiptStore>,
     query: EventQuery,
     cache: VecDeque<StateChangeEvent>,
+    filter: StateChangeFilter,
 }
 
 impl Events {
@@ -511,11 +758,17 @@ impl Events {
             receipt_store,
             query: EventQuery::Fetch(start_id),
             cache: VecDeque::default(),
+            filter: StateChangeFilter::default(),
         };
         iter.reload_cache()?;
         Ok(iter)
     }
 
+    fn with_filter(mut self, filter: StateChangeFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
     fn reload_cache(&mut self) -> Result<(), ScabbardStateError> {
         match self.query {
             EventQuery::Fetch(ref start_id) => {
@@ -557,12 +810,24 @@ impl Iterator for Events {
     type Item = StateChangeEvent;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.cache.is_empty() && self.query != EventQuery::Exhausted {
-            if let Err(err) = self.reload_cache() {
-                error!("Unable to reload iterator cache: {}", err);
+        loop {
+            if self.cache.is_empty() && self.query != EventQuery::Exhausted {
+                if let Err(err) = self.reload_cache() {
+                    error!("Unable to reload iterator cache: {}", err);
+                    return None;
+                }
+            }
+            // The cache is only reloaded from the last event it contained, so events that are
+            // filtered out are skipped without affecting where the next reload starts
+            match self.cache.pop_front() {
+                Some(event) => {
+                    if let Some(event) = self.filter.apply(event) {
+                        return Some(event);
+                    }
+                }
+                None => return None,
             }
         }
-        self.cache.pop_front()
     }
 }
 
@@ -1169,6 +1434,212 @@ mod tests {
         assert_eq!(some_event_ids, receipt_ids[1..].to_vec());
     }
 
+    /// Verify that a state change filter matches changes by address prefix and kind, and that an
+    /// empty filter matches everything.
+    #[test]
+    fn state_change_filter() {
+        let set = StateChange::Set {
+            key: "abcd01".into(),
+            value: vec![1],
+        };
+        let delete = StateChange::Delete {
+            key: "ef0102".into(),
+        };
+
+        let filter = StateChangeFilter::new();
+        assert!(filter.is_empty());
+        assert!(filter.matches(&set));
+        assert!(filter.matches(&delete));
+
+        let filter = StateChangeFilter::new().with_address_prefixes(vec!["ab".into(), "12".into()]);
+        assert!(filter.matches(&set));
+        assert!(!filter.matches(&delete));
+
+        let filter = StateChangeFilter::new().with_kind(StateChangeKind::Delete);
+        assert!(!filter.matches(&set));
+        assert!(filter.matches(&delete));
+
+        let filter = StateChangeFilter::new()
+            .with_address_prefixes(vec!["ab".into()])
+            .with_kind(StateChangeKind::Delete);
+        assert!(!filter.matches(&set));
+        assert!(!filter.matches(&delete));
+
+        // Applying a filter removes the changes that don't match, or the whole event if none match
+        let event = StateChangeEvent {
+            id: "event".into(),
+            state_changes: vec![set, delete],
+        };
+        let filtered = StateChangeFilter::new()
+            .with_address_prefixes(vec!["ef".into()])
+            .apply(event.clone())
+            .expect("event should match");
+        assert_eq!(filtered.state_changes.len(), 1);
+        assert_eq!(filtered.state_changes[0].key(), "ef0102");
+        assert!(StateChangeFilter::new()
+            .with_address_prefixes(vec!["00".into()])
+            .apply(event)
+            .is_none());
+    }
+
+    /// Verify that the filtered event iterator skips events without matching state changes and
+    /// that resuming from a delivered event only returns the matching events after it.
+    #[test]
+    fn filtered_event_iterator() {
+        let receipts = vec![
+            mock_transaction_receipt_with_set("ab", "aa0001"),
+            mock_transaction_receipt_with_set("cd", "bb0001"),
+            mock_transaction_receipt_with_set("ef", "aa0002"),
+            mock_transaction_receipt_with_set("gh", "aa0003"),
+        ];
+
+        let pool = create_connection_pool_and_migrate(":memory:".to_string());
+
+        let receipt_store = Arc::new(DieselReceiptStore::new(
+            pool,
+            Some("filtered_event_iterator".into()),
+        ));
+
+        receipt_store
+            .add_txn_receipts(receipts)
+            .expect("failed to add receipts to store");
+
+        let filter = StateChangeFilter::new().with_address_prefixes(vec!["aa".into()]);
+
+        let event_ids = Events::new(receipt_store.clone(), None)
+            .expect("failed to get iterator for all events")
+            .with_filter(filter.clone())
+            .map(|event| event.id)
+            .collect::<Vec<_>>();
+        assert_eq!(event_ids, vec!["ab", "ef", "gh"]);
+
+        let event_ids = Events::new(receipt_store, Some("ab".into()))
+            .expect("failed to get iterator for some events")
+            .with_filter(filter)
+            .map(|event| event.id)
+            .collect::<Vec<_>>();
+        assert_eq!(event_ids, vec!["ef", "gh"]);
+    }
+
+    /// Verify that receipts can be fetched by transaction ID and listed since a transaction ID.
+    ///
+    /// 1. Initialize a new, empty `ScabbardState` and add receipts to its receipt store.
+    /// 2. Verify that a receipt can be fetched by ID, and that an unknown ID returns `None`.
+    /// 3. Verify that listing returns the receipts after the given ID, up to the limit.
+    /// 4. Verify that listing since an unknown ID returns `None`.
+    #[test]
+    fn receipts() {
+        let db = create_btree_db();
+        let pool = create_connection_pool_and_migrate(":memory:".to_string());
+        let receipt_store = Arc::new(DieselReceiptStore::new(pool, Some("receipts".into())));
+
+        receipt_store
+            .add_txn_receipts(vec![
+                mock_transaction_receipt_with_set("ab", "aa0001"),
+                mock_transaction_receipt("cd"),
+                mock_transaction_receipt("ef"),
+            ])
+            .expect("failed to add receipts to store");
+
+        let state = ScabbardState::new(
+            MerkleState::new(MerkleStateConfig::key_value(db.clone_box()))
+                .expect("failed to create merkle state"),
+            false,
+            Arc::new(TransactCommitHashStore::new(db)),
+            receipt_store,
+            #[cfg(feature = "metrics")]
+            "svc0".to_string(),
+            #[cfg(feature = "metrics")]
+            "vzrQS-rvwf4".to_string(),
+            vec![],
+        )
+        .expect("Failed to initialize state");
+
+        let receipt = state
+            .get_receipt("ab")
+            .expect("failed to get receipt")
+            .expect("receipt not found");
+        assert_eq!(receipt.transaction_id, "ab");
+        match receipt.result {
+            ReceiptResult::Valid { state_changes, .. } => {
+                assert_eq!(state_changes.len(), 1);
+                assert_eq!(state_changes[0].key(), "aa0001");
+            }
+            ReceiptResult::Invalid { .. } => panic!("receipt should be valid"),
+        }
+        assert!(state
+            .get_receipt("zz")
+            .expect("failed to get receipt")
+            .is_none());
+
+        let ids = |receipts: Vec<Receipt>| {
+            receipts
+                .into_iter()
+                .map(|receipt| receipt.transaction_id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            ids(state
+                .list_receipts_since(None, 10)
+                .expect("failed to list receipts")
+                .expect("receipts not found")),
+            vec!["ab", "cd", "ef"]
+        );
+        assert_eq!(
+            ids(state
+                .list_receipts_since(Some("ab".into()), 1)
+                .expect("failed to list receipts")
+                .expect("receipts not found")),
+            vec!["cd"]
+        );
+        assert!(state
+            .list_receipts_since(Some("zz".into()), 10)
+            .expect("failed to list receipts")
+            .is_none());
+    }
+
+    /// Verify that a snapshot exported from `ScabbardState` contains all of state and verifies
+    /// against the current state root.
+    ///
+    /// 1. Initialize a new `ScabbardState` with an admin key, which sets the initial state.
+    /// 2. Export a snapshot and check that it is taken at the current state root.
+    /// 3. Verify that the snapshot contains the admin keys setting and verifies against the
+    ///    current state root.
+    #[test]
+    fn export_snapshot() {
+        let db = create_btree_db();
+        let receipt_store = Arc::new(DieselReceiptStore::new(
+            create_connection_pool_and_migrate(":memory:".to_string()),
+            None,
+        ));
+
+        let state = ScabbardState::new(
+            MerkleState::new(MerkleStateConfig::key_value(db.clone_box()))
+                .expect("failed to create merkle state"),
+            false,
+            Arc::new(TransactCommitHashStore::new(db)),
+            receipt_store,
+            #[cfg(feature = "metrics")]
+            "svc0".to_string(),
+            #[cfg(feature = "metrics")]
+            "vzrQS-rvwf4".to_string(),
+            vec!["admin_key".into()],
+        )
+        .expect("Failed to initialize state");
+
+        let snapshot = state.export_snapshot().expect("failed to export snapshot");
+        assert_eq!(snapshot.state_root(), state.current_state_root());
+        assert_eq!(snapshot.commit_hash(), state.current_state_root());
+        assert_eq!(snapshot.leaves().len(), 1);
+        assert_eq!(
+            snapshot.leaves()[0].address(),
+            ADMINISTRATORS_SETTING_ADDRESS
+        );
+        snapshot
+            .verify(state.current_state_root())
+            .expect("snapshot failed verification");
+    }
+
     /// Verify that the `ScabbardState::get_state_at_address` method works properly.
     ///
     /// 1. Initialize a new, empty `ScabbardState`.
@@ -1354,6 +1825,20 @@ mod tests {
         }
     }
 
+    fn mock_transaction_receipt_with_set(id: &str, key: &str) -> TransactionReceipt {
+        TransactionReceipt {
+            transaction_id: id.into(),
+            transaction_result: TransactionResult::Valid {
+                state_changes: vec![transact::protocol::receipt::StateChange::Set {
+                    key: key.into(),
+                    value: vec![1],
+                }],
+                events: vec![],
+                data: vec![],
+            },
+        }
+    }
+
     fn create_connection_pool_and_migrate(
         connection_string: String,
     ) -> Pool<ConnectionManager<SqliteConnection>> {
@@ -0,0 +1,396 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Background pruning of historical scabbard state roots.
+
+use std::collections::VecDeque;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+use transact::state::Prune;
+
+use crate::service::error::ScabbardStateError;
+use crate::store::CommitHashStore;
+
+use super::merkle_state::MerkleState;
+
+/// The number of committed state roots that are retained by default.
+pub const DEFAULT_RETAINED_STATE_ROOTS: usize = 1;
+
+/// A request for the pruning job to prune the expired roots and then record the roots that are
+/// still retained.
+struct PruneRequest {
+    expired_roots: Vec<String>,
+    retained_roots: Vec<String>,
+}
+
+/// Tracks the most recently committed state roots and prunes any roots that fall outside of the
+/// retention window.
+///
+/// The retained roots are persisted in the commit hash store once older roots have been pruned,
+/// so that a restarted pruner continues to prune roots that were retained before the restart.
+///
+/// Pruning is performed by a background thread so that commits are not delayed by it. A failure
+/// to prune is returned by the next call to the pruner. If autocleanup is enabled, the pruned
+/// entries are also removed from the underlying storage once the roots have been pruned.
+pub struct StatePruner {
+    retained_roots: usize,
+    recent_roots: VecDeque<String>,
+    sender: Option<Sender<PruneRequest>>,
+    error_receiver: Receiver<ScabbardStateError>,
+    join_handle: Option<JoinHandle<()>>,
+}
+
+impl StatePruner {
+    /// Starts a new pruner for the given merkle state.
+    ///
+    /// The roots retained by a previous pruner are restored from the commit hash store. They are
+    /// pruned once the number of roots to retain is set, or on the next commit.
+    ///
+    /// # Arguments
+    ///
+    /// * `merkle_state` - The merkle state to prune
+    /// * `commit_hash_store` - The store in which the retained roots are persisted
+    /// * `current_state_root` - The state root that is current when the pruner is started
+    /// * `autocleanup_enabled` - Whether pruned entries should be removed from storage
+    pub fn start(
+        merkle_state: MerkleState,
+        commit_hash_store: Arc<dyn CommitHashStore + Sync + Send>,
+        current_state_root: String,
+        autocleanup_enabled: bool,
+    ) -> Result<Self, ScabbardStateError> {
+        let mut recent_roots = commit_hash_store
+            .get_retained_commit_hashes()
+            .map_err(|err| {
+                ScabbardStateError(format!("failed to get retained state roots: {}", err))
+            })?
+            .into_iter()
+            .collect::<VecDeque<_>>();
+        if recent_roots.back() != Some(&current_state_root) {
+            recent_roots.push_back(current_state_root);
+        }
+
+        let (sender, receiver) = channel();
+        let (error_sender, error_receiver) = channel();
+
+        let join_handle = thread::Builder::new()
+            .name("ScabbardStatePruner".into())
+            .spawn(move || {
+                run_pruning_job(
+                    merkle_state,
+                    commit_hash_store,
+                    receiver,
+                    error_sender,
+                    autocleanup_enabled,
+                )
+            })
+            .map_err(|err| {
+                ScabbardStateError(format!("failed to start state pruning thread: {}", err))
+            })?;
+
+        Ok(Self {
+            retained_roots: DEFAULT_RETAINED_STATE_ROOTS,
+            recent_roots,
+            sender: Some(sender),
+            error_receiver,
+            join_handle: Some(join_handle),
+        })
+    }
+
+    /// Sets the number of committed state roots to retain. Roots beyond this number are pruned
+    /// immediately.
+    pub fn set_retained_roots(&mut self, retained_roots: usize) -> Result<(), ScabbardStateError> {
+        if retained_roots == 0 {
+            return Err(ScabbardStateError(
+                "at least one state root must be retained".into(),
+            ));
+        }
+
+        self.retained_roots = retained_roots;
+        self.prune_expired_roots()
+    }
+
+    /// Records a newly committed state root and schedules any roots that are no longer retained
+    /// to be pruned.
+    ///
+    /// Returns an error if a previously scheduled pruning failed.
+    pub fn add_committed_root(&mut self, state_root: String) -> Result<(), ScabbardStateError> {
+        if self.recent_roots.back() == Some(&state_root) {
+            return self.check_pruning_errors();
+        }
+
+        self.recent_roots.push_back(state_root);
+        self.prune_expired_roots()
+    }
+
+    /// Sends the roots beyond the retention window to the pruning job, along with the roots that
+    /// remain to be persisted.
+    fn prune_expired_roots(&mut self) -> Result<(), ScabbardStateError> {
+        self.check_pruning_errors()?;
+
+        let expired_count = self.recent_roots.len().saturating_sub(self.retained_roots);
+        let expired_roots = self.recent_roots.drain(..expired_count).collect();
+
+        self.sender
+            .as_ref()
+            .ok_or_else(|| ScabbardStateError("state pruning thread has stopped".into()))?
+            .send(PruneRequest {
+                expired_roots,
+                retained_roots: self.recent_roots.iter().cloned().collect(),
+            })
+            .map_err(|_| ScabbardStateError("state pruning thread has stopped".into()))
+    }
+
+    /// Returns the first error reported by the pruning job since the last check, if any.
+    fn check_pruning_errors(&self) -> Result<(), ScabbardStateError> {
+        match self.error_receiver.try_recv() {
+            Ok(err) => Err(err),
+            Err(_) => Ok(()),
+        }
+    }
+}
+
+impl Drop for StatePruner {
+    fn drop(&mut self) {
+        // Dropping the sender ends the pruning job once it has handled any outstanding roots
+        self.sender.take();
+        if let Some(join_handle) = self.join_handle.take() {
+            if join_handle.join().is_err() {
+                error!("State pruning thread panicked");
+            }
+        }
+
+        for err in self.error_receiver.try_iter() {
+            error!("{}", err);
+        }
+    }
+}
+
+fn run_pruning_job(
+    merkle_state: MerkleState,
+    commit_hash_store: Arc<dyn CommitHashStore + Sync + Send>,
+    receiver: Receiver<PruneRequest>,
+    error_sender: Sender<ScabbardStateError>,
+    autocleanup_enabled: bool,
+) {
+    // Roots that failed to be pruned are retried along with the next request
+    let mut expired_roots: Vec<String> = vec![];
+    while let Ok(request) = receiver.recv() {
+        expired_roots.extend(request.expired_roots);
+        let root_count = expired_roots.len();
+        if root_count > 0 {
+            match merkle_state.prune(expired_roots.clone()) {
+                Ok(pruned_entries) => {
+                    info!(
+                        "Pruned {} state root(s), reclaiming {} entries",
+                        root_count,
+                        pruned_entries.len()
+                    );
+                    expired_roots.clear();
+                }
+                Err(err) => {
+                    // The retained roots are not persisted, so that the expired roots are still
+                    // pruned after a restart
+                    let _ = error_sender.send(ScabbardStateError(format!(
+                        "failed to prune {} state root(s): {}",
+                        root_count, err
+                    )));
+                    continue;
+                }
+            }
+        }
+
+        if let Err(err) = commit_hash_store.set_retained_commit_hashes(&request.retained_roots) {
+            let _ = error_sender.send(ScabbardStateError(format!(
+                "failed to record retained state roots: {}",
+                err
+            )));
+            continue;
+        }
+
+        if autocleanup_enabled && root_count > 0 {
+            if let Err(err) = merkle_state.remove_pruned_entries() {
+                error!("Failed to cleanup pruned state: {}", err);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use transact::{
+        database::{btree::BTreeDatabase, Database},
+        state::{merkle::INDEXES, Read, StateChange, Write},
+    };
+
+    use crate::service::state::merkle_state::MerkleStateConfig;
+    use crate::store::transact::{TransactCommitHashStore, CURRENT_STATE_ROOT_INDEX};
+
+    /// Verify that the pruner only retains the configured number of state roots.
+    ///
+    /// 1. Commit three successive state roots and record each of them with a pruner that retains
+    ///    two roots.
+    /// 2. Stop the pruner, which waits for outstanding pruning to complete.
+    /// 3. Verify that the oldest root can no longer be read, while the two most recent roots can,
+    ///    and that the two most recent roots were persisted.
+    #[test]
+    fn retains_configured_roots() {
+        let merkle_state = new_merkle_state();
+        let commit_hash_store = new_commit_hash_store();
+
+        let initial_root = merkle_state
+            .get_initial_state_root()
+            .expect("failed to get initial root");
+
+        let mut pruner = StatePruner::start(
+            merkle_state.clone(),
+            commit_hash_store.clone(),
+            initial_root.clone(),
+            false,
+        )
+        .expect("failed to start pruner");
+        pruner
+            .set_retained_roots(2)
+            .expect("failed to set retained roots");
+        assert!(pruner.set_retained_roots(0).is_err());
+
+        let roots = commit_values(
+            &merkle_state,
+            &mut pruner,
+            initial_root,
+            &[b"value1", b"value2", b"value3"],
+        );
+
+        drop(pruner);
+
+        assert!(merkle_state
+            .get(&roots[0], &["abcdef".to_string()])
+            .is_err());
+        assert!(merkle_state.get(&roots[1], &["abcdef".to_string()]).is_ok());
+        assert!(merkle_state.get(&roots[2], &["abcdef".to_string()]).is_ok());
+        assert_eq!(
+            commit_hash_store
+                .get_retained_commit_hashes()
+                .expect("failed to get retained roots"),
+            roots[1..].to_vec()
+        );
+    }
+
+    /// Verify that a restarted pruner prunes the roots retained before the restart.
+    ///
+    /// 1. Commit three successive state roots with a pruner that retains three roots, then stop
+    ///    it.
+    /// 2. Start a new pruner with the same commit hash store and set it to retain one root.
+    /// 3. Stop the new pruner and verify that only the most recent root can be read.
+    #[test]
+    fn prunes_retained_roots_after_restart() {
+        let merkle_state = new_merkle_state();
+        let commit_hash_store = new_commit_hash_store();
+
+        let initial_root = merkle_state
+            .get_initial_state_root()
+            .expect("failed to get initial root");
+
+        let mut pruner = StatePruner::start(
+            merkle_state.clone(),
+            commit_hash_store.clone(),
+            initial_root.clone(),
+            false,
+        )
+        .expect("failed to start pruner");
+        pruner
+            .set_retained_roots(3)
+            .expect("failed to set retained roots");
+
+        let roots = commit_values(
+            &merkle_state,
+            &mut pruner,
+            initial_root,
+            &[b"value1", b"value2", b"value3"],
+        );
+
+        drop(pruner);
+
+        let mut pruner = StatePruner::start(
+            merkle_state.clone(),
+            commit_hash_store.clone(),
+            roots[2].clone(),
+            false,
+        )
+        .expect("failed to restart pruner");
+        pruner
+            .set_retained_roots(1)
+            .expect("failed to set retained roots");
+
+        drop(pruner);
+
+        assert!(merkle_state
+            .get(&roots[0], &["abcdef".to_string()])
+            .is_err());
+        assert!(merkle_state
+            .get(&roots[1], &["abcdef".to_string()])
+            .is_err());
+        assert!(merkle_state.get(&roots[2], &["abcdef".to_string()]).is_ok());
+        assert_eq!(
+            commit_hash_store
+                .get_retained_commit_hashes()
+                .expect("failed to get retained roots"),
+            roots[2..].to_vec()
+        );
+    }
+
+    fn new_merkle_state() -> MerkleState {
+        MerkleState::new(MerkleStateConfig::key_value(
+            BTreeDatabase::new(&INDEXES).clone_box(),
+        ))
+        .expect("failed to create merkle state")
+    }
+
+    fn new_commit_hash_store() -> Arc<dyn CommitHashStore + Sync + Send> {
+        Arc::new(TransactCommitHashStore::new(BTreeDatabase::new(&[
+            CURRENT_STATE_ROOT_INDEX,
+        ])))
+    }
+
+    /// Commits each value in turn to the same key, recording each new root with the pruner, and
+    /// returns the new roots.
+    fn commit_values(
+        merkle_state: &MerkleState,
+        pruner: &mut StatePruner,
+        initial_root: String,
+        values: &[&[u8]],
+    ) -> Vec<String> {
+        let mut roots = vec![];
+        let mut current_root = initial_root;
+        for value in values {
+            current_root = merkle_state
+                .commit(
+                    &current_root,
+                    &[StateChange::Set {
+                        key: "abcdef".into(),
+                        value: value.to_vec(),
+                    }],
+                )
+                .expect("failed to commit");
+            pruner
+                .add_committed_root(current_root.clone())
+                .expect("failed to add root");
+            roots.push(current_root.clone());
+        }
+        roots
+    }
+}
@@ -42,8 +42,14 @@ pub use lifecycle::ScabbardLifecycle;
 pub use message_converter::ScabbardMessageByteConverter;
 pub use message_handler::ScabbardMessageHandler;
 pub use message_handler_factory::ScabbardMessageHandlerFactory;
+#[cfg(all(feature = "scabbardv3-publisher", feature = "scabbardv3-consensus"))]
+pub use publisher::ProposedBatches;
 #[cfg(feature = "scabbardv3-publisher")]
-pub use publisher::{SignedTimestamp, SignedTimestampCreator, SignedTimestampCreatorFactory};
+pub use publisher::{
+    ExecutedBatches, ExecutedBatchesCommitter, ExecutedBatchesCreator,
+    ExecutedBatchesCreatorFactory, ScabbardExecutionContext, SignedTimestamp,
+    SignedTimestampCreator, SignedTimestampCreatorFactory,
+};
 #[cfg(feature = "scabbardv3-supervisor")]
 pub use supervisor::{
     Supervisor, SupervisorBuilder, SupervisorMessage, SupervisorNotifierFactory,
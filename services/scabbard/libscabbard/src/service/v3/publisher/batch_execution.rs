@@ -0,0 +1,703 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Contains struct and trait implementations for `ExecutedBatches` artifacts
+//!
+//! An `ExecutedBatches` artifact is the result of running a list of batches through transact
+//! against merkle state. The resulting state root is the value that scabbard agrees upon using
+//! two-phase commit; once agreed upon, the artifact is committed using an
+//! `ExecutedBatchesCommitter`.
+
+#[cfg(feature = "scabbardv3-consensus")]
+use std::convert::TryFrom;
+use std::convert::TryInto;
+use std::sync::Arc;
+use std::time::Duration;
+
+use sawtooth::artifact::{Artifact, ArtifactCreator, ArtifactCreatorFactory};
+use sawtooth::error::InternalError;
+use sawtooth::receipt::store::ReceiptStore;
+#[cfg(feature = "scabbardv3-consensus")]
+use serde::{Deserialize, Serialize};
+use splinter::service::FullyQualifiedServiceId;
+#[cfg(test)]
+use transact::families::command::CommandTransactionHandler;
+#[cfg(feature = "scabbardv3-consensus")]
+use transact::protocol::batch::Batch;
+#[cfg(feature = "scabbardv3-consensus")]
+use transact::protos::{FromBytes, IntoBytes};
+use transact::{
+    context::manager::sync::ContextManager,
+    execution::{adapter::static_adapter::StaticExecutionAdapter, executor::Executor},
+    families::sabre::{admin::SettingsAdminPermission, handler::SabreTransactionHandler},
+    protocol::{
+        batch::BatchPair,
+        receipt::{StateChange, TransactionReceipt, TransactionResult},
+    },
+    scheduler::{serial::SerialScheduler, BatchExecutionResult, Scheduler},
+    state::Write,
+};
+
+use crate::service::state::merkle_state::MerkleState;
+#[cfg(feature = "scabbardv3-consensus")]
+use crate::service::v3::ScabbardValue;
+use crate::store::CommitHashStore;
+
+const EXECUTION_TIMEOUT: u64 = 300; // five minutes
+
+/// The context used when executing batches for Scabbard v3
+pub struct ScabbardExecutionContext {
+    /// The service ID this context is for
+    service_id: FullyQualifiedServiceId,
+    /// The state root the next batches will be executed against
+    state_root: String,
+}
+
+impl ScabbardExecutionContext {
+    pub fn new(service_id: FullyQualifiedServiceId, state_root: String) -> Self {
+        ScabbardExecutionContext {
+            service_id,
+            state_root,
+        }
+    }
+
+    pub fn service_id(&self) -> &FullyQualifiedServiceId {
+        &self.service_id
+    }
+
+    pub fn state_root(&self) -> &str {
+        &self.state_root
+    }
+
+    /// Update the state root, after an artifact has been committed
+    pub fn set_state_root(&mut self, state_root: String) {
+        self.state_root = state_root;
+    }
+}
+
+/// The artifact produced by executing a list of batches
+///
+/// Only the batches whose transactions were all valid are included in the artifact; the IDs of
+/// the remaining batches are available via `invalid_batch_ids`.
+#[derive(Debug, Clone)]
+pub struct ExecutedBatches {
+    previous_state_root: String,
+    state_root: String,
+    batches: Vec<BatchPair>,
+    receipts: Vec<TransactionReceipt>,
+    invalid_batch_ids: Vec<String>,
+}
+
+impl ExecutedBatches {
+    /// The state root the batches were executed against
+    pub fn previous_state_root(&self) -> &str {
+        &self.previous_state_root
+    }
+
+    /// The state root that results from applying the batches' state changes
+    pub fn state_root(&self) -> &str {
+        &self.state_root
+    }
+
+    /// The valid batches that were executed
+    pub fn batches(&self) -> &[BatchPair] {
+        &self.batches
+    }
+
+    /// The receipts for the transactions in the valid batches, in execution order
+    pub fn receipts(&self) -> &[TransactionReceipt] {
+        &self.receipts
+    }
+
+    /// The header signatures of the batches that contained an invalid transaction
+    pub fn invalid_batch_ids(&self) -> &[String] {
+        &self.invalid_batch_ids
+    }
+
+    fn state_changes(&self) -> Result<Vec<StateChange>, InternalError> {
+        receipts_into_state_changes(&self.receipts)
+    }
+}
+
+impl Artifact for ExecutedBatches {
+    type Identifier = String;
+
+    fn artifact_id(&self) -> &Self::Identifier {
+        &self.state_root
+    }
+}
+
+/// Artifact creator for `ExecutedBatches`
+///
+/// Batches are run through a serial scheduler using the Sabre transaction handler.
+pub struct ExecutedBatchesCreator {
+    merkle_state: MerkleState,
+    executor: Option<Executor>,
+    context_manager: ContextManager,
+}
+
+impl ExecutedBatchesCreator {
+    /// Create a new `ExecutedBatchesCreator`, starting its executor
+    pub fn new(merkle_state: MerkleState) -> Result<Self, InternalError> {
+        let context_manager = ContextManager::new(Box::new(merkle_state.clone()));
+        let mut executor = Executor::new(vec![Box::new(
+            StaticExecutionAdapter::new_adapter(
+                vec![
+                    Box::new(SabreTransactionHandler::new(Box::new(
+                        SettingsAdminPermission,
+                    ))),
+                    #[cfg(test)]
+                    Box::new(CommandTransactionHandler::new()),
+                ],
+                context_manager.clone(),
+            )
+            .map_err(|err| InternalError::from_source(Box::new(err)))?,
+        )]);
+        executor
+            .start()
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(ExecutedBatchesCreator {
+            merkle_state,
+            executor: Some(executor),
+            context_manager,
+        })
+    }
+
+    fn execute(
+        &self,
+        state_root: &str,
+        batches: Vec<BatchPair>,
+    ) -> Result<Vec<BatchExecutionResult>, InternalError> {
+        let executor = self.executor.as_ref().ok_or_else(|| {
+            InternalError::with_message(
+                "attempting to execute batches on a stopped executor".into(),
+            )
+        })?;
+
+        let (result_tx, result_rx) = std::sync::mpsc::channel();
+        let mut scheduler = SerialScheduler::new(
+            Box::new(self.context_manager.clone()),
+            state_root.to_string(),
+        )
+        .map_err(|err| InternalError::from_source(Box::new(err)))?;
+        scheduler
+            .set_result_callback(Box::new(move |batch_result| {
+                if result_tx.send(batch_result).is_err() {
+                    error!("Unable to send batch result; receiver must have dropped");
+                }
+            }))
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        let batch_count = batches.len();
+        for batch in batches {
+            scheduler
+                .add_batch(batch)
+                .map_err(|err| InternalError::from_source(Box::new(err)))?;
+        }
+        scheduler
+            .finalize()
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+        executor
+            .execute(
+                scheduler
+                    .take_task_iterator()
+                    .map_err(|err| InternalError::from_source(Box::new(err)))?,
+                scheduler
+                    .new_notifier()
+                    .map_err(|err| InternalError::from_source(Box::new(err)))?,
+            )
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        // Collect the results until the scheduler signals that it is done by sending `None`
+        let mut results = Vec::with_capacity(batch_count);
+        loop {
+            match result_rx.recv_timeout(Duration::from_secs(EXECUTION_TIMEOUT)) {
+                Ok(Some(result)) => results.push(result),
+                Ok(None) => break,
+                Err(_) => {
+                    return Err(InternalError::with_message(
+                        "Failed to receive batch results in reasonable time".into(),
+                    ))
+                }
+            }
+        }
+
+        if results.len() != batch_count {
+            return Err(InternalError::with_message(format!(
+                "Expected {} batch results, but received {}",
+                batch_count,
+                results.len()
+            )));
+        }
+
+        Ok(results)
+    }
+}
+
+impl Drop for ExecutedBatchesCreator {
+    fn drop(&mut self) {
+        if let Some(executor) = self.executor.take() {
+            executor.stop();
+        }
+    }
+}
+
+impl ArtifactCreator for ExecutedBatchesCreator {
+    type Context = ScabbardExecutionContext;
+    type Input = Vec<BatchPair>;
+    type Artifact = ExecutedBatches;
+
+    /// Create a new `ExecutedBatches`
+    ///
+    /// Takes a list of batches as input. The batches are executed, in order, against the state
+    /// root in the `ScabbardExecutionContext`. Batches that contain an invalid transaction are
+    /// excluded from the artifact and their state changes are discarded. The state root of the
+    /// artifact is computed, but not committed, from the remaining receipts.
+    fn create(
+        &self,
+        context: &mut Self::Context,
+        input: Self::Input,
+    ) -> Result<Self::Artifact, InternalError> {
+        let previous_state_root = context.state_root().to_string();
+
+        let mut batches = Vec::with_capacity(input.len());
+        let mut receipts = vec![];
+        let mut invalid_batch_ids = vec![];
+
+        for result in self.execute(&previous_state_root, input)? {
+            let BatchExecutionResult {
+                batch,
+                receipts: batch_receipts,
+            } = result;
+
+            let all_valid = batch_receipts.iter().all(|receipt| {
+                matches!(receipt.transaction_result, TransactionResult::Valid { .. })
+            });
+
+            if all_valid {
+                batches.push(batch);
+                receipts.extend(batch_receipts);
+            } else {
+                debug!(
+                    "Batch {} for service {} contained an invalid transaction",
+                    batch.batch().header_signature(),
+                    context.service_id()
+                );
+                invalid_batch_ids.push(batch.batch().header_signature().to_string());
+            }
+        }
+
+        let state_root = self
+            .merkle_state
+            .compute_state_id(
+                &previous_state_root,
+                &receipts_into_state_changes(&receipts)?,
+            )
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(ExecutedBatches {
+            previous_state_root,
+            state_root,
+            batches,
+            receipts,
+            invalid_batch_ids,
+        })
+    }
+}
+
+/// Factory for creating an instance of `ExecutedBatchesCreator`
+pub struct ExecutedBatchesCreatorFactory {
+    merkle_state: MerkleState,
+}
+
+impl ExecutedBatchesCreatorFactory {
+    pub fn new(merkle_state: MerkleState) -> Self {
+        ExecutedBatchesCreatorFactory { merkle_state }
+    }
+}
+
+impl ArtifactCreatorFactory for ExecutedBatchesCreatorFactory {
+    type ArtifactCreator = ExecutedBatchesCreator;
+
+    /// Create a new `ExecutedBatchesCreator`
+    fn new_creator(&self) -> Result<ExecutedBatchesCreator, InternalError> {
+        ExecutedBatchesCreator::new(self.merkle_state.clone())
+    }
+}
+
+/// Commits `ExecutedBatches` artifacts once consensus has agreed upon them
+///
+/// Committing an artifact writes its state changes to merkle state, records the transaction
+/// receipts and updates the current commit hash.
+pub struct ExecutedBatchesCommitter {
+    merkle_state: MerkleState,
+    receipt_store: Arc<dyn ReceiptStore>,
+    commit_hash_store: Arc<dyn CommitHashStore + Sync + Send>,
+}
+
+impl ExecutedBatchesCommitter {
+    pub fn new(
+        merkle_state: MerkleState,
+        receipt_store: Arc<dyn ReceiptStore>,
+        commit_hash_store: Arc<dyn CommitHashStore + Sync + Send>,
+    ) -> Self {
+        ExecutedBatchesCommitter {
+            merkle_state,
+            receipt_store,
+            commit_hash_store,
+        }
+    }
+
+    /// Commit the given artifact, updating the context's state root
+    ///
+    /// Returns an error if the artifact was not created against the context's current state root
+    /// or if the committed state root does not match the artifact's state root.
+    pub fn commit(
+        &self,
+        context: &mut ScabbardExecutionContext,
+        artifact: ExecutedBatches,
+    ) -> Result<(), InternalError> {
+        if artifact.previous_state_root() != context.state_root() {
+            return Err(InternalError::with_message(format!(
+                "Artifact was created against state root {}, but the current state root is {}",
+                artifact.previous_state_root(),
+                context.state_root()
+            )));
+        }
+
+        let state_changes = artifact.state_changes()?;
+        let state_root = self
+            .merkle_state
+            .commit(&artifact.previous_state_root, &state_changes)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        if state_root != artifact.state_root {
+            return Err(InternalError::with_message(format!(
+                "Committed state root {} does not match the expected state root {}",
+                state_root, artifact.state_root
+            )));
+        }
+
+        self.commit_hash_store
+            .set_current_commit_hash(&state_root)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        info!(
+            "committed {} change(s) for new state root {}",
+            state_changes.len(),
+            state_root,
+        );
+
+        self.receipt_store
+            .add_txn_receipts(artifact.receipts)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        context.set_state_root(state_root);
+
+        Ok(())
+    }
+}
+
+/// The batches and resulting state root proposed for agreement by two-phase commit
+///
+/// A participant re-executes the batches against its own state and only votes to commit if the
+/// computed state root matches the expected state root.
+#[cfg(feature = "scabbardv3-consensus")]
+pub struct ProposedBatches {
+    state_root: String,
+    batches: Vec<BatchPair>,
+}
+
+#[cfg(feature = "scabbardv3-consensus")]
+impl ProposedBatches {
+    /// The state root the coordinator computed for the batches
+    pub fn state_root(&self) -> &str {
+        &self.state_root
+    }
+
+    pub fn batches(&self) -> &[BatchPair] {
+        &self.batches
+    }
+
+    /// Consumes the proposal, returning its batches
+    pub fn into_batches(self) -> Vec<BatchPair> {
+        self.batches
+    }
+
+    /// Returns true if the given artifact produced the proposed state root
+    pub fn matches(&self, artifact: &ExecutedBatches) -> bool {
+        artifact.state_root() == self.state_root && artifact.invalid_batch_ids().is_empty()
+    }
+}
+
+#[cfg(feature = "scabbardv3-consensus")]
+#[derive(Serialize, Deserialize)]
+struct ProposedBatchesValue {
+    state_root: String,
+    batches: Vec<Vec<u8>>,
+}
+
+#[cfg(feature = "scabbardv3-consensus")]
+impl TryFrom<&ExecutedBatches> for ScabbardValue {
+    type Error = InternalError;
+
+    fn try_from(artifact: &ExecutedBatches) -> Result<Self, Self::Error> {
+        let batches = artifact
+            .batches()
+            .iter()
+            .map(|batch| {
+                batch
+                    .batch()
+                    .clone()
+                    .into_bytes()
+                    .map_err(|err| InternalError::from_source(Box::new(err)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        serde_json::to_vec(&ProposedBatchesValue {
+            state_root: artifact.state_root().to_string(),
+            batches,
+        })
+        .map(ScabbardValue::new)
+        .map_err(|err| InternalError::from_source(Box::new(err)))
+    }
+}
+
+#[cfg(feature = "scabbardv3-consensus")]
+impl TryFrom<ScabbardValue> for ProposedBatches {
+    type Error = InternalError;
+
+    fn try_from(value: ScabbardValue) -> Result<Self, Self::Error> {
+        let bytes: Vec<u8> = value.into();
+        let ProposedBatchesValue {
+            state_root,
+            batches,
+        } = serde_json::from_slice(&bytes)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        let batches = batches
+            .iter()
+            .map(|bytes| {
+                Batch::from_bytes(bytes)
+                    .map_err(|err| InternalError::from_source(Box::new(err)))?
+                    .into_pair()
+                    .map_err(|err| InternalError::from_source(Box::new(err)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(ProposedBatches {
+            state_root,
+            batches,
+        })
+    }
+}
+
+fn receipts_into_state_changes(
+    receipts: &[TransactionReceipt],
+) -> Result<Vec<StateChange>, InternalError> {
+    Ok(receipts
+        .iter()
+        .cloned()
+        .map(|receipt| receipt.try_into())
+        .collect::<Result<Vec<Vec<StateChange>>, _>>()
+        .map_err(|err| InternalError::from_source(Box::new(err)))?
+        .into_iter()
+        .flatten()
+        .collect())
+}
+
+#[cfg(feature = "sqlite")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use cylinder::{secp256k1::Secp256k1Context, Context, Signer};
+    use diesel::{
+        r2d2::{ConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+    use sawtooth::migrations::run_sqlite_migrations;
+    use sawtooth::receipt::store::diesel::DieselReceiptStore;
+    use transact::{
+        database::{btree::BTreeDatabase, Database},
+        families::command::CommandTransactionBuilder,
+        protocol::command::{BytesEntry, Command, ReturnInvalid, SetState},
+        state::{merkle::INDEXES, Read},
+    };
+
+    use crate::service::state::merkle_state::MerkleStateConfig;
+    use crate::store::transact::{TransactCommitHashStore, CURRENT_STATE_ROOT_INDEX};
+
+    /// Verify that executing and committing batches produces the expected state.
+    ///
+    /// 1. Create an `ExecutedBatchesCreator` against an empty merkle state
+    /// 2. Create an artifact from one valid and one invalid batch
+    /// 3. Verify that only the valid batch is included and the invalid batch is reported
+    /// 4. Verify that state has not changed before the artifact is committed
+    /// 5. Commit the artifact and verify the state root, state value and receipts
+    #[test]
+    fn create_and_commit_executed_batches() {
+        let db = create_btree_db();
+        let merkle_state = MerkleState::new(MerkleStateConfig::key_value(db.clone_box()))
+            .expect("Unable to create merkle state");
+        let initial_state_root = merkle_state
+            .get_initial_state_root()
+            .expect("Unable to get initial state root");
+        let receipt_store = Arc::new(DieselReceiptStore::new(
+            create_connection_pool_and_migrate(),
+            None,
+        ));
+
+        let creator = ExecutedBatchesCreatorFactory::new(merkle_state.clone())
+            .new_creator()
+            .expect("Unable to create artifact creator");
+        let mut context =
+            ScabbardExecutionContext::new(new_service_id(), initial_state_root.clone());
+
+        let signing_context = Secp256k1Context::new();
+        let signer = signing_context.new_signer(signing_context.new_random_private_key());
+        let valid_batch = new_batch(
+            &*signer,
+            Command::SetState(SetState::new(vec![BytesEntry::new(
+                "abcdef".into(),
+                b"value".to_vec(),
+            )])),
+        );
+        let invalid_batch = new_batch(
+            &*signer,
+            Command::ReturnInvalid(ReturnInvalid::new("invalid".into())),
+        );
+        let invalid_batch_id = invalid_batch.batch().header_signature().to_string();
+
+        let artifact = creator
+            .create(&mut context, vec![valid_batch, invalid_batch])
+            .expect("Unable to create artifact");
+
+        assert_eq!(artifact.batches().len(), 1);
+        assert_eq!(artifact.receipts().len(), 1);
+        assert_eq!(artifact.invalid_batch_ids(), &[invalid_batch_id]);
+        assert_eq!(artifact.previous_state_root(), initial_state_root);
+        assert_ne!(artifact.artifact_id(), &initial_state_root);
+        assert_eq!(context.state_root(), initial_state_root);
+
+        let expected_state_root = artifact.state_root().to_string();
+
+        let committer = ExecutedBatchesCommitter::new(
+            merkle_state.clone(),
+            receipt_store.clone(),
+            Arc::new(TransactCommitHashStore::new(db)),
+        );
+        committer
+            .commit(&mut context, artifact)
+            .expect("Unable to commit artifact");
+
+        assert_eq!(context.state_root(), expected_state_root);
+        assert_eq!(
+            merkle_state
+                .get(&expected_state_root, &["abcdef".to_string()])
+                .expect("Unable to read state")
+                .remove("abcdef"),
+            Some(b"value".to_vec())
+        );
+        assert_eq!(
+            receipt_store
+                .count_txn_receipts()
+                .expect("Unable to count receipts"),
+            1
+        );
+    }
+
+    /// Verify that an artifact can be converted to a `ScabbardValue` and back, and that a second
+    /// creator executing the proposed batches computes the same state root.
+    #[cfg(feature = "scabbardv3-consensus")]
+    #[test]
+    fn proposed_batches_round_trip() {
+        let merkle_state =
+            MerkleState::new(MerkleStateConfig::key_value(create_btree_db().clone_box()))
+                .expect("Unable to create merkle state");
+        let initial_state_root = merkle_state
+            .get_initial_state_root()
+            .expect("Unable to get initial state root");
+
+        let creator = ExecutedBatchesCreator::new(merkle_state.clone())
+            .expect("Unable to create artifact creator");
+        let mut context =
+            ScabbardExecutionContext::new(new_service_id(), initial_state_root.clone());
+
+        let signing_context = Secp256k1Context::new();
+        let signer = signing_context.new_signer(signing_context.new_random_private_key());
+        let batch = new_batch(
+            &*signer,
+            Command::SetState(SetState::new(vec![BytesEntry::new(
+                "abcdef".into(),
+                b"value".to_vec(),
+            )])),
+        );
+
+        let artifact = creator
+            .create(&mut context, vec![batch])
+            .expect("Unable to create artifact");
+
+        let value = ScabbardValue::try_from(&artifact).expect("Unable to convert artifact");
+        let proposed = ProposedBatches::try_from(value).expect("Unable to convert value");
+        assert_eq!(proposed.state_root(), artifact.state_root());
+        assert_eq!(proposed.batches().len(), 1);
+
+        let participant =
+            ExecutedBatchesCreator::new(merkle_state).expect("Unable to create artifact creator");
+        let mut participant_context =
+            ScabbardExecutionContext::new(new_service_id(), initial_state_root);
+        let participant_artifact = participant
+            .create(&mut participant_context, proposed.batches().to_vec())
+            .expect("Unable to create artifact");
+
+        assert!(proposed.matches(&participant_artifact));
+    }
+
+    fn new_batch(signer: &dyn Signer, command: Command) -> BatchPair {
+        CommandTransactionBuilder::new()
+            .with_commands(vec![command])
+            .into_transaction_builder()
+            .expect("failed to convert to transaction builder")
+            .into_batch_builder(signer)
+            .expect("failed to build transaction")
+            .build_pair(signer)
+            .expect("Failed to build batch")
+    }
+
+    fn new_service_id() -> FullyQualifiedServiceId {
+        FullyQualifiedServiceId::new_from_string("abcde-fghij::aa00")
+            .expect("Unable to create service id")
+    }
+
+    fn create_connection_pool_and_migrate() -> Pool<ConnectionManager<SqliteConnection>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        pool
+    }
+
+    fn create_btree_db() -> BTreeDatabase {
+        let mut indexes = INDEXES.to_vec();
+        indexes.push(CURRENT_STATE_ROOT_INDEX);
+        BTreeDatabase::new(&indexes)
+    }
+}
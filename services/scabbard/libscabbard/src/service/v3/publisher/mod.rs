@@ -13,7 +13,14 @@
 // limitations under the License.
 
 mod artifact;
+mod batch_execution;
 mod publisher_context;
 
 pub use artifact::{SignedTimestamp, SignedTimestampCreator, SignedTimestampCreatorFactory};
+#[cfg(feature = "scabbardv3-consensus")]
+pub use batch_execution::ProposedBatches;
+pub use batch_execution::{
+    ExecutedBatches, ExecutedBatchesCommitter, ExecutedBatchesCreator,
+    ExecutedBatchesCreatorFactory, ScabbardExecutionContext,
+};
 pub use publisher_context::ScabbardPublishingContext;
@@ -0,0 +1,280 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Snapshots of a scabbard service's merkle state.
+//!
+//! A [`StateSnapshot`] captures every leaf of a scabbard service's state at a given state root,
+//! along with the commit hash that was recorded for the service. Snapshots are used to bring a
+//! node that has been added to an existing circuit up to date with the other members: the
+//! snapshot is exported from an existing member, verified against the state root that the
+//! circuit members agree on, and imported into the new member's state.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use splinter::error::InvalidStateError;
+use transact::{
+    database::{btree::BTreeDatabase, Database},
+    state::{
+        merkle::kv::{MerkleRadixTree, MerkleState, INDEXES},
+        StateChange, Write,
+    },
+};
+
+/// The complete contents of a scabbard service's state at a single state root.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateSnapshot {
+    state_root: String,
+    commit_hash: String,
+    leaves: Vec<StateSnapshotLeaf>,
+}
+
+impl StateSnapshot {
+    /// Creates a new snapshot.
+    ///
+    /// # Arguments
+    ///
+    /// * `state_root` - The state root the leaves were read from
+    /// * `commit_hash` - The commit hash recorded for the service when the snapshot was taken
+    /// * `leaves` - The address/value pairs stored in state at `state_root`
+    pub fn new(state_root: String, commit_hash: String, leaves: Vec<(String, Vec<u8>)>) -> Self {
+        Self {
+            state_root,
+            commit_hash,
+            leaves: leaves
+                .into_iter()
+                .map(|(address, value)| StateSnapshotLeaf { address, value })
+                .collect(),
+        }
+    }
+
+    /// Returns the state root the snapshot was taken at.
+    pub fn state_root(&self) -> &str {
+        &self.state_root
+    }
+
+    /// Returns the commit hash recorded for the service when the snapshot was taken.
+    pub fn commit_hash(&self) -> &str {
+        &self.commit_hash
+    }
+
+    /// Returns the leaves contained in the snapshot.
+    pub fn leaves(&self) -> &[StateSnapshotLeaf] {
+        &self.leaves
+    }
+
+    /// Verifies that the snapshot is consistent and represents the expected state root.
+    ///
+    /// The snapshot's state root and commit hash must both match `expected_root`, the leaves must
+    /// not contain duplicate addresses, and building a merkle tree from the leaves must result in
+    /// the expected root.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidStateError`] describing the first check that failed.
+    pub fn verify(&self, expected_root: &str) -> Result<(), InvalidStateError> {
+        if self.state_root != expected_root {
+            return Err(InvalidStateError::with_message(format!(
+                "Snapshot state root {} does not match expected root {}",
+                self.state_root, expected_root
+            )));
+        }
+
+        if self.commit_hash != self.state_root {
+            return Err(InvalidStateError::with_message(format!(
+                "Snapshot commit hash {} does not match its state root {}",
+                self.commit_hash, self.state_root
+            )));
+        }
+
+        let mut addresses = HashSet::with_capacity(self.leaves.len());
+        if let Some(leaf) = self
+            .leaves
+            .iter()
+            .find(|leaf| !addresses.insert(leaf.address.as_str()))
+        {
+            return Err(InvalidStateError::with_message(format!(
+                "Snapshot contains duplicate address {}",
+                leaf.address
+            )));
+        }
+
+        let computed_root = compute_state_root(&self.to_state_changes())?;
+        if computed_root != self.state_root {
+            return Err(InvalidStateError::with_message(format!(
+                "Snapshot leaves produce state root {}, expected {}",
+                computed_root, self.state_root
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the state changes that will recreate the snapshot's state when committed on top of
+    /// an empty merkle tree.
+    pub fn to_state_changes(&self) -> Vec<StateChange> {
+        self.leaves
+            .iter()
+            .map(|leaf| StateChange::Set {
+                key: leaf.address.clone(),
+                value: leaf.value.clone(),
+            })
+            .collect()
+    }
+}
+
+/// A single address/value pair in a [`StateSnapshot`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateSnapshotLeaf {
+    address: String,
+    #[serde(with = "hex_bytes")]
+    value: Vec<u8>,
+}
+
+impl StateSnapshotLeaf {
+    /// Returns the address of the leaf.
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    /// Returns the value stored at the leaf's address.
+    pub fn value(&self) -> &[u8] {
+        &self.value
+    }
+}
+
+/// Computes the state root that results from committing the given changes to an empty, in-memory
+/// merkle tree.
+fn compute_state_root(state_changes: &[StateChange]) -> Result<String, InvalidStateError> {
+    let database = BTreeDatabase::new(&INDEXES);
+    let initial_state_root = MerkleRadixTree::new(database.clone_box(), None)
+        .map_err(|err| {
+            InvalidStateError::with_message(format!("Unable to create merkle tree: {}", err))
+        })?
+        .get_merkle_root();
+
+    MerkleState::new(Box::new(database))
+        .compute_state_id(&initial_state_root, state_changes)
+        .map_err(|err| {
+            InvalidStateError::with_message(format!(
+                "Unable to compute state root from snapshot leaves: {}",
+                err
+            ))
+        })
+}
+
+/// Serializes leaf values as hex strings to keep exported snapshots compact and readable.
+mod hex_bytes {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    use crate::hex::{parse_hex, to_hex};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&to_hex(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        parse_hex(&hex).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that a snapshot built from a set of leaves verifies against the state root those
+    /// leaves produce.
+    ///
+    /// 1. Compute the state root for a set of leaves
+    /// 2. Create a snapshot of the leaves at that root and verify it
+    /// 3. Verify that checking against a different root fails
+    #[test]
+    fn verify_snapshot() {
+        let leaves = vec![
+            ("abcdef".to_string(), b"value1".to_vec()),
+            ("012345".to_string(), b"value2".to_vec()),
+        ];
+        let state_root = compute_state_root(&[
+            StateChange::Set {
+                key: "abcdef".into(),
+                value: b"value1".to_vec(),
+            },
+            StateChange::Set {
+                key: "012345".into(),
+                value: b"value2".to_vec(),
+            },
+        ])
+        .expect("Failed to compute state root");
+
+        let snapshot = StateSnapshot::new(state_root.clone(), state_root.clone(), leaves);
+        assert!(snapshot.verify(&state_root).is_ok());
+        assert!(snapshot.verify("not-the-root").is_err());
+    }
+
+    /// Verify that snapshots whose contents do not match their state root are rejected.
+    ///
+    /// 1. Verify that a snapshot with a mismatched commit hash is rejected
+    /// 2. Verify that a snapshot with a modified leaf value is rejected
+    /// 3. Verify that a snapshot with duplicate addresses is rejected
+    #[test]
+    fn verify_invalid_snapshot() {
+        let state_root = compute_state_root(&[StateChange::Set {
+            key: "abcdef".into(),
+            value: b"value1".to_vec(),
+        }])
+        .expect("Failed to compute state root");
+
+        let snapshot = StateSnapshot::new(
+            state_root.clone(),
+            "abcd".into(),
+            vec![("abcdef".into(), b"value1".to_vec())],
+        );
+        assert!(snapshot.verify(&state_root).is_err());
+
+        let snapshot = StateSnapshot::new(
+            state_root.clone(),
+            state_root.clone(),
+            vec![("abcdef".into(), b"value2".to_vec())],
+        );
+        assert!(snapshot.verify(&state_root).is_err());
+
+        let snapshot = StateSnapshot::new(
+            state_root.clone(),
+            state_root.clone(),
+            vec![
+                ("abcdef".into(), b"value1".to_vec()),
+                ("abcdef".into(), b"value1".to_vec()),
+            ],
+        );
+        assert!(snapshot.verify(&state_root).is_err());
+    }
+
+    /// Verify that a snapshot survives a JSON round trip and that leaf values are hex encoded.
+    #[test]
+    fn snapshot_json_round_trip() {
+        let snapshot = StateSnapshot::new(
+            "root".into(),
+            "root".into(),
+            vec![("abcdef".into(), vec![0x01, 0xff])],
+        );
+
+        let json = serde_json::to_string(&snapshot).expect("Failed to serialize snapshot");
+        assert!(json.contains("\"value\":\"01ff\""));
+
+        let deserialized: StateSnapshot =
+            serde_json::from_str(&json).expect("Failed to deserialize snapshot");
+        assert_eq!(snapshot, deserialized);
+    }
+}
@@ -25,7 +25,9 @@ use crate::store::pool::ConnectionPool;
 use super::{CommitHashStore, CommitHashStoreError};
 
 use operations::get_current_commit_hash::CommitHashStoreGetCurrentCommitHashOperation as _;
+use operations::get_retained_commit_hashes::CommitHashStoreGetRetainedCommitHashesOperation as _;
 use operations::set_current_commit_hash::CommitHashStoreSetCurrentCommitHashOperation as _;
+use operations::set_retained_commit_hashes::CommitHashStoreSetRetainedCommitHashesOperation as _;
 use operations::CommitHashStoreOperations;
 
 /// Database backed [CommitHashStore] implementation.
@@ -93,6 +95,26 @@ impl CommitHashStore for DieselCommitHashStore<diesel::pg::PgConnection> {
             )
         })
     }
+
+    fn get_retained_commit_hashes(&self) -> Result<Vec<String>, CommitHashStoreError> {
+        self.pool.execute_read(|conn| {
+            CommitHashStoreOperations::new(conn)
+                .get_retained_commit_hashes(&*self.circuit_id, &*self.service_id)
+        })
+    }
+
+    fn set_retained_commit_hashes(
+        &self,
+        commit_hashes: &[String],
+    ) -> Result<(), CommitHashStoreError> {
+        self.pool.execute_write(|conn| {
+            CommitHashStoreOperations::new(conn).set_retained_commit_hashes(
+                &*self.circuit_id,
+                &*self.service_id,
+                commit_hashes,
+            )
+        })
+    }
 }
 
 #[cfg(feature = "sqlite")]
@@ -113,6 +135,26 @@ impl CommitHashStore for DieselCommitHashStore<diesel::sqlite::SqliteConnection>
             )
         })
     }
+
+    fn get_retained_commit_hashes(&self) -> Result<Vec<String>, CommitHashStoreError> {
+        self.pool.execute_read(|conn| {
+            CommitHashStoreOperations::new(conn)
+                .get_retained_commit_hashes(&*self.circuit_id, &*self.service_id)
+        })
+    }
+
+    fn set_retained_commit_hashes(
+        &self,
+        commit_hashes: &[String],
+    ) -> Result<(), CommitHashStoreError> {
+        self.pool.execute_write(|conn| {
+            CommitHashStoreOperations::new(conn).set_retained_commit_hashes(
+                &*self.circuit_id,
+                &*self.service_id,
+                commit_hashes,
+            )
+        })
+    }
 }
 
 /// Database backed [CommitHashStore] implementation.
@@ -153,6 +195,22 @@ impl<'a> CommitHashStore for DieselInTransactionCommitHashStore<'a, diesel::pg::
             commit_hash,
         )
     }
+
+    fn get_retained_commit_hashes(&self) -> Result<Vec<String>, CommitHashStoreError> {
+        CommitHashStoreOperations::new(self.conn)
+            .get_retained_commit_hashes(&*self.circuit_id, &*self.service_id)
+    }
+
+    fn set_retained_commit_hashes(
+        &self,
+        commit_hashes: &[String],
+    ) -> Result<(), CommitHashStoreError> {
+        CommitHashStoreOperations::new(self.conn).set_retained_commit_hashes(
+            &*self.circuit_id,
+            &*self.service_id,
+            commit_hashes,
+        )
+    }
 }
 
 #[cfg(feature = "sqlite")]
@@ -171,6 +229,22 @@ impl<'a> CommitHashStore
             commit_hash,
         )
     }
+
+    fn get_retained_commit_hashes(&self) -> Result<Vec<String>, CommitHashStoreError> {
+        CommitHashStoreOperations::new(self.conn)
+            .get_retained_commit_hashes(&*self.circuit_id, &*self.service_id)
+    }
+
+    fn set_retained_commit_hashes(
+        &self,
+        commit_hashes: &[String],
+    ) -> Result<(), CommitHashStoreError> {
+        CommitHashStoreOperations::new(self.conn).set_retained_commit_hashes(
+            &*self.circuit_id,
+            &*self.service_id,
+            commit_hashes,
+        )
+    }
 }
 
 #[cfg(test)]
@@ -225,6 +299,49 @@ mod tests {
         Ok(())
     }
 
+    /// Test that a DieselCommitHashStore using a SQLite connection pool can
+    /// 1. Set and get the retained hashes of one circuit, in order
+    /// 2. Verify that they are isolated to that circuit
+    /// 3. Replace the retained hashes with a shorter list
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_sqlite_retained_commit_hashes() -> Result<(), Box<dyn std::error::Error>> {
+        let pool = create_connection_pool_and_migrate()?;
+        let commit_log_store_circuit_1 =
+            DieselCommitHashStore::new(pool.clone(), "circuit_1", "service");
+
+        assert!(commit_log_store_circuit_1
+            .get_retained_commit_hashes()?
+            .is_empty());
+
+        let retained = vec![
+            "abcdef0123456789".to_string(),
+            "9876543210fedcba".to_string(),
+            "0123456789abcdef".to_string(),
+        ];
+        commit_log_store_circuit_1.set_retained_commit_hashes(&retained)?;
+
+        assert_eq!(
+            retained,
+            commit_log_store_circuit_1.get_retained_commit_hashes()?
+        );
+
+        // Check that the service on a different circuit has no retained hashes.
+        let commit_log_store_circuit_2 = DieselCommitHashStore::new(pool, "circuit_2", "service");
+        assert!(commit_log_store_circuit_2
+            .get_retained_commit_hashes()?
+            .is_empty());
+
+        // Verify that setting the hashes replaces the previous ones.
+        commit_log_store_circuit_1.set_retained_commit_hashes(&retained[2..])?;
+        assert_eq!(
+            retained[2..].to_vec(),
+            commit_log_store_circuit_1.get_retained_commit_hashes()?
+        );
+
+        Ok(())
+    }
+
     /// Creates a connection pool for an in-memory SQLite database with only a single connection
     /// available. Each connection is backed by a different in-memory SQLite database, so limiting
     /// the pool to a single connection ensures that the same DB is used for all operations.
@@ -23,3 +23,13 @@ pub struct NewCommitHash<'a> {
     pub service_id: &'a str,
     pub commit_hash: &'a str,
 }
+
+#[derive(Insertable)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+#[table_name = "scabbard_retained_commit_hash"]
+pub struct NewRetainedCommitHash<'a> {
+    pub circuit_id: &'a str,
+    pub service_id: &'a str,
+    pub position: i32,
+    pub commit_hash: &'a str,
+}
@@ -0,0 +1,51 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use diesel::prelude::*;
+use diesel::sql_types::Text;
+use splinter::error::InternalError;
+
+use crate::store::{diesel::schema::scabbard_retained_commit_hash, CommitHashStoreError};
+
+use super::CommitHashStoreOperations;
+
+pub(in crate::store::commit_hash::diesel) trait CommitHashStoreGetRetainedCommitHashesOperation {
+    fn get_retained_commit_hashes(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+    ) -> Result<Vec<String>, CommitHashStoreError>;
+}
+
+impl<'a, C> CommitHashStoreGetRetainedCommitHashesOperation for CommitHashStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<Text, C::Backend>,
+{
+    fn get_retained_commit_hashes(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+    ) -> Result<Vec<String>, CommitHashStoreError> {
+        let retained_commit_hashes = scabbard_retained_commit_hash::table
+            .filter(scabbard_retained_commit_hash::circuit_id.eq(circuit_id))
+            .filter(scabbard_retained_commit_hash::service_id.eq(service_id))
+            .order(scabbard_retained_commit_hash::position.asc())
+            .select(scabbard_retained_commit_hash::commit_hash)
+            .load(self.conn)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(retained_commit_hashes)
+    }
+}
@@ -13,7 +13,9 @@
 // limitations under the License.
 
 pub(super) mod get_current_commit_hash;
+pub(super) mod get_retained_commit_hashes;
 pub(super) mod set_current_commit_hash;
+pub(super) mod set_retained_commit_hashes;
 
 pub struct CommitHashStoreOperations<'a, C> {
     conn: &'a C,
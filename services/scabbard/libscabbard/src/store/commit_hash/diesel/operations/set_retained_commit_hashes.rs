@@ -0,0 +1,117 @@
+// Copyright 2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use diesel::{delete, insert_into, prelude::*};
+use splinter::error::InternalError;
+
+use crate::store::{
+    diesel::{models::NewRetainedCommitHash, schema::scabbard_retained_commit_hash},
+    CommitHashStoreError,
+};
+
+use super::CommitHashStoreOperations;
+
+pub(in crate::store::commit_hash::diesel) trait CommitHashStoreSetRetainedCommitHashesOperation {
+    fn set_retained_commit_hashes(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+        commit_hashes: &[String],
+    ) -> Result<(), CommitHashStoreError>;
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> CommitHashStoreSetRetainedCommitHashesOperation
+    for CommitHashStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn set_retained_commit_hashes(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+        commit_hashes: &[String],
+    ) -> Result<(), CommitHashStoreError> {
+        let new_commit_hashes = to_models(circuit_id, service_id, commit_hashes)?;
+
+        self.conn
+            .transaction::<_, diesel::result::Error, _>(|| {
+                delete(
+                    scabbard_retained_commit_hash::table
+                        .filter(scabbard_retained_commit_hash::circuit_id.eq(circuit_id))
+                        .filter(scabbard_retained_commit_hash::service_id.eq(service_id)),
+                )
+                .execute(self.conn)?;
+
+                insert_into(scabbard_retained_commit_hash::table)
+                    .values(&new_commit_hashes)
+                    .execute(self.conn)?;
+
+                Ok(())
+            })
+            .map_err(|err| CommitHashStoreError::from(InternalError::from_source(Box::new(err))))
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> CommitHashStoreSetRetainedCommitHashesOperation
+    for CommitHashStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn set_retained_commit_hashes(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+        commit_hashes: &[String],
+    ) -> Result<(), CommitHashStoreError> {
+        let new_commit_hashes = to_models(circuit_id, service_id, commit_hashes)?;
+
+        self.conn
+            .transaction::<_, diesel::result::Error, _>(|| {
+                delete(
+                    scabbard_retained_commit_hash::table
+                        .filter(scabbard_retained_commit_hash::circuit_id.eq(circuit_id))
+                        .filter(scabbard_retained_commit_hash::service_id.eq(service_id)),
+                )
+                .execute(self.conn)?;
+
+                insert_into(scabbard_retained_commit_hash::table)
+                    .values(&new_commit_hashes)
+                    .execute(self.conn)?;
+
+                Ok(())
+            })
+            .map_err(|err| CommitHashStoreError::from(InternalError::from_source(Box::new(err))))
+    }
+}
+
+fn to_models<'a>(
+    circuit_id: &'a str,
+    service_id: &'a str,
+    commit_hashes: &'a [String],
+) -> Result<Vec<NewRetainedCommitHash<'a>>, CommitHashStoreError> {
+    commit_hashes
+        .iter()
+        .enumerate()
+        .map(|(position, commit_hash)| {
+            Ok(NewRetainedCommitHash {
+                circuit_id,
+                service_id,
+                position: i32::try_from(position).map_err(|_| {
+                    InternalError::with_message("too many retained commit hashes".into())
+                })?,
+                commit_hash,
+            })
+        })
+        .collect()
+}
@@ -19,3 +19,12 @@ table! {
         commit_hash -> Text,
     }
 }
+
+table! {
+    scabbard_retained_commit_hash (circuit_id, service_id, position) {
+        circuit_id -> Text,
+        service_id -> Text,
+        position -> Integer,
+        commit_hash -> Text,
+    }
+}
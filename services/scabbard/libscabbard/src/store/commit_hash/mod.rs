@@ -38,4 +38,21 @@ pub trait CommitHashStore {
     ///
     /// * `current_commit_hash` - the new "current" commit hash.
     fn set_current_commit_hash(&self, commit_hash: &str) -> Result<(), CommitHashStoreError>;
+
+    /// Returns the commit hashes that are retained for the instance, oldest first.
+    ///
+    /// Retained commit hashes are the state roots that have not yet been pruned from the
+    /// Merkle-Radix tree.
+    fn get_retained_commit_hashes(&self) -> Result<Vec<String>, CommitHashStoreError>;
+
+    /// Sets the commit hashes that are retained for the instance, replacing any that were
+    /// previously set.
+    ///
+    /// # Arguments
+    ///
+    /// * `commit_hashes` - the retained commit hashes, oldest first
+    fn set_retained_commit_hashes(
+        &self,
+        commit_hashes: &[String],
+    ) -> Result<(), CommitHashStoreError>;
 }
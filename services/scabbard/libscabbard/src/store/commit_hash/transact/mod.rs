@@ -16,8 +16,6 @@
 
 pub mod factory;
 
-use std::fmt::Write;
-
 use splinter::error::{InternalError, InvalidArgumentError, InvalidStateError};
 use transact::database::{lmdb::LmdbDatabase, Database, DatabaseError};
 
@@ -27,6 +25,9 @@ use super::{CommitHashStore, CommitHashStoreError};
 
 pub(crate) const CURRENT_STATE_ROOT_INDEX: &str = "current_state_root";
 
+/// The key of the retained commit hashes, which are stored as a comma-separated list
+const RETAINED_KEY: &[u8] = b"RETAINED";
+
 /// Provides an LMDB-backed CommitHashStore.
 pub type LmdbCommitHashStore = TransactCommitHashStore<LmdbDatabase>;
 
@@ -58,7 +59,7 @@ impl<D: Database + Clone> CommitHashStore for TransactCommitHashStore<D> {
             .map_err(|e| InternalError::from_source(Box::new(e)))?;
 
         match reader.index_get(CURRENT_STATE_ROOT_INDEX, b"HEAD") {
-            Ok(current_commit_hash) => Ok(current_commit_hash.map(|bytes| to_hex(&bytes))),
+            Ok(current_commit_hash) => Ok(current_commit_hash.map(|bytes| hex::to_hex(&bytes))),
             Err(DatabaseError::ReaderError(msg)) if msg.starts_with("Not an index") => Err(
                 CommitHashStoreError::InvalidState(InvalidStateError::with_message(
                     "Missing current_state_root index in LMDB database".into(),
@@ -106,15 +107,78 @@ impl<D: Database + Clone> CommitHashStore for TransactCommitHashStore<D> {
 
         Ok(())
     }
-}
 
-fn to_hex(bytes: &[u8]) -> String {
-    let mut buf = String::new();
-    for b in bytes {
-        write!(&mut buf, "{:02x}", b).expect("Unable to write to string");
+    fn get_retained_commit_hashes(&self) -> Result<Vec<String>, CommitHashStoreError> {
+        let reader = self
+            .db
+            .get_reader()
+            .map_err(|e| InternalError::from_source(Box::new(e)))?;
+
+        match reader.index_get(CURRENT_STATE_ROOT_INDEX, RETAINED_KEY) {
+            Ok(Some(bytes)) => String::from_utf8(bytes)
+                .map(|hashes| {
+                    hashes
+                        .split(',')
+                        .filter(|hash| !hash.is_empty())
+                        .map(String::from)
+                        .collect()
+                })
+                .map_err(|e| {
+                    CommitHashStoreError::Internal(InternalError::from_source(Box::new(e)))
+                }),
+            Ok(None) => Ok(vec![]),
+            Err(DatabaseError::ReaderError(msg)) if msg.starts_with("Not an index") => Err(
+                CommitHashStoreError::InvalidState(InvalidStateError::with_message(
+                    "Missing current_state_root index in LMDB database".into(),
+                )),
+            ),
+            Err(err) => Err(CommitHashStoreError::Internal(InternalError::from_source(
+                Box::new(err),
+            ))),
+        }
     }
 
-    buf
+    fn set_retained_commit_hashes(
+        &self,
+        commit_hashes: &[String],
+    ) -> Result<(), CommitHashStoreError> {
+        if let Some(commit_hash) = commit_hashes.iter().find(|hash| hash.contains(',')) {
+            return Err(CommitHashStoreError::InvalidArgument(
+                InvalidArgumentError::new(
+                    "commit_hashes",
+                    format!("The commit hash provided is invalid: {}", commit_hash),
+                ),
+            ));
+        }
+        let retained_bytes = commit_hashes.join(",").into_bytes();
+
+        let mut writer = self
+            .db
+            .get_writer()
+            .map_err(|e| InternalError::from_source(Box::new(e)))?;
+
+        match writer.index_put(CURRENT_STATE_ROOT_INDEX, RETAINED_KEY, &retained_bytes) {
+            Ok(()) => (),
+            Err(DatabaseError::WriterError(msg)) if msg.starts_with("Not an index") => {
+                return Err(CommitHashStoreError::InvalidState(
+                    InvalidStateError::with_message(
+                        "Missing current_state_root index in LMDB database".into(),
+                    ),
+                ))
+            }
+            Err(err) => {
+                return Err(CommitHashStoreError::Internal(InternalError::from_source(
+                    Box::new(err),
+                )))
+            }
+        }
+
+        writer
+            .commit()
+            .map_err(|e| InternalError::from_source(Box::new(e)))?;
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -162,6 +226,38 @@ mod tests {
         })
     }
 
+    /// Test that retained hashes can be stored with an LMDB back-end, in order, and replaced.
+    #[test]
+    fn test_lmdb_store_retained() -> Result<(), Box<dyn Error>> {
+        run_lmdb_test(|dbpath| {
+            let mut indexes = INDEXES.to_vec();
+            indexes.push(CURRENT_STATE_ROOT_INDEX);
+            let db = make_lmdb(&indexes, dbpath)?;
+
+            let commit_log_store = LmdbCommitHashStore::new(db);
+
+            assert!(commit_log_store.get_retained_commit_hashes()?.is_empty());
+
+            let retained = vec![
+                "abcdef0123456789".to_string(),
+                "9876543210fedcba".to_string(),
+            ];
+            commit_log_store.set_retained_commit_hashes(&retained)?;
+            assert_eq!(retained, commit_log_store.get_retained_commit_hashes()?);
+
+            commit_log_store.set_retained_commit_hashes(&retained[1..])?;
+            assert_eq!(
+                retained[1..].to_vec(),
+                commit_log_store.get_retained_commit_hashes()?
+            );
+
+            // The current commit hash is stored separately
+            assert_eq!(None, commit_log_store.get_current_commit_hash()?);
+
+            Ok(())
+        })
+    }
+
     /// Test that the LMDB implementation returns an error on get or set if the index table is not
     /// present.
     #[test]
@@ -356,6 +356,74 @@ impl ConfigBuilder {
                 .partial_configs
                 .iter()
                 .find_map(|p| p.influx_password().map(|v| (v, p.source()))),
+            #[cfg(feature = "tap-prometheus")]
+            enable_prometheus_metrics: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.enable_prometheus_metrics().map(|v| (v, p.source())))
+                .ok_or_else(|| {
+                    ConfigError::MissingValue("enable_prometheus_metrics".to_string())
+                })?,
+            #[cfg(feature = "tap-prometheus")]
+            prometheus_histogram_buckets: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.prometheus_histogram_buckets().map(|v| (v, p.source()))),
+            #[cfg(feature = "circuit-rate-limiting")]
+            circuit_rate_limit: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.circuit_rate_limit().map(|v| (v, p.source()))),
+            #[cfg(feature = "circuit-rate-limiting")]
+            service_rate_limit: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.service_rate_limit().map(|v| (v, p.source()))),
+            #[cfg(feature = "circuit-rate-limiting")]
+            circuit_rate_limit_burst: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.circuit_rate_limit_burst().map(|v| (v, p.source()))),
+            #[cfg(feature = "circuit-rate-limiting")]
+            service_rate_limit_burst: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.service_rate_limit_burst().map(|v| (v, p.source()))),
+            #[cfg(feature = "circuit-rate-limiting")]
+            circuit_rate_limits: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.circuit_rate_limits().map(|v| (v, p.source()))),
+            #[cfg(feature = "peer-retry-policy")]
+            peer_retry_policies: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.peer_retry_policies().map(|v| (v, p.source()))),
+            #[cfg(feature = "peer-retry-policy")]
+            endpoint_retry_policies: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.endpoint_retry_policies().map(|v| (v, p.source()))),
+            #[cfg(feature = "circuit-guaranteed-delivery")]
+            guaranteed_delivery_message_ttl: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.guaranteed_delivery_message_ttl().map(|v| (v, p.source()))),
+            #[cfg(feature = "registry-remote-signed")]
+            registry_trusted_keys: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.registry_trusted_keys().map(|v| (v, p.source()))),
+            #[cfg(feature = "scabbard-state-pruning")]
+            scabbard_retained_state_roots: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.scabbard_retained_state_roots().map(|v| (v, p.source()))),
+            #[cfg(feature = "encrypted-keys")]
+            key_passphrase_file: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.key_passphrase_file().map(|v| (v, p.source()))),
             peering_key: self
                 .partial_configs
                 .iter()
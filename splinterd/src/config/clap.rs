@@ -37,6 +37,22 @@ fn parse_value(matches: &ArgMatches, arg: &str) -> Result<Option<u64>, ConfigErr
     }
 }
 
+// Parses a rate limit, in messages per second, from a clap argument.
+#[cfg(feature = "circuit-rate-limiting")]
+fn parse_rate_limit(matches: &ArgMatches, arg: &str) -> Result<Option<u32>, ConfigError> {
+    match value_t!(matches.value_of(arg), u32) {
+        Ok(0) => Err(ConfigError::InvalidArgument(format!(
+            "{} must be greater than 0",
+            arg
+        ))),
+        Ok(v) => Ok(Some(v)),
+        Err(e) => match e.kind {
+            ErrorKind::ValueValidation => Err(ConfigError::InvalidArgument(e.to_string())),
+            _ => Ok(None),
+        },
+    }
+}
+
 impl<'a> ClapPartialConfigBuilder<'a> {
     pub fn new(matches: ArgMatches<'a>) -> Self {
         ClapPartialConfigBuilder { matches }
@@ -121,6 +137,23 @@ impl PartialConfigBuilder for ClapPartialConfigBuilder<'_> {
             )
         }
 
+        #[cfg(feature = "scabbard-state-pruning")]
+        {
+            partial_config = partial_config.with_scabbard_retained_state_roots(
+                parse_value(&self.matches, "scabbard_retained_state_roots")?
+                    .map(|roots| roots as usize),
+            )
+        }
+
+        #[cfg(feature = "encrypted-keys")]
+        {
+            partial_config = partial_config.with_key_passphrase_file(
+                self.matches
+                    .value_of("key_passphrase_file")
+                    .map(String::from),
+            )
+        }
+
         #[cfg(feature = "biome-credentials")]
         {
             partial_config = partial_config.with_enable_biome_credentials(Some(
@@ -184,6 +217,67 @@ impl PartialConfigBuilder for ClapPartialConfigBuilder<'_> {
                 .with_influx_password(self.matches.value_of("influx_password").map(String::from))
         }
 
+        #[cfg(feature = "tap-prometheus")]
+        {
+            partial_config = partial_config
+                .with_enable_prometheus_metrics(
+                    if self.matches.is_present("enable_prometheus_metrics") {
+                        Some(true)
+                    } else {
+                        None
+                    },
+                )
+                .with_prometheus_histogram_buckets(
+                    self.matches
+                        .values_of("prometheus_histogram_buckets")
+                        .map(|values| {
+                            values
+                                .map(|value| {
+                                    value.parse::<f64>().map_err(|_| {
+                                        ConfigError::InvalidArgument(format!(
+                                            "invalid Prometheus histogram bucket: {}",
+                                            value
+                                        ))
+                                    })
+                                })
+                                .collect::<Result<Vec<f64>, ConfigError>>()
+                        })
+                        .transpose()?,
+                )
+        }
+
+        #[cfg(feature = "circuit-rate-limiting")]
+        {
+            partial_config = partial_config
+                .with_circuit_rate_limit(parse_rate_limit(&self.matches, "circuit_rate_limit")?)
+                .with_service_rate_limit(parse_rate_limit(&self.matches, "service_rate_limit")?)
+                .with_circuit_rate_limit_burst(parse_rate_limit(
+                    &self.matches,
+                    "circuit_rate_limit_burst",
+                )?)
+                .with_service_rate_limit_burst(parse_rate_limit(
+                    &self.matches,
+                    "service_rate_limit_burst",
+                )?)
+        }
+
+        #[cfg(feature = "circuit-guaranteed-delivery")]
+        {
+            partial_config = partial_config.with_guaranteed_delivery_message_ttl(
+                parse_value(&self.matches, "guaranteed_delivery_message_ttl")?
+                    .map(Duration::from_secs),
+            );
+        }
+
+        #[cfg(feature = "registry-remote-signed")]
+        {
+            partial_config = partial_config.with_registry_trusted_keys(
+                self.matches
+                    .values_of("registry_trusted_keys")
+                    .map(|values| values.map(String::from).collect::<Vec<String>>()),
+            )
+        }
+
         #[cfg(feature = "service-timer-interval")]
         {
             partial_config = partial_config.with_service_timer_interval(
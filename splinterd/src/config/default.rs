@@ -116,6 +116,11 @@ impl PartialConfigBuilder for DefaultPartialConfigBuilder {
             partial_config = partial_config.with_enable_biome_credentials(Some(false))
         }
 
+        #[cfg(feature = "tap-prometheus")]
+        {
+            partial_config = partial_config.with_enable_prometheus_metrics(Some(false))
+        }
+
         let root_logger: Option<RootConfig> = Some(RootConfig {
             appenders: vec!["stdout".to_string()],
             level: log::Level::Warn,
@@ -25,6 +25,8 @@ const STATE_DIR_ENV: &str = "SPLINTER_STATE_DIR";
 const CERT_DIR_ENV: &str = "SPLINTER_CERT_DIR";
 const SPLINTER_HOME_ENV: &str = "SPLINTER_HOME";
 const SPLINTER_STRICT_REF_COUNT_ENV: &str = "SPLINTER_STRICT_REF_COUNT";
+#[cfg(feature = "encrypted-keys")]
+const KEY_PASSPHRASE_FILE_ENV: &str = "SPLINTER_KEY_PASSPHRASE_FILE";
 #[cfg(feature = "oauth")]
 const OAUTH_PROVIDER_ENV: &str = "OAUTH_PROVIDER";
 #[cfg(feature = "oauth")]
@@ -171,6 +173,11 @@ impl<K: EnvStore> PartialConfigBuilder for EnvPartialConfigBuilder<K> {
                 .with_influx_password(self.store.get(METRICS_PASSWORD_ENV))
         }
 
+        #[cfg(feature = "encrypted-keys")]
+        {
+            config = config.with_key_passphrase_file(self.store.get(KEY_PASSPHRASE_FILE_ENV));
+        }
+
         Ok(config)
     }
 }
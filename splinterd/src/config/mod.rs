@@ -27,6 +27,8 @@ mod logging;
 mod partial;
 mod toml;
 
+#[cfg(any(feature = "circuit-rate-limiting", feature = "peer-retry-policy"))]
+use std::collections::HashMap;
 use std::time::Duration;
 
 pub use crate::config::clap::ClapPartialConfigBuilder;
@@ -41,6 +43,36 @@ pub use logging::{
     AppenderConfig, LogConfig, LogEncoder, LogTarget, LoggerConfig, RawLogTarget, RootConfig,
 };
 
+/// The rate limit for the messages sent on a specific circuit, which overrides the default circuit
+/// rate limit.
+#[cfg(feature = "circuit-rate-limiting")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CircuitRateLimitConfig {
+    /// The maximum number of messages per second that may be sent on the circuit
+    pub messages_per_second: u32,
+    /// The maximum number of messages that may be sent on the circuit at once; defaults to
+    /// `messages_per_second`
+    pub burst: Option<u32>,
+}
+
+/// How a specific peer or endpoint is retried when its connection is lost. Values that are not
+/// set use the defaults of a peer retry policy.
+#[cfg(feature = "peer-retry-policy")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PeerRetryPolicyConfig {
+    /// The interval before the first retry
+    pub initial_interval: Option<Duration>,
+    /// The maximum interval between retries
+    pub max_interval: Option<Duration>,
+    /// The factor by which the interval grows after each attempt
+    pub multiplier: Option<u32>,
+    /// The fraction of each interval that is randomly added or subtracted
+    pub jitter: Option<f64>,
+    /// The number of attempts after which the peer is given up on; if not set, the peer is
+    /// retried forever
+    pub max_attempts: Option<u64>,
+}
+
 /// `Config` is the final representation of configuration values. This final config object assembles
 /// values from `PartialConfig` builder objects generated from various sources.
 #[derive(Debug)]
@@ -100,6 +132,32 @@ pub struct Config {
     influx_username: Option<(String, ConfigSource)>,
     #[cfg(feature = "tap")]
     influx_password: Option<(String, ConfigSource)>,
+    #[cfg(feature = "tap-prometheus")]
+    enable_prometheus_metrics: (bool, ConfigSource),
+    #[cfg(feature = "tap-prometheus")]
+    prometheus_histogram_buckets: Option<(Vec<f64>, ConfigSource)>,
+    #[cfg(feature = "circuit-rate-limiting")]
+    circuit_rate_limit: Option<(u32, ConfigSource)>,
+    #[cfg(feature = "circuit-rate-limiting")]
+    service_rate_limit: Option<(u32, ConfigSource)>,
+    #[cfg(feature = "circuit-rate-limiting")]
+    circuit_rate_limit_burst: Option<(u32, ConfigSource)>,
+    #[cfg(feature = "circuit-rate-limiting")]
+    service_rate_limit_burst: Option<(u32, ConfigSource)>,
+    #[cfg(feature = "circuit-rate-limiting")]
+    circuit_rate_limits: Option<(HashMap<String, CircuitRateLimitConfig>, ConfigSource)>,
+    #[cfg(feature = "peer-retry-policy")]
+    peer_retry_policies: Option<(HashMap<String, PeerRetryPolicyConfig>, ConfigSource)>,
+    #[cfg(feature = "peer-retry-policy")]
+    endpoint_retry_policies: Option<(HashMap<String, PeerRetryPolicyConfig>, ConfigSource)>,
+    #[cfg(feature = "circuit-guaranteed-delivery")]
+    guaranteed_delivery_message_ttl: Option<(Duration, ConfigSource)>,
+    #[cfg(feature = "registry-remote-signed")]
+    registry_trusted_keys: Option<(Vec<String>, ConfigSource)>,
+    #[cfg(feature = "scabbard-state-pruning")]
+    scabbard_retained_state_roots: Option<(usize, ConfigSource)>,
+    #[cfg(feature = "encrypted-keys")]
+    key_passphrase_file: Option<(String, ConfigSource)>,
     peering_key: (String, ConfigSource),
     root_logger: (RootConfig, ConfigSource),
     appenders: Option<Vec<(AppenderConfig, ConfigSource)>>,
@@ -344,6 +402,91 @@ impl Config {
         }
     }
 
+    #[cfg(feature = "tap-prometheus")]
+    pub fn enable_prometheus_metrics(&self) -> bool {
+        self.enable_prometheus_metrics.0
+    }
+
+    #[cfg(feature = "tap-prometheus")]
+    pub fn prometheus_histogram_buckets(&self) -> Option<&[f64]> {
+        if let Some((buckets, _)) = &self.prometheus_histogram_buckets {
+            Some(buckets)
+        } else {
+            None
+        }
+    }
+
+    #[cfg(feature = "circuit-rate-limiting")]
+    pub fn circuit_rate_limit(&self) -> Option<u32> {
+        self.circuit_rate_limit.as_ref().map(|(limit, _)| *limit)
+    }
+
+    #[cfg(feature = "circuit-rate-limiting")]
+    pub fn service_rate_limit(&self) -> Option<u32> {
+        self.service_rate_limit.as_ref().map(|(limit, _)| *limit)
+    }
+
+    #[cfg(feature = "circuit-rate-limiting")]
+    pub fn circuit_rate_limit_burst(&self) -> Option<u32> {
+        self.circuit_rate_limit_burst
+            .as_ref()
+            .map(|(burst, _)| *burst)
+    }
+
+    #[cfg(feature = "circuit-rate-limiting")]
+    pub fn service_rate_limit_burst(&self) -> Option<u32> {
+        self.service_rate_limit_burst
+            .as_ref()
+            .map(|(burst, _)| *burst)
+    }
+
+    #[cfg(feature = "circuit-rate-limiting")]
+    pub fn circuit_rate_limits(&self) -> Option<&HashMap<String, CircuitRateLimitConfig>> {
+        self.circuit_rate_limits.as_ref().map(|(limits, _)| limits)
+    }
+
+    #[cfg(feature = "circuit-guaranteed-delivery")]
+    pub fn guaranteed_delivery_message_ttl(&self) -> Option<Duration> {
+        self.guaranteed_delivery_message_ttl
+            .as_ref()
+            .map(|(ttl, _)| *ttl)
+    }
+
+    #[cfg(feature = "registry-remote-signed")]
+    pub fn registry_trusted_keys(&self) -> Option<&[String]> {
+        self.registry_trusted_keys
+            .as_ref()
+            .map(|(keys, _)| keys.as_slice())
+    }
+
+    #[cfg(feature = "peer-retry-policy")]
+    pub fn peer_retry_policies(&self) -> Option<&HashMap<String, PeerRetryPolicyConfig>> {
+        self.peer_retry_policies
+            .as_ref()
+            .map(|(policies, _)| policies)
+    }
+
+    #[cfg(feature = "peer-retry-policy")]
+    pub fn endpoint_retry_policies(&self) -> Option<&HashMap<String, PeerRetryPolicyConfig>> {
+        self.endpoint_retry_policies
+            .as_ref()
+            .map(|(policies, _)| policies)
+    }
+
+    #[cfg(feature = "scabbard-state-pruning")]
+    pub fn scabbard_retained_state_roots(&self) -> Option<usize> {
+        self.scabbard_retained_state_roots
+            .as_ref()
+            .map(|(roots, _)| *roots)
+    }
+
+    #[cfg(feature = "encrypted-keys")]
+    pub fn key_passphrase_file(&self) -> Option<&str> {
+        self.key_passphrase_file
+            .as_ref()
+            .map(|(file, _)| file.as_str())
+    }
+
     pub fn peering_key(&self) -> &str {
         &self.peering_key.0
     }
@@ -586,6 +729,87 @@ impl Config {
         }
     }
 
+    #[cfg(feature = "tap-prometheus")]
+    pub fn enable_prometheus_metrics_source(&self) -> &ConfigSource {
+        &self.enable_prometheus_metrics.1
+    }
+
+    #[cfg(feature = "tap-prometheus")]
+    pub fn prometheus_histogram_buckets_source(&self) -> Option<&ConfigSource> {
+        if let Some((_, source)) = &self.prometheus_histogram_buckets {
+            Some(source)
+        } else {
+            None
+        }
+    }
+
+    #[cfg(feature = "circuit-rate-limiting")]
+    fn circuit_rate_limit_source(&self) -> Option<&ConfigSource> {
+        self.circuit_rate_limit.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "circuit-rate-limiting")]
+    fn service_rate_limit_source(&self) -> Option<&ConfigSource> {
+        self.service_rate_limit.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "circuit-rate-limiting")]
+    fn circuit_rate_limit_burst_source(&self) -> Option<&ConfigSource> {
+        self.circuit_rate_limit_burst
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "circuit-rate-limiting")]
+    fn service_rate_limit_burst_source(&self) -> Option<&ConfigSource> {
+        self.service_rate_limit_burst
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "circuit-rate-limiting")]
+    fn circuit_rate_limits_source(&self) -> Option<&ConfigSource> {
+        self.circuit_rate_limits.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "circuit-guaranteed-delivery")]
+    fn guaranteed_delivery_message_ttl_source(&self) -> Option<&ConfigSource> {
+        self.guaranteed_delivery_message_ttl
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "registry-remote-signed")]
+    fn registry_trusted_keys_source(&self) -> Option<&ConfigSource> {
+        self.registry_trusted_keys
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "peer-retry-policy")]
+    fn peer_retry_policies_source(&self) -> Option<&ConfigSource> {
+        self.peer_retry_policies.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "peer-retry-policy")]
+    fn endpoint_retry_policies_source(&self) -> Option<&ConfigSource> {
+        self.endpoint_retry_policies
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "scabbard-state-pruning")]
+    fn scabbard_retained_state_roots_source(&self) -> Option<&ConfigSource> {
+        self.scabbard_retained_state_roots
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "encrypted-keys")]
+    fn key_passphrase_file_source(&self) -> Option<&ConfigSource> {
+        self.key_passphrase_file.as_ref().map(|(_, source)| source)
+    }
+
     fn peering_key_source(&self) -> &ConfigSource {
         &self.peering_key.1
     }
@@ -883,6 +1107,144 @@ impl Config {
                 debug!("Config: influx_password: <HIDDEN> (source: {:?})", source,);
             }
         }
+        #[cfg(feature = "tap-prometheus")]
+        {
+            debug!(
+                "Config: enable_prometheus_metrics: {:?} (source: {:?})",
+                self.enable_prometheus_metrics(),
+                self.enable_prometheus_metrics_source()
+            );
+
+            if let (Some(buckets), Some(source)) = (
+                self.prometheus_histogram_buckets(),
+                self.prometheus_histogram_buckets_source(),
+            ) {
+                debug!(
+                    "Config: prometheus_histogram_buckets: {:?} (source: {:?})",
+                    buckets, source,
+                );
+            }
+        }
+        #[cfg(feature = "circuit-rate-limiting")]
+        {
+            if let (Some(limit), Some(source)) =
+                (self.circuit_rate_limit(), self.circuit_rate_limit_source())
+            {
+                debug!(
+                    "Config: circuit_rate_limit: {:?} (source: {:?})",
+                    limit, source,
+                );
+            }
+
+            if let (Some(limit), Some(source)) =
+                (self.service_rate_limit(), self.service_rate_limit_source())
+            {
+                debug!(
+                    "Config: service_rate_limit: {:?} (source: {:?})",
+                    limit, source,
+                );
+            }
+
+            if let (Some(burst), Some(source)) = (
+                self.circuit_rate_limit_burst(),
+                self.circuit_rate_limit_burst_source(),
+            ) {
+                debug!(
+                    "Config: circuit_rate_limit_burst: {:?} (source: {:?})",
+                    burst, source,
+                );
+            }
+
+            if let (Some(burst), Some(source)) = (
+                self.service_rate_limit_burst(),
+                self.service_rate_limit_burst_source(),
+            ) {
+                debug!(
+                    "Config: service_rate_limit_burst: {:?} (source: {:?})",
+                    burst, source,
+                );
+            }
+
+            if let (Some(limits), Some(source)) = (
+                self.circuit_rate_limits(),
+                self.circuit_rate_limits_source(),
+            ) {
+                debug!(
+                    "Config: circuit_rate_limits: {:?} (source: {:?})",
+                    limits, source,
+                );
+            }
+        }
+        #[cfg(feature = "circuit-guaranteed-delivery")]
+        {
+            if let (Some(ttl), Some(source)) = (
+                self.guaranteed_delivery_message_ttl(),
+                self.guaranteed_delivery_message_ttl_source(),
+            ) {
+                debug!(
+                    "Config: guaranteed_delivery_message_ttl: {:?} (source: {:?})",
+                    ttl, source,
+                );
+            }
+        }
+        #[cfg(feature = "registry-remote-signed")]
+        {
+            if let (Some(keys), Some(source)) = (
+                self.registry_trusted_keys(),
+                self.registry_trusted_keys_source(),
+            ) {
+                debug!(
+                    "Config: registry_trusted_keys: {:?} (source: {:?})",
+                    keys, source,
+                );
+            }
+        }
+        #[cfg(feature = "peer-retry-policy")]
+        {
+            if let (Some(policies), Some(source)) = (
+                self.peer_retry_policies(),
+                self.peer_retry_policies_source(),
+            ) {
+                debug!(
+                    "Config: peer_retry_policies: {:?} (source: {:?})",
+                    policies, source,
+                );
+            }
+
+            if let (Some(policies), Some(source)) = (
+                self.endpoint_retry_policies(),
+                self.endpoint_retry_policies_source(),
+            ) {
+                debug!(
+                    "Config: endpoint_retry_policies: {:?} (source: {:?})",
+                    policies, source,
+                );
+            }
+        }
+        #[cfg(feature = "scabbard-state-pruning")]
+        {
+            if let (Some(roots), Some(source)) = (
+                self.scabbard_retained_state_roots(),
+                self.scabbard_retained_state_roots_source(),
+            ) {
+                debug!(
+                    "Config: scabbard_retained_state_roots: {:?} (source: {:?})",
+                    roots, source,
+                );
+            }
+        }
+        #[cfg(feature = "encrypted-keys")]
+        {
+            if let (Some(file), Some(source)) = (
+                self.key_passphrase_file(),
+                self.key_passphrase_file_source(),
+            ) {
+                debug!(
+                    "Config: key_passphrase_file: {:?} (source: {:?})",
+                    file, source,
+                );
+            }
+        }
         if let Some(loggers) = &self.loggers {
             for logger in loggers {
                 debug!("Config: logger: {:?} (source: {:?})", logger.0, logger.1);
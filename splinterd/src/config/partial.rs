@@ -19,6 +19,10 @@ use std::collections::HashMap;
 use std::time::Duration;
 
 use super::logging::{RootConfig, UnnamedAppenderConfig, UnnamedLoggerConfig};
+#[cfg(feature = "circuit-rate-limiting")]
+use super::CircuitRateLimitConfig;
+#[cfg(feature = "peer-retry-policy")]
+use super::PeerRetryPolicyConfig;
 use super::ScabbardState;
 
 /// `ConfigSource` displays the source of configuration values, used to identify which of the various
@@ -92,6 +96,32 @@ pub struct PartialConfig {
     influx_username: Option<String>,
     #[cfg(feature = "tap")]
     influx_password: Option<String>,
+    #[cfg(feature = "tap-prometheus")]
+    enable_prometheus_metrics: Option<bool>,
+    #[cfg(feature = "tap-prometheus")]
+    prometheus_histogram_buckets: Option<Vec<f64>>,
+    #[cfg(feature = "circuit-rate-limiting")]
+    circuit_rate_limit: Option<u32>,
+    #[cfg(feature = "circuit-rate-limiting")]
+    service_rate_limit: Option<u32>,
+    #[cfg(feature = "circuit-rate-limiting")]
+    circuit_rate_limit_burst: Option<u32>,
+    #[cfg(feature = "circuit-rate-limiting")]
+    service_rate_limit_burst: Option<u32>,
+    #[cfg(feature = "circuit-rate-limiting")]
+    circuit_rate_limits: Option<HashMap<String, CircuitRateLimitConfig>>,
+    #[cfg(feature = "peer-retry-policy")]
+    peer_retry_policies: Option<HashMap<String, PeerRetryPolicyConfig>>,
+    #[cfg(feature = "peer-retry-policy")]
+    endpoint_retry_policies: Option<HashMap<String, PeerRetryPolicyConfig>>,
+    #[cfg(feature = "circuit-guaranteed-delivery")]
+    guaranteed_delivery_message_ttl: Option<Duration>,
+    #[cfg(feature = "registry-remote-signed")]
+    registry_trusted_keys: Option<Vec<String>>,
+    #[cfg(feature = "scabbard-state-pruning")]
+    scabbard_retained_state_roots: Option<usize>,
+    #[cfg(feature = "encrypted-keys")]
+    key_passphrase_file: Option<String>,
     peering_key: Option<String>,
     root_logger: Option<RootConfig>,
     appenders: Option<HashMap<String, UnnamedAppenderConfig>>,
@@ -167,6 +197,32 @@ impl PartialConfig {
             influx_username: None,
             #[cfg(feature = "tap")]
             influx_password: None,
+            #[cfg(feature = "tap-prometheus")]
+            enable_prometheus_metrics: None,
+            #[cfg(feature = "tap-prometheus")]
+            prometheus_histogram_buckets: None,
+            #[cfg(feature = "circuit-rate-limiting")]
+            circuit_rate_limit: None,
+            #[cfg(feature = "circuit-rate-limiting")]
+            service_rate_limit: None,
+            #[cfg(feature = "circuit-rate-limiting")]
+            circuit_rate_limit_burst: None,
+            #[cfg(feature = "circuit-rate-limiting")]
+            service_rate_limit_burst: None,
+            #[cfg(feature = "circuit-rate-limiting")]
+            circuit_rate_limits: None,
+            #[cfg(feature = "peer-retry-policy")]
+            peer_retry_policies: None,
+            #[cfg(feature = "peer-retry-policy")]
+            endpoint_retry_policies: None,
+            #[cfg(feature = "circuit-guaranteed-delivery")]
+            guaranteed_delivery_message_ttl: None,
+            #[cfg(feature = "registry-remote-signed")]
+            registry_trusted_keys: None,
+            #[cfg(feature = "scabbard-state-pruning")]
+            scabbard_retained_state_roots: None,
+            #[cfg(feature = "encrypted-keys")]
+            key_passphrase_file: None,
             peering_key: None,
             appenders: None,
             loggers: None,
@@ -359,6 +415,71 @@ impl PartialConfig {
         self.influx_password.clone()
     }
 
+    #[cfg(feature = "tap-prometheus")]
+    pub fn enable_prometheus_metrics(&self) -> Option<bool> {
+        self.enable_prometheus_metrics
+    }
+
+    #[cfg(feature = "tap-prometheus")]
+    pub fn prometheus_histogram_buckets(&self) -> Option<Vec<f64>> {
+        self.prometheus_histogram_buckets.clone()
+    }
+
+    #[cfg(feature = "circuit-rate-limiting")]
+    pub fn circuit_rate_limit(&self) -> Option<u32> {
+        self.circuit_rate_limit
+    }
+
+    #[cfg(feature = "circuit-rate-limiting")]
+    pub fn service_rate_limit(&self) -> Option<u32> {
+        self.service_rate_limit
+    }
+
+    #[cfg(feature = "circuit-rate-limiting")]
+    pub fn circuit_rate_limit_burst(&self) -> Option<u32> {
+        self.circuit_rate_limit_burst
+    }
+
+    #[cfg(feature = "circuit-rate-limiting")]
+    pub fn service_rate_limit_burst(&self) -> Option<u32> {
+        self.service_rate_limit_burst
+    }
+
+    #[cfg(feature = "circuit-rate-limiting")]
+    pub fn circuit_rate_limits(&self) -> Option<HashMap<String, CircuitRateLimitConfig>> {
+        self.circuit_rate_limits.clone()
+    }
+
+    #[cfg(feature = "peer-retry-policy")]
+    pub fn peer_retry_policies(&self) -> Option<HashMap<String, PeerRetryPolicyConfig>> {
+        self.peer_retry_policies.clone()
+    }
+
+    #[cfg(feature = "peer-retry-policy")]
+    pub fn endpoint_retry_policies(&self) -> Option<HashMap<String, PeerRetryPolicyConfig>> {
+        self.endpoint_retry_policies.clone()
+    }
+
+    #[cfg(feature = "circuit-guaranteed-delivery")]
+    pub fn guaranteed_delivery_message_ttl(&self) -> Option<Duration> {
+        self.guaranteed_delivery_message_ttl
+    }
+
+    #[cfg(feature = "registry-remote-signed")]
+    pub fn registry_trusted_keys(&self) -> Option<Vec<String>> {
+        self.registry_trusted_keys.clone()
+    }
+
+    #[cfg(feature = "scabbard-state-pruning")]
+    pub fn scabbard_retained_state_roots(&self) -> Option<usize> {
+        self.scabbard_retained_state_roots
+    }
+
+    #[cfg(feature = "encrypted-keys")]
+    pub fn key_passphrase_file(&self) -> Option<String> {
+        self.key_passphrase_file.clone()
+    }
+
     pub fn peering_key(&self) -> Option<String> {
         self.peering_key.clone()
     }
@@ -859,6 +980,199 @@ impl PartialConfig {
         self
     }
 
+    #[cfg(feature = "tap-prometheus")]
+    /// Adds an `enable_prometheus_metrics` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `enable_prometheus_metrics` - Enables collecting metrics for the Prometheus `/metrics`
+    ///    REST API endpoint
+    ///
+    pub fn with_enable_prometheus_metrics(
+        mut self,
+        enable_prometheus_metrics: Option<bool>,
+    ) -> Self {
+        self.enable_prometheus_metrics = enable_prometheus_metrics;
+        self
+    }
+
+    #[cfg(feature = "tap-prometheus")]
+    /// Adds a `prometheus_histogram_buckets` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `prometheus_histogram_buckets` - The upper bounds of the buckets used for histograms
+    ///    exposed to Prometheus
+    ///
+    pub fn with_prometheus_histogram_buckets(
+        mut self,
+        prometheus_histogram_buckets: Option<Vec<f64>>,
+    ) -> Self {
+        self.prometheus_histogram_buckets = prometheus_histogram_buckets;
+        self
+    }
+
+    #[cfg(feature = "circuit-rate-limiting")]
+    /// Adds a `circuit_rate_limit` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit_rate_limit` - The maximum number of messages per second that may be sent on a
+    ///    circuit
+    ///
+    pub fn with_circuit_rate_limit(mut self, circuit_rate_limit: Option<u32>) -> Self {
+        self.circuit_rate_limit = circuit_rate_limit;
+        self
+    }
+
+    #[cfg(feature = "circuit-rate-limiting")]
+    /// Adds a `service_rate_limit` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `service_rate_limit` - The maximum number of messages per second that may be sent by a
+    ///    service on a circuit
+    ///
+    pub fn with_service_rate_limit(mut self, service_rate_limit: Option<u32>) -> Self {
+        self.service_rate_limit = service_rate_limit;
+        self
+    }
+
+    #[cfg(feature = "circuit-rate-limiting")]
+    /// Adds a `circuit_rate_limit_burst` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit_rate_limit_burst` - The maximum number of messages that may be sent on a
+    ///    circuit at once
+    ///
+    pub fn with_circuit_rate_limit_burst(mut self, circuit_rate_limit_burst: Option<u32>) -> Self {
+        self.circuit_rate_limit_burst = circuit_rate_limit_burst;
+        self
+    }
+
+    #[cfg(feature = "circuit-rate-limiting")]
+    /// Adds a `service_rate_limit_burst` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `service_rate_limit_burst` - The maximum number of messages that may be sent by a
+    ///    service on a circuit at once
+    ///
+    pub fn with_service_rate_limit_burst(mut self, service_rate_limit_burst: Option<u32>) -> Self {
+        self.service_rate_limit_burst = service_rate_limit_burst;
+        self
+    }
+
+    #[cfg(feature = "circuit-rate-limiting")]
+    /// Adds a `circuit_rate_limits` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit_rate_limits` - The rate limits for specific circuits, by circuit ID, which
+    ///    override the default circuit rate limit
+    ///
+    pub fn with_circuit_rate_limits(
+        mut self,
+        circuit_rate_limits: Option<HashMap<String, CircuitRateLimitConfig>>,
+    ) -> Self {
+        self.circuit_rate_limits = circuit_rate_limits;
+        self
+    }
+
+    #[cfg(feature = "peer-retry-policy")]
+    /// Adds a `peer_retry_policies` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `peer_retry_policies` - The retry policies for specific peers, by node ID, which are
+    ///    used when the peer is a member of a circuit
+    ///
+    pub fn with_peer_retry_policies(
+        mut self,
+        peer_retry_policies: Option<HashMap<String, PeerRetryPolicyConfig>>,
+    ) -> Self {
+        self.peer_retry_policies = peer_retry_policies;
+        self
+    }
+
+    #[cfg(feature = "peer-retry-policy")]
+    /// Adds an `endpoint_retry_policies` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint_retry_policies` - The retry policies for specific peers given in `peers`, by
+    ///    the peer's entry in `peers`
+    ///
+    pub fn with_endpoint_retry_policies(
+        mut self,
+        endpoint_retry_policies: Option<HashMap<String, PeerRetryPolicyConfig>>,
+    ) -> Self {
+        self.endpoint_retry_policies = endpoint_retry_policies;
+        self
+    }
+
+    #[cfg(feature = "circuit-guaranteed-delivery")]
+    /// Adds a `guaranteed_delivery_message_ttl` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `guaranteed_delivery_message_ttl` - How long a message on a guaranteed-delivery circuit
+    ///    is kept for a disconnected node before it is discarded
+    ///
+    pub fn with_guaranteed_delivery_message_ttl(
+        mut self,
+        guaranteed_delivery_message_ttl: Option<Duration>,
+    ) -> Self {
+        self.guaranteed_delivery_message_ttl = guaranteed_delivery_message_ttl;
+        self
+    }
+
+    #[cfg(feature = "registry-remote-signed")]
+    /// Adds a `registry_trusted_keys` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `registry_trusted_keys` - The hex-encoded public keys that are trusted to sign remote
+    ///    registry files
+    ///
+    pub fn with_registry_trusted_keys(
+        mut self,
+        registry_trusted_keys: Option<Vec<String>>,
+    ) -> Self {
+        self.registry_trusted_keys = registry_trusted_keys;
+        self
+    }
+
+    #[cfg(feature = "scabbard-state-pruning")]
+    /// Adds a `scabbard_retained_state_roots` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `scabbard_retained_state_roots` - The number of committed state roots each scabbard
+    ///    service retains before older roots are pruned
+    ///
+    pub fn with_scabbard_retained_state_roots(
+        mut self,
+        scabbard_retained_state_roots: Option<usize>,
+    ) -> Self {
+        self.scabbard_retained_state_roots = scabbard_retained_state_roots;
+        self
+    }
+
+    #[cfg(feature = "encrypted-keys")]
+    /// Adds a `key_passphrase_file` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_passphrase_file` - The file containing the passphrase used to decrypt encrypted
+    ///    private key files
+    ///
+    pub fn with_key_passphrase_file(mut self, key_passphrase_file: Option<String>) -> Self {
+        self.key_passphrase_file = key_passphrase_file;
+        self
+    }
+
     /// Adds an `peering_key` value to the `PartialConfig` object.
     ///
     /// # Arguments
@@ -22,10 +22,18 @@ use serde::Deserialize as DeserializeTrait;
 use serde_derive::Deserialize;
 use std::collections::HashMap;
 use std::convert::TryInto;
-#[cfg(feature = "service2")]
+#[cfg(any(
+    feature = "service2",
+    feature = "circuit-guaranteed-delivery",
+    feature = "peer-retry-policy"
+))]
 use std::time::Duration;
 
 use super::logging::{UnnamedAppenderConfig, UnnamedLoggerConfig};
+#[cfg(feature = "circuit-rate-limiting")]
+use super::CircuitRateLimitConfig;
+#[cfg(feature = "peer-retry-policy")]
+use super::PeerRetryPolicyConfig;
 use super::ScabbardState;
 
 /// `TOML_VERSION` represents the version of the toml config file.
@@ -44,6 +52,46 @@ pub enum TomlRawLogTarget {
     RollingFile,
 }
 
+#[cfg(feature = "circuit-rate-limiting")]
+#[derive(Deserialize, Clone, Debug)]
+pub struct TomlCircuitRateLimit {
+    pub messages_per_second: u32,
+    pub burst: Option<u32>,
+}
+
+#[cfg(feature = "circuit-rate-limiting")]
+impl From<TomlCircuitRateLimit> for CircuitRateLimitConfig {
+    fn from(toml: TomlCircuitRateLimit) -> Self {
+        CircuitRateLimitConfig {
+            messages_per_second: toml.messages_per_second,
+            burst: toml.burst,
+        }
+    }
+}
+
+#[cfg(feature = "peer-retry-policy")]
+#[derive(Deserialize, Clone, Debug)]
+pub struct TomlPeerRetryPolicy {
+    pub initial_interval: Option<u64>,
+    pub max_interval: Option<u64>,
+    pub multiplier: Option<u32>,
+    pub jitter: Option<f64>,
+    pub max_attempts: Option<u64>,
+}
+
+#[cfg(feature = "peer-retry-policy")]
+impl From<TomlPeerRetryPolicy> for PeerRetryPolicyConfig {
+    fn from(toml: TomlPeerRetryPolicy) -> Self {
+        PeerRetryPolicyConfig {
+            initial_interval: toml.initial_interval.map(Duration::from_secs),
+            max_interval: toml.max_interval.map(Duration::from_secs),
+            multiplier: toml.multiplier,
+            jitter: toml.jitter,
+            max_attempts: toml.max_attempts,
+        }
+    }
+}
+
 #[derive(Deserialize, Clone, Debug)]
 pub struct TomlUnnamedAppenderConfig {
     #[serde(alias = "pattern")]
@@ -202,6 +250,32 @@ struct TomlConfig {
     influx_username: Option<String>,
     #[cfg(feature = "tap")]
     influx_password: Option<String>,
+    #[cfg(feature = "tap-prometheus")]
+    enable_prometheus_metrics: Option<bool>,
+    #[cfg(feature = "tap-prometheus")]
+    prometheus_histogram_buckets: Option<Vec<f64>>,
+    #[cfg(feature = "circuit-rate-limiting")]
+    circuit_rate_limit: Option<u32>,
+    #[cfg(feature = "circuit-rate-limiting")]
+    service_rate_limit: Option<u32>,
+    #[cfg(feature = "circuit-rate-limiting")]
+    circuit_rate_limit_burst: Option<u32>,
+    #[cfg(feature = "circuit-rate-limiting")]
+    service_rate_limit_burst: Option<u32>,
+    #[cfg(feature = "circuit-rate-limiting")]
+    circuit_rate_limits: Option<HashMap<String, TomlCircuitRateLimit>>,
+    #[cfg(feature = "peer-retry-policy")]
+    peer_retry_policies: Option<HashMap<String, TomlPeerRetryPolicy>>,
+    #[cfg(feature = "peer-retry-policy")]
+    endpoint_retry_policies: Option<HashMap<String, TomlPeerRetryPolicy>>,
+    #[cfg(feature = "circuit-guaranteed-delivery")]
+    guaranteed_delivery_message_ttl: Option<u64>,
+    #[cfg(feature = "registry-remote-signed")]
+    registry_trusted_keys: Option<Vec<String>>,
+    #[cfg(feature = "scabbard-state-pruning")]
+    scabbard_retained_state_roots: Option<usize>,
+    #[cfg(feature = "encrypted-keys")]
+    key_passphrase_file: Option<String>,
     peering_key: Option<String>,
     appenders: Option<HashMap<String, TomlUnnamedAppenderConfig>>,
     loggers: Option<HashMap<String, TomlUnnamedLoggerConfig>>,
@@ -345,6 +419,74 @@ impl PartialConfigBuilder for TomlPartialConfigBuilder {
                 .with_influx_password(self.toml_config.influx_password)
         }
 
+        #[cfg(feature = "tap-prometheus")]
+        {
+            partial_config = partial_config
+                .with_enable_prometheus_metrics(self.toml_config.enable_prometheus_metrics)
+                .with_prometheus_histogram_buckets(self.toml_config.prometheus_histogram_buckets)
+        }
+
+        #[cfg(feature = "circuit-rate-limiting")]
+        {
+            partial_config = partial_config
+                .with_circuit_rate_limit(self.toml_config.circuit_rate_limit)
+                .with_service_rate_limit(self.toml_config.service_rate_limit)
+                .with_circuit_rate_limit_burst(self.toml_config.circuit_rate_limit_burst)
+                .with_service_rate_limit_burst(self.toml_config.service_rate_limit_burst)
+                .with_circuit_rate_limits(self.toml_config.circuit_rate_limits.map(|limits| {
+                    limits
+                        .into_iter()
+                        .map(|(circuit_id, limit)| (circuit_id, limit.into()))
+                        .collect()
+                }))
+        }
+
+        #[cfg(feature = "peer-retry-policy")]
+        {
+            partial_config = partial_config
+                .with_peer_retry_policies(self.toml_config.peer_retry_policies.map(|policies| {
+                    policies
+                        .into_iter()
+                        .map(|(node_id, policy)| (node_id, policy.into()))
+                        .collect()
+                }))
+                .with_endpoint_retry_policies(self.toml_config.endpoint_retry_policies.map(
+                    |policies| {
+                        policies
+                            .into_iter()
+                            .map(|(endpoint, policy)| (endpoint, policy.into()))
+                            .collect()
+                    },
+                ))
+        }
+
+        #[cfg(feature = "circuit-guaranteed-delivery")]
+        {
+            partial_config = partial_config.with_guaranteed_delivery_message_ttl(
+                self.toml_config
+                    .guaranteed_delivery_message_ttl
+                    .map(Duration::from_secs),
+            );
+        }
+
+        #[cfg(feature = "registry-remote-signed")]
+        {
+            partial_config =
+                partial_config.with_registry_trusted_keys(self.toml_config.registry_trusted_keys)
+        }
+
+        #[cfg(feature = "scabbard-state-pruning")]
+        {
+            partial_config = partial_config
+                .with_scabbard_retained_state_roots(self.toml_config.scabbard_retained_state_roots)
+        }
+
+        #[cfg(feature = "encrypted-keys")]
+        {
+            partial_config =
+                partial_config.with_key_passphrase_file(self.toml_config.key_passphrase_file)
+        }
+
         #[cfg(feature = "service-timer-interval")]
         {
             partial_config = partial_config.with_service_timer_interval(
@@ -792,6 +934,28 @@ mod tests {
             influx_db = "database"
             influx_username = "username"
             influx_password = "pa$$w0rd"
+            enable_prometheus_metrics = true
+            prometheus_histogram_buckets = [0.1, 1.0, 10.0]
+            circuit_rate_limit = 500
+            service_rate_limit = 50
+            circuit_rate_limit_burst = 1000
+            service_rate_limit_burst = 100
+            guaranteed_delivery_message_ttl = 3600
+            registry_trusted_keys = ["0123"]
+            scabbard_retained_state_roots = 10
+            key_passphrase_file = "/etc/splinter/key-passphrase"
+            [circuit_rate_limits.abcde-01234]
+            messages_per_second = 2000
+            burst = 4000
+            [circuit_rate_limits.fghij-56789]
+            messages_per_second = 10
+            [peer_retry_policies.hub-node]
+            initial_interval = 1
+            max_interval = 30
+            jitter = 0.0
+            [endpoint_retry_policies."tcps://partner.splinter.dev:8044"]
+            multiplier = 3
+            max_attempts = 5
             [appenders.stdout]
             kind = "stdout"
             pattern = "[{d(%Y-%m-%d %H:%M:%S%.3f)}] T[{T}] {l} [{M}] {m}\n"
@@ -877,6 +1041,88 @@ mod tests {
             assert!(matches!(toml.influx_password() , Some(text) if text == "pa$$w0rd"));
         }
 
+        #[cfg(feature = "tap-prometheus")]
+        {
+            assert_eq!(toml.enable_prometheus_metrics(), Some(true));
+            assert_eq!(
+                toml.prometheus_histogram_buckets(),
+                Some(vec![0.1, 1.0, 10.0])
+            );
+        }
+
+        #[cfg(feature = "circuit-rate-limiting")]
+        {
+            assert_eq!(toml.circuit_rate_limit(), Some(500));
+            assert_eq!(toml.service_rate_limit(), Some(50));
+            assert_eq!(toml.circuit_rate_limit_burst(), Some(1000));
+            assert_eq!(toml.service_rate_limit_burst(), Some(100));
+            let circuit_rate_limits = toml
+                .circuit_rate_limits()
+                .expect("circuit_rate_limits not set");
+            assert_eq!(
+                circuit_rate_limits.get("abcde-01234"),
+                Some(&CircuitRateLimitConfig {
+                    messages_per_second: 2000,
+                    burst: Some(4000),
+                })
+            );
+            assert_eq!(
+                circuit_rate_limits.get("fghij-56789"),
+                Some(&CircuitRateLimitConfig {
+                    messages_per_second: 10,
+                    burst: None,
+                })
+            );
+        }
+
+        #[cfg(feature = "peer-retry-policy")]
+        {
+            let peer_retry_policies = toml
+                .peer_retry_policies()
+                .expect("peer_retry_policies not set");
+            assert_eq!(
+                peer_retry_policies.get("hub-node"),
+                Some(&PeerRetryPolicyConfig {
+                    initial_interval: Some(Duration::from_secs(1)),
+                    max_interval: Some(Duration::from_secs(30)),
+                    multiplier: None,
+                    jitter: Some(0.0),
+                    max_attempts: None,
+                })
+            );
+            let endpoint_retry_policies = toml
+                .endpoint_retry_policies()
+                .expect("endpoint_retry_policies not set");
+            assert_eq!(
+                endpoint_retry_policies.get("tcps://partner.splinter.dev:8044"),
+                Some(&PeerRetryPolicyConfig {
+                    initial_interval: None,
+                    max_interval: None,
+                    multiplier: Some(3),
+                    jitter: None,
+                    max_attempts: Some(5),
+                })
+            );
+        }
+
+        #[cfg(feature = "circuit-guaranteed-delivery")]
+        assert_eq!(
+            toml.guaranteed_delivery_message_ttl(),
+            Some(Duration::from_secs(3600))
+        );
+
+        #[cfg(feature = "registry-remote-signed")]
+        assert_eq!(toml.registry_trusted_keys(), Some(vec!["0123".to_string()]));
+
+        #[cfg(feature = "scabbard-state-pruning")]
+        assert_eq!(toml.scabbard_retained_state_roots(), Some(10));
+
+        #[cfg(feature = "encrypted-keys")]
+        assert_eq!(
+            toml.key_passphrase_file(),
+            Some("/etc/splinter/key-passphrase".to_string())
+        );
+
         let appenders = toml.appenders();
         assert!(appenders.is_some());
         let appenders = appenders.unwrap();
@@ -12,12 +12,28 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(any(feature = "circuit-rate-limiting", feature = "peer-retry-policy"))]
+use std::collections::HashMap;
 use std::time::Duration;
 
+#[cfg(feature = "registry-remote-signed")]
+use cylinder::PublicKey;
 use cylinder::Signer;
+#[cfg(feature = "circuit-rate-limiting")]
+use splinter::circuit::rate_limit::{CircuitRateLimiter, CircuitRateLimiterBuilder, RateLimit};
 use splinter::mesh::Mesh;
 use splinter::peer::PeerAuthorizationToken;
-
+#[cfg(feature = "peer-retry-policy")]
+use splinter::peer::RetryPolicy;
+#[cfg(feature = "tap-prometheus")]
+use splinter::tap::prometheus::PrometheusHandle;
+#[cfg(feature = "tls-reload")]
+use splinter::transport::tls::reload::TlsReloader;
+
+#[cfg(feature = "circuit-rate-limiting")]
+use crate::config::CircuitRateLimitConfig;
+#[cfg(feature = "peer-retry-policy")]
+use crate::config::PeerRetryPolicyConfig;
 use crate::daemon::error::CreateError;
 use crate::daemon::SplinterDaemon;
 
@@ -71,6 +87,30 @@ pub struct SplinterDaemonBuilder {
     service_timer_interval: Option<Duration>,
     #[cfg(feature = "service2")]
     lifecycle_executor_interval: Option<Duration>,
+    #[cfg(feature = "tap-prometheus")]
+    prometheus_handle: Option<PrometheusHandle>,
+    #[cfg(feature = "circuit-rate-limiting")]
+    circuit_rate_limit: Option<u32>,
+    #[cfg(feature = "circuit-rate-limiting")]
+    service_rate_limit: Option<u32>,
+    #[cfg(feature = "circuit-rate-limiting")]
+    circuit_rate_limit_burst: Option<u32>,
+    #[cfg(feature = "circuit-rate-limiting")]
+    service_rate_limit_burst: Option<u32>,
+    #[cfg(feature = "circuit-rate-limiting")]
+    circuit_rate_limits: HashMap<String, CircuitRateLimitConfig>,
+    #[cfg(feature = "peer-retry-policy")]
+    peer_retry_policies: HashMap<String, PeerRetryPolicyConfig>,
+    #[cfg(feature = "peer-retry-policy")]
+    endpoint_retry_policies: HashMap<String, PeerRetryPolicyConfig>,
+    #[cfg(feature = "circuit-guaranteed-delivery")]
+    guaranteed_delivery_message_ttl: Option<Duration>,
+    #[cfg(feature = "registry-remote-signed")]
+    registry_trusted_keys: Vec<String>,
+    #[cfg(feature = "scabbard-state-pruning")]
+    scabbard_retained_state_roots: Option<usize>,
+    #[cfg(feature = "tls-reload")]
+    tls_reloaders: Vec<Box<dyn TlsReloader>>,
 }
 
 impl SplinterDaemonBuilder {
@@ -269,6 +309,89 @@ impl SplinterDaemonBuilder {
         self
     }
 
+    #[cfg(feature = "tap-prometheus")]
+    pub fn with_prometheus_handle(mut self, value: Option<PrometheusHandle>) -> Self {
+        self.prometheus_handle = value;
+        self
+    }
+
+    #[cfg(feature = "circuit-rate-limiting")]
+    pub fn with_circuit_rate_limit(mut self, value: Option<u32>) -> Self {
+        self.circuit_rate_limit = value;
+        self
+    }
+
+    #[cfg(feature = "circuit-rate-limiting")]
+    pub fn with_service_rate_limit(mut self, value: Option<u32>) -> Self {
+        self.service_rate_limit = value;
+        self
+    }
+
+    #[cfg(feature = "circuit-rate-limiting")]
+    pub fn with_circuit_rate_limit_burst(mut self, value: Option<u32>) -> Self {
+        self.circuit_rate_limit_burst = value;
+        self
+    }
+
+    #[cfg(feature = "circuit-rate-limiting")]
+    pub fn with_service_rate_limit_burst(mut self, value: Option<u32>) -> Self {
+        self.service_rate_limit_burst = value;
+        self
+    }
+
+    #[cfg(feature = "circuit-rate-limiting")]
+    pub fn with_circuit_rate_limits(
+        mut self,
+        value: HashMap<String, CircuitRateLimitConfig>,
+    ) -> Self {
+        self.circuit_rate_limits = value;
+        self
+    }
+
+    #[cfg(feature = "peer-retry-policy")]
+    pub fn with_peer_retry_policies(
+        mut self,
+        value: HashMap<String, PeerRetryPolicyConfig>,
+    ) -> Self {
+        self.peer_retry_policies = value;
+        self
+    }
+
+    #[cfg(feature = "peer-retry-policy")]
+    pub fn with_endpoint_retry_policies(
+        mut self,
+        value: HashMap<String, PeerRetryPolicyConfig>,
+    ) -> Self {
+        self.endpoint_retry_policies = value;
+        self
+    }
+
+    #[cfg(feature = "circuit-guaranteed-delivery")]
+    pub fn with_guaranteed_delivery_message_ttl(mut self, value: Option<Duration>) -> Self {
+        self.guaranteed_delivery_message_ttl = value;
+        self
+    }
+
+    #[cfg(feature = "registry-remote-signed")]
+    pub fn with_registry_trusted_keys(mut self, value: Vec<String>) -> Self {
+        self.registry_trusted_keys = value;
+        self
+    }
+
+    #[cfg(feature = "scabbard-state-pruning")]
+    pub fn with_scabbard_retained_state_roots(mut self, value: Option<usize>) -> Self {
+        self.scabbard_retained_state_roots = value;
+        self
+    }
+
+    /// Sets the TLS contexts, in addition to the REST API's, that are reloaded when their files
+    /// change or when the daemon receives SIGHUP
+    #[cfg(feature = "tls-reload")]
+    pub fn with_tls_reloaders(mut self, value: Vec<Box<dyn TlsReloader>>) -> Self {
+        self.tls_reloaders = value;
+        self
+    }
+
     pub fn build(self) -> Result<SplinterDaemon, CreateError> {
         let heartbeat = self.heartbeat.ok_or_else(|| {
             CreateError::MissingRequiredField("Missing field: heartbeat".to_string())
@@ -385,6 +508,37 @@ impl SplinterDaemonBuilder {
             )
         })?;
 
+        #[cfg(feature = "circuit-rate-limiting")]
+        let rate_limiter = build_rate_limiter(
+            self.circuit_rate_limit,
+            self.circuit_rate_limit_burst,
+            self.service_rate_limit,
+            self.service_rate_limit_burst,
+            self.circuit_rate_limits,
+        )?;
+
+        #[cfg(feature = "peer-retry-policy")]
+        let peer_retry_policies =
+            build_retry_policies("peer_retry_policies", self.peer_retry_policies)?;
+
+        #[cfg(feature = "peer-retry-policy")]
+        let endpoint_retry_policies =
+            build_retry_policies("endpoint_retry_policies", self.endpoint_retry_policies)?;
+
+        #[cfg(feature = "registry-remote-signed")]
+        let registry_trusted_keys = self
+            .registry_trusted_keys
+            .iter()
+            .map(|key| {
+                PublicKey::new_from_hex(key).map_err(|err| {
+                    CreateError::InvalidArgument(format!(
+                        "invalid registry trusted key {}: {}",
+                        key, err
+                    ))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
         Ok(SplinterDaemon {
             #[cfg(feature = "authorization-handler-allow-keys")]
             config_dir,
@@ -433,6 +587,113 @@ impl SplinterDaemonBuilder {
             service_timer_interval,
             #[cfg(feature = "service2")]
             lifecycle_executor_interval,
+            #[cfg(feature = "tap-prometheus")]
+            prometheus_handle: self.prometheus_handle,
+            #[cfg(feature = "circuit-rate-limiting")]
+            rate_limiter,
+            #[cfg(feature = "peer-retry-policy")]
+            peer_retry_policies,
+            #[cfg(feature = "peer-retry-policy")]
+            endpoint_retry_policies,
+            #[cfg(feature = "circuit-guaranteed-delivery")]
+            guaranteed_delivery_message_ttl: self.guaranteed_delivery_message_ttl,
+            #[cfg(feature = "registry-remote-signed")]
+            registry_trusted_keys,
+            #[cfg(feature = "scabbard-state-pruning")]
+            scabbard_retained_state_roots: self.scabbard_retained_state_roots,
+            #[cfg(feature = "tls-reload")]
+            tls_reloaders: self.tls_reloaders,
         })
     }
 }
+
+/// Builds the rate limiter for circuit messages. The burst of each limit defaults to its rate.
+/// Returns `None` if no limits are set.
+#[cfg(feature = "circuit-rate-limiting")]
+fn build_rate_limiter(
+    circuit_rate_limit: Option<u32>,
+    circuit_rate_limit_burst: Option<u32>,
+    service_rate_limit: Option<u32>,
+    service_rate_limit_burst: Option<u32>,
+    circuit_rate_limits: HashMap<String, CircuitRateLimitConfig>,
+) -> Result<Option<CircuitRateLimiter>, CreateError> {
+    if circuit_rate_limit.is_none() && circuit_rate_limit_burst.is_some() {
+        return Err(CreateError::InvalidArgument(
+            "circuit_rate_limit_burst requires circuit_rate_limit".into(),
+        ));
+    }
+    if service_rate_limit.is_none() && service_rate_limit_burst.is_some() {
+        return Err(CreateError::InvalidArgument(
+            "service_rate_limit_burst requires service_rate_limit".into(),
+        ));
+    }
+
+    if circuit_rate_limit.is_none()
+        && service_rate_limit.is_none()
+        && circuit_rate_limits.is_empty()
+    {
+        return Ok(None);
+    }
+
+    let mut builder = CircuitRateLimiterBuilder::new();
+    if let Some(limit) = circuit_rate_limit {
+        let burst = circuit_rate_limit_burst.unwrap_or(limit);
+        builder = builder.with_circuit_limit(RateLimit::new(limit, burst).map_err(|err| {
+            CreateError::InvalidArgument(format!("invalid circuit_rate_limit: {}", err))
+        })?);
+    }
+    if let Some(limit) = service_rate_limit {
+        let burst = service_rate_limit_burst.unwrap_or(limit);
+        builder = builder.with_service_limit(RateLimit::new(limit, burst).map_err(|err| {
+            CreateError::InvalidArgument(format!("invalid service_rate_limit: {}", err))
+        })?);
+    }
+    for (circuit_id, limit) in circuit_rate_limits {
+        let burst = limit.burst.unwrap_or(limit.messages_per_second);
+        let rate_limit = RateLimit::new(limit.messages_per_second, burst).map_err(|err| {
+            CreateError::InvalidArgument(format!(
+                "invalid circuit_rate_limits for circuit {}: {}",
+                circuit_id, err
+            ))
+        })?;
+        builder = builder.with_circuit_limit_for(&circuit_id, rate_limit);
+    }
+
+    Ok(Some(builder.build()))
+}
+
+/// Builds the retry policies of a `peer_retry_policies` or `endpoint_retry_policies` setting,
+/// keeping the keys of the setting.
+#[cfg(feature = "peer-retry-policy")]
+fn build_retry_policies(
+    setting: &str,
+    policies: HashMap<String, PeerRetryPolicyConfig>,
+) -> Result<HashMap<String, RetryPolicy>, CreateError> {
+    policies
+        .into_iter()
+        .map(|(key, config)| {
+            let mut builder = RetryPolicy::builder();
+            if let Some(initial_interval) = config.initial_interval {
+                builder = builder.with_initial_interval(initial_interval);
+            }
+            if let Some(max_interval) = config.max_interval {
+                builder = builder.with_max_interval(max_interval);
+            }
+            if let Some(multiplier) = config.multiplier {
+                builder = builder.with_multiplier(multiplier);
+            }
+            if let Some(jitter) = config.jitter {
+                builder = builder.with_jitter(jitter);
+            }
+            if let Some(max_attempts) = config.max_attempts {
+                builder = builder.with_max_attempts(max_attempts);
+            }
+
+            let policy = builder.build().map_err(|err| {
+                CreateError::InvalidArgument(format!("invalid {} for {}: {}", setting, key, err))
+            })?;
+
+            Ok((key, policy))
+        })
+        .collect()
+}
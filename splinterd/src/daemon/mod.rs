@@ -40,14 +40,18 @@ use scabbard::service::ScabbardFactoryBuilder;
 use splinter::admin::lifecycle::sync::SyncLifecycleInterface;
 use splinter::admin::lifecycle::LifecycleDispatch;
 use splinter::admin::service::{admin_service_id, AdminService, AdminServiceBuilder};
-#[cfg(feature = "biome-credentials")]
+#[cfg(all(feature = "biome-credentials", not(feature = "rest-api-actix-web-4")))]
 use splinter::biome::credentials::rest_api::BiomeCredentialsRestResourceProviderBuilder;
-#[cfg(feature = "biome-profile")]
+#[cfg(all(feature = "biome-profile", not(feature = "rest-api-actix-web-4")))]
 use splinter::biome::profile::rest_api::BiomeProfileRestResourceProvider;
+#[cfg(feature = "circuit-guaranteed-delivery")]
+use splinter::circuit::delivery::{GuaranteedDeliveryManager, GuaranteedDeliveryQueue};
 use splinter::circuit::handlers::{
     AdminDirectMessageHandler, CircuitDirectMessageHandler, CircuitErrorHandler,
     CircuitMessageHandler, ServiceConnectRequestHandler, ServiceDisconnectRequestHandler,
 };
+#[cfg(feature = "circuit-rate-limiting")]
+use splinter::circuit::rate_limit::CircuitRateLimiter;
 use splinter::circuit::routing::{memory::RoutingTable, RoutingTableReader, RoutingTableWriter};
 #[cfg(feature = "service2")]
 use splinter::error::InternalError;
@@ -65,28 +69,39 @@ use splinter::peer::interconnect::NetworkMessageSender;
 use splinter::peer::interconnect::PeerInterconnectBuilder;
 use splinter::peer::PeerAuthorizationToken;
 use splinter::peer::PeerManager;
+use splinter::peer::PeerManagerConnector;
+#[cfg(feature = "peer-retry-policy")]
+use splinter::peer::RetryPolicy;
 use splinter::protos::circuit::CircuitMessageType;
 use splinter::protos::network::NetworkMessageType;
 use splinter::public_key::PublicKey;
 use splinter::registry::{
     LocalYamlRegistry, RegistryReader, RemoteYamlRegistry, RwRegistry, UnifiedRegistry,
 };
+#[cfg(feature = "registry-node-health")]
+use splinter::registry::{NodeHealthMonitor, NodeHealthTracker};
 #[cfg(feature = "authorization-handler-allow-keys")]
 use splinter::rest_api::auth::authorization::allow_keys::AllowKeysAuthorizationHandler;
 #[cfg(feature = "authorization-handler-maintenance")]
 use splinter::rest_api::auth::authorization::maintenance::MaintenanceModeAuthorizationHandler;
+#[cfg(all(
+    feature = "authorization-handler-rbac",
+    not(feature = "rest-api-actix-web-4")
+))]
+use splinter::rest_api::auth::authorization::rbac::rest_api::RoleBasedAuthorizationResourceProvider;
 #[cfg(feature = "authorization-handler-rbac")]
-use splinter::rest_api::auth::authorization::rbac::{
-    rest_api::RoleBasedAuthorizationResourceProvider, RoleBasedAuthorizationHandler,
-};
+use splinter::rest_api::auth::authorization::rbac::RoleBasedAuthorizationHandler;
 #[cfg(any(
     feature = "authorization-handler-rbac",
     feature = "authorization-handler-maintenance",
     feature = "authorization-handler-allow-keys"
 ))]
 use splinter::rest_api::auth::authorization::AuthorizationHandler;
-#[cfg(feature = "oauth")]
+#[cfg(feature = "rest-api-actix-web-4")]
+use splinter::rest_api::auth::identity::{cylinder::CylinderKeyIdentityProvider, IdentityProvider};
+#[cfg(all(feature = "oauth", not(feature = "rest-api-actix-web-4")))]
 use splinter::rest_api::OAuthConfig;
+#[cfg(not(feature = "rest-api-actix-web-4"))]
 use splinter::rest_api::{AuthConfig, RestApiBuilder, RestResourceProvider};
 use splinter::runtime::service::instance::{
     ServiceOrchestratorBuilder, ServiceProcessor, ServiceProcessorShutdownHandle,
@@ -99,21 +114,58 @@ use splinter::runtime::service::{
 use splinter::service::instance::ServiceArgValidator;
 #[cfg(any(feature = "scabbardv3", feature = "service-echo"))]
 use splinter::service::{MessageHandler, MessageHandlerFactory, ServiceType};
+#[cfg(feature = "tap-prometheus")]
+use splinter::tap::prometheus::PrometheusHandle;
 use splinter::threading::lifecycle::ShutdownHandle;
+#[cfg(feature = "tls-reload")]
+use splinter::transport::tls::reload::{
+    TlsReloadStatus, TlsReloadWatcher, TlsReloadWatcherBuilder, TlsReloader,
+};
 use splinter::transport::{
     inproc::InprocTransport, multi::MultiTransport, AcceptError, Connection, Incoming, Listener,
     Transport,
 };
 #[cfg(feature = "service-echo")]
 use splinter_echo::service::{EchoMessageByteConverter, EchoMessageHandlerFactory};
+#[cfg(not(feature = "rest-api-actix-web-4"))]
 use splinter_rest_api_actix_web_1::admin::{AdminServiceRestProvider, CircuitResourceProvider};
-#[cfg(feature = "biome-key-management")]
+#[cfg(all(feature = "rest-api-audit-log", not(feature = "rest-api-actix-web-4")))]
+use splinter_rest_api_actix_web_1::audit::AuditLogResourceProvider;
+#[cfg(all(
+    feature = "biome-key-management",
+    not(feature = "rest-api-actix-web-4")
+))]
 use splinter_rest_api_actix_web_1::biome::key_management::BiomeKeyManagementRestResourceProvider;
+#[cfg(all(feature = "tap-prometheus", not(feature = "rest-api-actix-web-4")))]
+use splinter_rest_api_actix_web_1::metrics::MetricsResourceProvider;
+#[cfg(not(feature = "rest-api-actix-web-4"))]
 use splinter_rest_api_actix_web_1::open_api;
+#[cfg(all(
+    any(feature = "peer-inspection", feature = "peer-retry-policy"),
+    not(feature = "rest-api-actix-web-4")
+))]
+use splinter_rest_api_actix_web_1::peer::PeerResourceProvider;
+#[cfg(not(feature = "rest-api-actix-web-4"))]
 use splinter_rest_api_actix_web_1::registry::RwRegistryRestResourceProvider;
+#[cfg(not(feature = "rest-api-actix-web-4"))]
 use splinter_rest_api_actix_web_1::scabbard::ScabbardServiceEndpointProvider;
+#[cfg(not(feature = "rest-api-actix-web-4"))]
 use splinter_rest_api_actix_web_1::service::ServiceOrchestratorRestResourceProviderBuilder;
+#[cfg(not(feature = "rest-api-actix-web-4"))]
 use splinter_rest_api_actix_web_1::status;
+#[cfg(all(feature = "biome-credentials", feature = "rest-api-actix-web-4"))]
+use splinter_rest_api_actix_web_4::biome::credentials::BiomeCredentialsRestResourceProviderBuilder;
+#[cfg(all(feature = "biome-key-management", feature = "rest-api-actix-web-4"))]
+use splinter_rest_api_actix_web_4::biome::key_management::BiomeKeyManagementRestResourceProvider;
+#[cfg(all(feature = "tap-prometheus", feature = "rest-api-actix-web-4"))]
+use splinter_rest_api_actix_web_4::metrics::MetricsResourceProvider;
+#[cfg(feature = "rest-api-actix-web-4")]
+use splinter_rest_api_actix_web_4::{
+    admin::{AdminServiceRestProvider, CircuitResourceProvider},
+    registry::RwRegistryRestResourceProvider,
+    scabbard::ScabbardRestResourceProvider,
+    status, RestApiBuilder, RestResourceProvider,
+};
 
 use crate::node_id::get_node_id;
 
@@ -185,6 +237,24 @@ pub struct SplinterDaemon {
     service_timer_interval: Duration,
     #[cfg(feature = "service2")]
     lifecycle_executor_interval: Duration,
+    #[cfg(feature = "tap-prometheus")]
+    prometheus_handle: Option<PrometheusHandle>,
+    #[cfg(feature = "circuit-rate-limiting")]
+    rate_limiter: Option<CircuitRateLimiter>,
+    // Retry policies for circuit members, by node ID
+    #[cfg(feature = "peer-retry-policy")]
+    peer_retry_policies: HashMap<String, RetryPolicy>,
+    // Retry policies for the initial peers, by their entry in the initial peers
+    #[cfg(feature = "peer-retry-policy")]
+    endpoint_retry_policies: HashMap<String, RetryPolicy>,
+    #[cfg(feature = "circuit-guaranteed-delivery")]
+    guaranteed_delivery_message_ttl: Option<Duration>,
+    #[cfg(feature = "registry-remote-signed")]
+    registry_trusted_keys: Vec<cylinder::PublicKey>,
+    #[cfg(feature = "scabbard-state-pruning")]
+    scabbard_retained_state_roots: Option<usize>,
+    #[cfg(feature = "tls-reload")]
+    tls_reloaders: Vec<Box<dyn TlsReloader>>,
 }
 
 impl SplinterDaemon {
@@ -370,6 +440,25 @@ impl SplinterDaemon {
 
         let network_sender = interconnect.new_network_sender();
 
+        #[cfg(feature = "circuit-guaranteed-delivery")]
+        let mut delivery_manager_builder = GuaranteedDeliveryManager::builder()
+            .with_node_id(node_id.to_string())
+            .with_store(store_factory.get_pending_message_store())
+            .with_routing_table(routing_reader.clone())
+            .with_peer_connector(peer_connector.clone())
+            .with_message_sender(Box::new(network_sender.clone()));
+        #[cfg(feature = "circuit-guaranteed-delivery")]
+        if let Some(message_ttl) = self.guaranteed_delivery_message_ttl {
+            delivery_manager_builder = delivery_manager_builder.with_message_ttl(message_ttl);
+        }
+        #[cfg(feature = "circuit-guaranteed-delivery")]
+        let mut delivery_manager = delivery_manager_builder.start().map_err(|err| {
+            StartError::NetworkError(format!(
+                "Unable to start guaranteed delivery manager: {}",
+                err
+            ))
+        })?;
+
         #[cfg(feature = "service2")]
         let mut message_handler_task_pool = MessageHandlerTaskPoolBuilder::new()
             .with_size(8)
@@ -430,6 +519,11 @@ impl SplinterDaemon {
             message_handlers,
             #[cfg(feature = "service2")]
             message_handler_task_pool.task_runner(),
+            #[cfg(feature = "circuit-guaranteed-delivery")]
+            delivery_manager.queue(),
+            #[cfg(feature = "circuit-rate-limiting")]
+            self.rate_limiter.clone(),
+            peer_connector.clone(),
         );
         let mut circuit_dispatch_loop = DispatchLoopBuilder::new()
             .with_dispatcher(circuit_dispatcher)
@@ -442,12 +536,22 @@ impl SplinterDaemon {
 
         #[cfg(not(feature = "service2"))]
         // Set up the Network dispatcher
-        let network_dispatcher =
-            set_up_network_dispatcher(network_sender, &node_id, circuit_dispatch_sender);
+        let network_dispatcher = set_up_network_dispatcher(
+            network_sender,
+            &node_id,
+            circuit_dispatch_sender,
+            #[cfg(feature = "peer-inspection")]
+            peer_connector.clone(),
+        );
         #[cfg(feature = "service2")]
         // Set up the Network dispatcher
-        let network_dispatcher =
-            set_up_network_dispatcher(network_sender, &node_id, circuit_dispatch_sender);
+        let network_dispatcher = set_up_network_dispatcher(
+            network_sender,
+            &node_id,
+            circuit_dispatch_sender,
+            #[cfg(feature = "peer-inspection")]
+            peer_connector.clone(),
+        );
 
         let mut network_dispatch_loop = DispatchLoopBuilder::new()
             .with_dispatcher(network_dispatcher)
@@ -507,9 +611,20 @@ impl SplinterDaemon {
 
         // hold on to peer refs for the peers provided to ensure the connections are kept around
         let mut peer_refs = vec![];
-        for endpoint in self.initial_peers.iter() {
-            let (endpoint, token) = parse_peer_endpoint(endpoint, &self.peering_token, &node_id);
-            match peer_connector.add_unidentified_peer(endpoint, token) {
+        for peer in self.initial_peers.iter() {
+            let (endpoint, token) = parse_peer_endpoint(peer, &self.peering_token, &node_id);
+            #[cfg(feature = "peer-retry-policy")]
+            let result = match self.endpoint_retry_policies.get(peer) {
+                Some(retry_policy) => peer_connector.add_unidentified_peer_with_retry_policy(
+                    endpoint,
+                    token,
+                    retry_policy.clone(),
+                ),
+                None => peer_connector.add_unidentified_peer(endpoint, token),
+            };
+            #[cfg(not(feature = "peer-retry-policy"))]
+            let result = peer_connector.add_unidentified_peer(endpoint, token);
+            match result {
                 Ok(peer_ref) => peer_refs.push(peer_ref),
                 Err(err) => error!("Connect Error: {}", err),
             }
@@ -547,6 +662,12 @@ impl SplinterDaemon {
             .with_lmdb_state_enabled(self.enable_lmdb_state)
             .with_state_autocleanup_enabled(self.enable_state_autocleanup);
 
+        #[cfg(feature = "scabbard-state-pruning")]
+        if let Some(retained_state_roots) = self.scabbard_retained_state_roots {
+            scabbard_factory_builder =
+                scabbard_factory_builder.with_retained_state_roots(retained_state_roots);
+        }
+
         let scabbard_factory = scabbard_factory_builder
             .build()
             .map_err(|err| StartError::UserError(err.to_string()))?;
@@ -563,6 +684,7 @@ impl SplinterDaemon {
                 StartError::OrchestratorError(format!("failed to start orchestrator: {}", err))
             })?;
 
+        #[cfg(not(feature = "rest-api-actix-web-4"))]
         let orchestrator_resources = ServiceOrchestratorRestResourceProviderBuilder::new()
             .with_endpoint_factory(
                 scabbard::service::SERVICE_TYPE,
@@ -570,6 +692,8 @@ impl SplinterDaemon {
             )
             .build(&orchestrator)
             .resources();
+        #[cfg(feature = "rest-api-actix-web-4")]
+        let scabbard_resources = ScabbardRestResourceProvider::new(&orchestrator).resources();
         let mut orchestator_shutdown_handle =
             orchestrator.take_shutdown_handle().ok_or_else(|| {
                 StartError::OrchestratorError(
@@ -577,12 +701,34 @@ impl SplinterDaemon {
                 )
             })?;
 
+        #[cfg(feature = "registry-node-health")]
+        let health_tracker = NodeHealthTracker::new();
+
         let (registry, mut registry_shutdown) = create_registry(
             &self.state_dir,
             &self.registries,
             self.registry_auto_refresh,
             self.registry_forced_refresh,
             &*store_factory,
+            #[cfg(feature = "registry-node-health")]
+            health_tracker.clone(),
+            #[cfg(feature = "registry-remote-signed")]
+            &self.registry_trusted_keys,
+        );
+
+        #[cfg(feature = "registry-node-health")]
+        registry_shutdown.set_node_health_monitor(
+            NodeHealthMonitor::start(
+                health_tracker,
+                peer_connector.clone(),
+                registry.clone_box_as_reader(),
+            )
+            .map_err(|err| {
+                StartError::NetworkError(format!(
+                    "Unable to start registry node health monitor: {}",
+                    err
+                ))
+            })?,
         );
 
         let mut admin_service_builder = AdminServiceBuilder::new();
@@ -610,7 +756,7 @@ impl SplinterDaemon {
         admin_service_builder = admin_service_builder
             .with_node_id(node_id.clone())
             .with_lifecycle_dispatch(lifecycle_dispatches)
-            .with_peer_manager_connector(peer_connector)
+            .with_peer_manager_connector(peer_connector.clone())
             .with_admin_service_store(store_factory.get_admin_service_store())
             .with_signature_verifier(admin_service_verifier)
             .with_admin_key_verifier(Box::new(registry.clone_box_as_reader()))
@@ -632,6 +778,17 @@ impl SplinterDaemon {
                     })?,
             );
 
+        #[cfg(feature = "circuit-rate-limiting")]
+        if let Some(rate_limiter) = &self.rate_limiter {
+            admin_service_builder = admin_service_builder.with_rate_limiter(rate_limiter.clone());
+        }
+
+        #[cfg(feature = "peer-retry-policy")]
+        {
+            admin_service_builder =
+                admin_service_builder.with_peer_retry_policies(self.peer_retry_policies.clone());
+        }
+
         let mut validators: HashMap<String, Box<dyn ServiceArgValidator + Send>> = HashMap::new();
         validators.insert("scabbard".into(), Box::new(ScabbardArgValidator));
 
@@ -663,220 +820,454 @@ impl SplinterDaemon {
         #[cfg(feature = "https-bind")]
         let bind = self.build_rest_api_bind()?;
 
-        // Allowing unused_mut because rest_api_builder must be mutable if feature biome is enabled
+        // Allowing unused_mut because tls_reloaders must be mutable if feature https-bind is
+        // enabled
+        #[cfg(feature = "tls-reload")]
         #[allow(unused_mut)]
-        let mut rest_api_builder = RestApiBuilder::new()
-            .with_bind(bind)
-            .add_resources(AdminServiceRestProvider::new(&admin_service).resources())
-            .add_resources(RwRegistryRestResourceProvider::new(&registry).resources())
-            .add_resources(orchestrator_resources)
-            .add_resources(circuit_resource_provider.resources())
-            .add_resources(
-                status::StatusResourceProvider::new(
-                    node_id,
-                    display_name,
-                    #[cfg(feature = "service-endpoint")]
-                    service_endpoint,
-                    network_endpoints,
-                    advertised_endpoints,
-                )
-                .resources(),
-            )
-            .add_resources(open_api::OpenApiResourceProvider::default().resources());
+        let mut tls_reloaders = std::mem::take(&mut self.tls_reloaders);
+        #[cfg(feature = "tls-reload")]
+        let tls_reload_status = TlsReloadStatus::new();
 
-        #[cfg(feature = "authorization")]
-        {
-            // Allowing unused_mut because authorization_handlers must be mutable if
-            // `authorization-handler-allow-keys` or `auth-handler-maintenance` are enabled
+        #[cfg(not(feature = "rest-api-actix-web-4"))]
+        let (rest_api_shutdown_handle, rest_api_join_handle) = {
+            let status_resource_provider = status::StatusResourceProvider::new(
+                node_id,
+                display_name,
+                #[cfg(feature = "service-endpoint")]
+                service_endpoint,
+                network_endpoints,
+                advertised_endpoints,
+            );
+            #[cfg(feature = "tls-reload")]
+            let status_resource_provider =
+                status_resource_provider.with_tls_reload_status(tls_reload_status.clone());
+
+            // Allowing unused_mut because rest_api_builder must be mutable if feature biome is enabled
             #[allow(unused_mut)]
-            let mut authorization_handlers = vec![
-                #[cfg(feature = "authorization-handler-allow-keys")]
-                create_allow_keys_authorization_handler(
-                    create_allow_keys_path(
-                        &self.config_dir,
-                        #[cfg(feature = "config-allow-keys")]
-                        &self.allow_keys_file,
-                        #[cfg(not(feature = "config-allow-keys"))]
-                        "allow_keys",
-                    )
-                    .to_str()
-                    .expect("path built from &str cannot be invalid"),
-                )?,
-            ];
+            let mut rest_api_builder = RestApiBuilder::new()
+                .with_bind(bind)
+                .add_resources(AdminServiceRestProvider::new(&admin_service).resources())
+                .add_resources(RwRegistryRestResourceProvider::new(&registry).resources())
+                .add_resources(orchestrator_resources)
+                .add_resources(circuit_resource_provider.resources())
+                .add_resources(status_resource_provider.resources())
+                .add_resources(open_api::OpenApiResourceProvider::default().resources());
+
+            #[cfg(feature = "authorization")]
+            {
+                // Allowing unused_mut because authorization_handlers must be mutable if
+                // `authorization-handler-allow-keys` or `auth-handler-maintenance` are enabled
+                #[allow(unused_mut)]
+                let mut authorization_handlers = vec![
+                    #[cfg(feature = "authorization-handler-allow-keys")]
+                    create_allow_keys_authorization_handler(
+                        create_allow_keys_path(
+                            &self.config_dir,
+                            #[cfg(feature = "config-allow-keys")]
+                            &self.allow_keys_file,
+                            #[cfg(not(feature = "config-allow-keys"))]
+                            "allow_keys",
+                        )
+                        .to_str()
+                        .expect("path built from &str cannot be invalid"),
+                    )?,
+                ];
+
+                #[cfg(feature = "authorization-handler-rbac")]
+                let rbac_store = store_factory.get_role_based_authorization_store();
 
-            #[cfg(feature = "authorization-handler-rbac")]
-            let rbac_store = store_factory.get_role_based_authorization_store();
+                #[cfg(feature = "authorization-handler-maintenance")]
+                {
+                    #[cfg(feature = "authorization-handler-rbac")]
+                    let maintenance_mode_auth_handler =
+                        MaintenanceModeAuthorizationHandler::new(Some(rbac_store.clone()));
+                    #[cfg(not(feature = "authorization-handler-rbac"))]
+                    let maintenance_mode_auth_handler =
+                        MaintenanceModeAuthorizationHandler::default();
+                    rest_api_builder =
+                        rest_api_builder.add_resources(maintenance_mode_auth_handler.resources());
+                    authorization_handlers.push(Box::new(maintenance_mode_auth_handler));
+                }
 
-            #[cfg(feature = "authorization-handler-maintenance")]
-            {
                 #[cfg(feature = "authorization-handler-rbac")]
-                let maintenance_mode_auth_handler =
-                    MaintenanceModeAuthorizationHandler::new(Some(rbac_store.clone()));
-                #[cfg(not(feature = "authorization-handler-rbac"))]
-                let maintenance_mode_auth_handler = MaintenanceModeAuthorizationHandler::default();
+                {
+                    #[cfg(feature = "authorization-handler-rbac-scopes")]
+                    let rbac_handler = RoleBasedAuthorizationHandler::new(rbac_store)
+                        .with_admin_store(store_factory.get_admin_service_store());
+                    #[cfg(not(feature = "authorization-handler-rbac-scopes"))]
+                    let rbac_handler = RoleBasedAuthorizationHandler::new(rbac_store);
+                    authorization_handlers.push(Box::new(rbac_handler));
+                    rest_api_builder = rest_api_builder.add_resources(
+                        RoleBasedAuthorizationResourceProvider::new(
+                            store_factory.get_role_based_authorization_store(),
+                        )
+                        .resources(),
+                    );
+                }
+
                 rest_api_builder =
-                    rest_api_builder.add_resources(maintenance_mode_auth_handler.resources());
-                authorization_handlers.push(Box::new(maintenance_mode_auth_handler));
+                    rest_api_builder.with_authorization_handlers(authorization_handlers)
             }
 
-            #[cfg(feature = "authorization-handler-rbac")]
+            #[cfg(feature = "rest-api-cors")]
+            {
+                if let Some(list) = &self.allow_list {
+                    debug!("Allow listed domains added to CORS");
+                    rest_api_builder = rest_api_builder.with_allow_list(list.to_vec());
+                }
+            }
+
+            #[allow(unused_mut)]
+            let mut auth_configs = vec![
+                // Add Cylinder JWT as an auth provider
+                AuthConfig::Cylinder {
+                    verifier: auth_config_verifier,
+                },
+            ];
+
+            // Add Biome credentials as an auth provider if it's enabled
+            #[cfg(feature = "biome-credentials")]
+            if self.enable_biome_credentials {
+                let mut biome_credentials_builder: BiomeCredentialsRestResourceProviderBuilder =
+                    Default::default();
+
+                biome_credentials_builder = biome_credentials_builder
+                    .with_refresh_token_store(store_factory.get_biome_refresh_token_store())
+                    .with_credentials_store(store_factory.get_biome_credentials_store());
+
+                #[cfg(feature = "biome-key-management")]
+                {
+                    biome_credentials_builder = biome_credentials_builder
+                        .with_key_store(store_factory.get_biome_key_store())
+                }
+
+                let biome_credentials_resource_provider =
+                    biome_credentials_builder.build().map_err(|err| {
+                        StartError::RestApiError(format!(
+                            "Unable to build Biome credentials REST routes: {}",
+                            err
+                        ))
+                    })?;
+
+                auth_configs.push(AuthConfig::Biome {
+                    biome_credentials_resource_provider,
+                });
+            }
+
+            #[cfg(feature = "oauth")]
+            {
+                // Handle OAuth config. If no OAuth config values are provided, just skip this;
+                // otherwise, require that all are set.
+                let any_oauth_args_provided = self.oauth_provider.is_some()
+                    || self.oauth_client_id.is_some()
+                    || self.oauth_client_secret.is_some()
+                    || self.oauth_redirect_url.is_some();
+                if any_oauth_args_provided {
+                    let oauth_provider = self.oauth_provider.as_deref().ok_or_else(|| {
+                        StartError::RestApiError("missing OAuth provider configuration".into())
+                    })?;
+                    let client_id = self.oauth_client_id.clone().ok_or_else(|| {
+                        StartError::RestApiError("missing OAuth client ID configuration".into())
+                    })?;
+                    let client_secret = self.oauth_client_secret.clone().ok_or_else(|| {
+                        StartError::RestApiError("missing OAuth client secret configuration".into())
+                    })?;
+                    let redirect_url = self.oauth_redirect_url.clone().ok_or_else(|| {
+                        StartError::RestApiError("missing OAuth redirect URL configuration".into())
+                    })?;
+                    let oauth_config = match oauth_provider {
+                        "azure" => OAuthConfig::Azure {
+                            client_id,
+                            client_secret,
+                            redirect_url,
+                            oauth_openid_url: self.oauth_openid_url.clone().ok_or_else(|| {
+                                StartError::RestApiError(
+                                    "missing OAuth OpenID discovery document URL configuration"
+                                        .into(),
+                                )
+                            })?,
+                            inflight_request_store: store_factory
+                                .get_oauth_inflight_request_store(),
+                        },
+                        "github" => OAuthConfig::GitHub {
+                            client_id,
+                            client_secret,
+                            redirect_url,
+                            inflight_request_store: store_factory
+                                .get_oauth_inflight_request_store(),
+                        },
+                        "google" => OAuthConfig::Google {
+                            client_id,
+                            client_secret,
+                            redirect_url,
+                            inflight_request_store: store_factory
+                                .get_oauth_inflight_request_store(),
+                        },
+                        "openid" => OAuthConfig::OpenId {
+                            client_id,
+                            client_secret,
+                            redirect_url,
+                            oauth_openid_url: self.oauth_openid_url.clone().ok_or_else(|| {
+                                StartError::RestApiError(
+                                    "missing OAuth OpenID discovery document URL configuration"
+                                        .into(),
+                                )
+                            })?,
+                            auth_params: self.oauth_openid_auth_params.clone(),
+                            scopes: self.oauth_openid_scopes.clone(),
+                            inflight_request_store: store_factory
+                                .get_oauth_inflight_request_store(),
+                        },
+                        other_provider => {
+                            return Err(StartError::RestApiError(format!(
+                                "invalid OAuth provider: {}",
+                                other_provider
+                            )))
+                        }
+                    };
+
+                    auth_configs.push(AuthConfig::OAuth {
+                        oauth_config,
+                        oauth_user_session_store: store_factory
+                            .get_biome_oauth_user_session_store(),
+                        #[cfg(feature = "biome-profile")]
+                        user_profile_store: store_factory.get_biome_user_profile_store(),
+                    });
+                }
+            }
+
+            rest_api_builder = rest_api_builder.with_auth_configs(auth_configs);
+
+            #[cfg(feature = "biome-key-management")]
             {
-                authorization_handlers
-                    .push(Box::new(RoleBasedAuthorizationHandler::new(rbac_store)));
                 rest_api_builder = rest_api_builder.add_resources(
-                    RoleBasedAuthorizationResourceProvider::new(
-                        store_factory.get_role_based_authorization_store(),
-                    )
+                    BiomeKeyManagementRestResourceProvider::new(Arc::new(
+                        store_factory.get_biome_key_store(),
+                    ))
                     .resources(),
                 );
             }
 
-            rest_api_builder = rest_api_builder.with_authorization_handlers(authorization_handlers)
-        }
+            #[cfg(feature = "biome-profile")]
+            {
+                rest_api_builder = rest_api_builder.add_resources(
+                    BiomeProfileRestResourceProvider::new(Arc::new(
+                        store_factory.get_biome_user_profile_store(),
+                    ))
+                    .resources(),
+                );
+            }
 
-        #[cfg(feature = "rest-api-cors")]
-        {
-            if let Some(list) = &self.allow_list {
-                debug!("Allow listed domains added to CORS");
-                rest_api_builder = rest_api_builder.with_allow_list(list.to_vec());
+            #[cfg(feature = "tap-prometheus")]
+            if let Some(prometheus_handle) = self.prometheus_handle.clone() {
+                rest_api_builder = rest_api_builder
+                    .add_resources(MetricsResourceProvider::new(prometheus_handle).resources());
             }
-        }
 
-        #[allow(unused_mut)]
-        let mut auth_configs = vec![
-            // Add Cylinder JWT as an auth provider
-            AuthConfig::Cylinder {
-                verifier: auth_config_verifier,
-            },
-        ];
+            #[cfg(any(feature = "peer-inspection", feature = "peer-retry-policy"))]
+            {
+                rest_api_builder = rest_api_builder.add_resources(
+                    PeerResourceProvider::new(
+                        peer_connector,
+                        #[cfg(feature = "peer-inspection")]
+                        connection_connector.clone(),
+                    )
+                    .resources(),
+                );
+            }
 
-        // Add Biome credentials as an auth provider if it's enabled
-        #[cfg(feature = "biome-credentials")]
-        if self.enable_biome_credentials {
-            let mut biome_credentials_builder: BiomeCredentialsRestResourceProviderBuilder =
-                Default::default();
+            #[cfg(feature = "rest-api-audit-log")]
+            {
+                rest_api_builder = rest_api_builder
+                    .with_audit_log_store(store_factory.get_audit_log_store())
+                    .add_resources(
+                        AuditLogResourceProvider::new(store_factory.get_audit_log_store())
+                            .resources(),
+                    );
+            }
 
-            biome_credentials_builder = biome_credentials_builder
-                .with_refresh_token_store(store_factory.get_biome_refresh_token_store())
-                .with_credentials_store(store_factory.get_biome_credentials_store());
+            let rest_api = rest_api_builder.build()?;
 
-            #[cfg(feature = "biome-key-management")]
-            {
-                biome_credentials_builder =
-                    biome_credentials_builder.with_key_store(store_factory.get_biome_key_store())
+            #[cfg(all(feature = "https-bind", feature = "tls-reload"))]
+            if let Some(reloader) = rest_api.tls_reloader() {
+                tls_reloaders.push(Box::new(reloader));
             }
 
-            let biome_credentials_resource_provider =
-                biome_credentials_builder.build().map_err(|err| {
-                    StartError::RestApiError(format!(
-                        "Unable to build Biome credentials REST routes: {}",
-                        err
-                    ))
-                })?;
+            rest_api.run()?
+        };
 
-            auth_configs.push(AuthConfig::Biome {
-                biome_credentials_resource_provider,
-            });
-        }
+        #[cfg(feature = "rest-api-actix-web-4")]
+        let (rest_api_shutdown_handle, rest_api_join_handle) = {
+            #[cfg(feature = "rest-api-cors")]
+            if self.allow_list.is_some() {
+                warn!("CORS is not supported by the Actix Web v4 REST API; ignoring allow list");
+            }
 
-        #[cfg(feature = "oauth")]
-        {
-            // Handle OAuth config. If no OAuth config values are provided, just skip this;
-            // otherwise, require that all are set.
-            let any_oauth_args_provided = self.oauth_provider.is_some()
+            #[cfg(feature = "oauth")]
+            if self.oauth_provider.is_some()
                 || self.oauth_client_id.is_some()
                 || self.oauth_client_secret.is_some()
-                || self.oauth_redirect_url.is_some();
-            if any_oauth_args_provided {
-                let oauth_provider = self.oauth_provider.as_deref().ok_or_else(|| {
-                    StartError::RestApiError("missing OAuth provider configuration".into())
-                })?;
-                let client_id = self.oauth_client_id.clone().ok_or_else(|| {
-                    StartError::RestApiError("missing OAuth client ID configuration".into())
-                })?;
-                let client_secret = self.oauth_client_secret.clone().ok_or_else(|| {
-                    StartError::RestApiError("missing OAuth client secret configuration".into())
-                })?;
-                let redirect_url = self.oauth_redirect_url.clone().ok_or_else(|| {
-                    StartError::RestApiError("missing OAuth redirect URL configuration".into())
-                })?;
-                let oauth_config = match oauth_provider {
-                    "azure" => OAuthConfig::Azure {
-                        client_id,
-                        client_secret,
-                        redirect_url,
-                        oauth_openid_url: self.oauth_openid_url.clone().ok_or_else(|| {
-                            StartError::RestApiError(
-                                "missing OAuth OpenID discovery document URL configuration".into(),
-                            )
-                        })?,
-                        inflight_request_store: store_factory.get_oauth_inflight_request_store(),
-                    },
-                    "github" => OAuthConfig::GitHub {
-                        client_id,
-                        client_secret,
-                        redirect_url,
-                        inflight_request_store: store_factory.get_oauth_inflight_request_store(),
-                    },
-                    "google" => OAuthConfig::Google {
-                        client_id,
-                        client_secret,
-                        redirect_url,
-                        inflight_request_store: store_factory.get_oauth_inflight_request_store(),
-                    },
-                    "openid" => OAuthConfig::OpenId {
-                        client_id,
-                        client_secret,
-                        redirect_url,
-                        oauth_openid_url: self.oauth_openid_url.clone().ok_or_else(|| {
-                            StartError::RestApiError(
-                                "missing OAuth OpenID discovery document URL configuration".into(),
-                            )
-                        })?,
-                        auth_params: self.oauth_openid_auth_params.clone(),
-                        scopes: self.oauth_openid_scopes.clone(),
-                        inflight_request_store: store_factory.get_oauth_inflight_request_store(),
-                    },
-                    other_provider => {
-                        return Err(StartError::RestApiError(format!(
-                            "invalid OAuth provider: {}",
-                            other_provider
-                        )))
-                    }
-                };
-
-                auth_configs.push(AuthConfig::OAuth {
-                    oauth_config,
-                    oauth_user_session_store: store_factory.get_biome_oauth_user_session_store(),
-                    #[cfg(feature = "biome-profile")]
-                    user_profile_store: store_factory.get_biome_user_profile_store(),
-                });
+                || self.oauth_redirect_url.is_some()
+                || self.oauth_openid_url.is_some()
+                || self.oauth_openid_auth_params.is_some()
+                || self.oauth_openid_scopes.is_some()
+            {
+                return Err(StartError::RestApiError(
+                    "OAuth is not supported by the Actix Web v4 REST API".into(),
+                ));
             }
-        }
 
-        rest_api_builder = rest_api_builder.with_auth_configs(auth_configs);
+            check_actix_web_4_features()?;
 
-        #[cfg(feature = "biome-key-management")]
-        {
-            rest_api_builder = rest_api_builder.add_resources(
-                BiomeKeyManagementRestResourceProvider::new(Arc::new(
-                    store_factory.get_biome_key_store(),
-                ))
-                .resources(),
-            );
-        }
+            #[cfg(not(feature = "https-bind"))]
+            let bind = splinter::rest_api::BindConfig::Http(bind.into());
 
-        #[cfg(feature = "biome-profile")]
-        {
-            rest_api_builder = rest_api_builder.add_resources(
-                BiomeProfileRestResourceProvider::new(Arc::new(
-                    store_factory.get_biome_user_profile_store(),
-                ))
-                .resources(),
+            let status_resource_provider = status::StatusResourceProvider::new(
+                node_id,
+                display_name,
+                #[cfg(feature = "service-endpoint")]
+                service_endpoint,
+                network_endpoints,
+                advertised_endpoints,
             );
-        }
+            #[cfg(feature = "tls-reload")]
+            let status_resource_provider =
+                status_resource_provider.with_tls_reload_status(tls_reload_status.clone());
 
-        let (rest_api_shutdown_handle, rest_api_join_handle) = rest_api_builder.build()?.run()?;
+            // Allowing unused_mut because identity_providers must be mutable if feature
+            // biome-credentials is enabled
+            #[allow(unused_mut)]
+            let mut identity_providers: Vec<Box<dyn IdentityProvider>> = vec![Box::new(
+                CylinderKeyIdentityProvider::new(Arc::new(Mutex::new(auth_config_verifier))),
+            )];
+
+            // Add Biome credentials as an identity provider if it's enabled
+            #[cfg(feature = "biome-credentials")]
+            let biome_credentials_resources = if self.enable_biome_credentials {
+                #[allow(unused_mut)]
+                let mut biome_credentials_builder =
+                    BiomeCredentialsRestResourceProviderBuilder::default()
+                        .with_refresh_token_store(store_factory.get_biome_refresh_token_store())
+                        .with_credentials_store(store_factory.get_biome_credentials_store());
+
+                #[cfg(feature = "biome-key-management")]
+                {
+                    biome_credentials_builder = biome_credentials_builder
+                        .with_key_store(store_factory.get_biome_key_store())
+                }
+
+                let biome_credentials_resource_provider =
+                    biome_credentials_builder.build().map_err(|err| {
+                        StartError::RestApiError(format!(
+                            "Unable to build Biome credentials REST routes: {}",
+                            err
+                        ))
+                    })?;
+
+                identity_providers.push(Box::new(
+                    biome_credentials_resource_provider.get_identity_provider(),
+                ));
+                biome_credentials_resource_provider.resources()
+            } else {
+                vec![]
+            };
+
+            // Allowing unused_mut because rest_api_builder must be mutable if feature biome is
+            // enabled
+            #[allow(unused_mut)]
+            let mut rest_api_builder = RestApiBuilder::new()
+                .with_bind(bind)
+                .add_resources(AdminServiceRestProvider::new(&admin_service).resources())
+                .add_resources(RwRegistryRestResourceProvider::new(&registry).resources())
+                .add_resources(scabbard_resources)
+                .add_resources(circuit_resource_provider.resources())
+                .add_resources(status_resource_provider.resources())
+                .with_identity_providers(identity_providers);
+
+            #[cfg(feature = "biome-credentials")]
+            {
+                rest_api_builder = rest_api_builder.add_resources(biome_credentials_resources);
+            }
+
+            #[cfg(feature = "authorization")]
+            {
+                // Allowing unused_mut because authorization_handlers must be mutable if
+                // `authorization-handler-maintenance` or `authorization-handler-rbac` are enabled
+                #[allow(unused_mut)]
+                let mut authorization_handlers = vec![
+                    #[cfg(feature = "authorization-handler-allow-keys")]
+                    create_allow_keys_authorization_handler(
+                        create_allow_keys_path(
+                            &self.config_dir,
+                            #[cfg(feature = "config-allow-keys")]
+                            &self.allow_keys_file,
+                            #[cfg(not(feature = "config-allow-keys"))]
+                            "allow_keys",
+                        )
+                        .to_str()
+                        .expect("path built from &str cannot be invalid"),
+                    )?,
+                ];
+
+                #[cfg(feature = "authorization-handler-rbac")]
+                let rbac_store = store_factory.get_role_based_authorization_store();
+
+                // The maintenance mode and role-based authorization management endpoints are not
+                // available from the Actix Web v4 REST API, but the handlers are still enforced.
+                #[cfg(feature = "authorization-handler-maintenance")]
+                {
+                    #[cfg(feature = "authorization-handler-rbac")]
+                    authorization_handlers.push(Box::new(
+                        MaintenanceModeAuthorizationHandler::new(Some(rbac_store.clone())),
+                    ));
+                    #[cfg(not(feature = "authorization-handler-rbac"))]
+                    authorization_handlers
+                        .push(Box::new(MaintenanceModeAuthorizationHandler::default()));
+                }
+
+                #[cfg(feature = "authorization-handler-rbac")]
+                {
+                    #[cfg(feature = "authorization-handler-rbac-scopes")]
+                    let rbac_handler = RoleBasedAuthorizationHandler::new(rbac_store)
+                        .with_admin_store(store_factory.get_admin_service_store());
+                    #[cfg(not(feature = "authorization-handler-rbac-scopes"))]
+                    let rbac_handler = RoleBasedAuthorizationHandler::new(rbac_store);
+                    authorization_handlers.push(Box::new(rbac_handler));
+                }
+
+                rest_api_builder =
+                    rest_api_builder.with_authorization_handlers(authorization_handlers)
+            }
+
+            #[cfg(feature = "biome-key-management")]
+            {
+                rest_api_builder = rest_api_builder.add_resources(
+                    BiomeKeyManagementRestResourceProvider::new(Arc::new(
+                        store_factory.get_biome_key_store(),
+                    ))
+                    .resources(),
+                );
+            }
+
+            #[cfg(feature = "tap-prometheus")]
+            if let Some(prometheus_handle) = self.prometheus_handle.clone() {
+                rest_api_builder = rest_api_builder
+                    .add_resources(MetricsResourceProvider::new(prometheus_handle).resources());
+            }
+
+            let rest_api = rest_api_builder.build()?;
+
+            #[cfg(all(feature = "https-bind", feature = "tls-reload"))]
+            if let Some(reloader) = rest_api.tls_reloader() {
+                tls_reloaders.push(Box::new(reloader));
+            }
+
+            rest_api.run()?
+        };
+
+        #[cfg(feature = "tls-reload")]
+        let mut tls_reload_watcher =
+            Self::start_tls_reload_watcher(tls_reloaders, tls_reload_status)?;
 
         let mut admin_shutdown_handle = Self::start_admin_service(admin_connection, admin_service)?;
 
@@ -910,6 +1301,14 @@ impl SplinterDaemon {
         if let Err(err) = rest_api_shutdown_handle.shutdown() {
             error!("Unable to cleanly shut down REST API server: {}", err);
         }
+
+        #[cfg(feature = "tls-reload")]
+        {
+            tls_reload_watcher.signal_shutdown();
+            if let Err(err) = tls_reload_watcher.wait_for_shutdown() {
+                error!("Unable to cleanly shut down TLS reload watcher: {}", err);
+            }
+        }
         circuit_dispatch_loop.signal_shutdown();
         network_dispatch_loop.signal_shutdown();
 
@@ -921,6 +1320,17 @@ impl SplinterDaemon {
             error!("Unable to cleanly shut down network dispatch loop: {}", err);
         }
 
+        #[cfg(feature = "circuit-guaranteed-delivery")]
+        {
+            delivery_manager.signal_shutdown();
+            if let Err(err) = delivery_manager.wait_for_shutdown() {
+                error!(
+                    "Unable to cleanly shut down guaranteed delivery manager: {}",
+                    err
+                );
+            }
+        }
+
         registry_shutdown.signal_shutdown();
         if let Err(err) = registry_shutdown.wait_for_shutdown() {
             error!("Unable to cleanly shut down network dispatch loop: {}", err);
@@ -982,6 +1392,30 @@ impl SplinterDaemon {
         Ok(())
     }
 
+    /// Starts reloading the given TLS contexts when their files change, or when the daemon
+    /// receives SIGHUP.
+    #[cfg(feature = "tls-reload")]
+    fn start_tls_reload_watcher(
+        tls_reloaders: Vec<Box<dyn TlsReloader>>,
+        tls_reload_status: TlsReloadStatus,
+    ) -> Result<TlsReloadWatcher, StartError> {
+        let mut builder = TlsReloadWatcherBuilder::new().with_status(tls_reload_status);
+        for reloader in tls_reloaders {
+            debug!("Watching TLS context {} for changes", reloader.name());
+            builder = builder.with_reloader(reloader);
+        }
+        let watcher = builder
+            .start()
+            .map_err(|err| StartError::InternalError(err.to_string()))?;
+
+        signal_hook::flag::register(signal_hook::consts::SIGHUP, watcher.reload_trigger())
+            .map_err(|err| {
+                StartError::InternalError(format!("Unable to set SIGHUP handler: {}", err))
+            })?;
+
+        Ok(watcher)
+    }
+
     #[cfg(feature = "https-bind")]
     fn build_rest_api_bind(&self) -> Result<splinter::rest_api::BindConfig, StartError> {
         match self.rest_api_endpoint.strip_prefix("http://") {
@@ -1103,10 +1537,34 @@ impl SplinterDaemon {
     }
 }
 
+/// Checks that the enabled features which add REST API endpoints or middleware are supported by
+/// the Actix Web v4 REST API, so that the node does not start without them.
+#[cfg(feature = "rest-api-actix-web-4")]
+fn check_actix_web_4_features() -> Result<(), StartError> {
+    let unsupported_features: Vec<&str> = vec![
+        #[cfg(feature = "peer-inspection")]
+        "peer-inspection",
+        #[cfg(feature = "peer-retry-policy")]
+        "peer-retry-policy",
+        #[cfg(feature = "rest-api-audit-log")]
+        "rest-api-audit-log",
+    ];
+
+    if unsupported_features.is_empty() {
+        Ok(())
+    } else {
+        Err(StartError::RestApiError(format!(
+            "The following features are not supported by the Actix Web v4 REST API: {}",
+            unsupported_features.join(", ")
+        )))
+    }
+}
+
 fn set_up_network_dispatcher(
     network_sender: NetworkMessageSender,
     node_id: &str,
     circuit_sender: DispatchMessageSender<CircuitMessageType>,
+    #[cfg(feature = "peer-inspection")] peer_connector: PeerManagerConnector,
 ) -> Dispatcher<NetworkMessageType> {
     let mut dispatcher = Dispatcher::<NetworkMessageType>::new(Box::new(network_sender));
 
@@ -1114,6 +1572,8 @@ fn set_up_network_dispatcher(
     dispatcher.set_handler(Box::new(network_echo_handler));
 
     let network_heartbeat_handler = NetworkHeartbeatHandler::new();
+    #[cfg(feature = "peer-inspection")]
+    let network_heartbeat_handler = network_heartbeat_handler.with_peer_connector(peer_connector);
     // do not add auth guard
     dispatcher.set_handler(Box::new(network_heartbeat_handler));
 
@@ -1123,6 +1583,7 @@ fn set_up_network_dispatcher(
     dispatcher
 }
 
+#[allow(clippy::too_many_arguments)]
 fn set_up_circuit_dispatcher(
     network_sender: NetworkMessageSender,
     node_id: &str,
@@ -1133,6 +1594,9 @@ fn set_up_circuit_dispatcher(
     #[cfg(feature = "service2")] message_handler_task_runner: impl MessageHandlerTaskRunner
         + Send
         + 'static,
+    #[cfg(feature = "circuit-guaranteed-delivery")] delivery_queue: GuaranteedDeliveryQueue,
+    #[cfg(feature = "circuit-rate-limiting")] rate_limiter: Option<CircuitRateLimiter>,
+    peer_connector: PeerManagerConnector,
 ) -> Dispatcher<CircuitMessageType> {
     #[cfg(not(feature = "service2"))]
     let mut dispatcher = Dispatcher::<CircuitMessageType>::new(Box::new(network_sender));
@@ -1144,6 +1608,13 @@ fn set_up_circuit_dispatcher(
         routing_reader.clone(),
         routing_writer.clone(),
     );
+    #[cfg(feature = "circuit-rate-limiting")]
+    let service_connect_request_handler = match &rate_limiter {
+        Some(rate_limiter) => {
+            service_connect_request_handler.with_rate_limiter(rate_limiter.clone())
+        }
+        None => service_connect_request_handler,
+    };
     dispatcher.set_handler(Box::new(service_connect_request_handler));
 
     let service_disconnect_request_handler =
@@ -1164,7 +1635,15 @@ fn set_up_circuit_dispatcher(
             Box::new(RoutingTableServiceTypeResolver::new(routing_reader.clone())),
             Box::new(message_handler_task_runner),
         ),
-    );
+    )
+    .with_peer_connector(peer_connector.clone());
+    #[cfg(feature = "circuit-guaranteed-delivery")]
+    let direct_message_handler = direct_message_handler.with_delivery_queue(delivery_queue);
+    #[cfg(feature = "circuit-rate-limiting")]
+    let direct_message_handler = match rate_limiter {
+        Some(rate_limiter) => direct_message_handler.with_rate_limiter(rate_limiter),
+        None => direct_message_handler,
+    };
     dispatcher.set_handler(Box::new(direct_message_handler));
 
     let circuit_error_handler =
@@ -1173,7 +1652,8 @@ fn set_up_circuit_dispatcher(
 
     // Circuit Admin handlers
     let admin_direct_message_handler =
-        AdminDirectMessageHandler::new(node_id.to_string(), routing_reader, public_keys);
+        AdminDirectMessageHandler::new(node_id.to_string(), routing_reader, public_keys)
+            .with_peer_connector(peer_connector);
     dispatcher.set_handler(Box::new(admin_direct_message_handler));
 
     dispatcher
@@ -1185,6 +1665,8 @@ fn create_registry(
     auto_refresh_interval: u64,
     forced_refresh_interval: u64,
     store_factory: &dyn splinter::store::StoreFactory,
+    #[cfg(feature = "registry-node-health")] health_tracker: NodeHealthTracker,
+    #[cfg(feature = "registry-remote-signed")] trusted_keys: &[cylinder::PublicKey],
 ) -> (Box<dyn RwRegistry>, RegistryShutdownHandle) {
     let mut registry_shutdown_handle = RegistryShutdownHandle::new();
 
@@ -1225,12 +1707,32 @@ fn create_registry(
                 } else {
                     None
                 };
-                match RemoteYamlRegistry::new(
+                #[cfg(feature = "registry-remote-signed")]
+                let remote_registry = if trusted_keys.is_empty() {
+                    RemoteYamlRegistry::new(
+                        registry,
+                        state_dir,
+                        auto_refresh_interval,
+                        forced_refresh_interval,
+                    )
+                } else {
+                    RemoteYamlRegistry::new_signed(
+                        registry,
+                        state_dir,
+                        auto_refresh_interval,
+                        forced_refresh_interval,
+                        Secp256k1Context::new().new_verifier(),
+                        trusted_keys.to_vec(),
+                    )
+                };
+                #[cfg(not(feature = "registry-remote-signed"))]
+                let remote_registry = RemoteYamlRegistry::new(
                     registry,
                     state_dir,
                     auto_refresh_interval,
                     forced_refresh_interval,
-                ) {
+                );
+                match remote_registry {
                     Ok(mut registry) => {
                         // this should always return some
                         if let Some(shutdown_handle) = registry.take_shutdown_handle() {
@@ -1258,7 +1760,10 @@ fn create_registry(
         })
         .collect();
 
-    let unified_registry = Box::new(UnifiedRegistry::new(local_registry, read_only_registries));
+    let unified_registry = UnifiedRegistry::new(local_registry, read_only_registries);
+    #[cfg(feature = "registry-node-health")]
+    let unified_registry = unified_registry.with_health_tracker(health_tracker);
+    let unified_registry = Box::new(unified_registry);
 
     (unified_registry, registry_shutdown_handle)
 }
@@ -13,12 +13,16 @@
 // limitations under the License.
 
 use splinter::error::InternalError;
+#[cfg(feature = "registry-node-health")]
+use splinter::registry::NodeHealthMonitor;
 use splinter::registry::RemoteYamlShutdownHandle;
 use splinter::threading::lifecycle::ShutdownHandle;
 
 #[derive(Default)]
 pub struct RegistryShutdownHandle {
     remote_yaml_shutdown_handles: Vec<RemoteYamlShutdownHandle>,
+    #[cfg(feature = "registry-node-health")]
+    node_health_monitor: Option<NodeHealthMonitor>,
 }
 
 impl RegistryShutdownHandle {
@@ -29,6 +33,11 @@ impl RegistryShutdownHandle {
     pub fn add_remote_yaml_shutdown_handle(&mut self, handle: RemoteYamlShutdownHandle) {
         self.remote_yaml_shutdown_handles.push(handle);
     }
+
+    #[cfg(feature = "registry-node-health")]
+    pub fn set_node_health_monitor(&mut self, monitor: NodeHealthMonitor) {
+        self.node_health_monitor = Some(monitor);
+    }
 }
 
 impl ShutdownHandle for RegistryShutdownHandle {
@@ -36,6 +45,12 @@ impl ShutdownHandle for RegistryShutdownHandle {
         self.remote_yaml_shutdown_handles
             .iter_mut()
             .for_each(|handle| handle.signal_shutdown());
+        #[cfg(feature = "registry-node-health")]
+        {
+            if let Some(monitor) = self.node_health_monitor.as_mut() {
+                monitor.signal_shutdown();
+            }
+        }
     }
 
     fn wait_for_shutdown(self) -> Result<(), InternalError> {
@@ -45,6 +60,14 @@ impl ShutdownHandle for RegistryShutdownHandle {
                 errors.push(err);
             }
         }
+        #[cfg(feature = "registry-node-health")]
+        {
+            if let Some(monitor) = self.node_health_monitor {
+                if let Err(err) = monitor.wait_for_shutdown() {
+                    errors.push(err);
+                }
+            }
+        }
 
         match errors.len() {
             0 => Ok(()),
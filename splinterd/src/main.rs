@@ -24,14 +24,22 @@ mod logging;
 pub mod node_id;
 mod transport;
 
-use cylinder::{load_key_from_path, secp256k1::Secp256k1Context, Context, Signer};
+#[cfg(not(feature = "encrypted-keys"))]
+use cylinder::load_key_from_path;
+#[cfg(feature = "encrypted-keys")]
+use cylinder::PrivateKey;
+use cylinder::{secp256k1::Secp256k1Context, Context, Signer};
 use log4rs::Handle;
 use logging::{configure_logging, default_log_settings};
 
 use splinter::error::InternalError;
+#[cfg(feature = "encrypted-keys")]
+use splinter::keys::{encrypted, EncryptedKeyError};
 use splinter::peer::PeerAuthorizationToken;
 #[cfg(feature = "tap")]
 use splinter::tap::influx::InfluxRecorder;
+#[cfg(feature = "tap-prometheus")]
+use splinter::tap::prometheus::{PrometheusHandle, PrometheusRecorder};
 
 use crate::config::{
     ClapPartialConfigBuilder, Config, ConfigBuilder, ConfigError, DefaultPartialConfigBuilder,
@@ -98,10 +106,29 @@ fn find_node_id(config: &Config) -> Result<Option<String>, UserError> {
 
 type ChallengeAuthorizationArgs = (Vec<Box<dyn Signer>>, PeerAuthorizationToken);
 
+// load a private key file, decrypting it with the passphrase if it is encrypted
+#[cfg(feature = "encrypted-keys")]
+fn load_private_key(path: &Path, key_passphrase: Option<&[u8]>) -> Result<PrivateKey, UserError> {
+    encrypted::load_private_key(path, key_passphrase).map_err(|err| match err {
+        EncryptedKeyError::PassphraseRequired => UserError::InvalidArgument(format!(
+            "key file {} is encrypted, but no passphrase was provided; set {} or \
+             key_passphrase_file",
+            path.display(),
+            encrypted::KEY_PASSPHRASE_ENV
+        )),
+        err => UserError::InvalidArgument(format!(
+            "unable to load key file {}: {}",
+            path.display(),
+            err
+        )),
+    })
+}
+
 // load all signing keys from the configured splinterd key file
 fn load_signer_keys(
     config_dir: &str,
     peering_key: &str,
+    #[cfg(feature = "encrypted-keys")] key_passphrase: Option<&[u8]>,
 ) -> Result<ChallengeAuthorizationArgs, UserError> {
     let splinterd_key_path = Path::new(config_dir).join("keys");
     let paths = fs::read_dir(&splinterd_key_path).map_err(|err| UserError::IoError {
@@ -123,6 +150,9 @@ fn load_signer_keys(
             .path();
 
         if path.extension() == Some(OsStr::new("priv")) {
+            #[cfg(feature = "encrypted-keys")]
+            let private_key = load_private_key(&path, key_passphrase)?;
+            #[cfg(not(feature = "encrypted-keys"))]
             let private_key = load_key_from_path(&path).map_err(|err| {
                 UserError::InternalError(InternalError::from_source(Box::new(err)))
             })?;
@@ -487,6 +517,93 @@ fn main() {
                 .takes_value(true),
         );
 
+    #[cfg(feature = "tap-prometheus")]
+    let app = app
+        .arg(
+            Arg::with_name("enable_prometheus_metrics")
+                .long("enable-prometheus-metrics")
+                .long_help("Enable collecting metrics for the Prometheus /metrics endpoint"),
+        )
+        .arg(
+            Arg::with_name("prometheus_histogram_buckets")
+                .long("prometheus-histogram-buckets")
+                .value_name("buckets")
+                .long_help(
+                    "Comma-separated upper bounds of the buckets used for histograms exposed \
+                    to Prometheus",
+                )
+                .takes_value(true)
+                .multiple(true)
+                .use_delimiter(true),
+        );
+
+    #[cfg(feature = "circuit-rate-limiting")]
+    let app = app
+        .arg(
+            Arg::with_name("circuit_rate_limit")
+                .long("circuit-rate-limit")
+                .value_name("messages")
+                .long_help("Maximum number of messages per second that may be sent on a circuit")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("service_rate_limit")
+                .long("service-rate-limit")
+                .value_name("messages")
+                .long_help(
+                    "Maximum number of messages per second that may be sent by a service on a \
+                    circuit",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("circuit_rate_limit_burst")
+                .long("circuit-rate-limit-burst")
+                .value_name("messages")
+                .long_help(
+                    "Maximum number of messages that may be sent on a circuit at once (defaults \
+                    to the circuit rate limit)",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("service_rate_limit_burst")
+                .long("service-rate-limit-burst")
+                .value_name("messages")
+                .long_help(
+                    "Maximum number of messages that may be sent by a service on a circuit at \
+                    once (defaults to the service rate limit)",
+                )
+                .takes_value(true),
+        );
+
+    #[cfg(feature = "circuit-guaranteed-delivery")]
+    let app = app.arg(
+        Arg::with_name("guaranteed_delivery_message_ttl")
+            .long("guaranteed-delivery-message-ttl")
+            .value_name("seconds")
+            .long_help(
+                "How long a message on a guaranteed-delivery circuit is kept for a disconnected \
+                node before it is discarded",
+            )
+            .takes_value(true),
+    );
+
+    #[cfg(feature = "registry-remote-signed")]
+    let app = app.arg(
+        Arg::with_name("registry_trusted_keys")
+            .long("registry-trusted-keys")
+            .value_name("public-key")
+            .long_help(
+                "Hex-encoded public keys that are trusted to sign remote registry files; if any \
+                    keys are provided, remote registry files must have a valid signature",
+            )
+            .takes_value(true)
+            .multiple(true)
+            .use_delimiter(true)
+            .alias("registry-trusted-key"),
+    );
+
     #[cfg(feature = "service-timer-interval")]
     let app = app.arg(
         Arg::with_name("service_timer_interval")
@@ -526,6 +643,30 @@ fn main() {
             .long_help("Disable autocleanup of pruned scabbard merkle state."),
     );
 
+    #[cfg(feature = "encrypted-keys")]
+    let app = app.arg(
+        Arg::with_name("key_passphrase_file")
+            .long("key-passphrase-file")
+            .value_name("file")
+            .long_help(
+                "File containing the passphrase used to decrypt encrypted key files; if not \
+                set, the passphrase is read from SPLINTER_KEY_PASSPHRASE",
+            )
+            .takes_value(true),
+    );
+
+    #[cfg(feature = "scabbard-state-pruning")]
+    let app = app.arg(
+        Arg::with_name("scabbard_retained_state_roots")
+            .long("scabbard-retained-state-roots")
+            .value_name("count")
+            .long_help(
+                "Number of committed state roots each scabbard service retains; older roots \
+                are pruned in the background. Defaults to 1.",
+            )
+            .takes_value(true),
+    );
+
     let matches = app.get_matches();
 
     let log_handle = log4rs::init_config(default_log_settings());
@@ -574,6 +715,28 @@ fn setup_metrics_recorder(config: &Config) -> Result<(), UserError> {
     Ok(())
 }
 
+#[cfg(feature = "tap-prometheus")]
+fn setup_prometheus_recorder(config: &Config) -> Result<Option<PrometheusHandle>, UserError> {
+    if !config.enable_prometheus_metrics() {
+        return Ok(None);
+    }
+
+    // Only one recorder can be set for the metrics macros
+    if config.influx_db().is_some()
+        || config.influx_url().is_some()
+        || config.influx_username().is_some()
+        || config.influx_password().is_some()
+    {
+        return Err(UserError::InvalidArgument(
+            "Prometheus metrics cannot be enabled when InfluxDB metrics are configured".into(),
+        ));
+    }
+
+    PrometheusRecorder::init(config.prometheus_histogram_buckets().map(ToOwned::to_owned))
+        .map(Some)
+        .map_err(UserError::InternalError)
+}
+
 fn get_config_file(matches: &'_ ArgMatches) -> Result<String, UserError> {
     if let Some(value) = matches.value_of("config") {
         return Ok(value.to_string());
@@ -643,10 +806,18 @@ fn start_daemon(matches: ArgMatches, log_handle: Handle) -> Result<(), UserError
     }
 
     // set up metric recorder as soon as possible
+    #[cfg(feature = "tap-prometheus")]
+    let prometheus_handle = setup_prometheus_recorder(&config)?;
     #[cfg(feature = "tap")]
     setup_metrics_recorder(&config)?;
 
-    let transport = build_transport(&config)?;
+    #[cfg(feature = "tls-reload")]
+    let mut tls_reloaders = vec![];
+    let transport = build_transport(
+        &config,
+        #[cfg(feature = "tls-reload")]
+        &mut tls_reloaders,
+    )?;
 
     let rest_api_endpoint = config.rest_api_endpoint();
 
@@ -733,6 +904,73 @@ fn start_daemon(matches: ArgMatches, log_handle: Handle) -> Result<(), UserError
         }
     }
 
+    #[cfg(feature = "tap-prometheus")]
+    {
+        daemon_builder = daemon_builder.with_prometheus_handle(prometheus_handle);
+    }
+
+    #[cfg(feature = "tls-reload")]
+    {
+        daemon_builder = daemon_builder.with_tls_reloaders(tls_reloaders);
+    }
+
+    #[cfg(feature = "circuit-rate-limiting")]
+    {
+        daemon_builder = daemon_builder
+            .with_circuit_rate_limit(config.circuit_rate_limit())
+            .with_service_rate_limit(config.service_rate_limit())
+            .with_circuit_rate_limit_burst(config.circuit_rate_limit_burst())
+            .with_service_rate_limit_burst(config.service_rate_limit_burst())
+            .with_circuit_rate_limits(config.circuit_rate_limits().cloned().unwrap_or_default());
+    }
+
+    #[cfg(feature = "peer-retry-policy")]
+    {
+        daemon_builder = daemon_builder
+            .with_peer_retry_policies(config.peer_retry_policies().cloned().unwrap_or_default())
+            .with_endpoint_retry_policies(
+                config
+                    .endpoint_retry_policies()
+                    .cloned()
+                    .unwrap_or_default(),
+            );
+    }
+
+    #[cfg(feature = "circuit-guaranteed-delivery")]
+    {
+        daemon_builder = daemon_builder
+            .with_guaranteed_delivery_message_ttl(config.guaranteed_delivery_message_ttl());
+    }
+
+    #[cfg(feature = "scabbard-state-pruning")]
+    {
+        daemon_builder = daemon_builder
+            .with_scabbard_retained_state_roots(config.scabbard_retained_state_roots());
+    }
+
+    #[cfg(feature = "registry-remote-signed")]
+    {
+        daemon_builder = daemon_builder.with_registry_trusted_keys(
+            config
+                .registry_trusted_keys()
+                .map(|keys| keys.to_vec())
+                .unwrap_or_default(),
+        );
+    }
+
+    #[cfg(feature = "encrypted-keys")]
+    let (signers, peering_token) = {
+        let key_passphrase =
+            encrypted::load_key_passphrase(config.key_passphrase_file().map(Path::new)).map_err(
+                |err| UserError::io_err_with_source("Unable to load key passphrase", Box::new(err)),
+            )?;
+        load_signer_keys(
+            config.config_dir(),
+            config.peering_key(),
+            key_passphrase.as_deref(),
+        )?
+    };
+    #[cfg(not(feature = "encrypted-keys"))]
     let (signers, peering_token) = load_signer_keys(config.config_dir(), config.peering_key())?;
     daemon_builder = daemon_builder
         .with_signers(signers)
@@ -38,6 +38,8 @@ use splinter::rest_api::auth::{
 };
 use splinter::rest_api::BindConfig;
 use splinter::store::{memory::MemoryStoreFactory, StoreFactory};
+#[cfg(feature = "transport-fault")]
+use splinter::transport::fault::FaultController;
 
 use super::{RunnableNode, RunnableNodeRestApiVariant, ScabbardConfig};
 
@@ -132,6 +134,16 @@ impl NodeBuilder {
         self
     }
 
+    /// Specifies a controller for faults to inject into the node's outbound network connections.
+    /// The node's ID is used as its name in the controller.
+    #[cfg(feature = "transport-fault")]
+    pub fn with_fault_controller(mut self, fault_controller: FaultController) -> Self {
+        self.network_subsystem_builder = self
+            .network_subsystem_builder
+            .with_fault_controller(fault_controller);
+        self
+    }
+
     /// Configure whether or not strict reference counts will be used in the peer manager. Defaults
     /// to false.
     pub fn with_strict_ref_counts(mut self, strict_ref_counts: bool) -> Self {
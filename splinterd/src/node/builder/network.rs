@@ -18,6 +18,8 @@ use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use splinter::error::InternalError;
+#[cfg(feature = "transport-fault")]
+use splinter::transport::fault::{FaultController, FaultTransport};
 use splinter::transport::multi::MultiTransport;
 use splinter::transport::socket::TcpTransport;
 
@@ -33,6 +35,8 @@ pub struct NetworkSubsystemBuilder {
     network_endpoints: Option<Vec<String>>,
     signing_context: Option<Arc<Mutex<Box<dyn cylinder::VerifierFactory>>>>,
     signers: Option<Vec<Box<dyn cylinder::Signer>>>,
+    #[cfg(feature = "transport-fault")]
+    fault_controller: Option<FaultController>,
 }
 
 impl NetworkSubsystemBuilder {
@@ -80,6 +84,13 @@ impl NetworkSubsystemBuilder {
         self
     }
 
+    /// Specifies a controller for faults to inject into the node's outbound network connections
+    #[cfg(feature = "transport-fault")]
+    pub fn with_fault_controller(mut self, fault_controller: FaultController) -> Self {
+        self.fault_controller = Some(fault_controller);
+        self
+    }
+
     pub fn build(mut self) -> Result<RunnableNetworkSubsystem, InternalError> {
         let node_id = self.node_id.take().ok_or_else(|| {
             InternalError::with_message(
@@ -103,7 +114,17 @@ impl NetworkSubsystemBuilder {
             .take()
             .unwrap_or(DEFAULT_HEARTBEAT_INTERVAL);
 
+        #[cfg(not(feature = "transport-fault"))]
         let transport = MultiTransport::new(vec![Box::new(TcpTransport::default())]);
+        #[cfg(feature = "transport-fault")]
+        let transport = match self.fault_controller.take() {
+            Some(fault_controller) => MultiTransport::new(vec![Box::new(FaultTransport::new(
+                &node_id,
+                Box::new(TcpTransport::default()),
+                fault_controller,
+            ))]),
+            None => MultiTransport::new(vec![Box::new(TcpTransport::default())]),
+        };
 
         Ok(RunnableNetworkSubsystem {
             node_id,
@@ -34,6 +34,8 @@ use splinter::network::dispatch::{
 };
 use splinter::network::handlers::{NetworkEchoHandler, NetworkHeartbeatHandler};
 use splinter::peer::interconnect::NetworkMessageSender;
+#[cfg(feature = "peer-inspection")]
+use splinter::peer::PeerManagerConnector;
 use splinter::peer::{interconnect::PeerInterconnectBuilder, PeerManager};
 use splinter::protos::circuit::CircuitMessageType;
 use splinter::protos::network::NetworkMessageType;
@@ -156,8 +158,13 @@ impl RunnableNetworkSubsystem {
         let circuit_dispatch_sender = circuit_dispatch_loop.new_dispatcher_sender();
 
         // Set up the Network dispatcher
-        let network_dispatcher =
-            Self::set_up_network_dispatcher(network_sender, &node_id, circuit_dispatch_sender);
+        let network_dispatcher = Self::set_up_network_dispatcher(
+            network_sender,
+            &node_id,
+            circuit_dispatch_sender,
+            #[cfg(feature = "peer-inspection")]
+            peer_manager.connector(),
+        );
 
         let network_dispatch_loop = DispatchLoopBuilder::new()
             .with_dispatcher(network_dispatcher)
@@ -320,6 +327,7 @@ impl RunnableNetworkSubsystem {
         network_sender: NetworkMessageSender,
         node_id: &str,
         circuit_sender: DispatchMessageSender<CircuitMessageType>,
+        #[cfg(feature = "peer-inspection")] peer_connector: PeerManagerConnector,
     ) -> Dispatcher<NetworkMessageType> {
         let mut dispatcher = Dispatcher::<NetworkMessageType>::new(Box::new(network_sender));
 
@@ -327,6 +335,9 @@ impl RunnableNetworkSubsystem {
         dispatcher.set_handler(Box::new(network_echo_handler));
 
         let network_heartbeat_handler = NetworkHeartbeatHandler::new();
+        #[cfg(feature = "peer-inspection")]
+        let network_heartbeat_handler =
+            network_heartbeat_handler.with_peer_connector(peer_connector);
         // do not add auth guard
         dispatcher.set_handler(Box::new(network_heartbeat_handler));
 
@@ -18,6 +18,8 @@ use std::path::Path;
 use splinter::transport::multi::MultiTransport;
 use splinter::transport::socket::TcpTransport;
 use splinter::transport::socket::TlsTransport;
+#[cfg(feature = "tls-reload")]
+use splinter::transport::tls::reload::TlsReloader;
 use splinter::transport::tls::{TlsConfig, TlsConfigBuilder};
 #[cfg(feature = "ws-transport")]
 use splinter::transport::ws::WsTransport;
@@ -28,7 +30,14 @@ use crate::error::GetTransportError;
 
 type SendableTransport = Box<dyn Transport + Send>;
 
-pub fn build_transport(config: &Config) -> Result<MultiTransport, GetTransportError> {
+/// Builds the transport for the node's network connections.
+///
+/// If the `tls-reload` feature is enabled, the reloader of the TLS transport, if any, is added to
+/// `tls_reloaders`.
+pub fn build_transport(
+    config: &Config,
+    #[cfg(feature = "tls-reload")] tls_reloaders: &mut Vec<Box<dyn TlsReloader>>,
+) -> Result<MultiTransport, GetTransportError> {
     let mut transports: Vec<SendableTransport> = vec![
         // add tcp transport
         // this will be default for endpoints without a prefix
@@ -43,13 +52,18 @@ pub fn build_transport(config: &Config) -> Result<MultiTransport, GetTransportEr
         validate_tls_config(&tls_config)?;
         print_tls_config(&tls_config)?;
 
-        transports.push(Box::new(TlsTransport::new(
+        let tls_transport = TlsTransport::new(
             tls_config.ca_certs_file().to_owned(),
             tls_config.client_private_key_file().to_string(),
             tls_config.client_cert_file().to_string(),
             tls_config.server_private_key_file().to_string(),
             tls_config.server_cert_file().to_string(),
-        )?));
+        )?;
+
+        #[cfg(feature = "tls-reload")]
+        tls_reloaders.push(Box::new(tls_transport.reloader()));
+
+        transports.push(Box::new(tls_transport));
 
         #[cfg(feature = "ws-transport")]
         transports.push(Box::new(WsTransport::new(Some(&tls_config)).map_err(
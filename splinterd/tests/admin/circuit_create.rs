@@ -21,6 +21,8 @@ use std::time::Duration;
 use splinter::admin::client::event::{EventType, PublicKey};
 use splinter::admin::messages::AuthorizationType;
 use splinter::peer::{PeerAuthorizationToken, PeerManagerNotification, PeerTokenPair};
+#[cfg(feature = "transport-fault")]
+use splinter::transport::fault::{FaultAction, FaultController, FaultPolicyBuilder, FaultScript};
 use splinterd::node::{Node, RestApiVariant};
 
 use crate::admin::circuit_commit::{commit_2_party_circuit, commit_3_party_circuit};
@@ -56,6 +58,152 @@ pub fn test_2_party_circuit_creation() {
     shutdown!(network).expect("Unable to shutdown network");
 }
 
+/// Test that a 2-party circuit may be created on a 2-node network whose connections have latency
+/// and jitter.
+///
+/// 1. Start a 2-node network with a fault controller that delays every message between the nodes
+/// 2. Use commit_2_party_circuit to verify that a circuit can be created between the two nodes
+/// 3. Shutdown the network
+#[cfg(feature = "transport-fault")]
+#[test]
+pub fn test_2_party_circuit_creation_with_latency() {
+    let fault_controller = FaultController::new(0);
+    fault_controller.set_default_policy(
+        FaultPolicyBuilder::new()
+            .with_latency(Duration::from_millis(50))
+            .with_jitter(Duration::from_millis(25))
+            .build()
+            .expect("Unable to build fault policy"),
+    );
+
+    // Start a 2-node network
+    let mut network = Network::new()
+        .with_default_rest_api_variant(RestApiVariant::ActixWeb1)
+        .with_fault_controller(fault_controller)
+        .add_nodes_with_defaults(2)
+        .expect("Unable to start 2-node ActixWeb1 network");
+    // Get the first node in the network
+    let node_a = network.node(0).expect("Unable to get first node");
+    // Get the second node in the network
+    let node_b = network.node(1).expect("Unable to get second node");
+
+    let circuit_id = "ABCDE-01234";
+
+    commit_2_party_circuit(circuit_id, node_a, node_b, AuthorizationType::Trust);
+
+    shutdown!(network).expect("Unable to shutdown network");
+}
+
+/// Test that a 2-party circuit may be created on a 2-node network whose nodes are partitioned
+/// from each other when the proposal is submitted, once the partition is healed.
+///
+/// 1. Start a 2-node network with a fault controller that partitions the two nodes, so that the
+///    first node cannot peer with the second
+/// 2. Start a fault script that heals the partition after a delay
+/// 3. Use commit_2_party_circuit to verify that the circuit is created once the nodes are able to
+///    peer
+/// 4. Verify the partition was healed by the script
+/// 5. Shutdown the network
+#[cfg(feature = "transport-fault")]
+#[test]
+pub fn test_2_party_circuit_creation_partition_healed() {
+    let fault_controller = FaultController::new(0);
+    // The nodes are named by their index in the network
+    fault_controller.partition("n0", "n1");
+
+    // Start a 2-node network
+    let mut network = Network::new()
+        .with_default_rest_api_variant(RestApiVariant::ActixWeb1)
+        .with_fault_controller(fault_controller.clone())
+        .add_nodes_with_defaults(2)
+        .expect("Unable to start 2-node ActixWeb1 network");
+    // Get the first node in the network
+    let node_a = network.node(0).expect("Unable to get first node");
+    // Get the second node in the network
+    let node_b = network.node(1).expect("Unable to get second node");
+
+    let heal_handle = FaultScript::new()
+        .then(
+            Duration::from_secs(5),
+            FaultAction::Heal("n0".into(), "n1".into()),
+        )
+        .run(fault_controller.clone())
+        .expect("Unable to start fault script");
+
+    let circuit_id = "ABCDE-01234";
+
+    commit_2_party_circuit(circuit_id, node_a, node_b, AuthorizationType::Trust);
+
+    heal_handle.join().expect("Fault script panicked");
+    assert!(!fault_controller.is_partitioned("n0", "n1"));
+
+    shutdown!(network).expect("Unable to shutdown network");
+}
+
+/// Test that the members of a 2-party circuit reconnect after their connection is closed, and
+/// that another circuit may then be created between them.
+///
+/// 1. Start a 2-node network with a fault controller and a short heartbeat interval, so that
+///    closed connections are noticed quickly
+/// 2. Use commit_2_party_circuit to create a circuit between the two nodes
+/// 3. Subscribe to the peer notifications of the first node
+/// 4. Partition the nodes and close their connection, and wait for the first node to be notified
+///    that the second node is disconnected
+/// 5. Heal the partition, and wait for the first node to be notified that the second node is
+///    connected again
+/// 6. Use commit_2_party_circuit to verify that a second circuit can be created between the two
+///    nodes
+/// 7. Shutdown the network
+#[cfg(feature = "transport-fault")]
+#[test]
+pub fn test_2_party_circuit_peer_reconnect() {
+    let fault_controller = FaultController::new(0);
+
+    // Start a 2-node network
+    let mut network = Network::new()
+        .with_default_rest_api_variant(RestApiVariant::ActixWeb1)
+        .with_fault_controller(fault_controller.clone())
+        .with_heartbeat_interval(Duration::from_secs(1))
+        .add_nodes_with_defaults(2)
+        .expect("Unable to start 2-node ActixWeb1 network");
+    // Get the first node in the network
+    let node_a = network.node(0).expect("Unable to get first node");
+    // Get the second node in the network
+    let node_b = network.node(1).expect("Unable to get second node");
+
+    commit_2_party_circuit("ABCDE-01234", node_a, node_b, AuthorizationType::Trust);
+
+    let (tx, notification_rx): (mpsc::Sender<TestEnum>, mpsc::Receiver<TestEnum>) = mpsc::channel();
+    node_a
+        .peer_connector()
+        .subscribe_sender(tx)
+        .expect("Unable to get subscriber");
+
+    let node_b_peer = PeerTokenPair::new(
+        PeerAuthorizationToken::from_peer_id(node_b.node_id()),
+        PeerAuthorizationToken::from_peer_id(node_a.node_id()),
+    );
+
+    fault_controller.partition(node_a.node_id(), node_b.node_id());
+    fault_controller.disconnect(node_a.node_id(), node_b.node_id());
+    wait_for_peer_notification(
+        &notification_rx,
+        PeerManagerNotification::Disconnected {
+            peer: node_b_peer.clone(),
+        },
+    );
+
+    fault_controller.heal(node_a.node_id(), node_b.node_id());
+    wait_for_peer_notification(
+        &notification_rx,
+        PeerManagerNotification::Connected { peer: node_b_peer },
+    );
+
+    commit_2_party_circuit("FGHIJ-56789", node_a, node_b, AuthorizationType::Trust);
+
+    shutdown!(network).expect("Unable to shutdown network");
+}
+
 /// Test that a 2-party circuit may be created on a 2-node network using challenge authorization.
 ///
 /// 1. Start a two node network and get both nodes.
@@ -2097,6 +2245,28 @@ fn admin_pubkey(node: &Node) -> PublicKey {
     )
 }
 
+/// Waits up to 60 seconds for the given peer notification, skipping any other notifications
+#[cfg(feature = "transport-fault")]
+fn wait_for_peer_notification(
+    notification_rx: &mpsc::Receiver<TestEnum>,
+    expected: PeerManagerNotification,
+) {
+    let expected = TestEnum::Notification(expected);
+    let timeout = Duration::from_secs(60);
+    let start = std::time::Instant::now();
+    loop {
+        let remaining = timeout
+            .checked_sub(start.elapsed())
+            .unwrap_or_else(|| panic!("Timed out waiting for {:?}", expected));
+        let notification = notification_rx
+            .recv_timeout(remaining)
+            .unwrap_or_else(|_| panic!("Timed out waiting for {:?}", expected));
+        if notification == expected {
+            return;
+        }
+    }
+}
+
 #[derive(PartialEq, Debug)]
 enum TestEnum {
     Notification(PeerManagerNotification),
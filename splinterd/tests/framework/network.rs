@@ -16,6 +16,8 @@
 
 use std::collections::HashMap;
 use std::fs::File;
+#[cfg(feature = "transport-fault")]
+use std::time::Duration;
 
 use cylinder::{secp256k1::Secp256k1Context, Context, Signer};
 use diesel::Connection;
@@ -26,6 +28,8 @@ use splinter::store::sqlite::{
     create_sqlite_connection_pool_with_write_exclusivity, SqliteStoreFactory,
 };
 use splinter::threading::lifecycle::ShutdownHandle;
+#[cfg(feature = "transport-fault")]
+use splinter::transport::fault::FaultController;
 use splinterd::node::{
     Node, NodeBuilder, PermissionConfig, RestApiVariant, RunnableNode, ScabbardConfigBuilder,
 };
@@ -43,6 +47,10 @@ pub struct Network {
     permission_config: Option<Vec<PermissionConfig>>,
     admin_signer: Option<Box<dyn Signer>>,
     auth: Option<String>,
+    #[cfg(feature = "transport-fault")]
+    heartbeat_interval: Option<Duration>,
+    #[cfg(feature = "transport-fault")]
+    fault_controller: Option<FaultController>,
 }
 
 pub enum NetworkNode {
@@ -62,6 +70,10 @@ impl Network {
             permission_config: None,
             admin_signer: None,
             auth: None,
+            #[cfg(feature = "transport-fault")]
+            heartbeat_interval: None,
+            #[cfg(feature = "transport-fault")]
+            fault_controller: None,
         }
     }
 
@@ -70,6 +82,17 @@ impl Network {
         self
     }
 
+    /// Injects the faults of the given controller into the network connections between nodes.
+    ///
+    /// Nodes added after this call are named "n0", "n1", etc. by their index in the network, so
+    /// that partitions and link policies can be scripted against them and the seeded faults are
+    /// reproducible.
+    #[cfg(feature = "transport-fault")]
+    pub fn with_fault_controller(mut self, fault_controller: FaultController) -> Self {
+        self.fault_controller = Some(fault_controller);
+        self
+    }
+
     pub fn add_nodes_with_defaults(mut self, count: i32) -> Result<Network, InternalError> {
         let mut registry_info = vec![];
         let context = Secp256k1Context::new();
@@ -124,9 +147,22 @@ impl Network {
             if self.cylinder_auth {
                 builder = builder.with_cylinder_auth(Box::new(Secp256k1Context::new()));
             }
+            #[cfg(feature = "transport-fault")]
+            if let Some(fault_controller) = &self.fault_controller {
+                builder = builder
+                    .with_node_id(format!("n{}", self.nodes.len()))
+                    .with_fault_controller(fault_controller.clone());
+            }
+            #[cfg(feature = "transport-fault")]
+            if let Some(heartbeat_interval) = self.heartbeat_interval {
+                builder = builder.with_heartbeat_interval(heartbeat_interval);
+            }
 
             let node = builder.build()?.run()?;
 
+            #[cfg(feature = "transport-fault")]
+            self.register_fault_endpoints(&node);
+
             registry_info.push((
                 node.node_id().to_string(),
                 public_key,
@@ -186,6 +222,14 @@ impl Network {
         self
     }
 
+    /// Sets the heartbeat interval of nodes added after this call, which is how quickly they
+    /// notice a peer connection closed by the fault controller.
+    #[cfg(feature = "transport-fault")]
+    pub fn with_heartbeat_interval(mut self, heartbeat_interval: Duration) -> Self {
+        self.heartbeat_interval = Some(heartbeat_interval);
+        self
+    }
+
     pub fn node(&self, n: usize) -> Result<&Node, InvalidArgumentError> {
         match self.nodes.get(n) {
             Some(network_node) => match network_node {
@@ -220,6 +264,9 @@ impl Network {
             }
         };
 
+        #[cfg(feature = "transport-fault")]
+        self.register_fault_endpoints(&node);
+
         let registry_writer = node.registry_writer();
 
         // Update the registry
@@ -245,6 +292,16 @@ impl Network {
         Ok(self)
     }
 
+    /// Names the node's network endpoints in the fault controller, if there is one
+    #[cfg(feature = "transport-fault")]
+    fn register_fault_endpoints(&self, node: &Node) {
+        if let Some(fault_controller) = &self.fault_controller {
+            for endpoint in node.network_endpoints() {
+                fault_controller.add_endpoint(node.node_id(), endpoint);
+            }
+        }
+    }
+
     pub fn stop(mut self, index: usize) -> Result<Network, InternalError> {
         let runnable_node = match self.nodes.remove(index) {
             NetworkNode::Node(node) => node.stop()?,